@@ -96,7 +96,11 @@ fn bench_instantiate_v1(c: &mut Criterion) {
     c.bench_function("instantiate/v1", |b| {
         b.iter(|| {
             let mut store = v1::Store::new(module.engine(), ());
-            let _instance = linker.instantiate(&mut store, &module).unwrap();
+            let _instance = linker
+                .instantiate(&mut store, &module)
+                .unwrap()
+                .ensure_no_start(&mut store)
+                .unwrap();
         })
     });
 }
@@ -105,9 +109,9 @@ fn bench_execute_tiny_keccak_v0(c: &mut Criterion) {
     let instance = load_instance_from_file_v0(WASM_KERNEL);
 
     let test_data_ptr = assert_matches!(
-		instance.invoke_export("prepare_tiny_keccak", &[], &mut v0::NopExternals),
-		Ok(Some(v @ Value::I32(_))) => v
-	);
+        instance.invoke_export("prepare_tiny_keccak", &[], &mut v0::NopExternals),
+        Ok(Some(v @ Value::I32(_))) => v
+    );
 
     c.bench_function("execute/tiny_keccak/v0", |b| {
         b.iter(|| {
@@ -128,11 +132,11 @@ fn bench_execute_tiny_keccak_v1(c: &mut Criterion) {
         .get_export(&store, "bench_tiny_keccak")
         .and_then(v1::Extern::into_func)
         .unwrap();
-    let mut test_data_ptr = Value::I32(0);
+    let mut test_data_ptr = v1::Value::I32(0);
     prepare
         .call(&mut store, &[], slice::from_mut(&mut test_data_ptr))
         .unwrap();
-    assert_matches!(test_data_ptr, Value::I32(_));
+    assert_matches!(test_data_ptr, v1::Value::I32(_));
 
     c.bench_function("execute/tiny_keccak/v1", |b| {
         b.iter(|| {
@@ -150,10 +154,10 @@ fn bench_execute_rev_comp_v0(c: &mut Criterion) {
     let test_data_ptr: Value = {
         let input_size = Value::I32(REVCOMP_INPUT.len() as i32);
         assert_matches!(
-			instance.invoke_export("prepare_rev_complement", &[input_size], &mut v0::NopExternals),
-			Ok(Some(v @ Value::I32(_))) => v,
-			"",
-		)
+            instance.invoke_export("prepare_rev_complement", &[input_size], &mut v0::NopExternals),
+            Ok(Some(v @ Value::I32(_))) => v,
+            "",
+        )
     };
 
     // Get the pointer to the input buffer.
@@ -207,8 +211,8 @@ fn bench_execute_rev_comp_v1(c: &mut Criterion) {
     let (mut store, instance) = load_instance_from_file_v1(WASM_KERNEL);
 
     // Allocate buffers for the input and output.
-    let mut result = Value::I32(0);
-    let input_size = Value::I32(REVCOMP_INPUT.len() as i32);
+    let mut result = v1::Value::I32(0);
+    let input_size = v1::Value::I32(REVCOMP_INPUT.len() as i32);
     let prepare_rev_complement = instance
         .get_export(&store, "prepare_rev_complement")
         .and_then(v1::Extern::into_func)
@@ -217,7 +221,7 @@ fn bench_execute_rev_comp_v1(c: &mut Criterion) {
         .call(&mut store, &[input_size], slice::from_mut(&mut result))
         .unwrap();
     let test_data_ptr = match result {
-        value @ Value::I32(_) => value,
+        value @ v1::Value::I32(_) => value,
         _ => panic!("unexpected non-I32 result found for prepare_rev_complement"),
     };
 
@@ -230,7 +234,7 @@ fn bench_execute_rev_comp_v1(c: &mut Criterion) {
         .call(&mut store, &[test_data_ptr], slice::from_mut(&mut result))
         .unwrap();
     let input_data_mem_offset = match result {
-        Value::I32(value) => value,
+        v1::Value::I32(value) => value,
         _ => panic!("unexpected non-I32 result found for prepare_rev_complement"),
     };
 
@@ -268,7 +272,7 @@ fn bench_execute_rev_comp_v1(c: &mut Criterion) {
             .call(&mut store, &[test_data_ptr], slice::from_mut(&mut result))
             .unwrap();
         let output_data_mem_offset = match result {
-            Value::I32(value) => value,
+            v1::Value::I32(value) => value,
             _ => panic!("unexpected non-I32 result found for prepare_rev_complement"),
         };
 
@@ -287,10 +291,10 @@ fn bench_execute_regex_redux_v0(c: &mut Criterion) {
     let test_data_ptr: Value = {
         let input_size = Value::I32(REVCOMP_INPUT.len() as i32);
         assert_matches!(
-			instance.invoke_export("prepare_regex_redux", &[input_size], &mut v0::NopExternals),
-			Ok(Some(v @ Value::I32(_))) => v,
-			"",
-		)
+            instance.invoke_export("prepare_regex_redux", &[input_size], &mut v0::NopExternals),
+            Ok(Some(v @ Value::I32(_))) => v,
+            "",
+        )
     };
 
     // Get the pointer to the input buffer.
@@ -324,8 +328,8 @@ fn bench_execute_regex_redux_v1(c: &mut Criterion) {
     let (mut store, instance) = load_instance_from_file_v1(WASM_KERNEL);
 
     // Allocate buffers for the input and output.
-    let mut result = Value::I32(0);
-    let input_size = Value::I32(REVCOMP_INPUT.len() as i32);
+    let mut result = v1::Value::I32(0);
+    let input_size = v1::Value::I32(REVCOMP_INPUT.len() as i32);
     let prepare_regex_redux = instance
         .get_export(&store, "prepare_regex_redux")
         .and_then(v1::Extern::into_func)
@@ -334,7 +338,7 @@ fn bench_execute_regex_redux_v1(c: &mut Criterion) {
         .call(&mut store, &[input_size], slice::from_mut(&mut result))
         .unwrap();
     let test_data_ptr = match result {
-        value @ Value::I32(_) => value,
+        value @ v1::Value::I32(_) => value,
         _ => panic!("unexpected non-I32 result found for prepare_regex_redux"),
     };
 
@@ -347,7 +351,7 @@ fn bench_execute_regex_redux_v1(c: &mut Criterion) {
         .call(&mut store, &[test_data_ptr], slice::from_mut(&mut result))
         .unwrap();
     let input_data_mem_offset = match result {
-        Value::I32(value) => value,
+        v1::Value::I32(value) => value,
         _ => panic!("unexpected non-I32 result found for regex_redux_input_ptr"),
     };
 
@@ -396,13 +400,13 @@ fn bench_execute_count_until_v1(c: &mut Criterion) {
         .get_export(&store, "count_until")
         .and_then(v1::Extern::into_func)
         .unwrap();
-    let mut result = [Value::I32(0)];
+    let mut result = [v1::Value::I32(0)];
     c.bench_function("execute/count_until/v1", |b| {
         b.iter(|| {
             count_until
-                .call(&mut store, &[Value::I32(COUNT_UNTIL)], &mut result)
+                .call(&mut store, &[v1::Value::I32(COUNT_UNTIL)], &mut result)
                 .unwrap();
-            assert_matches!(result, [Value::I32(COUNT_UNTIL)]);
+            assert_matches!(result, [v1::Value::I32(COUNT_UNTIL)]);
         })
     });
 }
@@ -424,12 +428,12 @@ fn bench_execute_fac_recursive_v1(c: &mut Criterion) {
         .get_export(&store, "fac-rec")
         .and_then(v1::Extern::into_func)
         .unwrap();
-    let mut result = [Value::I64(0)];
+    let mut result = [v1::Value::I64(0)];
     c.bench_function("execute/factorial_recursive/v1", |b| {
         b.iter(|| {
-            fac.call(&mut store, &[Value::I64(25)], &mut result)
+            fac.call(&mut store, &[v1::Value::I64(25)], &mut result)
                 .unwrap();
-            assert_matches!(result, [Value::I64(7034535277573963776)]);
+            assert_matches!(result, [v1::Value::I64(7034535277573963776)]);
         })
     });
 }
@@ -451,12 +455,12 @@ fn bench_execute_fac_opt_v1(c: &mut Criterion) {
         .get_export(&store, "fac-opt")
         .and_then(v1::Extern::into_func)
         .unwrap();
-    let mut result = [Value::I64(0)];
+    let mut result = [v1::Value::I64(0)];
     c.bench_function("execute/factorial_optimized/v1", |b| {
         b.iter(|| {
-            fac.call(&mut store, &[Value::I64(25)], &mut result)
+            fac.call(&mut store, &[v1::Value::I64(25)], &mut result)
                 .unwrap();
-            assert_matches!(result, [Value::I64(7034535277573963776)]);
+            assert_matches!(result, [v1::Value::I64(7034535277573963776)]);
         })
     });
 }
@@ -483,13 +487,13 @@ fn bench_execute_recursive_ok_v1(c: &mut Criterion) {
         .get_export(&store, "call")
         .and_then(v1::Extern::into_func)
         .unwrap();
-    let mut result = [Value::I32(0)];
+    let mut result = [v1::Value::I32(0)];
     c.bench_function("execute/recursive_ok/v1", |b| {
         b.iter(|| {
             bench_call
-                .call(&mut store, &[Value::I32(RECURSIVE_DEPTH)], &mut result)
+                .call(&mut store, &[v1::Value::I32(RECURSIVE_DEPTH)], &mut result)
                 .unwrap();
-            assert_matches!(result, [Value::I32(0)]);
+            assert_matches!(result, [v1::Value::I32(0)]);
         })
     });
 }
@@ -510,10 +514,10 @@ fn bench_execute_recursive_trap_v1(c: &mut Criterion) {
         .get_export(&store, "call")
         .and_then(v1::Extern::into_func)
         .unwrap();
-    let mut result = [Value::I32(0)];
+    let mut result = [v1::Value::I32(0)];
     c.bench_function("execute/recursive_trap/v1", |b| {
         b.iter(|| {
-            let result = bench_call.call(&mut store, &[Value::I32(1000)], &mut result);
+            let result = bench_call.call(&mut store, &[v1::Value::I32(1000)], &mut result);
             assert_matches!(result, Err(_));
         })
     });
@@ -638,17 +642,17 @@ fn bench_execute_host_calls_v1(c: &mut Criterion) {
         .get_export(&store, "call")
         .and_then(v1::Extern::into_func)
         .unwrap();
-    let mut result = [Value::I64(0)];
+    let mut result = [v1::Value::I64(0)];
 
     c.bench_function("execute/host_calls/v1", |b| {
         b.iter(|| {
             call.call(
                 &mut store,
-                &[Value::I64(HOST_CALLS_REPETITIONS)],
+                &[v1::Value::I64(HOST_CALLS_REPETITIONS)],
                 &mut result,
             )
             .unwrap();
-            assert_matches!(result, [Value::I64(0)]);
+            assert_matches!(result, [v1::Value::I64(0)]);
         })
     });
 }
@@ -670,12 +674,12 @@ fn bench_execute_fibonacci_recursive_v1(c: &mut Criterion) {
         .get_export(&store, "fib_recursive")
         .and_then(v1::Extern::into_func)
         .unwrap();
-    let mut result = [Value::I32(0)];
+    let mut result = [v1::Value::I32(0)];
     c.bench_function("execute/fib_recursive/v1", |b| {
         b.iter(|| {
-            let result = bench_call.call(&mut store, &[Value::I32(25)], &mut result);
+            let result = bench_call.call(&mut store, &[v1::Value::I32(25)], &mut result);
             assert_matches!(result, Ok(_));
         });
-        assert_eq!(result, [Value::I32(75025)]);
+        assert_eq!(result, [v1::Value::I32(75025)]);
     });
 }
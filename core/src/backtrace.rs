@@ -0,0 +1,89 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt::{self, Display};
+
+/// A single Wasm function frame of a [`WasmBacktrace`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameInfo {
+    /// The index of the function within its Wasm module.
+    func_index: u32,
+    /// The name of the function as stated by the `name` custom section if any.
+    func_name: Option<Box<str>>,
+    /// The offset of the executed instruction within the original Wasm binary if known.
+    module_offset: Option<usize>,
+}
+
+impl FrameInfo {
+    /// Creates a new [`FrameInfo`].
+    pub fn new(func_index: u32, func_name: Option<Box<str>>, module_offset: Option<usize>) -> Self {
+        Self {
+            func_index,
+            func_name,
+            module_offset,
+        }
+    }
+
+    /// Returns the index of the function within its Wasm module.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Returns the name of the function if the Wasm module provides one.
+    pub fn func_name(&self) -> Option<&str> {
+        self.func_name.as_deref()
+    }
+
+    /// Returns the offset of the executed instruction within the original Wasm binary.
+    ///
+    /// Returns `None` if the offset is unknown.
+    pub fn module_offset(&self) -> Option<usize> {
+        self.module_offset
+    }
+}
+
+impl Display for FrameInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "at {} ", self.func_name().unwrap_or("<unknown>"))?;
+        match self.module_offset {
+            Some(offset) => write!(f, "(func {} @ {:#x})", self.func_index, offset),
+            None => write!(f, "(func {})", self.func_index),
+        }
+    }
+}
+
+/// The Wasm function frames that were active when a [`Trap`] occurred.
+///
+/// The innermost frame, i.e. the frame of the trapping function, comes first.
+///
+/// [`Trap`]: crate::Trap
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WasmBacktrace {
+    frames: Vec<FrameInfo>,
+}
+
+impl WasmBacktrace {
+    /// Creates a new [`WasmBacktrace`] from the given `frames`.
+    ///
+    /// The innermost frame is expected to come first.
+    pub fn new<I>(frames: I) -> Self
+    where
+        I: IntoIterator<Item = FrameInfo>,
+    {
+        Self {
+            frames: frames.into_iter().collect(),
+        }
+    }
+
+    /// Returns the frames of the [`WasmBacktrace`] starting with the innermost frame.
+    pub fn frames(&self) -> &[FrameInfo] {
+        &self.frames
+    }
+}
+
+impl Display for WasmBacktrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (n, frame) in self.frames.iter().enumerate() {
+            writeln!(f, "  {}: {}", n, frame)?;
+        }
+        Ok(())
+    }
+}
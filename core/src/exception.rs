@@ -0,0 +1,16 @@
+use core::fmt::{Debug, Display};
+use downcast_rs::{impl_downcast, DowncastSync};
+
+/// Trait implemented by exceptions of the Wasm `exception-handling` proposal.
+///
+/// Unlike a [`HostError`] an exception can be caught by Wasm code.
+/// The concrete exception type carrying the tag and payload of the exception
+/// is defined by the `wasmi` engine that threw it and can be accessed via
+/// [`WasmException::downcast_ref`].
+///
+/// Uncaught exceptions are reported to the embedder as [`Trap::Exception`].
+///
+/// [`HostError`]: crate::HostError
+/// [`Trap::Exception`]: crate::Trap::Exception
+pub trait WasmException: 'static + Display + Debug + DowncastSync {}
+impl_downcast!(WasmException);
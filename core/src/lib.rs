@@ -1,9 +1,13 @@
+mod backtrace;
+mod exception;
 mod host_error;
 mod nan_preserving_float;
 mod trap;
 mod untyped;
 mod value;
 
+#[cfg(feature = "simd")]
+mod simd;
 #[cfg(feature = "virtual_memory")]
 mod vmem;
 
@@ -13,6 +17,10 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std as alloc;
 
+#[cfg(feature = "simd")]
+pub use self::simd::V128;
+#[cfg(all(feature = "virtual_memory", target_os = "linux"))]
+pub use self::vmem::MemoryImage;
 #[cfg(feature = "virtual_memory")]
 pub use self::vmem::{VirtualMemory, VirtualMemoryError};
 
@@ -22,6 +30,8 @@ pub mod memory_units {
 }
 
 pub use self::{
+    backtrace::{FrameInfo, WasmBacktrace},
+    exception::WasmException,
     host_error::HostError,
     nan_preserving_float::{F32, F64},
     trap::{Trap, TrapCode},
@@ -0,0 +1,1391 @@
+//! Portable implementation of the 128-bit `simd` Wasm proposal.
+
+use crate::{
+    ArithmeticOps,
+    ExtendInto,
+    Float,
+    LittleEndianConvert,
+    TruncateSaturateInto,
+    UntypedValue,
+    WrapInto,
+    F32,
+    F64,
+};
+use core::{iter, ops::Neg};
+
+/// A 128-bit vector value of the Wasm `v128` type.
+///
+/// # Note
+///
+/// The bytes are stored in little endian order so that lane `0`
+/// always refers to the lowest bytes of the vector regardless of
+/// how the vector is interpreted.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct V128([u8; 16]);
+
+impl V128 {
+    /// Creates a [`V128`] from its 128-bit representation.
+    pub fn from_bits(bits: u128) -> Self {
+        Self(bits.to_le_bytes())
+    }
+
+    /// Returns the 128-bit representation of the [`V128`].
+    pub fn to_bits(self) -> u128 {
+        u128::from_le_bytes(self.0)
+    }
+
+    /// Returns an iterator over all lanes of the [`V128`] interpreted as `T`.
+    fn lanes<T: Lane>(self) -> impl Iterator<Item = T> {
+        let bytes = self.0;
+        (0..T::LANES).map(move |n| T::read(&bytes, n))
+    }
+
+    /// Creates a [`V128`] from the given `lanes` of type `T`.
+    ///
+    /// # Note
+    ///
+    /// Superfluous lanes are ignored and missing lanes are zero.
+    fn from_lanes<T: Lane>(lanes: impl IntoIterator<Item = T>) -> Self {
+        let mut bytes = [0x00; 16];
+        for (n, lane) in lanes.into_iter().take(T::LANES).enumerate() {
+            lane.write(&mut bytes, n);
+        }
+        Self(bytes)
+    }
+
+    /// Returns the lane `n` of the [`V128`] interpreted as `T`.
+    fn lane<T: Lane>(self, n: u8) -> T {
+        T::read(&self.0, usize::from(n))
+    }
+
+    /// Returns the [`V128`] with the lane `n` interpreted as `T` replaced by `value`.
+    fn with_lane<T: Lane>(mut self, n: u8, value: T) -> Self {
+        value.write(&mut self.0, usize::from(n));
+        self
+    }
+}
+
+impl LittleEndianConvert for V128 {
+    type Bytes = [u8; 16];
+
+    #[inline]
+    fn into_le_bytes(self) -> Self::Bytes {
+        self.0
+    }
+
+    #[inline]
+    fn from_le_bytes(bytes: Self::Bytes) -> Self {
+        Self(bytes)
+    }
+}
+
+/// A type that represents a single lane of a [`V128`].
+trait Lane: Copy + LittleEndianConvert {
+    /// The number of lanes of this type within a [`V128`].
+    const LANES: usize;
+
+    /// The number of bytes of a single lane.
+    const SIZE: usize = 16 / Self::LANES;
+
+    /// Reads the lane `n` from the little endian `bytes` of a [`V128`].
+    fn read(bytes: &[u8; 16], n: usize) -> Self {
+        let mut lane = <Self::Bytes as Default>::default();
+        lane.as_mut()
+            .copy_from_slice(&bytes[n * Self::SIZE..(n + 1) * Self::SIZE]);
+        Self::from_le_bytes(lane)
+    }
+
+    /// Writes `self` into lane `n` of the little endian `bytes` of a [`V128`].
+    fn write(self, bytes: &mut [u8; 16], n: usize) {
+        bytes[n * Self::SIZE..(n + 1) * Self::SIZE].copy_from_slice(self.into_le_bytes().as_ref());
+    }
+}
+
+macro_rules! impl_lane {
+    ( $( $ty:ty = $lanes:literal ),* $(,)? ) => {
+        $(
+            impl Lane for $ty {
+                const LANES: usize = $lanes;
+            }
+        )*
+    };
+}
+impl_lane!(
+    i8 = 16,
+    u8 = 16,
+    i16 = 8,
+    u16 = 8,
+    i32 = 4,
+    u32 = 4,
+    i64 = 2,
+    u64 = 2,
+    F32 = 4,
+    F64 = 2,
+);
+
+/// Applies `f` to all lanes of `value` interpreted as `T`.
+fn unary<T: Lane>(value: UntypedValue, f: impl Fn(T) -> T) -> UntypedValue {
+    V128::from_lanes(V128::from(value).lanes::<T>().map(f)).into()
+}
+
+/// Applies `f` to all pairs of lanes of `lhs` and `rhs` interpreted as `T`.
+fn binary<T: Lane>(lhs: UntypedValue, rhs: UntypedValue, f: impl Fn(T, T) -> T) -> UntypedValue {
+    let lhs = V128::from(lhs).lanes::<T>();
+    let rhs = V128::from(rhs).lanes::<T>();
+    V128::from_lanes(lhs.zip(rhs).map(|(lhs, rhs)| f(lhs, rhs))).into()
+}
+
+/// Compares all pairs of lanes of `lhs` and `rhs` interpreted as `T` using `f`.
+///
+/// Lanes of the result are all ones if the comparison holds and all zeros otherwise.
+fn comparison<T: Lane>(
+    lhs: UntypedValue,
+    rhs: UntypedValue,
+    f: impl Fn(T, T) -> bool,
+) -> UntypedValue {
+    let lhs = V128::from(lhs).lanes::<T>();
+    let rhs = V128::from(rhs).lanes::<T>();
+    let mut bytes = [0x00; 16];
+    for (lane, (lhs, rhs)) in bytes.chunks_exact_mut(T::SIZE).zip(lhs.zip(rhs)) {
+        if f(lhs, rhs) {
+            lane.fill(0xFF);
+        }
+    }
+    V128(bytes).into()
+}
+
+/// Shifts all lanes of `lhs` interpreted as `T` by the `rhs` amount using `f`.
+///
+/// The shift amount is taken modulo the lane width.
+fn shift<T: Lane>(lhs: UntypedValue, rhs: UntypedValue, f: impl Fn(T, u32) -> T) -> UntypedValue {
+    let amount = u32::from(rhs) % (T::SIZE as u32 * 8);
+    unary(lhs, |lane| f(lane, amount))
+}
+
+/// Converts the lanes of `value` interpreted as `T` into lanes of type `R` using `f`.
+///
+/// The conversion starts at the input lane `offset`.
+/// Lanes of the result that have no corresponding input lane are zero.
+fn convert<T: Lane, R: Lane>(
+    value: UntypedValue,
+    offset: usize,
+    f: impl Fn(T) -> R,
+) -> UntypedValue {
+    V128::from_lanes(V128::from(value).lanes::<T>().skip(offset).map(f)).into()
+}
+
+/// Converts the lanes of `lhs` followed by the lanes of `rhs` into lanes of type `R` using `f`.
+fn narrow<T: Lane, R: Lane>(
+    lhs: UntypedValue,
+    rhs: UntypedValue,
+    f: impl Fn(T) -> R,
+) -> UntypedValue {
+    let lhs = V128::from(lhs).lanes::<T>();
+    let rhs = V128::from(rhs).lanes::<T>();
+    V128::from_lanes(lhs.chain(rhs).map(f)).into()
+}
+
+/// Combines adjacent pairs of `lanes` using `f`.
+fn pairwise<T: Lane>(mut lanes: impl Iterator<Item = T>, f: impl Fn(T, T) -> T) -> UntypedValue {
+    V128::from_lanes(iter::from_fn(|| Some(f(lanes.next()?, lanes.next()?)))).into()
+}
+
+/// Returns `true` if all lanes of `value` interpreted as `T` are non-zero.
+fn all_true<T: Lane + Default + PartialEq>(value: UntypedValue) -> UntypedValue {
+    V128::from(value)
+        .lanes::<T>()
+        .all(|lane| lane != T::default())
+        .into()
+}
+
+/// Returns a mask of the sign bits of all lanes of `value` interpreted as `T`.
+fn bitmask<T: Lane + Default + PartialOrd>(value: UntypedValue) -> UntypedValue {
+    V128::from(value)
+        .lanes::<T>()
+        .enumerate()
+        .filter(|(_, lane)| *lane < T::default())
+        .fold(0_u32, |mask, (n, _)| mask | (1 << n))
+        .into()
+}
+
+/// Creates a [`V128`] with all lanes set to `value`.
+fn splat<T: Lane>(value: T) -> UntypedValue {
+    V128::from_lanes(iter::repeat(value)).into()
+}
+
+/// Narrows `value` into an `i8` saturating at its bounds.
+fn saturate_i8(value: i16) -> i8 {
+    value.clamp(i16::from(i8::MIN), i16::from(i8::MAX)) as i8
+}
+
+/// Narrows `value` into an `u8` saturating at its bounds.
+fn saturate_u8(value: i16) -> u8 {
+    value.clamp(0, i16::from(u8::MAX)) as u8
+}
+
+/// Narrows `value` into an `i16` saturating at its bounds.
+fn saturate_i16(value: i32) -> i16 {
+    value.clamp(i32::from(i16::MIN), i32::from(i16::MAX)) as i16
+}
+
+/// Narrows `value` into an `u16` saturating at its bounds.
+fn saturate_u16(value: i32) -> u16 {
+    value.clamp(0, i32::from(u16::MAX)) as u16
+}
+
+/// Returns the lane of `lhs` or `rhs` with the lesser value using the pseudo-minimum semantics.
+fn pmin<T: PartialOrd>(lhs: T, rhs: T) -> T {
+    if rhs < lhs {
+        rhs
+    } else {
+        lhs
+    }
+}
+
+/// Returns the lane of `lhs` or `rhs` with the greater value using the pseudo-maximum semantics.
+fn pmax<T: PartialOrd>(lhs: T, rhs: T) -> T {
+    if lhs < rhs {
+        rhs
+    } else {
+        lhs
+    }
+}
+
+impl UntypedValue {
+    /// Execute `i8x16.splat` Wasm operation.
+    pub fn i8x16_splat(self) -> Self {
+        splat(i8::from(self))
+    }
+
+    /// Execute `i16x8.splat` Wasm operation.
+    pub fn i16x8_splat(self) -> Self {
+        splat(i16::from(self))
+    }
+
+    /// Execute `i32x4.splat` Wasm operation.
+    pub fn i32x4_splat(self) -> Self {
+        splat(i32::from(self))
+    }
+
+    /// Execute `i64x2.splat` Wasm operation.
+    pub fn i64x2_splat(self) -> Self {
+        splat(i64::from(self))
+    }
+
+    /// Execute `f32x4.splat` Wasm operation.
+    pub fn f32x4_splat(self) -> Self {
+        splat(F32::from(self))
+    }
+
+    /// Execute `f64x2.splat` Wasm operation.
+    pub fn f64x2_splat(self) -> Self {
+        splat(F64::from(self))
+    }
+
+    /// Execute `i8x16.extract_lane_s` Wasm operation.
+    pub fn i8x16_extract_lane_s(self, lane: u8) -> Self {
+        i32::from(V128::from(self).lane::<i8>(lane)).into()
+    }
+
+    /// Execute `i8x16.extract_lane_u` Wasm operation.
+    pub fn i8x16_extract_lane_u(self, lane: u8) -> Self {
+        u32::from(V128::from(self).lane::<u8>(lane)).into()
+    }
+
+    /// Execute `i16x8.extract_lane_s` Wasm operation.
+    pub fn i16x8_extract_lane_s(self, lane: u8) -> Self {
+        i32::from(V128::from(self).lane::<i16>(lane)).into()
+    }
+
+    /// Execute `i16x8.extract_lane_u` Wasm operation.
+    pub fn i16x8_extract_lane_u(self, lane: u8) -> Self {
+        u32::from(V128::from(self).lane::<u16>(lane)).into()
+    }
+
+    /// Execute `i32x4.extract_lane` Wasm operation.
+    pub fn i32x4_extract_lane(self, lane: u8) -> Self {
+        V128::from(self).lane::<i32>(lane).into()
+    }
+
+    /// Execute `i64x2.extract_lane` Wasm operation.
+    pub fn i64x2_extract_lane(self, lane: u8) -> Self {
+        V128::from(self).lane::<i64>(lane).into()
+    }
+
+    /// Execute `f32x4.extract_lane` Wasm operation.
+    pub fn f32x4_extract_lane(self, lane: u8) -> Self {
+        V128::from(self).lane::<F32>(lane).into()
+    }
+
+    /// Execute `f64x2.extract_lane` Wasm operation.
+    pub fn f64x2_extract_lane(self, lane: u8) -> Self {
+        V128::from(self).lane::<F64>(lane).into()
+    }
+
+    /// Execute `i8x16.replace_lane` Wasm operation.
+    pub fn i8x16_replace_lane(self, lane: u8, value: Self) -> Self {
+        V128::from(self).with_lane(lane, i8::from(value)).into()
+    }
+
+    /// Execute `i16x8.replace_lane` Wasm operation.
+    pub fn i16x8_replace_lane(self, lane: u8, value: Self) -> Self {
+        V128::from(self).with_lane(lane, i16::from(value)).into()
+    }
+
+    /// Execute `i32x4.replace_lane` Wasm operation.
+    pub fn i32x4_replace_lane(self, lane: u8, value: Self) -> Self {
+        V128::from(self).with_lane(lane, i32::from(value)).into()
+    }
+
+    /// Execute `i64x2.replace_lane` Wasm operation.
+    pub fn i64x2_replace_lane(self, lane: u8, value: Self) -> Self {
+        V128::from(self).with_lane(lane, i64::from(value)).into()
+    }
+
+    /// Execute `f32x4.replace_lane` Wasm operation.
+    pub fn f32x4_replace_lane(self, lane: u8, value: Self) -> Self {
+        V128::from(self).with_lane(lane, F32::from(value)).into()
+    }
+
+    /// Execute `f64x2.replace_lane` Wasm operation.
+    pub fn f64x2_replace_lane(self, lane: u8, value: Self) -> Self {
+        V128::from(self).with_lane(lane, F64::from(value)).into()
+    }
+
+    /// Execute `i8x16.shuffle` Wasm operation.
+    ///
+    /// The `selector` lanes select the bytes of the concatenation of `self` and `rhs`.
+    pub fn i8x16_shuffle(self, rhs: Self, selector: Self) -> Self {
+        let lhs = V128::from(self).0;
+        let rhs = V128::from(rhs).0;
+        V128::from_lanes(V128::from(selector).lanes::<u8>().map(|n| {
+            let n = usize::from(n);
+            if n < 16 {
+                lhs[n]
+            } else {
+                rhs[n - 16]
+            }
+        }))
+        .into()
+    }
+
+    /// Execute `i8x16.swizzle` Wasm operation.
+    pub fn i8x16_swizzle(self, selector: Self) -> Self {
+        let bytes = V128::from(self).0;
+        V128::from_lanes(
+            V128::from(selector)
+                .lanes::<u8>()
+                .map(|n| bytes.get(usize::from(n)).copied().unwrap_or(0)),
+        )
+        .into()
+    }
+
+    /// Execute `i8x16.eq` Wasm operation.
+    pub fn i8x16_eq(self, rhs: Self) -> Self {
+        comparison::<i8>(self, rhs, |lhs, rhs| lhs == rhs)
+    }
+
+    /// Execute `i8x16.ne` Wasm operation.
+    pub fn i8x16_ne(self, rhs: Self) -> Self {
+        comparison::<i8>(self, rhs, |lhs, rhs| lhs != rhs)
+    }
+
+    /// Execute `i8x16.lt_s` Wasm operation.
+    pub fn i8x16_lt_s(self, rhs: Self) -> Self {
+        comparison::<i8>(self, rhs, |lhs, rhs| lhs < rhs)
+    }
+
+    /// Execute `i8x16.lt_u` Wasm operation.
+    pub fn i8x16_lt_u(self, rhs: Self) -> Self {
+        comparison::<u8>(self, rhs, |lhs, rhs| lhs < rhs)
+    }
+
+    /// Execute `i8x16.gt_s` Wasm operation.
+    pub fn i8x16_gt_s(self, rhs: Self) -> Self {
+        comparison::<i8>(self, rhs, |lhs, rhs| lhs > rhs)
+    }
+
+    /// Execute `i8x16.gt_u` Wasm operation.
+    pub fn i8x16_gt_u(self, rhs: Self) -> Self {
+        comparison::<u8>(self, rhs, |lhs, rhs| lhs > rhs)
+    }
+
+    /// Execute `i8x16.le_s` Wasm operation.
+    pub fn i8x16_le_s(self, rhs: Self) -> Self {
+        comparison::<i8>(self, rhs, |lhs, rhs| lhs <= rhs)
+    }
+
+    /// Execute `i8x16.le_u` Wasm operation.
+    pub fn i8x16_le_u(self, rhs: Self) -> Self {
+        comparison::<u8>(self, rhs, |lhs, rhs| lhs <= rhs)
+    }
+
+    /// Execute `i8x16.ge_s` Wasm operation.
+    pub fn i8x16_ge_s(self, rhs: Self) -> Self {
+        comparison::<i8>(self, rhs, |lhs, rhs| lhs >= rhs)
+    }
+
+    /// Execute `i8x16.ge_u` Wasm operation.
+    pub fn i8x16_ge_u(self, rhs: Self) -> Self {
+        comparison::<u8>(self, rhs, |lhs, rhs| lhs >= rhs)
+    }
+
+    /// Execute `i16x8.eq` Wasm operation.
+    pub fn i16x8_eq(self, rhs: Self) -> Self {
+        comparison::<i16>(self, rhs, |lhs, rhs| lhs == rhs)
+    }
+
+    /// Execute `i16x8.ne` Wasm operation.
+    pub fn i16x8_ne(self, rhs: Self) -> Self {
+        comparison::<i16>(self, rhs, |lhs, rhs| lhs != rhs)
+    }
+
+    /// Execute `i16x8.lt_s` Wasm operation.
+    pub fn i16x8_lt_s(self, rhs: Self) -> Self {
+        comparison::<i16>(self, rhs, |lhs, rhs| lhs < rhs)
+    }
+
+    /// Execute `i16x8.lt_u` Wasm operation.
+    pub fn i16x8_lt_u(self, rhs: Self) -> Self {
+        comparison::<u16>(self, rhs, |lhs, rhs| lhs < rhs)
+    }
+
+    /// Execute `i16x8.gt_s` Wasm operation.
+    pub fn i16x8_gt_s(self, rhs: Self) -> Self {
+        comparison::<i16>(self, rhs, |lhs, rhs| lhs > rhs)
+    }
+
+    /// Execute `i16x8.gt_u` Wasm operation.
+    pub fn i16x8_gt_u(self, rhs: Self) -> Self {
+        comparison::<u16>(self, rhs, |lhs, rhs| lhs > rhs)
+    }
+
+    /// Execute `i16x8.le_s` Wasm operation.
+    pub fn i16x8_le_s(self, rhs: Self) -> Self {
+        comparison::<i16>(self, rhs, |lhs, rhs| lhs <= rhs)
+    }
+
+    /// Execute `i16x8.le_u` Wasm operation.
+    pub fn i16x8_le_u(self, rhs: Self) -> Self {
+        comparison::<u16>(self, rhs, |lhs, rhs| lhs <= rhs)
+    }
+
+    /// Execute `i16x8.ge_s` Wasm operation.
+    pub fn i16x8_ge_s(self, rhs: Self) -> Self {
+        comparison::<i16>(self, rhs, |lhs, rhs| lhs >= rhs)
+    }
+
+    /// Execute `i16x8.ge_u` Wasm operation.
+    pub fn i16x8_ge_u(self, rhs: Self) -> Self {
+        comparison::<u16>(self, rhs, |lhs, rhs| lhs >= rhs)
+    }
+
+    /// Execute `i32x4.eq` Wasm operation.
+    pub fn i32x4_eq(self, rhs: Self) -> Self {
+        comparison::<i32>(self, rhs, |lhs, rhs| lhs == rhs)
+    }
+
+    /// Execute `i32x4.ne` Wasm operation.
+    pub fn i32x4_ne(self, rhs: Self) -> Self {
+        comparison::<i32>(self, rhs, |lhs, rhs| lhs != rhs)
+    }
+
+    /// Execute `i32x4.lt_s` Wasm operation.
+    pub fn i32x4_lt_s(self, rhs: Self) -> Self {
+        comparison::<i32>(self, rhs, |lhs, rhs| lhs < rhs)
+    }
+
+    /// Execute `i32x4.lt_u` Wasm operation.
+    pub fn i32x4_lt_u(self, rhs: Self) -> Self {
+        comparison::<u32>(self, rhs, |lhs, rhs| lhs < rhs)
+    }
+
+    /// Execute `i32x4.gt_s` Wasm operation.
+    pub fn i32x4_gt_s(self, rhs: Self) -> Self {
+        comparison::<i32>(self, rhs, |lhs, rhs| lhs > rhs)
+    }
+
+    /// Execute `i32x4.gt_u` Wasm operation.
+    pub fn i32x4_gt_u(self, rhs: Self) -> Self {
+        comparison::<u32>(self, rhs, |lhs, rhs| lhs > rhs)
+    }
+
+    /// Execute `i32x4.le_s` Wasm operation.
+    pub fn i32x4_le_s(self, rhs: Self) -> Self {
+        comparison::<i32>(self, rhs, |lhs, rhs| lhs <= rhs)
+    }
+
+    /// Execute `i32x4.le_u` Wasm operation.
+    pub fn i32x4_le_u(self, rhs: Self) -> Self {
+        comparison::<u32>(self, rhs, |lhs, rhs| lhs <= rhs)
+    }
+
+    /// Execute `i32x4.ge_s` Wasm operation.
+    pub fn i32x4_ge_s(self, rhs: Self) -> Self {
+        comparison::<i32>(self, rhs, |lhs, rhs| lhs >= rhs)
+    }
+
+    /// Execute `i32x4.ge_u` Wasm operation.
+    pub fn i32x4_ge_u(self, rhs: Self) -> Self {
+        comparison::<u32>(self, rhs, |lhs, rhs| lhs >= rhs)
+    }
+
+    /// Execute `i64x2.eq` Wasm operation.
+    pub fn i64x2_eq(self, rhs: Self) -> Self {
+        comparison::<i64>(self, rhs, |lhs, rhs| lhs == rhs)
+    }
+
+    /// Execute `i64x2.ne` Wasm operation.
+    pub fn i64x2_ne(self, rhs: Self) -> Self {
+        comparison::<i64>(self, rhs, |lhs, rhs| lhs != rhs)
+    }
+
+    /// Execute `i64x2.lt_s` Wasm operation.
+    pub fn i64x2_lt_s(self, rhs: Self) -> Self {
+        comparison::<i64>(self, rhs, |lhs, rhs| lhs < rhs)
+    }
+
+    /// Execute `i64x2.gt_s` Wasm operation.
+    pub fn i64x2_gt_s(self, rhs: Self) -> Self {
+        comparison::<i64>(self, rhs, |lhs, rhs| lhs > rhs)
+    }
+
+    /// Execute `i64x2.le_s` Wasm operation.
+    pub fn i64x2_le_s(self, rhs: Self) -> Self {
+        comparison::<i64>(self, rhs, |lhs, rhs| lhs <= rhs)
+    }
+
+    /// Execute `i64x2.ge_s` Wasm operation.
+    pub fn i64x2_ge_s(self, rhs: Self) -> Self {
+        comparison::<i64>(self, rhs, |lhs, rhs| lhs >= rhs)
+    }
+
+    /// Execute `f32x4.eq` Wasm operation.
+    pub fn f32x4_eq(self, rhs: Self) -> Self {
+        comparison::<F32>(self, rhs, |lhs, rhs| lhs == rhs)
+    }
+
+    /// Execute `f32x4.ne` Wasm operation.
+    pub fn f32x4_ne(self, rhs: Self) -> Self {
+        comparison::<F32>(self, rhs, |lhs, rhs| lhs != rhs)
+    }
+
+    /// Execute `f32x4.lt` Wasm operation.
+    pub fn f32x4_lt(self, rhs: Self) -> Self {
+        comparison::<F32>(self, rhs, |lhs, rhs| lhs < rhs)
+    }
+
+    /// Execute `f32x4.gt` Wasm operation.
+    pub fn f32x4_gt(self, rhs: Self) -> Self {
+        comparison::<F32>(self, rhs, |lhs, rhs| lhs > rhs)
+    }
+
+    /// Execute `f32x4.le` Wasm operation.
+    pub fn f32x4_le(self, rhs: Self) -> Self {
+        comparison::<F32>(self, rhs, |lhs, rhs| lhs <= rhs)
+    }
+
+    /// Execute `f32x4.ge` Wasm operation.
+    pub fn f32x4_ge(self, rhs: Self) -> Self {
+        comparison::<F32>(self, rhs, |lhs, rhs| lhs >= rhs)
+    }
+
+    /// Execute `f64x2.eq` Wasm operation.
+    pub fn f64x2_eq(self, rhs: Self) -> Self {
+        comparison::<F64>(self, rhs, |lhs, rhs| lhs == rhs)
+    }
+
+    /// Execute `f64x2.ne` Wasm operation.
+    pub fn f64x2_ne(self, rhs: Self) -> Self {
+        comparison::<F64>(self, rhs, |lhs, rhs| lhs != rhs)
+    }
+
+    /// Execute `f64x2.lt` Wasm operation.
+    pub fn f64x2_lt(self, rhs: Self) -> Self {
+        comparison::<F64>(self, rhs, |lhs, rhs| lhs < rhs)
+    }
+
+    /// Execute `f64x2.gt` Wasm operation.
+    pub fn f64x2_gt(self, rhs: Self) -> Self {
+        comparison::<F64>(self, rhs, |lhs, rhs| lhs > rhs)
+    }
+
+    /// Execute `f64x2.le` Wasm operation.
+    pub fn f64x2_le(self, rhs: Self) -> Self {
+        comparison::<F64>(self, rhs, |lhs, rhs| lhs <= rhs)
+    }
+
+    /// Execute `f64x2.ge` Wasm operation.
+    pub fn f64x2_ge(self, rhs: Self) -> Self {
+        comparison::<F64>(self, rhs, |lhs, rhs| lhs >= rhs)
+    }
+
+    /// Execute `v128.not` Wasm operation.
+    pub fn v128_not(self) -> Self {
+        V128::from_bits(!V128::from(self).to_bits()).into()
+    }
+
+    /// Execute `v128.and` Wasm operation.
+    pub fn v128_and(self, rhs: Self) -> Self {
+        V128::from_bits(V128::from(self).to_bits() & V128::from(rhs).to_bits()).into()
+    }
+
+    /// Execute `v128.andnot` Wasm operation.
+    pub fn v128_andnot(self, rhs: Self) -> Self {
+        V128::from_bits(V128::from(self).to_bits() & !V128::from(rhs).to_bits()).into()
+    }
+
+    /// Execute `v128.or` Wasm operation.
+    pub fn v128_or(self, rhs: Self) -> Self {
+        V128::from_bits(V128::from(self).to_bits() | V128::from(rhs).to_bits()).into()
+    }
+
+    /// Execute `v128.xor` Wasm operation.
+    pub fn v128_xor(self, rhs: Self) -> Self {
+        V128::from_bits(V128::from(self).to_bits() ^ V128::from(rhs).to_bits()).into()
+    }
+
+    /// Execute `v128.bitselect` Wasm operation.
+    pub fn v128_bitselect(self, rhs: Self, mask: Self) -> Self {
+        let lhs = V128::from(self).to_bits();
+        let rhs = V128::from(rhs).to_bits();
+        let mask = V128::from(mask).to_bits();
+        V128::from_bits((lhs & mask) | (rhs & !mask)).into()
+    }
+
+    /// Execute `v128.any_true` Wasm operation.
+    pub fn v128_any_true(self) -> Self {
+        (V128::from(self).to_bits() != 0).into()
+    }
+
+    /// Execute `i8x16.abs` Wasm operation.
+    pub fn i8x16_abs(self) -> Self {
+        unary(self, i8::wrapping_abs)
+    }
+
+    /// Execute `i8x16.neg` Wasm operation.
+    pub fn i8x16_neg(self) -> Self {
+        unary(self, i8::wrapping_neg)
+    }
+
+    /// Execute `i8x16.popcnt` Wasm operation.
+    pub fn i8x16_popcnt(self) -> Self {
+        unary::<u8>(self, |lane| lane.count_ones() as u8)
+    }
+
+    /// Execute `i8x16.all_true` Wasm operation.
+    pub fn i8x16_all_true(self) -> Self {
+        all_true::<i8>(self)
+    }
+
+    /// Execute `i8x16.bitmask` Wasm operation.
+    pub fn i8x16_bitmask(self) -> Self {
+        bitmask::<i8>(self)
+    }
+
+    /// Execute `i8x16.narrow_i16x8_s` Wasm operation.
+    pub fn i8x16_narrow_i16x8_s(self, rhs: Self) -> Self {
+        narrow(self, rhs, saturate_i8)
+    }
+
+    /// Execute `i8x16.narrow_i16x8_u` Wasm operation.
+    pub fn i8x16_narrow_i16x8_u(self, rhs: Self) -> Self {
+        narrow(self, rhs, saturate_u8)
+    }
+
+    /// Execute `i8x16.shl` Wasm operation.
+    pub fn i8x16_shl(self, rhs: Self) -> Self {
+        shift(self, rhs, i8::wrapping_shl)
+    }
+
+    /// Execute `i8x16.shr_s` Wasm operation.
+    pub fn i8x16_shr_s(self, rhs: Self) -> Self {
+        shift(self, rhs, i8::wrapping_shr)
+    }
+
+    /// Execute `i8x16.shr_u` Wasm operation.
+    pub fn i8x16_shr_u(self, rhs: Self) -> Self {
+        shift(self, rhs, u8::wrapping_shr)
+    }
+
+    /// Execute `i8x16.add` Wasm operation.
+    pub fn i8x16_add(self, rhs: Self) -> Self {
+        binary(self, rhs, i8::wrapping_add)
+    }
+
+    /// Execute `i8x16.add_sat_s` Wasm operation.
+    pub fn i8x16_add_sat_s(self, rhs: Self) -> Self {
+        binary(self, rhs, i8::saturating_add)
+    }
+
+    /// Execute `i8x16.add_sat_u` Wasm operation.
+    pub fn i8x16_add_sat_u(self, rhs: Self) -> Self {
+        binary(self, rhs, u8::saturating_add)
+    }
+
+    /// Execute `i8x16.sub` Wasm operation.
+    pub fn i8x16_sub(self, rhs: Self) -> Self {
+        binary(self, rhs, i8::wrapping_sub)
+    }
+
+    /// Execute `i8x16.sub_sat_s` Wasm operation.
+    pub fn i8x16_sub_sat_s(self, rhs: Self) -> Self {
+        binary(self, rhs, i8::saturating_sub)
+    }
+
+    /// Execute `i8x16.sub_sat_u` Wasm operation.
+    pub fn i8x16_sub_sat_u(self, rhs: Self) -> Self {
+        binary(self, rhs, u8::saturating_sub)
+    }
+
+    /// Execute `i8x16.min_s` Wasm operation.
+    pub fn i8x16_min_s(self, rhs: Self) -> Self {
+        binary(self, rhs, i8::min)
+    }
+
+    /// Execute `i8x16.min_u` Wasm operation.
+    pub fn i8x16_min_u(self, rhs: Self) -> Self {
+        binary(self, rhs, u8::min)
+    }
+
+    /// Execute `i8x16.max_s` Wasm operation.
+    pub fn i8x16_max_s(self, rhs: Self) -> Self {
+        binary(self, rhs, i8::max)
+    }
+
+    /// Execute `i8x16.max_u` Wasm operation.
+    pub fn i8x16_max_u(self, rhs: Self) -> Self {
+        binary(self, rhs, u8::max)
+    }
+
+    /// Execute `i8x16.avgr_u` Wasm operation.
+    pub fn i8x16_avgr_u(self, rhs: Self) -> Self {
+        binary::<u8>(self, rhs, |lhs, rhs| {
+            (u16::from(lhs) + u16::from(rhs)).div_ceil(2) as u8
+        })
+    }
+
+    /// Execute `i16x8.extadd_pairwise_i8x16_s` Wasm operation.
+    pub fn i16x8_extadd_pairwise_i8x16_s(self) -> Self {
+        pairwise(
+            V128::from(self).lanes::<i8>().map(i16::from),
+            i16::wrapping_add,
+        )
+    }
+
+    /// Execute `i16x8.extadd_pairwise_i8x16_u` Wasm operation.
+    pub fn i16x8_extadd_pairwise_i8x16_u(self) -> Self {
+        pairwise(
+            V128::from(self).lanes::<u8>().map(i16::from),
+            i16::wrapping_add,
+        )
+    }
+
+    /// Execute `i16x8.abs` Wasm operation.
+    pub fn i16x8_abs(self) -> Self {
+        unary(self, i16::wrapping_abs)
+    }
+
+    /// Execute `i16x8.neg` Wasm operation.
+    pub fn i16x8_neg(self) -> Self {
+        unary(self, i16::wrapping_neg)
+    }
+
+    /// Execute `i16x8.q15mulr_sat_s` Wasm operation.
+    pub fn i16x8_q15mulr_sat_s(self, rhs: Self) -> Self {
+        binary::<i16>(self, rhs, |lhs, rhs| {
+            saturate_i16((i32::from(lhs) * i32::from(rhs) + 0x4000) >> 15)
+        })
+    }
+
+    /// Execute `i16x8.all_true` Wasm operation.
+    pub fn i16x8_all_true(self) -> Self {
+        all_true::<i16>(self)
+    }
+
+    /// Execute `i16x8.bitmask` Wasm operation.
+    pub fn i16x8_bitmask(self) -> Self {
+        bitmask::<i16>(self)
+    }
+
+    /// Execute `i16x8.narrow_i32x4_s` Wasm operation.
+    pub fn i16x8_narrow_i32x4_s(self, rhs: Self) -> Self {
+        narrow(self, rhs, saturate_i16)
+    }
+
+    /// Execute `i16x8.narrow_i32x4_u` Wasm operation.
+    pub fn i16x8_narrow_i32x4_u(self, rhs: Self) -> Self {
+        narrow(self, rhs, saturate_u16)
+    }
+
+    /// Execute `i16x8.extend_low_i8x16_s` Wasm operation.
+    pub fn i16x8_extend_low_i8x16_s(self) -> Self {
+        convert::<i8, i16>(self, 0, i16::from)
+    }
+
+    /// Execute `i16x8.extend_high_i8x16_s` Wasm operation.
+    pub fn i16x8_extend_high_i8x16_s(self) -> Self {
+        convert::<i8, i16>(self, 8, i16::from)
+    }
+
+    /// Execute `i16x8.extend_low_i8x16_u` Wasm operation.
+    pub fn i16x8_extend_low_i8x16_u(self) -> Self {
+        convert::<u8, u16>(self, 0, u16::from)
+    }
+
+    /// Execute `i16x8.extend_high_i8x16_u` Wasm operation.
+    pub fn i16x8_extend_high_i8x16_u(self) -> Self {
+        convert::<u8, u16>(self, 8, u16::from)
+    }
+
+    /// Execute `i16x8.shl` Wasm operation.
+    pub fn i16x8_shl(self, rhs: Self) -> Self {
+        shift(self, rhs, i16::wrapping_shl)
+    }
+
+    /// Execute `i16x8.shr_s` Wasm operation.
+    pub fn i16x8_shr_s(self, rhs: Self) -> Self {
+        shift(self, rhs, i16::wrapping_shr)
+    }
+
+    /// Execute `i16x8.shr_u` Wasm operation.
+    pub fn i16x8_shr_u(self, rhs: Self) -> Self {
+        shift(self, rhs, u16::wrapping_shr)
+    }
+
+    /// Execute `i16x8.add` Wasm operation.
+    pub fn i16x8_add(self, rhs: Self) -> Self {
+        binary(self, rhs, i16::wrapping_add)
+    }
+
+    /// Execute `i16x8.add_sat_s` Wasm operation.
+    pub fn i16x8_add_sat_s(self, rhs: Self) -> Self {
+        binary(self, rhs, i16::saturating_add)
+    }
+
+    /// Execute `i16x8.add_sat_u` Wasm operation.
+    pub fn i16x8_add_sat_u(self, rhs: Self) -> Self {
+        binary(self, rhs, u16::saturating_add)
+    }
+
+    /// Execute `i16x8.sub` Wasm operation.
+    pub fn i16x8_sub(self, rhs: Self) -> Self {
+        binary(self, rhs, i16::wrapping_sub)
+    }
+
+    /// Execute `i16x8.sub_sat_s` Wasm operation.
+    pub fn i16x8_sub_sat_s(self, rhs: Self) -> Self {
+        binary(self, rhs, i16::saturating_sub)
+    }
+
+    /// Execute `i16x8.sub_sat_u` Wasm operation.
+    pub fn i16x8_sub_sat_u(self, rhs: Self) -> Self {
+        binary(self, rhs, u16::saturating_sub)
+    }
+
+    /// Execute `i16x8.mul` Wasm operation.
+    pub fn i16x8_mul(self, rhs: Self) -> Self {
+        binary(self, rhs, i16::wrapping_mul)
+    }
+
+    /// Execute `i16x8.min_s` Wasm operation.
+    pub fn i16x8_min_s(self, rhs: Self) -> Self {
+        binary(self, rhs, i16::min)
+    }
+
+    /// Execute `i16x8.min_u` Wasm operation.
+    pub fn i16x8_min_u(self, rhs: Self) -> Self {
+        binary(self, rhs, u16::min)
+    }
+
+    /// Execute `i16x8.max_s` Wasm operation.
+    pub fn i16x8_max_s(self, rhs: Self) -> Self {
+        binary(self, rhs, i16::max)
+    }
+
+    /// Execute `i16x8.max_u` Wasm operation.
+    pub fn i16x8_max_u(self, rhs: Self) -> Self {
+        binary(self, rhs, u16::max)
+    }
+
+    /// Execute `i16x8.avgr_u` Wasm operation.
+    pub fn i16x8_avgr_u(self, rhs: Self) -> Self {
+        binary::<u16>(self, rhs, |lhs, rhs| {
+            (u32::from(lhs) + u32::from(rhs)).div_ceil(2) as u16
+        })
+    }
+
+    /// Execute `i16x8.extmul_low_i8x16_s` Wasm operation.
+    pub fn i16x8_extmul_low_i8x16_s(self, rhs: Self) -> Self {
+        self.i16x8_extend_low_i8x16_s()
+            .i16x8_mul(rhs.i16x8_extend_low_i8x16_s())
+    }
+
+    /// Execute `i16x8.extmul_high_i8x16_s` Wasm operation.
+    pub fn i16x8_extmul_high_i8x16_s(self, rhs: Self) -> Self {
+        self.i16x8_extend_high_i8x16_s()
+            .i16x8_mul(rhs.i16x8_extend_high_i8x16_s())
+    }
+
+    /// Execute `i16x8.extmul_low_i8x16_u` Wasm operation.
+    pub fn i16x8_extmul_low_i8x16_u(self, rhs: Self) -> Self {
+        self.i16x8_extend_low_i8x16_u()
+            .i16x8_mul(rhs.i16x8_extend_low_i8x16_u())
+    }
+
+    /// Execute `i16x8.extmul_high_i8x16_u` Wasm operation.
+    pub fn i16x8_extmul_high_i8x16_u(self, rhs: Self) -> Self {
+        self.i16x8_extend_high_i8x16_u()
+            .i16x8_mul(rhs.i16x8_extend_high_i8x16_u())
+    }
+
+    /// Execute `i32x4.extadd_pairwise_i16x8_s` Wasm operation.
+    pub fn i32x4_extadd_pairwise_i16x8_s(self) -> Self {
+        pairwise(
+            V128::from(self).lanes::<i16>().map(i32::from),
+            i32::wrapping_add,
+        )
+    }
+
+    /// Execute `i32x4.extadd_pairwise_i16x8_u` Wasm operation.
+    pub fn i32x4_extadd_pairwise_i16x8_u(self) -> Self {
+        pairwise(
+            V128::from(self).lanes::<u16>().map(i32::from),
+            i32::wrapping_add,
+        )
+    }
+
+    /// Execute `i32x4.abs` Wasm operation.
+    pub fn i32x4_abs(self) -> Self {
+        unary(self, i32::wrapping_abs)
+    }
+
+    /// Execute `i32x4.neg` Wasm operation.
+    pub fn i32x4_neg(self) -> Self {
+        unary(self, i32::wrapping_neg)
+    }
+
+    /// Execute `i32x4.all_true` Wasm operation.
+    pub fn i32x4_all_true(self) -> Self {
+        all_true::<i32>(self)
+    }
+
+    /// Execute `i32x4.bitmask` Wasm operation.
+    pub fn i32x4_bitmask(self) -> Self {
+        bitmask::<i32>(self)
+    }
+
+    /// Execute `i32x4.extend_low_i16x8_s` Wasm operation.
+    pub fn i32x4_extend_low_i16x8_s(self) -> Self {
+        convert::<i16, i32>(self, 0, i32::from)
+    }
+
+    /// Execute `i32x4.extend_high_i16x8_s` Wasm operation.
+    pub fn i32x4_extend_high_i16x8_s(self) -> Self {
+        convert::<i16, i32>(self, 4, i32::from)
+    }
+
+    /// Execute `i32x4.extend_low_i16x8_u` Wasm operation.
+    pub fn i32x4_extend_low_i16x8_u(self) -> Self {
+        convert::<u16, u32>(self, 0, u32::from)
+    }
+
+    /// Execute `i32x4.extend_high_i16x8_u` Wasm operation.
+    pub fn i32x4_extend_high_i16x8_u(self) -> Self {
+        convert::<u16, u32>(self, 4, u32::from)
+    }
+
+    /// Execute `i32x4.shl` Wasm operation.
+    pub fn i32x4_shl(self, rhs: Self) -> Self {
+        shift(self, rhs, i32::wrapping_shl)
+    }
+
+    /// Execute `i32x4.shr_s` Wasm operation.
+    pub fn i32x4_shr_s(self, rhs: Self) -> Self {
+        shift(self, rhs, i32::wrapping_shr)
+    }
+
+    /// Execute `i32x4.shr_u` Wasm operation.
+    pub fn i32x4_shr_u(self, rhs: Self) -> Self {
+        shift(self, rhs, u32::wrapping_shr)
+    }
+
+    /// Execute `i32x4.add` Wasm operation.
+    pub fn i32x4_add(self, rhs: Self) -> Self {
+        binary(self, rhs, i32::wrapping_add)
+    }
+
+    /// Execute `i32x4.sub` Wasm operation.
+    pub fn i32x4_sub(self, rhs: Self) -> Self {
+        binary(self, rhs, i32::wrapping_sub)
+    }
+
+    /// Execute `i32x4.mul` Wasm operation.
+    pub fn i32x4_mul(self, rhs: Self) -> Self {
+        binary(self, rhs, i32::wrapping_mul)
+    }
+
+    /// Execute `i32x4.min_s` Wasm operation.
+    pub fn i32x4_min_s(self, rhs: Self) -> Self {
+        binary(self, rhs, i32::min)
+    }
+
+    /// Execute `i32x4.min_u` Wasm operation.
+    pub fn i32x4_min_u(self, rhs: Self) -> Self {
+        binary(self, rhs, u32::min)
+    }
+
+    /// Execute `i32x4.max_s` Wasm operation.
+    pub fn i32x4_max_s(self, rhs: Self) -> Self {
+        binary(self, rhs, i32::max)
+    }
+
+    /// Execute `i32x4.max_u` Wasm operation.
+    pub fn i32x4_max_u(self, rhs: Self) -> Self {
+        binary(self, rhs, u32::max)
+    }
+
+    /// Execute `i32x4.dot_i16x8_s` Wasm operation.
+    pub fn i32x4_dot_i16x8_s(self, rhs: Self) -> Self {
+        let lhs = V128::from(self).lanes::<i16>();
+        let rhs = V128::from(rhs).lanes::<i16>();
+        let products = lhs
+            .zip(rhs)
+            .map(|(lhs, rhs)| i32::from(lhs) * i32::from(rhs));
+        pairwise(products, i32::wrapping_add)
+    }
+
+    /// Execute `i32x4.extmul_low_i16x8_s` Wasm operation.
+    pub fn i32x4_extmul_low_i16x8_s(self, rhs: Self) -> Self {
+        self.i32x4_extend_low_i16x8_s()
+            .i32x4_mul(rhs.i32x4_extend_low_i16x8_s())
+    }
+
+    /// Execute `i32x4.extmul_high_i16x8_s` Wasm operation.
+    pub fn i32x4_extmul_high_i16x8_s(self, rhs: Self) -> Self {
+        self.i32x4_extend_high_i16x8_s()
+            .i32x4_mul(rhs.i32x4_extend_high_i16x8_s())
+    }
+
+    /// Execute `i32x4.extmul_low_i16x8_u` Wasm operation.
+    pub fn i32x4_extmul_low_i16x8_u(self, rhs: Self) -> Self {
+        self.i32x4_extend_low_i16x8_u()
+            .i32x4_mul(rhs.i32x4_extend_low_i16x8_u())
+    }
+
+    /// Execute `i32x4.extmul_high_i16x8_u` Wasm operation.
+    pub fn i32x4_extmul_high_i16x8_u(self, rhs: Self) -> Self {
+        self.i32x4_extend_high_i16x8_u()
+            .i32x4_mul(rhs.i32x4_extend_high_i16x8_u())
+    }
+
+    /// Execute `i64x2.abs` Wasm operation.
+    pub fn i64x2_abs(self) -> Self {
+        unary(self, i64::wrapping_abs)
+    }
+
+    /// Execute `i64x2.neg` Wasm operation.
+    pub fn i64x2_neg(self) -> Self {
+        unary(self, i64::wrapping_neg)
+    }
+
+    /// Execute `i64x2.all_true` Wasm operation.
+    pub fn i64x2_all_true(self) -> Self {
+        all_true::<i64>(self)
+    }
+
+    /// Execute `i64x2.bitmask` Wasm operation.
+    pub fn i64x2_bitmask(self) -> Self {
+        bitmask::<i64>(self)
+    }
+
+    /// Execute `i64x2.extend_low_i32x4_s` Wasm operation.
+    pub fn i64x2_extend_low_i32x4_s(self) -> Self {
+        convert::<i32, i64>(self, 0, i64::from)
+    }
+
+    /// Execute `i64x2.extend_high_i32x4_s` Wasm operation.
+    pub fn i64x2_extend_high_i32x4_s(self) -> Self {
+        convert::<i32, i64>(self, 2, i64::from)
+    }
+
+    /// Execute `i64x2.extend_low_i32x4_u` Wasm operation.
+    pub fn i64x2_extend_low_i32x4_u(self) -> Self {
+        convert::<u32, u64>(self, 0, u64::from)
+    }
+
+    /// Execute `i64x2.extend_high_i32x4_u` Wasm operation.
+    pub fn i64x2_extend_high_i32x4_u(self) -> Self {
+        convert::<u32, u64>(self, 2, u64::from)
+    }
+
+    /// Execute `i64x2.shl` Wasm operation.
+    pub fn i64x2_shl(self, rhs: Self) -> Self {
+        shift(self, rhs, i64::wrapping_shl)
+    }
+
+    /// Execute `i64x2.shr_s` Wasm operation.
+    pub fn i64x2_shr_s(self, rhs: Self) -> Self {
+        shift(self, rhs, i64::wrapping_shr)
+    }
+
+    /// Execute `i64x2.shr_u` Wasm operation.
+    pub fn i64x2_shr_u(self, rhs: Self) -> Self {
+        shift(self, rhs, u64::wrapping_shr)
+    }
+
+    /// Execute `i64x2.add` Wasm operation.
+    pub fn i64x2_add(self, rhs: Self) -> Self {
+        binary(self, rhs, i64::wrapping_add)
+    }
+
+    /// Execute `i64x2.sub` Wasm operation.
+    pub fn i64x2_sub(self, rhs: Self) -> Self {
+        binary(self, rhs, i64::wrapping_sub)
+    }
+
+    /// Execute `i64x2.mul` Wasm operation.
+    pub fn i64x2_mul(self, rhs: Self) -> Self {
+        binary(self, rhs, i64::wrapping_mul)
+    }
+
+    /// Execute `i64x2.extmul_low_i32x4_s` Wasm operation.
+    pub fn i64x2_extmul_low_i32x4_s(self, rhs: Self) -> Self {
+        self.i64x2_extend_low_i32x4_s()
+            .i64x2_mul(rhs.i64x2_extend_low_i32x4_s())
+    }
+
+    /// Execute `i64x2.extmul_high_i32x4_s` Wasm operation.
+    pub fn i64x2_extmul_high_i32x4_s(self, rhs: Self) -> Self {
+        self.i64x2_extend_high_i32x4_s()
+            .i64x2_mul(rhs.i64x2_extend_high_i32x4_s())
+    }
+
+    /// Execute `i64x2.extmul_low_i32x4_u` Wasm operation.
+    pub fn i64x2_extmul_low_i32x4_u(self, rhs: Self) -> Self {
+        self.i64x2_extend_low_i32x4_u()
+            .i64x2_mul(rhs.i64x2_extend_low_i32x4_u())
+    }
+
+    /// Execute `i64x2.extmul_high_i32x4_u` Wasm operation.
+    pub fn i64x2_extmul_high_i32x4_u(self, rhs: Self) -> Self {
+        self.i64x2_extend_high_i32x4_u()
+            .i64x2_mul(rhs.i64x2_extend_high_i32x4_u())
+    }
+
+    /// Execute `f32x4.ceil` Wasm operation.
+    pub fn f32x4_ceil(self) -> Self {
+        unary(self, <F32 as Float<F32>>::ceil)
+    }
+
+    /// Execute `f32x4.floor` Wasm operation.
+    pub fn f32x4_floor(self) -> Self {
+        unary(self, <F32 as Float<F32>>::floor)
+    }
+
+    /// Execute `f32x4.trunc` Wasm operation.
+    pub fn f32x4_trunc(self) -> Self {
+        unary(self, <F32 as Float<F32>>::trunc)
+    }
+
+    /// Execute `f32x4.nearest` Wasm operation.
+    pub fn f32x4_nearest(self) -> Self {
+        unary(self, <F32 as Float<F32>>::nearest)
+    }
+
+    /// Execute `f32x4.abs` Wasm operation.
+    pub fn f32x4_abs(self) -> Self {
+        unary(self, <F32 as Float<F32>>::abs)
+    }
+
+    /// Execute `f32x4.neg` Wasm operation.
+    pub fn f32x4_neg(self) -> Self {
+        unary(self, <F32 as Neg>::neg)
+    }
+
+    /// Execute `f32x4.sqrt` Wasm operation.
+    pub fn f32x4_sqrt(self) -> Self {
+        unary(self, <F32 as Float<F32>>::sqrt)
+    }
+
+    /// Execute `f32x4.add` Wasm operation.
+    pub fn f32x4_add(self, rhs: Self) -> Self {
+        binary(self, rhs, <F32 as ArithmeticOps<F32>>::add)
+    }
+
+    /// Execute `f32x4.sub` Wasm operation.
+    pub fn f32x4_sub(self, rhs: Self) -> Self {
+        binary(self, rhs, <F32 as ArithmeticOps<F32>>::sub)
+    }
+
+    /// Execute `f32x4.mul` Wasm operation.
+    pub fn f32x4_mul(self, rhs: Self) -> Self {
+        binary(self, rhs, <F32 as ArithmeticOps<F32>>::mul)
+    }
+
+    /// Execute `f32x4.div` Wasm operation.
+    pub fn f32x4_div(self, rhs: Self) -> Self {
+        binary::<F32>(self, rhs, |lhs, rhs| lhs / rhs)
+    }
+
+    /// Execute `f32x4.min` Wasm operation.
+    pub fn f32x4_min(self, rhs: Self) -> Self {
+        binary(self, rhs, <F32 as Float<F32>>::min)
+    }
+
+    /// Execute `f32x4.max` Wasm operation.
+    pub fn f32x4_max(self, rhs: Self) -> Self {
+        binary(self, rhs, <F32 as Float<F32>>::max)
+    }
+
+    /// Execute `f32x4.pmin` Wasm operation.
+    pub fn f32x4_pmin(self, rhs: Self) -> Self {
+        binary(self, rhs, pmin::<F32>)
+    }
+
+    /// Execute `f32x4.pmax` Wasm operation.
+    pub fn f32x4_pmax(self, rhs: Self) -> Self {
+        binary(self, rhs, pmax::<F32>)
+    }
+
+    /// Execute `f64x2.ceil` Wasm operation.
+    pub fn f64x2_ceil(self) -> Self {
+        unary(self, <F64 as Float<F64>>::ceil)
+    }
+
+    /// Execute `f64x2.floor` Wasm operation.
+    pub fn f64x2_floor(self) -> Self {
+        unary(self, <F64 as Float<F64>>::floor)
+    }
+
+    /// Execute `f64x2.trunc` Wasm operation.
+    pub fn f64x2_trunc(self) -> Self {
+        unary(self, <F64 as Float<F64>>::trunc)
+    }
+
+    /// Execute `f64x2.nearest` Wasm operation.
+    pub fn f64x2_nearest(self) -> Self {
+        unary(self, <F64 as Float<F64>>::nearest)
+    }
+
+    /// Execute `f64x2.abs` Wasm operation.
+    pub fn f64x2_abs(self) -> Self {
+        unary(self, <F64 as Float<F64>>::abs)
+    }
+
+    /// Execute `f64x2.neg` Wasm operation.
+    pub fn f64x2_neg(self) -> Self {
+        unary(self, <F64 as Neg>::neg)
+    }
+
+    /// Execute `f64x2.sqrt` Wasm operation.
+    pub fn f64x2_sqrt(self) -> Self {
+        unary(self, <F64 as Float<F64>>::sqrt)
+    }
+
+    /// Execute `f64x2.add` Wasm operation.
+    pub fn f64x2_add(self, rhs: Self) -> Self {
+        binary(self, rhs, <F64 as ArithmeticOps<F64>>::add)
+    }
+
+    /// Execute `f64x2.sub` Wasm operation.
+    pub fn f64x2_sub(self, rhs: Self) -> Self {
+        binary(self, rhs, <F64 as ArithmeticOps<F64>>::sub)
+    }
+
+    /// Execute `f64x2.mul` Wasm operation.
+    pub fn f64x2_mul(self, rhs: Self) -> Self {
+        binary(self, rhs, <F64 as ArithmeticOps<F64>>::mul)
+    }
+
+    /// Execute `f64x2.div` Wasm operation.
+    pub fn f64x2_div(self, rhs: Self) -> Self {
+        binary::<F64>(self, rhs, |lhs, rhs| lhs / rhs)
+    }
+
+    /// Execute `f64x2.min` Wasm operation.
+    pub fn f64x2_min(self, rhs: Self) -> Self {
+        binary(self, rhs, <F64 as Float<F64>>::min)
+    }
+
+    /// Execute `f64x2.max` Wasm operation.
+    pub fn f64x2_max(self, rhs: Self) -> Self {
+        binary(self, rhs, <F64 as Float<F64>>::max)
+    }
+
+    /// Execute `f64x2.pmin` Wasm operation.
+    pub fn f64x2_pmin(self, rhs: Self) -> Self {
+        binary(self, rhs, pmin::<F64>)
+    }
+
+    /// Execute `f64x2.pmax` Wasm operation.
+    pub fn f64x2_pmax(self, rhs: Self) -> Self {
+        binary(self, rhs, pmax::<F64>)
+    }
+
+    /// Execute `i32x4.trunc_sat_f32x4_s` Wasm operation.
+    pub fn i32x4_trunc_sat_f32x4_s(self) -> Self {
+        convert(
+            self,
+            0,
+            <F32 as TruncateSaturateInto<i32>>::truncate_saturate_into,
+        )
+    }
+
+    /// Execute `i32x4.trunc_sat_f32x4_u` Wasm operation.
+    pub fn i32x4_trunc_sat_f32x4_u(self) -> Self {
+        convert(
+            self,
+            0,
+            <F32 as TruncateSaturateInto<u32>>::truncate_saturate_into,
+        )
+    }
+
+    /// Execute `f32x4.convert_i32x4_s` Wasm operation.
+    pub fn f32x4_convert_i32x4_s(self) -> Self {
+        convert(self, 0, <i32 as ExtendInto<F32>>::extend_into)
+    }
+
+    /// Execute `f32x4.convert_i32x4_u` Wasm operation.
+    pub fn f32x4_convert_i32x4_u(self) -> Self {
+        convert(self, 0, <u32 as ExtendInto<F32>>::extend_into)
+    }
+
+    /// Execute `i32x4.trunc_sat_f64x2_s_zero` Wasm operation.
+    pub fn i32x4_trunc_sat_f64x2_s_zero(self) -> Self {
+        convert(
+            self,
+            0,
+            <F64 as TruncateSaturateInto<i32>>::truncate_saturate_into,
+        )
+    }
+
+    /// Execute `i32x4.trunc_sat_f64x2_u_zero` Wasm operation.
+    pub fn i32x4_trunc_sat_f64x2_u_zero(self) -> Self {
+        convert(
+            self,
+            0,
+            <F64 as TruncateSaturateInto<u32>>::truncate_saturate_into,
+        )
+    }
+
+    /// Execute `f64x2.convert_low_i32x4_s` Wasm operation.
+    pub fn f64x2_convert_low_i32x4_s(self) -> Self {
+        convert(self, 0, <i32 as ExtendInto<F64>>::extend_into)
+    }
+
+    /// Execute `f64x2.convert_low_i32x4_u` Wasm operation.
+    pub fn f64x2_convert_low_i32x4_u(self) -> Self {
+        convert(self, 0, <u32 as ExtendInto<F64>>::extend_into)
+    }
+
+    /// Execute `f32x4.demote_f64x2_zero` Wasm operation.
+    pub fn f32x4_demote_f64x2_zero(self) -> Self {
+        convert(self, 0, <F64 as WrapInto<F32>>::wrap_into)
+    }
+
+    /// Execute `f64x2.promote_low_f32x4` Wasm operation.
+    pub fn f64x2_promote_low_f32x4(self) -> Self {
+        convert(self, 0, <F32 as ExtendInto<F64>>::extend_into)
+    }
+}
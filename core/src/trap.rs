@@ -1,4 +1,4 @@
-use crate::HostError;
+use crate::{HostError, WasmBacktrace, WasmException};
 use alloc::boxed::Box;
 use core::fmt::{self, Display};
 
@@ -10,11 +10,30 @@ use std::error::Error as StdError;
 /// Under some conditions, wasm execution may produce a `Trap`, which immediately aborts execution.
 /// Traps can't be handled by WebAssembly code, but are reported to the embedder.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Trap {
     /// Traps during Wasm execution.
     Code(TrapCode),
     /// Traps and errors during host execution.
     Host(Box<dyn HostError>),
+    /// An exception that has been thrown but not caught by Wasm code.
+    ///
+    /// # Note
+    ///
+    /// Host functions may also return this trap in order to throw an
+    /// exception that can be caught by the calling Wasm code.
+    Exception(Box<dyn WasmException>),
+    /// A trap that occurred during Wasm execution together with its [`WasmBacktrace`].
+    ///
+    /// # Note
+    ///
+    /// The underlying trap is never a [`Trap::WithBacktrace`] itself.
+    WithBacktrace {
+        /// The underlying trap.
+        trap: Box<Trap>,
+        /// The Wasm function frames that were active when the trap occurred.
+        backtrace: WasmBacktrace,
+    },
 }
 
 impl Trap {
@@ -27,16 +46,79 @@ impl Trap {
         Self::Host(Box::new(host_error))
     }
 
+    /// Wraps the exception in a [`Trap`].
+    #[inline]
+    pub fn exception<U>(exception: U) -> Self
+    where
+        U: WasmException + Sized,
+    {
+        Self::Exception(Box::new(exception))
+    }
+
+    /// Attaches the [`WasmBacktrace`] to the [`Trap`].
+    ///
+    /// # Note
+    ///
+    /// Replaces the backtrace if `self` already has a backtrace attached.
+    pub fn with_backtrace(self, backtrace: WasmBacktrace) -> Self {
+        Self::WithBacktrace {
+            trap: Box::new(self.into_inner()),
+            backtrace,
+        }
+    }
+
+    /// Returns the [`WasmBacktrace`] of the [`Trap`] if any.
+    #[inline]
+    pub fn backtrace(&self) -> Option<&WasmBacktrace> {
+        if let Self::WithBacktrace { backtrace, .. } = self {
+            return Some(backtrace);
+        }
+        None
+    }
+
+    /// Returns the underlying [`Trap`] without its [`WasmBacktrace`].
+    #[inline]
+    pub fn inner(&self) -> &Self {
+        if let Self::WithBacktrace { trap, .. } = self {
+            return trap;
+        }
+        self
+    }
+
+    /// Returns the underlying [`Trap`] discarding its [`WasmBacktrace`].
+    #[inline]
+    pub fn into_inner(self) -> Self {
+        if let Self::WithBacktrace { trap, .. } = self {
+            return *trap;
+        }
+        self
+    }
+
     /// Returns `true` if `self` trap originating from host code.
     #[inline]
     pub fn is_host(&self) -> bool {
-        matches!(self, Self::Host(_))
+        matches!(self.inner(), Self::Host(_))
+    }
+
+    /// Returns `true` if `self` is an uncaught exception.
+    #[inline]
+    pub fn is_exception(&self) -> bool {
+        matches!(self.inner(), Self::Exception(_))
+    }
+
+    /// Returns the uncaught exception if `self` is an exception.
+    #[inline]
+    pub fn as_exception(&self) -> Option<&dyn WasmException> {
+        if let Self::Exception(exception) = self.inner() {
+            return Some(&**exception);
+        }
+        None
     }
 
     /// Returns the [`TrapCode`] traps originating from Wasm execution.
     #[inline]
     pub fn code(&self) -> Option<TrapCode> {
-        if let Self::Code(trap_code) = self {
+        if let Self::Code(trap_code) = self.inner() {
             return Some(*trap_code);
         }
         None
@@ -65,6 +147,12 @@ impl Display for Trap {
         match self {
             Trap::Code(trap_code) => Display::fmt(trap_code, f),
             Trap::Host(host_error) => Display::fmt(host_error, f),
+            Trap::Exception(exception) => Display::fmt(exception, f),
+            Trap::WithBacktrace { trap, backtrace } => {
+                writeln!(f, "{}", trap)?;
+                writeln!(f, "wasm backtrace:")?;
+                Display::fmt(backtrace, f)
+            }
         }
     }
 }
@@ -80,8 +168,13 @@ impl StdError for Trap {
 ///
 /// See [`Trap`] for details.
 ///
+/// # Note
+///
+/// New trap codes are added alongside new Wasm proposals and `wasmi` features.
+///
 /// [`Trap`]: struct.Trap.html
 #[derive(Debug, Copy, Clone)]
+#[non_exhaustive]
 pub enum TrapCode {
     /// Wasm code executed `unreachable` opcode.
     ///
@@ -148,6 +241,42 @@ pub enum TrapCode {
     /// with an index that points to a function with signature different of what is
     /// expected by this indirect call, this trap is raised.
     UnexpectedSignature,
+
+    /// Ran out of fuel while executing a function.
+    ///
+    /// This can only happen if fuel metering is enabled and
+    /// the executed function consumed all of its available fuel.
+    OutOfFuel,
+
+    /// Attempt to atomically access linear memory at an unaligned address.
+    ///
+    /// Atomic memory accesses of the Wasm `threads` proposal require their
+    /// effective address to be aligned to the size of the accessed value.
+    UnalignedAtomic,
+
+    /// Attempt to wait on a linear memory that is not shared.
+    ///
+    /// This can happen when `memory.atomic.wait32` or `memory.atomic.wait64`
+    /// is executed on a linear memory that is not shared between threads.
+    ExpectedSharedMemory,
+
+    /// The execution has been interrupted because its epoch deadline was reached.
+    ///
+    /// This can only happen if epoch interruption is enabled and the
+    /// epoch of the engine reached the epoch deadline of the store.
+    Interrupted,
+
+    /// A `memory.grow` or `table.grow` operation was denied by a resource limiter.
+    ///
+    /// This can only happen if the resource limiter of the store
+    /// is configured to trap instead of failing the growth operation.
+    GrowthOperationLimited,
+
+    /// Attempt to call a function that has been removed from its store.
+    ///
+    /// This can happen when calling a function of a removed instance
+    /// that is still referenced, e.g. by the table of another instance.
+    DanglingFunc,
 }
 
 impl TrapCode {
@@ -168,6 +297,12 @@ impl TrapCode {
             TrapCode::InvalidConversionToInt => "invalid conversion to integer",
             TrapCode::StackOverflow => "call stack exhausted",
             TrapCode::UnexpectedSignature => "indirect call type mismatch",
+            TrapCode::OutOfFuel => "all fuel consumed",
+            TrapCode::UnalignedAtomic => "unaligned atomic",
+            TrapCode::ExpectedSharedMemory => "expected shared memory",
+            TrapCode::Interrupted => "interrupted",
+            TrapCode::GrowthOperationLimited => "growth operation limited",
+            TrapCode::DanglingFunc => "dangling function",
         }
     }
 }
@@ -18,30 +18,63 @@ use core::{
     ops::{Neg, Shl, Shr},
 };
 
+#[cfg(feature = "simd")]
+use crate::V128;
+
 /// An untyped [`Value`].
 ///
 /// Provides a dense and simple interface to all functional Wasm operations.
+///
+/// # Note
+///
+/// With the `simd` crate feature enabled an [`UntypedValue`] is 128-bit wide
+/// in order to also represent values of the `v128` type.
 #[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
-#[repr(transparent)]
+#[cfg_attr(not(feature = "simd"), repr(transparent))]
 pub struct UntypedValue {
     /// This inner value is required to have enough bits to represent
     /// all fundamental WebAssembly types `i32`, `i64`, `f32` and `f64`.
     bits: u64,
+    /// The upper 64 bits of a `v128` value.
+    ///
+    /// This is always zero for all other WebAssembly types.
+    #[cfg(feature = "simd")]
+    hi_bits: u64,
 }
 
 impl UntypedValue {
+    /// Creates an [`UntypedValue`] from the given lower 64 bits.
+    fn from_bits(bits: u64) -> Self {
+        Self {
+            bits,
+            #[cfg(feature = "simd")]
+            hi_bits: 0,
+        }
+    }
+
     /// Returns the underlying bits of the [`UntypedValue`].
+    ///
+    /// # Note
+    ///
+    /// For `v128` values these are only the lower 64 bits.
     pub fn to_bits(self) -> u64 {
         self.bits
     }
 
     /// Converts the [`UntypedValue`] into a [`Value`].
+    ///
+    /// # Panics
+    ///
+    /// If `value_type` is a reference or vector type which cannot be represented by [`Value`].
     pub fn with_type(self, value_type: ValueType) -> Value {
         match value_type {
             ValueType::I32 => Value::I32(<_>::from(self)),
             ValueType::I64 => Value::I64(<_>::from(self)),
             ValueType::F32 => Value::F32(<_>::from(self)),
             ValueType::F64 => Value::F64(<_>::from(self)),
+            ValueType::FuncRef | ValueType::ExternRef | ValueType::V128 => {
+                panic!("cannot create a value of type {}", value_type)
+            }
         }
     }
 }
@@ -94,7 +127,7 @@ macro_rules! impl_from_prim {
         $(
             impl From<$prim> for UntypedValue {
                 fn from(value: $prim) -> Self {
-                    Self { bits: value as _ }
+                    Self::from_bits(value as _)
                 }
             }
         )*
@@ -112,9 +145,7 @@ macro_rules! impl_from_float {
         $(
             impl From<$float> for UntypedValue {
                 fn from(value: $float) -> Self {
-                    Self {
-                        bits: value.to_bits() as _,
-                    }
+                    Self::from_bits(value.to_bits() as _)
                 }
             }
         )*
@@ -122,6 +153,24 @@ macro_rules! impl_from_float {
 }
 impl_from_float!(f32, f64, F32, F64);
 
+#[cfg(feature = "simd")]
+impl From<V128> for UntypedValue {
+    fn from(value: V128) -> Self {
+        let bits = value.to_bits();
+        Self {
+            bits: bits as u64,
+            hi_bits: (bits >> 64) as u64,
+        }
+    }
+}
+
+#[cfg(feature = "simd")]
+impl From<UntypedValue> for V128 {
+    fn from(untyped: UntypedValue) -> Self {
+        Self::from_bits((u128::from(untyped.hi_bits) << 64) | u128::from(untyped.bits))
+    }
+}
+
 macro_rules! op {
     ( $operator:tt ) => {{
         |lhs, rhs| lhs $operator rhs
@@ -9,8 +9,13 @@ use parity_wasm::elements as pwasm;
 ///
 /// See [`Value`] for details.
 ///
+/// # Note
+///
+/// New value types are added alongside new Wasm proposals.
+///
 /// [`Value`]: enum.Value.html
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[non_exhaustive]
 pub enum ValueType {
     /// 32-bit signed or unsigned integer.
     I32,
@@ -20,6 +25,24 @@ pub enum ValueType {
     F32,
     /// 64-bit IEEE 754-2008 floating point number.
     F64,
+    /// A nullable function reference.
+    ///
+    /// # Note
+    ///
+    /// Introduced by the `reference-types` Wasm proposal.
+    FuncRef,
+    /// A nullable external reference.
+    ///
+    /// # Note
+    ///
+    /// Introduced by the `reference-types` Wasm proposal.
+    ExternRef,
+    /// A 128-bit vector of packed integer or floating point lanes.
+    ///
+    /// # Note
+    ///
+    /// Introduced by the `simd` Wasm proposal.
+    V128,
 }
 
 impl Display for ValueType {
@@ -29,11 +52,19 @@ impl Display for ValueType {
             Self::I64 => write!(f, "i64"),
             Self::F32 => write!(f, "f32"),
             Self::F64 => write!(f, "f64"),
+            Self::FuncRef => write!(f, "funcref"),
+            Self::ExternRef => write!(f, "externref"),
+            Self::V128 => write!(f, "v128"),
         }
     }
 }
 
 impl ValueType {
+    /// Returns `true` if [`ValueType`] is a reference type.
+    pub fn is_ref(&self) -> bool {
+        matches!(self, Self::FuncRef | Self::ExternRef)
+    }
+
     /// Converts into [`ValueType`] from [`pwasm::ValueType`].
     #[inline]
     pub fn from_elements(value_type: pwasm::ValueType) -> Self {
@@ -46,6 +77,10 @@ impl ValueType {
     }
 
     /// Converts from [`ValueType`] into [`pwasm::ValueType`].
+    ///
+    /// # Panics
+    ///
+    /// If `self` is a reference or vector type which is not supported by `parity-wasm`.
     #[inline]
     pub fn into_elements(self) -> pwasm::ValueType {
         match self {
@@ -53,6 +88,9 @@ impl ValueType {
             Self::I64 => pwasm::ValueType::I64,
             Self::F32 => pwasm::ValueType::F32,
             Self::F64 => pwasm::ValueType::F64,
+            Self::FuncRef | Self::ExternRef | Self::V128 => {
+                panic!("value type {} is not supported by parity-wasm", self)
+            }
         }
     }
 }
@@ -266,6 +304,10 @@ pub trait Float<T>: ArithmeticOps<T> {
 
 impl Value {
     /// Creates new default value of given type.
+    ///
+    /// # Panics
+    ///
+    /// If `value_type` is a reference or vector type which cannot be represented by [`Value`].
     #[inline]
     pub fn default(value_type: ValueType) -> Self {
         match value_type {
@@ -273,6 +315,9 @@ impl Value {
             ValueType::I64 => Value::I64(0),
             ValueType::F32 => Value::F32(0f32.into()),
             ValueType::F64 => Value::F64(0f64.into()),
+            ValueType::FuncRef | ValueType::ExternRef | ValueType::V128 => {
+                panic!("cannot create a value of type {}", value_type)
+            }
         }
     }
 
@@ -17,6 +17,9 @@ use region::{Allocation, Protection};
 pub enum VirtualMemoryError {
     Region(region::Error),
     AllocationOutOfBounds,
+    /// An operating system error upon operating on a [`MemoryImage`].
+    #[cfg(target_os = "linux")]
+    Image(std::io::Error),
 }
 
 impl From<region::Error> for VirtualMemoryError {
@@ -35,6 +38,12 @@ impl Display for VirtualMemoryError {
                 error
             ),
             Self::AllocationOutOfBounds => write!(f, "virtual memory allocation is too big"),
+            #[cfg(target_os = "linux")]
+            Self::Image(error) => write!(
+                f,
+                "encountered failure while operating with a memory image: {}",
+                error
+            ),
         }
     }
 }
@@ -43,12 +52,20 @@ impl Display for VirtualMemoryError {
 pub struct VirtualMemory {
     /// The virtual memory allocation.
     allocation: Allocation,
+    /// The amount of bytes at the start of the allocation that are accessible.
+    ///
+    /// # Note
+    ///
+    /// This is smaller than the length of the allocation for reserved
+    /// virtual memory that has not yet been committed entirely.
+    committed: usize,
 }
 
 impl Debug for VirtualMemory {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("VirtualMemory")
             .field("len", &self.allocation.len())
+            .field("committed", &self.committed)
             .finish()
     }
 }
@@ -74,29 +91,247 @@ impl VirtualMemory {
             return Err(VirtualMemoryError::AllocationOutOfBounds);
         }
         let allocation = region::alloc(len, Protection::READ_WRITE)?;
-        Ok(Self { allocation })
+        let committed = allocation.len();
+        Ok(Self {
+            allocation,
+            committed,
+        })
+    }
+
+    /// Reserves a new virtual memory allocation without committing any of it.
+    ///
+    /// # Note
+    ///
+    /// - The reserved virtual memory is inaccessible until it is committed
+    ///   via [`VirtualMemory::commit`]. This allows to reserve large sparse
+    ///   regions of virtual memory without occupying physical memory.
+    /// - Unlike [`VirtualMemory::new`] this is not limited to 4GB.
+    ///
+    /// # Errors
+    ///
+    /// - If `len` should not exceed `isize::max_value()`
+    /// - If `len` should be greater than 0.
+    /// - If the operating system returns an error upon virtual memory reservation.
+    pub fn reserve(len: usize) -> Result<Self, VirtualMemoryError> {
+        assert_ne!(len, 0, "cannot reserve empty virtual memory");
+        if len > isize::MAX as usize {
+            return Err(VirtualMemoryError::AllocationOutOfBounds);
+        }
+        let allocation = region::alloc(len, Protection::NONE)?;
+        Ok(Self {
+            allocation,
+            committed: 0,
+        })
+    }
+
+    /// Commits the first `len` bytes of the virtual memory allocation.
+    ///
+    /// # Note
+    ///
+    /// This is a no-op if at least `len` bytes are already committed.
+    ///
+    /// # Errors
+    ///
+    /// - If `len` exceeds the length of the virtual memory allocation.
+    /// - If the operating system returns an error upon committing the virtual memory.
+    pub fn commit(&mut self, len: usize) -> Result<(), VirtualMemoryError> {
+        if len <= self.committed {
+            return Ok(());
+        }
+        if len > self.allocation.len() {
+            return Err(VirtualMemoryError::AllocationOutOfBounds);
+        }
+        // # SAFETY
+        //
+        // The operation is safe since the changed address range is contained
+        // within the virtual memory allocation which is exclusively owned by `self`.
+        // Changing the protection of the already committed bytes is harmless
+        // since they already allow for read and write operations.
+        unsafe {
+            region::protect(
+                self.allocation.as_ptr::<u8>().add(self.committed),
+                len - self.committed,
+                Protection::READ_WRITE,
+            )?;
+        }
+        self.committed = len;
+        Ok(())
+    }
+
+    /// Maps the [`MemoryImage`] copy-on-write over the first bytes of the virtual memory allocation.
+    ///
+    /// # Note
+    ///
+    /// - Afterwards the first `image.len()` bytes of the virtual memory allocation
+    ///   read as the bytes of the [`MemoryImage`]. Pages are only copied once they
+    ///   are written to and writes never alter the [`MemoryImage`] itself.
+    /// - The mapped bytes are required to be committed already.
+    ///
+    /// # Errors
+    ///
+    /// - If the [`MemoryImage`] is longer than the committed bytes.
+    /// - If the operating system returns an error upon mapping the [`MemoryImage`].
+    #[cfg(target_os = "linux")]
+    pub fn map_image(&mut self, image: &MemoryImage) -> Result<(), VirtualMemoryError> {
+        if image.len() > self.committed {
+            return Err(VirtualMemoryError::AllocationOutOfBounds);
+        }
+        // # SAFETY
+        //
+        // The operation is safe since the replaced address range is contained
+        // within the committed bytes of the virtual memory allocation which is
+        // exclusively owned by `self`. The private mapping is unmapped together
+        // with the rest of the allocation once `self` is dropped.
+        let ptr = unsafe {
+            libc::mmap(
+                self.allocation.as_mut_ptr::<u8>().cast(),
+                image.len(),
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_PRIVATE | libc::MAP_FIXED,
+                image.fd,
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(VirtualMemoryError::Image(std::io::Error::last_os_error()));
+        }
+        Ok(())
     }
 
-    /// Returns a shared slice over the bytes of the virtual memory allocation.
+    /// Returns a shared slice over the committed bytes of the virtual memory allocation.
     #[inline]
     pub fn data(&self) -> &[u8] {
         // # SAFETY
         //
         // The operation is safe since we assume that the virtual memory allocation
-        // has been successful and allocated exactly `self.allocation.len()` bytes.
-        // Therefore creating a slice with `self.len` elements is valid.
+        // has been successful and allocated at least `self.committed` accessible bytes.
+        // Therefore creating a slice with `self.committed` elements is valid.
         // Aliasing guarantees are not violated since `self` is the only owner
         // of the underlying virtual memory allocation.
-        unsafe { slice::from_raw_parts(self.allocation.as_ptr(), self.allocation.len()) }
+        unsafe { slice::from_raw_parts(self.allocation.as_ptr(), self.committed) }
     }
 
-    /// Returns an exclusive slice over the bytes of the virtual memory allocation.
+    /// Returns an exclusive slice over the committed bytes of the virtual memory allocation.
     #[inline]
     pub fn data_mut(&mut self) -> &mut [u8] {
         // # SAFETY
         //
         // See safety proof of the `as_slice` method.
         // Additionally, it is not possible to obtain two mutable references for the same memory area.
-        unsafe { slice::from_raw_parts_mut(self.allocation.as_mut_ptr(), self.allocation.len()) }
+        unsafe { slice::from_raw_parts_mut(self.allocation.as_mut_ptr(), self.committed) }
+    }
+}
+
+/// An image of the initial bytes of a linear memory backed by an anonymous file.
+///
+/// # Note
+///
+/// A [`MemoryImage`] is prepared once and then mapped copy-on-write into
+/// any amount of [`VirtualMemory`] allocations via [`VirtualMemory::map_image`].
+/// This avoids copying the bytes of the image upon every mapping.
+#[cfg(target_os = "linux")]
+#[derive(Debug)]
+pub struct MemoryImage {
+    /// The file descriptor of the anonymous `memfd` file holding the image.
+    fd: libc::c_int,
+    /// The length of the image in bytes.
+    len: usize,
+}
+
+#[cfg(target_os = "linux")]
+impl MemoryImage {
+    /// Creates a new zero initialized [`MemoryImage`] with a length of `len` bytes.
+    ///
+    /// # Note
+    ///
+    /// Zero initialized parts of the [`MemoryImage`] do not occupy physical memory.
+    ///
+    /// # Errors
+    ///
+    /// - If `len` is not a multiple of the page size of the operating system.
+    /// - If the operating system returns an error upon creating the anonymous file.
+    pub fn new(len: usize) -> Result<Self, VirtualMemoryError> {
+        if !len.is_multiple_of(region::page::size()) || len > i64::MAX as usize {
+            return Err(VirtualMemoryError::AllocationOutOfBounds);
+        }
+        // # SAFETY
+        //
+        // The name is a valid nul-terminated C string.
+        let fd = unsafe { libc::memfd_create(c"wasmi_memory_image".as_ptr(), libc::MFD_CLOEXEC) };
+        if fd < 0 {
+            return Err(VirtualMemoryError::Image(std::io::Error::last_os_error()));
+        }
+        let image = Self { fd, len };
+        // # SAFETY
+        //
+        // The file descriptor refers to the anonymous file owned by `image`.
+        if unsafe { libc::ftruncate(image.fd, len as libc::off_t) } != 0 {
+            return Err(VirtualMemoryError::Image(std::io::Error::last_os_error()));
+        }
+        Ok(image)
+    }
+
+    /// Returns the length of the [`MemoryImage`] in bytes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the [`MemoryImage`] is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Writes the `bytes` into the [`MemoryImage`] starting at the `offset`.
+    ///
+    /// # Errors
+    ///
+    /// - If the `bytes` do not fit into the [`MemoryImage`] at the `offset`.
+    /// - If the operating system returns an error upon writing the `bytes`.
+    pub fn write(&mut self, offset: usize, bytes: &[u8]) -> Result<(), VirtualMemoryError> {
+        if offset
+            .checked_add(bytes.len())
+            .filter(|&end| end <= self.len)
+            .is_none()
+        {
+            return Err(VirtualMemoryError::AllocationOutOfBounds);
+        }
+        let mut written = 0;
+        while written < bytes.len() {
+            let rest = &bytes[written..];
+            // # SAFETY
+            //
+            // The file descriptor refers to the anonymous file owned by `self`
+            // and `rest` is valid for reads of `rest.len()` bytes.
+            let result = unsafe {
+                libc::pwrite(
+                    self.fd,
+                    rest.as_ptr().cast(),
+                    rest.len(),
+                    (offset + written) as libc::off_t,
+                )
+            };
+            if result < 0 {
+                let error = std::io::Error::last_os_error();
+                if error.kind() == std::io::ErrorKind::Interrupted {
+                    continue;
+                }
+                return Err(VirtualMemoryError::Image(error));
+            }
+            written += result as usize;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for MemoryImage {
+    fn drop(&mut self) {
+        // # SAFETY
+        //
+        // The file descriptor is exclusively owned by `self` and closed exactly once.
+        // Mappings of the [`MemoryImage`] stay valid after closing the file descriptor.
+        unsafe {
+            libc::close(self.fd);
+        }
     }
 }
@@ -56,6 +56,7 @@ impl ValueInternal {
             ValueType::I64 => RuntimeValue::I64(<_>::from_value_internal(self)),
             ValueType::F32 => RuntimeValue::F32(<_>::from_value_internal(self)),
             ValueType::F64 => RuntimeValue::F64(<_>::from_value_internal(self)),
+            _ => unreachable!("wasmi does not support reference or vector types"),
         }
     }
 }
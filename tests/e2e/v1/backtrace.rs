@@ -0,0 +1,110 @@
+//! Tests for the Wasm backtraces of traps in `wasmi_v1`.
+
+use super::utils::compile;
+use assert_matches::assert_matches;
+use wasmi_core::{Trap, TrapCode};
+use wasmi_v1::{Config, Engine, Extern, Func, Linker, Module, Store, TypedFunc};
+
+/// The Wasm module used in the tests below.
+///
+/// The exported `run` function traps within `$inner` which is called via `$outer`.
+const WAT: &str = r#"
+    (module $my_module
+        (import "env" "tick" (func $tick))
+        (func $inner (param $n i32) (result i32)
+            (call $tick)
+            (if (i32.eqz (local.get $n))
+                (then (unreachable))
+            )
+            (i32.div_u (i32.const 100) (i32.sub (local.get $n) (i32.const 1)))
+        )
+        (func $outer (param $n i32) (result i32)
+            (call $inner (local.get $n))
+        )
+        (func (export "run") (param $n i32) (result i32)
+            (call $outer (local.get $n))
+        )
+    )
+"#;
+
+/// Instantiates the Wasm module and returns its `run` function and Wasm binary.
+fn setup(wasm_backtrace: bool) -> (Store<()>, TypedFunc<i32, i32>, Vec<u8>) {
+    let config = Config::default().enable_wasm_backtrace(wasm_backtrace);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let tick = Func::wrap(&mut store, || {});
+    let mut linker = <Linker<()>>::new();
+    linker.define("env", "tick", tick).unwrap();
+    let run = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap()
+        .get_export(&store, "run")
+        .and_then(Extern::into_func)
+        .unwrap()
+        .typed::<i32, i32, _>(&store)
+        .unwrap();
+    (store, run, wasm)
+}
+
+#[test]
+fn module_names_are_parsed() {
+    let engine = Engine::default();
+    let module = compile(&engine, WAT);
+    assert_eq!(module.name(), Some("my_module"));
+    assert_eq!(module.func_name(0), Some("tick"));
+    assert_eq!(module.func_name(1), Some("inner"));
+    assert_eq!(module.func_name(2), Some("outer"));
+    assert_eq!(module.func_name(3), None);
+}
+
+#[test]
+fn backtrace_disabled_works() {
+    let (mut store, run, _) = setup(false);
+    let trap = run.call(&mut store, 0).unwrap_err();
+    assert_matches!(trap, Trap::Code(TrapCode::Unreachable));
+    assert!(trap.backtrace().is_none());
+}
+
+#[test]
+fn backtrace_of_trap_works() {
+    let (mut store, run, wasm) = setup(true);
+    assert_eq!(run.call(&mut store, 5).unwrap(), 25);
+    let trap = run.call(&mut store, 0).unwrap_err();
+    assert_matches!(trap.code(), Some(TrapCode::Unreachable));
+    let backtrace = trap.backtrace().unwrap();
+    let frames = backtrace.frames();
+    assert_eq!(frames.len(), 3);
+    let func_indices = frames.iter().map(|frame| frame.func_index());
+    assert!(func_indices.eq([1, 2, 3]));
+    let func_names = frames.iter().map(|frame| frame.func_name());
+    assert!(func_names.eq([Some("inner"), Some("outer"), None]));
+    // The offsets point to the trapping `unreachable` and the `call` operators.
+    let opcodes = frames
+        .iter()
+        .map(|frame| wasm[frame.module_offset().unwrap()]);
+    assert!(opcodes.eq([0x00, 0x10, 0x10]));
+    let inner = &frames[0];
+    assert_eq!(
+        inner.to_string(),
+        format!("at inner (func 1 @ {:#x})", inner.module_offset().unwrap())
+    );
+    assert!(trap
+        .to_string()
+        .starts_with("unreachable\nwasm backtrace:\n  0: at inner"));
+}
+
+#[test]
+fn backtrace_of_arithmetic_trap_works() {
+    let (mut store, run, wasm) = setup(true);
+    let trap = run.call(&mut store, 1).unwrap_err();
+    assert_matches!(trap.inner(), Trap::Code(TrapCode::DivisionByZero));
+    let frame = &trap.backtrace().unwrap().frames()[0];
+    // The offset points to the trapping `i32.div_u` operator.
+    assert_eq!(frame.func_name(), Some("inner"));
+    assert_eq!(wasm[frame.module_offset().unwrap()], 0x6E);
+    assert_matches!(trap.into_inner(), Trap::Code(TrapCode::DivisionByZero));
+}
@@ -0,0 +1,220 @@
+//! Tests for the `bulk-memory` Wasm proposal support of `wasmi_v1`.
+
+use super::utils::{compile, get_typed, instantiate};
+use assert_matches::assert_matches;
+use wasmi_core::{Trap, TrapCode};
+use wasmi_v1::{Config, Engine, Error, Instance, Module, Store};
+
+/// Returns a Wasm module exporting wrappers around all bulk memory instructions.
+///
+/// - The linear memory is initialized with `"ab"` by an active data segment.
+/// - The passive data segment `$hello` contains `"hello"`.
+/// - The table is initialized with `$f10` at index 0 by an active element segment.
+/// - The passive element segment `$funcs` contains `$f11` and `$f12`.
+fn setup_module(engine: &Engine) -> Module {
+    compile(
+        engine,
+        r#"
+        (module
+            (type $get (func (result i32)))
+            (memory 1)
+            (table 4 funcref)
+            (data $active (i32.const 0) "ab")
+            (data $hello "hello")
+            (elem $table (i32.const 0) $f10)
+            (elem $funcs func $f11 $f12)
+            (func $f10 (result i32) (i32.const 10))
+            (func $f11 (result i32) (i32.const 11))
+            (func $f12 (result i32) (i32.const 12))
+            (func (export "load8") (param $addr i32) (result i32)
+                (i32.load8_u (local.get $addr))
+            )
+            (func (export "memory.fill") (param $dst i32) (param $value i32) (param $len i32)
+                (memory.fill (local.get $dst) (local.get $value) (local.get $len))
+            )
+            (func (export "memory.copy") (param $dst i32) (param $src i32) (param $len i32)
+                (memory.copy (local.get $dst) (local.get $src) (local.get $len))
+            )
+            (func (export "memory.init") (param $dst i32) (param $src i32) (param $len i32)
+                (memory.init $hello (local.get $dst) (local.get $src) (local.get $len))
+            )
+            (func (export "memory.init.active") (param $dst i32) (param $src i32) (param $len i32)
+                (memory.init $active (local.get $dst) (local.get $src) (local.get $len))
+            )
+            (func (export "data.drop")
+                (data.drop $hello)
+            )
+            (func (export "call") (param $index i32) (result i32)
+                (call_indirect (type $get) (local.get $index))
+            )
+            (func (export "table.init") (param $dst i32) (param $src i32) (param $len i32)
+                (table.init $funcs (local.get $dst) (local.get $src) (local.get $len))
+            )
+            (func (export "table.copy") (param $dst i32) (param $src i32) (param $len i32)
+                (table.copy (local.get $dst) (local.get $src) (local.get $len))
+            )
+            (func (export "elem.drop")
+                (elem.drop $funcs)
+            )
+        )
+    "#,
+    )
+}
+
+/// Instantiates the Wasm module of [`setup_module`] within a new [`Store`].
+fn setup() -> (Store<()>, Instance) {
+    let engine = Engine::new(&Config::default().enable_bulk_memory(true));
+    let mut store = Store::new(&engine, ());
+    let module = setup_module(&engine);
+    let instance = instantiate(&mut store, &module);
+    (store, instance)
+}
+
+/// Returns the bytes of the linear memory in the range `start..end` using the exported `load8`.
+fn load_bytes(store: &mut Store<()>, instance: Instance, start: i32, end: i32) -> Vec<u8> {
+    let load8 = get_typed::<i32, i32>(&*store, instance, "load8");
+    (start..end)
+        .map(|addr| load8.call(&mut *store, addr).unwrap() as u8)
+        .collect()
+}
+
+#[test]
+fn memory_fill_works() {
+    let (mut store, instance) = setup();
+    let fill = get_typed::<(i32, i32, i32), ()>(&store, instance, "memory.fill");
+    fill.call(&mut store, (1, 0x1FF, 3)).unwrap();
+    // Only the lowest byte of the value is used.
+    assert_eq!(load_bytes(&mut store, instance, 0, 5), b"a\xFF\xFF\xFF\0");
+    // Filling zero bytes at the end of the linear memory is valid.
+    fill.call(&mut store, (65536, 0, 0)).unwrap();
+    assert_matches!(
+        fill.call(&mut store, (65535, 0, 2)),
+        Err(Trap::Code(TrapCode::MemoryAccessOutOfBounds))
+    );
+}
+
+#[test]
+fn memory_copy_works() {
+    let (mut store, instance) = setup();
+    let copy = get_typed::<(i32, i32, i32), ()>(&store, instance, "memory.copy");
+    copy.call(&mut store, (2, 0, 2)).unwrap();
+    assert_eq!(load_bytes(&mut store, instance, 0, 4), b"abab");
+    // Overlapping copies behave as if copied through an intermediate buffer.
+    copy.call(&mut store, (1, 0, 4)).unwrap();
+    assert_eq!(load_bytes(&mut store, instance, 0, 5), b"aabab");
+    assert_matches!(
+        copy.call(&mut store, (0, 65535, 2)),
+        Err(Trap::Code(TrapCode::MemoryAccessOutOfBounds))
+    );
+}
+
+#[test]
+fn memory_init_and_data_drop_works() {
+    let (mut store, instance) = setup();
+    let init = get_typed::<(i32, i32, i32), ()>(&store, instance, "memory.init");
+    let drop = get_typed::<(), ()>(&store, instance, "data.drop");
+    init.call(&mut store, (10, 1, 4)).unwrap();
+    assert_eq!(load_bytes(&mut store, instance, 10, 14), b"ello");
+    assert_matches!(
+        init.call(&mut store, (0, 2, 4)),
+        Err(Trap::Code(TrapCode::MemoryAccessOutOfBounds))
+    );
+    drop.call(&mut store, ()).unwrap();
+    // Dropped data segments behave like empty data segments.
+    init.call(&mut store, (0, 0, 0)).unwrap();
+    assert_matches!(
+        init.call(&mut store, (0, 0, 1)),
+        Err(Trap::Code(TrapCode::MemoryAccessOutOfBounds))
+    );
+    // Dropping a data segment multiple times is valid.
+    drop.call(&mut store, ()).unwrap();
+}
+
+#[test]
+fn active_data_segments_are_dropped() {
+    let (mut store, instance) = setup();
+    let init = get_typed::<(i32, i32, i32), ()>(&store, instance, "memory.init.active");
+    init.call(&mut store, (0, 0, 0)).unwrap();
+    assert_matches!(
+        init.call(&mut store, (0, 0, 1)),
+        Err(Trap::Code(TrapCode::MemoryAccessOutOfBounds))
+    );
+}
+
+#[test]
+fn data_drop_is_per_instance() {
+    let engine = Engine::new(&Config::default().enable_bulk_memory(true));
+    let mut store = Store::new(&engine, ());
+    let module = setup_module(&engine);
+    let instance = instantiate(&mut store, &module);
+    let other = instantiate(&mut store, &module);
+    let drop = get_typed::<(), ()>(&store, instance, "data.drop");
+    drop.call(&mut store, ()).unwrap();
+    // The data segment of the other instance has not been dropped.
+    let init = get_typed::<(i32, i32, i32), ()>(&store, other, "memory.init");
+    init.call(&mut store, (0, 0, 5)).unwrap();
+    assert_eq!(load_bytes(&mut store, other, 0, 5), b"hello");
+}
+
+#[test]
+fn table_init_and_elem_drop_works() {
+    let (mut store, instance) = setup();
+    let init = get_typed::<(i32, i32, i32), ()>(&store, instance, "table.init");
+    let drop = get_typed::<(), ()>(&store, instance, "elem.drop");
+    let call = get_typed::<i32, i32>(&store, instance, "call");
+    assert_eq!(call.call(&mut store, 0).unwrap(), 10);
+    assert_matches!(
+        call.call(&mut store, 1),
+        Err(Trap::Code(TrapCode::ElemUninitialized))
+    );
+    init.call(&mut store, (1, 0, 2)).unwrap();
+    assert_eq!(call.call(&mut store, 1).unwrap(), 11);
+    assert_eq!(call.call(&mut store, 2).unwrap(), 12);
+    assert_matches!(
+        init.call(&mut store, (3, 0, 2)),
+        Err(Trap::Code(TrapCode::TableAccessOutOfBounds))
+    );
+    drop.call(&mut store, ()).unwrap();
+    init.call(&mut store, (0, 0, 0)).unwrap();
+    assert_matches!(
+        init.call(&mut store, (0, 0, 1)),
+        Err(Trap::Code(TrapCode::TableAccessOutOfBounds))
+    );
+}
+
+#[test]
+fn table_copy_works() {
+    let (mut store, instance) = setup();
+    let init = get_typed::<(i32, i32, i32), ()>(&store, instance, "table.init");
+    let copy = get_typed::<(i32, i32, i32), ()>(&store, instance, "table.copy");
+    let call = get_typed::<i32, i32>(&store, instance, "call");
+    init.call(&mut store, (1, 0, 2)).unwrap();
+    // Overlapping copy of `[10, 11, 12, null]` to `[10, 10, 11, 12]`.
+    copy.call(&mut store, (1, 0, 3)).unwrap();
+    assert_eq!(call.call(&mut store, 0).unwrap(), 10);
+    assert_eq!(call.call(&mut store, 1).unwrap(), 10);
+    assert_eq!(call.call(&mut store, 2).unwrap(), 11);
+    assert_eq!(call.call(&mut store, 3).unwrap(), 12);
+    assert_matches!(
+        copy.call(&mut store, (2, 0, 3)),
+        Err(Trap::Code(TrapCode::TableAccessOutOfBounds))
+    );
+}
+
+#[test]
+fn bulk_memory_disabled_rejects_modules() {
+    let config = Config::default().enable_bulk_memory(false);
+    let engine = Engine::new(&config);
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (memory 1)
+            (func (param i32 i32 i32)
+                (memory.copy (local.get 0) (local.get 1) (local.get 2))
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    assert_matches!(Module::new(&engine, &wasm[..]), Err(Error::Module(_)));
+}
@@ -0,0 +1,172 @@
+//! Tests for the epoch based interruption of `wasmi_v1`.
+
+use super::utils::{compile, get_typed};
+use assert_matches::assert_matches;
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+use wasmi_core::{Trap, TrapCode};
+use wasmi_v1::{Config, Engine, Func, Linker, Store, TypedFunc};
+
+fn test_setup(epoch_interruption: bool) -> Store<()> {
+    let config = Config::default().enable_epoch_interruption(epoch_interruption);
+    let engine = Engine::new(&config);
+    Store::new(&engine, ())
+}
+
+/// Instantiates a Wasm module with a `count` function that loops `n` times
+/// and a `spin` function that loops forever.
+///
+/// Before every iteration the `count` function calls the imported `tick` host function.
+fn setup_module(store: &mut Store<()>, tick: Func) -> (TypedFunc<i32, ()>, TypedFunc<(), ()>) {
+    let module = compile(
+        store.engine(),
+        r#"
+        (module
+            (import "env" "tick" (func $tick))
+            (func (export "count") (param $n i32)
+                (block $exit
+                    (loop $continue
+                        (br_if $exit (i32.eqz (local.get $n)))
+                        (call $tick)
+                        (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                        (br $continue)
+                    )
+                )
+            )
+            (func (export "spin")
+                (loop $continue
+                    (br $continue)
+                )
+            )
+        )
+    "#,
+    );
+    let mut linker = <Linker<()>>::new();
+    linker.define("env", "tick", tick).unwrap();
+    let instance = linker
+        .instantiate(&mut *store, &module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap();
+    let count = get_typed::<i32, ()>(&*store, instance, "count");
+    let spin = get_typed::<(), ()>(&*store, instance, "spin");
+    (count, spin)
+}
+
+/// Returns a `tick` host function that increments the epoch of the [`Engine`].
+fn increment_epoch_tick(store: &mut Store<()>) -> Func {
+    let engine = store.engine().clone();
+    Func::wrap(store, move || engine.increment_epoch())
+}
+
+#[test]
+fn epoch_interruption_disabled_works() {
+    let mut store = test_setup(false);
+    let tick = increment_epoch_tick(&mut store);
+    let (count, _) = setup_module(&mut store, tick);
+    store.set_epoch_deadline(0);
+    count.call(&mut store, 10).unwrap();
+}
+
+#[test]
+fn without_deadline_is_never_interrupted() {
+    let mut store = test_setup(true);
+    let tick = increment_epoch_tick(&mut store);
+    let (count, _) = setup_module(&mut store, tick);
+    count.call(&mut store, 100).unwrap();
+}
+
+#[test]
+fn reached_deadline_traps() {
+    let mut store = test_setup(true);
+    let tick = increment_epoch_tick(&mut store);
+    let (count, _) = setup_module(&mut store, tick);
+    // Executions trap upon function entry if the deadline has already been reached.
+    store.set_epoch_deadline(0);
+    assert_matches!(
+        count.call(&mut store, 0),
+        Err(Trap::Code(TrapCode::Interrupted))
+    );
+    // Executions trap upon the loop back-edge following the deadline.
+    store.set_epoch_deadline(5);
+    assert_matches!(
+        count.call(&mut store, 10),
+        Err(Trap::Code(TrapCode::Interrupted))
+    );
+    store.set_epoch_deadline(5);
+    count.call(&mut store, 4).unwrap();
+}
+
+#[test]
+fn interrupt_from_other_thread_works() {
+    let mut store = test_setup(true);
+    let tick = Func::wrap(&mut store, || {});
+    let (_, spin) = setup_module(&mut store, tick);
+    store.set_epoch_deadline(1);
+    let engine = store.engine().clone();
+    let finished = Arc::new(AtomicBool::new(false));
+    let timer = thread::spawn({
+        let finished = finished.clone();
+        move || {
+            while !finished.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(1));
+                engine.increment_epoch();
+            }
+        }
+    });
+    let result = spin.call(&mut store, ());
+    finished.store(true, Ordering::Relaxed);
+    timer.join().unwrap();
+    assert_matches!(result, Err(Trap::Code(TrapCode::Interrupted)));
+}
+
+#[test]
+fn deadline_callback_extends_deadline() {
+    let mut store = test_setup(true);
+    let tick = increment_epoch_tick(&mut store);
+    let (count, _) = setup_module(&mut store, tick);
+    let invocations = Arc::new(AtomicU32::new(0));
+    store.epoch_deadline_callback({
+        let invocations = invocations.clone();
+        move |_ctx| {
+            invocations.fetch_add(1, Ordering::Relaxed);
+            Ok(1)
+        }
+    });
+    store.set_epoch_deadline(1);
+    count.call(&mut store, 10).unwrap();
+    assert_eq!(invocations.load(Ordering::Relaxed), 10);
+    // Restoring the default behavior traps once the deadline is reached.
+    store.epoch_deadline_trap();
+    assert_matches!(
+        count.call(&mut store, 10),
+        Err(Trap::Code(TrapCode::Interrupted))
+    );
+}
+
+#[test]
+fn deadline_callback_trap_interrupts() {
+    let mut store = test_setup(true);
+    let tick = increment_epoch_tick(&mut store);
+    let (count, _) = setup_module(&mut store, tick);
+    let invocations = Arc::new(AtomicU32::new(0));
+    store.epoch_deadline_callback({
+        let invocations = invocations.clone();
+        move |_ctx| match invocations.fetch_add(1, Ordering::Relaxed) {
+            0..=2 => Ok(1),
+            _ => Err(TrapCode::Unreachable.into()),
+        }
+    });
+    store.set_epoch_deadline(1);
+    assert_matches!(
+        count.call(&mut store, 10),
+        Err(Trap::Code(TrapCode::Unreachable))
+    );
+    assert_eq!(invocations.load(Ordering::Relaxed), 4);
+}
@@ -0,0 +1,233 @@
+//! Tests for the `exception-handling` Wasm proposal support of `wasmi_v1`.
+
+use super::utils::{compile, get_typed};
+use assert_matches::assert_matches;
+use wasmi_core::{Trap, TrapCode, ValueType};
+use wasmi_v1::{
+    errors::{LinkerError, TagError},
+    Caller,
+    Config,
+    Engine,
+    Error,
+    Exception,
+    Extern,
+    Func,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    Tag,
+    TagType,
+    Value,
+};
+
+/// The Wasm module used in the tests below.
+///
+/// The module imports the `host.tag` exception tag with an `i32` payload
+/// and the `host.check` function which throws it for negative inputs.
+const WAT: &str = r#"
+    (module
+        (import "host" "tag" (tag $host (param i32)))
+        (import "host" "check" (func $check (param i32) (result i32)))
+        (tag $e (export "e") (param i32))
+        (tag $empty)
+        (func $throw_if (param $n i32)
+            (if (i32.lt_s (local.get $n) (i32.const 0))
+                (then (throw $e (local.get $n)))
+            )
+        )
+        (func (export "catch") (param $n i32) (result i32)
+            (try (result i32)
+                (do
+                    (call $throw_if (local.get $n))
+                    (i32.const 0)
+                )
+                (catch $e
+                    (i32.add (i32.const 100))
+                )
+            )
+        )
+        (func (export "catch_all") (param $n i32) (result i32)
+            (try (result i32)
+                (do
+                    (if (local.get $n) (then (throw $empty)))
+                    (i32.const 0)
+                )
+                (catch $e (drop) (i32.const 1))
+                (catch_all (i32.const 2))
+            )
+        )
+        (func (export "rethrow") (param $n i32) (result i32)
+            (local $caught i32)
+            (try (result i32)
+                (do
+                    (try
+                        (do (call $throw_if (local.get $n)))
+                        (catch $e
+                            (local.set $caught)
+                            (rethrow 0)
+                        )
+                    )
+                    (i32.const 0)
+                )
+                (catch $e
+                    (i32.add (local.get $caught))
+                )
+            )
+        )
+        (func (export "delegate") (param $n i32) (result i32)
+            (try (result i32)
+                (do
+                    (try
+                        (do (call $throw_if (local.get $n)))
+                        (delegate 0)
+                    )
+                    (i32.const 0)
+                )
+                (catch $e)
+            )
+        )
+        (func (export "delegate_to_caller") (param $n i32) (result i32)
+            (try (result i32)
+                (do
+                    (try
+                        (do (call $throw_if (local.get $n)))
+                        (delegate 1)
+                    )
+                    (i32.const 0)
+                )
+                (catch_all (i32.const 1))
+            )
+        )
+        (func (export "catch_host") (param $n i32) (result i32)
+            (try (result i32)
+                (do (call $check (local.get $n)))
+                (catch $host (i32.const 1000) (i32.add))
+            )
+        )
+        (func (export "throw") (param $n i32)
+            (call $throw_if (local.get $n))
+        )
+    )
+"#;
+
+/// Instantiates the Wasm module in a new [`Store`] and returns the imported host tag.
+fn setup() -> (Store<()>, Instance, Tag) {
+    let config = Config::default().enable_exceptions(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let module = compile(&engine, WAT);
+    let tag = Tag::new(&mut store, TagType::new([ValueType::I32]));
+    let check = Func::wrap(&mut store, move |caller: Caller<()>, n: i32| {
+        if n < 0 {
+            let exception = Exception::new(&caller, tag, [Value::I32(n)]).unwrap();
+            return Err(Trap::from(exception));
+        }
+        Ok((n,))
+    });
+    let mut linker = <Linker<()>>::new();
+    linker.define("host", "tag", tag).unwrap();
+    linker.define("host", "check", check).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance, tag)
+}
+
+#[test]
+fn throw_and_catch() {
+    let (mut store, instance, _) = setup();
+    let catch = get_typed::<i32, i32>(&store, instance, "catch");
+    assert_eq!(catch.call(&mut store, 5).unwrap(), 0);
+    assert_eq!(catch.call(&mut store, -5).unwrap(), 95);
+    // The same instance can catch exceptions repeatedly.
+    assert_eq!(catch.call(&mut store, -1).unwrap(), 99);
+}
+
+#[test]
+fn catch_all_catches_other_tags() {
+    let (mut store, instance, _) = setup();
+    let catch_all = get_typed::<i32, i32>(&store, instance, "catch_all");
+    assert_eq!(catch_all.call(&mut store, 0).unwrap(), 0);
+    assert_eq!(catch_all.call(&mut store, 1).unwrap(), 2);
+}
+
+#[test]
+fn rethrow_and_delegate() {
+    let (mut store, instance, _) = setup();
+    let rethrow = get_typed::<i32, i32>(&store, instance, "rethrow");
+    let delegate = get_typed::<i32, i32>(&store, instance, "delegate");
+    let delegate_to_caller = get_typed::<i32, i32>(&store, instance, "delegate_to_caller");
+    assert_eq!(rethrow.call(&mut store, 3).unwrap(), 0);
+    assert_eq!(rethrow.call(&mut store, -3).unwrap(), -6);
+    assert_eq!(delegate.call(&mut store, 7).unwrap(), 0);
+    assert_eq!(delegate.call(&mut store, -7).unwrap(), -7);
+    assert_eq!(delegate_to_caller.call(&mut store, 7).unwrap(), 0);
+    let trap = delegate_to_caller.call(&mut store, -7).unwrap_err();
+    assert!(trap.is_exception());
+}
+
+#[test]
+fn host_thrown_exception_is_caught() {
+    let (mut store, instance, _) = setup();
+    let catch_host = get_typed::<i32, i32>(&store, instance, "catch_host");
+    assert_eq!(catch_host.call(&mut store, 5).unwrap(), 5);
+    assert_eq!(catch_host.call(&mut store, -5).unwrap(), 995);
+}
+
+#[test]
+fn uncaught_exception_reaches_host() {
+    let (mut store, instance, _) = setup();
+    let throw = get_typed::<i32, ()>(&store, instance, "throw");
+    let tag = instance
+        .get_export(&store, "e")
+        .and_then(Extern::into_tag)
+        .unwrap();
+    assert_eq!(tag.tag_type(&store), TagType::new([ValueType::I32]));
+    throw.call(&mut store, 1).unwrap();
+    let trap = throw.call(&mut store, -42).unwrap_err();
+    assert_matches!(trap, Trap::Exception(_));
+    let exception = Exception::from_trap(&trap).unwrap();
+    assert_eq!(exception.tag(), tag);
+    assert_matches!(exception.payload(), [Value::I32(-42)]);
+    assert!(Exception::from_trap(&Trap::from(TrapCode::Unreachable)).is_none());
+}
+
+#[test]
+fn exception_payload_is_type_checked() {
+    let (store, _, tag) = setup();
+    assert_matches!(
+        Exception::new(&store, tag, [Value::I64(1)]),
+        Err(TagError::PayloadMismatch { .. })
+    );
+    assert_matches!(
+        Exception::new(&store, tag, []),
+        Err(TagError::PayloadMismatch { .. })
+    );
+}
+
+#[test]
+fn imported_tag_type_must_match() {
+    let config = Config::default().enable_exceptions(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let module = compile(&engine, WAT);
+    let tag = Tag::new(&mut store, TagType::new([ValueType::I64]));
+    let check = Func::wrap(&mut store, |n: i32| n);
+    let mut linker = <Linker<()>>::new();
+    linker.define("host", "tag", tag).unwrap();
+    linker.define("host", "check", check).unwrap();
+    assert_matches!(
+        linker.instantiate(&mut store, &module),
+        Err(Error::Linker(LinkerError::TagTypeMismatch { .. }))
+    );
+}
+
+#[test]
+fn exceptions_disabled_rejects_modules() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(WAT).unwrap();
+    assert_matches!(Module::new(&engine, &wasm[..]), Err(Error::Module(_)));
+}
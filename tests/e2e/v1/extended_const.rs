@@ -0,0 +1,70 @@
+//! Tests for the `extended-const` Wasm proposal support of `wasmi_v1`.
+
+use super::utils::compile;
+use assert_matches::assert_matches;
+use wasmi_v1::{
+    Config,
+    Engine,
+    Error,
+    Extern,
+    Global,
+    Linker,
+    Memory,
+    MemoryType,
+    Module,
+    Mutability,
+    Store,
+    Value,
+};
+
+/// A Wasm module as produced by toolchains emitting position independent code.
+///
+/// The module places its data and globals relative to the imported `env.__memory_base`.
+const WAT: &str = r#"
+    (module
+        (import "env" "__memory_base" (global $memory_base i32))
+        (import "env" "memory" (memory 1))
+        (global (export "data_end") i32
+            (i32.add (global.get $memory_base) (i32.mul (i32.const 4) (i32.const 3)))
+        )
+        (global (export "wrapped") i64
+            (i64.sub (i64.const -9223372036854775808) (i64.const 1))
+        )
+        (data (offset (i32.add (global.get $memory_base) (i32.const 8))) "\2a")
+    )
+"#;
+
+#[test]
+fn pic_initializers() {
+    let config = Config::default().enable_extended_const(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let module = compile(&engine, WAT);
+    let memory_base = Global::new(&mut store, Value::I32(1024), Mutability::Const).unwrap();
+    let memory = Memory::new(&mut store, MemoryType::new(1, None)).unwrap();
+    let mut linker = <Linker<()>>::new();
+    linker.define("env", "__memory_base", memory_base).unwrap();
+    linker.define("env", "memory", memory).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let get_global = |name: &str| {
+        instance
+            .get_export(&store, name)
+            .and_then(Extern::into_global)
+            .unwrap()
+            .get(&store)
+    };
+    assert_matches!(get_global("data_end"), Value::I32(1036));
+    assert_matches!(get_global("wrapped"), Value::I64(i64::MAX));
+    assert_eq!(memory.data(&store)[1032], 42);
+}
+
+#[test]
+fn extended_const_disabled_rejects_modules() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(WAT).unwrap();
+    assert_matches!(Module::new(&engine, &wasm[..]), Err(Error::Module(_)));
+}
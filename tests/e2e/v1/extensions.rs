@@ -0,0 +1,122 @@
+//! Tests for the typed extensions of the `wasmi_v1` `Store`.
+
+use super::utils::{compile, get_typed};
+use wasmi_v1::{Caller, Engine, Linker, Store};
+
+/// The state of the `counter` host functions.
+#[derive(Debug, Default, PartialEq)]
+struct Counter(i32);
+
+/// The state of the `log` host function.
+#[derive(Debug, Default, PartialEq)]
+struct Log(Vec<i32>);
+
+#[test]
+fn store_extensions_work() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    assert_eq!(store.extension::<Counter>(), None);
+    assert_eq!(store.insert_extension(Counter(1)), None);
+    assert_eq!(store.insert_extension(Counter(2)), Some(Counter(1)));
+    store.extension_mut::<Counter>().unwrap().0 += 1;
+    assert_eq!(store.extension::<Counter>(), Some(&Counter(3)));
+    assert_eq!(store.extension::<Log>(), None);
+    assert_eq!(store.remove_extension::<Counter>(), Some(Counter(3)));
+    assert_eq!(store.remove_extension::<Counter>(), None);
+}
+
+#[test]
+fn host_funcs_with_independent_state_work() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    store.insert_extension(Counter::default());
+    let module = compile(
+        &engine,
+        r#"
+        (module
+            (import "host" "increment" (func $increment (param i32) (result i32)))
+            (import "host" "log" (func $log (param i32)))
+            (func (export "run") (result i32)
+                (call $log (call $increment (i32.const 1)))
+                (call $log (call $increment (i32.const 2)))
+                (call $increment (i32.const 3))
+            )
+        )
+    "#,
+    );
+    let mut linker = <Linker<()>>::new();
+    linker
+        .func_wrap("host", "increment", |mut caller: Caller<()>, delta: i32| {
+            let counter = caller.extension_mut::<Counter>().unwrap();
+            counter.0 += delta;
+            counter.0
+        })
+        .unwrap();
+    linker
+        .func_wrap("host", "log", |mut caller: Caller<()>, value: i32| {
+            // Lazily initializes the log upon first use.
+            match caller.extension_mut::<Log>() {
+                Some(log) => log.0.push(value),
+                None => {
+                    caller.insert_extension(Log(vec![value]));
+                }
+            }
+        })
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = get_typed::<(), i32>(&store, instance, "run");
+    assert_eq!(run.call(&mut store, ()).unwrap(), 6);
+    assert_eq!(store.extension::<Counter>(), Some(&Counter(6)));
+    assert_eq!(store.extension::<Log>(), Some(&Log(vec![1, 3])));
+}
+
+#[test]
+fn host_func_state_persists_across_calls() {
+    let engine = Engine::default();
+    let module = compile(
+        &engine,
+        r#"
+        (module
+            (import "host" "increment" (func $increment (param i32) (result i32)))
+            (func (export "run") (param $delta i32) (result i32)
+                (call $increment (local.get $delta))
+            )
+        )
+    "#,
+    );
+    let mut linker = <Linker<()>>::new();
+    linker
+        .func_wrap("host", "increment", |mut caller: Caller<()>, delta: i32| {
+            let counter = caller.extension_mut::<Counter>().unwrap();
+            counter.0 += delta;
+            counter.0
+        })
+        .unwrap();
+    // Both stores share the host function of the linker but own separate state.
+    let mut first = Store::new(&engine, ());
+    let mut second = Store::new(&engine, ());
+    first.insert_extension(Counter::default());
+    second.insert_extension(Counter(100));
+    let first_instance = linker
+        .instantiate(&mut first, &module)
+        .unwrap()
+        .start(&mut first)
+        .unwrap();
+    let second_instance = linker
+        .instantiate(&mut second, &module)
+        .unwrap()
+        .start(&mut second)
+        .unwrap();
+    let first_run = get_typed::<i32, i32>(&first, first_instance, "run");
+    let second_run = get_typed::<i32, i32>(&second, second_instance, "run");
+    assert_eq!(first_run.call(&mut first, 1).unwrap(), 1);
+    assert_eq!(first_run.call(&mut first, 2).unwrap(), 3);
+    assert_eq!(second_run.call(&mut second, 1).unwrap(), 101);
+    assert_eq!(first_run.call(&mut first, 3).unwrap(), 6);
+    assert_eq!(first.extension::<Counter>(), Some(&Counter(6)));
+    assert_eq!(second.extension::<Counter>(), Some(&Counter(101)));
+}
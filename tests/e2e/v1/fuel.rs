@@ -0,0 +1,152 @@
+//! Tests for the fuel metering of `wasmi_v1`.
+
+use assert_matches::assert_matches;
+use wasmi_core::{Trap, TrapCode};
+use wasmi_v1::{
+    errors::FuelError,
+    Caller,
+    Config,
+    Engine,
+    Extern,
+    Func,
+    Linker,
+    Module,
+    Store,
+    TypedFunc,
+};
+
+fn test_setup(fuel_metering: bool) -> Store<()> {
+    let config = Config::default().enable_fuel_metering(fuel_metering);
+    let engine = Engine::new(&config);
+    Store::new(&engine, ())
+}
+
+/// Instantiates a Wasm module with a `count` function that loops `n` times.
+///
+/// Before every iteration the `count` function calls the imported `tick` host function.
+fn setup_count(store: &mut Store<()>, tick: Func) -> TypedFunc<i32, ()> {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "tick" (func $tick))
+            (func (export "count") (param $n i32)
+                (block $exit
+                    (loop $continue
+                        (br_if $exit (i32.eqz (local.get $n)))
+                        (call $tick)
+                        (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                        (br $continue)
+                    )
+                )
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    let mut linker = <Linker<()>>::new();
+    linker.define("env", "tick", tick).unwrap();
+    linker
+        .instantiate(&mut *store, &module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap()
+        .get_export(&*store, "count")
+        .and_then(Extern::into_func)
+        .unwrap()
+        .typed::<i32, (), _>(&*store)
+        .unwrap()
+}
+
+#[test]
+fn fuel_metering_disabled_works() {
+    let mut store = test_setup(false);
+    assert_eq!(store.fuel_consumed(), None);
+    assert_eq!(store.remaining_fuel(), None);
+    assert_matches!(store.add_fuel(100), Err(FuelError::FuelMeteringDisabled));
+    assert_matches!(store.consume_fuel(1), Err(FuelError::FuelMeteringDisabled));
+    // Executions do not require fuel if fuel metering is disabled.
+    let tick = Func::wrap(&mut store, || {});
+    let count = setup_count(&mut store, tick);
+    count.call(&mut store, 10).unwrap();
+}
+
+#[test]
+fn store_fuel_api_works() {
+    let mut store = test_setup(true);
+    assert_eq!(store.fuel_consumed(), Some(0));
+    assert_eq!(store.remaining_fuel(), Some(0));
+    store.add_fuel(10).unwrap();
+    assert_eq!(store.remaining_fuel(), Some(10));
+    assert_eq!(store.consume_fuel(3), Ok(7));
+    assert_eq!(store.fuel_consumed(), Some(3));
+    // Consuming more fuel than remaining does not consume any fuel.
+    assert_matches!(store.consume_fuel(8), Err(FuelError::OutOfFuel));
+    assert_eq!(store.remaining_fuel(), Some(7));
+    assert_eq!(store.fuel_consumed(), Some(3));
+}
+
+#[test]
+fn fuel_consumption_is_deterministic() {
+    let mut store = test_setup(true);
+    let tick = Func::wrap(&mut store, || {});
+    let count = setup_count(&mut store, tick);
+    store.add_fuel(u64::MAX).unwrap();
+    count.call(&mut store, 10).unwrap();
+    let consumed_10 = store.fuel_consumed().unwrap();
+    count.call(&mut store, 10).unwrap();
+    assert_eq!(store.fuel_consumed().unwrap(), 2 * consumed_10);
+    count.call(&mut store, 20).unwrap();
+    let consumed_20 = store.fuel_consumed().unwrap() - 2 * consumed_10;
+    assert!(consumed_20 > consumed_10);
+    assert_eq!(
+        store.remaining_fuel().unwrap(),
+        u64::MAX - store.fuel_consumed().unwrap()
+    );
+}
+
+#[test]
+fn out_of_fuel_traps() {
+    let mut store = test_setup(true);
+    let tick = Func::wrap(&mut store, || {});
+    let count = setup_count(&mut store, tick);
+    // Executions trap immediately without any fuel.
+    assert_matches!(
+        count.call(&mut store, 1),
+        Err(Trap::Code(TrapCode::OutOfFuel))
+    );
+    store.add_fuel(100).unwrap();
+    assert_matches!(
+        count.call(&mut store, 1000),
+        Err(Trap::Code(TrapCode::OutOfFuel))
+    );
+    assert_eq!(store.fuel_consumed(), Some(100));
+    assert_eq!(store.remaining_fuel(), Some(0));
+    // Adding fuel allows for further executions.
+    store.add_fuel(1000).unwrap();
+    count.call(&mut store, 1).unwrap();
+}
+
+#[test]
+fn host_charge_fuel_works() {
+    let mut store = test_setup(true);
+    let tick = Func::wrap(&mut store, |mut caller: Caller<()>| caller.charge_fuel(100));
+    let count = setup_count(&mut store, tick);
+    store.add_fuel(1000).unwrap();
+    count.call(&mut store, 5).unwrap();
+    let consumed = store.fuel_consumed().unwrap();
+    assert!(consumed > 500);
+    // The host function runs out of fuel during the 10th iteration at the latest.
+    assert_matches!(
+        count.call(&mut store, 10),
+        Err(Trap::Code(TrapCode::OutOfFuel))
+    );
+}
+
+#[test]
+fn host_charge_fuel_without_fuel_metering_works() {
+    let mut store = test_setup(false);
+    let tick = Func::wrap(&mut store, |mut caller: Caller<()>| caller.charge_fuel(100));
+    let count = setup_count(&mut store, tick);
+    count.call(&mut store, 10).unwrap();
+}
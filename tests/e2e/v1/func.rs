@@ -1,8 +1,24 @@
 //! Tests for the `Func` type in `wasmi_v1`.
 
+use super::utils::get_func;
 use assert_matches::assert_matches;
-use wasmi_core::{Value, F32, F64};
-use wasmi_v1::{errors::FuncError, Engine, Error, Func, Store};
+use std::{sync::mpsc, thread, time::Duration};
+use wasmi_core::{Trap, TrapCode, ValueType, F32, F64};
+use wasmi_v1::{
+    errors::FuncError,
+    Caller,
+    Config,
+    Engine,
+    Error,
+    Extern,
+    Func,
+    FuncType,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    Value,
+};
 
 fn test_setup() -> Store<()> {
     let engine = Engine::default();
@@ -419,3 +435,343 @@ fn static_type_check_works() {
         Err(Error::Func(FuncError::MismatchingResults { .. }))
     );
 }
+
+/// Instantiates a Wasm module that computes the factorial by recursively
+/// calling back into the host which in turn calls back into Wasm.
+///
+/// The instance also exports a `trap` function that always traps.
+fn setup_reentrant_fac(store: &mut Store<()>) -> Instance {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "host_fac" (func $host_fac (param i32) (result i32)))
+            (func (export "fac") (param $n i32) (result i32)
+                (if (result i32) (i32.eqz (local.get $n))
+                    (then (i32.const 1))
+                    (else
+                        (i32.mul
+                            (local.get $n)
+                            (call $host_fac (i32.sub (local.get $n) (i32.const 1)))
+                        )
+                    )
+                )
+            )
+            (func (export "trap") (param i32) (result i32)
+                (unreachable)
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    // The host function calls back into the exported Wasm `fac` function.
+    let host_fac = Func::wrap(&mut *store, |mut caller: Caller<()>, n: i32| {
+        let fac = caller
+            .get_export("fac")
+            .and_then(Extern::into_func)
+            .unwrap()
+            .typed::<i32, i32, _>(&caller)
+            .unwrap();
+        fac.call(&mut caller, n).map(|result| (result,))
+    });
+    let mut linker = <Linker<()>>::new();
+    linker.define("env", "host_fac", host_fac).unwrap();
+    linker
+        .instantiate(&mut *store, &module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap()
+}
+
+#[test]
+fn static_reentrant_calls_work() {
+    let mut store = test_setup();
+    let instance = setup_reentrant_fac(&mut store);
+    let fac = get_func(&store, instance, "fac")
+        .typed::<i32, i32, _>(&store)
+        .unwrap();
+    assert_eq!(fac.call(&mut store, 0).unwrap(), 1);
+    assert_eq!(fac.call(&mut store, 1).unwrap(), 1);
+    assert_eq!(fac.call(&mut store, 5).unwrap(), 120);
+    assert_eq!(fac.call(&mut store, 10).unwrap(), 3628800);
+}
+
+#[test]
+fn dynamic_reentrant_calls_work() {
+    let mut store = test_setup();
+    let instance = setup_reentrant_fac(&mut store);
+    let fac = get_func(&store, instance, "fac");
+    let mut result = [Value::I32(0)];
+    fac.call(&mut store, &[Value::I32(5)], &mut result).unwrap();
+    assert_eq!(result, [Value::I32(120)]);
+}
+
+#[test]
+fn reentrant_call_from_host_func_works() {
+    let mut store = test_setup();
+    let instance = setup_reentrant_fac(&mut store);
+    let fac = get_func(&store, instance, "fac");
+    // A host function that calls into Wasm which calls back into the host.
+    let host_fac = Func::wrap(&mut store, move |mut caller: Caller<()>, n: i32| {
+        let fac = fac.typed::<i32, i32, _>(&caller).unwrap();
+        fac.call(&mut caller, n).map(|result| (result,))
+    });
+    let host_fac = host_fac.typed::<i32, i32, _>(&store).unwrap();
+    assert_eq!(host_fac.call(&mut store, 6).unwrap(), 720);
+}
+
+#[test]
+fn reentrant_call_trap_propagates() {
+    let mut store = test_setup();
+    let instance = setup_reentrant_fac(&mut store);
+    let fac = get_func(&store, instance, "fac");
+    let trap = get_func(&store, instance, "trap");
+    // A host function that calls into a trapping Wasm function.
+    let host_trap = Func::wrap(&mut store, move |mut caller: Caller<()>, n: i32| {
+        trap.typed::<i32, i32, _>(&caller)
+            .unwrap()
+            .call(&mut caller, n)
+            .map(|result| (result,))
+    });
+    let host_trap = host_trap.typed::<i32, i32, _>(&store).unwrap();
+    assert_matches!(
+        host_trap.call(&mut store, 1),
+        Err(Trap::Code(TrapCode::Unreachable))
+    );
+    // The engine must still be usable after the trap unwound nested executions.
+    let fac = fac.typed::<i32, i32, _>(&store).unwrap();
+    assert_eq!(fac.call(&mut store, 4).unwrap(), 24);
+}
+
+/// Creates a dynamically typed host function that adds its two `i32` parameters.
+fn setup_dynamic_add2() -> (Store<()>, Func) {
+    let mut store = test_setup();
+    let func_type = FuncType::new([ValueType::I32, ValueType::I32], [ValueType::I32]);
+    let add2 = Func::new(&mut store, func_type, |_caller, params, results| {
+        let lhs = params[0].try_into::<i32>().unwrap();
+        let rhs = params[1].try_into::<i32>().unwrap();
+        results[0] = Value::I32(lhs + rhs);
+        Ok(())
+    });
+    (store, add2)
+}
+
+#[test]
+fn dynamically_typed_host_func_works() {
+    let (mut store, add2) = setup_dynamic_add2();
+    let mut result = [Value::I32(0)];
+    add2.call(&mut store, &[Value::I32(1), Value::I32(2)], &mut result)
+        .unwrap();
+    assert_eq!(result, [Value::I32(3)]);
+    let typed_add2 = add2.typed::<(i32, i32), i32, _>(&store).unwrap();
+    assert_eq!(typed_add2.call(&mut store, (4, 5)).unwrap(), 9);
+    assert_matches!(
+        add2.typed::<(i32, i64), i32, _>(&store),
+        Err(Error::Func(FuncError::MismatchingParameters { .. }))
+    );
+}
+
+#[test]
+fn dynamically_typed_host_func_called_from_wasm_works() {
+    let mut store = test_setup();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "sum" (func $sum (param i32 i64 f64) (result i64 f64)))
+            (func (export "run") (result i64 f64)
+                (call $sum (i32.const 1) (i64.const 2) (f64.const 3.5))
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    let mut linker = <Linker<()>>::new();
+    let func_type = FuncType::new(
+        [ValueType::I32, ValueType::I64, ValueType::F64],
+        [ValueType::I64, ValueType::F64],
+    );
+    linker
+        .func_new("env", "sum", func_type, |_caller, params, results| {
+            let a = params[0].try_into::<i32>().unwrap();
+            let b = params[1].try_into::<i64>().unwrap();
+            let c = params[2].try_into::<F64>().unwrap();
+            results[0] = Value::I64(i64::from(a) + b);
+            results[1] = Value::F64(c);
+            Ok(())
+        })
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = get_func(&store, instance, "run")
+        .typed::<(), (i64, F64), _>(&store)
+        .unwrap();
+    assert_eq!(run.call(&mut store, ()).unwrap(), (3, F64::from(3.5)));
+}
+
+#[test]
+fn dynamically_typed_host_func_mismatching_results() {
+    let mut store = test_setup();
+    let func_type = FuncType::new([], [ValueType::I32]);
+    let func = Func::new(&mut store, func_type, |_caller, _params, results| {
+        results[0] = Value::I64(42);
+        Ok(())
+    });
+    let func = func.typed::<(), i32, _>(&store).unwrap();
+    match func.call(&mut store, ()) {
+        Err(Trap::Host(error)) => assert_matches!(
+            error.downcast_ref::<FuncError>(),
+            Some(FuncError::MismatchingHostFuncResults { .. })
+        ),
+        result => panic!(
+            "expected mismatching host function results but found {:?}",
+            result
+        ),
+    }
+}
+
+#[test]
+fn reentrant_recursion_overflows_stack() {
+    let mut store = test_setup();
+    let instance = setup_reentrant_fac(&mut store);
+    let fac = get_func(&store, instance, "fac")
+        .typed::<i32, i32, _>(&store)
+        .unwrap();
+    // Recursion through host functions never reaches the base case for negative inputs.
+    assert_matches!(
+        fac.call(&mut store, -1),
+        Err(Trap::Code(TrapCode::StackOverflow))
+    );
+    // The call depth of the store is restored after the nested executions trapped.
+    assert_eq!(fac.call(&mut store, 5).unwrap(), 120);
+}
+
+#[test]
+fn nested_executions_share_call_stack_limit() {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "nest" (func $nest (result i32)))
+            (func $deep (export "deep") (param $n i32) (param $nest i32) (result i32)
+                (if (result i32) (i32.eqz (local.get $n))
+                    (then
+                        (if (result i32) (local.get $nest)
+                            (then (call $nest))
+                            (else (i32.const 0))
+                        )
+                    )
+                    (else
+                        (call $deep (i32.sub (local.get $n) (i32.const 1)) (local.get $nest))
+                    )
+                )
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    let mut store = test_setup();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    // The host function starts another deep recursion nested in the calling execution.
+    let nest = Func::wrap(&mut store, |mut caller: Caller<()>| {
+        caller
+            .get_export("deep")
+            .and_then(Extern::into_func)
+            .unwrap()
+            .typed::<(i32, i32), i32, _>(&caller)
+            .unwrap()
+            .call(&mut caller, (40_000, 0))
+            .map(|result| (result,))
+    });
+    let mut linker = <Linker<()>>::new();
+    linker.define("env", "nest", nest).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let deep = get_func(&store, instance, "deep")
+        .typed::<(i32, i32), i32, _>(&store)
+        .unwrap();
+    // A single recursion is within the call stack limit.
+    assert_eq!(deep.call(&mut store, (40_000, 0)).unwrap(), 0);
+    // Both recursions together exceed the call stack limit.
+    assert_matches!(
+        deep.call(&mut store, (40_000, 1)),
+        Err(Trap::Code(TrapCode::StackOverflow))
+    );
+}
+
+/// Instantiates a Wasm module with a `spin` function that loops forever
+/// after calling the imported `started` host function and an `add` function.
+fn setup_spin(store: &mut Store<()>, started: Func) -> Instance {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "started" (func $started))
+            (func (export "spin")
+                (call $started)
+                (loop $continue
+                    (br $continue)
+                )
+            )
+            (func (export "add") (param i32 i32) (result i32)
+                (i32.add (local.get 0) (local.get 1))
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    let mut linker = <Linker<()>>::new();
+    linker.define("env", "started", started).unwrap();
+    linker
+        .instantiate(&mut *store, &module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap()
+}
+
+#[test]
+fn concurrent_executions_on_shared_engine_work() {
+    let engine = Engine::new(&Config::default().enable_epoch_interruption(true));
+    let (started_tx, started_rx) = mpsc::channel();
+    let spinning = {
+        let engine = engine.clone();
+        thread::spawn(move || {
+            let mut store = Store::new(&engine, ());
+            store.set_epoch_deadline(1);
+            let started = Func::wrap(&mut store, move || started_tx.send(()).unwrap());
+            let instance = setup_spin(&mut store, started);
+            get_func(&store, instance, "spin")
+                .typed::<(), (), _>(&store)
+                .unwrap()
+                .call(&mut store, ())
+        })
+    };
+    started_rx.recv().unwrap();
+    // The `spin` execution of the other thread must not block this execution.
+    let (result_tx, result_rx) = mpsc::channel();
+    {
+        let engine = engine.clone();
+        thread::spawn(move || {
+            let mut store = Store::new(&engine, ());
+            let started = Func::wrap(&mut store, || ());
+            let instance = setup_spin(&mut store, started);
+            let result = get_func(&store, instance, "add")
+                .typed::<(i32, i32), i32, _>(&store)
+                .unwrap()
+                .call(&mut store, (1, 2))
+                .unwrap();
+            result_tx.send(result).unwrap();
+        });
+    }
+    assert_eq!(result_rx.recv_timeout(Duration::from_secs(10)), Ok(3));
+    engine.increment_epoch();
+    assert_matches!(
+        spinning.join().unwrap().map_err(|trap| trap.code()),
+        Err(Some(TrapCode::Interrupted))
+    );
+}
@@ -0,0 +1,253 @@
+//! Tests for the resource limiter of the `wasmi_v1` `Store`.
+
+use super::utils::{compile, get_typed};
+use assert_matches::assert_matches;
+use wasmi_core::{memory_units::Pages, Trap, TrapCode, ValueType};
+use wasmi_v1::{
+    errors::{GlobalError, InstantiationError, MemoryError, TableError},
+    Config,
+    Engine,
+    Error,
+    Global,
+    Instance,
+    Linker,
+    Memory,
+    MemoryType,
+    Mutability,
+    RefType,
+    Store,
+    StoreLimits,
+    StoreLimitsBuilder,
+    Table,
+    TableType,
+    Value,
+};
+
+/// The size of a single linear memory page in bytes.
+const PAGE_SIZE: usize = 65536;
+
+/// Creates a new [`Store`] that is limited by the given `limits`.
+fn test_setup(limits: StoreLimits) -> Store<StoreLimits> {
+    let engine = Engine::new(&Config::default().enable_reference_types(true));
+    let mut store = Store::new(&engine, limits);
+    store.limiter(|limits| limits);
+    store
+}
+
+/// Instantiates the Wasm module given in the text format `wat`.
+fn instantiate(store: &mut Store<StoreLimits>, wat: &str) -> Result<Instance, Error> {
+    let module = compile(store.engine(), wat);
+    let mut linker = <Linker<StoreLimits>>::new();
+    linker
+        .instantiate(&mut *store, &module)?
+        .ensure_no_start(&mut *store)
+        .map_err(Into::into)
+}
+
+/// A Wasm module exporting functions to grow its linear memory and table.
+const GROW_WAT: &str = r#"
+    (module
+        (memory (export "memory") 1)
+        (table 1 funcref)
+        (func (export "memory_grow") (param $delta i32) (result i32)
+            (memory.grow (local.get $delta))
+        )
+        (func (export "table_grow") (param $delta i32) (result i32)
+            (table.grow (ref.null func) (local.get $delta))
+        )
+    )
+"#;
+
+/// Calls the exported `func` of `instance` with the `delta` parameter.
+fn call_grow(
+    store: &mut Store<StoreLimits>,
+    instance: Instance,
+    func: &str,
+    delta: i32,
+) -> Result<i32, Trap> {
+    get_typed::<i32, i32>(&*store, instance, func).call(&mut *store, delta)
+}
+
+#[test]
+fn denied_growth_returns_minus_one() {
+    let limits = StoreLimitsBuilder::new()
+        .memory_size(2 * PAGE_SIZE)
+        .table_elements(3)
+        .build();
+    let mut store = test_setup(limits);
+    let instance = instantiate(&mut store, GROW_WAT).unwrap();
+    assert_eq!(
+        call_grow(&mut store, instance, "memory_grow", 1).unwrap(),
+        1
+    );
+    assert_eq!(
+        call_grow(&mut store, instance, "memory_grow", 1).unwrap(),
+        -1
+    );
+    assert_eq!(
+        call_grow(&mut store, instance, "memory_grow", 0).unwrap(),
+        2
+    );
+    assert_eq!(call_grow(&mut store, instance, "table_grow", 2).unwrap(), 1);
+    assert_eq!(
+        call_grow(&mut store, instance, "table_grow", 1).unwrap(),
+        -1
+    );
+    assert_eq!(call_grow(&mut store, instance, "table_grow", 0).unwrap(), 3);
+}
+
+#[test]
+fn denied_growth_traps_if_configured() {
+    let limits = StoreLimitsBuilder::new()
+        .memory_size(PAGE_SIZE)
+        .table_elements(1)
+        .trap_on_grow_failure(true)
+        .build();
+    let mut store = test_setup(limits);
+    let instance = instantiate(&mut store, GROW_WAT).unwrap();
+    assert_matches!(
+        call_grow(&mut store, instance, "memory_grow", 1),
+        Err(Trap::Code(TrapCode::GrowthOperationLimited))
+    );
+    assert_matches!(
+        call_grow(&mut store, instance, "table_grow", 1),
+        Err(Trap::Code(TrapCode::GrowthOperationLimited))
+    );
+}
+
+#[test]
+fn denied_host_growth_fails() {
+    let limits = StoreLimitsBuilder::new()
+        .memory_size(2 * PAGE_SIZE)
+        .table_elements(1)
+        .build();
+    let mut store = test_setup(limits);
+    let memory_type = MemoryType::new(3, None);
+    assert_matches!(
+        Memory::new(&mut store, memory_type),
+        Err(MemoryError::ResourceLimitExceeded)
+    );
+    let memory = Memory::new(&mut store, MemoryType::new(1, None)).unwrap();
+    assert_eq!(memory.grow(&mut store, Pages(1)).unwrap(), Pages(1));
+    assert_matches!(
+        memory.grow(&mut store, Pages(1)),
+        Err(MemoryError::ResourceLimitExceeded)
+    );
+    let table_type = TableType::new(RefType::FuncRef, 1, None);
+    let table = Table::new(&mut store, table_type).unwrap();
+    assert_matches!(
+        table.grow(&mut store, 1, Value::default(ValueType::FuncRef)),
+        Err(TableError::ResourceLimitExceeded)
+    );
+    assert_eq!(table.len(&store), 1);
+    let table_type = TableType::new(RefType::FuncRef, 2, None);
+    assert_matches!(
+        Table::new(&mut store, table_type),
+        Err(TableError::ResourceLimitExceeded)
+    );
+}
+
+#[test]
+fn host_created_entities_respect_count_limits() {
+    let limits = StoreLimitsBuilder::new()
+        .tables(1)
+        .memories(1)
+        .globals(1)
+        .build();
+    let mut store = test_setup(limits);
+    let table_type = TableType::new(RefType::FuncRef, 1, None);
+    Table::new(&mut store, table_type).unwrap();
+    assert_matches!(
+        Table::new(&mut store, table_type),
+        Err(TableError::ResourceLimitExceeded)
+    );
+    let memory_type = MemoryType::new(1, None);
+    Memory::new(&mut store, memory_type).unwrap();
+    assert_matches!(
+        Memory::new(&mut store, memory_type),
+        Err(MemoryError::ResourceLimitExceeded)
+    );
+    Global::new(&mut store, Value::I32(0), Mutability::Const).unwrap();
+    assert_matches!(
+        Global::new(&mut store, Value::I32(1), Mutability::Const),
+        Err(GlobalError::ResourceLimitExceeded)
+    );
+    // Entities created by the host count towards the limits of instantiations.
+    assert_matches!(
+        instantiate(&mut store, "(module (global i32 (i32.const 0)))"),
+        Err(Error::Instantiation(
+            InstantiationError::ResourceLimitExceeded {
+                resource: "globals"
+            }
+        ))
+    );
+}
+
+#[test]
+fn instantiation_limits_work() {
+    let limits = StoreLimitsBuilder::new().instances(1).build();
+    let mut store = test_setup(limits);
+    instantiate(&mut store, "(module)").unwrap();
+    assert_matches!(
+        instantiate(&mut store, "(module)"),
+        Err(Error::Instantiation(
+            InstantiationError::ResourceLimitExceeded {
+                resource: "instances"
+            }
+        ))
+    );
+
+    let limits = StoreLimitsBuilder::new().memories(1).tables(1).build();
+    let mut store = test_setup(limits);
+    instantiate(&mut store, "(module (memory 1) (table 1 funcref))").unwrap();
+    assert_matches!(
+        instantiate(&mut store, "(module (memory 1))"),
+        Err(Error::Instantiation(
+            InstantiationError::ResourceLimitExceeded {
+                resource: "memories"
+            }
+        ))
+    );
+    assert_matches!(
+        instantiate(&mut store, "(module (table 1 funcref))"),
+        Err(Error::Instantiation(
+            InstantiationError::ResourceLimitExceeded { resource: "tables" }
+        ))
+    );
+
+    let limits = StoreLimitsBuilder::new().globals(1).build();
+    let mut store = test_setup(limits);
+    assert_matches!(
+        instantiate(
+            &mut store,
+            "(module (global i32 (i32.const 0)) (global i32 (i32.const 1)))"
+        ),
+        Err(Error::Instantiation(
+            InstantiationError::ResourceLimitExceeded {
+                resource: "globals"
+            }
+        ))
+    );
+}
+
+#[test]
+fn instantiation_respects_size_limits() {
+    let limits = StoreLimitsBuilder::new()
+        .memory_size(PAGE_SIZE)
+        .table_elements(10)
+        .build();
+    let mut store = test_setup(limits);
+    assert_matches!(
+        instantiate(&mut store, "(module (memory 2))"),
+        Err(Error::Instantiation(InstantiationError::Memory(
+            MemoryError::ResourceLimitExceeded
+        )))
+    );
+    assert_matches!(
+        instantiate(&mut store, "(module (table 11 funcref))"),
+        Err(Error::Instantiation(InstantiationError::Table(
+            TableError::ResourceLimitExceeded
+        )))
+    );
+    instantiate(&mut store, "(module (memory 1) (table 10 funcref))").unwrap();
+}
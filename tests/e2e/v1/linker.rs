@@ -0,0 +1,152 @@
+//! Tests for the `wasmi_v1` `Linker` API.
+
+use super::utils::{compile, get_func, get_typed};
+use assert_matches::assert_matches;
+use wasmi_v1::{errors::LinkerError, Caller, Engine, Error, Extern, Func, Instance, Linker, Store};
+
+/// Instantiates the Wasm module given as `wat` in `store` using `linker`.
+fn instantiate(linker: &mut Linker<i32>, store: &mut Store<i32>, wat: &str) -> Instance {
+    let module = compile(store.engine(), wat);
+    linker
+        .instantiate(&mut *store, &module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap()
+}
+
+/// Calls the exported `run` function of the `instance` and returns its result.
+fn call_run(store: &mut Store<i32>, instance: Instance) -> i32 {
+    get_typed::<(), i32>(&*store, instance, "run")
+        .call(store, ())
+        .unwrap()
+}
+
+const WAT_IMPORT_GET: &str = r#"
+    (module
+        (import "host" "get" (func $get (result i32)))
+        (func (export "run") (result i32)
+            (call $get)
+        )
+    )
+"#;
+
+#[test]
+fn func_wrap_works_in_multiple_stores() {
+    let engine = Engine::default();
+    let mut linker = <Linker<i32>>::new();
+    linker
+        .func_wrap("host", "get", |caller: Caller<i32>| *caller.host_data())
+        .unwrap();
+    let mut store_a = Store::new(&engine, 10);
+    let mut store_b = Store::new(&engine, 20);
+    let instance_a = instantiate(&mut linker, &mut store_a, WAT_IMPORT_GET);
+    let instance_b = instantiate(&mut linker, &mut store_b, WAT_IMPORT_GET);
+    assert_eq!(call_run(&mut store_a, instance_a), 10);
+    assert_eq!(call_run(&mut store_b, instance_b), 20);
+    // Host functions defined via `func_wrap` are not extern values.
+    assert!(linker.resolve("host", Some("get")).is_none());
+}
+
+#[test]
+fn instance_registers_exports() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, 0);
+    let mut linker = <Linker<i32>>::new();
+    let provider = instantiate(
+        &mut linker,
+        &mut store,
+        r#"
+            (module
+                (func (export "get") (result i32)
+                    (i32.const 42)
+                )
+                (memory (export "mem") 1)
+            )
+        "#,
+    );
+    linker.instance(&store, "host", provider).unwrap();
+    assert_matches!(linker.resolve("host", Some("mem")), Some(Extern::Memory(_)));
+    let consumer = instantiate(&mut linker, &mut store, WAT_IMPORT_GET);
+    assert_eq!(call_run(&mut store, consumer), 42);
+}
+
+#[test]
+fn alias_works() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, 0);
+    let mut linker = <Linker<i32>>::new();
+    let get = Func::wrap(&mut store, || 1_i32);
+    linker.define("env", "one", get).unwrap();
+    linker.func_wrap("env", "two", || 2_i32).unwrap();
+    linker.alias("env", "two", "host", "get").unwrap();
+    let instance = instantiate(&mut linker, &mut store, WAT_IMPORT_GET);
+    assert_eq!(call_run(&mut store, instance), 2);
+    assert_matches!(
+        linker.alias("env", "one", "host", "get"),
+        Err(LinkerError::DuplicateDefinition { .. })
+    );
+    assert_matches!(
+        linker.alias("env", "three", "host", "three"),
+        Err(LinkerError::CannotFindDefinitionForAlias { .. })
+    );
+}
+
+#[test]
+fn shadowing_works() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, 0);
+    let mut linker = <Linker<i32>>::new();
+    linker.func_wrap("host", "get", || 1_i32).unwrap();
+    assert_matches!(
+        linker.func_wrap("host", "get", || 2_i32),
+        Err(LinkerError::DuplicateHostFuncDefinition { .. })
+    );
+    linker.allow_shadowing(true);
+    linker.func_wrap("host", "get", || 2_i32).unwrap();
+    let instance = instantiate(&mut linker, &mut store, WAT_IMPORT_GET);
+    assert_eq!(call_run(&mut store, instance), 2);
+    let get = Func::wrap(&mut store, || 3_i32);
+    linker.define("host", "get", get).unwrap();
+    let instance = instantiate(&mut linker, &mut store, WAT_IMPORT_GET);
+    assert_eq!(call_run(&mut store, instance), 3);
+}
+
+#[test]
+fn func_wrap_is_allocated_once_per_store() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, 0);
+    let mut linker = <Linker<i32>>::new();
+    linker.func_wrap("host", "get", || 1_i32).unwrap();
+    let wat = r#"
+        (module
+            (import "host" "get" (func $get (result i32)))
+            (export "get" (func $get))
+        )
+    "#;
+    let instance_a = instantiate(&mut linker, &mut store, wat);
+    let instance_b = instantiate(&mut linker, &mut store, wat);
+    assert_eq!(
+        get_func(&store, instance_a, "get"),
+        get_func(&store, instance_b, "get")
+    );
+}
+
+#[test]
+fn func_wrap_type_mismatch_fails() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, 0);
+    let mut linker = <Linker<i32>>::new();
+    linker.func_wrap("host", "get", || 1_i32).unwrap();
+    let module = compile(
+        &engine,
+        r#"
+            (module
+                (import "host" "get" (func $get (result i64)))
+            )
+        "#,
+    );
+    assert_matches!(
+        linker.instantiate(&mut store, &module),
+        Err(Error::Linker(LinkerError::FuncTypeMismatch { .. }))
+    );
+}
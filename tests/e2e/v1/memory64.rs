@@ -0,0 +1,159 @@
+//! Tests for the `memory64` Wasm proposal support of `wasmi_v1`.
+
+use super::utils::{compile, get_typed};
+use assert_matches::assert_matches;
+use wasmi_core::{Trap, TrapCode};
+use wasmi_v1::{Config, Engine, Error, Instance, Linker, Memory, MemoryType, Module, Store};
+
+/// Returns the Wasm module used in the tests below.
+///
+/// The module imports the 64-bit `host.memory` linear memory.
+fn module(engine: &Engine) -> Module {
+    compile(
+        engine,
+        r#"
+        (module
+            (import "host" "memory" (memory i64 1 4))
+            (data (i64.const 8) "memory64")
+            (func (export "load") (param $ptr i64) (result i64)
+                (i64.load offset=1 (local.get $ptr))
+            )
+            (func (export "load_far") (param $ptr i64) (result i32)
+                (i32.load8_u offset=0x100000000 (local.get $ptr))
+            )
+            (func (export "store") (param $ptr i64) (param $value i64)
+                (i64.store offset=1 (local.get $ptr) (local.get $value))
+            )
+            (func (export "fill") (param $dst i64) (param $value i32) (param $len i64)
+                (memory.fill (local.get $dst) (local.get $value) (local.get $len))
+            )
+            (func (export "copy") (param $dst i64) (param $src i64) (param $len i64)
+                (memory.copy (local.get $dst) (local.get $src) (local.get $len))
+            )
+            (func (export "size") (result i64)
+                (memory.size)
+            )
+            (func (export "grow") (param $delta i64) (result i64)
+                (memory.grow (local.get $delta))
+            )
+        )
+    "#,
+    )
+}
+
+/// Instantiates the Wasm module of the tests with an imported 64-bit linear memory.
+fn setup() -> (Store<()>, Instance, Memory) {
+    let config = Config::default()
+        .enable_bulk_memory(true)
+        .enable_memory64(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let module = module(&engine);
+    let memory = Memory::new(&mut store, MemoryType::new64(1, Some(4))).unwrap();
+    let mut linker = <Linker<()>>::new();
+    linker.define("host", "memory", memory).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance, memory)
+}
+
+#[test]
+fn loads_and_stores_use_i64_addresses() {
+    let (mut store, instance, memory) = setup();
+    let load = get_typed::<i64, i64>(&store, instance, "load");
+    let load_far = get_typed::<i64, i32>(&store, instance, "load_far");
+    let store_i64 = get_typed::<(i64, i64), ()>(&store, instance, "store");
+    assert_eq!(&memory.data(&store)[8..16], b"memory64");
+    assert_eq!(
+        load.call(&mut store, 7).unwrap(),
+        i64::from_le_bytes(*b"memory64")
+    );
+    store_i64.call(&mut store, (99, -42)).unwrap();
+    assert_eq!(load.call(&mut store, 99).unwrap(), -42);
+    assert_eq!(&memory.data(&store)[100..108], &(-42_i64).to_le_bytes());
+    // Addresses beyond the `u32` range are out of bounds but do not wrap around.
+    assert_matches!(
+        load.call(&mut store, 1 << 32),
+        Err(Trap::Code(TrapCode::MemoryAccessOutOfBounds))
+    );
+    assert_matches!(
+        load.call(&mut store, -1),
+        Err(Trap::Code(TrapCode::MemoryAccessOutOfBounds))
+    );
+    // Offsets beyond the `u32` range are out of bounds but do not wrap around.
+    assert_matches!(
+        load_far.call(&mut store, 0),
+        Err(Trap::Code(TrapCode::MemoryAccessOutOfBounds))
+    );
+    assert_matches!(
+        store_i64.call(&mut store, (65528, 0)),
+        Err(Trap::Code(TrapCode::MemoryAccessOutOfBounds))
+    );
+}
+
+#[test]
+fn bulk_operations_use_i64_operands() {
+    let (mut store, instance, memory) = setup();
+    let fill = get_typed::<(i64, i32, i64), ()>(&store, instance, "fill");
+    let copy = get_typed::<(i64, i64, i64), ()>(&store, instance, "copy");
+    fill.call(&mut store, (100, i32::from(b'!'), 3)).unwrap();
+    assert_eq!(&memory.data(&store)[99..104], b"\0!!!\0");
+    copy.call(&mut store, (200, 8, 8)).unwrap();
+    assert_eq!(&memory.data(&store)[200..208], b"memory64");
+    assert_matches!(
+        fill.call(&mut store, (0, 0, 1 << 32)),
+        Err(Trap::Code(TrapCode::MemoryAccessOutOfBounds))
+    );
+    assert_matches!(
+        copy.call(&mut store, (1 << 32, 0, 0)),
+        Err(Trap::Code(TrapCode::MemoryAccessOutOfBounds))
+    );
+}
+
+#[test]
+fn size_and_grow_use_i64_operands() {
+    let (mut store, instance, memory) = setup();
+    let size = get_typed::<(), i64>(&store, instance, "size");
+    let grow = get_typed::<i64, i64>(&store, instance, "grow");
+    assert_eq!(size.call(&mut store, ()).unwrap(), 1);
+    assert_eq!(grow.call(&mut store, 2).unwrap(), 1);
+    assert_eq!(size.call(&mut store, ()).unwrap(), 3);
+    assert_eq!(memory.data(&store).len(), 3 * 65536);
+    // Growing beyond the maximum of the linear memory fails with `-1`.
+    assert_eq!(grow.call(&mut store, 2).unwrap(), -1);
+    assert_eq!(grow.call(&mut store, 1 << 40).unwrap(), -1);
+    assert_eq!(grow.call(&mut store, -1).unwrap(), -1);
+    assert_eq!(size.call(&mut store, ()).unwrap(), 3);
+}
+
+#[test]
+fn memory_type_mismatch_fails_instantiation() {
+    let config = Config::default()
+        .enable_bulk_memory(true)
+        .enable_memory64(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let module = module(&engine);
+    let memory = Memory::new(&mut store, MemoryType::new(1, Some(4))).unwrap();
+    assert!(!memory.memory_type(&store).is_64());
+    let mut linker = <Linker<()>>::new();
+    linker.define("host", "memory", memory).unwrap();
+    assert!(linker.instantiate(&mut store, &module).is_err());
+}
+
+#[test]
+fn memory64_disabled_rejects_modules() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (memory i64 1)
+        )
+    "#,
+    )
+    .unwrap();
+    assert_matches!(Module::new(&engine, &wasm[..]), Err(Error::Module(_)));
+}
@@ -0,0 +1,161 @@
+//! Tests for the initialization of `wasmi_v1` linear memories by their data segments.
+//!
+//! # Note
+//!
+//! With the `virtual_memory` crate feature on 64-bit Linux the linear memories
+//! are initialized by mapping a prepared memory image copy-on-write.
+
+use super::utils::compile;
+use wasmi_core::memory_units::Pages;
+use wasmi_v1::{
+    Config,
+    Engine,
+    Extern,
+    Global,
+    Instance,
+    Linker,
+    Memory,
+    MemoryType,
+    Module,
+    Mutability,
+    Store,
+    Value,
+};
+
+/// Instantiates the `module` with the `linker` and returns its exported `memory`.
+fn instantiate(store: &mut Store<()>, linker: &mut Linker<()>, module: &Module) -> Memory {
+    linker
+        .instantiate(&mut *store, module)
+        .unwrap()
+        .start(&mut *store)
+        .map(|instance: Instance| instance.get_export(&*store, "memory"))
+        .unwrap()
+        .and_then(Extern::into_memory)
+        .unwrap()
+}
+
+/// Asserts that instances of the `module` get their own linear memory
+/// whose first `len` bytes have been initialized to `0x2A`.
+fn assert_initialized_separately(module: &Module, len: usize) {
+    let mut store = Store::new(module.engine(), ());
+    let mut linker = <Linker<()>>::new();
+    let first = instantiate(&mut store, &mut linker, module);
+    let second = instantiate(&mut store, &mut linker, module);
+    for memory in [first, second] {
+        let data = memory.data(&store);
+        assert!(data[..len].iter().all(|byte| *byte == 0x2A));
+        assert!(data[len..].iter().all(|byte| *byte == 0x00));
+    }
+    first.data_mut(&mut store)[0] = 0x00;
+    assert_eq!(second.data(&store)[0], 0x2A);
+}
+
+/// Returns the Wasm module whose exported linear memory is initialized by `len` data segments.
+fn data_segments_wat(len: usize) -> String {
+    let segments = (0..len)
+        .map(|offset| format!("(data (i32.const {}) \"\\2A\")", offset))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("(module (memory (export \"memory\") 1) {})", segments)
+}
+
+#[test]
+fn instances_do_not_share_initialized_bytes() {
+    let module = compile(
+        &Engine::new(&Config::default().enable_bulk_memory(true)),
+        r#"
+        (module
+            (memory (export "memory") 2 3)
+            (data (i32.const 0) "abc")
+            (data (i32.const 1) "XY")
+            (data (i32.const 65546) "z")
+            (data "passive")
+        )
+    "#,
+    );
+    let mut store = Store::new(module.engine(), ());
+    let mut linker = <Linker<()>>::new();
+    let first = instantiate(&mut store, &mut linker, &module);
+    let second = instantiate(&mut store, &mut linker, &module);
+    for memory in [first, second] {
+        let data = memory.data(&store);
+        assert_eq!(data.len(), 2 * 65536);
+        assert_eq!(&data[0..4], b"aXY\0");
+        assert_eq!(&data[65545..65548], b"\0z\0");
+    }
+    first.data_mut(&mut store)[0..3].copy_from_slice(b"new");
+    first.grow(&mut store, Pages(1)).unwrap();
+    assert_eq!(&first.data(&store)[0..3], b"new");
+    assert!(first.data(&store)[2 * 65536..]
+        .iter()
+        .all(|byte| *byte == 0));
+    // Writing to one instance leaves other and later instances untouched.
+    let third = instantiate(&mut store, &mut linker, &module);
+    assert_eq!(&second.data(&store)[0..3], b"aXY");
+    assert_eq!(&third.data(&store)[0..3], b"aXY");
+}
+
+#[test]
+fn data_segment_offsets_may_depend_on_imports() {
+    let module = compile(
+        &Engine::default(),
+        r#"
+        (module
+            (import "host" "base" (global $base i32))
+            (memory (export "memory") 1)
+            (data (i32.const 0) "const")
+            (data (global.get $base) "imported")
+        )
+    "#,
+    );
+    let mut store = Store::new(module.engine(), ());
+    let mut linker = <Linker<()>>::new();
+    let base = Global::new(&mut store, Value::I32(100), Mutability::Const).unwrap();
+    linker.define("host", "base", base).unwrap();
+    let memory = instantiate(&mut store, &mut linker, &module);
+    assert_eq!(&memory.data(&store)[0..5], b"const");
+    assert_eq!(&memory.data(&store)[100..108], b"imported");
+}
+
+#[test]
+fn own_memory_follows_imported_memory() {
+    let module = compile(
+        &Engine::new(
+            &Config::default()
+                .enable_bulk_memory(true)
+                .enable_multi_memory(true),
+        ),
+        r#"
+        (module
+            (import "host" "memory" (memory $imported 1))
+            (memory $own (export "memory") 2)
+            (data (memory $own) (i32.const 65536) "own")
+            (data (memory $imported) (i32.const 0) "imported")
+        )
+    "#,
+    );
+    let mut store = Store::new(module.engine(), ());
+    let mut linker = <Linker<()>>::new();
+    let imported = Memory::new(&mut store, MemoryType::new(1, None)).unwrap();
+    linker.define("host", "memory", imported).unwrap();
+    let own = instantiate(&mut store, &mut linker, &module);
+    assert_eq!(own.current_pages(&store).0, 2);
+    assert_eq!(&own.data(&store)[65536..65539], b"own");
+    assert_eq!(&imported.data(&store)[0..8], b"imported");
+}
+
+#[test]
+fn many_data_segments_initialize_memory() {
+    // More data segments than the limit for memory images are copied instead.
+    let len = 1025;
+    let module = compile(&Engine::default(), &data_segments_wat(len));
+    assert_initialized_separately(&module, len);
+}
+
+#[test]
+fn disabled_memory_images_initialize_memory() {
+    let len = 3;
+    let config = Config::default().enable_memory_images(false);
+    let module = compile(&Engine::new(&config), &data_segments_wat(len));
+    assert_initialized_separately(&module, len);
+}
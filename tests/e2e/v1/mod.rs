@@ -1 +1,27 @@
+mod backtrace;
+mod bulk_memory;
+mod epoch;
+mod exceptions;
+mod extended_const;
+mod extensions;
+mod fuel;
 mod func;
+mod limits;
+mod linker;
+mod memory64;
+mod memory_image;
+mod multi_memory;
+mod peephole;
+mod reference_types;
+mod remove_instance;
+mod resumable;
+mod serialize;
+#[cfg(feature = "v1-simd")]
+mod simd;
+mod snapshot;
+mod tail_call;
+#[cfg(feature = "v1-threads")]
+mod threads;
+mod utils;
+#[cfg(feature = "v1-virtual-memory")]
+mod virtual_memory;
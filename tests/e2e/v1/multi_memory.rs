@@ -0,0 +1,179 @@
+//! Tests for the `multi-memory` Wasm proposal support of `wasmi_v1`.
+
+use super::utils::{compile, get_typed};
+use assert_matches::assert_matches;
+use wasmi_core::{Trap, TrapCode};
+use wasmi_v1::{
+    Config,
+    Engine,
+    Error,
+    Extern,
+    Instance,
+    Linker,
+    Memory,
+    MemoryType,
+    Module,
+    Store,
+};
+
+/// Instantiates a Wasm module with its own linear memory and an imported `host.io` linear memory.
+///
+/// The imported `host.io` linear memory is the default linear memory at index 0
+/// and the own linear memory of the Wasm module is at index 1.
+fn setup() -> (Store<()>, Instance, Memory) {
+    let config = Config::default()
+        .enable_bulk_memory(true)
+        .enable_multi_memory(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let module = compile(
+        &engine,
+        r#"
+        (module
+            (import "host" "io" (memory $io 1 4))
+            (memory $own (export "own") 1)
+            (data (memory $own) (i32.const 0) "own")
+            (data (memory $io) (i32.const 8) "io")
+            (data $greeting "hello")
+            (func (export "load_own") (param $ptr i32) (result i32)
+                (i32.load8_u (memory $own) (local.get $ptr))
+            )
+            (func (export "load_io") (param $ptr i32) (result i32)
+                (i32.load8_u (memory $io) (local.get $ptr))
+            )
+            (func (export "store_io") (param $ptr i32) (param $value i64)
+                (i64.store (memory $io) offset=1 (local.get $ptr) (local.get $value))
+            )
+            (func (export "load_io_i64") (param $ptr i32) (result i64)
+                (i64.load (memory $io) offset=1 (local.get $ptr))
+            )
+            (func (export "copy_io_to_own") (param $dst i32) (param $src i32) (param $len i32)
+                (memory.copy (memory $own) (memory $io)
+                    (local.get $dst) (local.get $src) (local.get $len)
+                )
+            )
+            (func (export "fill_io") (param $dst i32) (param $value i32) (param $len i32)
+                (memory.fill (memory $io) (local.get $dst) (local.get $value) (local.get $len))
+            )
+            (func (export "init_io") (param $dst i32)
+                (memory.init $greeting (memory $io) (local.get $dst) (i32.const 0) (i32.const 5))
+            )
+            (func (export "size_own") (result i32)
+                (memory.size $own)
+            )
+            (func (export "size_io") (result i32)
+                (memory.size $io)
+            )
+            (func (export "grow_io") (param $delta i32) (result i32)
+                (memory.grow (memory $io) (local.get $delta))
+            )
+        )
+    "#,
+    );
+    let io = Memory::new(&mut store, MemoryType::new(1, Some(4))).unwrap();
+    let mut linker = <Linker<()>>::new();
+    linker.define("host", "io", io).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance, io)
+}
+
+/// Returns the exported linear memory `name` of the `instance`.
+fn get_memory(store: &Store<()>, instance: Instance, name: &str) -> Memory {
+    instance
+        .get_export(store, name)
+        .and_then(Extern::into_memory)
+        .unwrap()
+}
+
+#[test]
+fn data_segments_initialize_their_memory() {
+    let (store, instance, io) = setup();
+    let own = get_memory(&store, instance, "own");
+    assert_eq!(&own.data(&store)[0..3], b"own");
+    assert_eq!(&own.data(&store)[8..10], &[0x00, 0x00]);
+    assert_eq!(&io.data(&store)[0..3], &[0x00, 0x00, 0x00]);
+    assert_eq!(&io.data(&store)[8..10], b"io");
+}
+
+#[test]
+fn loads_and_stores_access_their_memory() {
+    let (mut store, instance, io) = setup();
+    let load_own = get_typed::<i32, i32>(&store, instance, "load_own");
+    let load_io = get_typed::<i32, i32>(&store, instance, "load_io");
+    let store_io = get_typed::<(i32, i64), ()>(&store, instance, "store_io");
+    let load_io_i64 = get_typed::<i32, i64>(&store, instance, "load_io_i64");
+    assert_eq!(load_own.call(&mut store, 0).unwrap(), i32::from(b'o'));
+    assert_eq!(load_io.call(&mut store, 0).unwrap(), 0);
+    assert_eq!(load_io.call(&mut store, 8).unwrap(), i32::from(b'i'));
+    // The host can write to the I/O memory and Wasm reads it back.
+    io.write(&mut store, 100, &42_i64.to_le_bytes()).unwrap();
+    assert_eq!(load_io_i64.call(&mut store, 99).unwrap(), 42);
+    // Wasm writes to the I/O memory and the host reads it back.
+    store_io.call(&mut store, (199, -1)).unwrap();
+    let mut buffer = [0x00_u8; 8];
+    io.read(&store, 200, &mut buffer).unwrap();
+    assert_eq!(i64::from_le_bytes(buffer), -1);
+    assert_eq!(load_own.call(&mut store, 200).unwrap(), 0);
+    assert_matches!(
+        store_io.call(&mut store, (65528, 0)),
+        Err(Trap::Code(TrapCode::MemoryAccessOutOfBounds))
+    );
+}
+
+#[test]
+fn bulk_operations_access_their_memory() {
+    let (mut store, instance, io) = setup();
+    let own = get_memory(&store, instance, "own");
+    let copy_io_to_own = get_typed::<(i32, i32, i32), ()>(&store, instance, "copy_io_to_own");
+    let fill_io = get_typed::<(i32, i32, i32), ()>(&store, instance, "fill_io");
+    let init_io = get_typed::<i32, ()>(&store, instance, "init_io");
+    init_io.call(&mut store, 16).unwrap();
+    assert_eq!(&io.data(&store)[16..21], b"hello");
+    fill_io.call(&mut store, (21, i32::from(b'!'), 2)).unwrap();
+    assert_eq!(&io.data(&store)[16..23], b"hello!!");
+    copy_io_to_own.call(&mut store, (100, 16, 7)).unwrap();
+    assert_eq!(&own.data(&store)[100..107], b"hello!!");
+    assert_eq!(&own.data(&store)[16..23], &[0x00; 7]);
+    assert_matches!(
+        copy_io_to_own.call(&mut store, (0, 65535, 2)),
+        Err(Trap::Code(TrapCode::MemoryAccessOutOfBounds))
+    );
+    assert_matches!(
+        fill_io.call(&mut store, (65535, 0, 2)),
+        Err(Trap::Code(TrapCode::MemoryAccessOutOfBounds))
+    );
+}
+
+#[test]
+fn size_and_grow_access_their_memory() {
+    let (mut store, instance, io) = setup();
+    let size_own = get_typed::<(), i32>(&store, instance, "size_own");
+    let size_io = get_typed::<(), i32>(&store, instance, "size_io");
+    let grow_io = get_typed::<i32, i32>(&store, instance, "grow_io");
+    assert_eq!(grow_io.call(&mut store, 2).unwrap(), 1);
+    assert_eq!(size_io.call(&mut store, ()).unwrap(), 3);
+    assert_eq!(size_own.call(&mut store, ()).unwrap(), 1);
+    assert_eq!(io.data(&store).len(), 3 * 65536);
+    // Growing beyond the maximum of the I/O memory fails.
+    assert_eq!(grow_io.call(&mut store, 2).unwrap(), -1);
+    assert_eq!(size_io.call(&mut store, ()).unwrap(), 3);
+}
+
+#[test]
+fn multi_memory_disabled_rejects_modules() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (memory 1)
+            (memory 1)
+        )
+    "#,
+    )
+    .unwrap();
+    assert_matches!(Module::new(&engine, &wasm[..]), Err(Error::Module(_)));
+}
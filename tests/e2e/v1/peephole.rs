@@ -0,0 +1,195 @@
+//! Tests for the peephole optimizations of the `wasmi_v1` bytecode.
+//!
+//! # Note
+//!
+//! Every test runs the same Wasm module with and without bytecode
+//! optimizations which must not affect the results of the executions.
+
+use super::utils::{call, compile, instantiate, try_call};
+use assert_matches::assert_matches;
+use wasmi_core::{Trap, TrapCode};
+use wasmi_v1::{Config, Engine, Instance, Module, Store};
+
+const WAT: &str = r#"
+    (module
+        (memory 1)
+        (data (i32.const 0) "\01\02\03\04\05\06\07\08")
+        (global $g (mut i32) (i32.const 0))
+        (func (export "sum_bytes") (param $ptr i32) (param $len i32) (result i32)
+            (local $end i32)
+            (local $sum i32)
+            (local.set $end (i32.add (local.get $ptr) (local.get $len)))
+            (block $exit
+                (br_if $exit (i32.ge_u (local.get $ptr) (local.get $end)))
+                (loop $continue
+                    (local.set $sum
+                        (i32.add (local.get $sum) (i32.load8_u (local.get $ptr)))
+                    )
+                    (local.set $ptr (i32.add (local.get $ptr) (i32.const 1)))
+                    (br_if $continue (i32.lt_u (local.get $ptr) (local.get $end)))
+                )
+            )
+            local.get $sum
+        )
+        (func (export "load_i32") (param $ptr i32) (result i32)
+            (i32.load offset=1 (local.get $ptr))
+        )
+        (func (export "load_i64") (param $ptr i32) (result i64)
+            (i64.load (local.get $ptr))
+        )
+        (func (export "min_s") (param $a i32) (param $b i32) (result i32)
+            (if (result i32) (i32.lt_s (local.get $a) (local.get $b))
+                (then (local.get $a))
+                (else (local.get $b))
+            )
+        )
+        (func (export "is_zero") (param $x i64) (result i32)
+            (block $zero
+                (br_if $zero (i64.eqz (local.get $x)))
+                (return (i32.const 0))
+            )
+            (i32.const 1)
+        )
+        (func (export "sub_imm") (param $x i64) (result i64)
+            (i64.sub (local.get $x) (i64.const -3))
+        )
+        (func (export "folded") (result i32)
+            (i32.eqz (i32.shl (i32.const 1) (i32.const 32)))
+        )
+        (func (export "dropped") (param $x i32) (result i32)
+            (drop (global.get $g))
+            (drop (local.tee $x (i32.add (local.get $x) (i32.const 1))))
+            local.get $x
+        )
+        (func (export "br_into_add") (param $x i32) (param $c i32) (result i32)
+            ;; The `br_if` targets the `i32.add` which must not be fused
+            ;; with the `i32.const 2` that precedes it on the fallthrough path.
+            local.get $x
+            (block $b (result i32)
+                i32.const 1
+                local.get $c
+                br_if $b
+                drop
+                i32.const 2
+            )
+            i32.add
+        )
+        (func (export "count_down") (param $n i32) (result i32)
+            ;; The fused `i32.const 1; i32.sub` is the header of the loop.
+            local.get $n
+            (loop $continue (param i32) (result i32)
+                i32.const 1
+                i32.sub
+                local.tee $n
+                local.get $n
+                i32.const 5
+                i32.gt_s
+                br_if $continue
+            )
+        )
+    )
+"#;
+
+/// Asserts the results of the exported functions of the `instance`.
+fn assert_results(store: &mut Store<()>, instance: Instance) {
+    assert_eq!(call::<_, i32>(store, instance, "sum_bytes", (2, 4)), 18);
+    assert_eq!(call::<_, i32>(store, instance, "sum_bytes", (2, 0)), 0);
+    assert_eq!(call::<_, i32>(store, instance, "load_i32", 1), 0x0605_0403);
+    assert_eq!(
+        call::<_, i64>(store, instance, "load_i64", 0),
+        0x0807_0605_0403_0201
+    );
+    assert_eq!(call::<_, i32>(store, instance, "min_s", (-1, 1)), -1);
+    assert_eq!(call::<_, i32>(store, instance, "min_s", (3, 2)), 2);
+    assert_eq!(call::<_, i32>(store, instance, "is_zero", 0_i64), 1);
+    assert_eq!(call::<_, i32>(store, instance, "is_zero", 5_i64), 0);
+    assert_eq!(call::<_, i64>(store, instance, "sub_imm", 4_i64), 7);
+    assert_eq!(
+        call::<_, i64>(store, instance, "sub_imm", i64::MAX),
+        i64::MIN + 2
+    );
+    assert_eq!(call::<_, i32>(store, instance, "folded", ()), 0);
+    assert_eq!(call::<_, i32>(store, instance, "br_into_add", (10, 1)), 11);
+    assert_eq!(call::<_, i32>(store, instance, "br_into_add", (10, 0)), 12);
+    assert_eq!(call::<_, i32>(store, instance, "count_down", 10), 5);
+    assert_eq!(call::<_, i32>(store, instance, "count_down", 3), 2);
+    assert_eq!(call::<_, i32>(store, instance, "dropped", 41), 42);
+    assert_matches!(
+        try_call::<_, i32>(store, instance, "load_i32", 65533),
+        Err(Trap::Code(TrapCode::MemoryAccessOutOfBounds))
+    );
+}
+
+/// Returns the [`Config`] with bytecode optimizations enabled.
+fn optimized_config() -> Config {
+    Config::default().enable_bytecode_optimizations(true)
+}
+
+#[test]
+fn optimized_bytecode_works() {
+    let engine = Engine::new(&optimized_config());
+    let module = compile(&engine, WAT);
+    let mut store = Store::new(&engine, ());
+    let instance = instantiate(&mut store, &module);
+    assert_results(&mut store, instance);
+}
+
+#[test]
+fn unoptimized_bytecode_works() {
+    let engine = Engine::new(&Config::default().enable_bytecode_optimizations(false));
+    let module = compile(&engine, WAT);
+    let mut store = Store::new(&engine, ());
+    let instance = instantiate(&mut store, &module);
+    assert_results(&mut store, instance);
+}
+
+#[test]
+fn deserialized_optimized_bytecode_works() {
+    let engine = Engine::new(&optimized_config());
+    let bytes = compile(&engine, WAT).serialize().unwrap();
+    let module = unsafe { Module::deserialize(&engine, &bytes) }.unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = instantiate(&mut store, &module);
+    assert_results(&mut store, instance);
+}
+
+#[test]
+fn bytecode_optimizations_do_not_affect_fuel() {
+    let fuel_consumed = |config: Config| {
+        let engine = Engine::new(&config.enable_fuel_metering(true));
+        let module = compile(&engine, WAT);
+        let mut store = Store::new(&engine, ());
+        store.add_fuel(u64::MAX).unwrap();
+        let instance = instantiate(&mut store, &module);
+        assert_eq!(
+            call::<_, i32>(&mut store, instance, "sum_bytes", (0, 8)),
+            36
+        );
+        store.fuel_consumed().unwrap()
+    };
+    let optimized = fuel_consumed(optimized_config());
+    let unoptimized = fuel_consumed(Config::default().enable_bytecode_optimizations(false));
+    assert_eq!(optimized, unoptimized);
+}
+
+#[test]
+fn unoptimized_bytecode_consumes_fuel_per_operator() {
+    let engine = Engine::new(
+        &Config::default()
+            .enable_bytecode_optimizations(false)
+            .enable_fuel_metering(true),
+    );
+    let module = compile(&engine, WAT);
+    let mut store = Store::new(&engine, ());
+    store.add_fuel(u64::MAX).unwrap();
+    let instance = instantiate(&mut store, &module);
+    assert_eq!(
+        call::<_, i32>(&mut store, instance, "sum_bytes", (0, 8)),
+        36
+    );
+    // Without bytecode optimizations every Wasm operator other than the
+    // structured `block`, `loop` and `end` is translated into one instruction
+    // which consumes one unit of fuel: 8 operators before the loop, 13 per
+    // loop iteration and `local.get` followed by the implicit return.
+    assert_eq!(store.fuel_consumed(), Some(8 + 8 * 13 + 2));
+}
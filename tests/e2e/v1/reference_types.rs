@@ -0,0 +1,235 @@
+//! Tests for the `reference-types` Wasm proposal support of `wasmi_v1`.
+
+use super::utils::{compile, get_typed};
+use assert_matches::assert_matches;
+use wasmi_core::{Trap, TrapCode};
+use wasmi_v1::{
+    errors::TableError,
+    Caller,
+    Config,
+    Engine,
+    Error,
+    Extern,
+    ExternRef,
+    Func,
+    FuncRef,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    Value,
+};
+
+/// Returns a Wasm module exporting wrappers around all reference types instructions.
+///
+/// - The `funcref` table `$funcs` is initialized with `$f10` at index 0.
+/// - The `externref` table `$externs` is initially filled with `null` references.
+fn setup_module(engine: &Engine) -> Module {
+    compile(
+        engine,
+        r#"
+        (module
+            (import "host" "len" (func $len (param externref) (result i32)))
+            (type $get (func (result i32)))
+            (table $funcs (export "funcs") 4 funcref)
+            (table $externs (export "externs") 2 10 externref)
+            (elem (table $funcs) (i32.const 0) func $f10)
+            (elem declare func $f11)
+            (func $f10 (result i32) (i32.const 10))
+            (func $f11 (result i32) (i32.const 11))
+            (func (export "len") (param $ref externref) (result i32)
+                (call $len (local.get $ref))
+            )
+            (func (export "call") (param $index i32) (result i32)
+                (call_indirect $funcs (type $get) (local.get $index))
+            )
+            (func (export "set.f11") (param $index i32)
+                (table.set $funcs (local.get $index) (ref.func $f11))
+            )
+            (func (export "externs.get") (param $index i32) (result externref)
+                (table.get $externs (local.get $index))
+            )
+            (func (export "externs.set") (param $index i32) (param $ref externref)
+                (table.set $externs (local.get $index) (local.get $ref))
+            )
+            (func (export "externs.size") (result i32)
+                (table.size $externs)
+            )
+            (func (export "externs.grow") (param $ref externref) (param $delta i32) (result i32)
+                (table.grow $externs (local.get $ref) (local.get $delta))
+            )
+            (func (export "externs.fill") (param $dst i32) (param $ref externref) (param $len i32)
+                (table.fill $externs (local.get $dst) (local.get $ref) (local.get $len))
+            )
+            (func (export "is_null") (param $ref externref) (result i32)
+                (ref.is_null (local.get $ref))
+            )
+            (func (export "null") (result externref)
+                (ref.null extern)
+            )
+            (func (export "select") (param $a externref) (param $b externref) (param $c i32) (result externref)
+                (select (result externref) (local.get $a) (local.get $b) (local.get $c))
+            )
+        )
+    "#,
+    )
+}
+
+/// Instantiates the Wasm module of [`setup_module`] within a new [`Store`].
+///
+/// The imported `host.len` returns the length of the referenced `String` or `-1` if `null`.
+fn setup() -> (Store<()>, Instance) {
+    let config = Config::default()
+        .enable_bulk_memory(true)
+        .enable_reference_types(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let module = setup_module(&engine);
+    let len = Func::wrap(&mut store, |caller: Caller<()>, string: ExternRef| {
+        string
+            .data(&caller)
+            .map(|data| data.downcast_ref::<String>().unwrap().len() as i32)
+            .unwrap_or(-1)
+    });
+    let mut linker = <Linker<()>>::new();
+    linker.define("host", "len", len).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+#[test]
+fn externref_roundtrip_works() {
+    let (mut store, instance) = setup();
+    let len = get_typed::<ExternRef, i32>(&store, instance, "len");
+    let string = ExternRef::new(&mut store, String::from("hello"));
+    assert_eq!(len.call(&mut store, string).unwrap(), 5);
+    assert_eq!(len.call(&mut store, ExternRef::null()).unwrap(), -1);
+    let set = get_typed::<(i32, ExternRef), ()>(&store, instance, "externs.set");
+    let get = get_typed::<i32, ExternRef>(&store, instance, "externs.get");
+    set.call(&mut store, (1, string)).unwrap();
+    let result = get.call(&mut store, 1).unwrap();
+    assert_eq!(result, string);
+    assert_eq!(
+        result
+            .data(&store)
+            .unwrap()
+            .downcast_ref::<String>()
+            .unwrap(),
+        "hello"
+    );
+    assert!(get.call(&mut store, 0).unwrap().is_null());
+    assert_matches!(
+        get.call(&mut store, 2),
+        Err(Trap::Code(TrapCode::TableAccessOutOfBounds))
+    );
+}
+
+#[test]
+fn ref_func_and_call_indirect_works() {
+    let (mut store, instance) = setup();
+    let call = get_typed::<i32, i32>(&store, instance, "call");
+    let set = get_typed::<i32, ()>(&store, instance, "set.f11");
+    assert_eq!(call.call(&mut store, 0).unwrap(), 10);
+    assert_matches!(
+        call.call(&mut store, 1),
+        Err(Trap::Code(TrapCode::ElemUninitialized))
+    );
+    set.call(&mut store, 1).unwrap();
+    assert_eq!(call.call(&mut store, 1).unwrap(), 11);
+    assert_matches!(
+        set.call(&mut store, 4),
+        Err(Trap::Code(TrapCode::TableAccessOutOfBounds))
+    );
+}
+
+#[test]
+fn table_grow_size_and_fill_works() {
+    let (mut store, instance) = setup();
+    let size = get_typed::<(), i32>(&store, instance, "externs.size");
+    let grow = get_typed::<(ExternRef, i32), i32>(&store, instance, "externs.grow");
+    let fill = get_typed::<(i32, ExternRef, i32), ()>(&store, instance, "externs.fill");
+    let get = get_typed::<i32, ExternRef>(&store, instance, "externs.get");
+    let value = ExternRef::new(&mut store, String::from("wasmi"));
+    assert_eq!(size.call(&mut store, ()).unwrap(), 2);
+    // Returns the previous size of the table upon success.
+    assert_eq!(grow.call(&mut store, (value, 3)).unwrap(), 2);
+    assert_eq!(size.call(&mut store, ()).unwrap(), 5);
+    assert!(get.call(&mut store, 1).unwrap().is_null());
+    assert_eq!(get.call(&mut store, 4).unwrap(), value);
+    // Returns `-1` upon failure without growing the table.
+    assert_eq!(grow.call(&mut store, (value, 6)).unwrap(), -1);
+    assert_eq!(size.call(&mut store, ()).unwrap(), 5);
+    fill.call(&mut store, (0, ExternRef::null(), 5)).unwrap();
+    assert!(get.call(&mut store, 4).unwrap().is_null());
+    assert_matches!(
+        fill.call(&mut store, (4, value, 2)),
+        Err(Trap::Code(TrapCode::TableAccessOutOfBounds))
+    );
+}
+
+#[test]
+fn ref_is_null_and_select_works() {
+    let (mut store, instance) = setup();
+    let is_null = get_typed::<ExternRef, i32>(&store, instance, "is_null");
+    let null = get_typed::<(), ExternRef>(&store, instance, "null");
+    let select = get_typed::<(ExternRef, ExternRef, i32), ExternRef>(&store, instance, "select");
+    let a = ExternRef::new(&mut store, 1_i32);
+    let b = ExternRef::new(&mut store, 2_i32);
+    assert_eq!(is_null.call(&mut store, ExternRef::null()).unwrap(), 1);
+    assert_eq!(is_null.call(&mut store, a).unwrap(), 0);
+    assert!(null.call(&mut store, ()).unwrap().is_null());
+    assert_eq!(select.call(&mut store, (a, b, 1)).unwrap(), a);
+    assert_eq!(select.call(&mut store, (a, b, 0)).unwrap(), b);
+}
+
+#[test]
+fn host_table_access_works() {
+    let (mut store, instance) = setup();
+    let funcs = instance
+        .get_export(&store, "funcs")
+        .and_then(Extern::into_table)
+        .unwrap();
+    let externs = instance
+        .get_export(&store, "externs")
+        .and_then(Extern::into_table)
+        .unwrap();
+    let f10 = match funcs.get(&store, 0).unwrap() {
+        Value::FuncRef(funcref) => *funcref.func().unwrap(),
+        value => panic!("expected a funcref but found: {:?}", value),
+    };
+    funcs
+        .set(&mut store, 3, Value::FuncRef(FuncRef::new(f10)))
+        .unwrap();
+    let call = get_typed::<i32, i32>(&store, instance, "call");
+    assert_eq!(call.call(&mut store, 3).unwrap(), 10);
+    // Tables reject values that do not match their element type.
+    assert_matches!(
+        externs.set(&mut store, 0, Value::FuncRef(FuncRef::new(f10))),
+        Err(TableError::ElementTypeMismatch { .. })
+    );
+    assert_matches!(
+        externs.grow(&mut store, 1, Value::I32(0)),
+        Err(TableError::ElementTypeMismatch { .. })
+    );
+}
+
+#[test]
+fn reference_types_disabled_rejects_modules() {
+    let config = Config::default().enable_reference_types(false);
+    let engine = Engine::new(&config);
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (func (param externref) (result i32)
+                (ref.is_null (local.get 0))
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    assert_matches!(Module::new(&engine, &wasm[..]), Err(Error::Module(_)));
+}
@@ -0,0 +1,258 @@
+//! Tests for removing instances from the `wasmi_v1` `Store`.
+
+use super::utils::{compile, get_func, get_typed};
+use assert_matches::assert_matches;
+use wasmi_core::{Trap, TrapCode};
+use wasmi_v1::{
+    errors::RemoveInstanceError,
+    Engine,
+    Extern,
+    Instance,
+    Linker,
+    Memory,
+    Store,
+    StoreLimits,
+    StoreLimitsBuilder,
+};
+
+/// Instantiates the Wasm module given in the text format `wat`.
+///
+/// The `memory` is provided as the `env.memory` import.
+fn instantiate(store: &mut Store<StoreLimits>, wat: &str, memory: Option<Memory>) -> Instance {
+    let module = compile(store.engine(), wat);
+    let mut linker = <Linker<StoreLimits>>::new();
+    if let Some(memory) = memory {
+        linker.define("env", "memory", memory).unwrap();
+    }
+    linker
+        .instantiate(&mut *store, &module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap()
+}
+
+/// Calls the exported `load` function of `instance` with the `address` parameter.
+fn load(store: &mut Store<StoreLimits>, instance: Instance, address: i32) -> i32 {
+    get_typed::<i32, i32>(&*store, instance, "load")
+        .call(&mut *store, address)
+        .unwrap()
+}
+
+#[test]
+fn remove_instance_frees_resources() {
+    let engine = Engine::default();
+    let limits = StoreLimitsBuilder::new().instances(1).memories(1).build();
+    let mut store = Store::new(&engine, limits);
+    store.limiter(|limits| limits);
+    let wat = r#"
+        (module
+            (memory 1)
+            (data (i32.const 0) "\2A")
+            (func (export "load") (param $address i32) (result i32)
+                (i32.load8_u (local.get $address))
+            )
+        )
+    "#;
+    // The store limits allow only a single instance at a time. Without freeing
+    // the resources of removed instances the repeated instantiations would fail.
+    for _ in 0..10 {
+        let instance = instantiate(&mut store, wat, None);
+        assert_eq!(load(&mut store, instance, 0), 42);
+        store.remove_instance(instance).unwrap();
+    }
+}
+
+#[test]
+fn remove_instance_keeps_imports() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, StoreLimits::default());
+    let exporter = instantiate(
+        &mut store,
+        r#"
+        (module
+            (memory (export "memory") 1)
+            (data (i32.const 0) "\2A")
+            (func (export "load") (param $address i32) (result i32)
+                (i32.load8_u (local.get $address))
+            )
+        )
+    "#,
+        None,
+    );
+    let memory = exporter
+        .get_export(&store, "memory")
+        .and_then(Extern::into_memory)
+        .unwrap();
+    let importer = instantiate(
+        &mut store,
+        r#"
+        (module
+            (import "env" "memory" (memory 1))
+            (func (export "load") (param $address i32) (result i32)
+                (i32.load8_u (local.get $address))
+            )
+        )
+    "#,
+        Some(memory),
+    );
+    assert_eq!(load(&mut store, importer, 0), 42);
+    store.remove_instance(importer).unwrap();
+    // The imported linear memory is still owned by the exporting instance.
+    assert_eq!(memory.data(&store)[0], 42);
+    assert_eq!(load(&mut store, exporter, 0), 42);
+}
+
+#[test]
+fn remove_instance_with_imported_memory_fails() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, StoreLimits::default());
+    let exporter = instantiate(
+        &mut store,
+        r#"
+        (module
+            (memory (export "memory") 1)
+            (data (i32.const 0) "\2A")
+        )
+    "#,
+        None,
+    );
+    let memory = exporter
+        .get_export(&store, "memory")
+        .and_then(Extern::into_memory)
+        .unwrap();
+    let importer = instantiate(
+        &mut store,
+        r#"
+        (module
+            (import "env" "memory" (memory 1))
+            (func (export "load") (param $address i32) (result i32)
+                (i32.load8_u (local.get $address))
+            )
+        )
+    "#,
+        Some(memory),
+    );
+    // The linear memory of the exporting instance is still in use by the importer.
+    assert_eq!(
+        store.remove_instance(exporter),
+        Err(RemoveInstanceError::ImportedByOtherInstance)
+    );
+    assert_eq!(load(&mut store, importer, 0), 42);
+    assert!(exporter.get_export(&store, "memory").is_some());
+    // Once the importing instance is removed the exporter can be removed as well.
+    store.remove_instance(importer).unwrap();
+    store.remove_instance(exporter).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn removed_instance_cannot_be_used() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, StoreLimits::default());
+    let instance = instantiate(&mut store, "(module (memory (export \"memory\") 1))", None);
+    store.remove_instance(instance).unwrap();
+    instance.get_export(&store, "memory");
+}
+
+#[test]
+#[should_panic]
+fn removed_instance_does_not_alias_reused_slot() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, StoreLimits::default());
+    let wat = "(module (memory (export \"memory\") 1))";
+    let removed = instantiate(&mut store, wat, None);
+    store.remove_instance(removed).unwrap();
+    // The new instance reuses the slot of the removed instance.
+    let instance = instantiate(&mut store, wat, None);
+    assert!(instance.get_export(&store, "memory").is_some());
+    removed.get_export(&store, "memory");
+}
+
+#[test]
+fn calling_removed_funcs_traps() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, StoreLimits::default());
+    let callee = instantiate(
+        &mut store,
+        r#"
+        (module
+            (func (export "f") (result i32)
+                (i32.const 42)
+            )
+        )
+    "#,
+        None,
+    );
+    let f = get_func(&store, callee, "f");
+    let module = compile(
+        &engine,
+        r#"
+        (module
+            (import "env" "f" (func $f (result i32)))
+            (table 1 funcref)
+            (elem (i32.const 0) $f)
+            (func (export "call") (result i32)
+                (call $f)
+            )
+            (func (export "call_indirect") (result i32)
+                (call_indirect (result i32) (i32.const 0))
+            )
+        )
+    "#,
+    );
+    let mut linker = <Linker<StoreLimits>>::new();
+    linker.define("env", "f", f).unwrap();
+    let caller = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let call = get_typed::<(), i32>(&store, caller, "call");
+    let call_indirect = get_typed::<(), i32>(&store, caller, "call_indirect");
+    assert_eq!(call.call(&mut store, ()).unwrap(), 42);
+    assert_eq!(call_indirect.call(&mut store, ()).unwrap(), 42);
+    // The function is still referenced by the table and imports of the caller.
+    store.remove_instance(callee).unwrap();
+    assert_matches!(
+        call.call(&mut store, ()),
+        Err(Trap::Code(TrapCode::DanglingFunc))
+    );
+    assert_matches!(
+        call_indirect.call(&mut store, ()),
+        Err(Trap::Code(TrapCode::DanglingFunc))
+    );
+    // A new instance reusing the slots of the removed functions is not called.
+    instantiate(
+        &mut store,
+        "(module (func (export \"f\") (result i32) (i32.const 0)))",
+        None,
+    );
+    assert_matches!(
+        call_indirect.call(&mut store, ()),
+        Err(Trap::Code(TrapCode::DanglingFunc))
+    );
+}
+
+#[test]
+fn dropped_modules_free_func_bodies() {
+    let engine = Engine::default();
+    let wat = r#"
+        (module
+            (func (export "load") (param $address i32) (result i32)
+                (local.get $address)
+            )
+        )
+    "#;
+    // Instances of dropped modules keep the function bodies of their
+    // modules alive until they have been removed from the store.
+    let mut store = Store::new(&engine, StoreLimits::default());
+    let instance = instantiate(&mut store, wat, None);
+    for _ in 0..10 {
+        let module = compile(&engine, wat);
+        drop(module);
+    }
+    assert_eq!(load(&mut store, instance, 5), 5);
+    store.remove_instance(instance).unwrap();
+    let instance = instantiate(&mut store, wat, None);
+    assert_eq!(load(&mut store, instance, 7), 7);
+}
@@ -0,0 +1,212 @@
+//! Tests for resumable function calls in `wasmi_v1`.
+
+use assert_matches::assert_matches;
+use core::fmt;
+use wasmi_core::{HostError, Trap, TrapCode};
+use wasmi_v1::{
+    errors::FuncError,
+    Engine,
+    Error,
+    Extern,
+    Func,
+    Linker,
+    Module,
+    ResumableCall,
+    Store,
+    Value,
+};
+
+/// The host error that suspends the execution of a resumable call.
+#[derive(Debug)]
+struct Suspend(i32);
+
+impl fmt::Display for Suspend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "suspended with {}", self.0)
+    }
+}
+
+impl HostError for Suspend {}
+
+/// Instantiates a Wasm module with a `run` function computing `fetch(n) + fetch(n + 1)`.
+///
+/// The imported `fetch` host function doubles its input if it is non-negative
+/// and otherwise traps with the [`Suspend`] host error.
+///
+/// Returns the `fetch` host function and the exported `run` and `trap` Wasm functions.
+fn setup() -> (Store<()>, Func, Func, Func) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "fetch" (func $fetch (param i32) (result i32)))
+            (func (export "run") (param $n i32) (result i32)
+                (i32.add
+                    (call $fetch (local.get $n))
+                    (call $fetch (i32.add (local.get $n) (i32.const 1)))
+                )
+            )
+            (func (export "trap") (param $n i32) (result i32)
+                (drop (call $fetch (local.get $n)))
+                (unreachable)
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    let fetch = Func::wrap(&mut store, |n: i32| {
+        if n < 0 {
+            return Err(Trap::from(Suspend(n)));
+        }
+        Ok((n * 2,))
+    });
+    let mut linker = <Linker<()>>::new();
+    linker.define("env", "fetch", fetch).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance
+        .get_export(&store, "run")
+        .and_then(Extern::into_func)
+        .unwrap();
+    let trap = instance
+        .get_export(&store, "trap")
+        .and_then(Extern::into_func)
+        .unwrap();
+    (store, fetch, run, trap)
+}
+
+/// Asserts that `trap` is the [`Suspend`] host error with the given `value`.
+fn assert_suspend(trap: &Trap, value: i32) {
+    match trap {
+        Trap::Host(host_error) => {
+            let suspend = host_error.downcast_ref::<Suspend>().unwrap();
+            assert_eq!(suspend.0, value);
+        }
+        _ => panic!("expected host trap but found: {:?}", trap),
+    }
+}
+
+#[test]
+fn resumable_call_without_host_trap_finishes() {
+    let (mut store, _fetch, run, _trap) = setup();
+    let mut result = [Value::I32(0)];
+    let call = run
+        .call_resumable(&mut store, &[Value::I32(1)], &mut result)
+        .unwrap();
+    assert_matches!(call, ResumableCall::Finished);
+    assert_eq!(result, [Value::I32(2 + 4)]);
+}
+
+#[test]
+fn resumable_call_works() {
+    let (mut store, fetch, run, _trap) = setup();
+    let mut result = [Value::I32(0)];
+    // Both calls to `fetch` trap and suspend the execution.
+    let invocation = match run
+        .call_resumable(&mut store, &[Value::I32(-2)], &mut result)
+        .unwrap()
+    {
+        ResumableCall::Resumable(invocation) => invocation,
+        ResumableCall::Finished => panic!("expected the call to be suspended"),
+    };
+    assert_eq!(
+        invocation.host_func().func_type(&store),
+        fetch.func_type(&store)
+    );
+    assert_suspend(invocation.host_trap(), -2);
+    let invocation = match invocation
+        .resume(&mut store, &[Value::I32(10)], &mut result)
+        .unwrap()
+    {
+        ResumableCall::Resumable(invocation) => invocation,
+        ResumableCall::Finished => panic!("expected the call to be suspended again"),
+    };
+    assert_suspend(invocation.host_trap(), -1);
+    let call = invocation
+        .resume(&mut store, &[Value::I32(20)], &mut result)
+        .unwrap();
+    assert_matches!(call, ResumableCall::Finished);
+    assert_eq!(result, [Value::I32(30)]);
+    // The engine can still be used normally after the resumed call finished.
+    run.call(&mut store, &[Value::I32(1)], &mut result).unwrap();
+    assert_eq!(result, [Value::I32(6)]);
+}
+
+#[test]
+fn resumable_call_partially_suspends() {
+    let (mut store, _fetch, run, _trap) = setup();
+    let mut result = [Value::I32(0)];
+    // Only the first call to `fetch` traps since `-1 + 1` is non-negative.
+    let invocation = match run
+        .call_resumable(&mut store, &[Value::I32(-1)], &mut result)
+        .unwrap()
+    {
+        ResumableCall::Resumable(invocation) => invocation,
+        ResumableCall::Finished => panic!("expected the call to be suspended"),
+    };
+    assert_suspend(invocation.host_trap(), -1);
+    let call = invocation
+        .resume(&mut store, &[Value::I32(5)], &mut result)
+        .unwrap();
+    assert_matches!(call, ResumableCall::Finished);
+    assert_eq!(result, [Value::I32(5)]);
+}
+
+#[test]
+fn resumable_call_wasm_trap_is_not_resumable() {
+    let (mut store, _fetch, _run, trap) = setup();
+    let mut result = [Value::I32(0)];
+    assert_matches!(
+        trap.call_resumable(&mut store, &[Value::I32(1)], &mut result),
+        Err(Error::Trap(Trap::Code(TrapCode::Unreachable)))
+    );
+    // A resumed execution can also end in a trap that cannot be resumed.
+    let invocation = match trap
+        .call_resumable(&mut store, &[Value::I32(-1)], &mut result)
+        .unwrap()
+    {
+        ResumableCall::Resumable(invocation) => invocation,
+        ResumableCall::Finished => panic!("expected the call to be suspended"),
+    };
+    assert_matches!(
+        invocation.resume(&mut store, &[Value::I32(0)], &mut result),
+        Err(Error::Trap(Trap::Code(TrapCode::Unreachable)))
+    );
+}
+
+#[test]
+fn resumable_call_of_host_func_is_not_resumable() {
+    let (mut store, fetch, _run, _trap) = setup();
+    let mut result = [Value::I32(0)];
+    assert_matches!(
+        fetch.call_resumable(&mut store, &[Value::I32(-1)], &mut result),
+        Err(Error::Trap(Trap::Host(_)))
+    );
+    let call = fetch
+        .call_resumable(&mut store, &[Value::I32(3)], &mut result)
+        .unwrap();
+    assert_matches!(call, ResumableCall::Finished);
+    assert_eq!(result, [Value::I32(6)]);
+}
+
+#[test]
+fn resume_type_check_works() {
+    let (mut store, _fetch, run, _trap) = setup();
+    let mut result = [Value::I32(0)];
+    let invocation = match run
+        .call_resumable(&mut store, &[Value::I32(-1)], &mut result)
+        .unwrap()
+    {
+        ResumableCall::Resumable(invocation) => invocation,
+        ResumableCall::Finished => panic!("expected the call to be suspended"),
+    };
+    assert_matches!(
+        invocation.resume(&mut store, &[Value::I64(0)], &mut result),
+        Err(Error::Func(FuncError::MismatchingResults { .. }))
+    );
+}
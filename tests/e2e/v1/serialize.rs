@@ -0,0 +1,170 @@
+//! Tests for the serialization of `wasmi_v1` modules.
+
+use super::utils::{call, compile};
+use assert_matches::assert_matches;
+use wasmi_v1::{
+    errors::{DeserializeError, ModuleError},
+    Caller,
+    Config,
+    Engine,
+    Error,
+    Instance,
+    Linker,
+    Module,
+    Store,
+};
+
+/// The Wasm module used in the tests below.
+const WAT: &str = r#"
+    (module $serialized
+        (import "host" "offset" (func $offset (param i32) (result i32)))
+        (memory 1)
+        (data (i32.const 16) "\2A\00\00\00")
+        (table 2 funcref)
+        (elem (i32.const 0) $double $square)
+        (global $started (mut i32) (i32.const 0))
+        (tag $error (param i32))
+        (type $unary (func (param i32) (result i32)))
+        (func $start
+            (global.set $started (i32.const 1))
+        )
+        (start $start)
+        (func $double (param $x i32) (result i32)
+            (i32.add (local.get $x) (local.get $x))
+        )
+        (func $square (param $x i32) (result i32)
+            (i32.mul (local.get $x) (local.get $x))
+        )
+        (func (export "started") (result i32)
+            (global.get $started)
+        )
+        (func (export "load") (result i32)
+            (call $offset (i32.load (i32.const 16)))
+        )
+        (func (export "apply") (param $f i32) (param $x i32) (result i32)
+            (call_indirect (type $unary) (local.get $x) (local.get $f))
+        )
+        (func (export "select") (param $n i32) (result i32)
+            (block $c
+                (block $b
+                    (block $a
+                        (br_table $a $b $c (local.get $n))
+                    )
+                    (return (i32.const 10))
+                )
+                (return (i32.const 20))
+            )
+            (i32.const 30)
+        )
+        (func (export "catch") (param $n i32) (result i32)
+            (try (result i32)
+                (do
+                    (if (i32.lt_s (local.get $n) (i32.const 0))
+                        (then (throw $error (local.get $n)))
+                    )
+                    (local.get $n)
+                )
+                (catch $error
+                    (i32.add (i32.const 100))
+                )
+            )
+        )
+    )
+"#;
+
+/// Returns the [`Config`] used by the tests below.
+fn config() -> Config {
+    Config::default().enable_exceptions(true)
+}
+
+/// Instantiates the `module` and starts its `start` function.
+fn instantiate(store: &mut Store<()>, module: &Module) -> Instance {
+    let mut linker = <Linker<()>>::new();
+    linker
+        .func_wrap("host", "offset", |_caller: Caller<()>, value: i32| {
+            value + 1000
+        })
+        .unwrap();
+    linker
+        .instantiate(&mut *store, module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap()
+}
+
+#[test]
+fn deserialized_module_works() {
+    let bytes = compile(&Engine::new(&config()), WAT).serialize().unwrap();
+    // The module is deserialized into a different engine than it was compiled by.
+    let engine = Engine::new(&config());
+    let module = unsafe { Module::deserialize(&engine, &bytes) }.unwrap();
+    assert_eq!(module.name(), Some("serialized"));
+    assert_eq!(module.serialize().unwrap(), bytes);
+    let mut store = Store::new(&engine, ());
+    let instance = instantiate(&mut store, &module);
+    assert_eq!(call::<(), i32>(&mut store, instance, "started", ()), 1);
+    assert_eq!(call::<(), i32>(&mut store, instance, "load", ()), 1042);
+    assert_eq!(call::<_, i32>(&mut store, instance, "apply", (0, 7)), 14);
+    assert_eq!(call::<_, i32>(&mut store, instance, "apply", (1, 7)), 49);
+    assert_eq!(call::<_, i32>(&mut store, instance, "select", 0), 10);
+    assert_eq!(call::<_, i32>(&mut store, instance, "select", 1), 20);
+    assert_eq!(call::<_, i32>(&mut store, instance, "select", 5), 30);
+    assert_eq!(call::<_, i32>(&mut store, instance, "catch", 5), 5);
+    assert_eq!(call::<_, i32>(&mut store, instance, "catch", -5), 95);
+}
+
+#[test]
+fn incompatible_config_is_rejected() {
+    let bytes = compile(&Engine::new(&config()), WAT).serialize().unwrap();
+    let engine = Engine::new(&config().enable_wasm_backtrace(true));
+    assert_matches!(
+        unsafe { Module::deserialize(&engine, &bytes) },
+        Err(Error::Module(ModuleError::Deserialize(
+            DeserializeError::IncompatibleConfig
+        )))
+    );
+}
+
+#[test]
+fn incompatible_format_version_is_rejected() {
+    let engine = Engine::new(&config());
+    let mut bytes = compile(&engine, WAT).serialize().unwrap();
+    assert!(unsafe { Module::deserialize(&engine, &bytes) }.is_ok());
+    // The format version follows the 8 magic bytes as little-endian `u32`.
+    let format_version = u32::from_le_bytes(bytes[8..12].try_into().unwrap());
+    let mismatched = format_version + 1;
+    bytes[8..12].copy_from_slice(&mismatched.to_le_bytes());
+    assert_matches!(
+        unsafe { Module::deserialize(&engine, &bytes) },
+        Err(Error::Module(ModuleError::Deserialize(
+            DeserializeError::IncompatibleFormat { format_version }
+        ))) if format_version == mismatched
+    );
+}
+
+#[test]
+fn malformed_bytes_are_rejected() {
+    let engine = Engine::new(&config());
+    let bytes = compile(&engine, WAT).serialize().unwrap();
+    let wasm = wat::parse_str(WAT).unwrap();
+    assert_matches!(
+        unsafe { Module::deserialize(&engine, &wasm) },
+        Err(Error::Module(ModuleError::Deserialize(
+            DeserializeError::InvalidHeader
+        )))
+    );
+    assert_matches!(
+        unsafe { Module::deserialize(&engine, &bytes[..bytes.len() - 1]) },
+        Err(Error::Module(ModuleError::Deserialize(
+            DeserializeError::UnexpectedEnd
+        )))
+    );
+    let mut trailing = bytes.clone();
+    trailing.push(0x00);
+    assert_matches!(
+        unsafe { Module::deserialize(&engine, &trailing) },
+        Err(Error::Module(ModuleError::Deserialize(
+            DeserializeError::Malformed { .. }
+        )))
+    );
+}
@@ -0,0 +1,259 @@
+//! Tests for the `simd` Wasm proposal support of `wasmi_v1`.
+
+use super::utils::{compile, get_func, get_typed};
+use assert_matches::assert_matches;
+use wasmi_core::{Trap, TrapCode, V128};
+use wasmi_v1::{Config, Engine, Error, Func, Instance, Linker, Module, Store, Value};
+
+/// Creates a [`V128`] from the given `i32` lanes.
+fn i32x4(lanes: [i32; 4]) -> V128 {
+    let bits = lanes
+        .iter()
+        .rev()
+        .fold(0_u128, |bits, lane| bits << 32 | u128::from(*lane as u32));
+    V128::from_bits(bits)
+}
+
+/// Creates a [`V128`] from the given `i8` lanes.
+fn i8x16(lanes: [i8; 16]) -> V128 {
+    V128::from_bits(u128::from_le_bytes(lanes.map(|lane| lane as u8)))
+}
+
+/// Instantiates a Wasm module exporting wrappers around SIMD instructions.
+///
+/// The imported `host.sum` sums up all `i32` lanes of its `v128` input.
+fn setup() -> (Store<()>, Instance) {
+    let config = Config::default().enable_simd(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let module = compile(
+        &engine,
+        r#"
+        (module
+            (import "host" "sum" (func $sum (param v128) (result i32)))
+            (memory (export "memory") 1)
+            (global $ones v128 (v128.const i32x4 1 1 1 1))
+            (func (export "inc") (param $v v128) (result v128)
+                (i32x4.add (local.get $v) (global.get $ones))
+            )
+            (func (export "sum") (param $v v128) (result i32)
+                (call $sum (local.get $v))
+            )
+            (func (export "dot") (param $a v128) (param $b v128) (result i32)
+                (local $dot v128)
+                (local.set $dot (i32x4.dot_i16x8_s (local.get $a) (local.get $b)))
+                (i32.add
+                    (i32x4.extract_lane 0 (local.get $dot))
+                    (i32x4.extract_lane 1 (local.get $dot))
+                )
+            )
+            (func (export "reverse") (param $v v128) (result v128)
+                (i8x16.shuffle 15 14 13 12 11 10 9 8 7 6 5 4 3 2 1 0
+                    (local.get $v) (local.get $v)
+                )
+            )
+            (func (export "swizzle") (param $v v128) (param $s v128) (result v128)
+                (i8x16.swizzle (local.get $v) (local.get $s))
+            )
+            (func (export "replace") (param $v v128) (param $x i32) (result v128)
+                (i32x4.replace_lane 2 (local.get $v) (local.get $x))
+            )
+            (func (export "splat_shl") (param $x i32) (param $n i32) (result v128)
+                (i32x4.shl (i32x4.splat (local.get $x)) (local.get $n))
+            )
+            (func (export "narrow") (param $a v128) (param $b v128) (result v128)
+                (i8x16.narrow_i16x8_s (local.get $a) (local.get $b))
+            )
+            (func (export "all_true") (param $v v128) (result i32)
+                (i32x4.all_true (local.get $v))
+            )
+            (func (export "bitmask") (param $v v128) (result i32)
+                (i8x16.bitmask (local.get $v))
+            )
+            (func (export "trunc_sat") (param $v v128) (result v128)
+                (i32x4.trunc_sat_f32x4_s (local.get $v))
+            )
+            (func (export "load") (param $ptr i32) (result v128)
+                (v128.load offset=1 (local.get $ptr))
+            )
+            (func (export "store") (param $ptr i32) (param $v v128)
+                (v128.store (local.get $ptr) (local.get $v))
+            )
+            (func (export "load8x8_s") (param $ptr i32) (result v128)
+                (v128.load8x8_s (local.get $ptr))
+            )
+            (func (export "load32_zero") (param $ptr i32) (result v128)
+                (v128.load32_zero (local.get $ptr))
+            )
+            (func (export "load16_lane") (param $ptr i32) (param $v v128) (result v128)
+                (v128.load16_lane 7 (local.get $ptr) (local.get $v))
+            )
+            (func (export "store32_lane") (param $ptr i32) (param $v v128)
+                (v128.store32_lane 3 (local.get $ptr) (local.get $v))
+            )
+        )
+    "#,
+    );
+    let sum = Func::wrap(&mut store, |value: V128| {
+        value
+            .to_bits()
+            .to_le_bytes()
+            .chunks_exact(4)
+            .map(|lane| i32::from_le_bytes(lane.try_into().unwrap()))
+            .sum::<i32>()
+    });
+    let mut linker = <Linker<()>>::new();
+    linker.define("host", "sum", sum).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+#[test]
+fn v128_params_and_results_work() {
+    let (mut store, instance) = setup();
+    let inc = get_typed::<V128, V128>(&store, instance, "inc");
+    let sum = get_typed::<V128, i32>(&store, instance, "sum");
+    assert_eq!(
+        inc.call(&mut store, i32x4([1, -1, i32::MAX, 41])).unwrap(),
+        i32x4([2, 0, i32::MIN, 42])
+    );
+    assert_eq!(sum.call(&mut store, i32x4([1, 2, 3, 4])).unwrap(), 10);
+    // Untyped calls use `Value::V128` for `v128` values.
+    let func = get_func(&store, instance, "inc");
+    let mut result = [Value::V128(V128::default())];
+    func.call(&mut store, &[Value::V128(i32x4([0; 4]))], &mut result)
+        .unwrap();
+    assert_eq!(result, [Value::V128(i32x4([1; 4]))]);
+}
+
+#[test]
+fn lane_operations_work() {
+    let (mut store, instance) = setup();
+    let dot = get_typed::<(V128, V128), i32>(&store, instance, "dot");
+    let reverse = get_typed::<V128, V128>(&store, instance, "reverse");
+    let swizzle = get_typed::<(V128, V128), V128>(&store, instance, "swizzle");
+    let replace = get_typed::<(V128, i32), V128>(&store, instance, "replace");
+    let splat_shl = get_typed::<(i32, i32), V128>(&store, instance, "splat_shl");
+    let lanes = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+    // The `i16` lanes are `1, 2, 3, 4` and `5, 6, 7, 8` respectively.
+    let a = i32x4([0x0002_0001, 0x0004_0003, 0, 0]);
+    let b = i32x4([0x0006_0005, 0x0008_0007, 0, 0]);
+    assert_eq!(dot.call(&mut store, (a, b)).unwrap(), 5 + 12 + 21 + 32);
+    let mut reversed = lanes;
+    reversed.reverse();
+    assert_eq!(
+        reverse.call(&mut store, i8x16(lanes)).unwrap(),
+        i8x16(reversed)
+    );
+    // Out of bounds selectors yield zero lanes.
+    let selector = [3, 3, 16, -1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 15];
+    assert_eq!(
+        swizzle
+            .call(&mut store, (i8x16(lanes), i8x16(selector)))
+            .unwrap(),
+        i8x16([3, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 15])
+    );
+    assert_eq!(
+        replace.call(&mut store, (i32x4([1, 2, 3, 4]), -3)).unwrap(),
+        i32x4([1, 2, -3, 4])
+    );
+    // The shift amount is taken modulo the lane width.
+    assert_eq!(
+        splat_shl.call(&mut store, (3, 33)).unwrap(),
+        i32x4([6, 6, 6, 6])
+    );
+}
+
+#[test]
+fn saturating_and_boolean_operations_work() {
+    let (mut store, instance) = setup();
+    let narrow = get_typed::<(V128, V128), V128>(&store, instance, "narrow");
+    let all_true = get_typed::<V128, i32>(&store, instance, "all_true");
+    let bitmask = get_typed::<V128, i32>(&store, instance, "bitmask");
+    let trunc_sat = get_typed::<V128, V128>(&store, instance, "trunc_sat");
+    // The `i16` lanes are `300, -300, 1, -1` followed by zeros.
+    let a = i32x4([0xFED4_012C_u32 as i32, 0xFFFF_0001_u32 as i32, 0, 0]);
+    assert_eq!(
+        narrow.call(&mut store, (a, a)).unwrap(),
+        i8x16([127, -128, 1, -1, 0, 0, 0, 0, 127, -128, 1, -1, 0, 0, 0, 0])
+    );
+    assert_eq!(all_true.call(&mut store, i32x4([1, 2, 3, 4])).unwrap(), 1);
+    assert_eq!(all_true.call(&mut store, i32x4([1, 2, 0, 4])).unwrap(), 0);
+    assert_eq!(
+        bitmask
+            .call(&mut store, i32x4([-1, 0, 0, i32::MIN]))
+            .unwrap(),
+        0b1000_0000_0000_1111
+    );
+    let floats = [1.5_f32, -2.5, f32::NAN, f32::INFINITY];
+    let bits = floats
+        .iter()
+        .rev()
+        .fold(0_u128, |bits, lane| bits << 32 | u128::from(lane.to_bits()));
+    assert_eq!(
+        trunc_sat.call(&mut store, V128::from_bits(bits)).unwrap(),
+        i32x4([1, -2, 0, i32::MAX])
+    );
+}
+
+#[test]
+fn memory_operations_work() {
+    let (mut store, instance) = setup();
+    let load = get_typed::<i32, V128>(&store, instance, "load");
+    let store_v128 = get_typed::<(i32, V128), ()>(&store, instance, "store");
+    let load8x8_s = get_typed::<i32, V128>(&store, instance, "load8x8_s");
+    let load32_zero = get_typed::<i32, V128>(&store, instance, "load32_zero");
+    let load16_lane = get_typed::<(i32, V128), V128>(&store, instance, "load16_lane");
+    let store32_lane = get_typed::<(i32, V128), ()>(&store, instance, "store32_lane");
+    let value = i32x4([1, -2, 3, -4]);
+    store_v128.call(&mut store, (15, value)).unwrap();
+    assert_eq!(load.call(&mut store, 14).unwrap(), value);
+    // The `i8` lanes `1, 0, 0, 0, -2, -1, -1, -1` are sign extended to `i16` lanes.
+    assert_eq!(
+        load8x8_s.call(&mut store, 15).unwrap(),
+        i32x4([0x0000_0001, 0x0000_0000, 0xFFFF_FFFE_u32 as i32, -1])
+    );
+    assert_eq!(
+        load32_zero.call(&mut store, 19).unwrap(),
+        i32x4([-2, 0, 0, 0])
+    );
+    assert_eq!(
+        load16_lane
+            .call(&mut store, (19, i32x4([1, 2, 3, 4])))
+            .unwrap(),
+        i32x4([1, 2, 3, 0xFFFE_0004_u32 as i32])
+    );
+    store32_lane.call(&mut store, (0, value)).unwrap();
+    assert_eq!(
+        load32_zero.call(&mut store, 0).unwrap(),
+        i32x4([-4, 0, 0, 0])
+    );
+    assert_matches!(
+        load.call(&mut store, 65520),
+        Err(Trap::Code(TrapCode::MemoryAccessOutOfBounds))
+    );
+    assert_matches!(
+        store_v128.call(&mut store, (65521, value)),
+        Err(Trap::Code(TrapCode::MemoryAccessOutOfBounds))
+    );
+}
+
+#[test]
+fn simd_disabled_rejects_modules() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (func (param v128) (result v128)
+                (i32x4.add (local.get 0) (local.get 0))
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    assert_matches!(Module::new(&engine, &wasm[..]), Err(Error::Module(_)));
+}
@@ -0,0 +1,302 @@
+//! Tests for capturing and restoring snapshots of `wasmi_v1` instances.
+
+use super::utils::{call, compile, try_call};
+use assert_matches::assert_matches;
+use wasmi_v1::{
+    errors::SnapshotError,
+    Config,
+    Engine,
+    Error,
+    Extern,
+    ExternRef,
+    InstanceSnapshot,
+    Linker,
+    Module,
+    Store,
+    Value,
+};
+
+/// Returns an [`Engine`] with the Wasm proposals used by the tests enabled.
+fn test_engine() -> Engine {
+    let config = Config::default()
+        .enable_bulk_memory(true)
+        .enable_reference_types(true);
+    Engine::new(&config)
+}
+
+/// A Wasm module with an expensive `start` function.
+///
+/// - The `start` function grows the memory by a page, writes to the new page,
+///   increments the `$starts` counter and appends `$triple` to the table.
+/// - The `bump` function increments the `$counter` global variable.
+const WAT: &str = r#"
+    (module
+        (type $unary (func (param i32) (result i32)))
+        (memory (export "memory") 1)
+        (table $table 1 funcref)
+        (global $starts (mut i32) (i32.const 0))
+        (global $counter (mut i32) (i32.const 0))
+        (global (export "extern") (mut externref) (ref.null extern))
+        (elem declare func $triple)
+        (func $start
+            (drop (memory.grow (i32.const 1)))
+            (i32.store (i32.const 65544) (i32.const 7))
+            (global.set $starts (i32.add (global.get $starts) (i32.const 1)))
+            (drop (table.grow $table (ref.func $triple) (i32.const 1)))
+        )
+        (start $start)
+        (func $triple (param $x i32) (result i32)
+            (i32.mul (local.get $x) (i32.const 3))
+        )
+        (func (export "starts") (result i32)
+            (global.get $starts)
+        )
+        (func (export "bump") (result i32)
+            (global.set $counter (i32.add (global.get $counter) (i32.const 1)))
+            (global.get $counter)
+        )
+        (func (export "load") (param $address i32) (result i32)
+            (i32.load (local.get $address))
+        )
+        (func (export "apply") (param $f i32) (param $x i32) (result i32)
+            (call_indirect (type $unary) (local.get $x) (local.get $f))
+        )
+    )
+"#;
+
+#[test]
+fn restored_instance_resumes_captured_state() {
+    let engine = test_engine();
+    let module = compile(&engine, WAT);
+    let mut linker = <Linker<()>>::new();
+    let mut store = Store::new(&engine, ());
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    assert_eq!(call::<(), i32>(&mut store, instance, "bump", ()), 1);
+    let snapshot = instance.snapshot(&store).unwrap();
+    // Restore the snapshot into multiple fresh instances of another store.
+    let mut store = Store::new(&engine, ());
+    for _ in 0..2 {
+        let restored = linker
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .restore(&mut store, &snapshot)
+            .unwrap();
+        // The `start` function is not executed again upon restoring.
+        assert_eq!(call::<(), i32>(&mut store, restored, "starts", ()), 1);
+        assert_eq!(call::<(), i32>(&mut store, restored, "bump", ()), 2);
+        assert_eq!(call::<_, i32>(&mut store, restored, "load", 65544), 7);
+        assert_eq!(call::<_, i32>(&mut store, restored, "apply", (1, 5)), 15);
+        let memory = restored
+            .get_export(&store, "memory")
+            .and_then(Extern::into_memory)
+            .unwrap();
+        assert_eq!(memory.current_pages(&store).0, 2);
+    }
+}
+
+#[test]
+fn snapshot_of_other_module_is_rejected() {
+    let engine = test_engine();
+    let module = compile(&engine, WAT);
+    let other = compile(&engine, "(module (memory 2))");
+    let mut linker = <Linker<()>>::new();
+    let mut store = Store::new(&engine, ());
+    let snapshot = linker
+        .instantiate(&mut store, &other)
+        .unwrap()
+        .start(&mut store)
+        .unwrap()
+        .snapshot(&store)
+        .unwrap();
+    assert_matches!(
+        linker
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .restore(&mut store, &snapshot),
+        Err(Error::Snapshot(SnapshotError::ModuleMismatch))
+    );
+}
+
+#[test]
+fn snapshot_of_other_module_with_same_shape_is_rejected() {
+    let engine = test_engine();
+    let module = compile(&engine, WAT);
+    let mut linker = <Linker<()>>::new();
+    let mut store = Store::new(&engine, ());
+    let snapshot = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap()
+        .snapshot(&store)
+        .unwrap();
+    // Replaces the body of `$triple` which keeps the shape of the module intact.
+    let other = compile(&engine, &WAT.replace("(i32.const 3)", "(i32.const 4)"));
+    assert_matches!(
+        linker
+            .instantiate(&mut store, &other)
+            .unwrap()
+            .restore(&mut store, &snapshot),
+        Err(Error::Snapshot(SnapshotError::ModuleMismatch))
+    );
+}
+
+#[test]
+fn snapshot_of_module_with_same_wasm_bytes_is_restored() {
+    let engine = test_engine();
+    let module = compile(&engine, WAT);
+    let mut linker = <Linker<()>>::new();
+    let mut store = Store::new(&engine, ());
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    assert_eq!(call::<(), i32>(&mut store, instance, "bump", ()), 1);
+    let snapshot = instance.snapshot(&store).unwrap();
+    let same = compile(&engine, WAT);
+    let bytes = module.serialize().unwrap();
+    // SAFETY: the bytes have just been serialized by the same `wasmi_v1` build.
+    let deserialized = unsafe { Module::deserialize(&engine, &bytes) }.unwrap();
+    for module in [&same, &deserialized] {
+        let restored = linker
+            .instantiate(&mut store, module)
+            .unwrap()
+            .restore(&mut store, &snapshot)
+            .unwrap();
+        assert_eq!(call::<(), i32>(&mut store, restored, "bump", ()), 2);
+    }
+}
+
+#[test]
+fn deserialized_snapshot_resumes_captured_state() {
+    let engine = test_engine();
+    let module = compile(&engine, WAT);
+    let mut linker = <Linker<()>>::new();
+    let mut store = Store::new(&engine, ());
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    assert_eq!(call::<(), i32>(&mut store, instance, "bump", ()), 1);
+    let bytes = instance.snapshot(&store).unwrap().serialize().unwrap();
+    let snapshot = InstanceSnapshot::deserialize(&bytes).unwrap();
+    assert_eq!(snapshot.serialize().unwrap(), bytes);
+    // Restore the deserialized snapshot into an instance of a deserialized module.
+    let engine = test_engine();
+    let module = compile(&engine, WAT);
+    // SAFETY: the bytes have just been serialized by the same `wasmi_v1` build.
+    let module = unsafe { Module::deserialize(&engine, &module.serialize().unwrap()) }.unwrap();
+    let mut store = Store::new(&engine, ());
+    let restored = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .restore(&mut store, &snapshot)
+        .unwrap();
+    assert_eq!(call::<(), i32>(&mut store, restored, "starts", ()), 1);
+    assert_eq!(call::<(), i32>(&mut store, restored, "bump", ()), 2);
+    assert_eq!(call::<_, i32>(&mut store, restored, "load", 65544), 7);
+    assert_eq!(call::<_, i32>(&mut store, restored, "apply", (1, 5)), 15);
+}
+
+#[test]
+fn malformed_serialized_snapshot_is_rejected() {
+    let engine = test_engine();
+    let module = compile(&engine, WAT);
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new()
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let mut bytes = instance.snapshot(&store).unwrap().serialize().unwrap();
+    assert_matches!(
+        InstanceSnapshot::deserialize(&bytes[..bytes.len() - 1]),
+        Err(SnapshotError::Malformed)
+    );
+    assert_matches!(
+        InstanceSnapshot::deserialize(&module.serialize().unwrap()),
+        Err(SnapshotError::Malformed)
+    );
+    // The format version follows the 8 magic bytes as little-endian `u32`.
+    let format_version = u32::from_le_bytes(bytes[8..12].try_into().unwrap());
+    let mismatched = format_version + 1;
+    bytes[8..12].copy_from_slice(&mismatched.to_le_bytes());
+    assert_matches!(
+        InstanceSnapshot::deserialize(&bytes),
+        Err(SnapshotError::IncompatibleFormat { format_version }) if format_version == mismatched
+    );
+}
+
+#[test]
+fn restored_instance_keeps_dropped_segments() {
+    let wat = r#"
+        (module
+            (memory 1)
+            (table 1 funcref)
+            (data $kept "\01")
+            (data $dropped "\02")
+            (elem $elem func $f)
+            (func $f)
+            (func (export "drop")
+                (data.drop $dropped)
+                (elem.drop $elem)
+            )
+            (func (export "init_kept")
+                (memory.init $kept (i32.const 0) (i32.const 0) (i32.const 1))
+            )
+            (func (export "init_dropped")
+                (memory.init $dropped (i32.const 0) (i32.const 0) (i32.const 1))
+            )
+            (func (export "init_elem")
+                (table.init $elem (i32.const 0) (i32.const 0) (i32.const 1))
+            )
+        )
+    "#;
+    let engine = test_engine();
+    let module = compile(&engine, wat);
+    let mut linker = <Linker<()>>::new();
+    let mut store = Store::new(&engine, ());
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    call::<(), ()>(&mut store, instance, "drop", ());
+    let snapshot = instance.snapshot(&store).unwrap();
+    let restored = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .restore(&mut store, &snapshot)
+        .unwrap();
+    call::<(), ()>(&mut store, restored, "init_kept", ());
+    assert!(try_call::<(), ()>(&mut store, restored, "init_dropped", ()).is_err());
+    assert!(try_call::<(), ()>(&mut store, restored, "init_elem", ()).is_err());
+}
+
+#[test]
+fn snapshot_with_externref_is_rejected() {
+    let engine = test_engine();
+    let module = compile(&engine, WAT);
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new()
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let global = instance
+        .get_export(&store, "extern")
+        .and_then(Extern::into_global)
+        .unwrap();
+    let value = ExternRef::new(&mut store, 42_i32);
+    global.set(&mut store, Value::ExternRef(value)).unwrap();
+    assert_matches!(
+        instance.snapshot(&store),
+        Err(SnapshotError::UnsupportedReference)
+    );
+}
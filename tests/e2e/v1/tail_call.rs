@@ -0,0 +1,182 @@
+//! Tests for the `tail-call` Wasm proposal support of `wasmi_v1`.
+
+use super::utils::{compile, get_func, get_typed};
+use assert_matches::assert_matches;
+use core::fmt;
+use wasmi_core::{HostError, Trap, TrapCode};
+use wasmi_v1::{
+    Config,
+    Engine,
+    Error,
+    Func,
+    Instance,
+    Linker,
+    Module,
+    ResumableCall,
+    Store,
+    Value,
+};
+
+/// The host error that suspends the execution of a resumable call.
+#[derive(Debug)]
+struct Suspend;
+
+impl fmt::Display for Suspend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "suspended")
+    }
+}
+
+impl HostError for Suspend {}
+
+/// Instantiates a Wasm module exporting tail recursive functions.
+///
+/// The imported `host.double` doubles its input if it is non-negative
+/// and otherwise traps with the [`Suspend`] host error.
+fn setup() -> (Store<()>, Instance) {
+    let config = Config::default().enable_tail_call(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let module = compile(
+        &engine,
+        r#"
+        (module
+            (import "host" "double" (func $double (param i32) (result i32)))
+            (type $is (func (param i64) (result i32)))
+            (table funcref (elem $is_even $is_odd))
+            (func $sum (export "sum") (param $n i64) (param $acc i64) (result i64)
+                (if (i64.eqz (local.get $n))
+                    (then (return (local.get $acc)))
+                )
+                (return_call $sum
+                    (i64.sub (local.get $n) (i64.const 1))
+                    (i64.add (local.get $acc) (local.get $n))
+                )
+            )
+            (func $sum_no_tail (export "sum_no_tail") (param $n i64) (param $acc i64) (result i64)
+                (if (i64.eqz (local.get $n))
+                    (then (return (local.get $acc)))
+                )
+                (call $sum_no_tail
+                    (i64.sub (local.get $n) (i64.const 1))
+                    (i64.add (local.get $acc) (local.get $n))
+                )
+            )
+            (func $is_even (export "is_even") (param $n i64) (result i32)
+                (if (i64.eqz (local.get $n))
+                    (then (return (i32.const 1)))
+                )
+                (return_call_indirect (type $is)
+                    (i64.sub (local.get $n) (i64.const 1))
+                    (i32.const 1)
+                )
+            )
+            (func $is_odd (param $n i64) (result i32)
+                (if (i64.eqz (local.get $n))
+                    (then (return (i32.const 0)))
+                )
+                (return_call_indirect (type $is)
+                    (i64.sub (local.get $n) (i64.const 1))
+                    (i32.const 0)
+                )
+            )
+            (func (export "call_indirect_mismatch") (result i32)
+                (return_call_indirect (result i32) (i32.const 0))
+            )
+            (func $double_tail (export "double") (param $n i32) (result i32)
+                (return_call $double (local.get $n))
+            )
+            (func (export "double_plus_one") (param $n i32) (result i32)
+                (i32.add (call $double_tail (local.get $n)) (i32.const 1))
+            )
+        )
+    "#,
+    );
+    let double = Func::wrap(&mut store, |n: i32| {
+        if n < 0 {
+            return Err(Trap::from(Suspend));
+        }
+        Ok((n * 2,))
+    });
+    let mut linker = <Linker<()>>::new();
+    linker.define("host", "double", double).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+#[test]
+fn return_call_does_not_grow_call_stack() {
+    let (mut store, instance) = setup();
+    let sum = get_typed::<(i64, i64), i64>(&store, instance, "sum");
+    let sum_no_tail = get_typed::<(i64, i64), i64>(&store, instance, "sum_no_tail");
+    let n = 1_000_000_i64;
+    assert_eq!(sum.call(&mut store, (n, 0)).unwrap(), n * (n + 1) / 2);
+    assert_matches!(
+        sum_no_tail.call(&mut store, (n, 0)),
+        Err(Trap::Code(TrapCode::StackOverflow))
+    );
+}
+
+#[test]
+fn return_call_indirect_works() {
+    let (mut store, instance) = setup();
+    let is_even = get_typed::<i64, i32>(&store, instance, "is_even");
+    assert_eq!(is_even.call(&mut store, 0).unwrap(), 1);
+    assert_eq!(is_even.call(&mut store, 7).unwrap(), 0);
+    assert_eq!(is_even.call(&mut store, 1_000_000).unwrap(), 1);
+    let mismatch = get_typed::<(), i32>(&store, instance, "call_indirect_mismatch");
+    assert_matches!(
+        mismatch.call(&mut store, ()),
+        Err(Trap::Code(TrapCode::UnexpectedSignature))
+    );
+}
+
+#[test]
+fn return_call_host_func_works() {
+    let (mut store, instance) = setup();
+    let double = get_typed::<i32, i32>(&store, instance, "double");
+    let double_plus_one = get_typed::<i32, i32>(&store, instance, "double_plus_one");
+    assert_eq!(double.call(&mut store, 21).unwrap(), 42);
+    assert_eq!(double_plus_one.call(&mut store, 21).unwrap(), 43);
+}
+
+#[test]
+fn return_call_host_func_is_resumable() {
+    let (mut store, instance) = setup();
+    let mut result = [Value::I32(0)];
+    for (name, expected) in [("double", 10), ("double_plus_one", 11)] {
+        let func = get_func(&store, instance, name);
+        let invocation = match func
+            .call_resumable(&mut store, &[Value::I32(-1)], &mut result)
+            .unwrap()
+        {
+            ResumableCall::Resumable(invocation) => invocation,
+            ResumableCall::Finished => panic!("expected the call to be suspended"),
+        };
+        let call = invocation
+            .resume(&mut store, &[Value::I32(10)], &mut result)
+            .unwrap();
+        assert_matches!(call, ResumableCall::Finished);
+        assert_eq!(result, [Value::I32(expected)]);
+    }
+}
+
+#[test]
+fn tail_call_disabled_rejects_modules() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (func $f (result i32)
+                (return_call $f)
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    assert_matches!(Module::new(&engine, &wasm[..]), Err(Error::Module(_)));
+}
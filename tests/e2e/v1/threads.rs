@@ -0,0 +1,360 @@
+//! Tests for the `threads` Wasm proposal support of `wasmi_v1`.
+
+use super::utils::{compile, get_typed};
+use assert_matches::assert_matches;
+use std::{
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+use wasmi_core::{memory_units::Pages, Trap, TrapCode};
+use wasmi_v1::{
+    errors::LinkerError,
+    Config,
+    Engine,
+    Error,
+    Instance,
+    Linker,
+    Memory,
+    MemoryType,
+    Module,
+    ParkResult,
+    Parker,
+    ParkingSpot,
+    SharedMemory,
+    Store,
+};
+
+/// The Wasm module used in the tests below.
+///
+/// The module imports the shared `host.memory` linear memory.
+const WAT: &str = r#"
+    (module
+        (import "host" "memory" (memory 1 4 shared))
+        (func (export "increment") (param $ptr i32) (param $times i32)
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.eqz (local.get $times)))
+                    (drop (i32.atomic.rmw.add (local.get $ptr) (i32.const 1)))
+                    (local.set $times (i32.sub (local.get $times) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+        )
+        (func (export "load") (param $ptr i32) (result i32)
+            (i32.atomic.load (local.get $ptr))
+        )
+        (func (export "store8") (param $ptr i32) (param $value i32)
+            (i32.atomic.store8 (local.get $ptr) (local.get $value))
+        )
+        (func (export "cmpxchg16") (param $ptr i32) (param $expected i64) (param $value i64) (result i64)
+            (i64.atomic.rmw16.cmpxchg_u (local.get $ptr) (local.get $expected) (local.get $value))
+        )
+        (func (export "wait") (param $ptr i32) (param $expected i32) (param $timeout i64) (result i32)
+            (memory.atomic.wait32 (local.get $ptr) (local.get $expected) (local.get $timeout))
+        )
+        (func (export "notify") (param $ptr i32) (param $count i32) (result i32)
+            (memory.atomic.notify (local.get $ptr) (local.get $count))
+        )
+        (func (export "fence")
+            (atomic.fence)
+        )
+        (func (export "size") (result i32)
+            (memory.size)
+        )
+    )
+"#;
+
+/// Returns a new shared linear memory matching the import of the Wasm module.
+fn shared_memory() -> SharedMemory {
+    SharedMemory::new(MemoryType::new(1, Some(4)).into_shared()).unwrap()
+}
+
+/// Compiles the Wasm module using a new [`Engine`] with enabled `threads` support.
+///
+/// # Note
+///
+/// The returned [`Module`] is meant to be shared by all threads of a test
+/// the same way as its [`Engine`] which is shared by all of their stores.
+fn setup_module() -> Arc<Module> {
+    let config = Config::default().enable_threads(true);
+    let engine = Engine::new(&config);
+    Arc::new(compile(&engine, WAT))
+}
+
+/// Instantiates the `module` in a new [`Store`] importing the `memory`.
+fn instantiate(module: &Module, memory: &SharedMemory) -> (Store<()>, Instance) {
+    let mut store = Store::new(module.engine(), ());
+    let mut linker = <Linker<()>>::new();
+    linker
+        .define_shared_memory("host", "memory", memory.clone())
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+#[test]
+fn stores_on_threads_share_memory() {
+    const THREADS: u32 = 4;
+    const TIMES: i32 = 1000;
+    let module = setup_module();
+    let memory = shared_memory();
+    let handles = (0..THREADS)
+        .map(|_| {
+            let module = module.clone();
+            let memory = memory.clone();
+            thread::spawn(move || {
+                let (mut store, instance) = instantiate(&module, &memory);
+                let increment = get_typed::<(i32, i32), ()>(&store, instance, "increment");
+                increment.call(&mut store, (8, TIMES)).unwrap();
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    let (mut store, instance) = instantiate(&module, &memory);
+    let load = get_typed::<i32, i32>(&store, instance, "load");
+    assert_eq!(load.call(&mut store, 8).unwrap(), THREADS as i32 * TIMES);
+    let mut buffer = [0x00_u8; 4];
+    memory.read(8, &mut buffer).unwrap();
+    assert_eq!(u32::from_le_bytes(buffer), THREADS * TIMES as u32);
+}
+
+#[test]
+fn atomic_accesses_wrap_and_extend() {
+    let module = setup_module();
+    let memory = shared_memory();
+    let (mut store, instance) = instantiate(&module, &memory);
+    let load = get_typed::<i32, i32>(&store, instance, "load");
+    let store8 = get_typed::<(i32, i32), ()>(&store, instance, "store8");
+    let cmpxchg16 = get_typed::<(i32, i64, i64), i64>(&store, instance, "cmpxchg16");
+    let fence = get_typed::<(), ()>(&store, instance, "fence");
+    store8.call(&mut store, (0, 0x1234)).unwrap();
+    fence.call(&mut store, ()).unwrap();
+    assert_eq!(load.call(&mut store, 0).unwrap(), 0x34);
+    // The comparison fails and the memory is left unchanged.
+    assert_eq!(cmpxchg16.call(&mut store, (0, 0, -1)).unwrap(), 0x34);
+    assert_eq!(load.call(&mut store, 0).unwrap(), 0x34);
+    // The comparison succeeds and the wrapped replacement is stored.
+    assert_eq!(cmpxchg16.call(&mut store, (0, 0x34, -1)).unwrap(), 0x34);
+    assert_eq!(load.call(&mut store, 0).unwrap(), 0xFFFF);
+    assert_matches!(
+        load.call(&mut store, 1),
+        Err(Trap::Code(TrapCode::UnalignedAtomic))
+    );
+    assert_matches!(
+        load.call(&mut store, 65536),
+        Err(Trap::Code(TrapCode::MemoryAccessOutOfBounds))
+    );
+}
+
+#[test]
+fn wait_and_notify() {
+    let module = setup_module();
+    let memory = shared_memory();
+    let (mut store, instance) = instantiate(&module, &memory);
+    let wait = get_typed::<(i32, i32, i64), i32>(&store, instance, "wait");
+    let notify = get_typed::<(i32, i32), i32>(&store, instance, "notify");
+    // Values that are not equal to the expected value do not park the thread.
+    assert_eq!(wait.call(&mut store, (0, 1, -1)).unwrap(), 1);
+    // Timeouts are given in nanoseconds.
+    assert_eq!(wait.call(&mut store, (0, 0, 1_000_000)).unwrap(), 2);
+    assert_eq!(notify.call(&mut store, (0, 1)).unwrap(), 0);
+    // The waiting thread is parked while executing Wasm on the shared engine
+    // which must not prevent this thread from executing the notification.
+    let waiter = {
+        let module = module.clone();
+        let memory = memory.clone();
+        thread::spawn(move || {
+            let (mut store, instance) = instantiate(&module, &memory);
+            let wait = get_typed::<(i32, i32, i64), i32>(&store, instance, "wait");
+            wait.call(&mut store, (0, 0, -1)).unwrap()
+        })
+    };
+    // Notify until the waiting thread has been parked and woken up.
+    while notify.call(&mut store, (0, 1)).unwrap() == 0 {
+        thread::yield_now();
+    }
+    assert_eq!(waiter.join().unwrap(), 0);
+}
+
+#[test]
+fn notify_wakes_all_waiters_on_shared_engine() {
+    const WAITERS: i32 = 4;
+    let module = setup_module();
+    let memory = shared_memory();
+    let waiters = (0..WAITERS)
+        .map(|_| {
+            let module = module.clone();
+            let memory = memory.clone();
+            thread::spawn(move || {
+                let (mut store, instance) = instantiate(&module, &memory);
+                let wait = get_typed::<(i32, i32, i64), i32>(&store, instance, "wait");
+                wait.call(&mut store, (4, 0, -1)).unwrap()
+            })
+        })
+        .collect::<Vec<_>>();
+    let (mut store, instance) = instantiate(&module, &memory);
+    let notify = get_typed::<(i32, i32), i32>(&store, instance, "notify");
+    let mut unparked = 0;
+    while unparked < WAITERS {
+        unparked += notify.call(&mut store, (4, WAITERS)).unwrap();
+        thread::yield_now();
+    }
+    for waiter in waiters {
+        assert_eq!(waiter.join().unwrap(), 0);
+    }
+}
+
+#[test]
+fn host_waits_for_wasm_notification() {
+    let memory = shared_memory();
+    let waiter = {
+        let memory = memory.clone();
+        thread::spawn(move || memory.atomic_wait32(16, 0, None).unwrap())
+    };
+    let module = setup_module();
+    let (mut store, instance) = instantiate(&module, &memory);
+    let notify = get_typed::<(i32, i32), i32>(&store, instance, "notify");
+    while notify.call(&mut store, (16, 1)).unwrap() == 0 {
+        thread::yield_now();
+    }
+    assert_matches!(waiter.join().unwrap(), ParkResult::Unparked);
+    assert_matches!(
+        memory.atomic_wait64(16, 1, Some(Duration::from_millis(1))),
+        Ok(ParkResult::Invalid)
+    );
+    assert!(memory.atomic_wait32(17, 0, None).is_err());
+}
+
+#[test]
+fn grow_is_shared_between_stores() {
+    let module = setup_module();
+    let memory = shared_memory();
+    let (mut store_a, instance_a) = instantiate(&module, &memory);
+    let (mut store_b, instance_b) = instantiate(&module, &memory);
+    let size_a = get_typed::<(), i32>(&store_a, instance_a, "size");
+    let size_b = get_typed::<(), i32>(&store_b, instance_b, "size");
+    assert_eq!(memory.grow(Pages(2)).unwrap(), Pages(1));
+    assert_eq!(size_a.call(&mut store_a, ()).unwrap(), 3);
+    assert_eq!(size_b.call(&mut store_b, ()).unwrap(), 3);
+    let exported = Memory::from_shared(&mut store_a, memory.clone()).unwrap();
+    assert_eq!(exported.data(&store_a).len(), 3 * 65536);
+    assert_eq!(exported.shared(&store_a), Some(memory.clone()));
+    assert!(memory.grow(Pages(2)).is_err());
+}
+
+#[test]
+fn custom_parker_is_used() {
+    /// A [`Parker`] that counts the amount of unparked threads.
+    #[derive(Debug, Default)]
+    struct CountingParker {
+        spot: ParkingSpot,
+        unparked: AtomicU32,
+    }
+
+    impl Parker for CountingParker {
+        fn park(
+            &self,
+            key: usize,
+            validate: &mut dyn FnMut() -> bool,
+            timeout: Option<Duration>,
+        ) -> ParkResult {
+            self.spot.park(key, validate, timeout)
+        }
+
+        fn unpark(&self, key: usize, count: u32) -> u32 {
+            let unparked = self.spot.unpark(key, count);
+            self.unparked.fetch_add(unparked, Ordering::SeqCst);
+            unparked
+        }
+    }
+
+    let parker = Arc::new(CountingParker::default());
+    let memory =
+        SharedMemory::with_parker(MemoryType::new(1, Some(4)).into_shared(), parker.clone())
+            .unwrap();
+    let waiter = {
+        let memory = memory.clone();
+        thread::spawn(move || memory.atomic_wait32(0, 0, None).unwrap())
+    };
+    while memory.atomic_notify(0, 1).unwrap() == 0 {
+        thread::yield_now();
+    }
+    assert_matches!(waiter.join().unwrap(), ParkResult::Unparked);
+    assert_eq!(parker.unparked.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn atomics_on_unshared_memory() {
+    let config = Config::default().enable_threads(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (memory 1)
+            (func (export "add") (param $ptr i32) (param $value i64) (result i64)
+                (i64.atomic.rmw.add (local.get $ptr) (local.get $value))
+            )
+            (func (export "wait") (result i32)
+                (memory.atomic.wait32 (i32.const 0) (i32.const 0) (i64.const 0))
+            )
+            (func (export "notify") (result i32)
+                (memory.atomic.notify (i32.const 0) (i32.const 1))
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = <Linker<()>>::new()
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let add = get_typed::<(i32, i64), i64>(&store, instance, "add");
+    let wait = get_typed::<(), i32>(&store, instance, "wait");
+    let notify = get_typed::<(), i32>(&store, instance, "notify");
+    assert_eq!(add.call(&mut store, (8, 40)).unwrap(), 0);
+    assert_eq!(add.call(&mut store, (8, 2)).unwrap(), 40);
+    assert_matches!(
+        add.call(&mut store, (4, 0)),
+        Err(Trap::Code(TrapCode::UnalignedAtomic))
+    );
+    assert_eq!(notify.call(&mut store, ()).unwrap(), 0);
+    assert_matches!(
+        wait.call(&mut store, ()),
+        Err(Trap::Code(TrapCode::ExpectedSharedMemory))
+    );
+}
+
+#[test]
+fn invalid_shared_memory_definitions() {
+    assert!(SharedMemory::new(MemoryType::new(1, Some(4))).is_err());
+    assert!(SharedMemory::new(MemoryType::new(1, None).into_shared()).is_err());
+    let mut linker = <Linker<()>>::new();
+    linker
+        .define_shared_memory("host", "memory", shared_memory())
+        .unwrap();
+    assert_matches!(
+        linker.define_shared_memory("host", "memory", shared_memory()),
+        Err(LinkerError::DuplicateSharedMemoryDefinition { .. })
+    );
+    assert!(linker.resolve("host", Some("memory")).is_none());
+}
+
+#[test]
+fn threads_disabled_rejects_modules() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(WAT).unwrap();
+    assert_matches!(Module::new(&engine, &wasm[..]), Err(Error::Module(_)));
+}
@@ -0,0 +1,98 @@
+//! Utilities shared by the `wasmi_v1` end-to-end tests.
+
+use wasmi_core::Trap;
+use wasmi_v1::{
+    AsContext,
+    Engine,
+    Extern,
+    Func,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    TypedFunc,
+    WasmParams,
+    WasmResults,
+};
+
+/// Compiles the Wasm module given in the text format `wat` using the `engine`.
+pub fn compile(engine: &Engine, wat: &str) -> Module {
+    let wasm = wat::parse_str(wat).unwrap();
+    Module::new(engine, &wasm[..]).unwrap()
+}
+
+/// Instantiates the `module` without imports and runs its `start` function.
+pub fn instantiate(store: &mut Store<()>, module: &Module) -> Instance {
+    <Linker<()>>::new()
+        .instantiate(&mut *store, module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap()
+}
+
+/// Returns the exported function `name` of the `instance`.
+///
+/// # Panics
+///
+/// If `name` is not an exported function.
+pub fn get_func(store: impl AsContext, instance: Instance, name: &str) -> Func {
+    instance
+        .get_export(store, name)
+        .and_then(Extern::into_func)
+        .unwrap()
+}
+
+/// Returns the exported function `name` of the `instance` with the given signature.
+///
+/// # Panics
+///
+/// If `name` is not an exported function of matching type.
+pub fn get_typed<Params, Results>(
+    store: impl AsContext,
+    instance: Instance,
+    name: &str,
+) -> TypedFunc<Params, Results>
+where
+    Params: WasmParams,
+    Results: WasmResults,
+{
+    get_func(&store, instance, name)
+        .typed::<Params, Results, _>(&store)
+        .unwrap()
+}
+
+/// Calls the exported `func` of `instance` with the `params`.
+///
+/// # Panics
+///
+/// If `func` is not an exported function of matching type.
+pub fn try_call<Params, Results>(
+    store: &mut Store<()>,
+    instance: Instance,
+    func: &str,
+    params: Params,
+) -> Result<Results, Trap>
+where
+    Params: WasmParams,
+    Results: WasmResults,
+{
+    get_typed::<Params, Results>(&*store, instance, func).call(&mut *store, params)
+}
+
+/// Calls the exported `func` of `instance` with the `params`.
+///
+/// # Panics
+///
+/// If `func` is not an exported function of matching type or if the call traps.
+pub fn call<Params, Results>(
+    store: &mut Store<()>,
+    instance: Instance,
+    func: &str,
+    params: Params,
+) -> Results
+where
+    Params: WasmParams,
+    Results: WasmResults,
+{
+    try_call(store, instance, func, params).unwrap()
+}
@@ -0,0 +1,40 @@
+//! Tests for the `virtual_memory` crate feature of `wasmi_v1`.
+
+use super::utils::{compile, get_typed, instantiate};
+use wasmi_core::memory_units::Pages;
+use wasmi_v1::{Engine, Extern, Store};
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn memory_grow_does_not_move_bytes() {
+    let engine = Engine::default();
+    let module = compile(
+        &engine,
+        r#"
+        (module
+            (memory (export "memory") 1)
+            (func (export "grow") (param $delta i32) (result i32)
+                (memory.grow (local.get $delta))
+            )
+        )
+    "#,
+    );
+    let mut store = Store::new(&engine, ());
+    let instance = instantiate(&mut store, &module);
+    let memory = instance
+        .get_export(&store, "memory")
+        .and_then(Extern::into_memory)
+        .unwrap();
+    let grow = get_typed::<i32, i32>(&store, instance, "grow");
+    let ptr = memory.data_ptr(&mut store);
+    // SAFETY: the first page of the linear memory is accessible.
+    unsafe { ptr.write(42) };
+    assert_eq!(grow.call(&mut store, 2).unwrap(), 1);
+    assert_eq!(memory.data_ptr(&mut store), ptr);
+    assert_eq!(memory.grow(&mut store, Pages(3)).unwrap(), Pages(3));
+    assert_eq!(memory.data_ptr(&mut store), ptr);
+    assert_eq!(memory.data(&store)[0], 42);
+    // SAFETY: the linear memory has grown to 6 pages.
+    unsafe { ptr.add(5 * 65536).write(7) };
+    assert_eq!(memory.data(&store)[5 * 65536], 7);
+}
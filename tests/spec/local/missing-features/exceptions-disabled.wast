@@ -0,0 +1,15 @@
+(assert_invalid
+  (module
+    (tag $e)
+  )
+  "exceptions proposal not enabled"
+)
+
+(assert_invalid
+  (module
+    (func
+      (try (do) (catch_all))
+    )
+  )
+  "exceptions support is not enabled"
+)
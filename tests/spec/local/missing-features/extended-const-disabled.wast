@@ -0,0 +1,14 @@
+(assert_invalid
+  (module
+    (global i32 (i32.add (i32.const 1) (i32.const 2)))
+  )
+  "constant expression required"
+)
+
+(assert_invalid
+  (module
+    (memory 1)
+    (data (i32.sub (i32.const 2) (i32.const 1)) "a")
+  )
+  "constant expression required"
+)
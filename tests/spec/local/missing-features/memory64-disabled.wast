@@ -0,0 +1,13 @@
+(assert_invalid
+  (module
+    (memory i64 1)
+  )
+  "memory64 must be enabled for 64-bit memories"
+)
+
+(assert_invalid
+  (module
+    (import "spectest" "memory64" (memory i64 1))
+  )
+  "memory64 must be enabled for 64-bit memories"
+)
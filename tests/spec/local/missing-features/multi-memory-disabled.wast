@@ -0,0 +1,15 @@
+(assert_invalid
+  (module
+    (memory 1)
+    (memory 1)
+  )
+  "multiple memories"
+)
+
+(assert_invalid
+  (module
+    (import "spectest" "memory" (memory 1 2))
+    (memory 1)
+  )
+  "multiple memories"
+)
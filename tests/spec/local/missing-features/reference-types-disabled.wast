@@ -0,0 +1,34 @@
+(assert_invalid
+  (module
+    (func (param externref))
+  )
+  "reference types support is not enabled"
+)
+
+(assert_invalid
+  (module
+    (func (result i32)
+      ref.null func
+      ref.is_null
+    )
+  )
+  "reference types support is not enabled"
+)
+
+(assert_invalid
+  (module
+    (table 1 funcref)
+    (func (result i32)
+      table.size 0
+    )
+  )
+  "reference types support is not enabled"
+)
+
+(assert_invalid
+  (module
+    (table 1 funcref)
+    (table 1 funcref)
+  )
+  "multiple tables"
+)
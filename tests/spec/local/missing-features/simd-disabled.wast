@@ -0,0 +1,15 @@
+(assert_invalid
+  (module
+    (func (param v128))
+  )
+  "SIMD support is not enabled"
+)
+
+(assert_invalid
+  (module
+    (func (result i32)
+      (i32x4.extract_lane 0 (i32x4.splat (i32.const 1)))
+    )
+  )
+  "SIMD support is not enabled"
+)
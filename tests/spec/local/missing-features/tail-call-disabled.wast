@@ -0,0 +1,19 @@
+(assert_invalid
+  (module
+    (func $f (result i32)
+      (return_call $f)
+    )
+  )
+  "tail calls support is not enabled"
+)
+
+(assert_invalid
+  (module
+    (type $t (func))
+    (table 1 funcref)
+    (func
+      (return_call_indirect (type $t) (i32.const 0))
+    )
+  )
+  "tail calls support is not enabled"
+)
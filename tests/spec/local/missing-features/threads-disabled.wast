@@ -0,0 +1,16 @@
+(assert_invalid
+  (module
+    (memory 1 1 shared)
+  )
+  "threads must be enabled for shared memories"
+)
+
+(assert_invalid
+  (module
+    (memory 1 1)
+    (func (result i32)
+      (i32.atomic.load (i32.const 0))
+    )
+  )
+  "threads support is not enabled"
+)
@@ -1,13 +1,12 @@
 use super::{TestDescriptor, TestError, TestProfile, TestSpan};
 use anyhow::Result;
 use std::collections::HashMap;
-use wasmi::nan_preserving_float::{F32, F64};
-use wasmi_core::Value;
+use wasmi_core::ValueType;
 use wasmi_v1::{
     Config,
     Engine,
     Extern,
-    Func,
+    FuncType,
     Global,
     Instance,
     Linker,
@@ -15,9 +14,11 @@ use wasmi_v1::{
     MemoryType,
     Module,
     Mutability,
+    RefType,
     Store,
     Table,
     TableType,
+    Value,
 };
 use wast::Id;
 
@@ -51,45 +52,43 @@ impl<'a> TestContext<'a> {
     pub fn new(descriptor: &'a TestDescriptor, config: Config) -> Self {
         let engine = Engine::new(&config);
         let mut linker = Linker::default();
+        linker.allow_shadowing(true);
         let mut store = Store::new(&engine, ());
         let default_memory = Memory::new(&mut store, MemoryType::new(1, Some(2))).unwrap();
-        let default_table = Table::new(&mut store, TableType::new(10, Some(20)));
-        let global_i32 = Global::new(&mut store, Value::I32(666), Mutability::Const);
-        let global_f32 = Global::new(&mut store, Value::F32(666.0.into()), Mutability::Const);
-        let global_f64 = Global::new(&mut store, Value::F64(666.0.into()), Mutability::Const);
-        let print = Func::wrap(&mut store, || {
-            println!("print");
-        });
-        let print_i32 = Func::wrap(&mut store, |value: i32| {
-            println!("print: {}", value);
-        });
-        let print_f32 = Func::wrap(&mut store, |value: F32| {
-            println!("print: {:?}", value);
-        });
-        let print_f64 = Func::wrap(&mut store, |value: F64| {
-            println!("print: {:?}", value);
-        });
-        let print_i32_f32 = Func::wrap(&mut store, |v0: i32, v1: F32| {
-            println!("print: {:?} {:?}", v0, v1);
-        });
-        let print_f64_f64 = Func::wrap(&mut store, |v0: F64, v1: F64| {
-            println!("print: {:?} {:?}", v0, v1);
-        });
+        let default_table =
+            Table::new(&mut store, TableType::new(RefType::FuncRef, 10, Some(20))).unwrap();
+        let global_i32 = Global::new(&mut store, Value::I32(666), Mutability::Const).unwrap();
+        let global_f32 =
+            Global::new(&mut store, Value::F32(666.0.into()), Mutability::Const).unwrap();
+        let global_f64 =
+            Global::new(&mut store, Value::F64(666.0.into()), Mutability::Const).unwrap();
         linker.define("spectest", "memory", default_memory).unwrap();
         linker.define("spectest", "table", default_table).unwrap();
         linker.define("spectest", "global_i32", global_i32).unwrap();
         linker.define("spectest", "global_f32", global_f32).unwrap();
         linker.define("spectest", "global_f64", global_f64).unwrap();
-        linker.define("spectest", "print", print).unwrap();
-        linker.define("spectest", "print_i32", print_i32).unwrap();
-        linker.define("spectest", "print_f32", print_f32).unwrap();
-        linker.define("spectest", "print_f64", print_f64).unwrap();
-        linker
-            .define("spectest", "print_i32_f32", print_i32_f32)
-            .unwrap();
-        linker
-            .define("spectest", "print_f64_f64", print_f64_f64)
-            .unwrap();
+        let print_funcs = [
+            ("print", FuncType::new([], [])),
+            ("print_i32", FuncType::new([ValueType::I32], [])),
+            ("print_f32", FuncType::new([ValueType::F32], [])),
+            ("print_f64", FuncType::new([ValueType::F64], [])),
+            (
+                "print_i32_f32",
+                FuncType::new([ValueType::I32, ValueType::F32], []),
+            ),
+            (
+                "print_f64_f64",
+                FuncType::new([ValueType::F64, ValueType::F64], []),
+            ),
+        ];
+        for (name, func_type) in print_funcs {
+            linker
+                .func_new("spectest", name, func_type, |_caller, params, _results| {
+                    println!("print: {:?}", params);
+                    Ok(())
+                })
+                .unwrap();
+        }
         TestContext {
             engine,
             linker,
@@ -120,6 +119,16 @@ impl TestContext<'_> {
         &self.engine
     }
 
+    /// Returns a shared reference to the [`Store`] of the [`TestContext`].
+    pub fn store(&self) -> &Store<()> {
+        &self.store
+    }
+
+    /// Returns an exclusive reference to the [`Store`] of the [`TestContext`].
+    pub fn store_mut(&mut self) -> &mut Store<()> {
+        &mut self.store
+    }
+
     /// Returns an exclusive reference to the test profile.
     pub fn profile(&mut self) -> &mut TestProfile {
         &mut self.profile
@@ -148,9 +157,6 @@ impl TestContext<'_> {
         self.modules.push(module);
         if let Some(module_name) = module_name {
             self.instances.insert(module_name.to_string(), instance);
-            for (field_name, export) in instance.exports(&self.store) {
-                self.linker.define(module_name, field_name, *export)?;
-            }
         }
         self.last_instance = Some(instance);
         Ok(instance)
@@ -183,23 +189,15 @@ impl TestContext<'_> {
             })
     }
 
-    /// Registers the given [`Instance`] with the given `name` and sets it as the last instance.
+    /// Registers the exports of the given [`Instance`] under `name` and sets it as the last instance.
+    ///
+    /// # Note
+    ///
+    /// Registered exports shadow previously registered exports of the same name.
     pub fn register_instance(&mut self, name: &str, instance: Instance) {
-        if self.instances.get(name).is_some() {
-            // Already registered the instance.
-            return;
-        }
-        self.instances.insert(name.to_string(), instance);
-        for (field_name, export) in instance.exports(&self.store) {
-            self.linker
-                .define(name, field_name, *export)
-                .unwrap_or_else(|error| {
-                    panic!(
-                        "failed to define export {}::{}: {:?}: {}",
-                        name, field_name, export, error
-                    )
-                });
-        }
+        self.linker
+            .instance(&self.store, name, instance)
+            .unwrap_or_else(|error| panic!("failed to register instance {}: {}", name, error));
         self.last_instance = Some(instance);
     }
 
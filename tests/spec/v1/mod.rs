@@ -46,6 +46,15 @@ mod missing_features {
         fn wasm_mutable_global("missing-features/mutable-global-disabled");
         fn wasm_sign_extension("missing-features/sign-extension-disabled");
         fn wasm_saturating_float_to_int("missing-features/saturating-float-to-int-disabled");
+        fn wasm_bulk_memory("missing-features/bulk-memory-disabled");
+        fn wasm_reference_types("missing-features/reference-types-disabled");
+        fn wasm_tail_call("missing-features/tail-call-disabled");
+        fn wasm_simd("missing-features/simd-disabled");
+        fn wasm_multi_memory("missing-features/multi-memory-disabled");
+        fn wasm_memory64("missing-features/memory64-disabled");
+        fn wasm_threads("missing-features/threads-disabled");
+        fn wasm_exceptions("missing-features/exceptions-disabled");
+        fn wasm_extended_const("missing-features/extended-const-disabled");
     }
 }
 
@@ -113,6 +122,281 @@ mod multi_value {
     }
 }
 
+mod bulk_memory_operations {
+    use super::Config;
+
+    /// Run Wasm spec test suite using `bulk-memory` Wasm proposal enabled.
+    fn run_wasm_spec_test(file_name: &str) {
+        let config = Config::mvp()
+            .enable_mutable_global(true)
+            .enable_bulk_memory(true);
+        super::run::run_wasm_spec_test(file_name, config)
+    }
+
+    define_spec_tests! {
+        fn wasm_binary("proposals/bulk-memory-operations/binary");
+        fn wasm_bulk("proposals/bulk-memory-operations/bulk");
+        fn wasm_custom("proposals/bulk-memory-operations/custom");
+        fn wasm_data("proposals/bulk-memory-operations/data");
+        fn wasm_elem("proposals/bulk-memory-operations/elem");
+        fn wasm_imports("proposals/bulk-memory-operations/imports");
+        fn wasm_linking("proposals/bulk-memory-operations/linking");
+        fn wasm_memory_copy("proposals/bulk-memory-operations/memory_copy");
+        fn wasm_memory_fill("proposals/bulk-memory-operations/memory_fill");
+        fn wasm_memory_init("proposals/bulk-memory-operations/memory_init");
+        fn wasm_table_copy("proposals/bulk-memory-operations/table_copy");
+        fn wasm_table_init("proposals/bulk-memory-operations/table_init");
+    }
+}
+
+mod reference_types {
+    use super::Config;
+
+    /// Run Wasm spec test suite using `reference-types` Wasm proposal enabled.
+    fn run_wasm_spec_test(file_name: &str) {
+        let config = Config::mvp()
+            .enable_mutable_global(true)
+            .enable_sign_extension(true)
+            .enable_saturating_float_to_int(true)
+            .enable_multi_value(true)
+            .enable_bulk_memory(true)
+            .enable_reference_types(true);
+        super::run::run_wasm_spec_test(file_name, config)
+    }
+
+    define_spec_tests! {
+        fn wasm_binary("proposals/reference-types/binary");
+        fn wasm_br_table("proposals/reference-types/br_table");
+        fn wasm_bulk("proposals/reference-types/bulk");
+        fn wasm_call_indirect("proposals/reference-types/call_indirect");
+        fn wasm_elem("proposals/reference-types/elem");
+        fn wasm_exports("proposals/reference-types/exports");
+        fn wasm_global("proposals/reference-types/global");
+        fn wasm_imports("proposals/reference-types/imports");
+        fn wasm_linking("proposals/reference-types/linking");
+        fn wasm_memory_copy("proposals/reference-types/memory_copy");
+        fn wasm_memory_fill("proposals/reference-types/memory_fill");
+        fn wasm_memory_init("proposals/reference-types/memory_init");
+        fn wasm_ref_func("proposals/reference-types/ref_func");
+        fn wasm_ref_is_null("proposals/reference-types/ref_is_null");
+        fn wasm_ref_null("proposals/reference-types/ref_null");
+        fn wasm_select("proposals/reference-types/select");
+        fn wasm_table_sub("proposals/reference-types/table-sub");
+        fn wasm_table("proposals/reference-types/table");
+        fn wasm_table_copy("proposals/reference-types/table_copy");
+        fn wasm_table_fill("proposals/reference-types/table_fill");
+        fn wasm_table_get("proposals/reference-types/table_get");
+        fn wasm_table_grow("proposals/reference-types/table_grow");
+        fn wasm_table_init("proposals/reference-types/table_init");
+        fn wasm_table_set("proposals/reference-types/table_set");
+        fn wasm_table_size("proposals/reference-types/table_size");
+        fn wasm_unreached_invalid("proposals/reference-types/unreached-invalid");
+    }
+}
+
+mod tail_call {
+    use super::Config;
+
+    /// Run Wasm spec test suite using `tail-call` Wasm proposal enabled.
+    fn run_wasm_spec_test(file_name: &str) {
+        let config = Config::mvp()
+            .enable_mutable_global(true)
+            .enable_sign_extension(true)
+            .enable_saturating_float_to_int(true)
+            .enable_multi_value(true)
+            .enable_bulk_memory(true)
+            .enable_reference_types(true)
+            .enable_tail_call(true);
+        super::run::run_wasm_spec_test(file_name, config)
+    }
+
+    define_spec_tests! {
+        fn wasm_return_call("proposals/tail-call/return_call");
+        fn wasm_return_call_indirect("proposals/tail-call/return_call_indirect");
+    }
+}
+
+#[cfg(feature = "v1-simd")]
+mod simd {
+    use super::Config;
+
+    /// Run Wasm spec test suite using `simd` Wasm proposal enabled.
+    fn run_wasm_spec_test(file_name: &str) {
+        let config = Config::default()
+            .enable_bulk_memory(true)
+            .enable_reference_types(true)
+            .enable_simd(true);
+        super::run::run_wasm_spec_test(file_name, config)
+    }
+
+    define_spec_tests! {
+        fn wasm_simd_address("simd_address");
+        fn wasm_simd_align("simd_align");
+        fn wasm_simd_bit_shift("simd_bit_shift");
+        fn wasm_simd_bitwise("simd_bitwise");
+        fn wasm_simd_boolean("simd_boolean");
+        fn wasm_simd_const("simd_const");
+        fn wasm_simd_conversions("simd_conversions");
+        fn wasm_simd_f32x4("simd_f32x4");
+        fn wasm_simd_f32x4_arith("simd_f32x4_arith");
+        fn wasm_simd_f32x4_cmp("simd_f32x4_cmp");
+        fn wasm_simd_f32x4_pmin_pmax("simd_f32x4_pmin_pmax");
+        fn wasm_simd_f32x4_rounding("simd_f32x4_rounding");
+        fn wasm_simd_f64x2("simd_f64x2");
+        fn wasm_simd_f64x2_arith("simd_f64x2_arith");
+        fn wasm_simd_f64x2_cmp("simd_f64x2_cmp");
+        fn wasm_simd_f64x2_pmin_pmax("simd_f64x2_pmin_pmax");
+        fn wasm_simd_f64x2_rounding("simd_f64x2_rounding");
+        fn wasm_simd_i16x8_arith("simd_i16x8_arith");
+        fn wasm_simd_i16x8_arith2("simd_i16x8_arith2");
+        fn wasm_simd_i16x8_cmp("simd_i16x8_cmp");
+        fn wasm_simd_i16x8_extadd_pairwise_i8x16("simd_i16x8_extadd_pairwise_i8x16");
+        fn wasm_simd_i16x8_extmul_i8x16("simd_i16x8_extmul_i8x16");
+        fn wasm_simd_i16x8_q15mulr_sat_s("simd_i16x8_q15mulr_sat_s");
+        fn wasm_simd_i16x8_sat_arith("simd_i16x8_sat_arith");
+        fn wasm_simd_i32x4_arith("simd_i32x4_arith");
+        fn wasm_simd_i32x4_arith2("simd_i32x4_arith2");
+        fn wasm_simd_i32x4_cmp("simd_i32x4_cmp");
+        fn wasm_simd_i32x4_dot_i16x8("simd_i32x4_dot_i16x8");
+        fn wasm_simd_i32x4_extadd_pairwise_i16x8("simd_i32x4_extadd_pairwise_i16x8");
+        fn wasm_simd_i32x4_extmul_i16x8("simd_i32x4_extmul_i16x8");
+        fn wasm_simd_i32x4_trunc_sat_f32x4("simd_i32x4_trunc_sat_f32x4");
+        fn wasm_simd_i32x4_trunc_sat_f64x2("simd_i32x4_trunc_sat_f64x2");
+        fn wasm_simd_i64x2_arith("simd_i64x2_arith");
+        fn wasm_simd_i64x2_arith2("simd_i64x2_arith2");
+        fn wasm_simd_i64x2_cmp("simd_i64x2_cmp");
+        fn wasm_simd_i64x2_extmul_i32x4("simd_i64x2_extmul_i32x4");
+        fn wasm_simd_i8x16_arith("simd_i8x16_arith");
+        fn wasm_simd_i8x16_arith2("simd_i8x16_arith2");
+        fn wasm_simd_i8x16_cmp("simd_i8x16_cmp");
+        fn wasm_simd_i8x16_sat_arith("simd_i8x16_sat_arith");
+        fn wasm_simd_int_to_int_extend("simd_int_to_int_extend");
+        fn wasm_simd_lane("simd_lane");
+        fn wasm_simd_linking("simd_linking");
+        fn wasm_simd_load("simd_load");
+        fn wasm_simd_load16_lane("simd_load16_lane");
+        fn wasm_simd_load32_lane("simd_load32_lane");
+        fn wasm_simd_load64_lane("simd_load64_lane");
+        fn wasm_simd_load8_lane("simd_load8_lane");
+        fn wasm_simd_load_extend("simd_load_extend");
+        fn wasm_simd_load_splat("simd_load_splat");
+        fn wasm_simd_load_zero("simd_load_zero");
+        fn wasm_simd_splat("simd_splat");
+        fn wasm_simd_store("simd_store");
+        fn wasm_simd_store16_lane("simd_store16_lane");
+        fn wasm_simd_store32_lane("simd_store32_lane");
+        fn wasm_simd_store64_lane("simd_store64_lane");
+        fn wasm_simd_store8_lane("simd_store8_lane");
+    }
+}
+
+mod multi_memory {
+    use super::Config;
+
+    /// Run Wasm spec test suite using `multi-memory` Wasm proposal enabled.
+    fn run_wasm_spec_test(file_name: &str) {
+        let config = Config::default()
+            .enable_bulk_memory(true)
+            .enable_reference_types(true)
+            .enable_multi_memory(true);
+        super::run::run_wasm_spec_test(file_name, config)
+    }
+
+    define_spec_tests! {
+        fn wasm_binary("proposals/multi-memory/binary");
+        fn wasm_data("proposals/multi-memory/data");
+        fn wasm_imports("proposals/multi-memory/imports");
+        fn wasm_load("proposals/multi-memory/load");
+        fn wasm_memory("proposals/multi-memory/memory");
+        fn wasm_memory_grow("proposals/multi-memory/memory_grow");
+        fn wasm_memory_size("proposals/multi-memory/memory_size");
+        fn wasm_store("proposals/multi-memory/store");
+    }
+}
+
+mod memory64 {
+    use super::Config;
+
+    /// Run Wasm spec test suite using `memory64` Wasm proposal enabled.
+    fn run_wasm_spec_test(file_name: &str) {
+        let config = Config::default()
+            .enable_bulk_memory(true)
+            .enable_reference_types(true)
+            .enable_memory64(true);
+        super::run::run_wasm_spec_test(file_name, config)
+    }
+
+    define_spec_tests! {
+        fn wasm_address64("proposals/memory64/address64");
+        fn wasm_align64("proposals/memory64/align64");
+        fn wasm_endianness64("proposals/memory64/endianness64");
+        fn wasm_float_memory64("proposals/memory64/float_memory64");
+        fn wasm_load64("proposals/memory64/load64");
+        fn wasm_memory64("proposals/memory64/memory64");
+        fn wasm_memory_grow64("proposals/memory64/memory_grow64");
+        fn wasm_memory_redundancy64("proposals/memory64/memory_redundancy64");
+        fn wasm_memory_trap64("proposals/memory64/memory_trap64");
+    }
+}
+
+#[cfg(feature = "v1-threads")]
+mod threads {
+    use super::Config;
+
+    /// Run Wasm spec test suite using `threads` Wasm proposal enabled.
+    fn run_wasm_spec_test(file_name: &str) {
+        let config = Config::default()
+            .enable_bulk_memory(true)
+            .enable_reference_types(true)
+            .enable_threads(true);
+        super::run::run_wasm_spec_test(file_name, config)
+    }
+
+    define_spec_tests! {
+        fn wasm_atomic("proposals/threads/atomic");
+    }
+}
+
+mod exceptions {
+    use super::Config;
+
+    /// Run Wasm spec test suite using `exception-handling` Wasm proposal enabled.
+    fn run_wasm_spec_test(file_name: &str) {
+        let config = Config::default()
+            .enable_bulk_memory(true)
+            .enable_reference_types(true)
+            .enable_exceptions(true);
+        super::run::run_wasm_spec_test(file_name, config)
+    }
+
+    define_spec_tests! {
+        fn wasm_rethrow("proposals/exception-handling/rethrow");
+        fn wasm_tag("proposals/exception-handling/tag");
+        fn wasm_throw("proposals/exception-handling/throw");
+        fn wasm_try_catch("proposals/exception-handling/try_catch");
+        fn wasm_try_delegate("proposals/exception-handling/try_delegate");
+    }
+}
+
+mod extended_const {
+    use super::Config;
+
+    /// Run Wasm spec test suite using `extended-const` Wasm proposal enabled.
+    fn run_wasm_spec_test(file_name: &str) {
+        let config = Config::default()
+            .enable_bulk_memory(true)
+            .enable_reference_types(true)
+            .enable_extended_const(true);
+        super::run::run_wasm_spec_test(file_name, config)
+    }
+
+    define_spec_tests! {
+        fn wasm_data("proposals/extended-const/data");
+        fn wasm_elem("proposals/extended-const/elem");
+        fn wasm_global("proposals/extended-const/global");
+    }
+}
+
 define_spec_tests! {
     fn wasm_address("address");
     fn wasm_align("align");
@@ -158,7 +442,7 @@ define_spec_tests! {
     fn wasm_int_literals("int_literals");
     fn wasm_labels("labels");
     fn wasm_left_to_right("left-to-right");
-    #[ignore] fn wasm_linking("linking");
+    fn wasm_linking("linking");
     fn wasm_loop("loop");
     fn wasm_load("load");
     fn wasm_local_get("local_get");
@@ -1,11 +1,12 @@
 use super::{error::TestError, TestContext, TestDescriptor};
 use anyhow::Result;
-use wasmi_core::{Trap, Value, F32, F64};
-use wasmi_v1::{Config, Error as WasmiError};
+use wasmi_core::{Trap, F32, F64};
+use wasmi_v1::{Config, Error as WasmiError, ExternRef, FuncRef, Value};
 use wast::{
     lexer::Lexer,
     parser::ParseBuffer,
     AssertExpression,
+    HeapType,
     NanPattern,
     QuoteModule,
     Span,
@@ -14,9 +15,24 @@ use wast::{
     WastExecute,
     WastInvoke,
 };
+#[cfg(feature = "v1-simd")]
+use {
+    wasmi_core::V128,
+    wast::{V128Const, V128Pattern},
+};
 
 /// Runs the Wasm test spec identified by the given name.
+///
+/// # Note
+///
+/// The Wasm test spec is run with and without bytecode optimizations.
 pub fn run_wasm_spec_test(name: &str, config: Config) {
+    run_wasm_spec_test_with(name, config.enable_bytecode_optimizations(false));
+    run_wasm_spec_test_with(name, config.enable_bytecode_optimizations(true));
+}
+
+/// Runs the Wasm test spec identified by the given name using the `config`.
+fn run_wasm_spec_test_with(name: &str, config: Config) {
     let test = TestDescriptor::new(name);
     let mut context = TestContext::new(&test, config);
 
@@ -179,7 +195,12 @@ fn execute_directives(wast: Wast, test_context: &mut TestContext) -> Result<()>
                         test_context.spanned(span),
                         results
                     ),
-                    Err(_) => {}
+                    Err(TestError::Wasmi(WasmiError::Trap(Trap::Exception(_)))) => {}
+                    Err(error) => panic!(
+                        "{}: expected to fail due to exception but failed with: {}",
+                        test_context.spanned(span),
+                        error
+                    ),
                 }
             }
         }
@@ -264,6 +285,31 @@ fn assert_results(
             (Value::F64(result), AssertExpression::LegacyCanonicalNaN) => {
                 assert!(result.is_nan(), "in {}", context.spanned(span))
             }
+            (Value::FuncRef(result), AssertExpression::RefNull(Some(HeapType::Func))) => {
+                assert!(result.is_null(), "in {}", context.spanned(span))
+            }
+            (Value::FuncRef(result), AssertExpression::RefFunc(_)) => {
+                assert!(!result.is_null(), "in {}", context.spanned(span))
+            }
+            (Value::ExternRef(result), AssertExpression::RefNull(Some(HeapType::Extern))) => {
+                assert!(result.is_null(), "in {}", context.spanned(span))
+            }
+            (Value::ExternRef(result), AssertExpression::RefExtern(expected)) => {
+                let result = result
+                    .data(context.store())
+                    .and_then(|data| data.downcast_ref::<u32>());
+                assert_eq!(result, Some(expected), "in {}", context.spanned(span))
+            }
+            #[cfg(feature = "v1-simd")]
+            (Value::V128(result), AssertExpression::V128(expected)) => {
+                assert!(
+                    v128_matches(*result, expected),
+                    "in {}: expected {:?} but found {:?}",
+                    context.spanned(span),
+                    expected,
+                    result,
+                )
+            }
             (result, expected) => panic!(
                 "{}: encountered mismatch in evaluation. expected {:?} but found {:?}",
                 context.spanned(span),
@@ -274,6 +320,47 @@ fn assert_results(
     }
 }
 
+/// Returns `true` if the `v128` `result` matches the `expected` pattern.
+///
+/// # Note
+///
+/// Floating point lanes with a NaN pattern match any NaN value.
+#[cfg(feature = "v1-simd")]
+fn v128_matches(result: V128, expected: &V128Pattern) -> bool {
+    let bytes = result.to_bits().to_le_bytes();
+    let expected = match expected {
+        V128Pattern::I8x16(lanes) => V128Const::I8x16(*lanes),
+        V128Pattern::I16x8(lanes) => V128Const::I16x8(*lanes),
+        V128Pattern::I32x4(lanes) => V128Const::I32x4(*lanes),
+        V128Pattern::I64x2(lanes) => V128Const::I64x2(*lanes),
+        V128Pattern::F32x4(lanes) => {
+            return lanes
+                .iter()
+                .zip(bytes.chunks_exact(4))
+                .all(|(expected, lane)| {
+                    let lane = F32::from_bits(u32::from_le_bytes(lane.try_into().unwrap()));
+                    match expected {
+                        NanPattern::CanonicalNan | NanPattern::ArithmeticNan => lane.is_nan(),
+                        NanPattern::Value(expected) => lane.to_bits() == expected.bits,
+                    }
+                })
+        }
+        V128Pattern::F64x2(lanes) => {
+            return lanes
+                .iter()
+                .zip(bytes.chunks_exact(8))
+                .all(|(expected, lane)| {
+                    let lane = F64::from_bits(u64::from_le_bytes(lane.try_into().unwrap()));
+                    match expected {
+                        NanPattern::CanonicalNan | NanPattern::ArithmeticNan => lane.is_nan(),
+                        NanPattern::Value(expected) => lane.to_bits() == expected.bits,
+                    }
+                })
+        }
+    };
+    bytes == expected.to_le_bytes()
+}
+
 fn extract_module(quoted_module: QuoteModule) -> Option<wast::Module> {
     match quoted_module {
         QuoteModule::Module(module) => Some(module),
@@ -340,6 +427,15 @@ fn execute_wast_invoke(
             wast::Instruction::I64Const(value) => Value::I64(*value),
             wast::Instruction::F32Const(value) => Value::F32(F32::from_bits(value.bits)),
             wast::Instruction::F64Const(value) => Value::F64(F64::from_bits(value.bits)),
+            wast::Instruction::RefNull(HeapType::Func) => Value::FuncRef(FuncRef::null()),
+            wast::Instruction::RefNull(HeapType::Extern) => Value::ExternRef(ExternRef::null()),
+            wast::Instruction::RefExtern(value) => {
+                Value::ExternRef(ExternRef::new(context.store_mut(), *value))
+            }
+            #[cfg(feature = "v1-simd")]
+            wast::Instruction::V128Const(value) => {
+                Value::V128(V128::from_bits(u128::from_le_bytes(value.to_le_bytes())))
+            }
             unsupported => panic!(
                 "{}: encountered unsupported invoke instruction: {:?}",
                 context.spanned(span),
@@ -1,4 +1,4 @@
-use super::{Arena, Index, Iter, IterMut};
+use super::{Arena, ArenaIndex, Iter, IterMut};
 use alloc::collections::BTreeMap;
 use core::ops;
 
@@ -66,7 +66,7 @@ impl<Idx, T> DedupArena<Idx, T> {
 
 impl<Idx, T> DedupArena<Idx, T>
 where
-    Idx: Index,
+    Idx: ArenaIndex,
     T: Ord + Clone,
 {
     /// Returns the next entity index.
@@ -104,7 +104,7 @@ where
 
 impl<Idx, T> FromIterator<T> for DedupArena<Idx, T>
 where
-    Idx: Index,
+    Idx: ArenaIndex,
     T: Clone + Ord,
 {
     fn from_iter<I>(iter: I) -> Self
@@ -125,7 +125,7 @@ where
 
 impl<'a, Idx, T> IntoIterator for &'a DedupArena<Idx, T>
 where
-    Idx: Index,
+    Idx: ArenaIndex,
 {
     type IntoIter = Iter<'a, Idx, T>;
     type Item = (Idx, &'a T);
@@ -137,7 +137,7 @@ where
 
 impl<'a, Idx, T> IntoIterator for &'a mut DedupArena<Idx, T>
 where
-    Idx: Index,
+    Idx: ArenaIndex,
 {
     type IntoIter = IterMut<'a, Idx, T>;
     type Item = (Idx, &'a mut T);
@@ -149,7 +149,7 @@ where
 
 impl<Idx, T> ops::Index<Idx> for DedupArena<Idx, T>
 where
-    Idx: Index,
+    Idx: ArenaIndex,
 {
     type Output = T;
 
@@ -160,7 +160,7 @@ where
 
 impl<Idx, T> ops::IndexMut<Idx> for DedupArena<Idx, T>
 where
-    Idx: Index,
+    Idx: ArenaIndex,
 {
     fn index_mut(&mut self, index: Idx) -> &mut Self::Output {
         &mut self.entities[index]
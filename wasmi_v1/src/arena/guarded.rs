@@ -1,4 +1,5 @@
-use crate::arena::Index;
+use crate::arena::{ArenaIndex, Index};
+use core::num::NonZeroU64;
 
 /// A guarded entity.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -20,7 +21,7 @@ impl<GuardIdx, EntityIdx> GuardedEntity<GuardIdx, EntityIdx> {
 impl<GuardIdx, EntityIdx> GuardedEntity<GuardIdx, EntityIdx>
 where
     GuardIdx: Index,
-    EntityIdx: Index,
+    EntityIdx: Copy,
 {
     /// Returns the entity index of the [`GuardedEntity`].
     ///
@@ -32,3 +33,58 @@ where
         Some(self.entity_idx)
     }
 }
+
+/// The maximum guard index that can be encoded by [`GuardedEntity::to_bits`].
+pub const MAX_ENCODED_GUARD: usize = u32::MAX as usize - 1;
+
+/// The maximum entity slot that can be encoded by [`GuardedEntity::to_bits`].
+pub const MAX_ENCODED_SLOT: u32 = (1 << 24) - 1;
+
+/// The maximum entity generation that can be encoded by [`GuardedEntity::to_bits`].
+pub const MAX_ENCODED_GENERATION: u32 = (1 << 8) - 1;
+
+impl<GuardIdx, EntityIdx> GuardedEntity<GuardIdx, EntityIdx>
+where
+    GuardIdx: Index,
+    EntityIdx: ArenaIndex,
+{
+    /// Encodes the [`GuardedEntity`] into a non-zero `u64` value.
+    ///
+    /// # Note
+    ///
+    /// This is used to store references to entities as untyped
+    /// 64-bit values, for example on the value stack of the `wasmi`
+    /// interpreter. The zero value can be used to represent `null`.
+    ///
+    /// The guard index is stored in the high 32 bits followed by
+    /// 8 bits for the generation and 24 bits for the slot of the
+    /// entity index.
+    ///
+    /// Callers have to make sure that all values are within bounds:
+    /// store indices never exceed [`MAX_ENCODED_GUARD`] and the arenas
+    /// of encoded entities are limited to [`MAX_ENCODED_SLOT`] and
+    /// [`MAX_ENCODED_GENERATION`] via their [`ArenaIndex`] constants.
+    pub fn to_bits(self) -> NonZeroU64 {
+        let guard_idx = self.guard_idx.into_usize();
+        debug_assert!(guard_idx <= MAX_ENCODED_GUARD);
+        let entity_key = self.entity_idx.into_key();
+        let slot = entity_key & u64::from(u32::MAX);
+        let generation = entity_key >> 32;
+        debug_assert!(slot <= u64::from(MAX_ENCODED_SLOT));
+        debug_assert!(generation <= u64::from(MAX_ENCODED_GENERATION));
+        let bits = ((guard_idx as u64 + 1) << 32) | (generation << 24) | slot;
+        NonZeroU64::new(bits).expect("the encoded guard index is always non-zero")
+    }
+
+    /// Decodes a [`GuardedEntity`] from a value created by [`GuardedEntity::to_bits`].
+    pub fn from_bits(bits: NonZeroU64) -> Self {
+        let bits = bits.get();
+        let guard_idx = ((bits >> 32) as usize).wrapping_sub(1);
+        let generation = (bits >> 24) & u64::from(MAX_ENCODED_GENERATION);
+        let slot = bits & u64::from(MAX_ENCODED_SLOT);
+        Self::new(
+            GuardIdx::from_usize(guard_idx),
+            EntityIdx::from_key((generation << 32) | slot),
+        )
+    }
+}
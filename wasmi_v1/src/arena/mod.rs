@@ -1,6 +1,8 @@
 //! Fast arena allocators for different usage purposes.
 //!
-//! They cannot deallocate single allocated entities for extra efficiency.
+//! Removed entities leave a vacant slot behind that is reused by later
+//! allocations. Indices carry the generation of their slot so that indices
+//! of removed entities never refer to other entities.
 //! These allocators mainly serve as the backbone for an efficient Wasm store
 //! implementation.
 
@@ -10,7 +12,10 @@ mod guarded;
 #[cfg(test)]
 mod tests;
 
-pub use self::{dedup::DedupArena, guarded::GuardedEntity};
+pub use self::{
+    dedup::DedupArena,
+    guarded::{GuardedEntity, MAX_ENCODED_GENERATION, MAX_ENCODED_GUARD, MAX_ENCODED_SLOT},
+};
 use alloc::vec::Vec;
 use core::{
     iter,
@@ -28,12 +33,92 @@ pub trait Index: Copy {
     fn from_usize(value: usize) -> Self;
 }
 
+/// Types that can be used as indices for the entities of an [`Arena`].
+///
+/// # Note
+///
+/// The 64-bit key of an index stores the slot of its entity in the low
+/// 32 bits and the generation of the slot in the high 32 bits.
+pub trait ArenaIndex: Copy {
+    /// The maximum slot of an [`Arena`] using this index type.
+    const MAX_SLOT: u32 = u32::MAX;
+    /// The maximum generation of a slot of an [`Arena`] using this index type.
+    ///
+    /// Slots whose generations are exhausted are no longer reused.
+    const MAX_GENERATION: u32 = u32::MAX;
+
+    /// Converts the [`ArenaIndex`] into its underlying key.
+    fn into_key(self) -> u64;
+    /// Converts the key into the associated [`ArenaIndex`].
+    fn from_key(key: u64) -> Self;
+}
+
+/// A slot of an [`Arena`].
+#[derive(Debug, PartialEq, Eq)]
+struct Slot<T> {
+    /// The generation of the slot.
+    ///
+    /// This is incremented whenever the entity of the slot is removed.
+    generation: u32,
+    /// The entity of the slot or `None` if the slot is vacant.
+    entity: Option<T>,
+}
+
+impl<T> Slot<T> {
+    /// Returns the entity of the slot if the `generation` matches.
+    fn get(&self, generation: u32) -> Option<&T> {
+        if self.generation != generation {
+            return None;
+        }
+        self.entity.as_ref()
+    }
+
+    /// Returns the entity of the slot if the `generation` matches.
+    fn get_mut(&mut self, generation: u32) -> Option<&mut T> {
+        if self.generation != generation {
+            return None;
+        }
+        self.entity.as_mut()
+    }
+}
+
+/// Returns the index of the entity at `slot` with the given `generation`.
+fn encode_index<Idx>(slot: usize, generation: u32) -> Idx
+where
+    Idx: ArenaIndex,
+{
+    debug_assert!(slot <= Idx::MAX_SLOT as usize);
+    debug_assert!(generation <= Idx::MAX_GENERATION);
+    Idx::from_key((u64::from(generation) << 32) | slot as u64)
+}
+
+/// Returns the slot and generation of the entity at `index`.
+fn decode_index<Idx>(index: Idx) -> (usize, u32)
+where
+    Idx: ArenaIndex,
+{
+    let key = index.into_key();
+    let slot = (key & u64::from(u32::MAX)) as usize;
+    let generation = (key >> 32) as u32;
+    (slot, generation)
+}
+
 /// An arena allocator with a given index and entity type.
 ///
-/// For performance reasons the arena cannot deallocate single entities.
+/// # Note
+///
+/// Removing an entity drops it and leaves its slot vacant for later allocations.
+/// Each slot has a generation that is incremented upon removal and that is part
+/// of the indices of the entities of the slot. Therefore indices of removed
+/// entities never refer to entities allocated afterwards.
+/// Slots whose generations are exhausted are no longer reused.
 #[derive(Debug)]
 pub struct Arena<Idx, T> {
-    entities: Vec<T>,
+    entities: Vec<Slot<T>>,
+    /// The vacant slots available for reuse.
+    vacant: Vec<usize>,
+    /// The amount of entities that have not been removed.
+    len: usize,
     __marker: PhantomData<fn() -> Idx>,
 }
 
@@ -59,13 +144,15 @@ impl<Idx, T> Arena<Idx, T> {
     pub fn new() -> Self {
         Self {
             entities: Vec::new(),
+            vacant: Vec::new(),
+            len: 0,
             __marker: PhantomData,
         }
     }
 
-    /// Returns the allocated number of entities.
+    /// Returns the allocated number of entities that have not been removed.
     pub fn len(&self) -> usize {
-        self.entities.len()
+        self.len
     }
 
     /// Returns `true` if the arena has not yet allocated entities.
@@ -74,14 +161,22 @@ impl<Idx, T> Arena<Idx, T> {
     }
 
     /// Clears all entities from the arena.
+    ///
+    /// # Note
+    ///
+    /// Unlike removing entities this also resets the generations of all
+    /// slots, so indices of cleared entities may refer to later entities.
     pub fn clear(&mut self) {
         self.entities.clear();
+        self.vacant.clear();
+        self.len = 0;
     }
 
     /// Returns an iterator over the shared reference of the arena entities.
     pub fn iter(&self) -> Iter<Idx, T> {
         Iter {
             iter: self.entities.iter().enumerate(),
+            len: self.len,
             __marker: PhantomData,
         }
     }
@@ -90,6 +185,7 @@ impl<Idx, T> Arena<Idx, T> {
     pub fn iter_mut(&mut self) -> IterMut<Idx, T> {
         IterMut {
             iter: self.entities.iter_mut().enumerate(),
+            len: self.len,
             __marker: PhantomData,
         }
     }
@@ -97,28 +193,106 @@ impl<Idx, T> Arena<Idx, T> {
 
 impl<Idx, T> Arena<Idx, T>
 where
-    Idx: Index,
+    Idx: ArenaIndex,
 {
     /// Returns the next entity index.
     fn next_index(&self) -> Idx {
-        Idx::from_usize(self.entities.len())
+        match self.vacant.last() {
+            Some(&slot) => encode_index(slot, self.entities[slot].generation),
+            None => encode_index(self.entities.len(), 0),
+        }
+    }
+
+    /// Returns `true` if the arena is able to allocate `additional` entities.
+    pub fn can_alloc(&self, additional: usize) -> bool {
+        let max_slots = u64::from(Idx::MAX_SLOT) + 1;
+        let unused_slots = max_slots - self.entities.len() as u64;
+        let available = unused_slots.saturating_add(self.vacant.len() as u64);
+        additional as u64 <= available
     }
 
     /// Allocates a new entity and returns its index.
+    ///
+    /// # Note
+    ///
+    /// The slot of a removed entity is reused if available.
+    ///
+    /// # Panics
+    ///
+    /// If the arena has run out of slots, see [`Arena::can_alloc`].
     pub fn alloc(&mut self, entity: T) -> Idx {
-        let index = self.next_index();
-        self.entities.push(entity);
+        let index = match self.vacant.pop() {
+            Some(slot) => {
+                let vacant = &mut self.entities[slot];
+                debug_assert!(vacant.entity.is_none());
+                vacant.entity = Some(entity);
+                encode_index(slot, vacant.generation)
+            }
+            None => {
+                let slot = self.entities.len();
+                assert!(slot <= Idx::MAX_SLOT as usize, "arena is out of slots");
+                self.entities.push(Slot {
+                    generation: 0,
+                    entity: Some(entity),
+                });
+                encode_index(slot, 0)
+            }
+        };
+        self.len += 1;
         index
     }
 
+    /// Removes the entity at the given index and returns it if any.
+    ///
+    /// # Note
+    ///
+    /// The slot of the removed entity is reused by later allocations
+    /// unless its generation is exhausted.
+    pub fn remove(&mut self, index: Idx) -> Option<T> {
+        let (slot, generation) = decode_index(index);
+        let removed = self.entities.get_mut(slot)?;
+        if removed.generation != generation {
+            return None;
+        }
+        let entity = removed.entity.take()?;
+        self.len -= 1;
+        if removed.generation < Idx::MAX_GENERATION {
+            removed.generation += 1;
+            self.vacant.push(slot);
+        }
+        Some(entity)
+    }
+
     /// Returns a shared reference to the entity at the given index if any.
     pub fn get(&self, index: Idx) -> Option<&T> {
-        self.entities.get(index.into_usize())
+        let (slot, generation) = decode_index(index);
+        self.entities.get(slot)?.get(generation)
     }
 
     /// Returns an exclusive reference to the entity at the given index if any.
     pub fn get_mut(&mut self, index: Idx) -> Option<&mut T> {
-        self.entities.get_mut(index.into_usize())
+        let (slot, generation) = decode_index(index);
+        self.entities.get_mut(slot)?.get_mut(generation)
+    }
+
+    /// Returns an exclusive reference to the pair of entities at the given indices if any.
+    ///
+    /// Returns `None` if `fst` and `snd` refer to the same entity.
+    pub fn get_pair_mut(&mut self, fst: Idx, snd: Idx) -> Option<(&mut T, &mut T)> {
+        let (fst_index, fst_generation) = decode_index(fst);
+        let (snd_index, snd_generation) = decode_index(snd);
+        let max_index = core::cmp::max(fst_index, snd_index);
+        if fst_index == snd_index || max_index >= self.entities.len() {
+            return None;
+        }
+        let (fst, snd) = if fst_index < snd_index {
+            let (fst_set, snd_set) = self.entities.split_at_mut(snd_index);
+            (&mut fst_set[fst_index], &mut snd_set[0])
+        } else {
+            let (snd_set, fst_set) = self.entities.split_at_mut(fst_index);
+            (&mut fst_set[0], &mut snd_set[snd_index])
+        };
+        Some((fst.get_mut(fst_generation)?, snd.get_mut(snd_generation)?))
     }
 }
 
@@ -127,8 +301,17 @@ impl<Idx, T> FromIterator<T> for Arena<Idx, T> {
     where
         I: IntoIterator<Item = T>,
     {
+        let entities = iter
+            .into_iter()
+            .map(|entity| Slot {
+                generation: 0,
+                entity: Some(entity),
+            })
+            .collect::<Vec<_>>();
         Self {
-            entities: Vec::from_iter(iter),
+            len: entities.len(),
+            entities,
+            vacant: Vec::new(),
             __marker: PhantomData,
         }
     }
@@ -136,7 +319,7 @@ impl<Idx, T> FromIterator<T> for Arena<Idx, T> {
 
 impl<'a, Idx, T> IntoIterator for &'a Arena<Idx, T>
 where
-    Idx: Index,
+    Idx: ArenaIndex,
 {
     type IntoIter = Iter<'a, Idx, T>;
     type Item = (Idx, &'a T);
@@ -148,7 +331,7 @@ where
 
 impl<'a, Idx, T> IntoIterator for &'a mut Arena<Idx, T>
 where
-    Idx: Index,
+    Idx: ArenaIndex,
 {
     type IntoIter = IterMut<'a, Idx, T>;
     type Item = (Idx, &'a mut T);
@@ -161,94 +344,110 @@ where
 /// An iterator over shared references of arena entities and their indices.
 #[derive(Debug)]
 pub struct Iter<'a, Idx, T> {
-    iter: iter::Enumerate<slice::Iter<'a, T>>,
+    iter: iter::Enumerate<slice::Iter<'a, Slot<T>>>,
+    /// The amount of remaining entities that have not been removed.
+    len: usize,
     __marker: PhantomData<fn() -> Idx>,
 }
 
 impl<'a, Idx, T> Iterator for Iter<'a, Idx, T>
 where
-    Idx: Index,
+    Idx: ArenaIndex,
 {
     type Item = (Idx, &'a T);
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.iter.size_hint()
+        (self.len, Some(self.len))
     }
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter
-            .next()
-            .map(|(idx, entity)| (Idx::from_usize(idx), entity))
+        let found = self.iter.find_map(|(slot, entity)| {
+            let index = encode_index(slot, entity.generation);
+            Some((index, entity.entity.as_ref()?))
+        })?;
+        self.len -= 1;
+        Some(found)
     }
 }
 
 impl<'a, Idx, T> DoubleEndedIterator for Iter<'a, Idx, T>
 where
-    Idx: Index,
+    Idx: ArenaIndex,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
-        self.iter
-            .next()
-            .map(|(idx, entity)| (Idx::from_usize(idx), entity))
+        let found = self.iter.by_ref().rev().find_map(|(slot, entity)| {
+            let index = encode_index(slot, entity.generation);
+            Some((index, entity.entity.as_ref()?))
+        })?;
+        self.len -= 1;
+        Some(found)
     }
 }
 
 impl<'a, Idx, T> ExactSizeIterator for Iter<'a, Idx, T>
 where
-    Idx: Index,
+    Idx: ArenaIndex,
 {
     fn len(&self) -> usize {
-        self.iter.len()
+        self.len
     }
 }
 
 /// An iterator over exlusive references of arena entities and their indices.
 #[derive(Debug)]
 pub struct IterMut<'a, Idx, T> {
-    iter: iter::Enumerate<slice::IterMut<'a, T>>,
+    iter: iter::Enumerate<slice::IterMut<'a, Slot<T>>>,
+    /// The amount of remaining entities that have not been removed.
+    len: usize,
     __marker: PhantomData<fn() -> Idx>,
 }
 
 impl<'a, Idx, T> Iterator for IterMut<'a, Idx, T>
 where
-    Idx: Index,
+    Idx: ArenaIndex,
 {
     type Item = (Idx, &'a mut T);
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.iter.size_hint()
+        (self.len, Some(self.len))
     }
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter
-            .next()
-            .map(|(idx, entity)| (Idx::from_usize(idx), entity))
+        let found = self.iter.find_map(|(slot, entity)| {
+            let index = encode_index(slot, entity.generation);
+            Some((index, entity.entity.as_mut()?))
+        })?;
+        self.len -= 1;
+        Some(found)
     }
 }
 
 impl<'a, Idx, T> DoubleEndedIterator for IterMut<'a, Idx, T>
 where
-    Idx: Index,
+    Idx: ArenaIndex,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
-        self.iter
-            .next()
-            .map(|(idx, entity)| (Idx::from_usize(idx), entity))
+        let found = self.iter.by_ref().rev().find_map(|(slot, entity)| {
+            let index = encode_index(slot, entity.generation);
+            Some((index, entity.entity.as_mut()?))
+        })?;
+        self.len -= 1;
+        Some(found)
     }
 }
 
 impl<'a, Idx, T> ExactSizeIterator for IterMut<'a, Idx, T>
 where
-    Idx: Index,
+    Idx: ArenaIndex,
 {
     fn len(&self) -> usize {
-        self.iter.len()
+        self.len
     }
 }
 
 impl<Idx, T> ops::Index<Idx> for Arena<Idx, T>
 where
-    Idx: Index,
+    Idx: ArenaIndex,
 {
     type Output = T;
 
@@ -256,7 +455,7 @@ where
         self.get(index).unwrap_or_else(|| {
             panic!(
                 "tried to access out of bounds arena entity at {}",
-                index.into_usize()
+                index.into_key()
             )
         })
     }
@@ -264,13 +463,13 @@ where
 
 impl<Idx, T> ops::IndexMut<Idx> for Arena<Idx, T>
 where
-    Idx: Index,
+    Idx: ArenaIndex,
 {
     fn index_mut(&mut self, index: Idx) -> &mut Self::Output {
         self.get_mut(index).unwrap_or_else(|| {
             panic!(
                 "tried to access out of bounds arena entity at {}",
-                index.into_usize()
+                index.into_key()
             )
         })
     }
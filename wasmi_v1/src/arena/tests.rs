@@ -10,6 +10,33 @@ impl Index for usize {
     }
 }
 
+impl ArenaIndex for usize {
+    fn into_key(self) -> u64 {
+        self as u64
+    }
+
+    fn from_key(key: u64) -> Self {
+        key as usize
+    }
+}
+
+/// An index type for arenas with only a few slots and generations.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct SmallIdx(u64);
+
+impl ArenaIndex for SmallIdx {
+    const MAX_SLOT: u32 = 1;
+    const MAX_GENERATION: u32 = 3;
+
+    fn into_key(self) -> u64 {
+        self.0
+    }
+
+    fn from_key(key: u64) -> Self {
+        Self(key)
+    }
+}
+
 const TEST_ENTITIES: &[&'static str] = &["a", "b", "c", "d"];
 
 mod arena {
@@ -63,6 +90,94 @@ mod arena {
         assert!(arena.iter().eq(TEST_ENTITIES.iter().enumerate()));
     }
 
+    #[test]
+    fn remove_works() {
+        let mut arena = alloc_arena(TEST_ENTITIES);
+        assert_eq!(arena.remove(1), Some("b"));
+        assert_eq!(arena.remove(1), None);
+        assert_eq!(arena.get(1), None);
+        assert_eq!(arena.get_pair_mut(0, 1), None);
+        assert_eq!(arena.len(), TEST_ENTITIES.len() - 1);
+        let expected = [(0, &"a"), (2, &"c"), (3, &"d")];
+        assert!(arena.iter().eq(expected));
+        assert!(arena.iter().rev().eq(expected.into_iter().rev()));
+        assert_eq!(arena.iter().len(), expected.len());
+    }
+
+    #[test]
+    fn remove_reuses_slots() {
+        let mut arena = alloc_arena(TEST_ENTITIES);
+        assert_eq!(arena.remove(1), Some("b"));
+        // The slot of the removed entity is reused with the next generation.
+        let e = arena.alloc("e");
+        assert_eq!(decode_index(e), (1, 1));
+        assert_eq!(arena.get(e), Some(&"e"));
+        assert_eq!(arena.get_pair_mut(0, e), Some((&mut "a", &mut "e")));
+        // The index of the removed entity does not refer to the new entity.
+        assert_eq!(arena.get(1), None);
+        assert_eq!(arena.remove(1), None);
+        assert_eq!(arena.get_pair_mut(0, 1), None);
+        assert_eq!(arena.len(), TEST_ENTITIES.len());
+        let expected = [(0, &"a"), (e, &"e"), (2, &"c"), (3, &"d")];
+        assert!(arena.iter().eq(expected));
+        // Slots are reused before new slots are allocated.
+        assert_eq!(arena.alloc("f"), TEST_ENTITIES.len());
+    }
+
+    #[test]
+    fn remove_retires_exhausted_slots() {
+        let mut arena = <Arena<SmallIdx, &'static str>>::new();
+        let mut index = arena.alloc("a");
+        for generation in 1..=SmallIdx::MAX_GENERATION {
+            assert_eq!(arena.remove(index), Some("a"));
+            index = arena.alloc("a");
+            assert_eq!(decode_index(index), (0, generation));
+        }
+        // The slot is no longer reused once its generation is exhausted.
+        assert_eq!(arena.remove(index), Some("a"));
+        assert!(arena.can_alloc(1));
+        assert!(!arena.can_alloc(2));
+        assert_eq!(decode_index(arena.alloc("b")), (1, 0));
+        assert_eq!(arena.get(index), None);
+        assert_eq!(arena.len(), 1);
+        assert!(!arena.can_alloc(1));
+    }
+
+    #[test]
+    fn remove_does_not_retire_wide_slots() {
+        let mut arena = <Arena<usize, &'static str>>::new();
+        let mut index = arena.alloc("a");
+        for generation in 1..=1000 {
+            assert_eq!(arena.remove(index), Some("a"));
+            index = arena.alloc("a");
+            assert_eq!(decode_index(index), (0, generation));
+        }
+    }
+
+    #[test]
+    fn can_alloc_works() {
+        let mut arena = <Arena<SmallIdx, &'static str>>::new();
+        assert!(arena.can_alloc(2));
+        assert!(!arena.can_alloc(3));
+        let a = arena.alloc("a");
+        arena.alloc("b");
+        assert!(arena.can_alloc(0));
+        assert!(!arena.can_alloc(1));
+        // Vacant slots can be allocated again.
+        arena.remove(a);
+        assert!(arena.can_alloc(1));
+        assert!(!arena.can_alloc(2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn alloc_out_of_slots_panics() {
+        let mut arena = <Arena<SmallIdx, &'static str>>::new();
+        for entity in ["a", "b", "c"] {
+            arena.alloc(entity);
+        }
+    }
+
     #[test]
     fn from_iter_works() {
         let expected = alloc_arena(TEST_ENTITIES);
@@ -70,6 +185,17 @@ mod arena {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn get_pair_mut_works() {
+        let mut arena = alloc_arena(TEST_ENTITIES);
+        assert_eq!(arena.get_pair_mut(0, 3), Some((&mut "a", &mut "d")));
+        assert_eq!(arena.get_pair_mut(2, 1), Some((&mut "c", &mut "b")));
+        // Both indices must refer to distinct and existing entities.
+        assert_eq!(arena.get_pair_mut(1, 1), None);
+        assert_eq!(arena.get_pair_mut(0, arena.len()), None);
+        assert_eq!(arena.get_pair_mut(arena.len(), 0), None);
+    }
+
     #[test]
     fn duplicates_work() {
         let mut arena = alloc_arena(TEST_ENTITIES);
@@ -159,3 +285,25 @@ mod dedup_arena {
         assert_eq!(arena.len(), TEST_ENTITIES.len());
     }
 }
+
+mod guarded_entity {
+    use super::*;
+
+    /// Returns the entity index at `slot` with the given `generation`.
+    fn entity_index(slot: u32, generation: u32) -> usize {
+        encode_index(slot as usize, generation)
+    }
+
+    #[test]
+    fn bits_roundtrip_works() {
+        for (guard_idx, entity_idx) in [(0, 0), (0, 1), (1, 0), (42, 1337)] {
+            let entity = <GuardedEntity<usize, usize>>::new(guard_idx, entity_idx);
+            assert_eq!(GuardedEntity::from_bits(entity.to_bits()), entity);
+        }
+        let entity = <GuardedEntity<usize, usize>>::new(
+            MAX_ENCODED_GUARD,
+            entity_index(MAX_ENCODED_SLOT, MAX_ENCODED_GENERATION),
+        );
+        assert_eq!(GuardedEntity::from_bits(entity.to_bits()), entity);
+    }
+}
@@ -0,0 +1,698 @@
+//! The encoding of `wasmi` bytecode within serialized Wasm modules.
+//!
+//! # Note
+//!
+//! Every instruction is encoded by its opcode followed by its immediates.
+//! The opcodes are stated explicitly so that reordering the variants of
+//! [`Instruction`] does not silently change the serialization format.
+
+use super::{
+    super::InstructionIdx,
+    DataSegmentIdx,
+    DropKeep,
+    ElementSegmentIdx,
+    ExceptionSlot,
+    FuncIdx,
+    GlobalIdx,
+    Instruction,
+    LocalIdx,
+    MemoryIdx,
+    Offset,
+    Register,
+    SignatureIdx,
+    TableIdx,
+    TagIdx,
+    Target,
+};
+#[cfg(feature = "threads")]
+use super::{AtomicInstruction, AtomicOffset};
+#[cfg(feature = "simd")]
+use super::{SimdInstruction, SimdOffset};
+use crate::module::{Decode, DeserializeError, Encode, Reader, Writer};
+
+macro_rules! impl_codec_for_index {
+    ( $( $(#[$attr:meta])* $ty:ty: $inner:ty ),* $(,)? ) => {
+        $(
+            $( #[$attr] )*
+            impl Encode for $ty {
+                fn encode(&self, writer: &mut Writer) {
+                    self.into_inner().encode(writer)
+                }
+            }
+
+            $( #[$attr] )*
+            impl Decode for $ty {
+                fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+                    <$inner>::decode(reader).map(Self::from)
+                }
+            }
+        )*
+    };
+}
+impl_codec_for_index! {
+    LocalIdx: u32,
+    FuncIdx: u32,
+    SignatureIdx: u32,
+    GlobalIdx: u32,
+    TagIdx: u32,
+    ExceptionSlot: u32,
+    TableIdx: u32,
+    MemoryIdx: u32,
+    DataSegmentIdx: u32,
+    ElementSegmentIdx: u32,
+    Offset: u64,
+    Register: u16,
+    #[cfg(feature = "simd")]
+    SimdOffset: u32,
+    #[cfg(feature = "threads")]
+    AtomicOffset: u32,
+}
+
+impl Encode for InstructionIdx {
+    fn encode(&self, writer: &mut Writer) {
+        (self.into_usize() as u32).encode(writer)
+    }
+}
+
+impl Decode for InstructionIdx {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        u32::decode(reader).map(|index| Self::from_usize(index as usize))
+    }
+}
+
+impl Encode for DropKeep {
+    fn encode(&self, writer: &mut Writer) {
+        (self.drop() as u32).encode(writer);
+        (self.keep() as u32).encode(writer);
+    }
+}
+
+impl Decode for DropKeep {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        let drop = u32::decode(reader)?;
+        let keep = u32::decode(reader)?;
+        Ok(Self::new32(drop, keep))
+    }
+}
+
+impl Encode for Target {
+    fn encode(&self, writer: &mut Writer) {
+        self.destination_pc().encode(writer);
+        self.drop_keep().encode(writer);
+    }
+}
+
+impl Decode for Target {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        let dst_pc = InstructionIdx::decode(reader)?;
+        let drop_keep = DropKeep::decode(reader)?;
+        Ok(Self::new(dst_pc, drop_keep))
+    }
+}
+
+macro_rules! impl_codec_for_instructions {
+    (
+        impl Encode, Decode for $name:ident {
+            $(
+                $( #[$attr:meta] )*
+                $opcode:literal => $variant:ident
+                    $( { $( $field:ident ),* } )?
+                    $( ( $value:ident ) )?
+            ),* $(,)?
+        }
+        $( unencodable: $( $skip:pat ),* $(,)? )?
+    ) => {
+        impl Encode for $name {
+            fn encode(&self, writer: &mut Writer) {
+                match self {
+                    $(
+                        $( #[$attr] )*
+                        $name::$variant $( { $( $field ),* } )? $( ( $value ) )? => {
+                            writer.write_tag($opcode);
+                            $( $( $field.encode(writer); )* )?
+                            $( $value.encode(writer); )?
+                        }
+                    )*
+                    $(
+                        $( $skip )|* => panic!(
+                            "encountered instruction that cannot be encoded: {:?}",
+                            self,
+                        ),
+                    )?
+                }
+            }
+        }
+
+        impl Decode for $name {
+            fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+                let inst = match reader.read_tag()? {
+                    $(
+                        $( #[$attr] )*
+                        $opcode => $name::$variant
+                            $( { $( $field: Decode::decode(reader)? ),* } )?
+                            $( ( { let $value = Decode::decode(reader)?; $value } ) )?,
+                    )*
+                    _ => return Err(DeserializeError::malformed("instruction")),
+                };
+                Ok(inst)
+            }
+        }
+    };
+}
+impl_codec_for_instructions! {
+    impl Encode, Decode for Instruction {
+        0 => GetLocal { local_depth },
+        1 => SetLocal { local_depth },
+        2 => TeeLocal { local_depth },
+        3 => Br(value),
+        4 => BrIfEqz(value),
+        5 => BrIfNez(value),
+        6 => ReturnIfNez(value),
+        7 => BrTable { len_targets },
+        8 => Unreachable,
+        9 => Return(value),
+        10 => Call(value),
+        11 => CallIndirect { table, signature },
+        12 => ReturnCall { func, drop_keep },
+        13 => ReturnCallIndirect { table, signature },
+        14 => Throw(value),
+        15 => Rethrow(value),
+        16 => Drop,
+        17 => Select,
+        18 => GetGlobal(value),
+        19 => SetGlobal(value),
+        20 => I32Load { memory, offset },
+        21 => I64Load { memory, offset },
+        22 => F32Load { memory, offset },
+        23 => F64Load { memory, offset },
+        24 => I32Load8S { memory, offset },
+        25 => I32Load8U { memory, offset },
+        26 => I32Load16S { memory, offset },
+        27 => I32Load16U { memory, offset },
+        28 => I64Load8S { memory, offset },
+        29 => I64Load8U { memory, offset },
+        30 => I64Load16S { memory, offset },
+        31 => I64Load16U { memory, offset },
+        32 => I64Load32S { memory, offset },
+        33 => I64Load32U { memory, offset },
+        34 => I32Store { memory, offset },
+        35 => I64Store { memory, offset },
+        36 => F32Store { memory, offset },
+        37 => F64Store { memory, offset },
+        38 => I32Store8 { memory, offset },
+        39 => I32Store16 { memory, offset },
+        40 => I64Store8 { memory, offset },
+        41 => I64Store16 { memory, offset },
+        42 => I64Store32 { memory, offset },
+        43 => CurrentMemory(value),
+        44 => GrowMemory(value),
+        45 => MemoryInit { memory, data },
+        46 => DataDrop(value),
+        47 => MemoryCopy { dst, src },
+        48 => MemoryFill(value),
+        49 => TableInit { table, elem },
+        50 => ElemDrop(value),
+        51 => TableCopy { dst, src },
+        52 => TableGet(value),
+        53 => TableSet(value),
+        54 => TableSize(value),
+        55 => TableGrow(value),
+        56 => TableFill(value),
+        57 => RefIsNull,
+        58 => RefFunc(value),
+        59 => Const(value),
+        60 => I32Eqz,
+        61 => I32Eq,
+        62 => I32Ne,
+        63 => I32LtS,
+        64 => I32LtU,
+        65 => I32GtS,
+        66 => I32GtU,
+        67 => I32LeS,
+        68 => I32LeU,
+        69 => I32GeS,
+        70 => I32GeU,
+        71 => I64Eqz,
+        72 => I64Eq,
+        73 => I64Ne,
+        74 => I64LtS,
+        75 => I64LtU,
+        76 => I64GtS,
+        77 => I64GtU,
+        78 => I64LeS,
+        79 => I64LeU,
+        80 => I64GeS,
+        81 => I64GeU,
+        82 => F32Eq,
+        83 => F32Ne,
+        84 => F32Lt,
+        85 => F32Gt,
+        86 => F32Le,
+        87 => F32Ge,
+        88 => F64Eq,
+        89 => F64Ne,
+        90 => F64Lt,
+        91 => F64Gt,
+        92 => F64Le,
+        93 => F64Ge,
+        94 => I32Clz,
+        95 => I32Ctz,
+        96 => I32Popcnt,
+        97 => I32Add,
+        98 => I32Sub,
+        99 => I32Mul,
+        100 => I32DivS,
+        101 => I32DivU,
+        102 => I32RemS,
+        103 => I32RemU,
+        104 => I32And,
+        105 => I32Or,
+        106 => I32Xor,
+        107 => I32Shl,
+        108 => I32ShrS,
+        109 => I32ShrU,
+        110 => I32Rotl,
+        111 => I32Rotr,
+        112 => I64Clz,
+        113 => I64Ctz,
+        114 => I64Popcnt,
+        115 => I64Add,
+        116 => I64Sub,
+        117 => I64Mul,
+        118 => I64DivS,
+        119 => I64DivU,
+        120 => I64RemS,
+        121 => I64RemU,
+        122 => I64And,
+        123 => I64Or,
+        124 => I64Xor,
+        125 => I64Shl,
+        126 => I64ShrS,
+        127 => I64ShrU,
+        128 => I64Rotl,
+        129 => I64Rotr,
+        130 => F32Abs,
+        131 => F32Neg,
+        132 => F32Ceil,
+        133 => F32Floor,
+        134 => F32Trunc,
+        135 => F32Nearest,
+        136 => F32Sqrt,
+        137 => F32Add,
+        138 => F32Sub,
+        139 => F32Mul,
+        140 => F32Div,
+        141 => F32Min,
+        142 => F32Max,
+        143 => F32Copysign,
+        144 => F64Abs,
+        145 => F64Neg,
+        146 => F64Ceil,
+        147 => F64Floor,
+        148 => F64Trunc,
+        149 => F64Nearest,
+        150 => F64Sqrt,
+        151 => F64Add,
+        152 => F64Sub,
+        153 => F64Mul,
+        154 => F64Div,
+        155 => F64Min,
+        156 => F64Max,
+        157 => F64Copysign,
+        158 => I32WrapI64,
+        159 => I32TruncSF32,
+        160 => I32TruncUF32,
+        161 => I32TruncSF64,
+        162 => I32TruncUF64,
+        163 => I64ExtendSI32,
+        164 => I64ExtendUI32,
+        165 => I64TruncSF32,
+        166 => I64TruncUF32,
+        167 => I64TruncSF64,
+        168 => I64TruncUF64,
+        169 => F32ConvertSI32,
+        170 => F32ConvertUI32,
+        171 => F32ConvertSI64,
+        172 => F32ConvertUI64,
+        173 => F32DemoteF64,
+        174 => F64ConvertSI32,
+        175 => F64ConvertUI32,
+        176 => F64ConvertSI64,
+        177 => F64ConvertUI64,
+        178 => F64PromoteF32,
+        179 => I32ReinterpretF32,
+        180 => I64ReinterpretF64,
+        181 => F32ReinterpretI32,
+        182 => F64ReinterpretI64,
+        183 => I32Extend8S,
+        184 => I32Extend16S,
+        185 => I64Extend8S,
+        186 => I64Extend16S,
+        187 => I64Extend32S,
+        #[cfg(feature = "simd")]
+        188 => Simd(value),
+        #[cfg(feature = "threads")]
+        189 => Atomic(value),
+        190 => I32TruncSatF32S,
+        191 => I32TruncSatF32U,
+        192 => I32TruncSatF64S,
+        193 => I32TruncSatF64U,
+        194 => I64TruncSatF32S,
+        195 => I64TruncSatF32U,
+        196 => I64TruncSatF64S,
+        197 => I64TruncSatF64U,
+        198 => I32AddImm(value),
+        199 => I64AddImm(value),
+        200 => BrIfI32Eq(value),
+        201 => BrIfI32Ne(value),
+        202 => BrIfI32LtS(value),
+        203 => BrIfI32LtU(value),
+        204 => BrIfI32GtS(value),
+        205 => BrIfI32GtU(value),
+        206 => BrIfI32LeS(value),
+        207 => BrIfI32LeU(value),
+        208 => BrIfI32GeS(value),
+        209 => BrIfI32GeU(value),
+        210 => I32LoadLocal { base, memory, offset },
+        211 => I64LoadLocal { base, memory, offset },
+        212 => I32Load8ULocal { base, memory, offset },
+    }
+    unencodable:
+        Instruction::FuncBodyStart { .. },
+        Instruction::FuncBodyEnd,
+}
+
+#[cfg(feature = "simd")]
+impl_codec_for_instructions! {
+    impl Encode, Decode for SimdInstruction {
+        0 => V128Load { memory, offset },
+        1 => V128Load8x8S { memory, offset },
+        2 => V128Load8x8U { memory, offset },
+        3 => V128Load16x4S { memory, offset },
+        4 => V128Load16x4U { memory, offset },
+        5 => V128Load32x2S { memory, offset },
+        6 => V128Load32x2U { memory, offset },
+        7 => V128Load8Splat { memory, offset },
+        8 => V128Load16Splat { memory, offset },
+        9 => V128Load32Splat { memory, offset },
+        10 => V128Load64Splat { memory, offset },
+        11 => V128Load32Zero { memory, offset },
+        12 => V128Load64Zero { memory, offset },
+        13 => V128Store { memory, offset },
+        14 => V128Load8Lane { memory, offset, lane },
+        15 => V128Load16Lane { memory, offset, lane },
+        16 => V128Load32Lane { memory, offset, lane },
+        17 => V128Load64Lane { memory, offset, lane },
+        18 => V128Store8Lane { memory, offset, lane },
+        19 => V128Store16Lane { memory, offset, lane },
+        20 => V128Store32Lane { memory, offset, lane },
+        21 => V128Store64Lane { memory, offset, lane },
+        22 => V128Const,
+        23 => I8x16Shuffle,
+        24 => I8x16Splat,
+        25 => I16x8Splat,
+        26 => I32x4Splat,
+        27 => I64x2Splat,
+        28 => F32x4Splat,
+        29 => F64x2Splat,
+        30 => I8x16ExtractLaneS { lane },
+        31 => I8x16ExtractLaneU { lane },
+        32 => I16x8ExtractLaneS { lane },
+        33 => I16x8ExtractLaneU { lane },
+        34 => I32x4ExtractLane { lane },
+        35 => I64x2ExtractLane { lane },
+        36 => F32x4ExtractLane { lane },
+        37 => F64x2ExtractLane { lane },
+        38 => I8x16ReplaceLane { lane },
+        39 => I16x8ReplaceLane { lane },
+        40 => I32x4ReplaceLane { lane },
+        41 => I64x2ReplaceLane { lane },
+        42 => F32x4ReplaceLane { lane },
+        43 => F64x2ReplaceLane { lane },
+        44 => V128AnyTrue,
+        45 => I8x16AllTrue,
+        46 => I8x16Bitmask,
+        47 => I16x8AllTrue,
+        48 => I16x8Bitmask,
+        49 => I32x4AllTrue,
+        50 => I32x4Bitmask,
+        51 => I64x2AllTrue,
+        52 => I64x2Bitmask,
+        53 => I8x16Shl,
+        54 => I8x16ShrS,
+        55 => I8x16ShrU,
+        56 => I16x8Shl,
+        57 => I16x8ShrS,
+        58 => I16x8ShrU,
+        59 => I32x4Shl,
+        60 => I32x4ShrS,
+        61 => I32x4ShrU,
+        62 => I64x2Shl,
+        63 => I64x2ShrS,
+        64 => I64x2ShrU,
+        65 => V128Bitselect,
+        66 => V128Not,
+        67 => I8x16Abs,
+        68 => I8x16Neg,
+        69 => I8x16Popcnt,
+        70 => I16x8ExtAddPairwiseI8x16S,
+        71 => I16x8ExtAddPairwiseI8x16U,
+        72 => I16x8Abs,
+        73 => I16x8Neg,
+        74 => I16x8ExtendLowI8x16S,
+        75 => I16x8ExtendHighI8x16S,
+        76 => I16x8ExtendLowI8x16U,
+        77 => I16x8ExtendHighI8x16U,
+        78 => I32x4ExtAddPairwiseI16x8S,
+        79 => I32x4ExtAddPairwiseI16x8U,
+        80 => I32x4Abs,
+        81 => I32x4Neg,
+        82 => I32x4ExtendLowI16x8S,
+        83 => I32x4ExtendHighI16x8S,
+        84 => I32x4ExtendLowI16x8U,
+        85 => I32x4ExtendHighI16x8U,
+        86 => I64x2Abs,
+        87 => I64x2Neg,
+        88 => I64x2ExtendLowI32x4S,
+        89 => I64x2ExtendHighI32x4S,
+        90 => I64x2ExtendLowI32x4U,
+        91 => I64x2ExtendHighI32x4U,
+        92 => F32x4Ceil,
+        93 => F32x4Floor,
+        94 => F32x4Trunc,
+        95 => F32x4Nearest,
+        96 => F32x4Abs,
+        97 => F32x4Neg,
+        98 => F32x4Sqrt,
+        99 => F64x2Ceil,
+        100 => F64x2Floor,
+        101 => F64x2Trunc,
+        102 => F64x2Nearest,
+        103 => F64x2Abs,
+        104 => F64x2Neg,
+        105 => F64x2Sqrt,
+        106 => I32x4TruncSatF32x4S,
+        107 => I32x4TruncSatF32x4U,
+        108 => F32x4ConvertI32x4S,
+        109 => F32x4ConvertI32x4U,
+        110 => I32x4TruncSatF64x2SZero,
+        111 => I32x4TruncSatF64x2UZero,
+        112 => F64x2ConvertLowI32x4S,
+        113 => F64x2ConvertLowI32x4U,
+        114 => F32x4DemoteF64x2Zero,
+        115 => F64x2PromoteLowF32x4,
+        116 => I8x16Swizzle,
+        117 => I8x16Eq,
+        118 => I8x16Ne,
+        119 => I8x16LtS,
+        120 => I8x16LtU,
+        121 => I8x16GtS,
+        122 => I8x16GtU,
+        123 => I8x16LeS,
+        124 => I8x16LeU,
+        125 => I8x16GeS,
+        126 => I8x16GeU,
+        127 => I16x8Eq,
+        128 => I16x8Ne,
+        129 => I16x8LtS,
+        130 => I16x8LtU,
+        131 => I16x8GtS,
+        132 => I16x8GtU,
+        133 => I16x8LeS,
+        134 => I16x8LeU,
+        135 => I16x8GeS,
+        136 => I16x8GeU,
+        137 => I32x4Eq,
+        138 => I32x4Ne,
+        139 => I32x4LtS,
+        140 => I32x4LtU,
+        141 => I32x4GtS,
+        142 => I32x4GtU,
+        143 => I32x4LeS,
+        144 => I32x4LeU,
+        145 => I32x4GeS,
+        146 => I32x4GeU,
+        147 => I64x2Eq,
+        148 => I64x2Ne,
+        149 => I64x2LtS,
+        150 => I64x2GtS,
+        151 => I64x2LeS,
+        152 => I64x2GeS,
+        153 => F32x4Eq,
+        154 => F32x4Ne,
+        155 => F32x4Lt,
+        156 => F32x4Gt,
+        157 => F32x4Le,
+        158 => F32x4Ge,
+        159 => F64x2Eq,
+        160 => F64x2Ne,
+        161 => F64x2Lt,
+        162 => F64x2Gt,
+        163 => F64x2Le,
+        164 => F64x2Ge,
+        165 => V128And,
+        166 => V128AndNot,
+        167 => V128Or,
+        168 => V128Xor,
+        169 => I8x16NarrowI16x8S,
+        170 => I8x16NarrowI16x8U,
+        171 => I8x16Add,
+        172 => I8x16AddSatS,
+        173 => I8x16AddSatU,
+        174 => I8x16Sub,
+        175 => I8x16SubSatS,
+        176 => I8x16SubSatU,
+        177 => I8x16MinS,
+        178 => I8x16MinU,
+        179 => I8x16MaxS,
+        180 => I8x16MaxU,
+        181 => I8x16RoundingAverageU,
+        182 => I16x8Q15MulrSatS,
+        183 => I16x8NarrowI32x4S,
+        184 => I16x8NarrowI32x4U,
+        185 => I16x8Add,
+        186 => I16x8AddSatS,
+        187 => I16x8AddSatU,
+        188 => I16x8Sub,
+        189 => I16x8SubSatS,
+        190 => I16x8SubSatU,
+        191 => I16x8Mul,
+        192 => I16x8MinS,
+        193 => I16x8MinU,
+        194 => I16x8MaxS,
+        195 => I16x8MaxU,
+        196 => I16x8RoundingAverageU,
+        197 => I16x8ExtMulLowI8x16S,
+        198 => I16x8ExtMulHighI8x16S,
+        199 => I16x8ExtMulLowI8x16U,
+        200 => I16x8ExtMulHighI8x16U,
+        201 => I32x4Add,
+        202 => I32x4Sub,
+        203 => I32x4Mul,
+        204 => I32x4MinS,
+        205 => I32x4MinU,
+        206 => I32x4MaxS,
+        207 => I32x4MaxU,
+        208 => I32x4DotI16x8S,
+        209 => I32x4ExtMulLowI16x8S,
+        210 => I32x4ExtMulHighI16x8S,
+        211 => I32x4ExtMulLowI16x8U,
+        212 => I32x4ExtMulHighI16x8U,
+        213 => I64x2Add,
+        214 => I64x2Sub,
+        215 => I64x2Mul,
+        216 => I64x2ExtMulLowI32x4S,
+        217 => I64x2ExtMulHighI32x4S,
+        218 => I64x2ExtMulLowI32x4U,
+        219 => I64x2ExtMulHighI32x4U,
+        220 => F32x4Add,
+        221 => F32x4Sub,
+        222 => F32x4Mul,
+        223 => F32x4Div,
+        224 => F32x4Min,
+        225 => F32x4Max,
+        226 => F32x4PMin,
+        227 => F32x4PMax,
+        228 => F64x2Add,
+        229 => F64x2Sub,
+        230 => F64x2Mul,
+        231 => F64x2Div,
+        232 => F64x2Min,
+        233 => F64x2Max,
+        234 => F64x2PMin,
+        235 => F64x2PMax,
+    }
+}
+
+#[cfg(feature = "threads")]
+impl_codec_for_instructions! {
+    impl Encode, Decode for AtomicInstruction {
+        0 => MemoryAtomicNotify { memory, offset },
+        1 => MemoryAtomicWait32 { memory, offset },
+        2 => MemoryAtomicWait64 { memory, offset },
+        3 => I32AtomicLoad { memory, offset },
+        4 => I64AtomicLoad { memory, offset },
+        5 => I32AtomicLoad8U { memory, offset },
+        6 => I32AtomicLoad16U { memory, offset },
+        7 => I64AtomicLoad8U { memory, offset },
+        8 => I64AtomicLoad16U { memory, offset },
+        9 => I64AtomicLoad32U { memory, offset },
+        10 => I32AtomicStore { memory, offset },
+        11 => I64AtomicStore { memory, offset },
+        12 => I32AtomicStore8 { memory, offset },
+        13 => I32AtomicStore16 { memory, offset },
+        14 => I64AtomicStore8 { memory, offset },
+        15 => I64AtomicStore16 { memory, offset },
+        16 => I64AtomicStore32 { memory, offset },
+        17 => I32AtomicRmwAdd { memory, offset },
+        18 => I64AtomicRmwAdd { memory, offset },
+        19 => I32AtomicRmw8AddU { memory, offset },
+        20 => I32AtomicRmw16AddU { memory, offset },
+        21 => I64AtomicRmw8AddU { memory, offset },
+        22 => I64AtomicRmw16AddU { memory, offset },
+        23 => I64AtomicRmw32AddU { memory, offset },
+        24 => I32AtomicRmwSub { memory, offset },
+        25 => I64AtomicRmwSub { memory, offset },
+        26 => I32AtomicRmw8SubU { memory, offset },
+        27 => I32AtomicRmw16SubU { memory, offset },
+        28 => I64AtomicRmw8SubU { memory, offset },
+        29 => I64AtomicRmw16SubU { memory, offset },
+        30 => I64AtomicRmw32SubU { memory, offset },
+        31 => I32AtomicRmwAnd { memory, offset },
+        32 => I64AtomicRmwAnd { memory, offset },
+        33 => I32AtomicRmw8AndU { memory, offset },
+        34 => I32AtomicRmw16AndU { memory, offset },
+        35 => I64AtomicRmw8AndU { memory, offset },
+        36 => I64AtomicRmw16AndU { memory, offset },
+        37 => I64AtomicRmw32AndU { memory, offset },
+        38 => I32AtomicRmwOr { memory, offset },
+        39 => I64AtomicRmwOr { memory, offset },
+        40 => I32AtomicRmw8OrU { memory, offset },
+        41 => I32AtomicRmw16OrU { memory, offset },
+        42 => I64AtomicRmw8OrU { memory, offset },
+        43 => I64AtomicRmw16OrU { memory, offset },
+        44 => I64AtomicRmw32OrU { memory, offset },
+        45 => I32AtomicRmwXor { memory, offset },
+        46 => I64AtomicRmwXor { memory, offset },
+        47 => I32AtomicRmw8XorU { memory, offset },
+        48 => I32AtomicRmw16XorU { memory, offset },
+        49 => I64AtomicRmw8XorU { memory, offset },
+        50 => I64AtomicRmw16XorU { memory, offset },
+        51 => I64AtomicRmw32XorU { memory, offset },
+        52 => I32AtomicRmwXchg { memory, offset },
+        53 => I64AtomicRmwXchg { memory, offset },
+        54 => I32AtomicRmw8XchgU { memory, offset },
+        55 => I32AtomicRmw16XchgU { memory, offset },
+        56 => I64AtomicRmw8XchgU { memory, offset },
+        57 => I64AtomicRmw16XchgU { memory, offset },
+        58 => I64AtomicRmw32XchgU { memory, offset },
+        59 => I32AtomicRmwCmpxchg { memory, offset },
+        60 => I64AtomicRmwCmpxchg { memory, offset },
+        61 => I32AtomicRmw8CmpxchgU { memory, offset },
+        62 => I32AtomicRmw16CmpxchgU { memory, offset },
+        63 => I64AtomicRmw8CmpxchgU { memory, offset },
+        64 => I64AtomicRmw16CmpxchgU { memory, offset },
+        65 => I64AtomicRmw32CmpxchgU { memory, offset },
+        66 => AtomicFence,
+    }
+}
@@ -1,13 +1,38 @@
 //! The instruction architecture of the `wasmi` interpreter.
 
+mod codec;
+#[cfg(feature = "simd")]
+mod simd;
+#[cfg(feature = "threads")]
+mod threads;
 mod utils;
 mod visitor;
 
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "simd")]
+pub use self::simd::{SimdInstruction, SimdOffset};
+#[cfg(feature = "threads")]
+pub use self::threads::{AtomicInstruction, AtomicOffset};
 pub use self::{
-    utils::{BrTable, DropKeep, FuncIdx, GlobalIdx, LocalIdx, Offset, SignatureIdx, Target},
+    utils::{
+        BrTable,
+        DataSegmentIdx,
+        DropKeep,
+        ElementSegmentIdx,
+        ExceptionSlot,
+        FuncIdx,
+        GlobalIdx,
+        LocalIdx,
+        MemoryIdx,
+        Offset,
+        Register,
+        SignatureIdx,
+        TableIdx,
+        TagIdx,
+        Target,
+    },
     visitor::VisitInstruction,
 };
 use wasmi_core::UntypedValue;
@@ -41,37 +66,159 @@ pub enum Instruction {
     Unreachable,
     Return(DropKeep),
     Call(FuncIdx),
-    CallIndirect(SignatureIdx),
+    CallIndirect {
+        table: TableIdx,
+        signature: SignatureIdx,
+    },
+    ReturnCall {
+        func: FuncIdx,
+        drop_keep: DropKeep,
+    },
+    /// Tail calls a function indirectly through the `table`.
+    ///
+    /// # Note
+    ///
+    /// This instruction is always followed by a [`Instruction::Return`]
+    /// that encodes the [`DropKeep`] of the tail call and is never executed.
+    ReturnCallIndirect {
+        table: TableIdx,
+        signature: SignatureIdx,
+    },
+    /// Throws a new exception with the tag and the payload on top of the stack.
+    Throw(TagIdx),
+    /// Rethrows the exception caught by an enclosing `catch` or `catch_all` clause.
+    Rethrow(ExceptionSlot),
     Drop,
     Select,
     GetGlobal(GlobalIdx),
     SetGlobal(GlobalIdx),
-    I32Load(Offset),
-    I64Load(Offset),
-    F32Load(Offset),
-    F64Load(Offset),
-    I32Load8S(Offset),
-    I32Load8U(Offset),
-    I32Load16S(Offset),
-    I32Load16U(Offset),
-    I64Load8S(Offset),
-    I64Load8U(Offset),
-    I64Load16S(Offset),
-    I64Load16U(Offset),
-    I64Load32S(Offset),
-    I64Load32U(Offset),
-    I32Store(Offset),
-    I64Store(Offset),
-    F32Store(Offset),
-    F64Store(Offset),
-    I32Store8(Offset),
-    I32Store16(Offset),
-    I64Store8(Offset),
-    I64Store16(Offset),
-    I64Store32(Offset),
-    CurrentMemory,
-    GrowMemory,
-    Const(UntypedValue),
+    I32Load {
+        memory: MemoryIdx,
+        offset: Offset,
+    },
+    I64Load {
+        memory: MemoryIdx,
+        offset: Offset,
+    },
+    F32Load {
+        memory: MemoryIdx,
+        offset: Offset,
+    },
+    F64Load {
+        memory: MemoryIdx,
+        offset: Offset,
+    },
+    I32Load8S {
+        memory: MemoryIdx,
+        offset: Offset,
+    },
+    I32Load8U {
+        memory: MemoryIdx,
+        offset: Offset,
+    },
+    I32Load16S {
+        memory: MemoryIdx,
+        offset: Offset,
+    },
+    I32Load16U {
+        memory: MemoryIdx,
+        offset: Offset,
+    },
+    I64Load8S {
+        memory: MemoryIdx,
+        offset: Offset,
+    },
+    I64Load8U {
+        memory: MemoryIdx,
+        offset: Offset,
+    },
+    I64Load16S {
+        memory: MemoryIdx,
+        offset: Offset,
+    },
+    I64Load16U {
+        memory: MemoryIdx,
+        offset: Offset,
+    },
+    I64Load32S {
+        memory: MemoryIdx,
+        offset: Offset,
+    },
+    I64Load32U {
+        memory: MemoryIdx,
+        offset: Offset,
+    },
+    I32Store {
+        memory: MemoryIdx,
+        offset: Offset,
+    },
+    I64Store {
+        memory: MemoryIdx,
+        offset: Offset,
+    },
+    F32Store {
+        memory: MemoryIdx,
+        offset: Offset,
+    },
+    F64Store {
+        memory: MemoryIdx,
+        offset: Offset,
+    },
+    I32Store8 {
+        memory: MemoryIdx,
+        offset: Offset,
+    },
+    I32Store16 {
+        memory: MemoryIdx,
+        offset: Offset,
+    },
+    I64Store8 {
+        memory: MemoryIdx,
+        offset: Offset,
+    },
+    I64Store16 {
+        memory: MemoryIdx,
+        offset: Offset,
+    },
+    I64Store32 {
+        memory: MemoryIdx,
+        offset: Offset,
+    },
+    CurrentMemory(MemoryIdx),
+    GrowMemory(MemoryIdx),
+    MemoryInit {
+        memory: MemoryIdx,
+        data: DataSegmentIdx,
+    },
+    DataDrop(DataSegmentIdx),
+    MemoryCopy {
+        dst: MemoryIdx,
+        src: MemoryIdx,
+    },
+    MemoryFill(MemoryIdx),
+    TableInit {
+        table: TableIdx,
+        elem: ElementSegmentIdx,
+    },
+    ElemDrop(ElementSegmentIdx),
+    TableCopy {
+        dst: TableIdx,
+        src: TableIdx,
+    },
+    TableGet(TableIdx),
+    TableSet(TableIdx),
+    TableSize(TableIdx),
+    TableGrow(TableIdx),
+    TableFill(TableIdx),
+    RefIsNull,
+    RefFunc(FuncIdx),
+    /// Pushes a constant value onto the stack.
+    ///
+    /// # Note
+    ///
+    /// Only the lower 64 bits of a value are encoded. Constants of type `v128`
+    /// are split into two constants that are combined by a subsequent `V128Const`.
+    Const(u64),
     I32Eqz,
     I32Eq,
     I32Ne,
@@ -200,6 +347,12 @@ pub enum Instruction {
     I64Extend8S,
     I64Extend16S,
     I64Extend32S,
+    /// An instruction of the Wasm `simd` proposal.
+    #[cfg(feature = "simd")]
+    Simd(SimdInstruction),
+    /// An instruction of the Wasm `threads` proposal.
+    #[cfg(feature = "threads")]
+    Atomic(AtomicInstruction),
     I32TruncSatF32S,
     I32TruncSatF32U,
     I32TruncSatF64S,
@@ -208,6 +361,58 @@ pub enum Instruction {
     I64TruncSatF32U,
     I64TruncSatF64S,
     I64TruncSatF64U,
+    /// Adds the constant to the `i32` value on top of the stack.
+    ///
+    /// # Note
+    ///
+    /// This superinstruction is emitted for `i32.const c; i32.add`
+    /// as well as for `i32.const c; i32.sub` with the negated constant.
+    I32AddImm(i32),
+    /// Adds the constant to the `i64` value on top of the stack.
+    ///
+    /// # Note
+    ///
+    /// This superinstruction is emitted for `i64.const c; i64.add`
+    /// as well as for `i64.const c; i64.sub` with the negated constant.
+    I64AddImm(i64),
+    /// Branches to the `target` if `lhs == rhs` for the two `i32` values on top of the stack.
+    ///
+    /// # Note
+    ///
+    /// This and the following `BrIfI32*` superinstructions are emitted
+    /// for an `i32` comparison that is directly followed by a `br_if`.
+    BrIfI32Eq(Target),
+    BrIfI32Ne(Target),
+    BrIfI32LtS(Target),
+    BrIfI32LtU(Target),
+    BrIfI32GtS(Target),
+    BrIfI32GtU(Target),
+    BrIfI32LeS(Target),
+    BrIfI32LeU(Target),
+    BrIfI32GeS(Target),
+    BrIfI32GeU(Target),
+    /// Loads an `i32` value from the address stored in the `base` register.
+    ///
+    /// # Note
+    ///
+    /// This and the following `*Local` superinstructions are emitted for
+    /// `local.get` directly followed by a load. The loaded value is pushed
+    /// onto the stack while the `base` register is left untouched.
+    I32LoadLocal {
+        base: Register,
+        memory: MemoryIdx,
+        offset: Offset,
+    },
+    I64LoadLocal {
+        base: Register,
+        memory: MemoryIdx,
+        offset: Offset,
+    },
+    I32Load8ULocal {
+        base: Register,
+        memory: MemoryIdx,
+        offset: Offset,
+    },
 
     /// The start of a Wasm function body.
     ///
@@ -253,7 +458,8 @@ impl Instruction {
     where
         T: Into<UntypedValue>,
     {
-        Self::Const(value.into())
+        let value: UntypedValue = value.into();
+        Self::Const(value.to_bits())
     }
 
     /// Creates a new `local.get` instruction from the given local depth.
@@ -0,0 +1,310 @@
+use super::{MemoryIdx, Offset};
+
+/// The `wasmi` bytecode instructions of the Wasm `threads` proposal.
+///
+/// # Note
+///
+/// These are nested into [`Instruction::Atomic`] in order to keep the
+/// size of [`Instruction`] unaffected by the additional immediates.
+///
+/// [`Instruction`]: [`super::Instruction`]
+/// [`Instruction::Atomic`]: [`super::Instruction::Atomic`]
+#[derive(Copy, Debug, Clone, PartialEq, Eq)]
+pub enum AtomicInstruction {
+    MemoryAtomicNotify {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    MemoryAtomicWait32 {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    MemoryAtomicWait64 {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicLoad {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicLoad {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicLoad8U {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicLoad16U {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicLoad8U {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicLoad16U {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicLoad32U {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicStore {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicStore {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicStore8 {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicStore16 {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicStore8 {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicStore16 {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicStore32 {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicRmwAdd {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmwAdd {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicRmw8AddU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicRmw16AddU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmw8AddU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmw16AddU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmw32AddU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicRmwSub {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmwSub {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicRmw8SubU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicRmw16SubU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmw8SubU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmw16SubU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmw32SubU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicRmwAnd {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmwAnd {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicRmw8AndU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicRmw16AndU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmw8AndU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmw16AndU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmw32AndU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicRmwOr {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmwOr {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicRmw8OrU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicRmw16OrU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmw8OrU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmw16OrU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmw32OrU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicRmwXor {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmwXor {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicRmw8XorU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicRmw16XorU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmw8XorU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmw16XorU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmw32XorU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicRmwXchg {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmwXchg {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicRmw8XchgU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicRmw16XchgU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmw8XchgU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmw16XchgU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmw32XchgU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicRmwCmpxchg {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmwCmpxchg {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicRmw8CmpxchgU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I32AtomicRmw16CmpxchgU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmw8CmpxchgU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmw16CmpxchgU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    I64AtomicRmw32CmpxchgU {
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    },
+    AtomicFence,
+}
+
+/// The offset of an atomic memory access.
+///
+/// # Note
+///
+/// This is a 32-bit offset in order to keep the size of [`Instruction`] small.
+/// Atomic memory accesses with offsets exceeding the `u32` range are unsupported.
+///
+/// [`Instruction`]: [`super::Instruction`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct AtomicOffset(u32);
+
+impl From<u32> for AtomicOffset {
+    fn from(offset: u32) -> Self {
+        Self(offset)
+    }
+}
+
+impl AtomicOffset {
+    /// Returns the inner `u32` offset.
+    pub fn into_inner(self) -> u32 {
+        self.0
+    }
+}
+
+impl From<AtomicOffset> for Offset {
+    fn from(offset: AtomicOffset) -> Self {
+        Self::from(u64::from(offset.0))
+    }
+}
@@ -146,6 +146,47 @@ impl LocalIdx {
     }
 }
 
+/// A local variable referred to by a fused instruction.
+///
+/// # Note
+///
+/// Registers refer to the local variables of the executed function
+/// by their depth on the value stack where `0` refers to the top most value.
+/// Unlike the depths of [`LocalIdx`] the depth of a register is already
+/// adjusted to the stack height at which the fused instruction executes.
+///
+/// Registers are 16-bit in order to keep the size of [`Instruction`] small.
+/// Local variables that are deeper on the value stack are accessed by the
+/// unfused instructions instead.
+///
+/// [`Instruction`]: [`super::Instruction`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct Register(u16);
+
+impl From<u16> for Register {
+    fn from(depth: u16) -> Self {
+        Self(depth)
+    }
+}
+
+impl Register {
+    /// Creates a [`Register`] from the given `depth` if it fits into a [`Register`].
+    pub fn from_depth(depth: u32) -> Option<Self> {
+        u16::try_from(depth).ok().map(Self)
+    }
+
+    /// Returns the inner `u16` depth.
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// Returns the depth of the [`Register`] as `usize`.
+    pub fn into_usize(self) -> usize {
+        self.0 as usize
+    }
+}
+
 /// A global variable index.
 ///
 /// # Note
@@ -170,28 +211,172 @@ impl GlobalIdx {
     }
 }
 
-/// A linear memory access offset.
+/// An exception tag index.
 ///
 /// # Note
 ///
-/// Used to calculate the effective address of a linear memory access.
+/// Refers to an exception tag of the [`Instance`] of the currently executed function.
+///
+/// [`Instance`]: [`crate::Instance`]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[repr(transparent)]
-pub struct Offset(u32);
+pub struct TagIdx(u32);
 
-impl From<u32> for Offset {
+impl From<u32> for TagIdx {
     fn from(index: u32) -> Self {
         Self(index)
     }
 }
 
-impl Offset {
+impl TagIdx {
+    /// Returns the inner `u32` index.
+    pub fn into_inner(self) -> u32 {
+        self.0
+    }
+}
+
+/// The value stack slot of an exception caught by a `catch` or `catch_all` clause.
+///
+/// # Note
+///
+/// The slot is relative to the first operand stack value of the executed
+/// function frame, i.e. the value stack height after its local variables.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct ExceptionSlot(u32);
+
+impl From<u32> for ExceptionSlot {
+    fn from(slot: u32) -> Self {
+        Self(slot)
+    }
+}
+
+impl ExceptionSlot {
+    /// Returns the inner `u32` slot.
+    pub fn into_inner(self) -> u32 {
+        self.0
+    }
+}
+
+/// A table index.
+///
+/// # Note
+///
+/// Refers to a table of the [`Instance`] of the currently executed function.
+///
+/// [`Instance`]: [`crate::Instance`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct TableIdx(u32);
+
+impl From<u32> for TableIdx {
+    fn from(index: u32) -> Self {
+        Self(index)
+    }
+}
+
+impl TableIdx {
+    /// Returns the inner `u32` index.
+    pub fn into_inner(self) -> u32 {
+        self.0
+    }
+}
+
+/// A linear memory index.
+///
+/// # Note
+///
+/// Refers to a linear memory of the [`Instance`] of the currently executed function.
+///
+/// [`Instance`]: [`crate::Instance`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct MemoryIdx(u32);
+
+impl From<u32> for MemoryIdx {
+    fn from(index: u32) -> Self {
+        Self(index)
+    }
+}
+
+impl MemoryIdx {
+    /// Returns the inner `u32` index.
+    pub fn into_inner(self) -> u32 {
+        self.0
+    }
+}
+
+/// A linear memory data segment index.
+///
+/// # Note
+///
+/// Refers to a data segment of the [`Instance`] of the currently executed function.
+///
+/// [`Instance`]: [`crate::Instance`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct DataSegmentIdx(u32);
+
+impl From<u32> for DataSegmentIdx {
+    fn from(index: u32) -> Self {
+        Self(index)
+    }
+}
+
+impl DataSegmentIdx {
     /// Returns the inner `u32` index.
     pub fn into_inner(self) -> u32 {
         self.0
     }
 }
 
+/// A table element segment index.
+///
+/// # Note
+///
+/// Refers to an element segment of the [`Instance`] of the currently executed function.
+///
+/// [`Instance`]: [`crate::Instance`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct ElementSegmentIdx(u32);
+
+impl From<u32> for ElementSegmentIdx {
+    fn from(index: u32) -> Self {
+        Self(index)
+    }
+}
+
+impl ElementSegmentIdx {
+    /// Returns the inner `u32` index.
+    pub fn into_inner(self) -> u32 {
+        self.0
+    }
+}
+
+/// A linear memory access offset.
+///
+/// # Note
+///
+/// Used to calculate the effective address of a linear memory access.
+/// Offsets of accesses to 64-bit linear memories may exceed the `u32` range.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct Offset(u64);
+
+impl From<u64> for Offset {
+    fn from(index: u64) -> Self {
+        Self(index)
+    }
+}
+
+impl Offset {
+    /// Returns the inner `u64` offset.
+    pub fn into_inner(self) -> u64 {
+        self.0
+    }
+}
+
 /// A reference to a `wasmi` bytecode `br_table`.
 #[derive(Debug)]
 pub struct BrTable<'a> {
@@ -1,4 +1,24 @@
-use super::{BrTable, DropKeep, FuncIdx, GlobalIdx, LocalIdx, Offset, SignatureIdx, Target};
+#[cfg(feature = "threads")]
+use super::AtomicInstruction;
+#[cfg(feature = "simd")]
+use super::SimdInstruction;
+use super::{
+    BrTable,
+    DataSegmentIdx,
+    DropKeep,
+    ElementSegmentIdx,
+    ExceptionSlot,
+    FuncIdx,
+    GlobalIdx,
+    LocalIdx,
+    MemoryIdx,
+    Offset,
+    Register,
+    SignatureIdx,
+    TableIdx,
+    TagIdx,
+    Target,
+};
 use wasmi_core::UntypedValue;
 
 pub trait VisitInstruction {
@@ -16,36 +36,59 @@ pub trait VisitInstruction {
     fn visit_get_global(&mut self, global_idx: GlobalIdx) -> Self::Outcome;
     fn visit_set_global(&mut self, global_idx: GlobalIdx) -> Self::Outcome;
     fn visit_call(&mut self, func: FuncIdx) -> Self::Outcome;
-    fn visit_call_indirect(&mut self, signature: SignatureIdx) -> Self::Outcome;
+    fn visit_call_indirect(&mut self, table: TableIdx, signature: SignatureIdx) -> Self::Outcome;
+    fn visit_return_call(&mut self, func: FuncIdx, drop_keep: DropKeep) -> Self::Outcome;
+    fn visit_return_call_indirect(
+        &mut self,
+        table: TableIdx,
+        signature: SignatureIdx,
+        drop_keep: DropKeep,
+    ) -> Self::Outcome;
+    fn visit_throw(&mut self, tag: TagIdx) -> Self::Outcome;
+    fn visit_rethrow(&mut self, slot: ExceptionSlot) -> Self::Outcome;
     fn visit_const(&mut self, bytes: UntypedValue) -> Self::Outcome;
     fn visit_unreachable(&mut self) -> Self::Outcome;
     fn visit_drop(&mut self) -> Self::Outcome;
     fn visit_select(&mut self) -> Self::Outcome;
-    fn visit_current_memory(&mut self) -> Self::Outcome;
-    fn visit_grow_memory(&mut self) -> Self::Outcome;
-    fn visit_i32_load(&mut self, offset: Offset) -> Self::Outcome;
-    fn visit_i64_load(&mut self, offset: Offset) -> Self::Outcome;
-    fn visit_f32_load(&mut self, offset: Offset) -> Self::Outcome;
-    fn visit_f64_load(&mut self, offset: Offset) -> Self::Outcome;
-    fn visit_i32_load_i8(&mut self, offset: Offset) -> Self::Outcome;
-    fn visit_i32_load_u8(&mut self, offset: Offset) -> Self::Outcome;
-    fn visit_i32_load_i16(&mut self, offset: Offset) -> Self::Outcome;
-    fn visit_i32_load_u16(&mut self, offset: Offset) -> Self::Outcome;
-    fn visit_i64_load_i8(&mut self, offset: Offset) -> Self::Outcome;
-    fn visit_i64_load_u8(&mut self, offset: Offset) -> Self::Outcome;
-    fn visit_i64_load_i16(&mut self, offset: Offset) -> Self::Outcome;
-    fn visit_i64_load_u16(&mut self, offset: Offset) -> Self::Outcome;
-    fn visit_i64_load_i32(&mut self, offset: Offset) -> Self::Outcome;
-    fn visit_i64_load_u32(&mut self, offset: Offset) -> Self::Outcome;
-    fn visit_i32_store(&mut self, offset: Offset) -> Self::Outcome;
-    fn visit_i64_store(&mut self, offset: Offset) -> Self::Outcome;
-    fn visit_f32_store(&mut self, offset: Offset) -> Self::Outcome;
-    fn visit_f64_store(&mut self, offset: Offset) -> Self::Outcome;
-    fn visit_i32_store_8(&mut self, offset: Offset) -> Self::Outcome;
-    fn visit_i32_store_16(&mut self, offset: Offset) -> Self::Outcome;
-    fn visit_i64_store_8(&mut self, offset: Offset) -> Self::Outcome;
-    fn visit_i64_store_16(&mut self, offset: Offset) -> Self::Outcome;
-    fn visit_i64_store_32(&mut self, offset: Offset) -> Self::Outcome;
+    fn visit_current_memory(&mut self, memory: MemoryIdx) -> Self::Outcome;
+    fn visit_grow_memory(&mut self, memory: MemoryIdx) -> Self::Outcome;
+    fn visit_memory_init(&mut self, memory: MemoryIdx, segment: DataSegmentIdx) -> Self::Outcome;
+    fn visit_data_drop(&mut self, segment: DataSegmentIdx) -> Self::Outcome;
+    fn visit_memory_copy(&mut self, dst: MemoryIdx, src: MemoryIdx) -> Self::Outcome;
+    fn visit_memory_fill(&mut self, memory: MemoryIdx) -> Self::Outcome;
+    fn visit_table_init(&mut self, table: TableIdx, elem: ElementSegmentIdx) -> Self::Outcome;
+    fn visit_elem_drop(&mut self, segment: ElementSegmentIdx) -> Self::Outcome;
+    fn visit_table_copy(&mut self, dst: TableIdx, src: TableIdx) -> Self::Outcome;
+    fn visit_table_get(&mut self, table: TableIdx) -> Self::Outcome;
+    fn visit_table_set(&mut self, table: TableIdx) -> Self::Outcome;
+    fn visit_table_size(&mut self, table: TableIdx) -> Self::Outcome;
+    fn visit_table_grow(&mut self, table: TableIdx) -> Self::Outcome;
+    fn visit_table_fill(&mut self, table: TableIdx) -> Self::Outcome;
+    fn visit_ref_is_null(&mut self) -> Self::Outcome;
+    fn visit_ref_func(&mut self, func: FuncIdx) -> Self::Outcome;
+    fn visit_i32_load(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome;
+    fn visit_i64_load(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome;
+    fn visit_f32_load(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome;
+    fn visit_f64_load(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome;
+    fn visit_i32_load_i8(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome;
+    fn visit_i32_load_u8(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome;
+    fn visit_i32_load_i16(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome;
+    fn visit_i32_load_u16(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome;
+    fn visit_i64_load_i8(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome;
+    fn visit_i64_load_u8(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome;
+    fn visit_i64_load_i16(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome;
+    fn visit_i64_load_u16(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome;
+    fn visit_i64_load_i32(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome;
+    fn visit_i64_load_u32(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome;
+    fn visit_i32_store(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome;
+    fn visit_i64_store(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome;
+    fn visit_f32_store(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome;
+    fn visit_f64_store(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome;
+    fn visit_i32_store_8(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome;
+    fn visit_i32_store_16(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome;
+    fn visit_i64_store_8(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome;
+    fn visit_i64_store_16(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome;
+    fn visit_i64_store_32(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome;
     fn visit_i32_eqz(&mut self) -> Self::Outcome;
     fn visit_i32_eq(&mut self) -> Self::Outcome;
     fn visit_i32_ne(&mut self) -> Self::Outcome;
@@ -174,6 +217,10 @@ pub trait VisitInstruction {
     fn visit_i64_sign_extend8(&mut self) -> Self::Outcome;
     fn visit_i64_sign_extend16(&mut self) -> Self::Outcome;
     fn visit_i64_sign_extend32(&mut self) -> Self::Outcome;
+    #[cfg(feature = "simd")]
+    fn visit_simd(&mut self, inst: SimdInstruction) -> Self::Outcome;
+    #[cfg(feature = "threads")]
+    fn visit_atomic(&mut self, inst: AtomicInstruction) -> Self::Outcome;
     fn visit_i32_trunc_sat_f32(&mut self) -> Self::Outcome;
     fn visit_u32_trunc_sat_f32(&mut self) -> Self::Outcome;
     fn visit_i32_trunc_sat_f64(&mut self) -> Self::Outcome;
@@ -182,4 +229,34 @@ pub trait VisitInstruction {
     fn visit_u64_trunc_sat_f32(&mut self) -> Self::Outcome;
     fn visit_i64_trunc_sat_f64(&mut self) -> Self::Outcome;
     fn visit_u64_trunc_sat_f64(&mut self) -> Self::Outcome;
+    fn visit_i32_add_imm(&mut self, value: i32) -> Self::Outcome;
+    fn visit_i64_add_imm(&mut self, value: i64) -> Self::Outcome;
+    fn visit_br_if_i32_eq(&mut self, target: Target) -> Self::Outcome;
+    fn visit_br_if_i32_ne(&mut self, target: Target) -> Self::Outcome;
+    fn visit_br_if_i32_lt_s(&mut self, target: Target) -> Self::Outcome;
+    fn visit_br_if_i32_lt_u(&mut self, target: Target) -> Self::Outcome;
+    fn visit_br_if_i32_gt_s(&mut self, target: Target) -> Self::Outcome;
+    fn visit_br_if_i32_gt_u(&mut self, target: Target) -> Self::Outcome;
+    fn visit_br_if_i32_le_s(&mut self, target: Target) -> Self::Outcome;
+    fn visit_br_if_i32_le_u(&mut self, target: Target) -> Self::Outcome;
+    fn visit_br_if_i32_ge_s(&mut self, target: Target) -> Self::Outcome;
+    fn visit_br_if_i32_ge_u(&mut self, target: Target) -> Self::Outcome;
+    fn visit_i32_load_local(
+        &mut self,
+        base: Register,
+        memory: MemoryIdx,
+        offset: Offset,
+    ) -> Self::Outcome;
+    fn visit_i64_load_local(
+        &mut self,
+        base: Register,
+        memory: MemoryIdx,
+        offset: Offset,
+    ) -> Self::Outcome;
+    fn visit_i32_load_u8_local(
+        &mut self,
+        base: Register,
+        memory: MemoryIdx,
+        offset: Offset,
+    ) -> Self::Outcome;
 }
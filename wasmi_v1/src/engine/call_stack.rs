@@ -1,28 +1,17 @@
 //! Data structures to represent the Wasm call stack during execution.
 
 use super::{
-    super::{
-        func::WasmFuncEntity,
-        AsContext,
-        Func,
-        FuncBody,
-        FuncEntityInternal,
-        Instance,
-        Memory,
-        Table,
-    },
+    super::{func::WasmFuncEntity, AsContext, Func, Instance, Memory},
+    FuncBodyEntity,
     ResolvedFuncBody,
     ValueStack,
     DEFAULT_CALL_STACK_LIMIT,
 };
-use crate::{
-    core::TrapCode,
-    module::{DEFAULT_MEMORY_INDEX, DEFAULT_TABLE_INDEX},
-};
-use alloc::vec::Vec;
+use crate::{core::TrapCode, module::DEFAULT_MEMORY_INDEX};
+use alloc::{sync::Arc, vec::Vec};
 
 /// A function frame of a function in the call stack.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub struct FunctionFrame {
     /// Is `true` if the function frame has already been instantiated.
     ///
@@ -37,9 +26,15 @@ pub struct FunctionFrame {
     ///
     /// # Note
     ///
-    /// This is just an optimization since function body is always required
-    /// to be loaded and for nested calls it is loaded multiple times.
-    pub func_body: FuncBody,
+    /// The function body is shared with the [`Engine`] so that it can be
+    /// executed without locking the [`Engine`]. Also this is an optimization
+    /// since the function body is loaded every time the frame is executed.
+    ///
+    /// The function body of a function frame is never replaced which is why
+    /// it is kept alive for as long as the function frame is alive.
+    ///
+    /// [`Engine`]: [`super::Engine`]
+    func_body: Arc<FuncBodyEntity>,
     /// The instance in which the function has been defined.
     ///
     /// # Note
@@ -56,14 +51,6 @@ pub struct FunctionFrame {
     /// the default linear memory and avoids one indirection to look-up
     /// the linear memory in the `Instance`.
     default_memory: Option<Memory>,
-    /// The default table (index 0) of the `instance`.
-    ///
-    /// # Note
-    ///
-    /// This is just an optimization for the common case of indirectly
-    /// calling functions using the default table and avoids one indirection
-    /// to look-up the table in the `Instance`.
-    default_table: Option<Table>,
     /// The current value of the instruction pointer.
     ///
     /// # Note
@@ -71,39 +58,61 @@ pub struct FunctionFrame {
     /// The instruction pointer always points to the instruction
     /// that is going to executed next.
     pub inst_ptr: usize,
-}
-
-impl FunctionFrame {
-    /// Creates a new [`FunctionFrame`] from the given `func`.
+    /// The height of the value stack below the first operand of the function frame.
     ///
-    /// # Panics
+    /// # Note
     ///
-    /// If the `func` has no instance handle, i.e. is not a Wasm function.
-    pub fn new(ctx: impl AsContext, func: Func) -> Self {
-        match func.as_internal(ctx.as_context()) {
-            FuncEntityInternal::Wasm(wasm_func) => Self::new_wasm(func, wasm_func),
-            FuncEntityInternal::Host(host_func) => panic!(
-                "cannot execute host functions using Wasm interpreter: {:?}",
-                host_func
-            ),
-        }
-    }
+    /// This is set upon initialization of the function frame and used to
+    /// restore the value stack when an exception is caught by the frame.
+    pub stack_base: usize,
+}
 
+impl FunctionFrame {
     /// Creates a new [`FunctionFrame`] from the given Wasm function entity.
-    pub(super) fn new_wasm(func: Func, wasm_func: &WasmFuncEntity) -> Self {
+    ///
+    /// The `func_body` is the resolved function body of the `wasm_func`.
+    pub(super) fn new_wasm(
+        func: Func,
+        wasm_func: &WasmFuncEntity,
+        func_body: Arc<FuncBodyEntity>,
+    ) -> Self {
         let instance = wasm_func.instance();
-        let func_body = wasm_func.func_body();
         Self {
             instantiated: false,
             func,
             func_body,
             instance,
             default_memory: None,
-            default_table: None,
             inst_ptr: 0,
+            stack_base: 0,
         }
     }
 
+    /// Returns the linear memory at the `index` of the function frame.
+    ///
+    /// # Note
+    ///
+    /// Only the default linear memory (index 0) is cached by the function frame
+    /// since it is by far the most commonly accessed linear memory.
+    /// All other linear memories are looked up in the `instance` upon access.
+    ///
+    /// # Panics
+    ///
+    /// If there is no linear memory at the `index`.
+    pub fn memory(&mut self, ctx: impl AsContext, index: u32) -> Memory {
+        if index == DEFAULT_MEMORY_INDEX {
+            return self.default_memory(ctx);
+        }
+        self.instance
+            .get_memory(ctx.as_context(), index)
+            .unwrap_or_else(|| {
+                panic!(
+                    "func does not have linear memory at index {}: {:?}",
+                    index, self.func
+                )
+            })
+    }
+
     /// Returns the default linear memory of the function frame if any.
     ///
     /// # Note
@@ -113,7 +122,7 @@ impl FunctionFrame {
     /// # Panics
     ///
     /// If there is no default linear memory.
-    pub fn default_memory(&mut self, ctx: impl AsContext) -> Memory {
+    fn default_memory(&mut self, ctx: impl AsContext) -> Memory {
         match self.default_memory {
             Some(default_memory) => default_memory,
             None => {
@@ -130,30 +139,6 @@ impl FunctionFrame {
         }
     }
 
-    /// Returns the default table of the function frame if any.
-    ///
-    /// # Note
-    ///
-    /// This API allows to lazily and efficiently load the default table if available.
-    ///
-    /// # Panics
-    ///
-    /// If there is no default table.
-    pub fn default_table(&mut self, ctx: impl AsContext) -> Table {
-        match self.default_table {
-            Some(default_table) => default_table,
-            None => {
-                // Try to lazily load the default memory.
-                let default_table = self
-                    .instance
-                    .get_table(ctx.as_context(), DEFAULT_TABLE_INDEX)
-                    .unwrap_or_else(|| panic!("func does not have default table: {:?}", self.func));
-                self.default_table = Some(default_table);
-                default_table
-            }
-        }
-    }
-
     /// Initializes the function frame.
     ///
     /// # Note
@@ -176,10 +161,16 @@ impl FunctionFrame {
             .unwrap_or_else(|error| {
                 panic!("encountered stack overflow while pushing locals: {}", error)
             });
+        self.stack_base = value_stack.len();
         self.instantiated = true;
         Ok(())
     }
 
+    /// Returns the function body of the [`FunctionFrame`].
+    pub fn func_body(&self) -> &Arc<FuncBodyEntity> {
+        &self.func_body
+    }
+
     /// Returns the instance of the [`FunctionFrame`].
     pub fn instance(&self) -> Instance {
         self.instance
@@ -193,6 +184,9 @@ pub struct CallStack {
     frames: Vec<FunctionFrame>,
     /// The maximum allowed depth of the `frames` stack.
     recursion_limit: usize,
+    /// The depth reserved for the function frames of the executions
+    /// that the execution using the [`CallStack`] is nested in.
+    reserved: usize,
 }
 
 impl Default for CallStack {
@@ -207,16 +201,31 @@ impl CallStack {
         Self {
             frames: Vec::new(),
             recursion_limit,
+            reserved: 0,
         }
     }
 
+    /// Reserves `depth` levels of the [`CallStack`] for the function frames
+    /// of the executions that the current execution is nested in.
+    ///
+    /// # Errors
+    ///
+    /// If the reserved depth reaches the recursion limit.
+    pub fn reserve(&mut self, depth: usize) -> Result<(), TrapCode> {
+        if depth >= self.recursion_limit {
+            return Err(TrapCode::StackOverflow);
+        }
+        self.reserved = depth;
+        Ok(())
+    }
+
     /// Pushes another [`FunctionFrame`] to the [`CallStack`].
     ///
     /// # Errors
     ///
     /// If the [`FunctionFrame`] is at the set recursion limit.
     pub fn push(&mut self, frame: FunctionFrame) -> Result<(), TrapCode> {
-        if self.len() == self.recursion_limit {
+        if self.reserved + self.len() == self.recursion_limit {
             return Err(TrapCode::StackOverflow);
         }
         self.frames.push(frame);
@@ -233,6 +242,13 @@ impl CallStack {
         self.frames.len()
     }
 
+    /// Returns an iterator over the function frames of the [`CallStack`].
+    ///
+    /// The iterator yields the outermost function frame first.
+    pub fn iter(&self) -> core::slice::Iter<'_, FunctionFrame> {
+        self.frames.iter()
+    }
+
     /// Clears the [`CallStack`] entirely.
     ///
     /// # Note
@@ -243,5 +259,6 @@ impl CallStack {
     /// function execution happens.
     pub fn clear(&mut self) {
         self.frames.clear();
+        self.reserved = 0;
     }
 }
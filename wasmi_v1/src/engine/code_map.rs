@@ -3,13 +3,17 @@
 use super::{
     super::Index,
     bytecode::{BrTable, VisitInstruction},
+    Engine,
+    ExceptionHandler,
     Instruction,
 };
-use alloc::vec::Vec;
-use core::iter;
+use crate::module::{Encode, Writer};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{iter, ops::Deref};
+use wasmi_core::UntypedValue;
 
 /// A reference to a Wasm function body stored in the [`CodeMap`].
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct FuncBody(usize);
 
 impl Index for FuncBody {
@@ -22,41 +26,103 @@ impl Index for FuncBody {
     }
 }
 
-/// Datastructure to efficiently store Wasm function bodies.
-#[derive(Debug, Default)]
-pub struct CodeMap {
-    /// The instructions of all allocated function bodies.
-    ///
-    /// By storing all `wasmi` bytecode instructions in a single
-    /// allocation we avoid an indirection when calling a function
-    /// compared to a solution that stores instructions of different
-    /// function bodies in different allocations.
-    ///
-    /// Also this improves efficiency of deallocating the [`CodeMap`]
-    /// and generally improves data locality.
-    insts: Vec<Instruction>,
+/// Owns Wasm function bodies stored in the [`CodeMap`] of an [`Engine`].
+///
+/// # Note
+///
+/// The function bodies are freed from the [`Engine`] once their owner is dropped.
+/// Shared between a [`Module`] and all of its instances so that function bodies
+/// live as long as there are entities that might still execute them.
+///
+/// [`Module`]: [`crate::Module`]
+#[derive(Debug)]
+pub struct FuncBodies {
+    engine: Engine,
+    bodies: Vec<FuncBody>,
 }
 
-impl CodeMap {
-    /// Returns the next [`FuncBody`] index.
-    fn next_index(&self) -> FuncBody {
-        FuncBody(self.insts.len())
+impl FuncBodies {
+    /// Creates a new empty [`FuncBodies`] owning function bodies of the `engine`.
+    pub fn new(engine: &Engine) -> Self {
+        Self {
+            engine: engine.clone(),
+            bodies: Vec::new(),
+        }
     }
 
-    /// Allocates a new function body to the [`CodeMap`].
+    /// Takes ownership of the `func_body` allocated in the [`Engine`].
+    pub fn push(&mut self, func_body: FuncBody) {
+        self.bodies.push(func_body);
+    }
+}
+
+impl Deref for FuncBodies {
+    type Target = [FuncBody];
+
+    fn deref(&self) -> &Self::Target {
+        &self.bodies[..]
+    }
+}
+
+impl Drop for FuncBodies {
+    fn drop(&mut self) {
+        if !self.bodies.is_empty() {
+            self.engine.free_func_bodies(&self.bodies);
+        }
+    }
+}
+
+/// A Wasm function body stored in the [`CodeMap`].
+///
+/// # Note
+///
+/// Every function body is stored in its own shared allocation so that Wasm
+/// executions can resolve a function body once and then execute its
+/// instructions without holding the lock of the [`Engine`].
+#[derive(Debug)]
+pub struct FuncBodyEntity {
+    /// The instructions of the function body.
     ///
-    /// Returns a reference to the allocated function body that can
-    /// be used with [`CodeMap::resolve`] in order to resolve its
-    /// instructions.
-    pub fn alloc<I>(&mut self, len_locals: usize, max_stack_height: usize, insts: I) -> FuncBody
+    /// # Note
+    ///
+    /// We are inserting an artificial [`Instruction::FuncBodyStart`] and
+    /// [`Instruction::FuncBodyEnd`] around the instructions of the function
+    /// body as a small safety precaution.
+    insts: Box<[Instruction]>,
+    /// The exception handlers of the function body.
+    ///
+    /// # Note
+    ///
+    /// Exception handlers are only inspected when unwinding a thrown exception.
+    handlers: Box<[ExceptionHandler]>,
+    /// The source offsets of the instructions of the function body.
+    ///
+    /// # Note
+    ///
+    /// Source offsets map instructions back to the Wasm operators they originate from
+    /// and are only recorded if Wasm backtraces are enabled.
+    offsets: Box<[usize]>,
+}
+
+impl FuncBodyEntity {
+    /// Creates a new [`FuncBodyEntity`].
+    ///
+    /// # Panics
+    ///
+    /// If the function body has too many instructions, local variables or stack values.
+    fn new<I, H, O>(
+        len_locals: usize,
+        max_stack_height: usize,
+        insts: I,
+        handlers: H,
+        offsets: O,
+    ) -> Self
     where
         I: IntoIterator<Item = Instruction>,
         I::IntoIter: ExactSizeIterator,
+        H: IntoIterator<Item = ExceptionHandler>,
+        O: IntoIterator<Item = usize>,
     {
-        let idx = self.next_index();
-        // We are inserting an artificial `unreachable` Wasm instruction
-        // in between instructions of different function bodies as a small
-        // safety precaution.
         let insts = insts.into_iter();
         let len_instructions = insts.len().try_into().unwrap_or_else(|error| {
             panic!(
@@ -85,18 +151,16 @@ impl CodeMap {
             max_stack_height,
         });
         let end = iter::once(Instruction::FuncBodyEnd);
-        self.insts.extend(start.chain(insts).chain(end));
-        idx
+        Self {
+            insts: start.chain(insts).chain(end).collect(),
+            handlers: handlers.into_iter().collect(),
+            offsets: offsets.into_iter().collect(),
+        }
     }
 
-    /// Resolves the instruction of the function body.
-    ///
-    /// # Panics
-    ///
-    /// If the given `func_body` is invalid for this [`CodeMap`].
-    pub fn resolve(&self, func_body: FuncBody) -> ResolvedFuncBody {
-        let offset = func_body.into_usize();
-        let (len_instructions, len_locals, max_stack_height) = match &self.insts[offset] {
+    /// Resolves the instructions of the function body.
+    pub fn resolve(&self) -> ResolvedFuncBody<'_> {
+        let (len_instructions, len_locals, max_stack_height) = match &self.insts[0] {
             Instruction::FuncBodyStart {
                 len_instructions,
                 len_locals,
@@ -110,8 +174,6 @@ impl CodeMap {
         let len_instructions = len_instructions as usize;
         let len_locals = len_locals as usize;
         let max_stack_height = max_stack_height as usize;
-        // The index of the first instruction in the function body.
-        let first_inst = offset + 1;
         {
             // Assert that the end of the function instructions is
             // properly guarded with the `FuncBodyEnd` sentinel.
@@ -119,20 +181,154 @@ impl CodeMap {
             // This check is not needed to validate the integrity of
             // the resolution procedure and therefore the below assertion
             // is only performed in debug mode.
-            let end = &self.insts[first_inst + len_instructions];
+            let end = &self.insts[1 + len_instructions];
             debug_assert!(
                 matches!(end, Instruction::FuncBodyEnd),
                 "expected function end instruction but found: {:?}",
                 end,
             );
         }
-        let insts = &self.insts[first_inst..(first_inst + len_instructions)];
+        let insts = &self.insts[1..(1 + len_instructions)];
         ResolvedFuncBody {
             insts,
             len_locals,
             max_stack_height,
         }
     }
+
+    /// Returns the exception handlers of the function body.
+    ///
+    /// # Note
+    ///
+    /// The returned slice is empty if the function body has no exception handlers.
+    pub fn handlers(&self) -> &[ExceptionHandler] {
+        &self.handlers[..]
+    }
+
+    /// Returns the offset of the Wasm operator within the Wasm binary
+    /// that the instruction at `index` of the function body originates from.
+    ///
+    /// Returns `None` if no source offsets have been recorded for the function body.
+    pub fn source_offset(&self, index: usize) -> Option<usize> {
+        self.offsets.get(index).copied()
+    }
+
+    /// Encodes the function body into the `writer`.
+    ///
+    /// # Note
+    ///
+    /// The encoding contains the amount of local variables and the maximum stack height
+    /// followed by the instructions, exception handlers and source offsets of the function
+    /// body as expected by [`CodeMap::alloc`].
+    pub fn encode(&self, writer: &mut Writer) {
+        let resolved = self.resolve();
+        resolved.len_locals.encode(writer);
+        (resolved.max_stack_height - resolved.len_locals).encode(writer);
+        resolved.insts.encode(writer);
+        self.handlers().encode(writer);
+        self.offsets[..].encode(writer);
+    }
+}
+
+/// Datastructure to efficiently store Wasm function bodies.
+#[derive(Debug, Default)]
+pub struct CodeMap {
+    /// All allocated function bodies.
+    ///
+    /// This is `None` for function bodies that have been freed. Their
+    /// [`FuncBody`] index is reused by a later allocated function body.
+    ///
+    /// # Note
+    ///
+    /// Freeing a function body only removes it from the [`CodeMap`].
+    /// Its instructions are deallocated once they are no longer shared,
+    /// e.g. by the call frames of live or suspended executions.
+    bodies: Vec<Option<Arc<FuncBodyEntity>>>,
+    /// The [`FuncBody`] indices of freed function bodies available for reuse.
+    vacant: Vec<FuncBody>,
+}
+
+impl CodeMap {
+    /// Allocates a new function body to the [`CodeMap`].
+    ///
+    /// Returns a reference to the allocated function body that can
+    /// be used with [`CodeMap::get`] in order to resolve its
+    /// instructions.
+    pub fn alloc<I, H, O>(
+        &mut self,
+        len_locals: usize,
+        max_stack_height: usize,
+        insts: I,
+        handlers: H,
+        offsets: O,
+    ) -> FuncBody
+    where
+        I: IntoIterator<Item = Instruction>,
+        I::IntoIter: ExactSizeIterator,
+        H: IntoIterator<Item = ExceptionHandler>,
+        O: IntoIterator<Item = usize>,
+    {
+        let entity = Arc::new(FuncBodyEntity::new(
+            len_locals,
+            max_stack_height,
+            insts,
+            handlers,
+            offsets,
+        ));
+        match self.vacant.pop() {
+            Some(func_body) => {
+                self.bodies[func_body.into_usize()] = Some(entity);
+                func_body
+            }
+            None => {
+                self.bodies.push(Some(entity));
+                FuncBody(self.bodies.len() - 1)
+            }
+        }
+    }
+
+    /// Frees the function bodies so that their indices can be reused.
+    ///
+    /// # Note
+    ///
+    /// Callers must ensure that the [`FuncBody`] indices of the freed function
+    /// bodies are no longer used. Function bodies that have already been resolved
+    /// via [`CodeMap::get`] are unaffected.
+    pub fn free(&mut self, func_bodies: &[FuncBody]) {
+        for &func_body in func_bodies {
+            self.bodies[func_body.into_usize()]
+                .take()
+                .unwrap_or_else(|| panic!("tried to free function body twice: {:?}", func_body));
+            self.vacant.push(func_body);
+        }
+    }
+
+    /// Returns the function body referenced by `func_body`.
+    ///
+    /// # Note
+    ///
+    /// The returned function body is shared and therefore can be used
+    /// after the [`CodeMap`] has been modified.
+    ///
+    /// # Panics
+    ///
+    /// If the given `func_body` is invalid for this [`CodeMap`].
+    pub fn get(&self, func_body: FuncBody) -> &Arc<FuncBodyEntity> {
+        self.bodies
+            .get(func_body.into_usize())
+            .and_then(Option::as_ref)
+            .unwrap_or_else(|| panic!("failed to resolve function body: {:?}", func_body))
+    }
+
+    /// Resolves the instruction of the function body.
+    ///
+    /// # Panics
+    ///
+    /// If the given `func_body` is invalid for this [`CodeMap`].
+    #[cfg(test)]
+    pub fn resolve(&self, func_body: FuncBody) -> ResolvedFuncBody<'_> {
+        self.get(func_body).resolve()
+    }
 }
 
 /// A resolved Wasm function body that is stored in a [`CodeMap`].
@@ -217,37 +413,98 @@ impl<'a> ResolvedFuncBody<'a> {
             Instruction::Unreachable => visitor.visit_unreachable(),
             Instruction::Return(drop_keep) => visitor.visit_ret(*drop_keep),
             Instruction::Call(func) => visitor.visit_call(*func),
-            Instruction::CallIndirect(signature) => visitor.visit_call_indirect(*signature),
+            Instruction::CallIndirect { table, signature } => {
+                visitor.visit_call_indirect(*table, *signature)
+            }
+            Instruction::ReturnCall { func, drop_keep } => {
+                visitor.visit_return_call(*func, *drop_keep)
+            }
+            Instruction::ReturnCallIndirect { table, signature } => {
+                let drop_keep = match self.insts[index + 1] {
+                    Instruction::Return(drop_keep) => drop_keep,
+                    unexpected => panic!(
+                        "expected `Return` following `ReturnCallIndirect` but found: {:?}",
+                        unexpected
+                    ),
+                };
+                visitor.visit_return_call_indirect(*table, *signature, drop_keep)
+            }
+            Instruction::Throw(tag) => visitor.visit_throw(*tag),
+            Instruction::Rethrow(slot) => visitor.visit_rethrow(*slot),
             Instruction::Drop => visitor.visit_drop(),
             Instruction::Select => visitor.visit_select(),
             Instruction::GetGlobal(global_idx) => visitor.visit_get_global(*global_idx),
             Instruction::SetGlobal(global_idx) => visitor.visit_set_global(*global_idx),
-            Instruction::I32Load(offset) => visitor.visit_i32_load(*offset),
-            Instruction::I64Load(offset) => visitor.visit_i64_load(*offset),
-            Instruction::F32Load(offset) => visitor.visit_f32_load(*offset),
-            Instruction::F64Load(offset) => visitor.visit_f64_load(*offset),
-            Instruction::I32Load8S(offset) => visitor.visit_i32_load_i8(*offset),
-            Instruction::I32Load8U(offset) => visitor.visit_i32_load_u8(*offset),
-            Instruction::I32Load16S(offset) => visitor.visit_i32_load_i16(*offset),
-            Instruction::I32Load16U(offset) => visitor.visit_i32_load_u16(*offset),
-            Instruction::I64Load8S(offset) => visitor.visit_i64_load_i8(*offset),
-            Instruction::I64Load8U(offset) => visitor.visit_i64_load_u8(*offset),
-            Instruction::I64Load16S(offset) => visitor.visit_i64_load_i16(*offset),
-            Instruction::I64Load16U(offset) => visitor.visit_i64_load_u16(*offset),
-            Instruction::I64Load32S(offset) => visitor.visit_i64_load_i32(*offset),
-            Instruction::I64Load32U(offset) => visitor.visit_i64_load_u32(*offset),
-            Instruction::I32Store(offset) => visitor.visit_i32_store(*offset),
-            Instruction::I64Store(offset) => visitor.visit_i64_store(*offset),
-            Instruction::F32Store(offset) => visitor.visit_f32_store(*offset),
-            Instruction::F64Store(offset) => visitor.visit_f64_store(*offset),
-            Instruction::I32Store8(offset) => visitor.visit_i32_store_8(*offset),
-            Instruction::I32Store16(offset) => visitor.visit_i32_store_16(*offset),
-            Instruction::I64Store8(offset) => visitor.visit_i64_store_8(*offset),
-            Instruction::I64Store16(offset) => visitor.visit_i64_store_16(*offset),
-            Instruction::I64Store32(offset) => visitor.visit_i64_store_32(*offset),
-            Instruction::CurrentMemory => visitor.visit_current_memory(),
-            Instruction::GrowMemory => visitor.visit_grow_memory(),
-            Instruction::Const(bytes) => visitor.visit_const(*bytes),
+            Instruction::I32Load { memory, offset } => visitor.visit_i32_load(*memory, *offset),
+            Instruction::I64Load { memory, offset } => visitor.visit_i64_load(*memory, *offset),
+            Instruction::F32Load { memory, offset } => visitor.visit_f32_load(*memory, *offset),
+            Instruction::F64Load { memory, offset } => visitor.visit_f64_load(*memory, *offset),
+            Instruction::I32Load8S { memory, offset } => {
+                visitor.visit_i32_load_i8(*memory, *offset)
+            }
+            Instruction::I32Load8U { memory, offset } => {
+                visitor.visit_i32_load_u8(*memory, *offset)
+            }
+            Instruction::I32Load16S { memory, offset } => {
+                visitor.visit_i32_load_i16(*memory, *offset)
+            }
+            Instruction::I32Load16U { memory, offset } => {
+                visitor.visit_i32_load_u16(*memory, *offset)
+            }
+            Instruction::I64Load8S { memory, offset } => {
+                visitor.visit_i64_load_i8(*memory, *offset)
+            }
+            Instruction::I64Load8U { memory, offset } => {
+                visitor.visit_i64_load_u8(*memory, *offset)
+            }
+            Instruction::I64Load16S { memory, offset } => {
+                visitor.visit_i64_load_i16(*memory, *offset)
+            }
+            Instruction::I64Load16U { memory, offset } => {
+                visitor.visit_i64_load_u16(*memory, *offset)
+            }
+            Instruction::I64Load32S { memory, offset } => {
+                visitor.visit_i64_load_i32(*memory, *offset)
+            }
+            Instruction::I64Load32U { memory, offset } => {
+                visitor.visit_i64_load_u32(*memory, *offset)
+            }
+            Instruction::I32Store { memory, offset } => visitor.visit_i32_store(*memory, *offset),
+            Instruction::I64Store { memory, offset } => visitor.visit_i64_store(*memory, *offset),
+            Instruction::F32Store { memory, offset } => visitor.visit_f32_store(*memory, *offset),
+            Instruction::F64Store { memory, offset } => visitor.visit_f64_store(*memory, *offset),
+            Instruction::I32Store8 { memory, offset } => {
+                visitor.visit_i32_store_8(*memory, *offset)
+            }
+            Instruction::I32Store16 { memory, offset } => {
+                visitor.visit_i32_store_16(*memory, *offset)
+            }
+            Instruction::I64Store8 { memory, offset } => {
+                visitor.visit_i64_store_8(*memory, *offset)
+            }
+            Instruction::I64Store16 { memory, offset } => {
+                visitor.visit_i64_store_16(*memory, *offset)
+            }
+            Instruction::I64Store32 { memory, offset } => {
+                visitor.visit_i64_store_32(*memory, *offset)
+            }
+            Instruction::CurrentMemory(memory) => visitor.visit_current_memory(*memory),
+            Instruction::GrowMemory(memory) => visitor.visit_grow_memory(*memory),
+            Instruction::MemoryInit { memory, data } => visitor.visit_memory_init(*memory, *data),
+            Instruction::DataDrop(segment) => visitor.visit_data_drop(*segment),
+            Instruction::MemoryCopy { dst, src } => visitor.visit_memory_copy(*dst, *src),
+            Instruction::MemoryFill(memory) => visitor.visit_memory_fill(*memory),
+            Instruction::TableInit { table, elem } => visitor.visit_table_init(*table, *elem),
+            Instruction::ElemDrop(segment) => visitor.visit_elem_drop(*segment),
+            Instruction::TableCopy { dst, src } => visitor.visit_table_copy(*dst, *src),
+            Instruction::TableGet(table) => visitor.visit_table_get(*table),
+            Instruction::TableSet(table) => visitor.visit_table_set(*table),
+            Instruction::TableSize(table) => visitor.visit_table_size(*table),
+            Instruction::TableGrow(table) => visitor.visit_table_grow(*table),
+            Instruction::TableFill(table) => visitor.visit_table_fill(*table),
+            Instruction::RefIsNull => visitor.visit_ref_is_null(),
+            Instruction::RefFunc(func) => visitor.visit_ref_func(*func),
+            Instruction::Const(bits) => visitor.visit_const(UntypedValue::from(*bits)),
             Instruction::I32Eqz => visitor.visit_i32_eqz(),
             Instruction::I32Eq => visitor.visit_i32_eq(),
             Instruction::I32Ne => visitor.visit_i32_ne(),
@@ -384,6 +641,37 @@ impl<'a> ResolvedFuncBody<'a> {
             Instruction::I64Extend8S => visitor.visit_i64_sign_extend8(),
             Instruction::I64Extend16S => visitor.visit_i64_sign_extend16(),
             Instruction::I64Extend32S => visitor.visit_i64_sign_extend32(),
+            #[cfg(feature = "simd")]
+            Instruction::Simd(inst) => visitor.visit_simd(*inst),
+            #[cfg(feature = "threads")]
+            Instruction::Atomic(inst) => visitor.visit_atomic(*inst),
+            Instruction::I32AddImm(value) => visitor.visit_i32_add_imm(*value),
+            Instruction::I64AddImm(value) => visitor.visit_i64_add_imm(*value),
+            Instruction::BrIfI32Eq(target) => visitor.visit_br_if_i32_eq(*target),
+            Instruction::BrIfI32Ne(target) => visitor.visit_br_if_i32_ne(*target),
+            Instruction::BrIfI32LtS(target) => visitor.visit_br_if_i32_lt_s(*target),
+            Instruction::BrIfI32LtU(target) => visitor.visit_br_if_i32_lt_u(*target),
+            Instruction::BrIfI32GtS(target) => visitor.visit_br_if_i32_gt_s(*target),
+            Instruction::BrIfI32GtU(target) => visitor.visit_br_if_i32_gt_u(*target),
+            Instruction::BrIfI32LeS(target) => visitor.visit_br_if_i32_le_s(*target),
+            Instruction::BrIfI32LeU(target) => visitor.visit_br_if_i32_le_u(*target),
+            Instruction::BrIfI32GeS(target) => visitor.visit_br_if_i32_ge_s(*target),
+            Instruction::BrIfI32GeU(target) => visitor.visit_br_if_i32_ge_u(*target),
+            Instruction::I32LoadLocal {
+                base,
+                memory,
+                offset,
+            } => visitor.visit_i32_load_local(*base, *memory, *offset),
+            Instruction::I64LoadLocal {
+                base,
+                memory,
+                offset,
+            } => visitor.visit_i64_load_local(*base, *memory, *offset),
+            Instruction::I32Load8ULocal {
+                base,
+                memory,
+                offset,
+            } => visitor.visit_i32_load_u8_local(*base, *memory, *offset),
             Instruction::FuncBodyStart { .. } | Instruction::FuncBodyEnd => panic!(
                 "expected start of a new instruction at index {} but found: {:?}",
                 index, inst
@@ -391,3 +679,59 @@ impl<'a> ResolvedFuncBody<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Allocates a function body consisting of `insts` to the `code_map`.
+    fn alloc(code_map: &mut CodeMap, insts: &[Instruction]) -> FuncBody {
+        code_map.alloc(0, 0, insts.iter().copied(), [], [])
+    }
+
+    /// Asserts that the function body resolves to the `expected` instructions.
+    fn assert_insts(code_map: &CodeMap, func_body: FuncBody, expected: &[Instruction]) {
+        let resolved = code_map.resolve(func_body);
+        for (index, expected) in expected.iter().enumerate() {
+            assert_eq!(resolved.get(index), Some(expected));
+        }
+        assert_eq!(resolved.get(expected.len()), None);
+    }
+
+    #[test]
+    fn free_works() {
+        let mut code_map = CodeMap::default();
+        let a = alloc(&mut code_map, &[Instruction::I32Add]);
+        let b = alloc(&mut code_map, &[Instruction::I32Sub, Instruction::Drop]);
+        let c = alloc(&mut code_map, &[Instruction::I32Mul]);
+        code_map.free(&[b]);
+        assert_insts(&code_map, a, &[Instruction::I32Add]);
+        assert_insts(&code_map, c, &[Instruction::I32Mul]);
+        // The index of the freed function body is reused.
+        let d = alloc(&mut code_map, &[Instruction::I32And]);
+        assert_eq!(d, b);
+        assert_insts(&code_map, d, &[Instruction::I32And]);
+    }
+
+    #[test]
+    fn freed_func_body_outlives_its_index() {
+        let mut code_map = CodeMap::default();
+        let a = alloc(&mut code_map, &[Instruction::I32Add]);
+        let shared = code_map.get(a).clone();
+        code_map.free(&[a]);
+        let b = alloc(&mut code_map, &[Instruction::I32Sub]);
+        assert_eq!(a, b);
+        // The shared function body is unaffected by reusing its index.
+        assert_eq!(shared.resolve().get(0), Some(&Instruction::I32Add));
+        assert_insts(&code_map, b, &[Instruction::I32Sub]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn resolve_freed_panics() {
+        let mut code_map = CodeMap::default();
+        let a = alloc(&mut code_map, &[Instruction::I32Add]);
+        code_map.free(&[a]);
+        code_map.resolve(a);
+    }
+}
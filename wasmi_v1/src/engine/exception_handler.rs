@@ -0,0 +1,211 @@
+//! Exception handler tables of Wasm function bodies.
+
+use super::{bytecode::TagIdx, InstructionIdx};
+use crate::module::{Decode, DeserializeError, Encode, Reader, Writer};
+use alloc::vec::Vec;
+
+/// A `catch` or `catch_all` clause of a Wasm `try` block.
+#[derive(Debug, Copy, Clone)]
+pub struct CatchClause {
+    /// The tag of the caught exceptions or `None` for a `catch_all` clause.
+    tag: Option<TagIdx>,
+    /// The instruction index of the first instruction of the clause.
+    pc: InstructionIdx,
+}
+
+/// The way in which an [`ExceptionHandler`] handles thrown exceptions.
+#[derive(Debug, Clone)]
+pub enum HandlerKind {
+    /// The `catch` and `catch_all` clauses of a Wasm `try` block in order.
+    Catch(Vec<CatchClause>),
+    /// The exception is delegated by a Wasm `try` block to an enclosing handler.
+    ///
+    /// # Note
+    ///
+    /// Refers to the index of the enclosing [`ExceptionHandler`] of the same function
+    /// or is `None` if the exception is delegated to the caller of the function.
+    Delegate(Option<u32>),
+}
+
+/// An exception handler covering the instructions of a Wasm `try` block.
+///
+/// # Note
+///
+/// The exception handlers of a function body are ordered by the position of
+/// their Wasm `try` blocks so that enclosing handlers precede the handlers
+/// that they enclose.
+#[derive(Debug, Clone)]
+pub struct ExceptionHandler {
+    /// The index of the first instruction covered by the handler.
+    start: InstructionIdx,
+    /// The index of the first instruction no longer covered by the handler.
+    end: InstructionIdx,
+    /// The value stack height upon entering the Wasm `try` block.
+    ///
+    /// # Note
+    ///
+    /// The height is relative to the first operand of the function frame.
+    stack_height: u32,
+    /// The way in which the handler handles the exceptions it covers.
+    kind: HandlerKind,
+}
+
+/// The `catch` or `catch_all` clause that catches a thrown exception.
+#[derive(Debug, Copy, Clone)]
+pub struct CatchTarget {
+    /// The value stack height upon entering the Wasm `try` block.
+    pub stack_height: u32,
+    /// The instruction index of the first instruction of the clause.
+    pub pc: InstructionIdx,
+    /// Is `true` if the clause receives the payload of the exception.
+    ///
+    /// # Note
+    ///
+    /// This is `false` for `catch_all` clauses.
+    pub with_payload: bool,
+}
+
+impl ExceptionHandler {
+    /// Creates a new [`ExceptionHandler`] for a Wasm `try` block starting at `start`.
+    ///
+    /// # Note
+    ///
+    /// The handler does not cover any instructions until it has been closed.
+    pub fn new(start: InstructionIdx, stack_height: u32) -> Self {
+        Self {
+            start,
+            end: start,
+            stack_height,
+            kind: HandlerKind::Catch(Vec::new()),
+        }
+    }
+
+    /// Closes the instruction range covered by the [`ExceptionHandler`] at `end`.
+    pub fn close(&mut self, end: InstructionIdx) {
+        self.end = end;
+    }
+
+    /// Pushes a `catch` clause for `tag` or a `catch_all` clause if `tag` is `None`.
+    ///
+    /// # Panics
+    ///
+    /// If the [`ExceptionHandler`] delegates its exceptions.
+    pub fn push_catch(&mut self, tag: Option<TagIdx>, pc: InstructionIdx) {
+        match &mut self.kind {
+            HandlerKind::Catch(clauses) => clauses.push(CatchClause { tag, pc }),
+            HandlerKind::Delegate(_) => panic!(
+                "tried to push a catch clause to a delegating exception handler: {:?}",
+                self
+            ),
+        }
+    }
+
+    /// Delegates all covered exceptions to the handler at `target` or the caller if `None`.
+    pub fn delegate(&mut self, target: Option<u32>) {
+        self.kind = HandlerKind::Delegate(target);
+    }
+
+    /// Returns `true` if the [`ExceptionHandler`] covers the instruction at `pc`.
+    fn covers(&self, pc: usize) -> bool {
+        self.start.into_usize() <= pc && pc < self.end.into_usize()
+    }
+}
+
+impl Encode for CatchClause {
+    fn encode(&self, writer: &mut Writer) {
+        self.tag.encode(writer);
+        self.pc.encode(writer);
+    }
+}
+
+impl Decode for CatchClause {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        Ok(Self {
+            tag: Decode::decode(reader)?,
+            pc: Decode::decode(reader)?,
+        })
+    }
+}
+
+impl Encode for ExceptionHandler {
+    fn encode(&self, writer: &mut Writer) {
+        self.start.encode(writer);
+        self.end.encode(writer);
+        self.stack_height.encode(writer);
+        match &self.kind {
+            HandlerKind::Catch(clauses) => {
+                writer.write_tag(0);
+                clauses.encode(writer);
+            }
+            HandlerKind::Delegate(target) => {
+                writer.write_tag(1);
+                target.encode(writer);
+            }
+        }
+    }
+}
+
+impl Decode for ExceptionHandler {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        let start = Decode::decode(reader)?;
+        let end = Decode::decode(reader)?;
+        let stack_height = Decode::decode(reader)?;
+        let kind = match reader.read_tag()? {
+            0 => HandlerKind::Catch(Decode::decode(reader)?),
+            1 => HandlerKind::Delegate(Decode::decode(reader)?),
+            _ => return Err(DeserializeError::malformed("exception handler")),
+        };
+        Ok(Self {
+            start,
+            end,
+            stack_height,
+            kind,
+        })
+    }
+}
+
+/// Returns the clause of the `handlers` that catches an exception thrown at `pc`.
+///
+/// The `matches_tag` closure decides if a `catch` clause for a tag catches the exception.
+///
+/// Returns `None` if the exception is propagated to the caller of the function.
+pub fn find_catch_target<F>(
+    handlers: &[ExceptionHandler],
+    pc: usize,
+    mut matches_tag: F,
+) -> Option<CatchTarget>
+where
+    F: FnMut(TagIdx) -> bool,
+{
+    // Only handlers with an index lower than `candidates` are considered.
+    let mut candidates = handlers.len();
+    while let Some(index) = handlers[..candidates]
+        .iter()
+        .rposition(|handler| handler.covers(pc))
+    {
+        let handler = &handlers[index];
+        match &handler.kind {
+            HandlerKind::Catch(clauses) => {
+                let caught = clauses.iter().find(|clause| match clause.tag {
+                    Some(tag) => matches_tag(tag),
+                    None => true,
+                });
+                if let Some(clause) = caught {
+                    return Some(CatchTarget {
+                        stack_height: handler.stack_height,
+                        pc: clause.pc,
+                        with_payload: clause.tag.is_some(),
+                    });
+                }
+                candidates = index;
+            }
+            HandlerKind::Delegate(Some(target)) => {
+                // The target handler encloses the delegating handler
+                // and therefore also covers the instruction at `pc`.
+                candidates = *target as usize + 1;
+            }
+            HandlerKind::Delegate(None) => return None,
+        }
+    }
+    None
+}
@@ -1,9 +1,27 @@
+#[cfg(feature = "threads")]
+use super::bytecode::AtomicInstruction;
+#[cfg(feature = "simd")]
+use super::bytecode::SimdInstruction;
 use super::{
-    super::{Global, Memory, Table},
-    bytecode::{BrTable, FuncIdx, GlobalIdx, Instruction, LocalIdx, Offset, SignatureIdx},
+    super::{Global, Memory, MemoryType, Table},
+    bytecode::{
+        BrTable,
+        DataSegmentIdx,
+        ElementSegmentIdx,
+        ExceptionSlot,
+        FuncIdx,
+        GlobalIdx,
+        Instruction,
+        LocalIdx,
+        MemoryIdx,
+        Offset,
+        Register,
+        SignatureIdx,
+        TableIdx,
+        TagIdx,
+    },
     AsContextMut,
     DropKeep,
-    EngineInner,
     FunctionExecutionOutcome,
     FunctionFrame,
     ResolvedFuncBody,
@@ -13,10 +31,18 @@ use super::{
 };
 use crate::{
     core::{Trap, TrapCode, F32, F64},
+    errors::{MemoryError, TableError},
     Func,
+    FuncRef,
+    Value,
 };
 use wasmi_core::{memory_units::Pages, ExtendInto, LittleEndianConvert, UntypedValue, WrapInto};
 
+#[cfg(feature = "simd")]
+mod simd;
+#[cfg(feature = "threads")]
+mod threads;
+
 /// The outcome of a `wasmi` instruction execution.
 ///
 /// # Note
@@ -33,6 +59,12 @@ pub enum ExecutionOutcome {
     ExecuteCall(Func),
     /// Return from current function block.
     Return(DropKeep),
+    /// Execute a tail call replacing the current function frame.
+    ReturnCall(Func),
+    /// Throw a new exception with the given tag.
+    Throw(TagIdx),
+    /// Rethrow the exception caught at the given slot.
+    Rethrow(ExceptionSlot),
 }
 
 /// State that is used during Wasm function execution.
@@ -48,16 +80,18 @@ pub struct ExecutionContext<'engine, 'func> {
 
 impl<'engine, 'func> ExecutionContext<'engine, 'func> {
     /// Creates an execution context for the given [`FunctionFrame`].
+    ///
+    /// The `func_body` is the resolved function body of the `frame`.
     pub fn new(
-        engine: &'engine mut EngineInner,
+        func_body: ResolvedFuncBody<'engine>,
+        value_stack: &'engine mut ValueStack,
         frame: &'func mut FunctionFrame,
     ) -> Result<Self, Trap> {
-        let resolved = engine.code_map.resolve(frame.func_body);
-        frame.initialize(resolved, &mut engine.value_stack)?;
+        frame.initialize(func_body, value_stack)?;
         Ok(Self {
-            value_stack: &mut engine.value_stack,
+            value_stack,
             frame,
-            func_body: resolved,
+            func_body,
         })
     }
 
@@ -67,12 +101,39 @@ impl<'engine, 'func> ExecutionContext<'engine, 'func> {
     ///
     /// This executes instructions sequentially until either the function
     /// calls into another function or the function returns to its caller.
+    ///
+    /// If fuel metering is enabled every executed instruction consumes one unit of fuel.
+    ///
+    /// If epoch interruption is enabled the epoch deadline of the [`Store`] is
+    /// checked upon function entry and upon backward branches, e.g. loop back-edges.
+    ///
+    /// # Errors
+    ///
+    /// - If the execution of an instruction trapped.
+    /// - If fuel metering is enabled and the [`Store`] ran out of fuel.
+    ///
+    /// [`Store`]: [`crate::Store`]
     #[inline(always)]
     pub fn execute_frame(
         self,
         mut ctx: impl AsContextMut,
     ) -> Result<FunctionExecutionOutcome, Trap> {
+        let consume_fuel = ctx.as_context_mut().store.fuel_mut().is_enabled();
+        let check_epoch = ctx.as_context_mut().store.is_epoch_interruption_enabled();
+        if check_epoch
+            && self.frame.inst_ptr == 0
+            && ctx.as_context_mut().store.is_epoch_deadline_reached()
+        {
+            return Ok(FunctionExecutionOutcome::EpochDeadline);
+        }
         'outer: loop {
+            if consume_fuel {
+                ctx.as_context_mut()
+                    .store
+                    .fuel_mut()
+                    .consume(1)
+                    .map_err(|_| TrapCode::OutOfFuel)?;
+            }
             let pc = self.frame.inst_ptr;
             let inst_context =
                 InstructionExecutionContext::new(self.value_stack, self.frame, &mut ctx);
@@ -85,6 +146,12 @@ impl<'engine, 'func> ExecutionContext<'engine, 'func> {
                     self.value_stack.drop_keep(target.drop_keep());
                     // Set instruction pointer to the branch target.
                     self.frame.inst_ptr = target.destination_pc().into_usize();
+                    if check_epoch
+                        && self.frame.inst_ptr <= pc
+                        && ctx.as_context_mut().store.is_epoch_deadline_reached()
+                    {
+                        return Ok(FunctionExecutionOutcome::EpochDeadline);
+                    }
                 }
                 ExecutionOutcome::ExecuteCall(func) => {
                     // Advance instruction pointer.
@@ -95,6 +162,19 @@ impl<'engine, 'func> ExecutionContext<'engine, 'func> {
                     self.value_stack.drop_keep(drop_keep);
                     break 'outer;
                 }
+                ExecutionOutcome::ReturnCall(func) => {
+                    return Ok(FunctionExecutionOutcome::TailCall(func));
+                }
+                ExecutionOutcome::Throw(tag) => {
+                    // Advance instruction pointer.
+                    self.frame.inst_ptr += 1;
+                    return Ok(FunctionExecutionOutcome::Throw(tag));
+                }
+                ExecutionOutcome::Rethrow(slot) => {
+                    // Advance instruction pointer.
+                    self.frame.inst_ptr += 1;
+                    return Ok(FunctionExecutionOutcome::Rethrow(slot));
+                }
             }
         }
         Ok(FunctionExecutionOutcome::Return)
@@ -131,22 +211,35 @@ where
         }
     }
 
-    /// Returns the default linear memory.
+    /// Returns the linear memory at the given index.
     ///
     /// # Panics
     ///
-    /// If there is no default linear memory.
-    fn default_memory(&mut self) -> Memory {
-        self.frame.default_memory(self.ctx.as_context())
+    /// If there is no linear memory at the given index.
+    fn memory(&mut self, memory_index: MemoryIdx) -> Memory {
+        self.frame
+            .memory(self.ctx.as_context(), memory_index.into_inner())
     }
 
-    /// Returns the default linear memory.
+    /// Returns the table at the given index.
     ///
     /// # Panics
     ///
-    /// If there is no default linear memory.
-    fn default_table(&mut self) -> Table {
-        self.frame.default_table(self.ctx.as_context())
+    /// If there is no table at the given index.
+    fn table(&self, table_index: TableIdx) -> Table {
+        self.frame
+            .instance
+            .get_table(self.ctx.as_context(), table_index.into_inner())
+            .unwrap_or_else(|| panic!("missing table at index {:?}", table_index))
+    }
+
+    /// Pops the destination index, source index and length operands
+    /// of a bulk memory instruction from the value stack.
+    fn pop_bulk_operands(&mut self) -> (usize, usize, usize) {
+        let len: u32 = self.value_stack.pop_as();
+        let src: u32 = self.value_stack.pop_as();
+        let dst: u32 = self.value_stack.pop_as();
+        (dst as usize, src as usize, len as usize)
     }
 
     /// Returns the global variable at the given index.
@@ -161,27 +254,117 @@ where
             .unwrap_or_else(|| panic!("missing global at index {:?}", global_index))
     }
 
+    /// Returns the [`Func`] at the `func_index` of the executed instance.
+    ///
+    /// # Panics
+    ///
+    /// If there is no function at the given index.
+    fn func(&mut self, func_index: FuncIdx) -> Func {
+        self.frame
+            .instance
+            .get_func(self.ctx.as_context_mut(), func_index.into_inner())
+            .unwrap_or_else(|| panic!("missing function at index {:?}", func_index))
+    }
+
+    /// Pops the element index from the value stack and returns the [`Func`]
+    /// at this index of the `table` for an indirect call.
+    ///
+    /// # Errors
+    ///
+    /// - If the element index is out of bounds for the `table`.
+    /// - If the table element at the index is `null`.
+    /// - If the [`Func`] has been removed from its store.
+    /// - If the signature of the [`Func`] does not match the expected signature.
+    fn indirect_func(
+        &mut self,
+        table: TableIdx,
+        signature_index: SignatureIdx,
+    ) -> Result<Func, Trap> {
+        let func_index: u32 = self.value_stack.pop_as();
+        let table = self.table(table);
+        let element = self
+            .ctx
+            .as_context()
+            .store
+            .resolve_table(table)
+            .get_untyped(func_index as usize)
+            .map_err(|_| TrapCode::TableAccessOutOfBounds)?;
+        let func = FuncRef::from(element)
+            .func()
+            .copied()
+            .ok_or(TrapCode::ElemUninitialized)?;
+        let actual_signature = self
+            .ctx
+            .as_context()
+            .store
+            .try_resolve_func(func)
+            .ok_or(TrapCode::DanglingFunc)?
+            .signature();
+        let expected_signature = self
+            .frame
+            .instance
+            .get_signature(self.ctx.as_context(), signature_index.into_inner())
+            .unwrap_or_else(|| {
+                panic!(
+                    "missing signature for call_indirect at index: {:?}",
+                    signature_index,
+                )
+            });
+        if actual_signature != expected_signature {
+            return Err(TrapCode::UnexpectedSignature.into());
+        }
+        Ok(func)
+    }
+
     /// Returns the local depth as `usize`.
     fn convert_local_depth(local_depth: LocalIdx) -> usize {
         // TODO: calculate the -1 offset at module compilation time.
         (local_depth.into_inner() - 1) as usize
     }
 
+    /// Converts the `address` operand of a linear memory instruction into a `u64` value.
+    ///
+    /// # Note
+    ///
+    /// The `address` operand is an `i64` value if the linear memory
+    /// is indexed using 64-bit addresses and an `i32` value otherwise.
+    fn address_operand(memory_type: MemoryType, address: UntypedValue) -> u64 {
+        match memory_type.is_64() {
+            true => u64::from(address),
+            false => u64::from(u32::from(address)),
+        }
+    }
+
+    /// Converts the `value` operand of a linear memory instruction into a `usize` value.
+    ///
+    /// # Errors
+    ///
+    /// If the `value` does not fit into a `usize` in which case it is out of bounds.
+    fn bounds_operand(value: u64) -> Result<usize, Trap> {
+        usize::try_from(value)
+            .map_err(|_| TrapCode::MemoryAccessOutOfBounds)
+            .map_err(Into::into)
+    }
+
     /// Calculates the effective address of a linear memory access.
     ///
     /// # Errors
     ///
     /// If the resulting effective address overflows.
-    fn effective_address(offset: Offset, address: u32) -> Result<usize, Trap> {
+    fn effective_address(
+        memory_type: MemoryType,
+        offset: Offset,
+        address: UntypedValue,
+    ) -> Result<usize, Trap> {
         offset
             .into_inner()
-            .checked_add(address)
-            .map(|address| address as usize)
+            .checked_add(Self::address_operand(memory_type, address))
             .ok_or(TrapCode::MemoryAccessOutOfBounds)
             .map_err(Into::into)
+            .and_then(Self::bounds_operand)
     }
 
-    /// Loads a value of type `T` from the default memory at the given address offset.
+    /// Loads a value of type `T` from the `memory` at the given address offset.
     ///
     /// # Note
     ///
@@ -191,25 +374,29 @@ where
     /// - `i64.load`
     /// - `f32.load`
     /// - `f64.load`
-    fn execute_load<T>(&mut self, offset: Offset) -> Result<ExecutionOutcome, Trap>
+    fn execute_load<T>(
+        &mut self,
+        memory: MemoryIdx,
+        offset: Offset,
+    ) -> Result<ExecutionOutcome, Trap>
     where
         UntypedValue: From<T>,
         T: LittleEndianConvert,
     {
-        let memory = self.default_memory();
+        let memory = self.memory(memory);
+        let memory = self.ctx.as_context().store.resolve_memory(memory);
         let entry = self.value_stack.last_mut();
-        let raw_address = u32::from(*entry);
-        let address = Self::effective_address(offset, raw_address)?;
+        let address = Self::effective_address(memory.memory_type(), offset, *entry)?;
         let mut bytes = <<T as LittleEndianConvert>::Bytes as Default>::default();
         memory
-            .read(self.ctx.as_context(), address, bytes.as_mut())
+            .read(address, bytes.as_mut())
             .map_err(|_| TrapCode::MemoryAccessOutOfBounds)?;
         let value = <T as LittleEndianConvert>::from_le_bytes(bytes);
         *entry = value.into();
         Ok(ExecutionOutcome::Continue)
     }
 
-    /// Loads a vaoue of type `U` from the default memory at the given address offset and extends it into `T`.
+    /// Loads a vaoue of type `U` from the `memory` at the given address offset and extends it into `T`.
     ///
     /// # Note
     ///
@@ -225,25 +412,94 @@ where
     /// - `i64.load_16u`
     /// - `i64.load_32s`
     /// - `i64.load_32u`
-    fn execute_load_extend<T, U>(&mut self, offset: Offset) -> Result<ExecutionOutcome, Trap>
+    fn execute_load_extend<T, U>(
+        &mut self,
+        memory: MemoryIdx,
+        offset: Offset,
+    ) -> Result<ExecutionOutcome, Trap>
     where
         T: ExtendInto<U> + LittleEndianConvert,
         UntypedValue: From<U>,
     {
-        let memory = self.default_memory();
+        let memory = self.memory(memory);
+        let memory = self.ctx.as_context().store.resolve_memory(memory);
         let entry = self.value_stack.last_mut();
-        let raw_address = u32::from(*entry);
-        let address = Self::effective_address(offset, raw_address)?;
+        let address = Self::effective_address(memory.memory_type(), offset, *entry)?;
         let mut bytes = <<T as LittleEndianConvert>::Bytes as Default>::default();
         memory
-            .read(self.ctx.as_context(), address, bytes.as_mut())
+            .read(address, bytes.as_mut())
             .map_err(|_| TrapCode::MemoryAccessOutOfBounds)?;
         let extended = <T as LittleEndianConvert>::from_le_bytes(bytes).extend_into();
         *entry = extended.into();
         Ok(ExecutionOutcome::Continue)
     }
 
-    /// Stores a value of type `T` into the default memory at the given address offset.
+    /// Loads a value of type `T` from the `memory` at the address stored in the `base` register.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`Self::execute_load`] this pushes the loaded value onto the stack.
+    ///
+    /// This can be used to emulate `local.get` followed by the following Wasm operands:
+    ///
+    /// - `i32.load`
+    /// - `i64.load`
+    fn execute_load_local<T>(
+        &mut self,
+        base: Register,
+        memory: MemoryIdx,
+        offset: Offset,
+    ) -> Result<ExecutionOutcome, Trap>
+    where
+        UntypedValue: From<T>,
+        T: LittleEndianConvert,
+    {
+        let memory = self.memory(memory);
+        let memory = self.ctx.as_context().store.resolve_memory(memory);
+        let base = self.value_stack.peek(base.into_usize());
+        let address = Self::effective_address(memory.memory_type(), offset, base)?;
+        let mut bytes = <<T as LittleEndianConvert>::Bytes as Default>::default();
+        memory
+            .read(address, bytes.as_mut())
+            .map_err(|_| TrapCode::MemoryAccessOutOfBounds)?;
+        let value = <T as LittleEndianConvert>::from_le_bytes(bytes);
+        self.value_stack.push(value);
+        Ok(ExecutionOutcome::Continue)
+    }
+
+    /// Loads a value of type `U` from the address in the `base` register and extends it into `T`.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`Self::execute_load_extend`] this pushes the loaded value onto the stack.
+    ///
+    /// This can be used to emulate `local.get` followed by the following Wasm operands:
+    ///
+    /// - `i32.load_8u`
+    fn execute_load_extend_local<T, U>(
+        &mut self,
+        base: Register,
+        memory: MemoryIdx,
+        offset: Offset,
+    ) -> Result<ExecutionOutcome, Trap>
+    where
+        T: ExtendInto<U> + LittleEndianConvert,
+        UntypedValue: From<U>,
+    {
+        let memory = self.memory(memory);
+        let memory = self.ctx.as_context().store.resolve_memory(memory);
+        let base = self.value_stack.peek(base.into_usize());
+        let address = Self::effective_address(memory.memory_type(), offset, base)?;
+        let mut bytes = <<T as LittleEndianConvert>::Bytes as Default>::default();
+        memory
+            .read(address, bytes.as_mut())
+            .map_err(|_| TrapCode::MemoryAccessOutOfBounds)?;
+        let extended = <T as LittleEndianConvert>::from_le_bytes(bytes).extend_into();
+        self.value_stack.push(extended);
+        Ok(ExecutionOutcome::Continue)
+    }
+
+    /// Stores a value of type `T` into the `memory` at the given address offset.
     ///
     /// # Note
     ///
@@ -253,22 +509,27 @@ where
     /// - `i64.store`
     /// - `f32.store`
     /// - `f64.store`
-    fn execute_store<T>(&mut self, offset: Offset) -> Result<ExecutionOutcome, Trap>
+    fn execute_store<T>(
+        &mut self,
+        memory: MemoryIdx,
+        offset: Offset,
+    ) -> Result<ExecutionOutcome, Trap>
     where
         T: LittleEndianConvert + From<UntypedValue>,
     {
         let stack_value = self.value_stack.pop_as::<T>();
-        let raw_address = self.value_stack.pop_as::<u32>();
-        let address = Self::effective_address(offset, raw_address)?;
-        let memory = self.default_memory();
+        let raw_address = self.value_stack.pop();
+        let memory = self.memory(memory);
+        let memory = self.ctx.as_context_mut().store.resolve_memory_mut(memory);
+        let address = Self::effective_address(memory.memory_type(), offset, raw_address)?;
         let bytes = <T as LittleEndianConvert>::into_le_bytes(stack_value);
         memory
-            .write(self.ctx.as_context_mut(), address, bytes.as_ref())
+            .write(address, bytes.as_ref())
             .map_err(|_| TrapCode::MemoryAccessOutOfBounds)?;
         Ok(ExecutionOutcome::Continue)
     }
 
-    /// Stores a value of type `T` wrapped to type `U` into the default memory at the given address offset.
+    /// Stores a value of type `T` wrapped to type `U` into the `memory` at the given address offset.
     ///
     /// # Note
     ///
@@ -279,18 +540,23 @@ where
     /// - `i64.store8`
     /// - `i64.store16`
     /// - `i64.store32`
-    fn execute_store_wrap<T, U>(&mut self, offset: Offset) -> Result<ExecutionOutcome, Trap>
+    fn execute_store_wrap<T, U>(
+        &mut self,
+        memory: MemoryIdx,
+        offset: Offset,
+    ) -> Result<ExecutionOutcome, Trap>
     where
         T: WrapInto<U> + From<UntypedValue>,
         U: LittleEndianConvert,
     {
         let wrapped_value = self.value_stack.pop_as::<T>().wrap_into();
-        let raw_address = self.value_stack.pop_as::<u32>();
-        let address = Self::effective_address(offset, raw_address)?;
-        let memory = self.default_memory();
+        let raw_address = self.value_stack.pop();
+        let memory = self.memory(memory);
+        let memory = self.ctx.as_context_mut().store.resolve_memory_mut(memory);
+        let address = Self::effective_address(memory.memory_type(), offset, raw_address)?;
         let bytes = <U as LittleEndianConvert>::into_le_bytes(wrapped_value);
         memory
-            .write(self.ctx.as_context_mut(), address, bytes.as_ref())
+            .write(address, bytes.as_ref())
             .map_err(|_| TrapCode::MemoryAccessOutOfBounds)?;
         Ok(ExecutionOutcome::Continue)
     }
@@ -335,6 +601,24 @@ where
         Ok(ExecutionOutcome::Continue)
     }
 
+    /// Branches to the `target` if `f` evaluates to `true` for the top two stack entries.
+    ///
+    /// # Note
+    ///
+    /// This is used to emulate a binary comparison directly followed by a Wasm `br_if`.
+    fn execute_br_if_binary(
+        &mut self,
+        target: Target,
+        f: fn(UntypedValue, UntypedValue) -> UntypedValue,
+    ) -> Result<ExecutionOutcome, Trap> {
+        let (left, right) = self.value_stack.pop2();
+        if bool::from(f(left, right)) {
+            Ok(ExecutionOutcome::Branch(target))
+        } else {
+            Ok(ExecutionOutcome::Continue)
+        }
+    }
+
     fn execute_reinterpret<T, U>(&mut self) -> Result<ExecutionOutcome, Trap>
     where
         UntypedValue: From<U>,
@@ -431,10 +715,10 @@ where
 
     fn visit_set_global(&mut self, global_index: GlobalIdx) -> Self::Outcome {
         let global = self.global(global_index);
-        let new_value = self
-            .value_stack
-            .pop()
-            .with_type(global.value_type(self.ctx.as_context()));
+        let new_value = Value::from_untyped(
+            self.value_stack.pop(),
+            global.value_type(self.ctx.as_context()),
+        );
         global
             .set(self.ctx.as_context_mut(), new_value)
             .unwrap_or_else(|error| panic!("encountered type mismatch upon global_set: {}", error));
@@ -442,38 +726,44 @@ where
     }
 
     fn visit_call(&mut self, func_index: FuncIdx) -> Self::Outcome {
-        let func = self
-            .frame
-            .instance
-            .get_func(self.ctx.as_context_mut(), func_index.into_inner())
-            .unwrap_or_else(|| panic!("missing function at index {:?}", func_index));
+        let func = self.func(func_index);
         Ok(ExecutionOutcome::ExecuteCall(func))
     }
 
-    fn visit_call_indirect(&mut self, signature_index: SignatureIdx) -> Self::Outcome {
-        let func_index: u32 = self.value_stack.pop_as();
-        let table = self.default_table();
-        let func = table
-            .get(self.ctx.as_context(), func_index as usize)
-            .map_err(|_| TrapCode::TableAccessOutOfBounds)?
-            .ok_or(TrapCode::ElemUninitialized)?;
-        let actual_signature = func.signature(self.ctx.as_context());
-        let expected_signature = self
-            .frame
-            .instance
-            .get_signature(self.ctx.as_context(), signature_index.into_inner())
-            .unwrap_or_else(|| {
-                panic!(
-                    "missing signature for call_indirect at index: {:?}",
-                    signature_index,
-                )
-            });
-        if actual_signature != expected_signature {
-            return Err(TrapCode::UnexpectedSignature).map_err(Into::into);
-        }
+    fn visit_call_indirect(
+        &mut self,
+        table: TableIdx,
+        signature_index: SignatureIdx,
+    ) -> Self::Outcome {
+        let func = self.indirect_func(table, signature_index)?;
         Ok(ExecutionOutcome::ExecuteCall(func))
     }
 
+    fn visit_return_call(&mut self, func_index: FuncIdx, drop_keep: DropKeep) -> Self::Outcome {
+        let func = self.func(func_index);
+        self.value_stack.drop_keep(drop_keep);
+        Ok(ExecutionOutcome::ReturnCall(func))
+    }
+
+    fn visit_return_call_indirect(
+        &mut self,
+        table: TableIdx,
+        signature_index: SignatureIdx,
+        drop_keep: DropKeep,
+    ) -> Self::Outcome {
+        let func = self.indirect_func(table, signature_index)?;
+        self.value_stack.drop_keep(drop_keep);
+        Ok(ExecutionOutcome::ReturnCall(func))
+    }
+
+    fn visit_throw(&mut self, tag: TagIdx) -> Self::Outcome {
+        Ok(ExecutionOutcome::Throw(tag))
+    }
+
+    fn visit_rethrow(&mut self, slot: ExceptionSlot) -> Self::Outcome {
+        Ok(ExecutionOutcome::Rethrow(slot))
+    }
+
     fn visit_const(&mut self, bytes: UntypedValue) -> Self::Outcome {
         self.value_stack.push(bytes);
         Ok(ExecutionOutcome::Continue)
@@ -493,118 +783,382 @@ where
         Ok(ExecutionOutcome::Continue)
     }
 
-    fn visit_current_memory(&mut self) -> Self::Outcome {
-        let memory = self.default_memory();
-        let result = memory.current_pages(self.ctx.as_context()).0 as u32;
-        self.value_stack.push(result);
+    fn visit_current_memory(&mut self, memory: MemoryIdx) -> Self::Outcome {
+        let memory = self.memory(memory);
+        let memory = self.ctx.as_context().store.resolve_memory(memory);
+        let result = memory.current_pages().0 as u64;
+        match memory.memory_type().is_64() {
+            true => self.value_stack.push(result),
+            false => self.value_stack.push(result as u32),
+        }
+        Ok(ExecutionOutcome::Continue)
+    }
+
+    fn visit_grow_memory(&mut self, memory: MemoryIdx) -> Self::Outcome {
+        let pages = self.value_stack.pop();
+        let memory = self.memory(memory);
+        let memory_type = memory.memory_type(self.ctx.as_context());
+        let pages = Self::address_operand(memory_type, pages);
+        let result = match usize::try_from(pages) {
+            Ok(pages) => memory.grow(self.ctx.as_context_mut(), Pages(pages)),
+            Err(_) => Err(MemoryError::OutOfBoundsGrowth),
+        };
+        let new_size = match result {
+            Ok(Pages(old_size)) => old_size as u64,
+            Err(MemoryError::ResourceLimitExceeded)
+                if self.ctx.as_context_mut().store.trap_on_grow_failure() =>
+            {
+                return Err(TrapCode::GrowthOperationLimited.into());
+            }
+            // Note: The WebAssembly spec demands to return `-1`
+            //       in case of failure for this instruction.
+            Err(_) => u64::MAX,
+        };
+        match memory_type.is_64() {
+            true => self.value_stack.push(new_size),
+            false => self.value_stack.push(new_size as u32),
+        }
+        Ok(ExecutionOutcome::Continue)
+    }
+
+    fn visit_memory_init(&mut self, memory: MemoryIdx, segment: DataSegmentIdx) -> Self::Outcome {
+        let len: u32 = self.value_stack.pop_as();
+        let src: u32 = self.value_stack.pop_as();
+        let dst = self.value_stack.pop();
+        let (src, len) = (src as usize, len as usize);
+        let memory = self.memory(memory);
+        let (instance, memory) = self
+            .ctx
+            .as_context_mut()
+            .store
+            .resolve_instance_and_memory_mut(self.frame.instance, memory);
+        let dst = Self::bounds_operand(Self::address_operand(memory.memory_type(), dst))?;
+        let bytes = instance
+            .get_data_segment(segment.into_inner())
+            .unwrap_or_else(|| panic!("missing data segment at index {:?}", segment))
+            .bytes();
+        let src_bytes = src
+            .checked_add(len)
+            .and_then(|src_end| bytes.get(src..src_end))
+            .ok_or(TrapCode::MemoryAccessOutOfBounds)?;
+        let dst_bytes = dst
+            .checked_add(len)
+            .and_then(|dst_end| memory.data_mut().get_mut(dst..dst_end))
+            .ok_or(TrapCode::MemoryAccessOutOfBounds)?;
+        dst_bytes.copy_from_slice(src_bytes);
+        Ok(ExecutionOutcome::Continue)
+    }
+
+    fn visit_data_drop(&mut self, segment: DataSegmentIdx) -> Self::Outcome {
+        let instance = self.frame.instance;
+        self.ctx
+            .as_context_mut()
+            .store
+            .resolve_instance_mut(instance)
+            .get_data_segment_mut(segment.into_inner())
+            .unwrap_or_else(|| panic!("missing data segment at index {:?}", segment))
+            .drop_bytes();
+        Ok(ExecutionOutcome::Continue)
+    }
+
+    fn visit_memory_copy(&mut self, dst_memory: MemoryIdx, src_memory: MemoryIdx) -> Self::Outcome {
+        let len = self.value_stack.pop();
+        let src = self.value_stack.pop();
+        let dst = self.value_stack.pop();
+        let dst_memory = self.memory(dst_memory);
+        let src_memory = self.memory(src_memory);
+        let store = self.ctx.as_context().store;
+        let dst_type = store.resolve_memory(dst_memory).memory_type();
+        let src_type = store.resolve_memory(src_memory).memory_type();
+        // Note: The `len` operand is an `i64` value only if both linear memories are 64-bit.
+        let len_type = match dst_type.is_64() {
+            true => src_type,
+            false => dst_type,
+        };
+        let dst = Self::bounds_operand(Self::address_operand(dst_type, dst))?;
+        let src = Self::bounds_operand(Self::address_operand(src_type, src))?;
+        let len = Self::bounds_operand(Self::address_operand(len_type, len))?;
+        let same_memory = dst_memory.into_inner() == src_memory.into_inner();
+        // Note: Distinct linear memories might still operate on the same bytes
+        //       if both have been created from the same shared linear memory.
+        #[cfg(feature = "threads")]
+        let same_memory = same_memory
+            || store
+                .resolve_memory(dst_memory)
+                .shares_bytes_with(store.resolve_memory(src_memory));
+        if same_memory {
+            let bytes = dst_memory.data_mut(self.ctx.as_context_mut());
+            let len_bytes = bytes.len();
+            let src_end = src
+                .checked_add(len)
+                .filter(|&src_end| src_end <= len_bytes)
+                .ok_or(TrapCode::MemoryAccessOutOfBounds)?;
+            dst.checked_add(len)
+                .filter(|&dst_end| dst_end <= len_bytes)
+                .ok_or(TrapCode::MemoryAccessOutOfBounds)?;
+            bytes.copy_within(src..src_end, dst);
+            return Ok(ExecutionOutcome::Continue);
+        }
+        let (dst_memory, src_memory) = self
+            .ctx
+            .as_context_mut()
+            .store
+            .resolve_memory_pair_mut(dst_memory, src_memory);
+        let src_bytes = src
+            .checked_add(len)
+            .and_then(|src_end| src_memory.data().get(src..src_end))
+            .ok_or(TrapCode::MemoryAccessOutOfBounds)?;
+        let dst_bytes = dst
+            .checked_add(len)
+            .and_then(|dst_end| dst_memory.data_mut().get_mut(dst..dst_end))
+            .ok_or(TrapCode::MemoryAccessOutOfBounds)?;
+        dst_bytes.copy_from_slice(src_bytes);
+        Ok(ExecutionOutcome::Continue)
+    }
+
+    fn visit_memory_fill(&mut self, memory: MemoryIdx) -> Self::Outcome {
+        let len = self.value_stack.pop();
+        let value: u32 = self.value_stack.pop_as();
+        let dst = self.value_stack.pop();
+        let memory = self.memory(memory);
+        let memory = self.ctx.as_context_mut().store.resolve_memory_mut(memory);
+        let memory_type = memory.memory_type();
+        let dst = Self::bounds_operand(Self::address_operand(memory_type, dst))?;
+        let len = Self::bounds_operand(Self::address_operand(memory_type, len))?;
+        let bytes = dst
+            .checked_add(len)
+            .and_then(|dst_end| memory.data_mut().get_mut(dst..dst_end))
+            .ok_or(TrapCode::MemoryAccessOutOfBounds)?;
+        bytes.fill(value as u8);
+        Ok(ExecutionOutcome::Continue)
+    }
+
+    fn visit_table_init(&mut self, table: TableIdx, elem: ElementSegmentIdx) -> Self::Outcome {
+        let (dst, src, len) = self.pop_bulk_operands();
+        let table = self.table(table);
+        let (instance, table) = self
+            .ctx
+            .as_context_mut()
+            .store
+            .resolve_instance_and_table_mut(self.frame.instance, table);
+        let items = instance
+            .get_element_segment(elem.into_inner())
+            .unwrap_or_else(|| panic!("missing element segment at index {:?}", elem))
+            .items();
+        table
+            .init(dst, items, src, len)
+            .map_err(|_| TrapCode::TableAccessOutOfBounds)?;
+        Ok(ExecutionOutcome::Continue)
+    }
+
+    fn visit_elem_drop(&mut self, segment: ElementSegmentIdx) -> Self::Outcome {
+        let instance = self.frame.instance;
+        self.ctx
+            .as_context_mut()
+            .store
+            .resolve_instance_mut(instance)
+            .get_element_segment_mut(segment.into_inner())
+            .unwrap_or_else(|| panic!("missing element segment at index {:?}", segment))
+            .drop_items();
+        Ok(ExecutionOutcome::Continue)
+    }
+
+    fn visit_table_copy(&mut self, dst: TableIdx, src: TableIdx) -> Self::Outcome {
+        let (dst_index, src_index, len) = self.pop_bulk_operands();
+        let dst_table = self.table(dst);
+        let src_table = self.table(src);
+        Table::copy(
+            self.ctx.as_context_mut(),
+            &dst_table,
+            dst_index,
+            &src_table,
+            src_index,
+            len,
+        )
+        .map_err(|_| TrapCode::TableAccessOutOfBounds)?;
+        Ok(ExecutionOutcome::Continue)
+    }
+
+    fn visit_table_get(&mut self, table: TableIdx) -> Self::Outcome {
+        let index: u32 = self.value_stack.pop_as();
+        let table = self.table(table);
+        let element = self
+            .ctx
+            .as_context()
+            .store
+            .resolve_table(table)
+            .get_untyped(index as usize)
+            .map_err(|_| TrapCode::TableAccessOutOfBounds)?;
+        self.value_stack.push(element);
+        Ok(ExecutionOutcome::Continue)
+    }
+
+    fn visit_table_set(&mut self, table: TableIdx) -> Self::Outcome {
+        let element = self.value_stack.pop();
+        let index: u32 = self.value_stack.pop_as();
+        let table = self.table(table);
+        self.ctx
+            .as_context_mut()
+            .store
+            .resolve_table_mut(table)
+            .set_untyped(index as usize, element)
+            .map_err(|_| TrapCode::TableAccessOutOfBounds)?;
+        Ok(ExecutionOutcome::Continue)
+    }
+
+    fn visit_table_size(&mut self, table: TableIdx) -> Self::Outcome {
+        let table = self.table(table);
+        let len = table.len(self.ctx.as_context()) as u32;
+        self.value_stack.push(len);
         Ok(ExecutionOutcome::Continue)
     }
 
-    fn visit_grow_memory(&mut self) -> Self::Outcome {
-        let pages: u32 = self.value_stack.pop_as();
-        let memory = self.default_memory();
-        let new_size = match memory.grow(self.ctx.as_context_mut(), Pages(pages as usize)) {
-            Ok(Pages(old_size)) => old_size as u32,
-            Err(_) => {
-                // Note: The WebAssembly spec demands to return `0xFFFF_FFFF`
-                //       in case of failure for this instruction.
-                u32::MAX
+    fn visit_table_grow(&mut self, table: TableIdx) -> Self::Outcome {
+        let grow_by: u32 = self.value_stack.pop_as();
+        let init = self.value_stack.pop();
+        let table = self.table(table);
+        let result = table
+            .ensure_growth_permitted(self.ctx.as_context_mut(), grow_by as usize)
+            .and_then(|()| {
+                let table = self.ctx.as_context_mut().store.resolve_table_mut(table);
+                let len = table.len();
+                table.grow_untyped(grow_by as usize, init).map(|()| len)
+            });
+        let new_len = match result {
+            Ok(len) => len as u32,
+            Err(TableError::ResourceLimitExceeded)
+                if self.ctx.as_context_mut().store.trap_on_grow_failure() =>
+            {
+                return Err(TrapCode::GrowthOperationLimited.into());
             }
+            // Note: The WebAssembly spec demands to return `-1`
+            //       in case of failure for this instruction.
+            Err(_) => u32::MAX,
         };
-        self.value_stack.push(new_size);
+        self.value_stack.push(new_len);
+        Ok(ExecutionOutcome::Continue)
+    }
+
+    fn visit_table_fill(&mut self, table: TableIdx) -> Self::Outcome {
+        let len: u32 = self.value_stack.pop_as();
+        let value = self.value_stack.pop();
+        let dst: u32 = self.value_stack.pop_as();
+        let table = self.table(table);
+        self.ctx
+            .as_context_mut()
+            .store
+            .resolve_table_mut(table)
+            .fill(dst as usize, value, len as usize)
+            .map_err(|_| TrapCode::TableAccessOutOfBounds)?;
         Ok(ExecutionOutcome::Continue)
     }
 
-    fn visit_i32_load(&mut self, offset: Offset) -> Self::Outcome {
-        self.execute_load::<i32>(offset)
+    fn visit_ref_is_null(&mut self) -> Self::Outcome {
+        let entry = self.value_stack.last_mut();
+        *entry = UntypedValue::from(entry.to_bits() == 0);
+        Ok(ExecutionOutcome::Continue)
     }
 
-    fn visit_i64_load(&mut self, offset: Offset) -> Self::Outcome {
-        self.execute_load::<i64>(offset)
+    fn visit_ref_func(&mut self, func_index: FuncIdx) -> Self::Outcome {
+        let func = self
+            .frame
+            .instance
+            .get_func(self.ctx.as_context(), func_index.into_inner())
+            .unwrap_or_else(|| panic!("missing function at index {:?}", func_index));
+        self.value_stack.push(FuncRef::new(func));
+        Ok(ExecutionOutcome::Continue)
     }
 
-    fn visit_f32_load(&mut self, offset: Offset) -> Self::Outcome {
-        self.execute_load::<F32>(offset)
+    fn visit_i32_load(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome {
+        self.execute_load::<i32>(memory, offset)
     }
 
-    fn visit_f64_load(&mut self, offset: Offset) -> Self::Outcome {
-        self.execute_load::<F64>(offset)
+    fn visit_i64_load(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome {
+        self.execute_load::<i64>(memory, offset)
     }
 
-    fn visit_i32_load_i8(&mut self, offset: Offset) -> Self::Outcome {
-        self.execute_load_extend::<i8, i32>(offset)
+    fn visit_f32_load(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome {
+        self.execute_load::<F32>(memory, offset)
     }
 
-    fn visit_i32_load_u8(&mut self, offset: Offset) -> Self::Outcome {
-        self.execute_load_extend::<u8, i32>(offset)
+    fn visit_f64_load(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome {
+        self.execute_load::<F64>(memory, offset)
     }
 
-    fn visit_i32_load_i16(&mut self, offset: Offset) -> Self::Outcome {
-        self.execute_load_extend::<i16, i32>(offset)
+    fn visit_i32_load_i8(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome {
+        self.execute_load_extend::<i8, i32>(memory, offset)
     }
 
-    fn visit_i32_load_u16(&mut self, offset: Offset) -> Self::Outcome {
-        self.execute_load_extend::<u16, i32>(offset)
+    fn visit_i32_load_u8(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome {
+        self.execute_load_extend::<u8, i32>(memory, offset)
     }
 
-    fn visit_i64_load_i8(&mut self, offset: Offset) -> Self::Outcome {
-        self.execute_load_extend::<i8, i64>(offset)
+    fn visit_i32_load_i16(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome {
+        self.execute_load_extend::<i16, i32>(memory, offset)
     }
 
-    fn visit_i64_load_u8(&mut self, offset: Offset) -> Self::Outcome {
-        self.execute_load_extend::<u8, i64>(offset)
+    fn visit_i32_load_u16(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome {
+        self.execute_load_extend::<u16, i32>(memory, offset)
     }
 
-    fn visit_i64_load_i16(&mut self, offset: Offset) -> Self::Outcome {
-        self.execute_load_extend::<i16, i64>(offset)
+    fn visit_i64_load_i8(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome {
+        self.execute_load_extend::<i8, i64>(memory, offset)
     }
 
-    fn visit_i64_load_u16(&mut self, offset: Offset) -> Self::Outcome {
-        self.execute_load_extend::<u16, i64>(offset)
+    fn visit_i64_load_u8(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome {
+        self.execute_load_extend::<u8, i64>(memory, offset)
     }
 
-    fn visit_i64_load_i32(&mut self, offset: Offset) -> Self::Outcome {
-        self.execute_load_extend::<i32, i64>(offset)
+    fn visit_i64_load_i16(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome {
+        self.execute_load_extend::<i16, i64>(memory, offset)
     }
 
-    fn visit_i64_load_u32(&mut self, offset: Offset) -> Self::Outcome {
-        self.execute_load_extend::<u32, i64>(offset)
+    fn visit_i64_load_u16(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome {
+        self.execute_load_extend::<u16, i64>(memory, offset)
     }
 
-    fn visit_i32_store(&mut self, offset: Offset) -> Self::Outcome {
-        self.execute_store::<i32>(offset)
+    fn visit_i64_load_i32(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome {
+        self.execute_load_extend::<i32, i64>(memory, offset)
     }
 
-    fn visit_i64_store(&mut self, offset: Offset) -> Self::Outcome {
-        self.execute_store::<i64>(offset)
+    fn visit_i64_load_u32(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome {
+        self.execute_load_extend::<u32, i64>(memory, offset)
     }
 
-    fn visit_f32_store(&mut self, offset: Offset) -> Self::Outcome {
-        self.execute_store::<F32>(offset)
+    fn visit_i32_store(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome {
+        self.execute_store::<i32>(memory, offset)
     }
 
-    fn visit_f64_store(&mut self, offset: Offset) -> Self::Outcome {
-        self.execute_store::<F64>(offset)
+    fn visit_i64_store(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome {
+        self.execute_store::<i64>(memory, offset)
     }
 
-    fn visit_i32_store_8(&mut self, offset: Offset) -> Self::Outcome {
-        self.execute_store_wrap::<i32, i8>(offset)
+    fn visit_f32_store(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome {
+        self.execute_store::<F32>(memory, offset)
     }
 
-    fn visit_i32_store_16(&mut self, offset: Offset) -> Self::Outcome {
-        self.execute_store_wrap::<i32, i16>(offset)
+    fn visit_f64_store(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome {
+        self.execute_store::<F64>(memory, offset)
     }
 
-    fn visit_i64_store_8(&mut self, offset: Offset) -> Self::Outcome {
-        self.execute_store_wrap::<i64, i8>(offset)
+    fn visit_i32_store_8(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome {
+        self.execute_store_wrap::<i32, i8>(memory, offset)
     }
 
-    fn visit_i64_store_16(&mut self, offset: Offset) -> Self::Outcome {
-        self.execute_store_wrap::<i64, i16>(offset)
+    fn visit_i32_store_16(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome {
+        self.execute_store_wrap::<i32, i16>(memory, offset)
     }
 
-    fn visit_i64_store_32(&mut self, offset: Offset) -> Self::Outcome {
-        self.execute_store_wrap::<i64, i32>(offset)
+    fn visit_i64_store_8(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome {
+        self.execute_store_wrap::<i64, i8>(memory, offset)
+    }
+
+    fn visit_i64_store_16(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome {
+        self.execute_store_wrap::<i64, i16>(memory, offset)
+    }
+
+    fn visit_i64_store_32(&mut self, memory: MemoryIdx, offset: Offset) -> Self::Outcome {
+        self.execute_store_wrap::<i64, i32>(memory, offset)
     }
 
     fn visit_i32_eqz(&mut self) -> Self::Outcome {
@@ -1119,6 +1673,16 @@ where
         self.execute_unary(UntypedValue::i64_extend32_s)
     }
 
+    #[cfg(feature = "simd")]
+    fn visit_simd(&mut self, inst: SimdInstruction) -> Self::Outcome {
+        self.execute_simd(inst)
+    }
+
+    #[cfg(feature = "threads")]
+    fn visit_atomic(&mut self, inst: AtomicInstruction) -> Self::Outcome {
+        self.execute_atomic(inst)
+    }
+
     fn visit_i32_trunc_sat_f32(&mut self) -> Self::Outcome {
         self.execute_unary(UntypedValue::i32_trunc_sat_f32_s)
     }
@@ -1150,4 +1714,83 @@ where
     fn visit_u64_trunc_sat_f64(&mut self) -> Self::Outcome {
         self.execute_unary(UntypedValue::i64_trunc_sat_f64_u)
     }
+
+    fn visit_i32_add_imm(&mut self, value: i32) -> Self::Outcome {
+        let entry = self.value_stack.last_mut();
+        *entry = entry.i32_add(UntypedValue::from(value));
+        Ok(ExecutionOutcome::Continue)
+    }
+
+    fn visit_i64_add_imm(&mut self, value: i64) -> Self::Outcome {
+        let entry = self.value_stack.last_mut();
+        *entry = entry.i64_add(UntypedValue::from(value));
+        Ok(ExecutionOutcome::Continue)
+    }
+
+    fn visit_br_if_i32_eq(&mut self, target: Target) -> Self::Outcome {
+        self.execute_br_if_binary(target, UntypedValue::i32_eq)
+    }
+
+    fn visit_br_if_i32_ne(&mut self, target: Target) -> Self::Outcome {
+        self.execute_br_if_binary(target, UntypedValue::i32_ne)
+    }
+
+    fn visit_br_if_i32_lt_s(&mut self, target: Target) -> Self::Outcome {
+        self.execute_br_if_binary(target, UntypedValue::i32_lt_s)
+    }
+
+    fn visit_br_if_i32_lt_u(&mut self, target: Target) -> Self::Outcome {
+        self.execute_br_if_binary(target, UntypedValue::i32_lt_u)
+    }
+
+    fn visit_br_if_i32_gt_s(&mut self, target: Target) -> Self::Outcome {
+        self.execute_br_if_binary(target, UntypedValue::i32_gt_s)
+    }
+
+    fn visit_br_if_i32_gt_u(&mut self, target: Target) -> Self::Outcome {
+        self.execute_br_if_binary(target, UntypedValue::i32_gt_u)
+    }
+
+    fn visit_br_if_i32_le_s(&mut self, target: Target) -> Self::Outcome {
+        self.execute_br_if_binary(target, UntypedValue::i32_le_s)
+    }
+
+    fn visit_br_if_i32_le_u(&mut self, target: Target) -> Self::Outcome {
+        self.execute_br_if_binary(target, UntypedValue::i32_le_u)
+    }
+
+    fn visit_br_if_i32_ge_s(&mut self, target: Target) -> Self::Outcome {
+        self.execute_br_if_binary(target, UntypedValue::i32_ge_s)
+    }
+
+    fn visit_br_if_i32_ge_u(&mut self, target: Target) -> Self::Outcome {
+        self.execute_br_if_binary(target, UntypedValue::i32_ge_u)
+    }
+
+    fn visit_i32_load_local(
+        &mut self,
+        base: Register,
+        memory: MemoryIdx,
+        offset: Offset,
+    ) -> Self::Outcome {
+        self.execute_load_local::<i32>(base, memory, offset)
+    }
+
+    fn visit_i64_load_local(
+        &mut self,
+        base: Register,
+        memory: MemoryIdx,
+        offset: Offset,
+    ) -> Self::Outcome {
+        self.execute_load_local::<i64>(base, memory, offset)
+    }
+
+    fn visit_i32_load_u8_local(
+        &mut self,
+        base: Register,
+        memory: MemoryIdx,
+        offset: Offset,
+    ) -> Self::Outcome {
+        self.execute_load_extend_local::<u8, i32>(base, memory, offset)
+    }
 }
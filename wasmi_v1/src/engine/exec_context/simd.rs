@@ -0,0 +1,581 @@
+use super::{ExecutionOutcome, InstructionExecutionContext};
+use crate::{
+    core::Trap,
+    engine::{
+        bytecode::{MemoryIdx, SimdInstruction, SimdOffset},
+        AsContextMut,
+    },
+};
+use wasmi_core::{LittleEndianConvert, UntypedValue, V128};
+
+impl<'engine, 'func, Ctx> InstructionExecutionContext<'engine, 'func, Ctx>
+where
+    Ctx: AsContextMut,
+{
+    /// Executes the given `wasmi` bytecode instruction of the Wasm `simd` proposal.
+    pub(super) fn execute_simd(&mut self, inst: SimdInstruction) -> Result<ExecutionOutcome, Trap> {
+        match inst {
+            SimdInstruction::V128Load { memory, offset } => {
+                self.execute_load::<V128>(memory, offset.into())
+            }
+            SimdInstruction::V128Load8x8S { memory, offset } => self.execute_v128_load::<u64>(
+                memory,
+                offset,
+                UntypedValue::i16x8_extend_low_i8x16_s,
+            ),
+            SimdInstruction::V128Load8x8U { memory, offset } => self.execute_v128_load::<u64>(
+                memory,
+                offset,
+                UntypedValue::i16x8_extend_low_i8x16_u,
+            ),
+            SimdInstruction::V128Load16x4S { memory, offset } => self.execute_v128_load::<u64>(
+                memory,
+                offset,
+                UntypedValue::i32x4_extend_low_i16x8_s,
+            ),
+            SimdInstruction::V128Load16x4U { memory, offset } => self.execute_v128_load::<u64>(
+                memory,
+                offset,
+                UntypedValue::i32x4_extend_low_i16x8_u,
+            ),
+            SimdInstruction::V128Load32x2S { memory, offset } => self.execute_v128_load::<u64>(
+                memory,
+                offset,
+                UntypedValue::i64x2_extend_low_i32x4_s,
+            ),
+            SimdInstruction::V128Load32x2U { memory, offset } => self.execute_v128_load::<u64>(
+                memory,
+                offset,
+                UntypedValue::i64x2_extend_low_i32x4_u,
+            ),
+            SimdInstruction::V128Load8Splat { memory, offset } => {
+                self.execute_v128_load::<u8>(memory, offset, UntypedValue::i8x16_splat)
+            }
+            SimdInstruction::V128Load16Splat { memory, offset } => {
+                self.execute_v128_load::<u16>(memory, offset, UntypedValue::i16x8_splat)
+            }
+            SimdInstruction::V128Load32Splat { memory, offset } => {
+                self.execute_v128_load::<u32>(memory, offset, UntypedValue::i32x4_splat)
+            }
+            SimdInstruction::V128Load64Splat { memory, offset } => {
+                self.execute_v128_load::<u64>(memory, offset, UntypedValue::i64x2_splat)
+            }
+            SimdInstruction::V128Load32Zero { memory, offset } => {
+                self.execute_load::<u32>(memory, offset.into())
+            }
+            SimdInstruction::V128Load64Zero { memory, offset } => {
+                self.execute_load::<u64>(memory, offset.into())
+            }
+            SimdInstruction::V128Store { memory, offset } => {
+                self.execute_store::<V128>(memory, offset.into())
+            }
+            SimdInstruction::V128Load8Lane {
+                memory,
+                offset,
+                lane,
+            } => self.execute_v128_load_lane::<u8>(
+                memory,
+                offset,
+                lane,
+                UntypedValue::i8x16_replace_lane,
+            ),
+            SimdInstruction::V128Load16Lane {
+                memory,
+                offset,
+                lane,
+            } => self.execute_v128_load_lane::<u16>(
+                memory,
+                offset,
+                lane,
+                UntypedValue::i16x8_replace_lane,
+            ),
+            SimdInstruction::V128Load32Lane {
+                memory,
+                offset,
+                lane,
+            } => self.execute_v128_load_lane::<u32>(
+                memory,
+                offset,
+                lane,
+                UntypedValue::i32x4_replace_lane,
+            ),
+            SimdInstruction::V128Load64Lane {
+                memory,
+                offset,
+                lane,
+            } => self.execute_v128_load_lane::<u64>(
+                memory,
+                offset,
+                lane,
+                UntypedValue::i64x2_replace_lane,
+            ),
+            SimdInstruction::V128Store8Lane {
+                memory,
+                offset,
+                lane,
+            } => self.execute_v128_store_lane::<i8>(
+                memory,
+                offset,
+                lane,
+                UntypedValue::i8x16_extract_lane_s,
+            ),
+            SimdInstruction::V128Store16Lane {
+                memory,
+                offset,
+                lane,
+            } => self.execute_v128_store_lane::<i16>(
+                memory,
+                offset,
+                lane,
+                UntypedValue::i16x8_extract_lane_s,
+            ),
+            SimdInstruction::V128Store32Lane {
+                memory,
+                offset,
+                lane,
+            } => self.execute_v128_store_lane::<i32>(
+                memory,
+                offset,
+                lane,
+                UntypedValue::i32x4_extract_lane,
+            ),
+            SimdInstruction::V128Store64Lane {
+                memory,
+                offset,
+                lane,
+            } => self.execute_v128_store_lane::<i64>(
+                memory,
+                offset,
+                lane,
+                UntypedValue::i64x2_extract_lane,
+            ),
+            SimdInstruction::V128Const => self.execute_v128_const(),
+            SimdInstruction::I8x16Shuffle => self.execute_ternary(UntypedValue::i8x16_shuffle),
+            SimdInstruction::I8x16Splat => self.execute_unary(UntypedValue::i8x16_splat),
+            SimdInstruction::I16x8Splat => self.execute_unary(UntypedValue::i16x8_splat),
+            SimdInstruction::I32x4Splat => self.execute_unary(UntypedValue::i32x4_splat),
+            SimdInstruction::I64x2Splat => self.execute_unary(UntypedValue::i64x2_splat),
+            SimdInstruction::F32x4Splat => self.execute_unary(UntypedValue::f32x4_splat),
+            SimdInstruction::F64x2Splat => self.execute_unary(UntypedValue::f64x2_splat),
+            SimdInstruction::I8x16ExtractLaneS { lane } => {
+                self.execute_extract_lane(lane, UntypedValue::i8x16_extract_lane_s)
+            }
+            SimdInstruction::I8x16ExtractLaneU { lane } => {
+                self.execute_extract_lane(lane, UntypedValue::i8x16_extract_lane_u)
+            }
+            SimdInstruction::I16x8ExtractLaneS { lane } => {
+                self.execute_extract_lane(lane, UntypedValue::i16x8_extract_lane_s)
+            }
+            SimdInstruction::I16x8ExtractLaneU { lane } => {
+                self.execute_extract_lane(lane, UntypedValue::i16x8_extract_lane_u)
+            }
+            SimdInstruction::I32x4ExtractLane { lane } => {
+                self.execute_extract_lane(lane, UntypedValue::i32x4_extract_lane)
+            }
+            SimdInstruction::I64x2ExtractLane { lane } => {
+                self.execute_extract_lane(lane, UntypedValue::i64x2_extract_lane)
+            }
+            SimdInstruction::F32x4ExtractLane { lane } => {
+                self.execute_extract_lane(lane, UntypedValue::f32x4_extract_lane)
+            }
+            SimdInstruction::F64x2ExtractLane { lane } => {
+                self.execute_extract_lane(lane, UntypedValue::f64x2_extract_lane)
+            }
+            SimdInstruction::I8x16ReplaceLane { lane } => {
+                self.execute_replace_lane(lane, UntypedValue::i8x16_replace_lane)
+            }
+            SimdInstruction::I16x8ReplaceLane { lane } => {
+                self.execute_replace_lane(lane, UntypedValue::i16x8_replace_lane)
+            }
+            SimdInstruction::I32x4ReplaceLane { lane } => {
+                self.execute_replace_lane(lane, UntypedValue::i32x4_replace_lane)
+            }
+            SimdInstruction::I64x2ReplaceLane { lane } => {
+                self.execute_replace_lane(lane, UntypedValue::i64x2_replace_lane)
+            }
+            SimdInstruction::F32x4ReplaceLane { lane } => {
+                self.execute_replace_lane(lane, UntypedValue::f32x4_replace_lane)
+            }
+            SimdInstruction::F64x2ReplaceLane { lane } => {
+                self.execute_replace_lane(lane, UntypedValue::f64x2_replace_lane)
+            }
+            SimdInstruction::V128AnyTrue => self.execute_unary(UntypedValue::v128_any_true),
+            SimdInstruction::I8x16AllTrue => self.execute_unary(UntypedValue::i8x16_all_true),
+            SimdInstruction::I8x16Bitmask => self.execute_unary(UntypedValue::i8x16_bitmask),
+            SimdInstruction::I16x8AllTrue => self.execute_unary(UntypedValue::i16x8_all_true),
+            SimdInstruction::I16x8Bitmask => self.execute_unary(UntypedValue::i16x8_bitmask),
+            SimdInstruction::I32x4AllTrue => self.execute_unary(UntypedValue::i32x4_all_true),
+            SimdInstruction::I32x4Bitmask => self.execute_unary(UntypedValue::i32x4_bitmask),
+            SimdInstruction::I64x2AllTrue => self.execute_unary(UntypedValue::i64x2_all_true),
+            SimdInstruction::I64x2Bitmask => self.execute_unary(UntypedValue::i64x2_bitmask),
+            SimdInstruction::V128Not => self.execute_unary(UntypedValue::v128_not),
+            SimdInstruction::I8x16Abs => self.execute_unary(UntypedValue::i8x16_abs),
+            SimdInstruction::I8x16Neg => self.execute_unary(UntypedValue::i8x16_neg),
+            SimdInstruction::I8x16Popcnt => self.execute_unary(UntypedValue::i8x16_popcnt),
+            SimdInstruction::I16x8ExtAddPairwiseI8x16S => {
+                self.execute_unary(UntypedValue::i16x8_extadd_pairwise_i8x16_s)
+            }
+            SimdInstruction::I16x8ExtAddPairwiseI8x16U => {
+                self.execute_unary(UntypedValue::i16x8_extadd_pairwise_i8x16_u)
+            }
+            SimdInstruction::I16x8Abs => self.execute_unary(UntypedValue::i16x8_abs),
+            SimdInstruction::I16x8Neg => self.execute_unary(UntypedValue::i16x8_neg),
+            SimdInstruction::I16x8ExtendLowI8x16S => {
+                self.execute_unary(UntypedValue::i16x8_extend_low_i8x16_s)
+            }
+            SimdInstruction::I16x8ExtendHighI8x16S => {
+                self.execute_unary(UntypedValue::i16x8_extend_high_i8x16_s)
+            }
+            SimdInstruction::I16x8ExtendLowI8x16U => {
+                self.execute_unary(UntypedValue::i16x8_extend_low_i8x16_u)
+            }
+            SimdInstruction::I16x8ExtendHighI8x16U => {
+                self.execute_unary(UntypedValue::i16x8_extend_high_i8x16_u)
+            }
+            SimdInstruction::I32x4ExtAddPairwiseI16x8S => {
+                self.execute_unary(UntypedValue::i32x4_extadd_pairwise_i16x8_s)
+            }
+            SimdInstruction::I32x4ExtAddPairwiseI16x8U => {
+                self.execute_unary(UntypedValue::i32x4_extadd_pairwise_i16x8_u)
+            }
+            SimdInstruction::I32x4Abs => self.execute_unary(UntypedValue::i32x4_abs),
+            SimdInstruction::I32x4Neg => self.execute_unary(UntypedValue::i32x4_neg),
+            SimdInstruction::I32x4ExtendLowI16x8S => {
+                self.execute_unary(UntypedValue::i32x4_extend_low_i16x8_s)
+            }
+            SimdInstruction::I32x4ExtendHighI16x8S => {
+                self.execute_unary(UntypedValue::i32x4_extend_high_i16x8_s)
+            }
+            SimdInstruction::I32x4ExtendLowI16x8U => {
+                self.execute_unary(UntypedValue::i32x4_extend_low_i16x8_u)
+            }
+            SimdInstruction::I32x4ExtendHighI16x8U => {
+                self.execute_unary(UntypedValue::i32x4_extend_high_i16x8_u)
+            }
+            SimdInstruction::I64x2Abs => self.execute_unary(UntypedValue::i64x2_abs),
+            SimdInstruction::I64x2Neg => self.execute_unary(UntypedValue::i64x2_neg),
+            SimdInstruction::I64x2ExtendLowI32x4S => {
+                self.execute_unary(UntypedValue::i64x2_extend_low_i32x4_s)
+            }
+            SimdInstruction::I64x2ExtendHighI32x4S => {
+                self.execute_unary(UntypedValue::i64x2_extend_high_i32x4_s)
+            }
+            SimdInstruction::I64x2ExtendLowI32x4U => {
+                self.execute_unary(UntypedValue::i64x2_extend_low_i32x4_u)
+            }
+            SimdInstruction::I64x2ExtendHighI32x4U => {
+                self.execute_unary(UntypedValue::i64x2_extend_high_i32x4_u)
+            }
+            SimdInstruction::F32x4Ceil => self.execute_unary(UntypedValue::f32x4_ceil),
+            SimdInstruction::F32x4Floor => self.execute_unary(UntypedValue::f32x4_floor),
+            SimdInstruction::F32x4Trunc => self.execute_unary(UntypedValue::f32x4_trunc),
+            SimdInstruction::F32x4Nearest => self.execute_unary(UntypedValue::f32x4_nearest),
+            SimdInstruction::F32x4Abs => self.execute_unary(UntypedValue::f32x4_abs),
+            SimdInstruction::F32x4Neg => self.execute_unary(UntypedValue::f32x4_neg),
+            SimdInstruction::F32x4Sqrt => self.execute_unary(UntypedValue::f32x4_sqrt),
+            SimdInstruction::F64x2Ceil => self.execute_unary(UntypedValue::f64x2_ceil),
+            SimdInstruction::F64x2Floor => self.execute_unary(UntypedValue::f64x2_floor),
+            SimdInstruction::F64x2Trunc => self.execute_unary(UntypedValue::f64x2_trunc),
+            SimdInstruction::F64x2Nearest => self.execute_unary(UntypedValue::f64x2_nearest),
+            SimdInstruction::F64x2Abs => self.execute_unary(UntypedValue::f64x2_abs),
+            SimdInstruction::F64x2Neg => self.execute_unary(UntypedValue::f64x2_neg),
+            SimdInstruction::F64x2Sqrt => self.execute_unary(UntypedValue::f64x2_sqrt),
+            SimdInstruction::I32x4TruncSatF32x4S => {
+                self.execute_unary(UntypedValue::i32x4_trunc_sat_f32x4_s)
+            }
+            SimdInstruction::I32x4TruncSatF32x4U => {
+                self.execute_unary(UntypedValue::i32x4_trunc_sat_f32x4_u)
+            }
+            SimdInstruction::F32x4ConvertI32x4S => {
+                self.execute_unary(UntypedValue::f32x4_convert_i32x4_s)
+            }
+            SimdInstruction::F32x4ConvertI32x4U => {
+                self.execute_unary(UntypedValue::f32x4_convert_i32x4_u)
+            }
+            SimdInstruction::I32x4TruncSatF64x2SZero => {
+                self.execute_unary(UntypedValue::i32x4_trunc_sat_f64x2_s_zero)
+            }
+            SimdInstruction::I32x4TruncSatF64x2UZero => {
+                self.execute_unary(UntypedValue::i32x4_trunc_sat_f64x2_u_zero)
+            }
+            SimdInstruction::F64x2ConvertLowI32x4S => {
+                self.execute_unary(UntypedValue::f64x2_convert_low_i32x4_s)
+            }
+            SimdInstruction::F64x2ConvertLowI32x4U => {
+                self.execute_unary(UntypedValue::f64x2_convert_low_i32x4_u)
+            }
+            SimdInstruction::F32x4DemoteF64x2Zero => {
+                self.execute_unary(UntypedValue::f32x4_demote_f64x2_zero)
+            }
+            SimdInstruction::F64x2PromoteLowF32x4 => {
+                self.execute_unary(UntypedValue::f64x2_promote_low_f32x4)
+            }
+            SimdInstruction::I8x16Shl => self.execute_binary(UntypedValue::i8x16_shl),
+            SimdInstruction::I8x16ShrS => self.execute_binary(UntypedValue::i8x16_shr_s),
+            SimdInstruction::I8x16ShrU => self.execute_binary(UntypedValue::i8x16_shr_u),
+            SimdInstruction::I16x8Shl => self.execute_binary(UntypedValue::i16x8_shl),
+            SimdInstruction::I16x8ShrS => self.execute_binary(UntypedValue::i16x8_shr_s),
+            SimdInstruction::I16x8ShrU => self.execute_binary(UntypedValue::i16x8_shr_u),
+            SimdInstruction::I32x4Shl => self.execute_binary(UntypedValue::i32x4_shl),
+            SimdInstruction::I32x4ShrS => self.execute_binary(UntypedValue::i32x4_shr_s),
+            SimdInstruction::I32x4ShrU => self.execute_binary(UntypedValue::i32x4_shr_u),
+            SimdInstruction::I64x2Shl => self.execute_binary(UntypedValue::i64x2_shl),
+            SimdInstruction::I64x2ShrS => self.execute_binary(UntypedValue::i64x2_shr_s),
+            SimdInstruction::I64x2ShrU => self.execute_binary(UntypedValue::i64x2_shr_u),
+            SimdInstruction::I8x16Swizzle => self.execute_binary(UntypedValue::i8x16_swizzle),
+            SimdInstruction::I8x16Eq => self.execute_binary(UntypedValue::i8x16_eq),
+            SimdInstruction::I8x16Ne => self.execute_binary(UntypedValue::i8x16_ne),
+            SimdInstruction::I8x16LtS => self.execute_binary(UntypedValue::i8x16_lt_s),
+            SimdInstruction::I8x16LtU => self.execute_binary(UntypedValue::i8x16_lt_u),
+            SimdInstruction::I8x16GtS => self.execute_binary(UntypedValue::i8x16_gt_s),
+            SimdInstruction::I8x16GtU => self.execute_binary(UntypedValue::i8x16_gt_u),
+            SimdInstruction::I8x16LeS => self.execute_binary(UntypedValue::i8x16_le_s),
+            SimdInstruction::I8x16LeU => self.execute_binary(UntypedValue::i8x16_le_u),
+            SimdInstruction::I8x16GeS => self.execute_binary(UntypedValue::i8x16_ge_s),
+            SimdInstruction::I8x16GeU => self.execute_binary(UntypedValue::i8x16_ge_u),
+            SimdInstruction::I16x8Eq => self.execute_binary(UntypedValue::i16x8_eq),
+            SimdInstruction::I16x8Ne => self.execute_binary(UntypedValue::i16x8_ne),
+            SimdInstruction::I16x8LtS => self.execute_binary(UntypedValue::i16x8_lt_s),
+            SimdInstruction::I16x8LtU => self.execute_binary(UntypedValue::i16x8_lt_u),
+            SimdInstruction::I16x8GtS => self.execute_binary(UntypedValue::i16x8_gt_s),
+            SimdInstruction::I16x8GtU => self.execute_binary(UntypedValue::i16x8_gt_u),
+            SimdInstruction::I16x8LeS => self.execute_binary(UntypedValue::i16x8_le_s),
+            SimdInstruction::I16x8LeU => self.execute_binary(UntypedValue::i16x8_le_u),
+            SimdInstruction::I16x8GeS => self.execute_binary(UntypedValue::i16x8_ge_s),
+            SimdInstruction::I16x8GeU => self.execute_binary(UntypedValue::i16x8_ge_u),
+            SimdInstruction::I32x4Eq => self.execute_binary(UntypedValue::i32x4_eq),
+            SimdInstruction::I32x4Ne => self.execute_binary(UntypedValue::i32x4_ne),
+            SimdInstruction::I32x4LtS => self.execute_binary(UntypedValue::i32x4_lt_s),
+            SimdInstruction::I32x4LtU => self.execute_binary(UntypedValue::i32x4_lt_u),
+            SimdInstruction::I32x4GtS => self.execute_binary(UntypedValue::i32x4_gt_s),
+            SimdInstruction::I32x4GtU => self.execute_binary(UntypedValue::i32x4_gt_u),
+            SimdInstruction::I32x4LeS => self.execute_binary(UntypedValue::i32x4_le_s),
+            SimdInstruction::I32x4LeU => self.execute_binary(UntypedValue::i32x4_le_u),
+            SimdInstruction::I32x4GeS => self.execute_binary(UntypedValue::i32x4_ge_s),
+            SimdInstruction::I32x4GeU => self.execute_binary(UntypedValue::i32x4_ge_u),
+            SimdInstruction::I64x2Eq => self.execute_binary(UntypedValue::i64x2_eq),
+            SimdInstruction::I64x2Ne => self.execute_binary(UntypedValue::i64x2_ne),
+            SimdInstruction::I64x2LtS => self.execute_binary(UntypedValue::i64x2_lt_s),
+            SimdInstruction::I64x2GtS => self.execute_binary(UntypedValue::i64x2_gt_s),
+            SimdInstruction::I64x2LeS => self.execute_binary(UntypedValue::i64x2_le_s),
+            SimdInstruction::I64x2GeS => self.execute_binary(UntypedValue::i64x2_ge_s),
+            SimdInstruction::F32x4Eq => self.execute_binary(UntypedValue::f32x4_eq),
+            SimdInstruction::F32x4Ne => self.execute_binary(UntypedValue::f32x4_ne),
+            SimdInstruction::F32x4Lt => self.execute_binary(UntypedValue::f32x4_lt),
+            SimdInstruction::F32x4Gt => self.execute_binary(UntypedValue::f32x4_gt),
+            SimdInstruction::F32x4Le => self.execute_binary(UntypedValue::f32x4_le),
+            SimdInstruction::F32x4Ge => self.execute_binary(UntypedValue::f32x4_ge),
+            SimdInstruction::F64x2Eq => self.execute_binary(UntypedValue::f64x2_eq),
+            SimdInstruction::F64x2Ne => self.execute_binary(UntypedValue::f64x2_ne),
+            SimdInstruction::F64x2Lt => self.execute_binary(UntypedValue::f64x2_lt),
+            SimdInstruction::F64x2Gt => self.execute_binary(UntypedValue::f64x2_gt),
+            SimdInstruction::F64x2Le => self.execute_binary(UntypedValue::f64x2_le),
+            SimdInstruction::F64x2Ge => self.execute_binary(UntypedValue::f64x2_ge),
+            SimdInstruction::V128And => self.execute_binary(UntypedValue::v128_and),
+            SimdInstruction::V128AndNot => self.execute_binary(UntypedValue::v128_andnot),
+            SimdInstruction::V128Or => self.execute_binary(UntypedValue::v128_or),
+            SimdInstruction::V128Xor => self.execute_binary(UntypedValue::v128_xor),
+            SimdInstruction::I8x16NarrowI16x8S => {
+                self.execute_binary(UntypedValue::i8x16_narrow_i16x8_s)
+            }
+            SimdInstruction::I8x16NarrowI16x8U => {
+                self.execute_binary(UntypedValue::i8x16_narrow_i16x8_u)
+            }
+            SimdInstruction::I8x16Add => self.execute_binary(UntypedValue::i8x16_add),
+            SimdInstruction::I8x16AddSatS => self.execute_binary(UntypedValue::i8x16_add_sat_s),
+            SimdInstruction::I8x16AddSatU => self.execute_binary(UntypedValue::i8x16_add_sat_u),
+            SimdInstruction::I8x16Sub => self.execute_binary(UntypedValue::i8x16_sub),
+            SimdInstruction::I8x16SubSatS => self.execute_binary(UntypedValue::i8x16_sub_sat_s),
+            SimdInstruction::I8x16SubSatU => self.execute_binary(UntypedValue::i8x16_sub_sat_u),
+            SimdInstruction::I8x16MinS => self.execute_binary(UntypedValue::i8x16_min_s),
+            SimdInstruction::I8x16MinU => self.execute_binary(UntypedValue::i8x16_min_u),
+            SimdInstruction::I8x16MaxS => self.execute_binary(UntypedValue::i8x16_max_s),
+            SimdInstruction::I8x16MaxU => self.execute_binary(UntypedValue::i8x16_max_u),
+            SimdInstruction::I8x16RoundingAverageU => {
+                self.execute_binary(UntypedValue::i8x16_avgr_u)
+            }
+            SimdInstruction::I16x8Q15MulrSatS => {
+                self.execute_binary(UntypedValue::i16x8_q15mulr_sat_s)
+            }
+            SimdInstruction::I16x8NarrowI32x4S => {
+                self.execute_binary(UntypedValue::i16x8_narrow_i32x4_s)
+            }
+            SimdInstruction::I16x8NarrowI32x4U => {
+                self.execute_binary(UntypedValue::i16x8_narrow_i32x4_u)
+            }
+            SimdInstruction::I16x8Add => self.execute_binary(UntypedValue::i16x8_add),
+            SimdInstruction::I16x8AddSatS => self.execute_binary(UntypedValue::i16x8_add_sat_s),
+            SimdInstruction::I16x8AddSatU => self.execute_binary(UntypedValue::i16x8_add_sat_u),
+            SimdInstruction::I16x8Sub => self.execute_binary(UntypedValue::i16x8_sub),
+            SimdInstruction::I16x8SubSatS => self.execute_binary(UntypedValue::i16x8_sub_sat_s),
+            SimdInstruction::I16x8SubSatU => self.execute_binary(UntypedValue::i16x8_sub_sat_u),
+            SimdInstruction::I16x8Mul => self.execute_binary(UntypedValue::i16x8_mul),
+            SimdInstruction::I16x8MinS => self.execute_binary(UntypedValue::i16x8_min_s),
+            SimdInstruction::I16x8MinU => self.execute_binary(UntypedValue::i16x8_min_u),
+            SimdInstruction::I16x8MaxS => self.execute_binary(UntypedValue::i16x8_max_s),
+            SimdInstruction::I16x8MaxU => self.execute_binary(UntypedValue::i16x8_max_u),
+            SimdInstruction::I16x8RoundingAverageU => {
+                self.execute_binary(UntypedValue::i16x8_avgr_u)
+            }
+            SimdInstruction::I16x8ExtMulLowI8x16S => {
+                self.execute_binary(UntypedValue::i16x8_extmul_low_i8x16_s)
+            }
+            SimdInstruction::I16x8ExtMulHighI8x16S => {
+                self.execute_binary(UntypedValue::i16x8_extmul_high_i8x16_s)
+            }
+            SimdInstruction::I16x8ExtMulLowI8x16U => {
+                self.execute_binary(UntypedValue::i16x8_extmul_low_i8x16_u)
+            }
+            SimdInstruction::I16x8ExtMulHighI8x16U => {
+                self.execute_binary(UntypedValue::i16x8_extmul_high_i8x16_u)
+            }
+            SimdInstruction::I32x4Add => self.execute_binary(UntypedValue::i32x4_add),
+            SimdInstruction::I32x4Sub => self.execute_binary(UntypedValue::i32x4_sub),
+            SimdInstruction::I32x4Mul => self.execute_binary(UntypedValue::i32x4_mul),
+            SimdInstruction::I32x4MinS => self.execute_binary(UntypedValue::i32x4_min_s),
+            SimdInstruction::I32x4MinU => self.execute_binary(UntypedValue::i32x4_min_u),
+            SimdInstruction::I32x4MaxS => self.execute_binary(UntypedValue::i32x4_max_s),
+            SimdInstruction::I32x4MaxU => self.execute_binary(UntypedValue::i32x4_max_u),
+            SimdInstruction::I32x4DotI16x8S => self.execute_binary(UntypedValue::i32x4_dot_i16x8_s),
+            SimdInstruction::I32x4ExtMulLowI16x8S => {
+                self.execute_binary(UntypedValue::i32x4_extmul_low_i16x8_s)
+            }
+            SimdInstruction::I32x4ExtMulHighI16x8S => {
+                self.execute_binary(UntypedValue::i32x4_extmul_high_i16x8_s)
+            }
+            SimdInstruction::I32x4ExtMulLowI16x8U => {
+                self.execute_binary(UntypedValue::i32x4_extmul_low_i16x8_u)
+            }
+            SimdInstruction::I32x4ExtMulHighI16x8U => {
+                self.execute_binary(UntypedValue::i32x4_extmul_high_i16x8_u)
+            }
+            SimdInstruction::I64x2Add => self.execute_binary(UntypedValue::i64x2_add),
+            SimdInstruction::I64x2Sub => self.execute_binary(UntypedValue::i64x2_sub),
+            SimdInstruction::I64x2Mul => self.execute_binary(UntypedValue::i64x2_mul),
+            SimdInstruction::I64x2ExtMulLowI32x4S => {
+                self.execute_binary(UntypedValue::i64x2_extmul_low_i32x4_s)
+            }
+            SimdInstruction::I64x2ExtMulHighI32x4S => {
+                self.execute_binary(UntypedValue::i64x2_extmul_high_i32x4_s)
+            }
+            SimdInstruction::I64x2ExtMulLowI32x4U => {
+                self.execute_binary(UntypedValue::i64x2_extmul_low_i32x4_u)
+            }
+            SimdInstruction::I64x2ExtMulHighI32x4U => {
+                self.execute_binary(UntypedValue::i64x2_extmul_high_i32x4_u)
+            }
+            SimdInstruction::F32x4Add => self.execute_binary(UntypedValue::f32x4_add),
+            SimdInstruction::F32x4Sub => self.execute_binary(UntypedValue::f32x4_sub),
+            SimdInstruction::F32x4Mul => self.execute_binary(UntypedValue::f32x4_mul),
+            SimdInstruction::F32x4Div => self.execute_binary(UntypedValue::f32x4_div),
+            SimdInstruction::F32x4Min => self.execute_binary(UntypedValue::f32x4_min),
+            SimdInstruction::F32x4Max => self.execute_binary(UntypedValue::f32x4_max),
+            SimdInstruction::F32x4PMin => self.execute_binary(UntypedValue::f32x4_pmin),
+            SimdInstruction::F32x4PMax => self.execute_binary(UntypedValue::f32x4_pmax),
+            SimdInstruction::F64x2Add => self.execute_binary(UntypedValue::f64x2_add),
+            SimdInstruction::F64x2Sub => self.execute_binary(UntypedValue::f64x2_sub),
+            SimdInstruction::F64x2Mul => self.execute_binary(UntypedValue::f64x2_mul),
+            SimdInstruction::F64x2Div => self.execute_binary(UntypedValue::f64x2_div),
+            SimdInstruction::F64x2Min => self.execute_binary(UntypedValue::f64x2_min),
+            SimdInstruction::F64x2Max => self.execute_binary(UntypedValue::f64x2_max),
+            SimdInstruction::F64x2PMin => self.execute_binary(UntypedValue::f64x2_pmin),
+            SimdInstruction::F64x2PMax => self.execute_binary(UntypedValue::f64x2_pmax),
+            SimdInstruction::V128Bitselect => self.execute_ternary(UntypedValue::v128_bitselect),
+        }
+    }
+
+    /// Loads a value of type `T` from the `memory` and applies `f` to it.
+    ///
+    /// # Note
+    ///
+    /// This can be used to emulate the extending and splatting `v128` loads.
+    fn execute_v128_load<T>(
+        &mut self,
+        memory: MemoryIdx,
+        offset: SimdOffset,
+        f: fn(UntypedValue) -> UntypedValue,
+    ) -> Result<ExecutionOutcome, Trap>
+    where
+        UntypedValue: From<T>,
+        T: LittleEndianConvert,
+    {
+        self.execute_load::<T>(memory, offset.into())?;
+        self.execute_unary(f)
+    }
+
+    /// Loads a value of type `T` from the `memory` into a lane of the `v128` operand.
+    fn execute_v128_load_lane<T>(
+        &mut self,
+        memory: MemoryIdx,
+        offset: SimdOffset,
+        lane: u8,
+        f: fn(UntypedValue, u8, UntypedValue) -> UntypedValue,
+    ) -> Result<ExecutionOutcome, Trap>
+    where
+        UntypedValue: From<T>,
+        T: LittleEndianConvert,
+    {
+        let vector = self.value_stack.pop();
+        self.execute_load::<T>(memory, offset.into())?;
+        let entry = self.value_stack.last_mut();
+        *entry = f(vector, lane, *entry);
+        Ok(ExecutionOutcome::Continue)
+    }
+
+    /// Stores a lane of the `v128` operand as value of type `T` into the `memory`.
+    fn execute_v128_store_lane<T>(
+        &mut self,
+        memory: MemoryIdx,
+        offset: SimdOffset,
+        lane: u8,
+        f: fn(UntypedValue, u8) -> UntypedValue,
+    ) -> Result<ExecutionOutcome, Trap>
+    where
+        T: LittleEndianConvert + From<UntypedValue>,
+    {
+        let entry = self.value_stack.last_mut();
+        *entry = f(*entry, lane);
+        self.execute_store::<T>(memory, offset.into())
+    }
+
+    /// Combines the lower and upper 64 bits on top of the stack into a `v128` value.
+    fn execute_v128_const(&mut self) -> Result<ExecutionOutcome, Trap> {
+        let hi = u64::from(self.value_stack.pop());
+        let entry = self.value_stack.last_mut();
+        let lo = u64::from(*entry);
+        *entry = V128::from_bits(u128::from(hi) << 64 | u128::from(lo)).into();
+        Ok(ExecutionOutcome::Continue)
+    }
+
+    fn execute_extract_lane(
+        &mut self,
+        lane: u8,
+        f: fn(UntypedValue, u8) -> UntypedValue,
+    ) -> Result<ExecutionOutcome, Trap> {
+        let entry = self.value_stack.last_mut();
+        *entry = f(*entry, lane);
+        Ok(ExecutionOutcome::Continue)
+    }
+
+    fn execute_replace_lane(
+        &mut self,
+        lane: u8,
+        f: fn(UntypedValue, u8, UntypedValue) -> UntypedValue,
+    ) -> Result<ExecutionOutcome, Trap> {
+        let value = self.value_stack.pop();
+        let entry = self.value_stack.last_mut();
+        *entry = f(*entry, lane, value);
+        Ok(ExecutionOutcome::Continue)
+    }
+
+    fn execute_ternary(
+        &mut self,
+        f: fn(UntypedValue, UntypedValue, UntypedValue) -> UntypedValue,
+    ) -> Result<ExecutionOutcome, Trap> {
+        let third = self.value_stack.pop();
+        let second = self.value_stack.pop();
+        let entry = self.value_stack.last_mut();
+        *entry = f(*entry, second, third);
+        Ok(ExecutionOutcome::Continue)
+    }
+}
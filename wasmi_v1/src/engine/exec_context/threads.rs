@@ -0,0 +1,455 @@
+use super::{ExecutionOutcome, InstructionExecutionContext};
+use crate::{
+    core::{Trap, TrapCode},
+    engine::{
+        bytecode::{AtomicInstruction, AtomicOffset, MemoryIdx},
+        AsContextMut,
+    },
+    errors::MemoryError,
+    memory::AtomicInt,
+    ParkResult,
+};
+use core::{
+    ops::{BitAnd, BitOr, BitXor},
+    sync::atomic::{self, Ordering},
+};
+use std::time::Duration;
+use wasmi_core::UntypedValue;
+
+impl<'engine, 'func, Ctx> InstructionExecutionContext<'engine, 'func, Ctx>
+where
+    Ctx: AsContextMut,
+{
+    /// Executes the given `wasmi` bytecode instruction of the Wasm `threads` proposal.
+    pub(super) fn execute_atomic(
+        &mut self,
+        inst: AtomicInstruction,
+    ) -> Result<ExecutionOutcome, Trap> {
+        match inst {
+            AtomicInstruction::MemoryAtomicNotify { memory, offset } => {
+                self.execute_atomic_notify(memory, offset)
+            }
+            AtomicInstruction::MemoryAtomicWait32 { memory, offset } => {
+                self.execute_atomic_wait::<u32>(memory, offset)
+            }
+            AtomicInstruction::MemoryAtomicWait64 { memory, offset } => {
+                self.execute_atomic_wait::<u64>(memory, offset)
+            }
+            AtomicInstruction::I32AtomicLoad { memory, offset } => {
+                self.execute_atomic_load::<u32>(memory, offset)
+            }
+            AtomicInstruction::I64AtomicLoad { memory, offset } => {
+                self.execute_atomic_load::<u64>(memory, offset)
+            }
+            AtomicInstruction::I32AtomicLoad8U { memory, offset } => {
+                self.execute_atomic_load::<u8>(memory, offset)
+            }
+            AtomicInstruction::I32AtomicLoad16U { memory, offset } => {
+                self.execute_atomic_load::<u16>(memory, offset)
+            }
+            AtomicInstruction::I64AtomicLoad8U { memory, offset } => {
+                self.execute_atomic_load::<u8>(memory, offset)
+            }
+            AtomicInstruction::I64AtomicLoad16U { memory, offset } => {
+                self.execute_atomic_load::<u16>(memory, offset)
+            }
+            AtomicInstruction::I64AtomicLoad32U { memory, offset } => {
+                self.execute_atomic_load::<u32>(memory, offset)
+            }
+            AtomicInstruction::I32AtomicStore { memory, offset } => {
+                self.execute_atomic_store::<u32>(memory, offset)
+            }
+            AtomicInstruction::I64AtomicStore { memory, offset } => {
+                self.execute_atomic_store::<u64>(memory, offset)
+            }
+            AtomicInstruction::I32AtomicStore8 { memory, offset } => {
+                self.execute_atomic_store::<u8>(memory, offset)
+            }
+            AtomicInstruction::I32AtomicStore16 { memory, offset } => {
+                self.execute_atomic_store::<u16>(memory, offset)
+            }
+            AtomicInstruction::I64AtomicStore8 { memory, offset } => {
+                self.execute_atomic_store::<u8>(memory, offset)
+            }
+            AtomicInstruction::I64AtomicStore16 { memory, offset } => {
+                self.execute_atomic_store::<u16>(memory, offset)
+            }
+            AtomicInstruction::I64AtomicStore32 { memory, offset } => {
+                self.execute_atomic_store::<u32>(memory, offset)
+            }
+            AtomicInstruction::I32AtomicRmwAdd { memory, offset } => {
+                self.execute_atomic_rmw::<u32>(memory, offset, <u32>::wrapping_add)
+            }
+            AtomicInstruction::I64AtomicRmwAdd { memory, offset } => {
+                self.execute_atomic_rmw::<u64>(memory, offset, <u64>::wrapping_add)
+            }
+            AtomicInstruction::I32AtomicRmw8AddU { memory, offset } => {
+                self.execute_atomic_rmw::<u8>(memory, offset, <u8>::wrapping_add)
+            }
+            AtomicInstruction::I32AtomicRmw16AddU { memory, offset } => {
+                self.execute_atomic_rmw::<u16>(memory, offset, <u16>::wrapping_add)
+            }
+            AtomicInstruction::I64AtomicRmw8AddU { memory, offset } => {
+                self.execute_atomic_rmw::<u8>(memory, offset, <u8>::wrapping_add)
+            }
+            AtomicInstruction::I64AtomicRmw16AddU { memory, offset } => {
+                self.execute_atomic_rmw::<u16>(memory, offset, <u16>::wrapping_add)
+            }
+            AtomicInstruction::I64AtomicRmw32AddU { memory, offset } => {
+                self.execute_atomic_rmw::<u32>(memory, offset, <u32>::wrapping_add)
+            }
+            AtomicInstruction::I32AtomicRmwSub { memory, offset } => {
+                self.execute_atomic_rmw::<u32>(memory, offset, <u32>::wrapping_sub)
+            }
+            AtomicInstruction::I64AtomicRmwSub { memory, offset } => {
+                self.execute_atomic_rmw::<u64>(memory, offset, <u64>::wrapping_sub)
+            }
+            AtomicInstruction::I32AtomicRmw8SubU { memory, offset } => {
+                self.execute_atomic_rmw::<u8>(memory, offset, <u8>::wrapping_sub)
+            }
+            AtomicInstruction::I32AtomicRmw16SubU { memory, offset } => {
+                self.execute_atomic_rmw::<u16>(memory, offset, <u16>::wrapping_sub)
+            }
+            AtomicInstruction::I64AtomicRmw8SubU { memory, offset } => {
+                self.execute_atomic_rmw::<u8>(memory, offset, <u8>::wrapping_sub)
+            }
+            AtomicInstruction::I64AtomicRmw16SubU { memory, offset } => {
+                self.execute_atomic_rmw::<u16>(memory, offset, <u16>::wrapping_sub)
+            }
+            AtomicInstruction::I64AtomicRmw32SubU { memory, offset } => {
+                self.execute_atomic_rmw::<u32>(memory, offset, <u32>::wrapping_sub)
+            }
+            AtomicInstruction::I32AtomicRmwAnd { memory, offset } => {
+                self.execute_atomic_rmw::<u32>(memory, offset, <u32 as BitAnd>::bitand)
+            }
+            AtomicInstruction::I64AtomicRmwAnd { memory, offset } => {
+                self.execute_atomic_rmw::<u64>(memory, offset, <u64 as BitAnd>::bitand)
+            }
+            AtomicInstruction::I32AtomicRmw8AndU { memory, offset } => {
+                self.execute_atomic_rmw::<u8>(memory, offset, <u8 as BitAnd>::bitand)
+            }
+            AtomicInstruction::I32AtomicRmw16AndU { memory, offset } => {
+                self.execute_atomic_rmw::<u16>(memory, offset, <u16 as BitAnd>::bitand)
+            }
+            AtomicInstruction::I64AtomicRmw8AndU { memory, offset } => {
+                self.execute_atomic_rmw::<u8>(memory, offset, <u8 as BitAnd>::bitand)
+            }
+            AtomicInstruction::I64AtomicRmw16AndU { memory, offset } => {
+                self.execute_atomic_rmw::<u16>(memory, offset, <u16 as BitAnd>::bitand)
+            }
+            AtomicInstruction::I64AtomicRmw32AndU { memory, offset } => {
+                self.execute_atomic_rmw::<u32>(memory, offset, <u32 as BitAnd>::bitand)
+            }
+            AtomicInstruction::I32AtomicRmwOr { memory, offset } => {
+                self.execute_atomic_rmw::<u32>(memory, offset, <u32 as BitOr>::bitor)
+            }
+            AtomicInstruction::I64AtomicRmwOr { memory, offset } => {
+                self.execute_atomic_rmw::<u64>(memory, offset, <u64 as BitOr>::bitor)
+            }
+            AtomicInstruction::I32AtomicRmw8OrU { memory, offset } => {
+                self.execute_atomic_rmw::<u8>(memory, offset, <u8 as BitOr>::bitor)
+            }
+            AtomicInstruction::I32AtomicRmw16OrU { memory, offset } => {
+                self.execute_atomic_rmw::<u16>(memory, offset, <u16 as BitOr>::bitor)
+            }
+            AtomicInstruction::I64AtomicRmw8OrU { memory, offset } => {
+                self.execute_atomic_rmw::<u8>(memory, offset, <u8 as BitOr>::bitor)
+            }
+            AtomicInstruction::I64AtomicRmw16OrU { memory, offset } => {
+                self.execute_atomic_rmw::<u16>(memory, offset, <u16 as BitOr>::bitor)
+            }
+            AtomicInstruction::I64AtomicRmw32OrU { memory, offset } => {
+                self.execute_atomic_rmw::<u32>(memory, offset, <u32 as BitOr>::bitor)
+            }
+            AtomicInstruction::I32AtomicRmwXor { memory, offset } => {
+                self.execute_atomic_rmw::<u32>(memory, offset, <u32 as BitXor>::bitxor)
+            }
+            AtomicInstruction::I64AtomicRmwXor { memory, offset } => {
+                self.execute_atomic_rmw::<u64>(memory, offset, <u64 as BitXor>::bitxor)
+            }
+            AtomicInstruction::I32AtomicRmw8XorU { memory, offset } => {
+                self.execute_atomic_rmw::<u8>(memory, offset, <u8 as BitXor>::bitxor)
+            }
+            AtomicInstruction::I32AtomicRmw16XorU { memory, offset } => {
+                self.execute_atomic_rmw::<u16>(memory, offset, <u16 as BitXor>::bitxor)
+            }
+            AtomicInstruction::I64AtomicRmw8XorU { memory, offset } => {
+                self.execute_atomic_rmw::<u8>(memory, offset, <u8 as BitXor>::bitxor)
+            }
+            AtomicInstruction::I64AtomicRmw16XorU { memory, offset } => {
+                self.execute_atomic_rmw::<u16>(memory, offset, <u16 as BitXor>::bitxor)
+            }
+            AtomicInstruction::I64AtomicRmw32XorU { memory, offset } => {
+                self.execute_atomic_rmw::<u32>(memory, offset, <u32 as BitXor>::bitxor)
+            }
+            AtomicInstruction::I32AtomicRmwXchg { memory, offset } => {
+                self.execute_atomic_rmw::<u32>(memory, offset, |_, value| value)
+            }
+            AtomicInstruction::I64AtomicRmwXchg { memory, offset } => {
+                self.execute_atomic_rmw::<u64>(memory, offset, |_, value| value)
+            }
+            AtomicInstruction::I32AtomicRmw8XchgU { memory, offset } => {
+                self.execute_atomic_rmw::<u8>(memory, offset, |_, value| value)
+            }
+            AtomicInstruction::I32AtomicRmw16XchgU { memory, offset } => {
+                self.execute_atomic_rmw::<u16>(memory, offset, |_, value| value)
+            }
+            AtomicInstruction::I64AtomicRmw8XchgU { memory, offset } => {
+                self.execute_atomic_rmw::<u8>(memory, offset, |_, value| value)
+            }
+            AtomicInstruction::I64AtomicRmw16XchgU { memory, offset } => {
+                self.execute_atomic_rmw::<u16>(memory, offset, |_, value| value)
+            }
+            AtomicInstruction::I64AtomicRmw32XchgU { memory, offset } => {
+                self.execute_atomic_rmw::<u32>(memory, offset, |_, value| value)
+            }
+            AtomicInstruction::I32AtomicRmwCmpxchg { memory, offset } => {
+                self.execute_atomic_cmpxchg::<u32>(memory, offset)
+            }
+            AtomicInstruction::I64AtomicRmwCmpxchg { memory, offset } => {
+                self.execute_atomic_cmpxchg::<u64>(memory, offset)
+            }
+            AtomicInstruction::I32AtomicRmw8CmpxchgU { memory, offset } => {
+                self.execute_atomic_cmpxchg::<u8>(memory, offset)
+            }
+            AtomicInstruction::I32AtomicRmw16CmpxchgU { memory, offset } => {
+                self.execute_atomic_cmpxchg::<u16>(memory, offset)
+            }
+            AtomicInstruction::I64AtomicRmw8CmpxchgU { memory, offset } => {
+                self.execute_atomic_cmpxchg::<u8>(memory, offset)
+            }
+            AtomicInstruction::I64AtomicRmw16CmpxchgU { memory, offset } => {
+                self.execute_atomic_cmpxchg::<u16>(memory, offset)
+            }
+            AtomicInstruction::I64AtomicRmw32CmpxchgU { memory, offset } => {
+                self.execute_atomic_cmpxchg::<u32>(memory, offset)
+            }
+            AtomicInstruction::AtomicFence => {
+                atomic::fence(Ordering::SeqCst);
+                Ok(ExecutionOutcome::Continue)
+            }
+        }
+    }
+
+    /// Converts the [`MemoryError`] of an atomic linear memory access into a [`Trap`].
+    fn atomic_trap(error: MemoryError) -> Trap {
+        match error {
+            MemoryError::UnalignedAtomicAccess => TrapCode::UnalignedAtomic.into(),
+            _ => TrapCode::MemoryAccessOutOfBounds.into(),
+        }
+    }
+
+    /// Computes the effective address of an atomic access of the `memory`.
+    fn atomic_address(
+        &mut self,
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+        address: UntypedValue,
+    ) -> Result<usize, Trap> {
+        let memory = self.memory(memory);
+        let memory_type = memory.memory_type(self.ctx.as_context());
+        Self::effective_address(memory_type, offset.into(), address)
+    }
+
+    /// Atomically loads a value of type `T` from the `memory` and zero-extends it.
+    ///
+    /// # Note
+    ///
+    /// This can be used to emulate the following Wasm operands:
+    ///
+    /// - `{i32, i64}.atomic.load`
+    /// - `{i32, i64}.atomic.load{8, 16}_u`
+    /// - `i64.atomic.load32_u`
+    fn execute_atomic_load<T>(
+        &mut self,
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    ) -> Result<ExecutionOutcome, Trap>
+    where
+        T: AtomicInt,
+        UntypedValue: From<T>,
+    {
+        let raw_address = self.value_stack.pop();
+        let address = self.atomic_address(memory, offset, raw_address)?;
+        let memory = self.memory(memory);
+        let value = self
+            .ctx
+            .as_context()
+            .store
+            .resolve_memory(memory)
+            .atomic_load::<T>(address)
+            .map_err(Self::atomic_trap)?;
+        self.value_stack.push(value);
+        Ok(ExecutionOutcome::Continue)
+    }
+
+    /// Atomically stores the value operand wrapped to type `T` into the `memory`.
+    ///
+    /// # Note
+    ///
+    /// This can be used to emulate the following Wasm operands:
+    ///
+    /// - `{i32, i64}.atomic.store`
+    /// - `{i32, i64}.atomic.store{8, 16}`
+    /// - `i64.atomic.store32`
+    fn execute_atomic_store<T>(
+        &mut self,
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    ) -> Result<ExecutionOutcome, Trap>
+    where
+        T: AtomicInt + From<UntypedValue>,
+    {
+        let value = self.value_stack.pop_as::<T>();
+        let raw_address = self.value_stack.pop();
+        let address = self.atomic_address(memory, offset, raw_address)?;
+        let memory = self.memory(memory);
+        self.ctx
+            .as_context_mut()
+            .store
+            .resolve_memory_mut(memory)
+            .atomic_store::<T>(address, value)
+            .map_err(Self::atomic_trap)?;
+        Ok(ExecutionOutcome::Continue)
+    }
+
+    /// Atomically applies `op` to the value of type `T` in the `memory` and the value operand.
+    ///
+    /// Pushes the zero-extended value of the `memory` before the operation.
+    ///
+    /// # Note
+    ///
+    /// This can be used to emulate the Wasm `atomic.rmw*.{add, sub, and, or, xor, xchg}` operands.
+    fn execute_atomic_rmw<T>(
+        &mut self,
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+        op: fn(T, T) -> T,
+    ) -> Result<ExecutionOutcome, Trap>
+    where
+        T: AtomicInt + From<UntypedValue>,
+        UntypedValue: From<T>,
+    {
+        let value = self.value_stack.pop_as::<T>();
+        let raw_address = self.value_stack.pop();
+        let address = self.atomic_address(memory, offset, raw_address)?;
+        let memory = self.memory(memory);
+        let previous = self
+            .ctx
+            .as_context_mut()
+            .store
+            .resolve_memory_mut(memory)
+            .atomic_rmw::<T>(address, value, op)
+            .map_err(Self::atomic_trap)?;
+        self.value_stack.push(previous);
+        Ok(ExecutionOutcome::Continue)
+    }
+
+    /// Atomically replaces the value of type `T` in the `memory` if it equals the expected operand.
+    ///
+    /// Pushes the zero-extended value of the `memory` before the operation.
+    ///
+    /// # Note
+    ///
+    /// Both the expected and the replacement operands are wrapped to type `T`.
+    /// This can be used to emulate the Wasm `atomic.rmw*.cmpxchg*` operands.
+    fn execute_atomic_cmpxchg<T>(
+        &mut self,
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    ) -> Result<ExecutionOutcome, Trap>
+    where
+        T: AtomicInt + From<UntypedValue>,
+        UntypedValue: From<T>,
+    {
+        let replacement = self.value_stack.pop_as::<T>();
+        let expected = self.value_stack.pop_as::<T>();
+        let raw_address = self.value_stack.pop();
+        let address = self.atomic_address(memory, offset, raw_address)?;
+        let memory = self.memory(memory);
+        let previous = self
+            .ctx
+            .as_context_mut()
+            .store
+            .resolve_memory_mut(memory)
+            .atomic_cmpxchg::<T>(address, expected, replacement)
+            .map_err(Self::atomic_trap)?;
+        self.value_stack.push(previous);
+        Ok(ExecutionOutcome::Continue)
+    }
+
+    /// Executes the Wasm `memory.atomic.notify` operand.
+    ///
+    /// Pushes the amount of threads that have been unparked.
+    fn execute_atomic_notify(
+        &mut self,
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    ) -> Result<ExecutionOutcome, Trap> {
+        let count = self.value_stack.pop_as::<u32>();
+        let raw_address = self.value_stack.pop();
+        let address = self.atomic_address(memory, offset, raw_address)?;
+        let memory = self.memory(memory);
+        let unparked = self
+            .ctx
+            .as_context()
+            .store
+            .resolve_memory(memory)
+            .atomic_notify(address, count)
+            .map_err(Self::atomic_trap)?;
+        self.value_stack.push(unparked);
+        Ok(ExecutionOutcome::Continue)
+    }
+
+    /// Executes the Wasm `memory.atomic.wait{32, 64}` operands.
+    ///
+    /// Pushes `0` if the thread has been unparked, `1` if the value in the `memory`
+    /// did not equal the expected operand and `2` if the timeout expired.
+    ///
+    /// # Note
+    ///
+    /// A negative timeout operand parks the thread indefinitely.
+    ///
+    /// The [`Engine`] is not locked while executing Wasm so that parking the
+    /// thread does not block other threads executing on the same [`Engine`],
+    /// including the thread that is about to notify the parked thread.
+    ///
+    /// # Errors
+    ///
+    /// If the `memory` is not a shared linear memory.
+    ///
+    /// [`Engine`]: crate::Engine
+    fn execute_atomic_wait<T>(
+        &mut self,
+        memory: MemoryIdx,
+        offset: AtomicOffset,
+    ) -> Result<ExecutionOutcome, Trap>
+    where
+        T: AtomicInt + From<UntypedValue>,
+    {
+        let timeout = self.value_stack.pop_as::<i64>();
+        let expected = self.value_stack.pop_as::<T>();
+        let raw_address = self.value_stack.pop();
+        let address = self.atomic_address(memory, offset, raw_address)?;
+        let memory = self.memory(memory);
+        let shared = self
+            .ctx
+            .as_context()
+            .store
+            .resolve_memory(memory)
+            .shared()
+            .cloned()
+            .ok_or(TrapCode::ExpectedSharedMemory)?;
+        let timeout = u64::try_from(timeout).ok().map(Duration::from_nanos);
+        let result = shared
+            .atomic_wait(address, expected, timeout)
+            .map_err(Self::atomic_trap)?;
+        let result: u32 = match result {
+            ParkResult::Unparked => 0,
+            ParkResult::Invalid => 1,
+            ParkResult::TimedOut => 2,
+        };
+        self.value_stack.push(result);
+        Ok(ExecutionOutcome::Continue)
+    }
+}
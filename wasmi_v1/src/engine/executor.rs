@@ -0,0 +1,593 @@
+//! The executor that drives the execution of Wasm and host functions.
+
+use super::{
+    super::{AsContext, AsContextMut, Func, FuncEntityInternal},
+    bytecode::TagIdx,
+    find_catch_target,
+    CallParams,
+    CallResults,
+    DedupFuncType,
+    Engine,
+    ExecutionContext,
+    FuncParams,
+    FunctionExecutionOutcome,
+    FunctionFrame,
+    Stack,
+};
+use crate::{
+    core::{FrameInfo, Trap, TrapCode, WasmBacktrace},
+    func::{HostFuncEntity, WasmFuncEntity},
+    Exception,
+    Instance,
+    Value,
+};
+use alloc::{boxed::Box, sync::Arc};
+use core::{cmp, iter};
+
+/// A [`Trap`] that is tagged with the information required to resume the execution.
+#[derive(Debug)]
+pub enum TaggedTrap {
+    /// A trap after which the execution cannot be resumed.
+    ///
+    /// # Note
+    ///
+    /// This is the case for all traps originating from Wasm executions
+    /// as well as for traps of host functions that are called directly.
+    Wasm(Trap),
+    /// A trap returned by a host function that has been called from Wasm.
+    Host {
+        /// The host function that returned the trap.
+        host_func: Func,
+        /// The trap returned by the host function.
+        host_trap: Trap,
+        /// The function frame of the Wasm function that called the host function.
+        ///
+        /// # Note
+        ///
+        /// The instruction pointer of the frame already points to the
+        /// instruction following the call to the host function.
+        ///
+        /// This is `None` if the host function has been tail called
+        /// by the root Wasm function frame of the execution.
+        caller: Option<Box<FunctionFrame>>,
+    },
+}
+
+impl TaggedTrap {
+    /// Returns the underlying [`Trap`] discarding the tagged information.
+    pub fn into_trap(self) -> Trap {
+        match self {
+            Self::Wasm(trap) => trap,
+            Self::Host { host_trap, .. } => host_trap,
+        }
+    }
+}
+
+impl From<Trap> for TaggedTrap {
+    fn from(trap: Trap) -> Self {
+        Self::Wasm(trap)
+    }
+}
+
+impl From<TrapCode> for TaggedTrap {
+    fn from(trap_code: TrapCode) -> Self {
+        Self::Wasm(trap_code.into())
+    }
+}
+
+/// Executes Wasm and host functions using a dedicated [`Stack`].
+///
+/// # Note
+///
+/// The lock of the [`Engine`] is never held while executing Wasm or host functions.
+/// This allows host functions to call back into Wasm using the same [`Engine`]
+/// and other threads to execute Wasm using the same [`Engine`] concurrently.
+#[derive(Debug)]
+pub struct EngineExecutor<'engine> {
+    /// The engine that owns the executed function bodies.
+    engine: &'engine Engine,
+    /// The value and call stacks used by the execution.
+    stack: &'engine mut Stack,
+}
+
+impl<'engine> EngineExecutor<'engine> {
+    /// Creates a new [`EngineExecutor`] for the given [`Engine`] and [`Stack`].
+    pub fn new(engine: &'engine Engine, stack: &'engine mut Stack) -> Self {
+        Self { engine, stack }
+    }
+
+    /// Executes the given [`Func`] using the given arguments `params` and stores the result into `results`.
+    ///
+    /// # Errors
+    ///
+    /// - If the given arguments `params` do not match the expected parameters of `func`.
+    /// - If the given `results` do not match the the length of the expected results of `func`.
+    /// - If the execution is nested too deeply in other executions via host functions.
+    /// - When encountering a Wasm trap during the execution of `func`.
+    pub fn execute_func<Params, Results>(
+        &mut self,
+        mut ctx: impl AsContextMut,
+        func: Func,
+        params: Params,
+        results: Results,
+    ) -> Result<<Results as CallResults>::Results, TaggedTrap>
+    where
+        Params: CallParams,
+        Results: CallResults,
+    {
+        self.initialize_args(params);
+        self.stack.reserve(ctx.as_context().store.call_depth())?;
+        let signature = match func.as_internal(&ctx) {
+            FuncEntityInternal::Wasm(wasm_func) => {
+                let signature = wasm_func.signature();
+                let frame = self.new_frame(func, wasm_func);
+                self.execute_wasm_frames(&mut ctx, frame)?;
+                signature
+            }
+            FuncEntityInternal::Host(host_func) => {
+                let signature = host_func.signature();
+                let host_func = host_func.clone();
+                self.execute_host_func(&mut ctx, host_func, None)?;
+                signature
+            }
+        };
+        let results = self.write_results_back(signature, results);
+        Ok(results)
+    }
+
+    /// Resumes the execution of `func` that has been suspended by a trapping host function.
+    ///
+    /// The `params` are the results of the `host_func` that returned the trap.
+    /// The `caller` is the function frame of the Wasm function that called the `host_func`
+    /// or `None` if the `host_func` has been tail called by the root Wasm function frame.
+    ///
+    /// # Note
+    ///
+    /// The [`Stack`] is required to be in the state in which the suspended execution left it.
+    ///
+    /// # Errors
+    ///
+    /// - If the value stack overflowed upon pushing the `params`.
+    /// - If the execution is nested too deeply in other executions via host functions.
+    /// - When encountering a Wasm trap during the execution of `func`.
+    pub fn resume_func<Params, Results>(
+        &mut self,
+        mut ctx: impl AsContextMut,
+        func: Func,
+        host_func: Func,
+        caller: Option<FunctionFrame>,
+        params: Params,
+        results: Results,
+    ) -> Result<<Results as CallResults>::Results, TaggedTrap>
+    where
+        Params: CallParams,
+        Results: CallResults,
+    {
+        self.stack.reserve(ctx.as_context().store.call_depth())?;
+        // Replace the parameters of the trapped host function with its results.
+        let len_inputs = self
+            .engine
+            .resolve_func_type(host_func.signature(&ctx), |func_type| {
+                func_type.params().len()
+            });
+        self.stack.values.drop(len_inputs);
+        self.stack.values.reserve(params.len_params())?;
+        for param in params.feed_params() {
+            self.stack.values.push(param);
+        }
+        if let Some(caller) = caller {
+            self.execute_wasm_frames(&mut ctx, caller)?;
+        }
+        let results = self.write_results_back(func.signature(&ctx), results);
+        Ok(results)
+    }
+
+    /// Initializes the value stack with the given arguments `params`.
+    fn initialize_args<Params>(&mut self, params: Params)
+    where
+        Params: CallParams,
+    {
+        self.stack.clear();
+        for param in params.feed_params() {
+            self.stack.values.push(param);
+        }
+    }
+
+    /// Writes the results of the function execution back into the `results` buffer.
+    ///
+    /// # Note
+    ///
+    /// The value stack is empty after this operation.
+    ///
+    /// # Panics
+    ///
+    /// - If the `results` buffer length does not match the remaining amount of stack values.
+    fn write_results_back<Results>(
+        &mut self,
+        func_type: DedupFuncType,
+        results: Results,
+    ) -> <Results as CallResults>::Results
+    where
+        Results: CallResults,
+    {
+        let values = &mut self.stack.values;
+        self.engine.resolve_func_type(func_type, |func_type| {
+            let result_types = func_type.results();
+            assert_eq!(
+                values.len(),
+                results.len_results(),
+                "expected {} values on the stack after function execution but found {}",
+                results.len_results(),
+                values.len(),
+            );
+            assert_eq!(results.len_results(), result_types.len());
+            results.feed_results(
+                values
+                    .drain()
+                    .iter()
+                    .zip(result_types)
+                    .map(|(raw_value, value_type)| Value::from_untyped(*raw_value, *value_type)),
+            )
+        })
+    }
+
+    /// Executes the Wasm function `frame` and all of the remaining frames on the call stack.
+    ///
+    /// # Note
+    ///
+    /// The current contents of the value stack are used as parameters if
+    /// the `frame` has not yet been initialized.
+    ///
+    /// # Errors
+    ///
+    /// - When encountering a Wasm trap during the execution.
+    /// - If a host function called from Wasm returned a trap.
+    fn execute_wasm_frames(
+        &mut self,
+        mut ctx: impl AsContextMut,
+        mut function_frame: FunctionFrame,
+    ) -> Result<(), TaggedTrap> {
+        'outer: loop {
+            let outcome = match self.execute_frame(&mut ctx, &mut function_frame) {
+                Ok(outcome) => outcome,
+                Err(trap) => return Err(self.with_backtrace(&ctx, &function_frame, trap).into()),
+            };
+            match outcome {
+                FunctionExecutionOutcome::Return => match self.stack.frames.pop() {
+                    Some(frame) => {
+                        function_frame = frame;
+                        continue 'outer;
+                    }
+                    None => return Ok(()),
+                },
+                FunctionExecutionOutcome::NestedCall(func) => match func.try_as_internal(&ctx) {
+                    Some(FuncEntityInternal::Wasm(wasm_func)) => {
+                        let nested_frame = self.new_frame(func, wasm_func);
+                        self.stack.frames.push(function_frame)?;
+                        function_frame = nested_frame;
+                    }
+                    Some(FuncEntityInternal::Host(host_func)) => {
+                        let instance = function_frame.instance();
+                        let host_func_entity = host_func.clone();
+                        if let Err(host_trap) =
+                            self.execute_host_func(&mut ctx, host_func_entity, Some(instance))
+                        {
+                            if let Some(exception) = Exception::from_trap(&host_trap) {
+                                // Exceptions thrown by host functions are caught by the caller.
+                                let exception = exception.clone();
+                                function_frame = self.unwind(&ctx, function_frame, exception)?;
+                                continue 'outer;
+                            }
+                            return Err(TaggedTrap::Host {
+                                host_func: func,
+                                host_trap,
+                                caller: Some(Box::new(function_frame)),
+                            });
+                        }
+                    }
+                    None => {
+                        let trap = TrapCode::DanglingFunc.into();
+                        return Err(self.with_backtrace(&ctx, &function_frame, trap).into());
+                    }
+                },
+                FunctionExecutionOutcome::TailCall(func) => match func.try_as_internal(&ctx) {
+                    Some(FuncEntityInternal::Wasm(wasm_func)) => {
+                        // The tail called function replaces the current function frame
+                        // so that the call stack does not grow upon tail calls.
+                        function_frame = self.new_frame(func, wasm_func);
+                    }
+                    Some(FuncEntityInternal::Host(host_func)) => {
+                        let instance = function_frame.instance();
+                        let host_func_entity = host_func.clone();
+                        // The host function returns directly to the caller of the current frame.
+                        let caller = self.stack.frames.pop();
+                        if let Err(host_trap) =
+                            self.execute_host_func(&mut ctx, host_func_entity, Some(instance))
+                        {
+                            if let Some(exception) = Exception::from_trap(&host_trap) {
+                                match caller {
+                                    Some(caller) => {
+                                        let exception = exception.clone();
+                                        function_frame = self.unwind(&ctx, caller, exception)?;
+                                        continue 'outer;
+                                    }
+                                    None => return Err(TaggedTrap::Wasm(host_trap)),
+                                }
+                            }
+                            return Err(TaggedTrap::Host {
+                                host_func: func,
+                                host_trap,
+                                caller: caller.map(Box::new),
+                            });
+                        }
+                        match caller {
+                            Some(frame) => function_frame = frame,
+                            None => return Ok(()),
+                        }
+                    }
+                    None => {
+                        let trap = TrapCode::DanglingFunc.into();
+                        return Err(self.with_backtrace(&ctx, &function_frame, trap).into());
+                    }
+                },
+                FunctionExecutionOutcome::Throw(tag) => {
+                    let exception = self.new_exception(&ctx, &function_frame, tag);
+                    function_frame = self.unwind(&ctx, function_frame, exception)?;
+                }
+                FunctionExecutionOutcome::Rethrow(slot) => {
+                    let position = function_frame.stack_base + slot.into_inner() as usize;
+                    let exception = self.stack.exceptions.get(position).clone();
+                    function_frame = self.unwind(&ctx, function_frame, exception)?;
+                }
+                FunctionExecutionOutcome::EpochDeadline => {
+                    // The epoch deadline callback is invoked without holding the engine lock.
+                    if let Err(trap) = ctx.as_context_mut().store.check_epoch_deadline() {
+                        return Err(self.with_backtrace(&ctx, &function_frame, trap).into());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Creates a new [`FunctionFrame`] for the Wasm function `func`.
+    ///
+    /// # Note
+    ///
+    /// The [`Engine`] is only locked while resolving the function body.
+    fn new_frame(&self, func: Func, wasm_func: &WasmFuncEntity) -> FunctionFrame {
+        let func_body = self.engine.resolve_func_body(wasm_func.func_body());
+        FunctionFrame::new_wasm(func, wasm_func, func_body)
+    }
+
+    /// Creates a new [`Exception`] for the tag at `tag_idx` thrown by the `frame`.
+    ///
+    /// # Note
+    ///
+    /// The payload of the exception is popped from the value stack.
+    fn new_exception(
+        &mut self,
+        ctx: impl AsContext,
+        frame: &FunctionFrame,
+        tag_idx: TagIdx,
+    ) -> Exception {
+        let tag = frame
+            .instance()
+            .get_tag(&ctx, tag_idx.into_inner())
+            .unwrap_or_else(|| {
+                panic!(
+                    "missing tag at index {:?} for instance {:?}",
+                    tag_idx,
+                    frame.instance()
+                )
+            });
+        let values = &mut self.stack.values;
+        let payload = self
+            .engine
+            .resolve_func_type(tag.signature(&ctx), |func_type| {
+                let mut payload = func_type
+                    .params()
+                    .iter()
+                    .rev()
+                    .map(|value_type| Value::from_untyped(values.pop(), *value_type))
+                    .collect::<Box<[Value]>>();
+                payload.reverse();
+                payload
+            });
+        Exception::new_unchecked(tag, payload)
+    }
+
+    /// Unwinds the call stack starting at `frame` until the `exception` is caught.
+    ///
+    /// Returns the function frame that caught the `exception` with its instruction
+    /// pointer set to the start of the `catch` or `catch_all` clause that caught it.
+    ///
+    /// # Note
+    ///
+    /// The caught exception is pushed to the value stack as defined by the clause.
+    ///
+    /// # Errors
+    ///
+    /// If the `exception` is not caught by any function frame on the call stack.
+    fn unwind(
+        &mut self,
+        ctx: impl AsContext,
+        mut frame: FunctionFrame,
+        exception: Exception,
+    ) -> Result<FunctionFrame, TaggedTrap> {
+        loop {
+            // Note: The instruction pointer already points to the instruction
+            //       following the instruction that threw the exception.
+            let pc = frame.inst_ptr - 1;
+            let instance = frame.instance();
+            let target = find_catch_target(frame.func_body().handlers(), pc, |tag_idx| {
+                instance.get_tag(&ctx, tag_idx.into_inner()) == Some(exception.tag())
+            });
+            if let Some(target) = target {
+                let height = frame.stack_base + target.stack_height as usize;
+                let values = &mut self.stack.values;
+                values.drop(values.len() - height);
+                // The slot of the caught exception is only used by `rethrow` instructions.
+                values.push(0_i32);
+                if target.with_payload {
+                    for value in exception.payload() {
+                        values.push(*value);
+                    }
+                }
+                self.stack.exceptions.push(height, exception);
+                frame.inst_ptr = target.pc.into_usize();
+                return Ok(frame);
+            }
+            match self.stack.frames.pop() {
+                Some(caller) => frame = caller,
+                None => return Err(TaggedTrap::Wasm(exception.into())),
+            }
+        }
+    }
+
+    /// Attaches a [`WasmBacktrace`] to the `trap` if Wasm backtraces are enabled.
+    ///
+    /// # Note
+    ///
+    /// The `frame` is the function frame of the trapping Wasm function whose
+    /// instruction pointer still points to the trapping instruction.
+    /// All of its callers are taken from the call stack.
+    fn with_backtrace(&self, ctx: impl AsContext, frame: &FunctionFrame, trap: Trap) -> Trap {
+        if !self.engine.config().wasm_backtrace() {
+            return trap;
+        }
+        let callers = self.stack.frames.iter().rev().map(|caller| {
+            // Note: The instruction pointer of a caller already points to
+            //       the instruction following its call instruction.
+            (caller, caller.inst_ptr.saturating_sub(1))
+        });
+        let frames = iter::once((frame, frame.inst_ptr))
+            .chain(callers)
+            .map(|(frame, pc)| {
+                let instance = ctx.as_context().store.resolve_instance(frame.instance());
+                let func_index = instance.get_func_index(frame.func).unwrap_or_else(|| {
+                    panic!(
+                        "missing function {:?} in the instance of its function frame",
+                        frame.func
+                    )
+                });
+                let func_name = instance.get_func_name(func_index).map(Into::into);
+                let module_offset = frame.func_body().source_offset(pc);
+                FrameInfo::new(func_index, func_name, module_offset)
+            });
+        trap.with_backtrace(WasmBacktrace::new(frames))
+    }
+
+    /// Executes the given function frame and returns the outcome.
+    ///
+    /// # Note
+    ///
+    /// The [`Engine`] lock is not held since the function frame shares its
+    /// function body with the [`Engine`]. This allows other executions of
+    /// the same [`Engine`] to run concurrently.
+    ///
+    /// # Errors
+    ///
+    /// If the function frame execution trapped.
+    #[inline(always)]
+    fn execute_frame(
+        &mut self,
+        mut ctx: impl AsContextMut,
+        frame: &mut FunctionFrame,
+    ) -> Result<FunctionExecutionOutcome, Trap> {
+        let func_body = Arc::as_ptr(frame.func_body());
+        // Safety: The function body is kept alive by the `frame` since the function
+        //         body of a function frame is never replaced. Also the function body
+        //         is not part of the `frame` itself but a separate shared allocation
+        //         which is why the mutable borrow of the `frame` does not alias it.
+        //
+        //         Not cloning the shared function body avoids two atomic operations
+        //         every time a function frame is executed.
+        let func_body = unsafe { &*func_body };
+        ExecutionContext::new(func_body.resolve(), &mut self.stack.values, frame)?
+            .execute_frame(&mut ctx)
+    }
+
+    /// Executes the given host function.
+    ///
+    /// # Note
+    ///
+    /// The [`Engine`] lock is not held while the host function is running.
+    ///
+    /// The function frames of the execution are accounted for in the call depth
+    /// of the [`Store`] while the host function is running so that executions
+    /// nested in the host function respect the call stack limit.
+    ///
+    /// Upon a trap the parameters of the host function remain on top of
+    /// the value stack so that the execution can be resumed later on.
+    ///
+    /// # Errors
+    ///
+    /// - If the host function returns a host side error or trap.
+    /// - If the value stack overflowed upon pushing parameters or results.
+    ///
+    /// [`Store`]: [`crate::Store`]
+    #[inline(never)]
+    fn execute_host_func<C>(
+        &mut self,
+        mut ctx: C,
+        host_func: HostFuncEntity<<C as AsContext>::UserState>,
+        instance: Option<Instance>,
+    ) -> Result<(), Trap>
+    where
+        C: AsContextMut,
+    {
+        // The host function signature is required for properly
+        // adjusting, inspecting and manipulating the value stack.
+        let (len_inputs, len_outputs) =
+            self.engine
+                .resolve_func_type(host_func.signature(), |func_type| {
+                    let (input_types, output_types) = func_type.params_results();
+                    (input_types.len(), output_types.len())
+                });
+        // In case the host function returns more values than it takes
+        // we are required to extend the value stack.
+        let max_inout = cmp::max(len_inputs, len_outputs);
+        self.stack.values.reserve(max_inout)?;
+        if len_outputs > len_inputs {
+            let delta = len_outputs - len_inputs;
+            self.stack.values.extend_zeros(delta)?;
+        }
+        let params_results = FuncParams::new(
+            self.stack.values.peek_as_slice_mut(max_inout),
+            len_inputs,
+            len_outputs,
+        );
+        // Now we are ready to perform the host function call.
+        // Note: We need to clone the host function due to some borrowing issues.
+        //       This should not be a big deal since host functions usually are cheap to clone.
+        //
+        // Note: The function frame of the Wasm caller is not on the call stack.
+        let frames = self.stack.frames.len() + usize::from(instance.is_some());
+        ctx.as_context_mut()
+            .store
+            .call_depth_mut()
+            .enter_host(frames);
+        let result = host_func.call(ctx.as_context_mut(), instance, params_results);
+        ctx.as_context_mut()
+            .store
+            .call_depth_mut()
+            .leave_host(frames);
+        if let Err(trap) = result {
+            if len_outputs > len_inputs {
+                let delta = len_outputs - len_inputs;
+                self.stack.values.drop(delta);
+            }
+            return Err(trap);
+        }
+        // If the host functions returns fewer results than it receives parameters
+        // the value stack needs to be shrinked for the delta.
+        if len_outputs < len_inputs {
+            let delta = len_inputs - len_outputs;
+            self.stack.values.drop(delta);
+        }
+        // At this point the host function has been called and has directly
+        // written its results into the value stack so that the last entries
+        // in the value stack are the result values of the host function call.
+        Ok(())
+    }
+}
@@ -56,6 +56,33 @@ impl<'a> FuncParams<'a> {
             .unwrap_or_else(|error| panic!("encountered unexpected invalid tuple length: {error}"))
     }
 
+    /// Returns the raw (encoded but untyped) host function parameters.
+    pub fn params(&self) -> &[UntypedValue] {
+        &self.params_results[..self.len_params]
+    }
+
+    /// Sets the raw (encoded but untyped) results of the function invocation.
+    ///
+    /// # Panics
+    ///
+    /// If the number of results does not match the expected amount.
+    pub fn write_untyped_results<I>(self, results: I) -> FuncResults
+    where
+        I: IntoIterator<Item = UntypedValue>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let results = results.into_iter();
+        assert_eq!(
+            results.len(),
+            self.len_results,
+            "encountered unexpected number of results"
+        );
+        for (slot, result) in self.params_results.iter_mut().zip(results) {
+            *slot = result;
+        }
+        FuncResults {}
+    }
+
     /// Sets the results of the function invocation.
     ///
     /// # Panics
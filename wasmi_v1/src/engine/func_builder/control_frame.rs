@@ -173,6 +173,83 @@ impl IfControlFrame {
     }
 }
 
+/// A Wasm `try` control flow frame of the `exception-handling` proposal.
+#[derive(Debug, Copy, Clone)]
+pub struct TryControlFrame {
+    /// Label representing the end of the [`TryControlFrame`].
+    end_label: LabelIdx,
+    /// The type of the [`TryControlFrame`].
+    block_type: BlockType,
+    /// The value stack height upon entering the [`TryControlFrame`].
+    stack_height: u32,
+    /// The index of the exception handler of the [`TryControlFrame`].
+    handler: u32,
+    /// Is `true` once the first `catch` or `catch_all` clause has been encountered.
+    ///
+    /// # Note
+    ///
+    /// Exceptions thrown within `catch` and `catch_all` clauses are not
+    /// caught by the exception handler of the [`TryControlFrame`].
+    catching: bool,
+}
+
+impl TryControlFrame {
+    /// Creates a new [`TryControlFrame`].
+    pub fn new(
+        block_type: BlockType,
+        end_label: LabelIdx,
+        stack_height: u32,
+        handler: u32,
+    ) -> Self {
+        Self {
+            block_type,
+            end_label,
+            stack_height,
+            handler,
+            catching: false,
+        }
+    }
+
+    /// Returns the label for the branch destination of the [`TryControlFrame`].
+    ///
+    /// # Note
+    ///
+    /// Branches to [`TryControlFrame`] jump to the end of the frame.
+    pub fn branch_destination(&self) -> LabelIdx {
+        self.end_label
+    }
+
+    /// Returns the label to the end of the [`TryControlFrame`].
+    pub fn end_label(&self) -> LabelIdx {
+        self.end_label
+    }
+
+    /// Returns the value stack height upon entering the [`TryControlFrame`].
+    pub fn stack_height(&self) -> u32 {
+        self.stack_height
+    }
+
+    /// Returns the [`BlockType`] of the [`TryControlFrame`].
+    pub fn block_type(&self) -> BlockType {
+        self.block_type
+    }
+
+    /// Returns the index of the exception handler of the [`TryControlFrame`].
+    pub fn handler(&self) -> u32 {
+        self.handler
+    }
+
+    /// Returns `true` if the first `catch` or `catch_all` clause has been encountered.
+    pub fn is_catching(&self) -> bool {
+        self.catching
+    }
+
+    /// Signals that the first `catch` or `catch_all` clause has been encountered.
+    pub fn start_catching(&mut self) {
+        self.catching = true;
+    }
+}
+
 /// An unreachable control flow frame of any kind.
 #[derive(Debug, Copy, Clone)]
 pub struct UnreachableControlFrame {
@@ -193,6 +270,8 @@ pub enum ControlFrameKind {
     Loop,
     /// An `if` and `else` block control flow frame.
     If,
+    /// A `try` block control flow frame.
+    Try,
 }
 
 impl UnreachableControlFrame {
@@ -230,6 +309,8 @@ pub enum ControlFrame {
     Loop(LoopControlFrame),
     /// If and else control frame.
     If(IfControlFrame),
+    /// Try control frame.
+    Try(TryControlFrame),
     /// An unreachable control frame.
     Unreachable(UnreachableControlFrame),
 }
@@ -252,6 +333,12 @@ impl From<IfControlFrame> for ControlFrame {
     }
 }
 
+impl From<TryControlFrame> for ControlFrame {
+    fn from(frame: TryControlFrame) -> Self {
+        Self::Try(frame)
+    }
+}
+
 impl From<UnreachableControlFrame> for ControlFrame {
     fn from(frame: UnreachableControlFrame) -> Self {
         Self::Unreachable(frame)
@@ -265,6 +352,7 @@ impl ControlFrame {
             ControlFrame::Block(_) => ControlFrameKind::Block,
             ControlFrame::Loop(_) => ControlFrameKind::Loop,
             ControlFrame::If(_) => ControlFrameKind::If,
+            ControlFrame::Try(_) => ControlFrameKind::Try,
             ControlFrame::Unreachable(frame) => frame.kind(),
         }
     }
@@ -275,6 +363,7 @@ impl ControlFrame {
             Self::Block(frame) => frame.branch_destination(),
             Self::Loop(frame) => frame.branch_destination(),
             Self::If(frame) => frame.branch_destination(),
+            Self::Try(frame) => frame.branch_destination(),
             Self::Unreachable(frame) => panic!(
                 "tried to get `branch_destination` for an unreachable control frame: {:?}",
                 frame,
@@ -291,6 +380,7 @@ impl ControlFrame {
         match self {
             Self::Block(frame) => frame.end_label(),
             Self::If(frame) => frame.end_label(),
+            Self::Try(frame) => frame.end_label(),
             Self::Loop(frame) => panic!(
                 "tried to get `end_label` for a loop control frame: {:?}",
                 frame
@@ -308,6 +398,7 @@ impl ControlFrame {
             Self::Block(frame) => frame.stack_height(),
             Self::Loop(frame) => frame.stack_height(),
             Self::If(frame) => frame.stack_height(),
+            Self::Try(frame) => frame.stack_height(),
             Self::Unreachable(frame) => frame.stack_height(),
         }
     }
@@ -318,6 +409,7 @@ impl ControlFrame {
             Self::Block(frame) => frame.block_type(),
             Self::Loop(frame) => frame.block_type(),
             Self::If(frame) => frame.block_type(),
+            Self::Try(frame) => frame.block_type(),
             Self::Unreachable(frame) => frame.block_type(),
         }
     }
@@ -0,0 +1,205 @@
+use super::{
+    ControlFrame,
+    ControlFrameKind,
+    FunctionBuilder,
+    Reloc,
+    TryControlFrame,
+    UnreachableControlFrame,
+};
+use crate::{
+    engine::{ExceptionHandler, Instruction, Target},
+    module::{BlockType, TagIdx},
+    FuncType,
+    ModuleError,
+};
+use wasmi_core::ValueType;
+
+impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
+    /// Resolves the [`FuncType`] describing the payload of the given [`TagIdx`].
+    fn tag_type_of(&self, tag_idx: TagIdx) -> FuncType {
+        let dedup_func_type = self.res.get_type_of_tag(tag_idx);
+        self.res
+            .engine()
+            .resolve_func_type(dedup_func_type, Clone::clone)
+    }
+
+    /// Returns the reachable `try` control flow frame on top of the control flow stack.
+    ///
+    /// Returns `None` if the `try` control flow frame is unreachable.
+    ///
+    /// # Panics
+    ///
+    /// If there is no `try` control flow frame on top of the control flow stack.
+    fn last_try_frame(&self) -> Option<TryControlFrame> {
+        match self.control_frames.last() {
+            ControlFrame::Try(try_frame) => Some(*try_frame),
+            ControlFrame::Unreachable(frame) if matches!(frame.kind(), ControlFrameKind::Try) => {
+                None
+            }
+            unexpected => panic!(
+                "expected `try` control flow frame on top but found: {:?}",
+                unexpected,
+            ),
+        }
+    }
+
+    /// Ends the `try` body or the current `catch` or `catch_all` clause of the `try_frame`.
+    ///
+    /// # Note
+    ///
+    /// - If `leave` is `true` or a clause is ended the end of the ended code is
+    ///   connected to the end of the `try` block if it is reachable. This also
+    ///   drops the slot of the caught exception of an ended clause.
+    /// - Ending the `try` body closes the range of instructions covered by the
+    ///   exception handler of the `try_frame`.
+    fn end_try_section(&mut self, try_frame: TryControlFrame, leave: bool) {
+        if self.is_reachable() && (leave || try_frame.is_catching()) {
+            let drop_keep = self.compute_drop_keep(0);
+            let dst_pc =
+                self.try_resolve_label(try_frame.end_label(), |pc| Reloc::Br { inst_idx: pc });
+            self.inst_builder
+                .push_inst(Instruction::Br(Target::new(dst_pc, drop_keep)));
+        }
+        if !try_frame.is_catching() {
+            let end = self.inst_builder.pin_current_pc();
+            self.exception_handlers[try_frame.handler() as usize].close(end);
+        }
+    }
+
+    /// Starts a `catch` or `catch_all` clause of the `try` control flow frame on top.
+    ///
+    /// # Note
+    ///
+    /// The clause receives the slot of the caught exception on top of the
+    /// value stack followed by the given `payload` types.
+    fn start_catch_clause(&mut self, tag: Option<TagIdx>, payload: &[ValueType]) {
+        let mut try_frame = match self.last_try_frame() {
+            Some(try_frame) => try_frame,
+            None => {
+                // The entire `try` block is unreachable and so are its clauses.
+                return;
+            }
+        };
+        self.end_try_section(try_frame, true);
+        let pc = self.inst_builder.pin_current_pc();
+        self.exception_handlers[try_frame.handler() as usize]
+            .push_catch(tag.map(|tag| tag.into_u32().into()), pc);
+        try_frame.start_catching();
+        *self.control_frames.last_mut() = try_frame.into();
+        self.value_stack.shrink_to(try_frame.stack_height());
+        // The slot of the caught exception is used by `rethrow` instructions.
+        self.value_stack.push(ValueType::I32);
+        for value_type in payload {
+            self.value_stack.push(*value_type);
+        }
+        // We can reset reachability now since the parent `try` block was reachable.
+        self.reachable = true;
+    }
+
+    /// Translates a Wasm `try` control flow operator.
+    pub fn translate_try(&mut self, block_type: BlockType) -> Result<(), ModuleError> {
+        let stack_height = self.frame_stack_height(block_type);
+        if self.is_reachable() {
+            let end_label = self.inst_builder.new_label();
+            let start = self.inst_builder.pin_current_pc();
+            let handler = self
+                .exception_handlers
+                .len()
+                .try_into()
+                .unwrap_or_else(|error| {
+                    panic!("encountered too many `try` blocks in function: {}", error)
+                });
+            self.exception_handlers
+                .push(ExceptionHandler::new(start, stack_height));
+            self.control_frames.push_frame(TryControlFrame::new(
+                block_type,
+                end_label,
+                stack_height,
+                handler,
+            ));
+        } else {
+            self.control_frames.push_frame(UnreachableControlFrame::new(
+                ControlFrameKind::Try,
+                block_type,
+                stack_height,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Translates a Wasm `catch` control flow operator.
+    pub fn translate_catch(&mut self, tag_idx: TagIdx) -> Result<(), ModuleError> {
+        let tag_type = self.tag_type_of(tag_idx);
+        self.start_catch_clause(Some(tag_idx), tag_type.params());
+        Ok(())
+    }
+
+    /// Translates a Wasm `catch_all` control flow operator.
+    pub fn translate_catch_all(&mut self) -> Result<(), ModuleError> {
+        self.start_catch_clause(None, &[]);
+        Ok(())
+    }
+
+    /// Ends the `try` control flow frame on top before translating its `end`.
+    pub(super) fn translate_end_try(&mut self) {
+        if let Some(try_frame) = self.last_try_frame() {
+            self.end_try_section(try_frame, false);
+        }
+    }
+
+    /// Translates a Wasm `delegate` control flow operator.
+    ///
+    /// # Note
+    ///
+    /// Exceptions thrown in the `try` body are delegated to the innermost
+    /// `try` body enclosing the label at `relative_depth` or to the caller
+    /// of the function if there is none.
+    pub fn translate_delegate(&mut self, relative_depth: u32) -> Result<(), ModuleError> {
+        if let Some(try_frame) = self.last_try_frame() {
+            // Note: The label of the delegating `try` block itself is not in scope.
+            let len_frames = self.control_frames.len() as u32;
+            let target = (relative_depth + 1..len_frames).find_map(|depth| {
+                match self.control_frames.nth_back(depth) {
+                    ControlFrame::Try(frame) if !frame.is_catching() => Some(frame.handler()),
+                    _ => None,
+                }
+            });
+            self.exception_handlers[try_frame.handler() as usize].delegate(target);
+        }
+        self.translate_end()
+    }
+
+    /// Translates a Wasm `throw` instruction.
+    pub fn translate_throw(&mut self, tag_idx: TagIdx) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let tag_type = builder.tag_type_of(tag_idx);
+            for param in tag_type.params().iter().rev() {
+                let popped = builder.value_stack.pop1();
+                debug_assert_eq!(popped, *param);
+            }
+            builder
+                .inst_builder
+                .push_inst(Instruction::Throw(tag_idx.into_u32().into()));
+            builder.reachable = false;
+            Ok(())
+        })
+    }
+
+    /// Translates a Wasm `rethrow` instruction.
+    pub fn translate_rethrow(&mut self, relative_depth: u32) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let slot = match builder.control_frames.nth_back(relative_depth) {
+                ControlFrame::Try(frame) if frame.is_catching() => frame.stack_height(),
+                unexpected => panic!(
+                    "expected `catch` or `catch_all` clause for `rethrow` but found: {:?}",
+                    unexpected,
+                ),
+            };
+            builder
+                .inst_builder
+                .push_inst(Instruction::Rethrow(slot.into()));
+            builder.reachable = false;
+            Ok(())
+        })
+    }
+}
@@ -1,6 +1,6 @@
 //! Abstractions to build up instructions forming Wasm function bodies.
 
-use crate::engine::{Engine, FuncBody, Instruction};
+use crate::engine::{Engine, ExceptionHandler, FuncBody, Instruction};
 use alloc::vec::Vec;
 use core::mem;
 
@@ -74,7 +74,7 @@ pub struct LabelIdx(pub(crate) usize);
 /// A relocation entry that specifies.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Reloc {
-    /// Patch the target of the `br`, `br_eqz` or `br_nez` instruction.
+    /// Patch the target of the `br`, `br_eqz`, `br_nez` or fused `br_if` instruction.
     Br { inst_idx: InstructionIdx },
     /// Patch the specified target index inside of a Wasm `br_table` instruction.
     BrTable {
@@ -110,6 +110,21 @@ pub struct InstructionsBuilder {
     insts: Vec<Instruction>,
     /// All labels and their uses.
     labels: Vec<Label>,
+    /// The offsets of the Wasm operators within the Wasm binary the instructions originate from.
+    ///
+    /// # Note
+    ///
+    /// This is only filled if source offsets are recorded for Wasm backtraces.
+    offsets: Vec<usize>,
+    /// The offset of the currently translated Wasm operator if recorded.
+    source_offset: Option<usize>,
+    /// The amount of leading instructions that must not be rewritten anymore.
+    ///
+    /// # Note
+    ///
+    /// Positions that are referred to by labels or exception handlers are pinned
+    /// so that rewriting the trailing instructions never invalidates them.
+    pinned: usize,
 }
 
 impl InstructionsBuilder {
@@ -118,6 +133,63 @@ impl InstructionsBuilder {
         InstructionIdx::from_usize(self.insts.len())
     }
 
+    /// Sets the offset of the currently translated Wasm operator within the Wasm binary.
+    ///
+    /// # Note
+    ///
+    /// All instructions pushed afterwards are associated to this offset.
+    pub fn set_source_offset(&mut self, offset: usize) {
+        self.source_offset = Some(offset);
+    }
+
+    /// Returns the current instruction pointer as index and pins it.
+    ///
+    /// # Note
+    ///
+    /// Instructions before a pinned position are never rewritten
+    /// by [`InstructionsBuilder::replace_last`].
+    pub fn pin_current_pc(&mut self) -> InstructionIdx {
+        self.pinned = self.insts.len();
+        self.current_pc()
+    }
+
+    /// Returns the last `N` instructions if they can be rewritten.
+    ///
+    /// Returns `None` if there are less than `N` instructions or if a position
+    /// within the last `N` instructions has been pinned, e.g. by a label.
+    pub fn last_insts<const N: usize>(&self) -> Option<&[Instruction; N]> {
+        let start = self.insts.len().checked_sub(N)?;
+        if start < self.pinned {
+            return None;
+        }
+        self.insts[start..].try_into().ok()
+    }
+
+    /// Replaces the last `len` instructions with `inst`.
+    ///
+    /// Returns an [`InstructionIdx`] to refer to the replacing instruction.
+    ///
+    /// # Note
+    ///
+    /// This is used to fuse sequences of instructions into a single instruction
+    /// which is only valid for instructions returned by [`InstructionsBuilder::last_insts`].
+    pub fn replace_last(&mut self, len: usize, inst: Instruction) -> InstructionIdx {
+        self.remove_last(len);
+        self.push_inst(inst)
+    }
+
+    /// Removes the last `len` instructions.
+    ///
+    /// # Note
+    ///
+    /// This is only valid for instructions returned by [`InstructionsBuilder::last_insts`].
+    pub fn remove_last(&mut self, len: usize) {
+        let start = self.insts.len() - len;
+        debug_assert!(start >= self.pinned, "tried to rewrite pinned instructions");
+        self.insts.truncate(start);
+        self.offsets.truncate(start);
+    }
+
     /// Creates a new unresolved label and returns an index to it.
     pub fn new_label(&mut self) -> LabelIdx {
         let idx = LabelIdx(self.labels.len());
@@ -162,7 +234,7 @@ impl InstructionsBuilder {
     ///
     /// If the label has already been resolved.
     pub fn resolve_label(&mut self, label: LabelIdx) {
-        let dst_pc = self.current_pc();
+        let dst_pc = self.pin_current_pc();
         let old_label = mem::replace(&mut self.labels[label.0], Label::Resolved(dst_pc));
         match old_label {
             Label::Resolved(idx) => panic!(
@@ -201,6 +273,9 @@ impl InstructionsBuilder {
     pub fn push_inst(&mut self, inst: Instruction) -> InstructionIdx {
         let idx = self.current_pc();
         self.insts.push(inst);
+        if let Some(offset) = self.source_offset {
+            self.offsets.push(offset);
+        }
         idx
     }
 
@@ -210,7 +285,17 @@ impl InstructionsBuilder {
             Reloc::Br { inst_idx } => match &mut self.insts[inst_idx.into_usize()] {
                 Instruction::Br(target)
                 | Instruction::BrIfEqz(target)
-                | Instruction::BrIfNez(target) => {
+                | Instruction::BrIfNez(target)
+                | Instruction::BrIfI32Eq(target)
+                | Instruction::BrIfI32Ne(target)
+                | Instruction::BrIfI32LtS(target)
+                | Instruction::BrIfI32LtU(target)
+                | Instruction::BrIfI32GtS(target)
+                | Instruction::BrIfI32GtU(target)
+                | Instruction::BrIfI32LeS(target)
+                | Instruction::BrIfI32LeU(target)
+                | Instruction::BrIfI32GeS(target)
+                | Instruction::BrIfI32GeU(target) => {
                     target.update_destination_pc(dst_pc);
                 }
                 _ => panic!(
@@ -241,13 +326,32 @@ impl InstructionsBuilder {
     /// into the [`Engine`] so that the [`Engine`] is
     /// aware of the Wasm function existance. Returns a `FuncBody`
     /// reference that allows to retrieve the instructions.
+    ///
+    /// The exception `handlers` and the recorded source offsets of the
+    /// function body are stored alongside.
     #[must_use]
-    pub fn finish(
+    pub fn finish<H>(
         &mut self,
         engine: &Engine,
         len_locals: usize,
         max_stack_height: usize,
-    ) -> FuncBody {
-        engine.alloc_func_body(len_locals, max_stack_height, self.insts.drain(..))
+        handlers: H,
+    ) -> FuncBody
+    where
+        H: IntoIterator<Item = ExceptionHandler>,
+    {
+        debug_assert!(
+            self.offsets.is_empty() || self.offsets.len() == self.insts.len(),
+            "expected a source offset for every instruction",
+        );
+        self.source_offset = None;
+        self.pinned = 0;
+        engine.alloc_func_body(
+            len_locals,
+            max_stack_height,
+            self.insts.drain(..),
+            handlers,
+            self.offsets.drain(..),
+        )
     }
 }
@@ -2,8 +2,14 @@
 
 mod control_frame;
 mod control_stack;
+mod exceptions;
 mod inst_builder;
 mod locals_registry;
+mod peephole;
+#[cfg(feature = "simd")]
+mod simd;
+#[cfg(feature = "threads")]
+mod threads;
 mod value_stack;
 
 pub use self::inst_builder::{InstructionIdx, InstructionsBuilder, LabelIdx, RelativeDepth, Reloc};
@@ -14,31 +20,24 @@ use self::{
         ControlFrameKind,
         IfControlFrame,
         LoopControlFrame,
+        TryControlFrame,
         UnreachableControlFrame,
     },
     control_stack::ControlFlowStack,
     locals_registry::LocalsRegistry,
     value_stack::ValueStack,
 };
-use super::{DropKeep, FuncBody, Instruction, Target};
+use super::{DropKeep, ExceptionHandler, FuncBody, Instruction, Target};
 use crate::{
-    engine::bytecode::Offset,
-    module::{
-        BlockType,
-        FuncIdx,
-        FuncTypeIdx,
-        GlobalIdx,
-        MemoryIdx,
-        ModuleResources,
-        TableIdx,
-        DEFAULT_MEMORY_INDEX,
-    },
+    engine::bytecode::{self, Offset},
+    module::{BlockType, FuncIdx, FuncTypeIdx, GlobalIdx, MemoryIdx, ModuleResources, TableIdx},
     Engine,
     FuncType,
     ModuleError,
     Mutability,
+    Value,
 };
-use wasmi_core::{Value, ValueType, F32, F64};
+use wasmi_core::{ValueType, F32, F64};
 
 /// The interface to translate a `wasmi` bytecode function using Wasm bytecode.
 #[derive(Debug)]
@@ -71,6 +70,20 @@ pub struct FunctionBuilder<'engine, 'parser> {
     /// Visiting the Wasm `Else` or `End` control flow operator resets
     /// reachability to `true` again.
     reachable: bool,
+    /// The exception handlers of the Wasm `try` blocks of the function.
+    ///
+    /// # Note
+    ///
+    /// The handlers are ordered by the position of their `try` blocks.
+    exception_handlers: Vec<ExceptionHandler>,
+    /// Is `true` if the translated `wasmi` bytecode is optimized.
+    ///
+    /// # Note
+    ///
+    /// This caches [`Config::bytecode_optimizations`] of the [`Engine`].
+    ///
+    /// [`Config::bytecode_optimizations`]: [`crate::Config::bytecode_optimizations`]
+    bytecode_optimizations: bool,
 }
 
 impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
@@ -91,6 +104,8 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
             inst_builder,
             locals,
             reachable: true,
+            exception_handlers: Vec::new(),
+            bytecode_optimizations: engine.config().bytecode_optimizations(),
         }
     }
 
@@ -178,12 +193,23 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         len_params_locals - len_params
     }
 
+    /// Sets the offset of the currently translated Wasm operator within the Wasm binary.
+    ///
+    /// # Note
+    ///
+    /// This is used to map instructions back to their Wasm operators in Wasm backtraces.
+    pub fn set_source_offset(&mut self, offset: usize) {
+        self.inst_builder.set_source_offset(offset);
+    }
+
     /// Finishes constructing the function and returns its [`FuncBody`].
     pub fn finish(mut self) -> FuncBody {
+        let len_locals = self.len_locals();
         self.inst_builder.finish(
             self.engine,
-            self.len_locals(),
+            len_locals,
             self.value_stack.max_stack_height() as usize,
+            self.exception_handlers.drain(..),
         )
     }
 
@@ -217,7 +243,7 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         let frame = self.control_frames.nth_back(depth);
         // Find out how many values we need to keep (copy to the new stack location after the drop).
         let keep = match frame.kind() {
-            ControlFrameKind::Block | ControlFrameKind::If => {
+            ControlFrameKind::Block | ControlFrameKind::If | ControlFrameKind::Try => {
                 frame.block_type().len_results(self.engine)
             }
             ControlFrameKind::Loop => frame.block_type().len_params(self.engine),
@@ -268,6 +294,31 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         )
     }
 
+    /// Compute [`DropKeep`] for the tail call of a function with the given [`FuncType`].
+    ///
+    /// # Note
+    ///
+    /// Keeps the parameters of the tail called function and drops all
+    /// other values on the value stack as well as all local variables
+    /// and parameters of the function that is being translated.
+    ///
+    /// # Panics
+    ///
+    /// If the value stack is underflown.
+    fn drop_keep_return_call(&self, func_type: &FuncType) -> DropKeep {
+        debug_assert!(self.is_reachable());
+        let keep = func_type.params().len();
+        let height = self.value_stack.len() as usize;
+        assert!(
+            keep <= height,
+            "tried to keep {} values while having only {} values available on the stack",
+            keep,
+            height,
+        );
+        let len_params_locals = self.locals.len_registered() as usize;
+        DropKeep::new(height - keep + len_params_locals, keep)
+    }
+
     /// Returns the relative depth on the stack of the local variable.
     ///
     /// # Note
@@ -409,10 +460,10 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
                 else_label,
                 stack_height,
             ));
+            let make_br = self.fuse_branch_condition(true);
             let dst_pc = self.try_resolve_label(else_label, |pc| Reloc::Br { inst_idx: pc });
             let branch_target = Target::new(dst_pc, DropKeep::new(0, 0));
-            self.inst_builder
-                .push_inst(Instruction::BrIfEqz(branch_target));
+            self.inst_builder.push_inst(make_br(branch_target));
         } else {
             let stack_height = self.frame_stack_height(block_type);
             self.control_frames.push_frame(UnreachableControlFrame::new(
@@ -473,6 +524,9 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
 
     /// Translates a Wasm `end` control flow operator.
     pub fn translate_end(&mut self) -> Result<(), ModuleError> {
+        if matches!(self.control_frames.last().kind(), ControlFrameKind::Try) {
+            self.translate_end_try();
+        }
         let frame = self.control_frames.last();
         if let ControlFrame::If(if_frame) = &frame {
             // At this point we can resolve the `Else` label.
@@ -536,11 +590,12 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
             debug_assert_eq!(condition, ValueType::I32);
             match builder.acquire_target(relative_depth) {
                 AquiredTarget::Branch(end_label, drop_keep) => {
+                    let make_br = builder.fuse_branch_condition(false);
                     let dst_pc =
                         builder.try_resolve_label(end_label, |pc| Reloc::Br { inst_idx: pc });
                     builder
                         .inst_builder
-                        .push_inst(Instruction::BrIfNez(Target::new(dst_pc, drop_keep)));
+                        .push_inst(make_br(Target::new(dst_pc, drop_keep)));
                 }
                 AquiredTarget::Return(drop_keep) => {
                     builder
@@ -644,17 +699,54 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         table_idx: TableIdx,
     ) -> Result<(), ModuleError> {
         self.translate_if_reachable(|builder| {
-            /// The default Wasm MVP table index.
-            const DEFAULT_TABLE_INDEX: u32 = 0;
-            assert_eq!(table_idx.into_u32(), DEFAULT_TABLE_INDEX);
             let func_type_offset = builder.value_stack.pop1();
             debug_assert_eq!(func_type_offset, ValueType::I32);
             let func_type = builder.func_type_at(func_type_idx);
             builder.adjust_value_stack_for_call(&func_type);
-            let func_type_idx = func_type_idx.into_u32().into();
+            builder.inst_builder.push_inst(Instruction::CallIndirect {
+                table: table_idx.into_u32().into(),
+                signature: func_type_idx.into_u32().into(),
+            });
+            Ok(())
+        })
+    }
+
+    /// Translates a Wasm `return_call` instruction.
+    pub fn translate_return_call(&mut self, func_idx: FuncIdx) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let func_type = builder.func_type_of(func_idx);
+            let drop_keep = builder.drop_keep_return_call(&func_type);
+            builder.inst_builder.push_inst(Instruction::ReturnCall {
+                func: func_idx.into_u32().into(),
+                drop_keep,
+            });
+            builder.reachable = false;
+            Ok(())
+        })
+    }
+
+    /// Translates a Wasm `return_call_indirect` instruction.
+    pub fn translate_return_call_indirect(
+        &mut self,
+        func_type_idx: FuncTypeIdx,
+        table_idx: TableIdx,
+    ) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let func_type_offset = builder.value_stack.pop1();
+            debug_assert_eq!(func_type_offset, ValueType::I32);
+            let func_type = builder.func_type_at(func_type_idx);
+            let drop_keep = builder.drop_keep_return_call(&func_type);
+            builder
+                .inst_builder
+                .push_inst(Instruction::ReturnCallIndirect {
+                    table: table_idx.into_u32().into(),
+                    signature: func_type_idx.into_u32().into(),
+                });
+            // The `DropKeep` of the tail call is encoded by the following instruction.
             builder
                 .inst_builder
-                .push_inst(Instruction::CallIndirect(func_type_idx));
+                .push_inst(Instruction::Return(drop_keep));
+            builder.reachable = false;
             Ok(())
         })
     }
@@ -663,7 +755,7 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
     pub fn translate_drop(&mut self) -> Result<(), ModuleError> {
         self.translate_if_reachable(|builder| {
             builder.value_stack.pop1();
-            builder.inst_builder.push_inst(Instruction::Drop);
+            builder.push_drop();
             Ok(())
         })
     }
@@ -680,6 +772,24 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         })
     }
 
+    /// Translates a Wasm typed `select` instruction.
+    ///
+    /// # Note
+    ///
+    /// This is introduced by the `reference-types` Wasm proposal and
+    /// shares its implementation with the untyped `select` instruction.
+    pub fn translate_typed_select(&mut self, value_type: ValueType) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let (v0, v1, selector) = builder.value_stack.pop3();
+            debug_assert_eq!(selector, ValueType::I32);
+            debug_assert_eq!(v0, value_type);
+            debug_assert_eq!(v1, value_type);
+            builder.value_stack.push(value_type);
+            builder.inst_builder.push_inst(Instruction::Select);
+            Ok(())
+        })
+    }
+
     /// Translate a Wasm `local.get` instruction.
     pub fn translate_local_get(&mut self, local_idx: u32) -> Result<(), ModuleError> {
         self.translate_if_reachable(|builder| {
@@ -782,17 +892,16 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
     fn translate_load(
         &mut self,
         memory_idx: MemoryIdx,
-        offset: u32,
+        offset: u64,
         loaded_type: ValueType,
-        make_inst: fn(Offset) -> Instruction,
+        make_inst: fn(bytecode::MemoryIdx, Offset) -> Instruction,
     ) -> Result<(), ModuleError> {
         self.translate_if_reachable(|builder| {
-            debug_assert_eq!(memory_idx.into_u32(), DEFAULT_MEMORY_INDEX);
             let pointer = builder.value_stack.pop1();
-            debug_assert_eq!(pointer, ValueType::I32);
+            debug_assert_eq!(pointer, builder.memory_index_type(memory_idx));
             builder.value_stack.push(loaded_type);
             let offset = Offset::from(offset);
-            builder.inst_builder.push_inst(make_inst(offset));
+            builder.push_load(make_inst(memory_idx.into_u32().into(), offset));
             Ok(())
         })
     }
@@ -801,126 +910,154 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
     pub fn translate_i32_load(
         &mut self,
         memory_idx: MemoryIdx,
-        offset: u32,
+        offset: u64,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::I32, Instruction::I32Load)
+        self.translate_load(memory_idx, offset, ValueType::I32, |memory, offset| {
+            Instruction::I32Load { memory, offset }
+        })
     }
 
     /// Translate a Wasm `i64.load` instruction.
     pub fn translate_i64_load(
         &mut self,
         memory_idx: MemoryIdx,
-        offset: u32,
+        offset: u64,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::I64, Instruction::I64Load)
+        self.translate_load(memory_idx, offset, ValueType::I64, |memory, offset| {
+            Instruction::I64Load { memory, offset }
+        })
     }
 
     /// Translate a Wasm `f32.load` instruction.
     pub fn translate_f32_load(
         &mut self,
         memory_idx: MemoryIdx,
-        offset: u32,
+        offset: u64,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::F32, Instruction::F32Load)
+        self.translate_load(memory_idx, offset, ValueType::F32, |memory, offset| {
+            Instruction::F32Load { memory, offset }
+        })
     }
 
     /// Translate a Wasm `f64.load` instruction.
     pub fn translate_f64_load(
         &mut self,
         memory_idx: MemoryIdx,
-        offset: u32,
+        offset: u64,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::F64, Instruction::F64Load)
+        self.translate_load(memory_idx, offset, ValueType::F64, |memory, offset| {
+            Instruction::F64Load { memory, offset }
+        })
     }
 
     /// Translate a Wasm `i32.load_i8` instruction.
     pub fn translate_i32_load_i8(
         &mut self,
         memory_idx: MemoryIdx,
-        offset: u32,
+        offset: u64,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::I32, Instruction::I32Load8S)
+        self.translate_load(memory_idx, offset, ValueType::I32, |memory, offset| {
+            Instruction::I32Load8S { memory, offset }
+        })
     }
 
     /// Translate a Wasm `i32.load_u8` instruction.
     pub fn translate_i32_load_u8(
         &mut self,
         memory_idx: MemoryIdx,
-        offset: u32,
+        offset: u64,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::I32, Instruction::I32Load8U)
+        self.translate_load(memory_idx, offset, ValueType::I32, |memory, offset| {
+            Instruction::I32Load8U { memory, offset }
+        })
     }
 
     /// Translate a Wasm `i32.load_i16` instruction.
     pub fn translate_i32_load_i16(
         &mut self,
         memory_idx: MemoryIdx,
-        offset: u32,
+        offset: u64,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::I32, Instruction::I32Load16S)
+        self.translate_load(memory_idx, offset, ValueType::I32, |memory, offset| {
+            Instruction::I32Load16S { memory, offset }
+        })
     }
 
     /// Translate a Wasm `i32.load_u16` instruction.
     pub fn translate_i32_load_u16(
         &mut self,
         memory_idx: MemoryIdx,
-        offset: u32,
+        offset: u64,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::I32, Instruction::I32Load16U)
+        self.translate_load(memory_idx, offset, ValueType::I32, |memory, offset| {
+            Instruction::I32Load16U { memory, offset }
+        })
     }
 
     /// Translate a Wasm `i64.load_i8` instruction.
     pub fn translate_i64_load_i8(
         &mut self,
         memory_idx: MemoryIdx,
-        offset: u32,
+        offset: u64,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::I64, Instruction::I64Load8S)
+        self.translate_load(memory_idx, offset, ValueType::I64, |memory, offset| {
+            Instruction::I64Load8S { memory, offset }
+        })
     }
 
     /// Translate a Wasm `i64.load_u8` instruction.
     pub fn translate_i64_load_u8(
         &mut self,
         memory_idx: MemoryIdx,
-        offset: u32,
+        offset: u64,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::I64, Instruction::I64Load8U)
+        self.translate_load(memory_idx, offset, ValueType::I64, |memory, offset| {
+            Instruction::I64Load8U { memory, offset }
+        })
     }
 
     /// Translate a Wasm `i64.load_i16` instruction.
     pub fn translate_i64_load_i16(
         &mut self,
         memory_idx: MemoryIdx,
-        offset: u32,
+        offset: u64,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::I64, Instruction::I64Load16S)
+        self.translate_load(memory_idx, offset, ValueType::I64, |memory, offset| {
+            Instruction::I64Load16S { memory, offset }
+        })
     }
 
     /// Translate a Wasm `i64.load_u16` instruction.
     pub fn translate_i64_load_u16(
         &mut self,
         memory_idx: MemoryIdx,
-        offset: u32,
+        offset: u64,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::I64, Instruction::I64Load16U)
+        self.translate_load(memory_idx, offset, ValueType::I64, |memory, offset| {
+            Instruction::I64Load16U { memory, offset }
+        })
     }
 
     /// Translate a Wasm `i64.load_i32` instruction.
     pub fn translate_i64_load_i32(
         &mut self,
         memory_idx: MemoryIdx,
-        offset: u32,
+        offset: u64,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::I64, Instruction::I64Load32S)
+        self.translate_load(memory_idx, offset, ValueType::I64, |memory, offset| {
+            Instruction::I64Load32S { memory, offset }
+        })
     }
 
     /// Translate a Wasm `i64.load_u32` instruction.
     pub fn translate_i64_load_u32(
         &mut self,
         memory_idx: MemoryIdx,
-        offset: u32,
+        offset: u64,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::I64, Instruction::I64Load32U)
+        self.translate_load(memory_idx, offset, ValueType::I64, |memory, offset| {
+            Instruction::I64Load32U { memory, offset }
+        })
     }
 
     /// Translate a Wasm `<ty>.store` instruction.
@@ -941,17 +1078,18 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
     fn translate_store(
         &mut self,
         memory_idx: MemoryIdx,
-        offset: u32,
+        offset: u64,
         stored_value: ValueType,
-        make_inst: fn(Offset) -> Instruction,
+        make_inst: fn(bytecode::MemoryIdx, Offset) -> Instruction,
     ) -> Result<(), ModuleError> {
         self.translate_if_reachable(|builder| {
-            debug_assert_eq!(memory_idx.into_u32(), DEFAULT_MEMORY_INDEX);
             let (pointer, stored) = builder.value_stack.pop2();
-            debug_assert_eq!(pointer, ValueType::I32);
+            debug_assert_eq!(pointer, builder.memory_index_type(memory_idx));
             assert_eq!(stored_value, stored);
             let offset = Offset::from(offset);
-            builder.inst_builder.push_inst(make_inst(offset));
+            builder
+                .inst_builder
+                .push_inst(make_inst(memory_idx.into_u32().into(), offset));
             Ok(())
         })
     }
@@ -960,89 +1098,109 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
     pub fn translate_i32_store(
         &mut self,
         memory_idx: MemoryIdx,
-        offset: u32,
+        offset: u64,
     ) -> Result<(), ModuleError> {
-        self.translate_store(memory_idx, offset, ValueType::I32, Instruction::I32Store)
+        self.translate_store(memory_idx, offset, ValueType::I32, |memory, offset| {
+            Instruction::I32Store { memory, offset }
+        })
     }
 
     /// Translate a Wasm `i64.store` instruction.
     pub fn translate_i64_store(
         &mut self,
         memory_idx: MemoryIdx,
-        offset: u32,
+        offset: u64,
     ) -> Result<(), ModuleError> {
-        self.translate_store(memory_idx, offset, ValueType::I64, Instruction::I64Store)
+        self.translate_store(memory_idx, offset, ValueType::I64, |memory, offset| {
+            Instruction::I64Store { memory, offset }
+        })
     }
 
     /// Translate a Wasm `f32.store` instruction.
     pub fn translate_f32_store(
         &mut self,
         memory_idx: MemoryIdx,
-        offset: u32,
+        offset: u64,
     ) -> Result<(), ModuleError> {
-        self.translate_store(memory_idx, offset, ValueType::F32, Instruction::F32Store)
+        self.translate_store(memory_idx, offset, ValueType::F32, |memory, offset| {
+            Instruction::F32Store { memory, offset }
+        })
     }
 
     /// Translate a Wasm `f64.store` instruction.
     pub fn translate_f64_store(
         &mut self,
         memory_idx: MemoryIdx,
-        offset: u32,
+        offset: u64,
     ) -> Result<(), ModuleError> {
-        self.translate_store(memory_idx, offset, ValueType::F64, Instruction::F64Store)
+        self.translate_store(memory_idx, offset, ValueType::F64, |memory, offset| {
+            Instruction::F64Store { memory, offset }
+        })
     }
 
     /// Translate a Wasm `i32.store_i8` instruction.
     pub fn translate_i32_store_i8(
         &mut self,
         memory_idx: MemoryIdx,
-        offset: u32,
+        offset: u64,
     ) -> Result<(), ModuleError> {
-        self.translate_store(memory_idx, offset, ValueType::I32, Instruction::I32Store8)
+        self.translate_store(memory_idx, offset, ValueType::I32, |memory, offset| {
+            Instruction::I32Store8 { memory, offset }
+        })
     }
 
     /// Translate a Wasm `i32.store_i16` instruction.
     pub fn translate_i32_store_i16(
         &mut self,
         memory_idx: MemoryIdx,
-        offset: u32,
+        offset: u64,
     ) -> Result<(), ModuleError> {
-        self.translate_store(memory_idx, offset, ValueType::I32, Instruction::I32Store16)
+        self.translate_store(memory_idx, offset, ValueType::I32, |memory, offset| {
+            Instruction::I32Store16 { memory, offset }
+        })
     }
 
     /// Translate a Wasm `i64.store_i8` instruction.
     pub fn translate_i64_store_i8(
         &mut self,
         memory_idx: MemoryIdx,
-        offset: u32,
+        offset: u64,
     ) -> Result<(), ModuleError> {
-        self.translate_store(memory_idx, offset, ValueType::I64, Instruction::I64Store8)
+        self.translate_store(memory_idx, offset, ValueType::I64, |memory, offset| {
+            Instruction::I64Store8 { memory, offset }
+        })
     }
 
     /// Translate a Wasm `i64.store_i16` instruction.
     pub fn translate_i64_store_i16(
         &mut self,
         memory_idx: MemoryIdx,
-        offset: u32,
+        offset: u64,
     ) -> Result<(), ModuleError> {
-        self.translate_store(memory_idx, offset, ValueType::I64, Instruction::I64Store16)
+        self.translate_store(memory_idx, offset, ValueType::I64, |memory, offset| {
+            Instruction::I64Store16 { memory, offset }
+        })
     }
 
     /// Translate a Wasm `i64.store_i32` instruction.
     pub fn translate_i64_store_i32(
         &mut self,
         memory_idx: MemoryIdx,
-        offset: u32,
+        offset: u64,
     ) -> Result<(), ModuleError> {
-        self.translate_store(memory_idx, offset, ValueType::I64, Instruction::I64Store32)
+        self.translate_store(memory_idx, offset, ValueType::I64, |memory, offset| {
+            Instruction::I64Store32 { memory, offset }
+        })
     }
 
     /// Translate a Wasm `memory.size` instruction.
     pub fn translate_memory_size(&mut self, memory_idx: MemoryIdx) -> Result<(), ModuleError> {
         self.translate_if_reachable(|builder| {
-            debug_assert_eq!(memory_idx.into_u32(), DEFAULT_MEMORY_INDEX);
-            builder.value_stack.push(ValueType::I32);
-            builder.inst_builder.push_inst(Instruction::CurrentMemory);
+            let index_type = builder.memory_index_type(memory_idx);
+            builder.value_stack.push(index_type);
+            builder
+                .inst_builder
+                .push_inst(Instruction::CurrentMemory(memory_idx.into_u32().into()));
             Ok(())
         })
     }
@@ -1050,13 +1208,260 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
     /// Translate a Wasm `memory.grow` instruction.
     pub fn translate_memory_grow(&mut self, memory_idx: MemoryIdx) -> Result<(), ModuleError> {
         self.translate_if_reachable(|builder| {
-            debug_assert_eq!(memory_idx.into_u32(), DEFAULT_MEMORY_INDEX);
-            debug_assert_eq!(builder.value_stack.top(), ValueType::I32);
-            builder.inst_builder.push_inst(Instruction::GrowMemory);
+            debug_assert_eq!(
+                builder.value_stack.top(),
+                builder.memory_index_type(memory_idx)
+            );
+            builder
+                .inst_builder
+                .push_inst(Instruction::GrowMemory(memory_idx.into_u32().into()));
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm `memory.init` instruction.
+    pub fn translate_memory_init(
+        &mut self,
+        segment_index: u32,
+        memory_idx: MemoryIdx,
+    ) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let index_type = builder.memory_index_type(memory_idx);
+            builder.pop_bulk_operands(index_type, ValueType::I32, ValueType::I32);
+            builder.inst_builder.push_inst(Instruction::MemoryInit {
+                memory: memory_idx.into_u32().into(),
+                data: segment_index.into(),
+            });
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm `data.drop` instruction.
+    pub fn translate_data_drop(&mut self, segment_index: u32) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            builder
+                .inst_builder
+                .push_inst(Instruction::DataDrop(segment_index.into()));
             Ok(())
         })
     }
 
+    /// Translate a Wasm `memory.copy` instruction.
+    pub fn translate_memory_copy(
+        &mut self,
+        dst_memory_idx: MemoryIdx,
+        src_memory_idx: MemoryIdx,
+    ) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let dst_type = builder.memory_index_type(dst_memory_idx);
+            let src_type = builder.memory_index_type(src_memory_idx);
+            let len_type = match (dst_type, src_type) {
+                (ValueType::I64, ValueType::I64) => ValueType::I64,
+                _ => ValueType::I32,
+            };
+            builder.pop_bulk_operands(dst_type, src_type, len_type);
+            builder.inst_builder.push_inst(Instruction::MemoryCopy {
+                dst: dst_memory_idx.into_u32().into(),
+                src: src_memory_idx.into_u32().into(),
+            });
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm `memory.fill` instruction.
+    pub fn translate_memory_fill(&mut self, memory_idx: MemoryIdx) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let index_type = builder.memory_index_type(memory_idx);
+            builder.pop_bulk_operands(index_type, ValueType::I32, index_type);
+            builder
+                .inst_builder
+                .push_inst(Instruction::MemoryFill(memory_idx.into_u32().into()));
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm `table.init` instruction.
+    pub fn translate_table_init(
+        &mut self,
+        segment_index: u32,
+        table_idx: TableIdx,
+    ) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            builder.pop_bulk_operands(ValueType::I32, ValueType::I32, ValueType::I32);
+            builder.inst_builder.push_inst(Instruction::TableInit {
+                table: table_idx.into_u32().into(),
+                elem: segment_index.into(),
+            });
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm `elem.drop` instruction.
+    pub fn translate_elem_drop(&mut self, segment_index: u32) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            builder
+                .inst_builder
+                .push_inst(Instruction::ElemDrop(segment_index.into()));
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm `table.copy` instruction.
+    pub fn translate_table_copy(
+        &mut self,
+        dst_table_idx: TableIdx,
+        src_table_idx: TableIdx,
+    ) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            builder.pop_bulk_operands(ValueType::I32, ValueType::I32, ValueType::I32);
+            builder.inst_builder.push_inst(Instruction::TableCopy {
+                dst: dst_table_idx.into_u32().into(),
+                src: src_table_idx.into_u32().into(),
+            });
+            Ok(())
+        })
+    }
+
+    /// Translates a Wasm `table.get` instruction.
+    pub fn translate_table_get(&mut self, table_idx: TableIdx) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let element = builder.res.get_type_of_table(table_idx).element();
+            let index = builder.value_stack.pop1();
+            debug_assert_eq!(index, ValueType::I32);
+            builder.value_stack.push(element);
+            builder
+                .inst_builder
+                .push_inst(Instruction::TableGet(table_idx.into_u32().into()));
+            Ok(())
+        })
+    }
+
+    /// Translates a Wasm `table.set` instruction.
+    pub fn translate_table_set(&mut self, table_idx: TableIdx) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let element = builder.res.get_type_of_table(table_idx).element();
+            let (index, value) = builder.value_stack.pop2();
+            debug_assert_eq!(index, ValueType::I32);
+            debug_assert_eq!(value, element);
+            builder
+                .inst_builder
+                .push_inst(Instruction::TableSet(table_idx.into_u32().into()));
+            Ok(())
+        })
+    }
+
+    /// Translates a Wasm `table.size` instruction.
+    pub fn translate_table_size(&mut self, table_idx: TableIdx) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            builder.value_stack.push(ValueType::I32);
+            builder
+                .inst_builder
+                .push_inst(Instruction::TableSize(table_idx.into_u32().into()));
+            Ok(())
+        })
+    }
+
+    /// Translates a Wasm `table.grow` instruction.
+    pub fn translate_table_grow(&mut self, table_idx: TableIdx) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let element = builder.res.get_type_of_table(table_idx).element();
+            let (init, delta) = builder.value_stack.pop2();
+            debug_assert_eq!(init, element);
+            debug_assert_eq!(delta, ValueType::I32);
+            builder.value_stack.push(ValueType::I32);
+            builder
+                .inst_builder
+                .push_inst(Instruction::TableGrow(table_idx.into_u32().into()));
+            Ok(())
+        })
+    }
+
+    /// Translates a Wasm `table.fill` instruction.
+    pub fn translate_table_fill(&mut self, table_idx: TableIdx) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let element = builder.res.get_type_of_table(table_idx).element();
+            let (dst, value, len) = builder.value_stack.pop3();
+            debug_assert_eq!(dst, ValueType::I32);
+            debug_assert_eq!(value, element);
+            debug_assert_eq!(len, ValueType::I32);
+            builder
+                .inst_builder
+                .push_inst(Instruction::TableFill(table_idx.into_u32().into()));
+            Ok(())
+        })
+    }
+
+    /// Translates a Wasm `ref.null` instruction.
+    pub fn translate_ref_null(&mut self, value_type: ValueType) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            debug_assert!(value_type.is_ref());
+            builder.value_stack.push(value_type);
+            builder
+                .inst_builder
+                .push_inst(Instruction::constant(Value::default(value_type)));
+            Ok(())
+        })
+    }
+
+    /// Translates a Wasm `ref.is_null` instruction.
+    pub fn translate_ref_is_null(&mut self) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let reference = builder.value_stack.pop1();
+            debug_assert!(reference.is_ref());
+            builder.value_stack.push(ValueType::I32);
+            builder.inst_builder.push_inst(Instruction::RefIsNull);
+            Ok(())
+        })
+    }
+
+    /// Translates a Wasm `ref.func` instruction.
+    pub fn translate_ref_func(&mut self, func_idx: FuncIdx) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            builder.value_stack.push(ValueType::FuncRef);
+            builder
+                .inst_builder
+                .push_inst(Instruction::RefFunc(func_idx.into_u32().into()));
+            Ok(())
+        })
+    }
+
+    /// Pops the three operands of a bulk memory instruction from the emulated value stack.
+    ///
+    /// # Note
+    ///
+    /// This is used as the translation backend of the following Wasm instructions:
+    ///
+    /// - `memory.init`
+    /// - `memory.copy`
+    /// - `memory.fill`
+    /// - `table.init`
+    /// - `table.copy`
+    ///
+    /// The operands are of type `i32` unless they address a 64-bit linear memory.
+    fn pop_bulk_operands(
+        &mut self,
+        dst_type: ValueType,
+        src_or_value_type: ValueType,
+        len_type: ValueType,
+    ) {
+        let (dst, src_or_value, len) = self.value_stack.pop3();
+        debug_assert_eq!(dst, dst_type);
+        debug_assert_eq!(src_or_value, src_or_value_type);
+        debug_assert_eq!(len, len_type);
+    }
+
+    /// Returns the [`ValueType`] of the addresses of the indexed linear memory.
+    ///
+    /// # Note
+    ///
+    /// This is `i64` for 64-bit linear memories and `i32` otherwise.
+    fn memory_index_type(&self, memory_idx: MemoryIdx) -> ValueType {
+        match self.res.get_type_of_memory(memory_idx).is_64() {
+            true => ValueType::I64,
+            false => ValueType::I32,
+        }
+    }
+
     /// Translate a Wasm `<ty>.const` instruction.
     ///
     /// # Note
@@ -1116,7 +1521,7 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
             let condition = builder.value_stack.pop1();
             debug_assert_eq!(condition, input_type);
             builder.value_stack.push(ValueType::I32);
-            builder.inst_builder.push_inst(inst);
+            builder.push_unary(inst);
             Ok(())
         })
     }
@@ -1148,7 +1553,7 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
             debug_assert_eq!(v0, v1);
             debug_assert_eq!(v0, input_type);
             builder.value_stack.push(ValueType::I32);
-            builder.inst_builder.push_inst(inst);
+            builder.push_binary(inst);
             Ok(())
         })
     }
@@ -1342,7 +1747,7 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         self.translate_if_reachable(|builder| {
             let actual_type = builder.value_stack.top();
             debug_assert_eq!(actual_type, value_type);
-            builder.inst_builder.push_inst(inst);
+            builder.push_unary(inst);
             Ok(())
         })
     }
@@ -1393,7 +1798,7 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
             debug_assert_eq!(v0, v1);
             debug_assert_eq!(v0, value_type);
             builder.value_stack.push(value_type);
-            builder.inst_builder.push_inst(inst);
+            builder.push_binary(inst);
             Ok(())
         })
     }
@@ -1729,7 +2134,7 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
             let input = builder.value_stack.pop1();
             debug_assert_eq!(input, input_type);
             builder.value_stack.push(output_type);
-            builder.inst_builder.push_inst(inst);
+            builder.push_unary(inst);
             Ok(())
         })
     }
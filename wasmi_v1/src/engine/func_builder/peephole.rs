@@ -0,0 +1,291 @@
+//! Peephole optimizations of the `wasmi` bytecode during translation.
+//!
+//! # Note
+//!
+//! The optimizations rewrite the trailing instructions of the [`InstructionsBuilder`]
+//! before the next instruction is pushed. They never rewrite instructions across
+//! positions that have been pinned, e.g. by the labels of branch targets.
+//!
+//! [`InstructionsBuilder`]: [`super::InstructionsBuilder`]
+
+use super::{FunctionBuilder, Instruction, Target};
+use crate::engine::bytecode::Register;
+use wasmi_core::UntypedValue;
+
+/// A binary operation on two [`UntypedValue`] operands.
+type BinaryOp = fn(UntypedValue, UntypedValue) -> UntypedValue;
+
+/// A unary operation on an [`UntypedValue`] operand.
+type UnaryOp = fn(UntypedValue) -> UntypedValue;
+
+/// A constructor of a conditional branch instruction.
+type MakeBranch = fn(Target) -> Instruction;
+
+impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
+    /// Pushes the unary `inst` after trying to fold it with a preceding constant.
+    pub(super) fn push_unary(&mut self, inst: Instruction) {
+        if self.bytecode_optimizations {
+            if let (Some([Instruction::Const(input)]), Some(f)) =
+                (self.inst_builder.last_insts::<1>(), fold_unary(inst))
+            {
+                let result = f(UntypedValue::from(*input));
+                self.inst_builder
+                    .replace_last(1, Instruction::constant(result));
+                return;
+            }
+        }
+        self.inst_builder.push_inst(inst);
+    }
+
+    /// Pushes the binary `inst` after trying to fuse it with the preceding instructions.
+    ///
+    /// # Note
+    ///
+    /// In order of priority this
+    ///
+    /// - folds `i32.const a; i32.const b; i32.add` into `i32.const (a + b)`,
+    /// - fuses `i32.const c; i32.add` into `I32AddImm(c)`.
+    ///
+    /// The same applies to the other non-trapping `i32` and `i64` binary instructions.
+    pub(super) fn push_binary(&mut self, inst: Instruction) {
+        if self.bytecode_optimizations {
+            if let Some((len, fused)) = self.fuse_binary(inst) {
+                self.inst_builder.replace_last(len, fused);
+                return;
+            }
+        }
+        self.inst_builder.push_inst(inst);
+    }
+
+    /// Returns the fused instruction and the amount of preceding instructions it replaces.
+    fn fuse_binary(&self, inst: Instruction) -> Option<(usize, Instruction)> {
+        if let (Some([Instruction::Const(lhs), Instruction::Const(rhs)]), Some(f)) =
+            (self.inst_builder.last_insts::<2>(), fold_binary(inst))
+        {
+            let result = f(UntypedValue::from(*lhs), UntypedValue::from(*rhs));
+            return Some((2, Instruction::constant(result)));
+        }
+        match self.inst_builder.last_insts::<1>()? {
+            [Instruction::Const(rhs)] => Some((1, add_imm(inst, UntypedValue::from(*rhs))?)),
+            _ => None,
+        }
+    }
+
+    /// Pushes the load `inst` after trying to fuse it with a preceding `local.get`.
+    ///
+    /// # Note
+    ///
+    /// This fuses `local.get a; i32.load offset` into `I32LoadLocal` which
+    /// reads its address from the local variable directly.
+    pub(super) fn push_load(&mut self, inst: Instruction) {
+        if self.bytecode_optimizations {
+            if let Some(fused) = self.fuse_load(inst) {
+                self.inst_builder.replace_last(1, fused);
+                return;
+            }
+        }
+        self.inst_builder.push_inst(inst);
+    }
+
+    /// Returns the instruction that replaces the load `inst` and its preceding `local.get`.
+    fn fuse_load(&self, inst: Instruction) -> Option<Instruction> {
+        let base = match self.inst_builder.last_insts::<1>()? {
+            [Instruction::GetLocal { local_depth }] => {
+                Register::from_depth(local_depth.into_inner() - 1)?
+            }
+            _ => return None,
+        };
+        let fused = match inst {
+            Instruction::I32Load { memory, offset } => Instruction::I32LoadLocal {
+                base,
+                memory,
+                offset,
+            },
+            Instruction::I64Load { memory, offset } => Instruction::I64LoadLocal {
+                base,
+                memory,
+                offset,
+            },
+            Instruction::I32Load8U { memory, offset } => Instruction::I32Load8ULocal {
+                base,
+                memory,
+                offset,
+            },
+            _ => return None,
+        };
+        Some(fused)
+    }
+
+    /// Pushes a `drop` unless its value is produced by the preceding side effect free instruction.
+    ///
+    /// # Note
+    ///
+    /// A dropped `local.tee` is turned into a `local.set` instead.
+    pub(super) fn push_drop(&mut self) {
+        if self.bytecode_optimizations {
+            match self.inst_builder.last_insts::<1>() {
+                Some([Instruction::Const(_)])
+                | Some([Instruction::GetLocal { .. }])
+                | Some([Instruction::GetGlobal(_)]) => {
+                    self.inst_builder.remove_last(1);
+                    return;
+                }
+                Some([Instruction::TeeLocal { local_depth }]) => {
+                    // The depth of `local.set` is relative to the stack height after
+                    // popping the value whereas `local.tee` keeps it on the stack.
+                    let local_depth = local_depth.into_inner() - 1;
+                    self.inst_builder
+                        .replace_last(1, Instruction::local_set(local_depth));
+                    return;
+                }
+                _ => {}
+            }
+        }
+        self.inst_builder.push_inst(Instruction::Drop);
+    }
+
+    /// Returns the constructor of the conditional branch of a `br_if` or `if`.
+    ///
+    /// # Note
+    ///
+    /// - A `br_if` branches if its condition is non-zero whereas an `if` branches
+    ///   to its `else` block if its condition is zero which is requested via `negate`.
+    /// - A preceding `i32.eqz` or `i32` comparison is fused into the returned
+    ///   branch instruction and therefore removed.
+    pub(super) fn fuse_branch_condition(&mut self, negate: bool) -> MakeBranch {
+        let fused = match self.inst_builder.last_insts::<1>() {
+            Some([condition]) if self.bytecode_optimizations => fuse_branch(*condition),
+            _ => None,
+        };
+        let (make_br, make_negated): (MakeBranch, MakeBranch) = match fused {
+            Some(fused) => {
+                self.inst_builder.remove_last(1);
+                fused
+            }
+            None => (Instruction::BrIfNez, Instruction::BrIfEqz),
+        };
+        if negate {
+            make_negated
+        } else {
+            make_br
+        }
+    }
+}
+
+/// Returns the conditional branches that fuse the `condition` instruction.
+///
+/// The second branch of the returned pair branches if the `condition` does not hold.
+///
+/// Returns `None` if `condition` cannot be fused into a conditional branch.
+fn fuse_branch(condition: Instruction) -> Option<(MakeBranch, MakeBranch)> {
+    let fused: (MakeBranch, MakeBranch) = match condition {
+        Instruction::I32Eqz => (Instruction::BrIfEqz, Instruction::BrIfNez),
+        Instruction::I32Eq => (Instruction::BrIfI32Eq, Instruction::BrIfI32Ne),
+        Instruction::I32Ne => (Instruction::BrIfI32Ne, Instruction::BrIfI32Eq),
+        Instruction::I32LtS => (Instruction::BrIfI32LtS, Instruction::BrIfI32GeS),
+        Instruction::I32LtU => (Instruction::BrIfI32LtU, Instruction::BrIfI32GeU),
+        Instruction::I32GtS => (Instruction::BrIfI32GtS, Instruction::BrIfI32LeS),
+        Instruction::I32GtU => (Instruction::BrIfI32GtU, Instruction::BrIfI32LeU),
+        Instruction::I32LeS => (Instruction::BrIfI32LeS, Instruction::BrIfI32GtS),
+        Instruction::I32LeU => (Instruction::BrIfI32LeU, Instruction::BrIfI32GtU),
+        Instruction::I32GeS => (Instruction::BrIfI32GeS, Instruction::BrIfI32LtS),
+        Instruction::I32GeU => (Instruction::BrIfI32GeU, Instruction::BrIfI32LtU),
+        _ => return None,
+    };
+    Some(fused)
+}
+
+/// Returns the superinstruction that adds the constant `rhs` for the binary `inst`.
+fn add_imm(inst: Instruction, rhs: UntypedValue) -> Option<Instruction> {
+    let fused = match inst {
+        Instruction::I32Add => Instruction::I32AddImm(i32::from(rhs)),
+        Instruction::I32Sub => Instruction::I32AddImm(i32::from(rhs).wrapping_neg()),
+        Instruction::I64Add => Instruction::I64AddImm(i64::from(rhs)),
+        Instruction::I64Sub => Instruction::I64AddImm(i64::from(rhs).wrapping_neg()),
+        _ => return None,
+    };
+    Some(fused)
+}
+
+/// Returns the operation to fold the unary `inst` applied to a constant.
+///
+/// Returns `None` if `inst` cannot be folded.
+fn fold_unary(inst: Instruction) -> Option<UnaryOp> {
+    let f: UnaryOp = match inst {
+        Instruction::I32Eqz => UntypedValue::i32_eqz,
+        Instruction::I64Eqz => UntypedValue::i64_eqz,
+        Instruction::I32Clz => UntypedValue::i32_clz,
+        Instruction::I32Ctz => UntypedValue::i32_ctz,
+        Instruction::I32Popcnt => UntypedValue::i32_popcnt,
+        Instruction::I64Clz => UntypedValue::i64_clz,
+        Instruction::I64Ctz => UntypedValue::i64_ctz,
+        Instruction::I64Popcnt => UntypedValue::i64_popcnt,
+        Instruction::I32WrapI64 => UntypedValue::i32_wrap_i64,
+        Instruction::I64ExtendSI32 => UntypedValue::i64_extend_i32_s,
+        Instruction::I64ExtendUI32 => UntypedValue::i64_extend_i32_u,
+        Instruction::I32Extend8S => UntypedValue::i32_extend8_s,
+        Instruction::I32Extend16S => UntypedValue::i32_extend16_s,
+        Instruction::I64Extend8S => UntypedValue::i64_extend8_s,
+        Instruction::I64Extend16S => UntypedValue::i64_extend16_s,
+        Instruction::I64Extend32S => UntypedValue::i64_extend32_s,
+        _ => return None,
+    };
+    Some(f)
+}
+
+/// Returns the operation to fold the binary `inst` applied to two constants.
+///
+/// Returns `None` if `inst` cannot be folded.
+///
+/// # Note
+///
+/// Binary instructions that may trap, e.g. `i32.div_s`, as well as
+/// floating point instructions are never folded.
+fn fold_binary(inst: Instruction) -> Option<BinaryOp> {
+    let f: BinaryOp = match inst {
+        Instruction::I32Eq => UntypedValue::i32_eq,
+        Instruction::I32Ne => UntypedValue::i32_ne,
+        Instruction::I32LtS => UntypedValue::i32_lt_s,
+        Instruction::I32LtU => UntypedValue::i32_lt_u,
+        Instruction::I32GtS => UntypedValue::i32_gt_s,
+        Instruction::I32GtU => UntypedValue::i32_gt_u,
+        Instruction::I32LeS => UntypedValue::i32_le_s,
+        Instruction::I32LeU => UntypedValue::i32_le_u,
+        Instruction::I32GeS => UntypedValue::i32_ge_s,
+        Instruction::I32GeU => UntypedValue::i32_ge_u,
+        Instruction::I64Eq => UntypedValue::i64_eq,
+        Instruction::I64Ne => UntypedValue::i64_ne,
+        Instruction::I64LtS => UntypedValue::i64_lt_s,
+        Instruction::I64LtU => UntypedValue::i64_lt_u,
+        Instruction::I64GtS => UntypedValue::i64_gt_s,
+        Instruction::I64GtU => UntypedValue::i64_gt_u,
+        Instruction::I64LeS => UntypedValue::i64_le_s,
+        Instruction::I64LeU => UntypedValue::i64_le_u,
+        Instruction::I64GeS => UntypedValue::i64_ge_s,
+        Instruction::I64GeU => UntypedValue::i64_ge_u,
+        Instruction::I32Add => UntypedValue::i32_add,
+        Instruction::I32Sub => UntypedValue::i32_sub,
+        Instruction::I32Mul => UntypedValue::i32_mul,
+        Instruction::I32And => UntypedValue::i32_and,
+        Instruction::I32Or => UntypedValue::i32_or,
+        Instruction::I32Xor => UntypedValue::i32_xor,
+        Instruction::I32Shl => UntypedValue::i32_shl,
+        Instruction::I32ShrS => UntypedValue::i32_shr_s,
+        Instruction::I32ShrU => UntypedValue::i32_shr_u,
+        Instruction::I32Rotl => UntypedValue::i32_rotl,
+        Instruction::I32Rotr => UntypedValue::i32_rotr,
+        Instruction::I64Add => UntypedValue::i64_add,
+        Instruction::I64Sub => UntypedValue::i64_sub,
+        Instruction::I64Mul => UntypedValue::i64_mul,
+        Instruction::I64And => UntypedValue::i64_and,
+        Instruction::I64Or => UntypedValue::i64_or,
+        Instruction::I64Xor => UntypedValue::i64_xor,
+        Instruction::I64Shl => UntypedValue::i64_shl,
+        Instruction::I64ShrS => UntypedValue::i64_shr_s,
+        Instruction::I64ShrU => UntypedValue::i64_shr_u,
+        Instruction::I64Rotl => UntypedValue::i64_rotl,
+        Instruction::I64Rotr => UntypedValue::i64_rotr,
+        _ => return None,
+    };
+    Some(f)
+}
@@ -0,0 +1,227 @@
+use super::FunctionBuilder;
+use crate::{
+    engine::{
+        bytecode::{self, SimdInstruction, SimdOffset},
+        Instruction,
+    },
+    module::MemoryIdx,
+    ModuleError,
+};
+use wasmi_core::{ValueType, V128};
+
+impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
+    /// Translate a Wasm `v128.const` instruction.
+    ///
+    /// # Note
+    ///
+    /// The `v128` constant is encoded as two 64-bit constants that
+    /// are combined by the [`SimdInstruction::V128Const`] instruction.
+    pub fn translate_v128_const(&mut self, value: V128) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let bits = value.to_bits();
+            builder.value_stack.push(ValueType::I64);
+            builder.value_stack.push(ValueType::I64);
+            builder.value_stack.pop2();
+            builder.value_stack.push(ValueType::V128);
+            builder
+                .inst_builder
+                .push_inst(Instruction::Const(bits as u64));
+            builder
+                .inst_builder
+                .push_inst(Instruction::Const((bits >> 64) as u64));
+            builder
+                .inst_builder
+                .push_inst(Instruction::Simd(SimdInstruction::V128Const));
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm `i8x16.shuffle` instruction.
+    ///
+    /// # Note
+    ///
+    /// The shuffle `lanes` are encoded as a `v128` constant operand.
+    pub fn translate_i8x16_shuffle(&mut self, lanes: [u8; 16]) -> Result<(), ModuleError> {
+        self.translate_v128_const(V128::from_bits(u128::from_le_bytes(lanes)))?;
+        self.translate_simd_ternary(SimdInstruction::I8x16Shuffle)
+    }
+
+    /// Translate a Wasm `v128` load instruction.
+    ///
+    /// # Note
+    ///
+    /// This is used to translate all Wasm `v128.load*` instructions
+    /// except for the ones loading into a single lane.
+    pub fn translate_v128_load(
+        &mut self,
+        memory_idx: MemoryIdx,
+        offset: u32,
+        make_inst: fn(bytecode::MemoryIdx, SimdOffset) -> SimdInstruction,
+    ) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let pointer = builder.value_stack.pop1();
+            debug_assert_eq!(pointer, builder.memory_index_type(memory_idx));
+            builder.value_stack.push(ValueType::V128);
+            let offset = SimdOffset::from(offset);
+            builder.inst_builder.push_inst(Instruction::Simd(make_inst(
+                memory_idx.into_u32().into(),
+                offset,
+            )));
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm `v128.store` instruction.
+    pub fn translate_v128_store(
+        &mut self,
+        memory_idx: MemoryIdx,
+        offset: u32,
+        make_inst: fn(bytecode::MemoryIdx, SimdOffset) -> SimdInstruction,
+    ) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let (pointer, stored) = builder.value_stack.pop2();
+            debug_assert_eq!(pointer, builder.memory_index_type(memory_idx));
+            debug_assert_eq!(stored, ValueType::V128);
+            let offset = SimdOffset::from(offset);
+            builder.inst_builder.push_inst(Instruction::Simd(make_inst(
+                memory_idx.into_u32().into(),
+                offset,
+            )));
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm `v128.load*_lane` instruction.
+    pub fn translate_v128_load_lane(
+        &mut self,
+        memory_idx: MemoryIdx,
+        offset: u32,
+        lane: u8,
+        make_inst: fn(bytecode::MemoryIdx, SimdOffset, u8) -> SimdInstruction,
+    ) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let (pointer, vector) = builder.value_stack.pop2();
+            debug_assert_eq!(pointer, builder.memory_index_type(memory_idx));
+            debug_assert_eq!(vector, ValueType::V128);
+            builder.value_stack.push(ValueType::V128);
+            let offset = SimdOffset::from(offset);
+            builder.inst_builder.push_inst(Instruction::Simd(make_inst(
+                memory_idx.into_u32().into(),
+                offset,
+                lane,
+            )));
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm `v128.store*_lane` instruction.
+    pub fn translate_v128_store_lane(
+        &mut self,
+        memory_idx: MemoryIdx,
+        offset: u32,
+        lane: u8,
+        make_inst: fn(bytecode::MemoryIdx, SimdOffset, u8) -> SimdInstruction,
+    ) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let (pointer, vector) = builder.value_stack.pop2();
+            debug_assert_eq!(pointer, builder.memory_index_type(memory_idx));
+            debug_assert_eq!(vector, ValueType::V128);
+            let offset = SimdOffset::from(offset);
+            builder.inst_builder.push_inst(Instruction::Simd(make_inst(
+                memory_idx.into_u32().into(),
+                offset,
+                lane,
+            )));
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm SIMD instruction converting between `v128` and a scalar type.
+    ///
+    /// # Note
+    ///
+    /// This is used to translate the following Wasm instructions:
+    ///
+    /// - `{i8x16, i16x8, i32x4, i64x2, f32x4, f64x2}.splat`
+    /// - `{i8x16, i16x8, i32x4, i64x2, f32x4, f64x2}.extract_lane*`
+    /// - `{i8x16, i16x8, i32x4, i64x2}.{all_true, bitmask}`
+    /// - `v128.any_true`
+    pub fn translate_simd_conversion(
+        &mut self,
+        input_type: ValueType,
+        output_type: ValueType,
+        inst: SimdInstruction,
+    ) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let input = builder.value_stack.pop1();
+            debug_assert_eq!(input, input_type);
+            builder.value_stack.push(output_type);
+            builder.inst_builder.push_inst(Instruction::Simd(inst));
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm SIMD instruction with a `v128` and a scalar operand.
+    ///
+    /// # Note
+    ///
+    /// This is used to translate the following Wasm instructions:
+    ///
+    /// - `{i8x16, i16x8, i32x4, i64x2, f32x4, f64x2}.replace_lane`
+    /// - `{i8x16, i16x8, i32x4, i64x2}.{shl, shr_s, shr_u}`
+    pub fn translate_simd_vector_scalar(
+        &mut self,
+        scalar_type: ValueType,
+        inst: SimdInstruction,
+    ) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let (vector, scalar) = builder.value_stack.pop2();
+            debug_assert_eq!(vector, ValueType::V128);
+            debug_assert_eq!(scalar, scalar_type);
+            builder.value_stack.push(ValueType::V128);
+            builder.inst_builder.push_inst(Instruction::Simd(inst));
+            Ok(())
+        })
+    }
+
+    /// Translate a unary Wasm SIMD instruction operating on `v128` values.
+    pub fn translate_simd_unary(&mut self, inst: SimdInstruction) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            debug_assert_eq!(builder.value_stack.top(), ValueType::V128);
+            builder.inst_builder.push_inst(Instruction::Simd(inst));
+            Ok(())
+        })
+    }
+
+    /// Translate a binary Wasm SIMD instruction operating on `v128` values.
+    pub fn translate_simd_binary(&mut self, inst: SimdInstruction) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let (v0, v1) = builder.value_stack.pop2();
+            debug_assert_eq!(v0, ValueType::V128);
+            debug_assert_eq!(v1, ValueType::V128);
+            builder.value_stack.push(ValueType::V128);
+            builder.inst_builder.push_inst(Instruction::Simd(inst));
+            Ok(())
+        })
+    }
+
+    /// Translate a ternary Wasm SIMD instruction operating on `v128` values.
+    ///
+    /// # Note
+    ///
+    /// This is used to translate the following Wasm instructions:
+    ///
+    /// - `v128.bitselect`
+    /// - `i8x16.shuffle` with its lanes as third operand
+    pub fn translate_simd_ternary(&mut self, inst: SimdInstruction) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let (v0, v1, v2) = builder.value_stack.pop3();
+            debug_assert_eq!(v0, ValueType::V128);
+            debug_assert_eq!(v1, ValueType::V128);
+            debug_assert_eq!(v2, ValueType::V128);
+            builder.value_stack.push(ValueType::V128);
+            builder.inst_builder.push_inst(Instruction::Simd(inst));
+            Ok(())
+        })
+    }
+}
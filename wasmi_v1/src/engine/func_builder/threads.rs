@@ -0,0 +1,159 @@
+use super::FunctionBuilder;
+use crate::{
+    engine::{
+        bytecode::{self, AtomicInstruction, AtomicOffset},
+        Instruction,
+    },
+    module::MemoryIdx,
+    ModuleError,
+};
+use wasmi_core::ValueType;
+
+/// Constructs an [`AtomicInstruction`] accessing a linear memory.
+type MakeAtomic = fn(bytecode::MemoryIdx, AtomicOffset) -> AtomicInstruction;
+
+impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
+    /// Pushes the atomic instruction accessing the linear memory at `memory_idx`.
+    fn push_atomic_inst(&mut self, memory_idx: MemoryIdx, offset: u32, make_inst: MakeAtomic) {
+        let offset = AtomicOffset::from(offset);
+        self.inst_builder.push_inst(Instruction::Atomic(make_inst(
+            memory_idx.into_u32().into(),
+            offset,
+        )));
+    }
+
+    /// Translate a Wasm `atomic.load` instruction.
+    ///
+    /// # Note
+    ///
+    /// This is used to translate all Wasm `{i32, i64}.atomic.load*` instructions.
+    pub fn translate_atomic_load(
+        &mut self,
+        memory_idx: MemoryIdx,
+        offset: u32,
+        loaded_type: ValueType,
+        make_inst: MakeAtomic,
+    ) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let pointer = builder.value_stack.pop1();
+            debug_assert_eq!(pointer, builder.memory_index_type(memory_idx));
+            builder.value_stack.push(loaded_type);
+            builder.push_atomic_inst(memory_idx, offset, make_inst);
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm `atomic.store` instruction.
+    ///
+    /// # Note
+    ///
+    /// This is used to translate all Wasm `{i32, i64}.atomic.store*` instructions.
+    pub fn translate_atomic_store(
+        &mut self,
+        memory_idx: MemoryIdx,
+        offset: u32,
+        stored_type: ValueType,
+        make_inst: MakeAtomic,
+    ) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let (pointer, stored) = builder.value_stack.pop2();
+            debug_assert_eq!(pointer, builder.memory_index_type(memory_idx));
+            debug_assert_eq!(stored, stored_type);
+            builder.push_atomic_inst(memory_idx, offset, make_inst);
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm `atomic.rmw` instruction.
+    ///
+    /// # Note
+    ///
+    /// This is used to translate all Wasm `{i32, i64}.atomic.rmw*` instructions
+    /// except for the `cmpxchg` instructions.
+    pub fn translate_atomic_rmw(
+        &mut self,
+        memory_idx: MemoryIdx,
+        offset: u32,
+        value_type: ValueType,
+        make_inst: MakeAtomic,
+    ) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let (pointer, value) = builder.value_stack.pop2();
+            debug_assert_eq!(pointer, builder.memory_index_type(memory_idx));
+            debug_assert_eq!(value, value_type);
+            builder.value_stack.push(value_type);
+            builder.push_atomic_inst(memory_idx, offset, make_inst);
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm `atomic.rmw.cmpxchg` instruction.
+    ///
+    /// # Note
+    ///
+    /// This is used to translate all Wasm `{i32, i64}.atomic.rmw*.cmpxchg*` instructions.
+    pub fn translate_atomic_cmpxchg(
+        &mut self,
+        memory_idx: MemoryIdx,
+        offset: u32,
+        value_type: ValueType,
+        make_inst: MakeAtomic,
+    ) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let (pointer, expected, replacement) = builder.value_stack.pop3();
+            debug_assert_eq!(pointer, builder.memory_index_type(memory_idx));
+            debug_assert_eq!(expected, value_type);
+            debug_assert_eq!(replacement, value_type);
+            builder.value_stack.push(value_type);
+            builder.push_atomic_inst(memory_idx, offset, make_inst);
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm `memory.atomic.notify` instruction.
+    pub fn translate_atomic_notify(
+        &mut self,
+        memory_idx: MemoryIdx,
+        offset: u32,
+    ) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let (pointer, count) = builder.value_stack.pop2();
+            debug_assert_eq!(pointer, builder.memory_index_type(memory_idx));
+            debug_assert_eq!(count, ValueType::I32);
+            builder.value_stack.push(ValueType::I32);
+            builder.push_atomic_inst(memory_idx, offset, |memory, offset| {
+                AtomicInstruction::MemoryAtomicNotify { memory, offset }
+            });
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm `memory.atomic.wait{32, 64}` instruction.
+    pub fn translate_atomic_wait(
+        &mut self,
+        memory_idx: MemoryIdx,
+        offset: u32,
+        expected_type: ValueType,
+        make_inst: MakeAtomic,
+    ) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let (pointer, expected, timeout) = builder.value_stack.pop3();
+            debug_assert_eq!(pointer, builder.memory_index_type(memory_idx));
+            debug_assert_eq!(expected, expected_type);
+            debug_assert_eq!(timeout, ValueType::I64);
+            builder.value_stack.push(ValueType::I32);
+            builder.push_atomic_inst(memory_idx, offset, make_inst);
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm `atomic.fence` instruction.
+    pub fn translate_atomic_fence(&mut self) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            builder
+                .inst_builder
+                .push_inst(Instruction::Atomic(AtomicInstruction::AtomicFence));
+            Ok(())
+        })
+    }
+}
@@ -36,7 +36,7 @@ impl ValueStack {
     /// to the stack since we are just emulating the Wasm [`ValueStack`] during
     /// translation from Wasm bytecode to `wasmi` bytecode.
     ///
-    /// [`Value`]: [`wasmi_core::Value`]
+    /// [`Value`]: [`crate::Value`]
     pub fn push(&mut self, value_type: ValueType) {
         self.values.push(value_type);
         self.update_max_height();
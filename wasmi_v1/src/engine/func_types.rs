@@ -2,7 +2,7 @@ use wasmi_core::ValueType;
 
 use super::{EngineIdx, Guarded};
 use crate::{
-    arena::{DedupArena, GuardedEntity},
+    arena::{ArenaIndex, DedupArena, GuardedEntity},
     FuncType,
     Index,
     Store,
@@ -10,15 +10,15 @@ use crate::{
 
 /// A raw index to a function signature entity.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub struct DedupFuncTypeIdx(usize);
+pub struct DedupFuncTypeIdx(u64);
 
-impl Index for DedupFuncTypeIdx {
-    fn into_usize(self) -> usize {
+impl ArenaIndex for DedupFuncTypeIdx {
+    fn into_key(self) -> u64 {
         self.0
     }
 
-    fn from_usize(value: usize) -> Self {
-        Self(value)
+    fn from_key(key: u64) -> Self {
+        Self(key)
     }
 }
 
@@ -105,7 +105,7 @@ impl FuncTypeRegistry {
     /// If the guarded entity is not owned by the engine.
     fn unwrap_index<Idx>(&self, func_type: Guarded<Idx>) -> Idx
     where
-        Idx: Index,
+        Idx: Copy,
     {
         func_type.entity_index(self.engine_idx).unwrap_or_else(|| {
             panic!(
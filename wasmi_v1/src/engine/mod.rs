@@ -3,41 +3,49 @@
 pub mod bytecode;
 pub mod call_stack;
 pub mod code_map;
+mod exception_handler;
 pub mod exec_context;
+mod executor;
 mod func_args;
 mod func_builder;
 mod func_types;
+mod resumable;
+mod stack;
 mod traits;
 pub mod value_stack;
 
-pub(crate) use self::func_args::{FuncParams, FuncResults};
 pub use self::{
     bytecode::{DropKeep, Target},
-    code_map::FuncBody,
+    code_map::{FuncBodies, FuncBody},
     func_builder::{FunctionBuilder, InstructionIdx, LabelIdx, RelativeDepth, Reloc},
+    resumable::{ResumableCall, ResumableInvocation},
     traits::{CallParams, CallResults},
 };
 use self::{
-    bytecode::{Instruction, VisitInstruction},
+    bytecode::{ExceptionSlot, Instruction, TagIdx, VisitInstruction},
     call_stack::{CallStack, FunctionFrame},
-    code_map::{CodeMap, ResolvedFuncBody},
+    code_map::{CodeMap, FuncBodyEntity, ResolvedFuncBody},
+    exception_handler::{find_catch_target, ExceptionHandler},
     exec_context::ExecutionContext,
+    executor::{EngineExecutor, TaggedTrap},
     func_types::FuncTypeRegistry,
+    stack::{Stack, StackPool},
     value_stack::ValueStack,
 };
-use super::{func::FuncEntityInternal, AsContext, AsContextMut, Func};
+pub(crate) use self::{
+    func_args::{FuncParams, FuncResults},
+    resumable::ResumableCallBase,
+    stack::CallDepth,
+};
+use super::{AsContextMut, Func};
 use crate::{
     arena::{GuardedEntity, Index},
     core::Trap,
-    func::HostFuncEntity,
+    module::{Decode, DeserializeError, Reader, Writer},
     FuncType,
-    Instance,
-};
-use alloc::sync::Arc;
-use core::{
-    cmp,
-    sync::atomic::{AtomicUsize, Ordering},
 };
+use alloc::{sync::Arc, vec::Vec};
+use core::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 pub use func_types::DedupFuncType;
 use spin::mutex::Mutex;
 
@@ -47,6 +55,13 @@ pub const DEFAULT_VALUE_STACK_LIMIT: usize = 1024 * 1024;
 /// Maximum number of levels on the call stack.
 pub const DEFAULT_CALL_STACK_LIMIT: usize = 64 * 1024;
 
+/// Maximum number of executions nested in each other.
+///
+/// # Note
+///
+/// Executions are nested if host functions call back into Wasm.
+pub const DEFAULT_NESTED_EXECUTION_LIMIT: usize = 64;
+
 /// The outcome of a `wasmi` function execution.
 #[derive(Debug, Copy, Clone)]
 pub enum FunctionExecutionOutcome {
@@ -54,6 +69,24 @@ pub enum FunctionExecutionOutcome {
     Return,
     /// The function called another function.
     NestedCall(Func),
+    /// The function tail called another function.
+    ///
+    /// # Note
+    ///
+    /// The tail called function replaces the function frame of its caller.
+    TailCall(Func),
+    /// The function threw a new exception with the tag.
+    Throw(TagIdx),
+    /// The function rethrew the exception caught at the slot.
+    Rethrow(ExceptionSlot),
+    /// The function reached the epoch deadline of the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// The function continues its execution if the deadline is extended.
+    ///
+    /// [`Store`]: [`crate::Store`]
+    EpochDeadline,
 }
 
 /// A unique engine index.
@@ -97,6 +130,13 @@ type Guarded<Idx> = GuardedEntity<EngineIdx, Idx>;
 #[derive(Debug, Clone)]
 pub struct Engine {
     inner: Arc<Mutex<EngineInner>>,
+    /// The current epoch used for epoch based interruption.
+    ///
+    /// # Note
+    ///
+    /// This is stored outside of the [`EngineInner`] so that it can
+    /// be incremented while the [`Engine`] is executing Wasm code.
+    epoch: Arc<AtomicU64>,
 }
 
 /// Configuration for an [`Engine`].
@@ -115,7 +155,18 @@ pub struct Config {
     ///
     /// Reaching this limit during execution of a Wasm function will
     /// cause a stack overflow trap.
+    ///
+    /// The function frames of all executions that an execution is nested
+    /// in count towards the call stack limit of the nested execution.
     call_stack_limit: usize,
+    /// The limit of executions nested in each other.
+    ///
+    /// # Note
+    ///
+    /// Host functions that call back into Wasm start a new execution nested
+    /// in the execution that called them. Reaching this limit will cause a
+    /// stack overflow trap since every nested execution consumes native stack space.
+    nested_execution_limit: usize,
     /// Is `true` if the [`mutable-global`] Wasm proposal is enabled.
     ///
     /// # Note
@@ -148,6 +199,147 @@ pub struct Config {
     ///
     /// [`multi-value`]: https://github.com/WebAssembly/multi-value
     multi_value: bool,
+    /// Is `true` if the [`bulk-memory`] Wasm proposal is enabled.
+    ///
+    /// # Note
+    ///
+    /// Disabled by default.
+    ///
+    /// [`bulk-memory`]: https://github.com/WebAssembly/bulk-memory-operations
+    bulk_memory: bool,
+    /// Is `true` if the [`reference-types`] Wasm proposal is enabled.
+    ///
+    /// # Note
+    ///
+    /// Disabled by default.
+    ///
+    /// [`reference-types`]: https://github.com/WebAssembly/reference-types
+    reference_types: bool,
+    /// Is `true` if the [`tail-call`] Wasm proposal is enabled.
+    ///
+    /// # Note
+    ///
+    /// Disabled by default.
+    ///
+    /// Tail calls replace the function frame of their caller so that
+    /// arbitrarily deep tail recursion does not exhaust the call stack.
+    ///
+    /// [`tail-call`]: https://github.com/WebAssembly/tail-call
+    tail_call: bool,
+    /// Is `true` if the [`simd`] Wasm proposal is enabled.
+    ///
+    /// # Note
+    ///
+    /// Disabled by default.
+    ///
+    /// Requires the `simd` crate feature of `wasmi` since it widens
+    /// the internal value representation to 128 bits.
+    ///
+    /// [`simd`]: https://github.com/WebAssembly/simd
+    simd: bool,
+    /// Is `true` if the [`multi-memory`] Wasm proposal is enabled.
+    ///
+    /// # Note
+    ///
+    /// Disabled by default.
+    ///
+    /// [`multi-memory`]: https://github.com/WebAssembly/multi-memory
+    multi_memory: bool,
+    /// Is `true` if the [`memory64`] Wasm proposal is enabled.
+    ///
+    /// # Note
+    ///
+    /// Disabled by default.
+    ///
+    /// [`memory64`]: https://github.com/WebAssembly/memory64
+    memory64: bool,
+    /// Is `true` if the [`threads`] Wasm proposal is enabled.
+    ///
+    /// # Note
+    ///
+    /// Disabled by default.
+    ///
+    /// Requires the `threads` crate feature of `wasmi` which provides
+    /// shared linear memories and the parking of waiting threads.
+    ///
+    /// [`threads`]: https://github.com/WebAssembly/threads
+    threads: bool,
+    /// Is `true` if the [`exception-handling`] Wasm proposal is enabled.
+    ///
+    /// # Note
+    ///
+    /// Disabled by default.
+    ///
+    /// [`exception-handling`]: https://github.com/WebAssembly/exception-handling
+    exceptions: bool,
+    /// Is `true` if the [`extended-const`] Wasm proposal is enabled.
+    ///
+    /// # Note
+    ///
+    /// Disabled by default.
+    ///
+    /// [`extended-const`]: https://github.com/WebAssembly/extended-const
+    extended_const: bool,
+    /// Is `true` if Wasm executions consume fuel.
+    ///
+    /// # Note
+    ///
+    /// Disabled by default.
+    ///
+    /// Every executed `wasmi` bytecode instruction consumes one unit of
+    /// fuel from the [`Store`] and traps with [`TrapCode::OutOfFuel`]
+    /// once there is no fuel left.
+    ///
+    /// [`Store`]: [`crate::Store`]
+    /// [`TrapCode::OutOfFuel`]: [`crate::core::TrapCode::OutOfFuel`]
+    fuel_metering: bool,
+    /// Is `true` if epoch based interruption of Wasm executions is enabled.
+    ///
+    /// # Note
+    ///
+    /// Disabled by default.
+    ///
+    /// Wasm executions check the epoch deadline of their [`Store`] upon
+    /// function entry and loop back-edges and trap with
+    /// [`TrapCode::Interrupted`] once the deadline has been reached.
+    ///
+    /// [`Store`]: [`crate::Store`]
+    /// [`TrapCode::Interrupted`]: [`crate::core::TrapCode::Interrupted`]
+    epoch_interruption: bool,
+    /// Is `true` if Wasm backtraces are attached to traps of Wasm executions.
+    ///
+    /// # Note
+    ///
+    /// Disabled by default.
+    ///
+    /// Enabling Wasm backtraces additionally records the offsets of all
+    /// Wasm operators within the Wasm binary during compilation so that
+    /// trapping instructions can be mapped back to the original Wasm code.
+    wasm_backtrace: bool,
+    /// Is `true` if the translated `wasmi` bytecode is optimized.
+    ///
+    /// # Note
+    ///
+    /// Disabled by default.
+    ///
+    /// Common sequences of Wasm operators are fused into superinstructions,
+    /// constant expressions are folded and dead `drop` operators are removed.
+    /// Otherwise Wasm operators are translated one by one.
+    /// Ignored if [`Config::fuel_metering`] is enabled so that the consumed
+    /// fuel does not depend on the bytecode optimizations.
+    bytecode_optimizations: bool,
+    /// Is `true` if linear memories are initialized from memory images.
+    ///
+    /// # Note
+    ///
+    /// Enabled by default.
+    ///
+    /// The memory image of a linear memory is prepared upon the first
+    /// instantiation of its module and mapped copy-on-write into the linear
+    /// memories of all instances instead of copying their data segments.
+    /// This requires the `virtual_memory` crate feature on 64-bit Linux
+    /// and has no effect elsewhere.
+    memory_images: bool,
 }
 
 impl Default for Config {
@@ -155,10 +347,25 @@ impl Default for Config {
         Self {
             value_stack_limit: DEFAULT_VALUE_STACK_LIMIT,
             call_stack_limit: DEFAULT_CALL_STACK_LIMIT,
+            nested_execution_limit: DEFAULT_NESTED_EXECUTION_LIMIT,
             mutable_global: true,
             sign_extension: true,
             saturating_float_to_int: true,
             multi_value: true,
+            bulk_memory: false,
+            reference_types: false,
+            tail_call: false,
+            simd: false,
+            multi_memory: false,
+            memory64: false,
+            threads: false,
+            exceptions: false,
+            extended_const: false,
+            fuel_metering: false,
+            epoch_interruption: false,
+            wasm_backtrace: false,
+            bytecode_optimizations: false,
+            memory_images: true,
         }
     }
 }
@@ -173,10 +380,25 @@ impl Config {
         Self {
             value_stack_limit: DEFAULT_VALUE_STACK_LIMIT,
             call_stack_limit: DEFAULT_CALL_STACK_LIMIT,
+            nested_execution_limit: DEFAULT_NESTED_EXECUTION_LIMIT,
             mutable_global: false,
             sign_extension: false,
             saturating_float_to_int: false,
             multi_value: false,
+            bulk_memory: false,
+            reference_types: false,
+            tail_call: false,
+            simd: false,
+            multi_memory: false,
+            memory64: false,
+            threads: false,
+            exceptions: false,
+            extended_const: false,
+            fuel_metering: false,
+            epoch_interruption: false,
+            wasm_backtrace: false,
+            bytecode_optimizations: false,
+            memory_images: true,
         }
     }
 
@@ -223,6 +445,216 @@ impl Config {
     pub const fn multi_value(&self) -> bool {
         self.multi_value
     }
+
+    /// Enables the `bulk-memory` Wasm proposal.
+    pub const fn enable_bulk_memory(mut self, enable: bool) -> Self {
+        self.bulk_memory = enable;
+        self
+    }
+
+    /// Returns `true` if the `bulk-memory` Wasm proposal is enabled.
+    pub const fn bulk_memory(&self) -> bool {
+        self.bulk_memory
+    }
+
+    /// Enables the `reference-types` Wasm proposal.
+    pub const fn enable_reference_types(mut self, enable: bool) -> Self {
+        self.reference_types = enable;
+        self
+    }
+
+    /// Returns `true` if the `reference-types` Wasm proposal is enabled.
+    pub const fn reference_types(&self) -> bool {
+        self.reference_types
+    }
+
+    /// Enables the `tail-call` Wasm proposal.
+    pub const fn enable_tail_call(mut self, enable: bool) -> Self {
+        self.tail_call = enable;
+        self
+    }
+
+    /// Returns `true` if the `tail-call` Wasm proposal is enabled.
+    pub const fn tail_call(&self) -> bool {
+        self.tail_call
+    }
+
+    /// Enables the `simd` Wasm proposal.
+    ///
+    /// # Note
+    ///
+    /// This has no effect unless the `simd` crate feature is enabled.
+    pub const fn enable_simd(mut self, enable: bool) -> Self {
+        self.simd = enable;
+        self
+    }
+
+    /// Returns `true` if the `simd` Wasm proposal is enabled.
+    ///
+    /// # Note
+    ///
+    /// This is always `false` if the `simd` crate feature is disabled.
+    pub const fn simd(&self) -> bool {
+        cfg!(feature = "simd") && self.simd
+    }
+
+    /// Enables the `multi-memory` Wasm proposal.
+    pub const fn enable_multi_memory(mut self, enable: bool) -> Self {
+        self.multi_memory = enable;
+        self
+    }
+
+    /// Returns `true` if the `multi-memory` Wasm proposal is enabled.
+    pub const fn multi_memory(&self) -> bool {
+        self.multi_memory
+    }
+
+    /// Enables the `memory64` Wasm proposal.
+    pub const fn enable_memory64(mut self, enable: bool) -> Self {
+        self.memory64 = enable;
+        self
+    }
+
+    /// Returns `true` if the `memory64` Wasm proposal is enabled.
+    pub const fn memory64(&self) -> bool {
+        self.memory64
+    }
+
+    /// Enables the `threads` Wasm proposal.
+    ///
+    /// # Note
+    ///
+    /// This has no effect unless the `threads` crate feature is enabled.
+    pub const fn enable_threads(mut self, enable: bool) -> Self {
+        self.threads = enable;
+        self
+    }
+
+    /// Returns `true` if the `threads` Wasm proposal is enabled.
+    ///
+    /// # Note
+    ///
+    /// This is always `false` if the `threads` crate feature is disabled.
+    pub const fn threads(&self) -> bool {
+        cfg!(feature = "threads") && self.threads
+    }
+
+    /// Enables the `exception-handling` Wasm proposal.
+    pub const fn enable_exceptions(mut self, enable: bool) -> Self {
+        self.exceptions = enable;
+        self
+    }
+
+    /// Returns `true` if the `exception-handling` Wasm proposal is enabled.
+    pub const fn exceptions(&self) -> bool {
+        self.exceptions
+    }
+
+    /// Enables the `extended-const` Wasm proposal.
+    pub const fn enable_extended_const(mut self, enable: bool) -> Self {
+        self.extended_const = enable;
+        self
+    }
+
+    /// Returns `true` if the `extended-const` Wasm proposal is enabled.
+    pub const fn extended_const(&self) -> bool {
+        self.extended_const
+    }
+
+    /// Enables fuel metering for Wasm executions.
+    ///
+    /// # Note
+    ///
+    /// The fuel of a [`Store`] is managed via [`Store::add_fuel`].
+    ///
+    /// [`Store`]: [`crate::Store`]
+    /// [`Store::add_fuel`]: [`crate::Store::add_fuel`]
+    pub const fn enable_fuel_metering(mut self, enable: bool) -> Self {
+        self.fuel_metering = enable;
+        self
+    }
+
+    /// Returns `true` if fuel metering is enabled for Wasm executions.
+    pub const fn fuel_metering(&self) -> bool {
+        self.fuel_metering
+    }
+
+    /// Enables epoch based interruption of Wasm executions.
+    ///
+    /// # Note
+    ///
+    /// The epoch is advanced via [`Engine::increment_epoch`] and the
+    /// deadline of a [`Store`] is set via [`Store::set_epoch_deadline`].
+    ///
+    /// [`Store`]: [`crate::Store`]
+    /// [`Store::set_epoch_deadline`]: [`crate::Store::set_epoch_deadline`]
+    pub const fn enable_epoch_interruption(mut self, enable: bool) -> Self {
+        self.epoch_interruption = enable;
+        self
+    }
+
+    /// Returns `true` if epoch based interruption is enabled for Wasm executions.
+    pub const fn epoch_interruption(&self) -> bool {
+        self.epoch_interruption
+    }
+
+    /// Enables Wasm backtraces for traps of Wasm executions.
+    ///
+    /// # Note
+    ///
+    /// Traps originating from Wasm executions are returned as [`Trap::WithBacktrace`]
+    /// if enabled. Use [`Trap::code`] to inspect the underlying [`TrapCode`].
+    ///
+    /// [`Trap::WithBacktrace`]: [`crate::core::Trap::WithBacktrace`]
+    /// [`Trap::code`]: [`crate::core::Trap::code`]
+    /// [`TrapCode`]: [`crate::core::TrapCode`]
+    pub const fn enable_wasm_backtrace(mut self, enable: bool) -> Self {
+        self.wasm_backtrace = enable;
+        self
+    }
+
+    /// Returns `true` if Wasm backtraces are enabled for traps of Wasm executions.
+    pub const fn wasm_backtrace(&self) -> bool {
+        self.wasm_backtrace
+    }
+
+    /// Enables optimizations of the translated `wasmi` bytecode.
+    ///
+    /// # Note
+    ///
+    /// This does not affect the semantics of Wasm executions. Bytecode optimizations
+    /// are not applied if fuel metering is enabled since every executed instruction
+    /// consumes one unit of fuel and optimized bytecode executes fewer instructions.
+    pub const fn enable_bytecode_optimizations(mut self, enable: bool) -> Self {
+        self.bytecode_optimizations = enable;
+        self
+    }
+
+    /// Returns `true` if the translated `wasmi` bytecode is optimized.
+    ///
+    /// # Note
+    ///
+    /// Returns `false` if fuel metering is enabled.
+    pub const fn bytecode_optimizations(&self) -> bool {
+        self.bytecode_optimizations && !self.fuel_metering
+    }
+
+    /// Enables the initialization of linear memories from memory images.
+    ///
+    /// # Note
+    ///
+    /// Every memory image holds a file descriptor for as long as its module
+    /// is alive. Disabling memory images initializes linear memories by
+    /// copying the bytes of their data segments upon every instantiation.
+    pub const fn enable_memory_images(mut self, enable: bool) -> Self {
+        self.memory_images = enable;
+        self
+    }
+
+    /// Returns `true` if linear memories are initialized from memory images.
+    pub const fn memory_images(&self) -> bool {
+        self.memory_images
+    }
 }
 
 impl Default for Engine {
@@ -240,9 +672,29 @@ impl Engine {
     pub fn new(config: &Config) -> Self {
         Self {
             inner: Arc::new(Mutex::new(EngineInner::new(config))),
+            epoch: Arc::new(AtomicU64::new(0)),
         }
     }
 
+    /// Increments the epoch of the [`Engine`] by one.
+    ///
+    /// # Note
+    ///
+    /// - This does not lock the [`Engine`] and therefore may be called
+    ///   from any thread while Wasm code is executing, e.g. from a timer.
+    /// - Executions of [`Store`] instances whose epoch deadline has been
+    ///   reached are interrupted if epoch interruption is enabled.
+    ///
+    /// [`Store`]: [`crate::Store`]
+    pub fn increment_epoch(&self) {
+        self.epoch.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the current epoch of the [`Engine`].
+    pub(crate) fn current_epoch(&self) -> u64 {
+        self.epoch.load(Ordering::Relaxed)
+    }
+
     /// Returns a shared reference to the [`Config`] of the [`Engine`].
     pub fn config(&self) -> Config {
         *self.inner.lock().config()
@@ -270,19 +722,71 @@ impl Engine {
     /// Allocates the instructions of a Wasm function body to the [`Engine`].
     ///
     /// Returns a [`FuncBody`] reference to the allocated function body.
-    pub(super) fn alloc_func_body<I>(
+    pub(super) fn alloc_func_body<I, H, O>(
         &self,
         len_locals: usize,
         max_stack_height: usize,
         insts: I,
+        handlers: H,
+        offsets: O,
     ) -> FuncBody
     where
         I: IntoIterator<Item = Instruction>,
         I::IntoIter: ExactSizeIterator,
+        H: IntoIterator<Item = ExceptionHandler>,
+        O: IntoIterator<Item = usize>,
     {
         self.inner
             .lock()
-            .alloc_func_body(len_locals, max_stack_height, insts)
+            .alloc_func_body(len_locals, max_stack_height, insts, handlers, offsets)
+    }
+
+    /// Frees the Wasm function bodies from the [`Engine`].
+    ///
+    /// # Note
+    ///
+    /// Live or suspended executions of the freed function bodies are unaffected
+    /// since their call frames share the function bodies they are executing.
+    pub(super) fn free_func_bodies(&self, func_bodies: &[FuncBody]) {
+        self.inner.lock().free_func_bodies(func_bodies)
+    }
+
+    /// Resolves the [`FuncBody`] to the shared Wasm function body.
+    ///
+    /// # Note
+    ///
+    /// The [`Engine`] is only locked for the look-up so that the returned
+    /// function body can be executed while the [`Engine`] is used elsewhere.
+    ///
+    /// # Panics
+    ///
+    /// If the [`FuncBody`] is invalid for the [`Engine`].
+    fn resolve_func_body(&self, func_body: FuncBody) -> Arc<FuncBodyEntity> {
+        self.inner.lock().code_map.get(func_body).clone()
+    }
+
+    /// Encodes the Wasm function body into the `writer`.
+    ///
+    /// # Panics
+    ///
+    /// If the [`FuncBody`] is invalid for the [`Engine`].
+    pub(crate) fn encode_func_body(&self, func_body: FuncBody, writer: &mut Writer) {
+        self.resolve_func_body(func_body).encode(writer)
+    }
+
+    /// Decodes a Wasm function body encoded by [`Engine::encode_func_body`] from the `reader`.
+    ///
+    /// Returns a [`FuncBody`] reference to the allocated function body.
+    pub(crate) fn decode_func_body(
+        &self,
+        reader: &mut Reader,
+    ) -> Result<FuncBody, DeserializeError> {
+        let len_locals = usize::decode(reader)?;
+        let max_stack_height = usize::decode(reader)?;
+        let insts = Vec::<Instruction>::decode(reader)?;
+        let handlers = Vec::<ExceptionHandler>::decode(reader)?;
+        let offsets = Vec::<usize>::decode(reader)?;
+        Ok(self.alloc_func_body(len_locals, max_stack_height, insts, handlers, offsets))
     }
 
     /// Resolves the [`FuncBody`] to the underlying `wasmi` bytecode instructions.
@@ -315,6 +819,10 @@ impl Engine {
     /// Those checks are usually done at the [`Func::call`] API or when creating
     /// a new [`TypedFunc`] instance via [`Func::typed`].
     ///
+    /// Host functions may call back into Wasm using the same [`Engine`] since
+    /// every execution uses its own value and call stacks and the [`Engine`]
+    /// is not locked while Wasm or host functions are running.
+    ///
     /// # Errors
     ///
     /// - If the given `func` is not a Wasm function, e.g. if it is a host function.
@@ -324,7 +832,7 @@ impl Engine {
     ///
     /// [`TypedFunc`]: [`crate::TypedFunc`]
     pub(crate) fn execute_func<Params, Results>(
-        &mut self,
+        &self,
         ctx: impl AsContextMut,
         func: Func,
         params: Params,
@@ -334,7 +842,101 @@ impl Engine {
         Params: CallParams,
         Results: CallResults,
     {
-        self.inner.lock().execute_func(ctx, func, params, results)
+        let mut stack = self.inner.lock().stacks.reuse_or_new();
+        let results =
+            EngineExecutor::new(self, &mut stack).execute_func(ctx, func, params, results);
+        self.inner.lock().recycle_stack(stack);
+        results.map_err(TaggedTrap::into_trap)
+    }
+
+    /// Executes the given [`Func`] in a resumable way using the given arguments `params`.
+    ///
+    /// # Note
+    ///
+    /// - If a host function called from Wasm returns a trap the execution is
+    ///   suspended and can be resumed using the returned [`ResumableInvocation`].
+    /// - This API assumes that the `params` and `results` are well typed and
+    ///   therefore won't perform type checks.
+    ///
+    /// # Errors
+    ///
+    /// When encountering a trap during the execution of `func` that cannot be resumed.
+    pub(crate) fn execute_func_resumable<Params, Results>(
+        &self,
+        ctx: impl AsContextMut,
+        func: Func,
+        params: Params,
+        results: Results,
+    ) -> Result<ResumableCallBase<<Results as CallResults>::Results>, Trap>
+    where
+        Params: CallParams,
+        Results: CallResults,
+    {
+        let mut stack = self.inner.lock().stacks.reuse_or_new();
+        let results =
+            EngineExecutor::new(self, &mut stack).execute_func(ctx, func, params, results);
+        self.handle_resumable_outcome(func, stack, results)
+    }
+
+    /// Resumes the suspended execution of the [`ResumableInvocation`].
+    ///
+    /// The `params` are used as the results of the host function that suspended the execution.
+    ///
+    /// # Note
+    ///
+    /// This API assumes that the `params` and `results` are well typed and
+    /// therefore won't perform type checks.
+    /// Those checks are usually done at the [`ResumableInvocation::resume`] API.
+    ///
+    /// # Errors
+    ///
+    /// When encountering a trap during the execution that cannot be resumed.
+    pub(crate) fn resume_func<Params, Results>(
+        &self,
+        ctx: impl AsContextMut,
+        invocation: ResumableInvocation,
+        params: Params,
+        results: Results,
+    ) -> Result<ResumableCallBase<<Results as CallResults>::Results>, Trap>
+    where
+        Params: CallParams,
+        Results: CallResults,
+    {
+        let func = invocation.func();
+        let host_func = invocation.host_func();
+        let caller = invocation.caller.map(|caller| *caller);
+        let mut stack = invocation.stack;
+        let results = EngineExecutor::new(self, &mut stack)
+            .resume_func(ctx, func, host_func, caller, params, results);
+        self.handle_resumable_outcome(func, stack, results)
+    }
+
+    /// Converts the outcome of a resumable execution of `func` into a [`ResumableCallBase`].
+    ///
+    /// The `stack` is recycled unless the execution has been suspended.
+    fn handle_resumable_outcome<Results>(
+        &self,
+        func: Func,
+        stack: Stack,
+        results: Result<Results, TaggedTrap>,
+    ) -> Result<ResumableCallBase<Results>, Trap> {
+        match results {
+            Ok(results) => {
+                self.inner.lock().recycle_stack(stack);
+                Ok(ResumableCallBase::Finished(results))
+            }
+            Err(TaggedTrap::Wasm(trap)) => {
+                self.inner.lock().recycle_stack(stack);
+                Err(trap)
+            }
+            Err(TaggedTrap::Host {
+                host_func,
+                host_trap,
+                caller,
+            }) => Ok(ResumableCallBase::Resumable(ResumableInvocation::new(
+                func, host_func, host_trap, caller, stack,
+            ))),
+        }
     }
 }
 
@@ -343,10 +945,8 @@ impl Engine {
 pub struct EngineInner {
     /// The configuration with which the [`Engine`] has been created.
     config: Config,
-    /// Stores the value stack of live values on the Wasm stack.
-    value_stack: ValueStack,
-    /// Stores the call stack of live function invocations.
-    call_stack: CallStack,
+    /// Stores unused value and call stacks for reuse by later executions.
+    stacks: StackPool,
     /// Stores all Wasm function bodies that the interpreter is aware of.
     code_map: CodeMap,
     /// Deduplicated function types.
@@ -364,8 +964,7 @@ impl EngineInner {
         let engine_idx = EngineIdx::new();
         Self {
             config: *config,
-            value_stack: ValueStack::new(64, config.value_stack_limit),
-            call_stack: CallStack::new(config.call_stack_limit),
+            stacks: StackPool::new(config),
             code_map: CodeMap::default(),
             func_types: FuncTypeRegistry::new(engine_idx),
         }
@@ -417,204 +1016,31 @@ impl EngineInner {
     /// Allocates the instructions of a Wasm function body to the [`Engine`].
     ///
     /// Returns a [`FuncBody`] reference to the allocated function body.
-    pub fn alloc_func_body<I>(
+    pub fn alloc_func_body<I, H, O>(
         &mut self,
         len_locals: usize,
         max_stack_height: usize,
         insts: I,
+        handlers: H,
+        offsets: O,
     ) -> FuncBody
     where
         I: IntoIterator<Item = Instruction>,
         I::IntoIter: ExactSizeIterator,
+        H: IntoIterator<Item = ExceptionHandler>,
+        O: IntoIterator<Item = usize>,
     {
-        self.code_map.alloc(len_locals, max_stack_height, insts)
+        self.code_map
+            .alloc(len_locals, max_stack_height, insts, handlers, offsets)
     }
 
-    /// Executes the given [`Func`] using the given arguments `args` and stores the result into `results`.
-    ///
-    /// # Errors
-    ///
-    /// - If the given arguments `args` do not match the expected parameters of `func`.
-    /// - If the given `results` do not match the the length of the expected results of `func`.
-    /// - When encountering a Wasm trap during the execution of `func`.
-    pub fn execute_func<Params, Results>(
-        &mut self,
-        mut ctx: impl AsContextMut,
-        func: Func,
-        params: Params,
-        results: Results,
-    ) -> Result<<Results as CallResults>::Results, Trap>
-    where
-        Params: CallParams,
-        Results: CallResults,
-    {
-        self.initialize_args(params);
-        let signature = match func.as_internal(&ctx) {
-            FuncEntityInternal::Wasm(wasm_func) => {
-                let signature = wasm_func.signature();
-                self.execute_wasm_func(&mut ctx, func)?;
-                signature
-            }
-            FuncEntityInternal::Host(host_func) => {
-                let signature = host_func.signature();
-                let host_func = host_func.clone();
-                self.execute_host_func(&mut ctx, host_func, None)?;
-                signature
-            }
-        };
-        let results = self.write_results_back(signature, results);
-        Ok(results)
+    /// Frees the Wasm function bodies from the [`Engine`].
+    pub fn free_func_bodies(&mut self, func_bodies: &[FuncBody]) {
+        self.code_map.free(func_bodies);
     }
 
-    /// Initializes the value stack with the given arguments `params`.
-    fn initialize_args<Params>(&mut self, params: Params)
-    where
-        Params: CallParams,
-    {
-        self.value_stack.clear();
-        self.call_stack.clear();
-        for param in params.feed_params() {
-            self.value_stack.push(param);
-        }
-    }
-
-    /// Writes the results of the function execution back into the `results` buffer.
-    ///
-    /// # Note
-    ///
-    /// The value stack is empty after this operation.
-    ///
-    /// # Panics
-    ///
-    /// - If the `results` buffer length does not match the remaining amount of stack values.
-    fn write_results_back<Results>(
-        &mut self,
-        func_type: DedupFuncType,
-        results: Results,
-    ) -> <Results as CallResults>::Results
-    where
-        Results: CallResults,
-    {
-        let result_types = self.func_types.resolve_func_type(func_type).results();
-        assert_eq!(
-            self.value_stack.len(),
-            results.len_results(),
-            "expected {} values on the stack after function execution but found {}",
-            results.len_results(),
-            self.value_stack.len(),
-        );
-        assert_eq!(results.len_results(), result_types.len());
-        results.feed_results(
-            self.value_stack
-                .drain()
-                .iter()
-                .zip(result_types)
-                .map(|(raw_value, value_type)| raw_value.with_type(*value_type)),
-        )
-    }
-
-    /// Executes the given Wasm [`Func`] using the given arguments `args` and stores the result into `results`.
-    ///
-    /// # Note
-    ///
-    /// The caller is required to ensure that the given `func` actually is a Wasm function.
-    ///
-    /// # Errors
-    ///
-    /// - If the given arguments `args` do not match the expected parameters of `func`.
-    /// - If the given `results` do not match the the length of the expected results of `func`.
-    /// - When encountering a Wasm trap during the execution of `func`.
-    fn execute_wasm_func(&mut self, mut ctx: impl AsContextMut, func: Func) -> Result<(), Trap> {
-        let mut function_frame = FunctionFrame::new(&ctx, func);
-        'outer: loop {
-            match self.execute_frame(&mut ctx, &mut function_frame)? {
-                FunctionExecutionOutcome::Return => match self.call_stack.pop() {
-                    Some(frame) => {
-                        function_frame = frame;
-                        continue 'outer;
-                    }
-                    None => return Ok(()),
-                },
-                FunctionExecutionOutcome::NestedCall(func) => match func.as_internal(&ctx) {
-                    FuncEntityInternal::Wasm(wasm_func) => {
-                        let nested_frame = FunctionFrame::new_wasm(func, wasm_func);
-                        self.call_stack.push(function_frame)?;
-                        function_frame = nested_frame;
-                    }
-                    FuncEntityInternal::Host(host_func) => {
-                        let instance = function_frame.instance();
-                        let host_func = host_func.clone();
-                        self.execute_host_func(&mut ctx, host_func, Some(instance))?;
-                    }
-                },
-            }
-        }
-    }
-
-    /// Executes the given function frame and returns the outcome.
-    ///
-    /// # Errors
-    ///
-    /// If the function frame execution trapped.
-    #[inline(always)]
-    fn execute_frame(
-        &mut self,
-        mut ctx: impl AsContextMut,
-        frame: &mut FunctionFrame,
-    ) -> Result<FunctionExecutionOutcome, Trap> {
-        ExecutionContext::new(self, frame)?.execute_frame(&mut ctx)
-    }
-
-    /// Executes the given host function.
-    ///
-    /// # Errors
-    ///
-    /// - If the host function returns a host side error or trap.
-    /// - If the value stack overflowed upon pushing parameters or results.
-    #[inline(never)]
-    fn execute_host_func<C>(
-        &mut self,
-        mut ctx: C,
-        host_func: HostFuncEntity<<C as AsContext>::UserState>,
-        instance: Option<Instance>,
-    ) -> Result<(), Trap>
-    where
-        C: AsContextMut,
-    {
-        // The host function signature is required for properly
-        // adjusting, inspecting and manipulating the value stack.
-        let (input_types, output_types) = self
-            .func_types
-            .resolve_func_type(host_func.signature())
-            .params_results();
-        // In case the host function returns more values than it takes
-        // we are required to extend the value stack.
-        let len_inputs = input_types.len();
-        let len_outputs = output_types.len();
-        let max_inout = cmp::max(len_inputs, len_outputs);
-        self.value_stack.reserve(max_inout)?;
-        if len_outputs > len_inputs {
-            let delta = len_outputs - len_inputs;
-            self.value_stack.extend_zeros(delta)?;
-        }
-        let params_results = FuncParams::new(
-            self.value_stack.peek_as_slice_mut(max_inout),
-            len_inputs,
-            len_outputs,
-        );
-        // Now we are ready to perform the host function call.
-        // Note: We need to clone the host function due to some borrowing issues.
-        //       This should not be a big deal since host functions usually are cheap to clone.
-        host_func.call(ctx.as_context_mut(), instance, params_results)?;
-        // If the host functions returns fewer results than it receives parameters
-        // the value stack needs to be shrinked for the delta.
-        if len_outputs < len_inputs {
-            let delta = len_inputs - len_outputs;
-            self.value_stack.drop(delta);
-        }
-        // At this point the host function has been called and has directly
-        // written its results into the value stack so that the last entries
-        // in the value stack are the result values of the host function call.
-        Ok(())
+    /// Returns the `stack` of a finished execution to the [`StackPool`].
+    pub fn recycle_stack(&mut self, stack: Stack) {
+        self.stacks.recycle(stack);
     }
 }
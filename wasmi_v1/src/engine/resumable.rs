@@ -0,0 +1,140 @@
+//! Data structures to represent resumable Wasm function executions.
+
+use super::{FunctionFrame, Stack};
+use crate::{core::Trap, errors::FuncError, AsContextMut, Error, Func, Value};
+use alloc::boxed::Box;
+
+/// The outcome of a resumable function execution.
+///
+/// # Note
+///
+/// This is the generic counterpart of [`ResumableCall`] used by the [`Engine`].
+///
+/// [`Engine`]: [`super::Engine`]
+#[derive(Debug)]
+pub(crate) enum ResumableCallBase<T> {
+    /// The execution has finished with the given results.
+    Finished(T),
+    /// The execution has been suspended by a trapping host function.
+    Resumable(ResumableInvocation),
+}
+
+/// Returned by [`Func::call_resumable`] and [`ResumableInvocation::resume`].
+#[derive(Debug)]
+pub enum ResumableCall {
+    /// The function call has finished and its results have been written to the `outputs` buffer.
+    Finished,
+    /// The function call has been suspended by a host function that returned a trap.
+    ///
+    /// The host can inspect the trap and resume the execution afterwards.
+    Resumable(ResumableInvocation),
+}
+
+impl<T> From<ResumableCallBase<T>> for ResumableCall {
+    fn from(call: ResumableCallBase<T>) -> Self {
+        match call {
+            ResumableCallBase::Finished(_) => Self::Finished,
+            ResumableCallBase::Resumable(invocation) => Self::Resumable(invocation),
+        }
+    }
+}
+
+/// A suspended Wasm function execution that can be resumed.
+///
+/// # Note
+///
+/// Owns the value and call stacks of the suspended execution.
+/// Dropping a [`ResumableInvocation`] discards the suspended execution.
+#[derive(Debug)]
+pub struct ResumableInvocation {
+    /// The function that has originally been called.
+    func: Func,
+    /// The host function that returned the trap.
+    host_func: Func,
+    /// The trap returned by the host function.
+    host_trap: Trap,
+    /// The function frame of the Wasm function that called the host function.
+    ///
+    /// This is `None` if the host function has been tail called
+    /// by the root Wasm function frame of the execution.
+    pub(super) caller: Option<Box<FunctionFrame>>,
+    /// The value and call stacks of the suspended execution.
+    pub(super) stack: Stack,
+}
+
+impl ResumableInvocation {
+    /// Creates a new [`ResumableInvocation`] from the suspended execution.
+    pub(super) fn new(
+        func: Func,
+        host_func: Func,
+        host_trap: Trap,
+        caller: Option<Box<FunctionFrame>>,
+        stack: Stack,
+    ) -> Self {
+        Self {
+            func,
+            host_func,
+            host_trap,
+            caller,
+            stack,
+        }
+    }
+
+    /// Returns the function that has originally been called.
+    pub(super) fn func(&self) -> Func {
+        self.func
+    }
+
+    /// Returns the host function that returned the trap and suspended the execution.
+    pub fn host_func(&self) -> Func {
+        self.host_func
+    }
+
+    /// Returns a shared reference to the trap returned by the host function.
+    pub fn host_trap(&self) -> &Trap {
+        &self.host_trap
+    }
+
+    /// Resumes the suspended execution using `inputs` as the results of the trapped host function.
+    ///
+    /// The results of the originally called function are written back into the `outputs` buffer
+    /// once the execution has finished.
+    ///
+    /// # Note
+    ///
+    /// The execution might be suspended again if another host function returns a trap.
+    ///
+    /// # Errors
+    ///
+    /// - If the types of the `inputs` do not match the result types of the host function.
+    /// - If the number of output values does not match the expected number of
+    ///   outputs required by the function signature of the originally called function.
+    /// - If the resumed execution returned a [`Trap`] that cannot be resumed.
+    pub fn resume<T>(
+        self,
+        mut ctx: impl AsContextMut<UserState = T>,
+        inputs: &[Value],
+        outputs: &mut [Value],
+    ) -> Result<ResumableCall, Error> {
+        let host_func_type = self.host_func.func_type(&ctx);
+        let expected_inputs = host_func_type.results();
+        let actual_inputs = inputs.iter().map(|value| value.value_type());
+        if expected_inputs.iter().copied().ne(actual_inputs) {
+            return Err(FuncError::MismatchingResults {
+                func: self.host_func,
+            }
+            .into());
+        }
+        if self.func.func_type(&ctx).results().len() != outputs.len() {
+            return Err(FuncError::MismatchingResults { func: self.func }.into());
+        }
+        // Note: Cloning an [`Engine`] is intentionally a cheap operation.
+        let call = ctx.as_context().store.engine().clone().resume_func(
+            ctx.as_context_mut(),
+            self,
+            inputs,
+            outputs,
+        )?;
+        Ok(call.into())
+    }
+}
@@ -0,0 +1,200 @@
+//! Data structures to represent the stacks of a single Wasm function execution.
+
+use super::{CallStack, Config, ValueStack};
+use crate::{core::TrapCode, Exception};
+use alloc::vec::Vec;
+
+/// The value and call stacks of a single `wasmi` function execution.
+///
+/// # Note
+///
+/// Every call into the [`Engine`] uses its own [`Stack`] so that host
+/// functions can call back into Wasm while an outer execution is still live.
+///
+/// [`Engine`]: [`super::Engine`]
+#[derive(Debug)]
+pub struct Stack {
+    /// Stores the value stack of live values on the Wasm stack.
+    pub(super) values: ValueStack,
+    /// Stores the call stack of live function invocations.
+    pub(super) frames: CallStack,
+    /// Stores the exceptions caught by live `catch` and `catch_all` clauses.
+    pub(super) exceptions: CaughtExceptions,
+    /// The maximum amount of executions that the execution using the [`Stack`] may be nested in.
+    nested_execution_limit: usize,
+}
+
+impl Stack {
+    /// Creates a new [`Stack`] using the stack limits of the given [`Config`].
+    pub fn new(config: &Config) -> Self {
+        Self {
+            values: ValueStack::new(64, config.value_stack_limit),
+            frames: CallStack::new(config.call_stack_limit),
+            exceptions: CaughtExceptions::default(),
+            nested_execution_limit: config.nested_execution_limit,
+        }
+    }
+
+    /// Reserves the call `depth` used by the executions that the
+    /// execution using the [`Stack`] is nested in.
+    ///
+    /// # Errors
+    ///
+    /// If the nested execution exceeds the call stack limit or the nested execution limit.
+    pub fn reserve(&mut self, depth: CallDepth) -> Result<(), TrapCode> {
+        if depth.nested() >= self.nested_execution_limit {
+            return Err(TrapCode::StackOverflow);
+        }
+        self.frames.reserve(depth.frames())
+    }
+
+    /// Clears the [`Stack`] entirely.
+    ///
+    /// # Note
+    ///
+    /// This is required since sometimes execution can halt in the middle of
+    /// function execution which leaves the [`Stack`] in an unspecified state.
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.frames.clear();
+        self.exceptions.clear();
+    }
+}
+
+/// The call depth of the executions of a [`Store`] that are in progress.
+///
+/// # Note
+///
+/// Host functions called from Wasm may call back into Wasm which starts
+/// a new execution with its own [`Stack`] nested in the execution of the
+/// Wasm caller. Without tracking the call depth across nested executions
+/// recursion through host functions would be unbounded and eventually
+/// overflow the native stack.
+///
+/// [`Store`]: [`crate::Store`]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct CallDepth {
+    /// The amount of function frames of all executions that called a host function.
+    frames: usize,
+    /// The amount of executions that called a host function.
+    nested: usize,
+}
+
+impl CallDepth {
+    /// Returns the amount of function frames of all executions that called a host function.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Returns the amount of executions that called a host function.
+    ///
+    /// # Note
+    ///
+    /// This is the amount of executions that a new execution is nested in.
+    pub fn nested(&self) -> usize {
+        self.nested
+    }
+
+    /// Enters a host function called by an execution with `frames` live function frames.
+    pub fn enter_host(&mut self, frames: usize) {
+        self.frames += frames;
+        self.nested += 1;
+    }
+
+    /// Leaves a host function entered via [`CallDepth::enter_host`].
+    pub fn leave_host(&mut self, frames: usize) {
+        self.frames -= frames;
+        self.nested -= 1;
+    }
+}
+
+/// The exceptions caught by live `catch` and `catch_all` clauses.
+///
+/// # Note
+///
+/// Every caught exception is associated to the position of its slot on the
+/// value stack which is where the clause that caught the exception starts.
+/// Since those positions are only ever reused after the clause has been left
+/// stale exceptions are discarded lazily upon catching another exception.
+#[derive(Debug, Default)]
+pub struct CaughtExceptions {
+    /// The caught exceptions with the positions of their slots in ascending order.
+    exceptions: Vec<(usize, Exception)>,
+}
+
+impl CaughtExceptions {
+    /// Pushes the `exception` caught at the slot `position` of the value stack.
+    ///
+    /// # Note
+    ///
+    /// This discards all exceptions at or above the slot `position`
+    /// since their `catch` clauses can no longer be live.
+    pub fn push(&mut self, position: usize, exception: Exception) {
+        let live = self
+            .exceptions
+            .iter()
+            .position(|(slot, _)| *slot >= position)
+            .unwrap_or(self.exceptions.len());
+        self.exceptions.truncate(live);
+        self.exceptions.push((position, exception));
+    }
+
+    /// Returns the exception caught at the slot `position` of the value stack.
+    ///
+    /// # Panics
+    ///
+    /// If no exception has been caught at the slot `position`.
+    pub fn get(&self, position: usize) -> &Exception {
+        self.exceptions
+            .iter()
+            .rev()
+            .find(|(slot, _)| *slot == position)
+            .map(|(_, exception)| exception)
+            .unwrap_or_else(|| panic!("missing caught exception at stack position {}", position))
+    }
+
+    /// Clears all caught exceptions.
+    pub fn clear(&mut self) {
+        self.exceptions.clear();
+    }
+}
+
+/// A pool of reusable [`Stack`] instances.
+///
+/// # Note
+///
+/// Allocating new stacks for every function execution is expensive.
+/// Therefore the [`Engine`] recycles stacks of finished executions.
+///
+/// [`Engine`]: [`super::Engine`]
+#[derive(Debug)]
+pub struct StackPool {
+    /// The configuration used to create new [`Stack`] instances.
+    config: Config,
+    /// All currently unused stacks.
+    stacks: Vec<Stack>,
+}
+
+impl StackPool {
+    /// Creates a new empty [`StackPool`] for the given [`Config`].
+    pub fn new(config: &Config) -> Self {
+        Self {
+            config: *config,
+            stacks: Vec::new(),
+        }
+    }
+
+    /// Returns a cleared [`Stack`] from the pool or creates a new one.
+    pub fn reuse_or_new(&mut self) -> Stack {
+        match self.stacks.pop() {
+            Some(stack) => stack,
+            None => Stack::new(&self.config),
+        }
+    }
+
+    /// Returns the `stack` to the pool so that it can be reused.
+    pub fn recycle(&mut self, mut stack: Stack) {
+        stack.clear();
+        self.stacks.push(stack);
+    }
+}
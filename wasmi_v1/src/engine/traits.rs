@@ -1,4 +1,4 @@
-use crate::core::Value;
+use crate::Value;
 use core::{iter, slice};
 
 /// Types implementing this trait may be used as parameters for function execution.
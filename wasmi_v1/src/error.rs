@@ -1,11 +1,15 @@
 use super::errors::{
+    FuelError,
     FuncError,
     GlobalError,
     InstantiationError,
     LinkerError,
     MemoryError,
     ModuleError,
+    RemoveInstanceError,
+    SnapshotError,
     TableError,
+    TagError,
 };
 use crate::core::Trap;
 use core::{fmt, fmt::Display};
@@ -28,6 +32,14 @@ pub enum Error {
     Module(ModuleError),
     /// A function error.
     Func(FuncError),
+    /// A fuel metering error.
+    Fuel(FuelError),
+    /// An exception tag error.
+    Tag(TagError),
+    /// An instance snapshot error.
+    Snapshot(SnapshotError),
+    /// An instance removal error.
+    RemoveInstance(RemoveInstanceError),
     /// A trap as defined by the WebAssembly specification.
     Trap(Trap),
 }
@@ -44,6 +56,10 @@ impl Display for Error {
             Self::Table(error) => Display::fmt(error, f),
             Self::Linker(error) => Display::fmt(error, f),
             Self::Func(error) => Display::fmt(error, f),
+            Self::Fuel(error) => Display::fmt(error, f),
+            Self::Tag(error) => Display::fmt(error, f),
+            Self::Snapshot(error) => Display::fmt(error, f),
+            Self::RemoveInstance(error) => Display::fmt(error, f),
             Self::Instantiation(error) => Display::fmt(error, f),
             Self::Module(error) => Display::fmt(error, f),
         }
@@ -97,3 +113,27 @@ impl From<FuncError> for Error {
         Self::Func(error)
     }
 }
+
+impl From<FuelError> for Error {
+    fn from(error: FuelError) -> Self {
+        Self::Fuel(error)
+    }
+}
+
+impl From<TagError> for Error {
+    fn from(error: TagError) -> Self {
+        Self::Tag(error)
+    }
+}
+
+impl From<SnapshotError> for Error {
+    fn from(error: SnapshotError) -> Self {
+        Self::Snapshot(error)
+    }
+}
+
+impl From<RemoveInstanceError> for Error {
+    fn from(error: RemoveInstanceError) -> Self {
+        Self::RemoveInstance(error)
+    }
+}
@@ -0,0 +1,92 @@
+use super::{errors::TagError, AsContext, FuncType, Tag, Value};
+use crate::core::{Trap, WasmException};
+use alloc::boxed::Box;
+use core::{fmt, fmt::Display};
+
+/// A Wasm exception of the `exception-handling` proposal.
+///
+/// # Note
+///
+/// Host functions can throw an [`Exception`] by returning it as [`Trap`]
+/// which allows the calling Wasm code to catch it via its `catch` clauses.
+/// Exceptions that are not caught by Wasm code are reported back to the
+/// host as [`Trap::Exception`].
+#[derive(Debug, Clone)]
+pub struct Exception {
+    /// The tag of the exception.
+    tag: Tag,
+    /// The payload values of the exception.
+    payload: Box<[Value]>,
+}
+
+impl WasmException for Exception {}
+
+impl Display for Exception {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "uncaught exception with payload {:?}", &self.payload[..])
+    }
+}
+
+impl From<Exception> for Trap {
+    fn from(exception: Exception) -> Self {
+        Trap::exception(exception)
+    }
+}
+
+impl Exception {
+    /// Creates a new [`Exception`] for the `tag` with the given `payload`.
+    ///
+    /// # Errors
+    ///
+    /// If the types of the `payload` values do not match the parameters of the `tag`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own the `tag`.
+    pub fn new<I>(ctx: impl AsContext, tag: Tag, payload: I) -> Result<Self, TagError>
+    where
+        I: IntoIterator<Item = Value>,
+    {
+        let payload = payload.into_iter().collect::<Box<[Value]>>();
+        let tag_type = tag.tag_type(&ctx);
+        let matches = tag_type.params().len() == payload.len()
+            && tag_type
+                .params()
+                .iter()
+                .zip(&payload[..])
+                .all(|(expected, value)| *expected == value.value_type());
+        if !matches {
+            return Err(TagError::PayloadMismatch {
+                expected: tag_type,
+                encountered: FuncType::new(payload.iter().map(Value::value_type), []),
+            });
+        }
+        Ok(Self::new_unchecked(tag, payload))
+    }
+
+    /// Creates a new [`Exception`] without checking the `payload` types.
+    ///
+    /// # Note
+    ///
+    /// This is used by the engine for exceptions thrown by validated Wasm code.
+    pub(crate) fn new_unchecked(tag: Tag, payload: Box<[Value]>) -> Self {
+        Self { tag, payload }
+    }
+
+    /// Returns the [`Tag`] of the exception.
+    pub fn tag(&self) -> Tag {
+        self.tag
+    }
+
+    /// Returns the payload values of the exception.
+    pub fn payload(&self) -> &[Value] {
+        &self.payload
+    }
+
+    /// Returns the [`Exception`] if the `trap` is an exception thrown by `wasmi`.
+    ///
+    /// Returns `None` otherwise.
+    pub fn from_trap(trap: &Trap) -> Option<&Self> {
+        trap.as_exception()?.downcast_ref::<Self>()
+    }
+}
@@ -0,0 +1,87 @@
+use alloc::{boxed::Box, collections::BTreeMap};
+use core::{
+    any::{Any, TypeId},
+    fmt,
+    fmt::Debug,
+};
+
+/// A type erased extension value of a [`Store`].
+///
+/// [`Store`]: [`crate::Store`]
+type Extension = Box<dyn Any + Send + Sync>;
+
+/// Independent slots of host state owned by a [`Store`] indexed by their type.
+///
+/// # Note
+///
+/// Extensions allow host functions to keep their own mutable state
+/// in the [`Store`] without having to bundle all host state into the
+/// single user provided state `T` of a `Store<T>`.
+///
+/// [`Store`]: [`crate::Store`]
+#[derive(Default)]
+pub struct Extensions {
+    map: BTreeMap<TypeId, Extension>,
+}
+
+impl Debug for Extensions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.map.keys()).finish()
+    }
+}
+
+impl Extensions {
+    /// Inserts the extension `value` of type `U`.
+    ///
+    /// Returns the previous extension of type `U` if any.
+    pub fn insert<U>(&mut self, value: U) -> Option<U>
+    where
+        U: Send + Sync + 'static,
+    {
+        self.map
+            .insert(TypeId::of::<U>(), Box::new(value))
+            .map(Self::downcast)
+    }
+
+    /// Returns a shared reference to the extension of type `U` if any.
+    pub fn get<U>(&self) -> Option<&U>
+    where
+        U: Send + Sync + 'static,
+    {
+        self.map
+            .get(&TypeId::of::<U>())
+            .and_then(|value| value.downcast_ref())
+    }
+
+    /// Returns an exclusive reference to the extension of type `U` if any.
+    pub fn get_mut<U>(&mut self) -> Option<&mut U>
+    where
+        U: Send + Sync + 'static,
+    {
+        self.map
+            .get_mut(&TypeId::of::<U>())
+            .and_then(|value| value.downcast_mut())
+    }
+
+    /// Removes and returns the extension of type `U` if any.
+    pub fn remove<U>(&mut self) -> Option<U>
+    where
+        U: Send + Sync + 'static,
+    {
+        self.map.remove(&TypeId::of::<U>()).map(Self::downcast)
+    }
+
+    /// Converts the type erased `value` back into its extension type `U`.
+    ///
+    /// # Panics
+    ///
+    /// If `value` is not of type `U`.
+    fn downcast<U>(value: Extension) -> U
+    where
+        U: Send + Sync + 'static,
+    {
+        *value
+            .downcast()
+            .unwrap_or_else(|_| panic!("encountered extension of unexpected type"))
+    }
+}
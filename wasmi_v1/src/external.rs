@@ -1,4 +1,4 @@
-use super::{Func, Global, Memory, Table};
+use super::{Func, Global, Memory, Table, Tag};
 
 /// An external reference.
 #[derive(Debug, Copy, Clone)]
@@ -11,6 +11,8 @@ pub enum Extern {
     Memory(Memory),
     /// An externally defined Wasm or host function.
     Func(Func),
+    /// An externally defined exception tag.
+    Tag(Tag),
 }
 
 impl From<Global> for Extern {
@@ -37,6 +39,12 @@ impl From<Func> for Extern {
     }
 }
 
+impl From<Tag> for Extern {
+    fn from(tag: Tag) -> Self {
+        Self::Tag(tag)
+    }
+}
+
 impl Extern {
     /// Returns the underlying global variable if `self` is a global variable.
     ///
@@ -77,4 +85,14 @@ impl Extern {
         }
         None
     }
+
+    /// Returns the underlying exception tag if `self` is an exception tag.
+    ///
+    /// Returns `None` otherwise.
+    pub fn into_tag(self) -> Option<Tag> {
+        if let Self::Tag(tag) = self {
+            return Some(tag);
+        }
+        None
+    }
 }
@@ -0,0 +1,160 @@
+use super::{
+    arena::{MAX_ENCODED_GENERATION, MAX_ENCODED_SLOT},
+    ArenaIndex,
+    AsContextMut,
+    StoreContext,
+    Stored,
+};
+use alloc::boxed::Box;
+use core::{any::Any, fmt, num::NonZeroU64};
+use wasmi_core::UntypedValue;
+
+/// A raw index to an external object entity.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ExternObjectIdx(u64);
+
+impl ArenaIndex for ExternObjectIdx {
+    // Limited so that references can be encoded as untyped values.
+    const MAX_SLOT: u32 = MAX_ENCODED_SLOT;
+    const MAX_GENERATION: u32 = MAX_ENCODED_GENERATION;
+
+    fn into_key(self) -> u64 {
+        self.0
+    }
+
+    fn from_key(key: u64) -> Self {
+        Self(key)
+    }
+}
+
+/// An external object entity.
+///
+/// # Note
+///
+/// This owns the host data that is referenced by an [`ExternRef`].
+pub struct ExternObjectEntity {
+    inner: Box<dyn 'static + Any + Send + Sync>,
+}
+
+impl fmt::Debug for ExternObjectEntity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ExternObjectEntity").finish_non_exhaustive()
+    }
+}
+
+impl ExternObjectEntity {
+    /// Creates a new [`ExternObjectEntity`] owning the given `object`.
+    pub fn new<T>(object: T) -> Self
+    where
+        T: 'static + Any + Send + Sync,
+    {
+        Self {
+            inner: Box::new(object),
+        }
+    }
+
+    /// Returns a shared reference to the owned host data.
+    pub fn data(&self) -> &dyn Any {
+        &*self.inner
+    }
+}
+
+/// A reference to an [`ExternObjectEntity`] of a [`Store`].
+///
+/// [`Store`]: [`crate::Store`]
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[repr(transparent)]
+pub struct ExternObject(Stored<ExternObjectIdx>);
+
+impl ExternObject {
+    /// Creates a new external object reference.
+    pub(super) fn from_inner(stored: Stored<ExternObjectIdx>) -> Self {
+        Self(stored)
+    }
+
+    /// Returns the underlying stored representation.
+    pub(super) fn into_inner(self) -> Stored<ExternObjectIdx> {
+        self.0
+    }
+
+    /// Returns a shared reference to the host data of the [`ExternObject`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`ExternObject`].
+    pub fn data<'a, T: 'a>(&self, ctx: impl Into<StoreContext<'a, T>>) -> &'a dyn Any {
+        ctx.into().store.resolve_extern_object(*self).data()
+    }
+}
+
+/// A nullable reference to host data owned by a [`Store`].
+///
+/// # Note
+///
+/// This is the type of the `externref` values of the `reference-types` Wasm proposal.
+/// It allows to pass opaque host data into Wasm and back without the need to
+/// manage a table of indices on the host side.
+///
+/// [`Store`]: [`crate::Store`]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+#[repr(transparent)]
+pub struct ExternRef {
+    inner: Option<ExternObject>,
+}
+
+impl From<UntypedValue> for ExternRef {
+    fn from(untyped: UntypedValue) -> Self {
+        let inner = NonZeroU64::new(untyped.to_bits())
+            .map(Stored::from_bits)
+            .map(ExternObject::from_inner);
+        Self { inner }
+    }
+}
+
+impl From<ExternRef> for UntypedValue {
+    fn from(externref: ExternRef) -> Self {
+        let bits = externref
+            .inner
+            .map(|object| object.into_inner().to_bits().get())
+            .unwrap_or(0);
+        UntypedValue::from(bits)
+    }
+}
+
+impl ExternRef {
+    /// Creates a new [`ExternRef`] to the given `object` which is owned by the [`Store`].
+    ///
+    /// Returns a `null` [`ExternRef`] if `object` is `None`.
+    ///
+    /// [`Store`]: [`crate::Store`]
+    pub fn new<T>(mut ctx: impl AsContextMut, object: impl Into<Option<T>>) -> Self
+    where
+        T: 'static + Any + Send + Sync,
+    {
+        let inner = object.into().map(|object| {
+            ctx.as_context_mut()
+                .store
+                .alloc_extern_object(ExternObjectEntity::new(object))
+        });
+        Self { inner }
+    }
+
+    /// Creates a `null` [`ExternRef`].
+    pub fn null() -> Self {
+        Self { inner: None }
+    }
+
+    /// Returns `true` if the [`ExternRef`] is `null`.
+    pub fn is_null(&self) -> bool {
+        self.inner.is_none()
+    }
+
+    /// Returns a shared reference to the host data of the [`ExternRef`] if it is not `null`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`ExternRef`].
+    pub fn data<'a, T: 'a>(&self, ctx: impl Into<StoreContext<'a, T>>) -> Option<&'a dyn Any> {
+        self.inner.map(|object| object.data(ctx))
+    }
+}
@@ -1,5 +1,11 @@
 use super::super::{AsContext, AsContextMut, StoreContext, StoreContextMut};
-use crate::{Engine, Extern, Instance};
+use crate::{
+    core::{Trap, TrapCode},
+    errors::FuelError,
+    Engine,
+    Extern,
+    Instance,
+};
 
 /// Represents the caller’s context when creating a host function via [`Func::wrap`].
 ///
@@ -44,10 +50,95 @@ impl<'a, T> Caller<'a, T> {
         self.store.store.state_mut()
     }
 
+    /// Returns a shared reference to the [`Store`] extension of type `U` if any.
+    ///
+    /// [`Store`]: [`crate::Store`]
+    pub fn extension<U>(&self) -> Option<&U>
+    where
+        U: Send + Sync + 'static,
+    {
+        self.store.store.extension()
+    }
+
+    /// Returns an exclusive reference to the [`Store`] extension of type `U` if any.
+    ///
+    /// # Note
+    ///
+    /// This allows host functions to operate on their own mutable state
+    /// owned by the [`Store`] independent of the host provided data.
+    ///
+    /// [`Store`]: [`crate::Store`]
+    pub fn extension_mut<U>(&mut self) -> Option<&mut U>
+    where
+        U: Send + Sync + 'static,
+    {
+        self.store.store.extension_mut()
+    }
+
+    /// Inserts the extension `value` of type `U` into the [`Store`].
+    ///
+    /// Returns the previous extension of type `U` if any.
+    ///
+    /// [`Store`]: [`crate::Store`]
+    pub fn insert_extension<U>(&mut self, value: U) -> Option<U>
+    where
+        U: Send + Sync + 'static,
+    {
+        self.store.store.insert_extension(value)
+    }
+
     /// Returns a shared reference to the used [`Engine`].
     pub fn engine(&self) -> &Engine {
         self.store.store.engine()
     }
+
+    /// Returns the amount of fuel consumed by executions of the [`Store`] so far.
+    ///
+    /// Returns `None` if fuel metering is disabled.
+    ///
+    /// [`Store`]: [`crate::Store`]
+    pub fn fuel_consumed(&self) -> Option<u64> {
+        self.store.store.fuel_consumed()
+    }
+
+    /// Returns the amount of fuel remaining in the [`Store`].
+    ///
+    /// Returns `None` if fuel metering is disabled.
+    ///
+    /// [`Store`]: [`crate::Store`]
+    pub fn remaining_fuel(&self) -> Option<u64> {
+        self.store.store.remaining_fuel()
+    }
+
+    /// Adds `delta` units of fuel to the [`Store`].
+    ///
+    /// # Errors
+    ///
+    /// If fuel metering is disabled.
+    ///
+    /// [`Store`]: [`crate::Store`]
+    pub fn add_fuel(&mut self, delta: u64) -> Result<(), FuelError> {
+        self.store.store.add_fuel(delta)
+    }
+
+    /// Charges `delta` units of fuel for the work done by the host function.
+    ///
+    /// # Note
+    ///
+    /// Does nothing if fuel metering is disabled so that host functions
+    /// can charge fuel regardless of the [`Config`] in use.
+    ///
+    /// # Errors
+    ///
+    /// Traps with [`TrapCode::OutOfFuel`] if there is less than `delta` fuel remaining.
+    ///
+    /// [`Config`]: [`crate::Config`]
+    pub fn charge_fuel(&mut self, delta: u64) -> Result<(), Trap> {
+        match self.store.store.consume_fuel(delta) {
+            Ok(_) | Err(FuelError::FuelMeteringDisabled) => Ok(()),
+            Err(FuelError::OutOfFuel) => Err(TrapCode::OutOfFuel.into()),
+        }
+    }
 }
 
 impl<T> AsContext for Caller<'_, T> {
@@ -1,4 +1,5 @@
 use super::Func;
+use crate::{core::HostError, FuncType};
 use core::{fmt, fmt::Display};
 
 /// Errors that can occur upon operating with [`Func`] instances.
@@ -14,6 +15,9 @@ pub enum FuncError {
     ///
     /// [`TypedFunc`]: [`super::TypedFunc`]
     MismatchingResults { func: Func },
+    /// Encountered when a host function created via [`Func::new`]
+    /// returned results that do not match its function type.
+    MismatchingHostFuncResults { func_type: FuncType },
 }
 
 impl Display for FuncError {
@@ -29,6 +33,13 @@ impl Display for FuncError {
                 "encountered mismatching function result types for TypedFunc: {:?}",
                 func
             ),
+            FuncError::MismatchingHostFuncResults { func_type } => write!(
+                f,
+                "encountered mismatching host function result types for {:?}",
+                func_type
+            ),
         }
     }
 }
+
+impl HostError for FuncError {}
@@ -0,0 +1,62 @@
+use super::Func;
+use crate::Stored;
+use core::num::NonZeroU64;
+use wasmi_core::UntypedValue;
+
+/// A nullable [`Func`] reference.
+///
+/// # Note
+///
+/// This is the type of the `funcref` values of the `reference-types` Wasm proposal.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+#[repr(transparent)]
+pub struct FuncRef {
+    inner: Option<Func>,
+}
+
+impl From<Func> for FuncRef {
+    fn from(func: Func) -> Self {
+        Self::new(func)
+    }
+}
+
+impl From<UntypedValue> for FuncRef {
+    fn from(untyped: UntypedValue) -> Self {
+        let inner = NonZeroU64::new(untyped.to_bits())
+            .map(Stored::from_bits)
+            .map(Func::from_inner);
+        Self { inner }
+    }
+}
+
+impl From<FuncRef> for UntypedValue {
+    fn from(funcref: FuncRef) -> Self {
+        let bits = funcref
+            .inner
+            .map(|func| func.into_inner().to_bits().get())
+            .unwrap_or(0);
+        UntypedValue::from(bits)
+    }
+}
+
+impl FuncRef {
+    /// Creates a new [`FuncRef`] which may be `null`.
+    pub fn new(func: impl Into<Option<Func>>) -> Self {
+        Self { inner: func.into() }
+    }
+
+    /// Creates a `null` [`FuncRef`].
+    pub fn null() -> Self {
+        Self { inner: None }
+    }
+
+    /// Returns `true` if the [`FuncRef`] is `null`.
+    pub fn is_null(&self) -> bool {
+        self.inner.is_none()
+    }
+
+    /// Returns the referenced [`Func`] if the [`FuncRef`] is not `null`.
+    pub fn func(&self) -> Option<&Func> {
+        self.inner.as_ref()
+    }
+}
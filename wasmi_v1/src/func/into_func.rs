@@ -3,12 +3,18 @@ use super::{
     HostFuncTrampoline,
 };
 use crate::{
-    core::{FromValue, Trap, Value, ValueType, F32, F64},
+    core::{Trap, ValueType, F32, F64},
     foreach_tuple::for_each_tuple,
     Caller,
+    ExternRef,
+    FromValue,
+    FuncRef,
     FuncType,
+    Value,
 };
 use core::{array, iter::FusedIterator};
+#[cfg(feature = "simd")]
+use wasmi_core::V128;
 use wasmi_core::{DecodeUntypedSlice, EncodeUntypedSlice, UntypedValue};
 
 /// Closures and functions that can be used as host functions.
@@ -162,6 +168,13 @@ impl_wasm_type! {
     type i64 = I64;
     type F32 = F32;
     type F64 = F64;
+    type FuncRef = FuncRef;
+    type ExternRef = ExternRef;
+}
+
+#[cfg(feature = "simd")]
+impl_wasm_type! {
+    type V128 = V128;
 }
 
 /// A list of [`WasmType`] types.
@@ -1,42 +1,54 @@
 mod caller;
 mod error;
+mod funcref;
 mod into_func;
 mod typed_func;
 
+#[cfg(test)]
+mod tests;
+
 pub use self::{
     caller::Caller,
     error::FuncError,
+    funcref::FuncRef,
     into_func::IntoFunc,
     typed_func::{TypedFunc, WasmParams, WasmResults},
 };
 use super::{
     engine::{DedupFuncType, FuncBody, FuncParams, FuncResults},
+    ArenaIndex,
     AsContext,
     AsContextMut,
-    Index,
     Instance,
     StoreContext,
     Stored,
 };
 use crate::{
-    core::{Trap, Value},
+    arena::{MAX_ENCODED_GENERATION, MAX_ENCODED_SLOT},
+    core::{Trap, UntypedValue},
     Error,
     FuncType,
+    ResumableCall,
+    Value,
 };
-use alloc::sync::Arc;
+use alloc::{sync::Arc, vec::Vec};
 use core::{fmt, fmt::Debug};
 
 /// A raw index to a function entity.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub struct FuncIdx(usize);
+pub struct FuncIdx(u64);
+
+impl ArenaIndex for FuncIdx {
+    // Limited so that references can be encoded as untyped values.
+    const MAX_SLOT: u32 = MAX_ENCODED_SLOT;
+    const MAX_GENERATION: u32 = MAX_ENCODED_GENERATION;
 
-impl Index for FuncIdx {
-    fn into_usize(self) -> usize {
+    fn into_key(self) -> u64 {
         self.0
     }
 
-    fn from_usize(value: usize) -> Self {
-        Self(value)
+    fn from_key(key: u64) -> Self {
+        Self(key)
     }
 }
 
@@ -75,6 +87,17 @@ impl<T> FuncEntity<T> {
         }
     }
 
+    /// Creates a new host function from the given function type and trampoline.
+    pub(crate) fn new_host(
+        ctx: impl AsContextMut,
+        func_type: FuncType,
+        trampoline: HostFuncTrampoline<T>,
+    ) -> Self {
+        Self {
+            internal: FuncEntityInternal::Host(HostFuncEntity::new(ctx, func_type, trampoline)),
+        }
+    }
+
     /// Returns the internal function entity.
     ///
     /// # Note
@@ -181,6 +204,52 @@ impl<T> HostFuncTrampoline<T> {
             closure: Arc::new(trampoline),
         }
     }
+
+    /// Creates a new [`HostFuncTrampoline`] from the given dynamically typed host function.
+    ///
+    /// The host function receives its parameters as [`Value`] slice and is
+    /// expected to write values of the result types of `func_type` into the
+    /// results [`Value`] slice.
+    pub fn new_dynamic<F>(func_type: FuncType, func: F) -> Self
+    where
+        F: Fn(Caller<T>, &[Value], &mut [Value]) -> Result<(), Trap>,
+        F: Send + Sync + 'static,
+    {
+        Self::new(
+            move |caller: Caller<T>, params_results: FuncParams| -> Result<FuncResults, Trap> {
+                let (param_types, result_types) = func_type.params_results();
+                // Parameters and results share a single buffer so that only
+                // a single heap allocation is required per host function call.
+                let mut values = params_results
+                    .params()
+                    .iter()
+                    .zip(param_types)
+                    .map(|(param, param_type)| Value::from_untyped(*param, *param_type))
+                    .chain(result_types.iter().copied().map(Value::default))
+                    .collect::<Vec<_>>();
+                let (params, results) = values.split_at_mut(param_types.len());
+                func(caller, params, results)?;
+                let actual_result_types = results.iter().map(Value::value_type);
+                if result_types.iter().copied().ne(actual_result_types) {
+                    return Err(Trap::host(FuncError::MismatchingHostFuncResults {
+                        func_type: func_type.clone(),
+                    }));
+                }
+                Ok(params_results
+                    .write_untyped_results(results.iter().copied().map(UntypedValue::from)))
+            },
+        )
+    }
+
+    /// Returns a key identifying the host function of the [`HostFuncTrampoline`].
+    ///
+    /// # Note
+    ///
+    /// Clones of a [`HostFuncTrampoline`] share the same key. The key is unique
+    /// for as long as any clone of the [`HostFuncTrampoline`] is alive.
+    pub(crate) fn key(&self) -> usize {
+        Arc::as_ptr(&self.closure) as *const () as usize
+    }
 }
 
 impl<T> Clone for HostFuncTrampoline<T> {
@@ -200,11 +269,20 @@ impl<T> Debug for HostFuncEntity<T> {
 impl<T> HostFuncEntity<T> {
     /// Creates a new host function from the given closure.
     pub fn wrap<Params, Results>(
-        mut ctx: impl AsContextMut,
+        ctx: impl AsContextMut,
         func: impl IntoFunc<T, Params, Results>,
     ) -> Self {
-        let (signature, trampoline) = func.into_func();
-        let signature = ctx.as_context_mut().store.alloc_func_type(signature);
+        let (func_type, trampoline) = func.into_func();
+        Self::new(ctx, func_type, trampoline)
+    }
+
+    /// Creates a new host function from the given function type and trampoline.
+    pub fn new(
+        mut ctx: impl AsContextMut,
+        func_type: FuncType,
+        trampoline: HostFuncTrampoline<T>,
+    ) -> Self {
+        let signature = ctx.as_context_mut().store.alloc_func_type(func_type);
         Self {
             signature,
             trampoline,
@@ -231,7 +309,7 @@ impl<T> HostFuncEntity<T> {
 }
 
 /// A Wasm or host function reference.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 #[repr(transparent)]
 pub struct Func(Stored<FuncIdx>);
 
@@ -255,6 +333,53 @@ impl Func {
         ctx.as_context_mut().store.alloc_func(func)
     }
 
+    /// Creates a new dynamically typed host function.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`Func::wrap`] the parameter and result types of the host function
+    /// are given by `func_type` at runtime. The host function receives its parameters
+    /// as [`Value`] slice and writes its results into the results [`Value`] slice.
+    ///
+    /// Calling the host function results in a [`Trap`] if it writes results that
+    /// do not match the result types of `func_type`.
+    pub fn new<T>(
+        mut ctx: impl AsContextMut<UserState = T>,
+        func_type: FuncType,
+        func: impl Fn(Caller<T>, &[Value], &mut [Value]) -> Result<(), Trap> + Send + Sync + 'static,
+    ) -> Self {
+        let trampoline = HostFuncTrampoline::new_dynamic(func_type.clone(), func);
+        let func = FuncEntity::new_host(ctx.as_context_mut(), func_type, trampoline);
+        ctx.as_context_mut().store.alloc_func(func)
+    }
+
+    /// Returns the host function of the given function type and trampoline.
+    ///
+    /// # Note
+    ///
+    /// This is used by the [`Linker`] to allocate host functions defined
+    /// via [`Linker::func_wrap`] or [`Linker::func_new`] in the store used
+    /// for instantiation. The host function is allocated only once per store
+    /// and reused by later instantiations.
+    ///
+    /// [`Linker`]: crate::Linker
+    /// [`Linker::func_wrap`]: crate::Linker::func_wrap
+    /// [`Linker::func_new`]: crate::Linker::func_new
+    pub(crate) fn new_host<T>(
+        mut ctx: impl AsContextMut<UserState = T>,
+        func_type: FuncType,
+        trampoline: HostFuncTrampoline<T>,
+    ) -> Self {
+        let key = trampoline.key();
+        if let Some(func) = ctx.as_context().store.get_linker_func(key) {
+            return func;
+        }
+        let func = FuncEntity::new_host(ctx.as_context_mut(), func_type, trampoline);
+        let func = ctx.as_context_mut().store.alloc_func(func);
+        ctx.as_context_mut().store.insert_linker_func(key, func);
+        func
+    }
+
     /// Returns the signature of the function.
     pub(crate) fn signature(&self, ctx: impl AsContext) -> DedupFuncType {
         ctx.as_context().store.resolve_func(*self).signature()
@@ -286,6 +411,74 @@ impl Func {
         inputs: &[Value],
         outputs: &mut [Value],
     ) -> Result<(), Error> {
+        self.verify_inputs_outputs(&ctx, inputs, outputs)?;
+        // Note: Cloning an [`Engine`] is intentionally a cheap operation.
+        ctx.as_context().store.engine().clone().execute_func(
+            ctx.as_context_mut(),
+            *self,
+            inputs,
+            outputs,
+        )?;
+        Ok(())
+    }
+
+    /// Calls the Wasm or host function with the given inputs in a resumable way.
+    ///
+    /// The result is written back into the `outputs` buffer once the call has finished.
+    ///
+    /// # Note
+    ///
+    /// If a host function called from Wasm returns a [`Trap`] the execution is
+    /// suspended instead of aborted. The returned [`ResumableInvocation`] can be
+    /// used to inspect the [`Trap`] and to resume the execution with the results
+    /// of the host function.
+    ///
+    /// # Errors
+    ///
+    /// - If the function returned a [`Trap`] that cannot be resumed.
+    ///   This includes all traps originating from Wasm as well as traps
+    ///   of host functions that are not called from Wasm.
+    /// - If the types of the `inputs` do not match the expected types for the
+    ///   function signature of `self`.
+    /// - If the number of input values does not match the expected number of
+    ///   inputs required by the function signature of `self`.
+    /// - If the number of output values does not match the expected number of
+    ///   outputs required by the function signature of `self`.
+    ///
+    /// [`ResumableInvocation`]: [`crate::ResumableInvocation`]
+    pub fn call_resumable<T>(
+        &self,
+        mut ctx: impl AsContextMut<UserState = T>,
+        inputs: &[Value],
+        outputs: &mut [Value],
+    ) -> Result<ResumableCall, Error> {
+        self.verify_inputs_outputs(&ctx, inputs, outputs)?;
+        // Note: Cloning an [`Engine`] is intentionally a cheap operation.
+        let call = ctx
+            .as_context()
+            .store
+            .engine()
+            .clone()
+            .execute_func_resumable(ctx.as_context_mut(), *self, inputs, outputs)?;
+        Ok(call.into())
+    }
+
+    /// Verifies that the `inputs` and `outputs` match the function signature of `self`.
+    ///
+    /// # Errors
+    ///
+    /// - If the types of the `inputs` do not match the expected types for the
+    ///   function signature of `self`.
+    /// - If the number of input values does not match the expected number of
+    ///   inputs required by the function signature of `self`.
+    /// - If the number of output values does not match the expected number of
+    ///   outputs required by the function signature of `self`.
+    fn verify_inputs_outputs(
+        &self,
+        ctx: impl AsContext,
+        inputs: &[Value],
+        outputs: &[Value],
+    ) -> Result<(), FuncError> {
         // Since [`Func`] is a dynamically typed function instance there is
         // a need to verify that the given input parameters match the required
         // types and that the given output slice matches the expected length.
@@ -295,18 +488,11 @@ impl Func {
         let (expected_inputs, expected_outputs) = func_type.params_results();
         let actual_inputs = inputs.iter().map(|value| value.value_type());
         if expected_inputs.iter().copied().ne(actual_inputs) {
-            return Err(FuncError::MismatchingParameters { func: *self }).map_err(Into::into);
+            return Err(FuncError::MismatchingParameters { func: *self });
         }
         if expected_outputs.len() != outputs.len() {
-            return Err(FuncError::MismatchingResults { func: *self }).map_err(Into::into);
+            return Err(FuncError::MismatchingResults { func: *self });
         }
-        // Note: Cloning an [`Engine`] is intentionally a cheap operation.
-        ctx.as_context().store.engine().clone().execute_func(
-            ctx.as_context_mut(),
-            *self,
-            inputs,
-            outputs,
-        )?;
         Ok(())
     }
 
@@ -338,4 +524,17 @@ impl Func {
     ) -> &'a FuncEntityInternal<T> {
         ctx.into().store.resolve_func(*self).as_internal()
     }
+
+    /// Returns the internal representation of the [`Func`] instance.
+    ///
+    /// Returns `None` if the [`Func`] has been removed from its store.
+    pub(crate) fn try_as_internal<'a, T: 'a>(
+        &self,
+        ctx: impl Into<StoreContext<'a, T>>,
+    ) -> Option<&'a FuncEntityInternal<T>> {
+        ctx.into()
+            .store
+            .try_resolve_func(*self)
+            .map(FuncEntity::as_internal)
+    }
 }
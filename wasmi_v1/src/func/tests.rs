@@ -0,0 +1,38 @@
+use super::*;
+use crate::{Engine, Linker, Module, Store};
+
+#[test]
+fn func_new_is_not_cached_per_store() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let func_type = FuncType::new([], []);
+    for _ in 0..10 {
+        Func::new(
+            &mut store,
+            func_type.clone(),
+            |_caller, _params, _results| Ok(()),
+        );
+    }
+    assert_eq!(store.len_linker_funcs(), 0);
+}
+
+#[test]
+fn linker_func_new_is_allocated_once_per_store() {
+    let wasm = wat::parse_str(r#"(module (import "host" "f" (func)))"#).unwrap();
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new();
+    linker
+        .func_new(
+            "host",
+            "f",
+            FuncType::new([], []),
+            |_caller, _params, _results| Ok(()),
+        )
+        .unwrap();
+    for _ in 0..10 {
+        linker.instantiate(&mut store, &module).unwrap();
+    }
+    assert_eq!(store.len_linker_funcs(), 1);
+}
@@ -1,18 +1,18 @@
-use super::{AsContext, AsContextMut, Index, Stored};
-use crate::core::{Value, ValueType};
+use super::{ArenaIndex, AsContext, AsContextMut, Stored};
+use crate::{core::ValueType, Value};
 use core::{fmt, fmt::Display};
 
 /// A raw index to a global variable entity.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub struct GlobalIdx(usize);
+pub struct GlobalIdx(u64);
 
-impl Index for GlobalIdx {
-    fn into_usize(self) -> usize {
+impl ArenaIndex for GlobalIdx {
+    fn into_key(self) -> u64 {
         self.0
     }
 
-    fn from_usize(value: usize) -> Self {
-        Self(value)
+    fn from_key(key: u64) -> Self {
+        Self(key)
     }
 }
 
@@ -29,6 +29,8 @@ pub enum GlobalError {
         /// The type of the new value that mismatches the type of the global variable.
         encountered: ValueType,
     },
+    /// Occurs when the resource limiter of the store denied the allocation.
+    ResourceLimitExceeded,
 }
 
 impl Display for GlobalError {
@@ -45,6 +47,9 @@ impl Display for GlobalError {
                     expected, encountered,
                 )
             }
+            Self::ResourceLimitExceeded => {
+                write!(f, "resource limiter denied the global variable allocation")
+            }
         }
     }
 }
@@ -168,10 +173,22 @@ impl Global {
     }
 
     /// Creates a new global variable to the store.
-    pub fn new(mut ctx: impl AsContextMut, initial_value: Value, mutability: Mutability) -> Self {
-        ctx.as_context_mut()
-            .store
-            .alloc_global(GlobalEntity::new(initial_value, mutability))
+    ///
+    /// # Errors
+    ///
+    /// If the [`ResourceLimiter`] of the store denies the allocation.
+    ///
+    /// [`ResourceLimiter`]: [`crate::ResourceLimiter`]
+    pub fn new(
+        mut ctx: impl AsContextMut,
+        initial_value: Value,
+        mutability: Mutability,
+    ) -> Result<Self, GlobalError> {
+        let store = ctx.as_context_mut().store;
+        if !store.can_create_global() {
+            return Err(GlobalError::ResourceLimitExceeded);
+        }
+        Ok(store.alloc_global(GlobalEntity::new(initial_value, mutability)))
     }
 
     /// Returns `true` if the global variable is mutable.
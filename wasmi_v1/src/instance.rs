@@ -1,46 +1,183 @@
 use super::{
-    engine::DedupFuncType,
+    engine::{DedupFuncType, FuncBodies},
+    module::{ModuleHash, ModuleNames},
+    ArenaIndex,
     AsContext,
     Extern,
     Func,
     Global,
-    Index,
     Memory,
     StoreContext,
     Stored,
     Table,
+    Tag,
 };
 use alloc::{
+    boxed::Box,
     collections::{btree_map, BTreeMap},
     string::{String, ToString},
+    sync::Arc,
     vec::Vec,
 };
 use core::{iter::FusedIterator, ops::Deref};
+use wasmi_core::UntypedValue;
 
 /// A raw index to a module instance entity.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub struct InstanceIdx(usize);
+pub struct InstanceIdx(u64);
 
-impl Index for InstanceIdx {
-    fn into_usize(self) -> usize {
+impl ArenaIndex for InstanceIdx {
+    fn into_key(self) -> u64 {
         self.0
     }
 
-    fn from_usize(value: usize) -> Self {
-        Self(value)
+    fn from_key(key: u64) -> Self {
+        Self(key)
     }
 }
 
+/// A data segment of a module instance.
+///
+/// # Note
+///
+/// Active data segments and data segments dropped via `data.drop`
+/// are represented as empty data segments.
+#[derive(Debug)]
+pub struct DataSegmentEntity {
+    /// The bytes of the data segment or `None` if it has been dropped.
+    bytes: Option<Arc<[u8]>>,
+}
+
+impl DataSegmentEntity {
+    /// Creates a new [`DataSegmentEntity`] from the given `bytes`.
+    pub fn new(bytes: Arc<[u8]>) -> Self {
+        Self { bytes: Some(bytes) }
+    }
+
+    /// Creates a new dropped [`DataSegmentEntity`].
+    pub fn dropped() -> Self {
+        Self { bytes: None }
+    }
+
+    /// Returns the bytes of the [`DataSegmentEntity`].
+    ///
+    /// Returns an empty slice if the [`DataSegmentEntity`] has been dropped.
+    pub fn bytes(&self) -> &[u8] {
+        self.bytes.as_deref().unwrap_or(&[])
+    }
+
+    /// Drops the bytes of the [`DataSegmentEntity`].
+    pub fn drop_bytes(&mut self) {
+        self.bytes = None;
+    }
+
+    /// Returns `true` if the [`DataSegmentEntity`] has been dropped.
+    pub fn is_dropped(&self) -> bool {
+        self.bytes.is_none()
+    }
+}
+
+/// An element segment of a module instance.
+///
+/// # Note
+///
+/// Active and declared element segments as well as element segments
+/// dropped via `elem.drop` are represented as empty element segments.
+#[derive(Debug)]
+pub struct ElementSegmentEntity {
+    /// The resolved references of the element segment.
+    ///
+    /// # Note
+    ///
+    /// The references are stored in their untyped encoding
+    /// which allows to efficiently initialize tables with them.
+    items: Box<[UntypedValue]>,
+}
+
+impl ElementSegmentEntity {
+    /// Creates a new [`ElementSegmentEntity`] from the given resolved `items`.
+    pub fn new(items: Box<[UntypedValue]>) -> Self {
+        Self { items }
+    }
+
+    /// Creates a new dropped [`ElementSegmentEntity`].
+    pub fn dropped() -> Self {
+        Self {
+            items: Box::default(),
+        }
+    }
+
+    /// Returns the items of the [`ElementSegmentEntity`].
+    ///
+    /// Returns an empty slice if the [`ElementSegmentEntity`] has been dropped.
+    pub fn items(&self) -> &[UntypedValue] {
+        &self.items[..]
+    }
+
+    /// Drops the items of the [`ElementSegmentEntity`].
+    pub fn drop_items(&mut self) {
+        self.items = Box::default();
+    }
+
+    /// Returns `true` if the [`ElementSegmentEntity`] has been dropped.
+    ///
+    /// # Note
+    ///
+    /// Empty element segments cannot be told apart from dropped ones
+    /// which is fine since both behave the same.
+    pub fn is_dropped(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+/// The amount of imported entities of an [`InstanceEntity`] per kind.
+///
+/// # Note
+///
+/// All entities that follow the imported entities are owned by the [`InstanceEntity`].
+#[derive(Debug, Default, Copy, Clone)]
+struct LenImported {
+    funcs: usize,
+    tables: usize,
+    memories: usize,
+    globals: usize,
+    tags: usize,
+}
+
+/// The entities owned by an [`InstanceEntity`].
+///
+/// # Note
+///
+/// Owned entities have been allocated upon instantiation
+/// as opposed to entities that have been imported.
+#[derive(Debug)]
+pub struct OwnedEntities<'a> {
+    pub funcs: &'a [Func],
+    pub tables: &'a [Table],
+    pub memories: &'a [Memory],
+    pub globals: &'a [Global],
+    pub tags: &'a [Tag],
+}
+
 /// A module instance entity.
 #[derive(Debug)]
 pub struct InstanceEntity {
     initialized: bool,
+    /// The hash of the instantiated module or `None` if uninitialized.
+    module_hash: Option<ModuleHash>,
+    len_imported: LenImported,
+    /// Keeps the Wasm function bodies of the instantiated module alive.
+    func_bodies: Option<Arc<FuncBodies>>,
     func_types: Vec<DedupFuncType>,
     tables: Vec<Table>,
     funcs: Vec<Func>,
     memories: Vec<Memory>,
     globals: Vec<Global>,
+    tags: Vec<Tag>,
+    data_segments: Vec<DataSegmentEntity>,
+    element_segments: Vec<ElementSegmentEntity>,
     exports: BTreeMap<String, Extern>,
+    names: Arc<ModuleNames>,
 }
 
 impl InstanceEntity {
@@ -48,12 +185,19 @@ impl InstanceEntity {
     pub(crate) fn uninitialized() -> InstanceEntity {
         Self {
             initialized: false,
+            module_hash: None,
+            len_imported: LenImported::default(),
+            func_bodies: None,
             func_types: Vec::new(),
             tables: Vec::new(),
             funcs: Vec::new(),
             memories: Vec::new(),
             globals: Vec::new(),
+            tags: Vec::new(),
+            data_segments: Vec::new(),
+            element_segments: Vec::new(),
             exports: BTreeMap::new(),
+            names: Arc::default(),
         }
     }
 
@@ -62,12 +206,19 @@ impl InstanceEntity {
         InstanceEntityBuilder {
             instance: Self {
                 initialized: false,
+                module_hash: None,
+                len_imported: LenImported::default(),
+                func_bodies: None,
                 func_types: Vec::default(),
                 tables: Vec::default(),
                 funcs: Vec::default(),
                 memories: Vec::default(),
                 globals: Vec::default(),
+                tags: Vec::default(),
+                data_segments: Vec::default(),
+                element_segments: Vec::default(),
                 exports: BTreeMap::default(),
+                names: Arc::default(),
             },
         }
     }
@@ -77,6 +228,58 @@ impl InstanceEntity {
         self.initialized
     }
 
+    /// Returns the hash of the module instantiated by the [`InstanceEntity`].
+    pub(crate) fn module_hash(&self) -> Option<ModuleHash> {
+        self.module_hash
+    }
+
+    /// Returns the entities owned by the [`InstanceEntity`].
+    pub(crate) fn owned_entities(&self) -> OwnedEntities<'_> {
+        let len_imported = self.len_imported;
+        OwnedEntities {
+            funcs: &self.funcs[len_imported.funcs..],
+            tables: &self.tables[len_imported.tables..],
+            memories: &self.memories[len_imported.memories..],
+            globals: &self.globals[len_imported.globals..],
+            tags: &self.tags[len_imported.tags..],
+        }
+    }
+
+    /// Returns `true` if the [`InstanceEntity`] imports any of the tables,
+    /// linear memories, global variables or exception tags of `owned`.
+    pub(crate) fn imports_any(&self, owned: &OwnedEntities) -> bool {
+        /// Returns `true` if any of the `imported` entities is found in `owned`.
+        fn contains_any<T, Idx>(
+            imported: &[T],
+            owned: &[T],
+            into_inner: impl Fn(T) -> Stored<Idx>,
+        ) -> bool
+        where
+            T: Copy,
+            Idx: PartialEq,
+        {
+            imported.iter().any(|imported| {
+                owned
+                    .iter()
+                    .any(|owned| into_inner(*owned) == into_inner(*imported))
+            })
+        }
+        let len_imported = self.len_imported;
+        contains_any(
+            &self.tables[..len_imported.tables],
+            owned.tables,
+            Table::into_inner,
+        ) || contains_any(
+            &self.memories[..len_imported.memories],
+            owned.memories,
+            Memory::into_inner,
+        ) || contains_any(
+            &self.globals[..len_imported.globals],
+            owned.globals,
+            Global::into_inner,
+        ) || contains_any(&self.tags[..len_imported.tags], owned.tags, Tag::into_inner)
+    }
+
     /// Returns the linear memory at the `index` if any.
     pub(crate) fn get_memory(&self, index: u32) -> Option<Memory> {
         self.memories.get(index as usize).copied()
@@ -92,16 +295,67 @@ impl InstanceEntity {
         self.globals.get(index as usize).copied()
     }
 
+    /// Returns the exception tag at the `index` if any.
+    pub(crate) fn get_tag(&self, index: u32) -> Option<Tag> {
+        self.tags.get(index as usize).copied()
+    }
+
     /// Returns the function at the `index` if any.
     pub(crate) fn get_func(&self, index: u32) -> Option<Func> {
         self.funcs.get(index as usize).copied()
     }
 
+    /// Returns the function index of the `func` within the [`InstanceEntity`] if any.
+    pub(crate) fn get_func_index(&self, func: Func) -> Option<u32> {
+        self.funcs
+            .iter()
+            .position(|f| *f == func)
+            .map(|index| index as u32)
+    }
+
+    /// Returns the name of the function at the `index` if any.
+    pub(crate) fn get_func_name(&self, index: u32) -> Option<&str> {
+        self.names.func(index)
+    }
+
     /// Returns the signature at the `index` if any.
     pub(crate) fn get_signature(&self, index: u32) -> Option<DedupFuncType> {
         self.func_types.get(index as usize).copied()
     }
 
+    /// Returns the data segment at the `index` if any.
+    pub(crate) fn get_data_segment(&self, index: u32) -> Option<&DataSegmentEntity> {
+        self.data_segments.get(index as usize)
+    }
+
+    /// Returns an exclusive reference to the data segment at the `index` if any.
+    pub(crate) fn get_data_segment_mut(&mut self, index: u32) -> Option<&mut DataSegmentEntity> {
+        self.data_segments.get_mut(index as usize)
+    }
+
+    /// Returns the element segment at the `index` if any.
+    pub(crate) fn get_element_segment(&self, index: u32) -> Option<&ElementSegmentEntity> {
+        self.element_segments.get(index as usize)
+    }
+
+    /// Returns the data segments of the [`InstanceEntity`].
+    pub(crate) fn data_segments(&self) -> &[DataSegmentEntity] {
+        &self.data_segments[..]
+    }
+
+    /// Returns the element segments of the [`InstanceEntity`].
+    pub(crate) fn element_segments(&self) -> &[ElementSegmentEntity] {
+        &self.element_segments[..]
+    }
+
+    /// Returns an exclusive reference to the element segment at the `index` if any.
+    pub(crate) fn get_element_segment_mut(
+        &mut self,
+        index: u32,
+    ) -> Option<&mut ElementSegmentEntity> {
+        self.element_segments.get_mut(index as usize)
+    }
+
     /// Returns the value exported to the given `name` if any.
     pub(crate) fn get_export(&self, name: &str) -> Option<Extern> {
         self.exports.get(name).copied()
@@ -178,11 +432,54 @@ impl InstanceEntityBuilder {
         self.instance.globals.push(global);
     }
 
+    /// Pushes a new [`Tag`] to the [`InstanceEntity`] under construction.
+    pub(crate) fn push_tag(&mut self, tag: Tag) {
+        self.instance.tags.push(tag);
+    }
+
+    /// Marks all entities pushed so far as imported by the [`InstanceEntity`] under construction.
+    ///
+    /// All entities pushed afterwards are owned by the [`InstanceEntity`].
+    pub(crate) fn finish_imports(&mut self) {
+        self.instance.len_imported = LenImported {
+            funcs: self.instance.funcs.len(),
+            tables: self.instance.tables.len(),
+            memories: self.instance.memories.len(),
+            globals: self.instance.globals.len(),
+            tags: self.instance.tags.len(),
+        };
+    }
+
+    /// Sets the Wasm function bodies of the module of the [`InstanceEntity`] under construction.
+    pub(crate) fn set_func_bodies(&mut self, func_bodies: Arc<FuncBodies>) {
+        self.instance.func_bodies = Some(func_bodies);
+    }
+
+    /// Sets the hash of the module of the [`InstanceEntity`] under construction.
+    pub(crate) fn set_module_hash(&mut self, module_hash: ModuleHash) {
+        self.instance.module_hash = Some(module_hash);
+    }
+
+    /// Sets the debug names of the [`InstanceEntity`] under construction.
+    pub(crate) fn set_names(&mut self, names: Arc<ModuleNames>) {
+        self.instance.names = names;
+    }
+
     /// Pushes a new [`Func`] to the [`InstanceEntity`] under construction.
     pub(crate) fn push_func(&mut self, func: Func) {
         self.instance.funcs.push(func);
     }
 
+    /// Pushes a new [`DataSegmentEntity`] to the [`InstanceEntity`] under construction.
+    pub(crate) fn push_data_segment(&mut self, segment: DataSegmentEntity) {
+        self.instance.data_segments.push(segment);
+    }
+
+    /// Pushes a new [`ElementSegmentEntity`] to the [`InstanceEntity`] under construction.
+    pub(crate) fn push_element_segment(&mut self, segment: ElementSegmentEntity) {
+        self.instance.element_segments.push(segment);
+    }
+
     /// Pushes a new deduplicated [`FuncType`] to the [`InstanceEntity`]
     /// under construction.
     ///
@@ -282,6 +579,19 @@ impl Instance {
             .get_global(index)
     }
 
+    /// Returns the exception tag at the `index` if any.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `store` does not own this [`Instance`].
+    pub(crate) fn get_tag(&self, store: impl AsContext, index: u32) -> Option<Tag> {
+        store
+            .as_context()
+            .store
+            .resolve_instance(*self)
+            .get_tag(index)
+    }
+
     /// Returns the function at the `index` if any.
     ///
     /// # Panics
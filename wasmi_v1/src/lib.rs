@@ -16,16 +16,23 @@ mod foreach_tuple;
 mod arena;
 mod engine;
 mod error;
+mod exception;
+mod extensions;
 mod external;
+mod externref;
 mod func;
 mod func_type;
 mod global;
 mod instance;
+mod limits;
 mod linker;
 mod memory;
 mod module;
+mod snapshot;
 mod store;
 mod table;
+mod tag;
+mod value;
 
 /// Definitions from the `wasmi_core` crate.
 #[doc(inline)]
@@ -38,32 +45,58 @@ pub mod errors {
         global::GlobalError,
         linker::LinkerError,
         memory::MemoryError,
-        module::{InstantiationError, ModuleError},
+        module::{DeserializeError, InstantiationError, ModuleError, SerializeError},
+        snapshot::SnapshotError,
+        store::{FuelError, RemoveInstanceError},
         table::TableError,
+        tag::TagError,
     };
 }
 
+#[cfg(feature = "threads")]
+pub use self::memory::{ParkResult, Parker, ParkingSpot, SharedMemory};
 use self::{
-    arena::{GuardedEntity, Index},
-    engine::FuncBody,
+    arena::{ArenaIndex, GuardedEntity, Index},
+    externref::{ExternObject, ExternObjectEntity, ExternObjectIdx},
     func::{FuncEntity, FuncEntityInternal, FuncIdx},
     global::{GlobalEntity, GlobalIdx},
-    instance::{InstanceEntity, InstanceEntityBuilder, InstanceIdx},
-    memory::{MemoryEntity, MemoryIdx},
+    instance::{
+        DataSegmentEntity,
+        ElementSegmentEntity,
+        InstanceEntity,
+        InstanceEntityBuilder,
+        InstanceIdx,
+    },
+    memory::{MemoryEntity, MemoryIdx, MemoryImage},
     store::Stored,
     table::{TableEntity, TableIdx},
+    tag::{TagEntity, TagIdx},
 };
 pub use self::{
-    engine::{Config, Engine},
+    engine::{Config, Engine, ResumableCall, ResumableInvocation},
     error::Error,
+    exception::Exception,
     external::Extern,
-    func::{Caller, Func, TypedFunc, WasmParams, WasmResults},
+    externref::ExternRef,
+    func::{Caller, Func, FuncRef, TypedFunc, WasmParams, WasmResults},
     func_type::FuncType,
     global::{Global, GlobalType, Mutability},
     instance::{ExportsIter, Instance},
+    limits::{
+        ResourceLimiter,
+        StoreLimits,
+        StoreLimitsBuilder,
+        DEFAULT_GLOBAL_LIMIT,
+        DEFAULT_INSTANCE_LIMIT,
+        DEFAULT_MEMORY_LIMIT,
+        DEFAULT_TABLE_LIMIT,
+    },
     linker::Linker,
     memory::{Memory, MemoryType},
     module::{InstancePre, Module, ModuleError, Read},
+    snapshot::InstanceSnapshot,
     store::{AsContext, AsContextMut, Store, StoreContext, StoreContextMut},
-    table::{Table, TableType},
+    table::{RefType, Table, TableType},
+    tag::{Tag, TagType},
+    value::{FromValue, Value},
 };
@@ -0,0 +1,237 @@
+/// The default limit for the number of instances of a [`Store`].
+///
+/// [`Store`]: [`crate::Store`]
+pub const DEFAULT_INSTANCE_LIMIT: usize = 10_000;
+
+/// The default limit for the number of tables of a [`Store`].
+///
+/// [`Store`]: [`crate::Store`]
+pub const DEFAULT_TABLE_LIMIT: usize = 10_000;
+
+/// The default limit for the number of linear memories of a [`Store`].
+///
+/// [`Store`]: [`crate::Store`]
+pub const DEFAULT_MEMORY_LIMIT: usize = 10_000;
+
+/// The default limit for the number of global variables of a [`Store`].
+///
+/// [`Store`]: [`crate::Store`]
+pub const DEFAULT_GLOBAL_LIMIT: usize = 100_000;
+
+/// Used by the host to limit the resources a [`Store`] may consume.
+///
+/// # Note
+///
+/// A [`Store`] consults its [`ResourceLimiter`] upon instantiating Wasm modules,
+/// upon creating linear memories, tables and global variables from the host via
+/// [`Memory::new`], [`Table::new`] and [`Global::new`] as well as upon growing
+/// linear memories and tables either from Wasm via `memory.grow` and `table.grow`
+/// or from the host via [`Memory::grow`] and [`Table::grow`].
+///
+/// Use [`Store::limiter`] to install a [`ResourceLimiter`].
+///
+/// [`Store`]: [`crate::Store`]
+/// [`Store::limiter`]: [`crate::Store::limiter`]
+/// [`Memory::new`]: [`crate::Memory::new`]
+/// [`Table::new`]: [`crate::Table::new`]
+/// [`Global::new`]: [`crate::Global::new`]
+/// [`Memory::grow`]: [`crate::Memory::grow`]
+/// [`Table::grow`]: [`crate::Table::grow`]
+pub trait ResourceLimiter {
+    /// Returns `true` if a linear memory may grow from `current` to `desired` bytes.
+    ///
+    /// The `maximum` is the maximum size in bytes of the linear memory if any.
+    ///
+    /// # Note
+    ///
+    /// Newly created linear memories grow from `0` bytes to their initial size.
+    fn memory_growing(&mut self, current: usize, desired: usize, maximum: Option<usize>) -> bool;
+
+    /// Returns `true` if a table may grow from `current` to `desired` elements.
+    ///
+    /// The `maximum` is the maximum number of elements of the table if any.
+    ///
+    /// # Note
+    ///
+    /// Newly created tables grow from `0` elements to their initial size.
+    fn table_growing(&mut self, current: usize, desired: usize, maximum: Option<usize>) -> bool;
+
+    /// Returns `true` if denied `memory.grow` and `table.grow` operations trap.
+    ///
+    /// Otherwise denied growth operations return `-1` to Wasm as the
+    /// WebAssembly specification requires for failed growth operations.
+    ///
+    /// Returns `false` by default.
+    fn trap_on_grow_failure(&self) -> bool {
+        false
+    }
+
+    /// Returns the maximum number of instances a [`Store`] may hold.
+    ///
+    /// Returns [`DEFAULT_INSTANCE_LIMIT`] by default.
+    ///
+    /// [`Store`]: [`crate::Store`]
+    fn instances(&self) -> usize {
+        DEFAULT_INSTANCE_LIMIT
+    }
+
+    /// Returns the maximum number of tables a [`Store`] may hold.
+    ///
+    /// Returns [`DEFAULT_TABLE_LIMIT`] by default.
+    ///
+    /// [`Store`]: [`crate::Store`]
+    fn tables(&self) -> usize {
+        DEFAULT_TABLE_LIMIT
+    }
+
+    /// Returns the maximum number of linear memories a [`Store`] may hold.
+    ///
+    /// Returns [`DEFAULT_MEMORY_LIMIT`] by default.
+    ///
+    /// [`Store`]: [`crate::Store`]
+    fn memories(&self) -> usize {
+        DEFAULT_MEMORY_LIMIT
+    }
+
+    /// Returns the maximum number of global variables a [`Store`] may hold.
+    ///
+    /// Returns [`DEFAULT_GLOBAL_LIMIT`] by default.
+    ///
+    /// [`Store`]: [`crate::Store`]
+    fn globals(&self) -> usize {
+        DEFAULT_GLOBAL_LIMIT
+    }
+}
+
+/// A [`ResourceLimiter`] with static limits.
+///
+/// Use the [`StoreLimitsBuilder`] to create new [`StoreLimits`].
+#[derive(Debug, Clone)]
+pub struct StoreLimits {
+    /// The maximum size in bytes of each linear memory if any.
+    memory_size: Option<usize>,
+    /// The maximum number of elements of each table if any.
+    table_elements: Option<usize>,
+    /// The maximum number of instances.
+    instances: usize,
+    /// The maximum number of tables.
+    tables: usize,
+    /// The maximum number of linear memories.
+    memories: usize,
+    /// The maximum number of global variables.
+    globals: usize,
+    /// Whether denied growth operations trap.
+    trap_on_grow_failure: bool,
+}
+
+impl Default for StoreLimits {
+    fn default() -> Self {
+        Self {
+            memory_size: None,
+            table_elements: None,
+            instances: DEFAULT_INSTANCE_LIMIT,
+            tables: DEFAULT_TABLE_LIMIT,
+            memories: DEFAULT_MEMORY_LIMIT,
+            globals: DEFAULT_GLOBAL_LIMIT,
+            trap_on_grow_failure: false,
+        }
+    }
+}
+
+impl ResourceLimiter for StoreLimits {
+    fn memory_growing(&mut self, _current: usize, desired: usize, _maximum: Option<usize>) -> bool {
+        desired <= self.memory_size.unwrap_or(usize::MAX)
+    }
+
+    fn table_growing(&mut self, _current: usize, desired: usize, _maximum: Option<usize>) -> bool {
+        desired <= self.table_elements.unwrap_or(usize::MAX)
+    }
+
+    fn trap_on_grow_failure(&self) -> bool {
+        self.trap_on_grow_failure
+    }
+
+    fn instances(&self) -> usize {
+        self.instances
+    }
+
+    fn tables(&self) -> usize {
+        self.tables
+    }
+
+    fn memories(&self) -> usize {
+        self.memories
+    }
+
+    fn globals(&self) -> usize {
+        self.globals
+    }
+}
+
+/// Used to build [`StoreLimits`].
+#[derive(Debug, Default, Clone)]
+pub struct StoreLimitsBuilder {
+    limits: StoreLimits,
+}
+
+impl StoreLimitsBuilder {
+    /// Creates a new [`StoreLimitsBuilder`] with the default limits.
+    ///
+    /// # Note
+    ///
+    /// By default the sizes of linear memories and tables are not limited
+    /// and the number of instances, tables, linear memories and global
+    /// variables are limited by their respective default limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the size in bytes of each linear memory to `limit`.
+    pub fn memory_size(mut self, limit: usize) -> Self {
+        self.limits.memory_size = Some(limit);
+        self
+    }
+
+    /// Limits the number of elements of each table to `limit`.
+    pub fn table_elements(mut self, limit: usize) -> Self {
+        self.limits.table_elements = Some(limit);
+        self
+    }
+
+    /// Limits the number of instances to `limit`.
+    pub fn instances(mut self, limit: usize) -> Self {
+        self.limits.instances = limit;
+        self
+    }
+
+    /// Limits the number of tables to `limit`.
+    pub fn tables(mut self, limit: usize) -> Self {
+        self.limits.tables = limit;
+        self
+    }
+
+    /// Limits the number of linear memories to `limit`.
+    pub fn memories(mut self, limit: usize) -> Self {
+        self.limits.memories = limit;
+        self
+    }
+
+    /// Limits the number of global variables to `limit`.
+    pub fn globals(mut self, limit: usize) -> Self {
+        self.limits.globals = limit;
+        self
+    }
+
+    /// Configures whether denied `memory.grow` and `table.grow` operations trap.
+    ///
+    /// By default denied growth operations return `-1` to Wasm.
+    pub fn trap_on_grow_failure(mut self, enable: bool) -> Self {
+        self.limits.trap_on_grow_failure = enable;
+        self
+    }
+
+    /// Returns the built [`StoreLimits`].
+    pub fn build(self) -> StoreLimits {
+        self.limits
+    }
+}
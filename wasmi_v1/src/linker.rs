@@ -1,16 +1,26 @@
 use super::{
     errors::{MemoryError, TableError},
+    AsContext,
     AsContextMut,
+    Caller,
     Error,
     Extern,
+    Func,
+    Instance,
     InstancePre,
     Module,
 };
 use crate::{
+    core::Trap,
+    func::{HostFuncTrampoline, IntoFunc},
     module::{ImportName, ModuleImport, ModuleImportType},
     FuncType,
     GlobalType,
+    TagType,
+    Value,
 };
+#[cfg(feature = "threads")]
+use crate::{Memory, SharedMemory};
 use alloc::{
     collections::{btree_map::Entry, BTreeMap},
     sync::Arc,
@@ -36,6 +46,30 @@ pub enum LinkerError {
         /// This refers to the second inserted item.
         import_item: Extern,
     },
+    /// Encountered duplicate shared linear memory definitions for the same name.
+    #[cfg(feature = "threads")]
+    DuplicateSharedMemoryDefinition {
+        /// The duplicate import name of the definition.
+        import_name: ImportName,
+        /// The duplicated shared linear memory.
+        ///
+        /// This refers to the second inserted shared linear memory.
+        import_item: SharedMemory,
+    },
+    /// Encountered duplicate host function definitions for the same name.
+    DuplicateHostFuncDefinition {
+        /// The duplicate import name of the definition.
+        import_name: ImportName,
+        /// The function type of the duplicated host function.
+        ///
+        /// This refers to the second inserted host function.
+        func_type: FuncType,
+    },
+    /// Encountered when no definition for an aliased name is found.
+    CannotFindDefinitionForAlias {
+        /// The name for which no definition was found.
+        name: ImportName,
+    },
     /// Encountered when no definition for an import is found.
     CannotFindDefinitionForImport {
         /// The name of the import for which no definition was found.
@@ -69,6 +103,15 @@ pub enum LinkerError {
         /// The actual global variable type found.
         actual: GlobalType,
     },
+    /// Encountered when an imported exception tag has a mismatching tag type.
+    TagTypeMismatch {
+        /// The name of the import with the mismatched type.
+        name: ImportName,
+        /// The expected exception tag type.
+        expected: TagType,
+        /// The actual exception tag type found.
+        actual: TagType,
+    },
 }
 
 impl LinkerError {
@@ -109,6 +152,30 @@ impl Display for LinkerError {
                     import_name, import_item
                 )
             }
+            #[cfg(feature = "threads")]
+            Self::DuplicateSharedMemoryDefinition {
+                import_name,
+                import_item,
+            } => {
+                write!(
+                    f,
+                    "encountered duplicate definition `{}` of {:?}",
+                    import_name, import_item
+                )
+            }
+            Self::DuplicateHostFuncDefinition {
+                import_name,
+                func_type,
+            } => {
+                write!(
+                    f,
+                    "encountered duplicate host function definition `{}` of {:?}",
+                    import_name, func_type
+                )
+            }
+            Self::CannotFindDefinitionForAlias { name } => {
+                write!(f, "cannot find definition for alias {}", name)
+            }
             Self::CannotFindDefinitionForImport { name, item_type } => {
                 write!(
                     f,
@@ -138,6 +205,17 @@ impl Display for LinkerError {
                     name, expected, actual
                 )
             }
+            Self::TagTypeMismatch {
+                name,
+                expected,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "exception tag type mismatch for import {}: expected {:?} but found {:?}",
+                    name, expected, actual
+                )
+            }
             Self::Table(error) => Display::fmt(error, f),
             Self::Memory(error) => Display::fmt(error, f),
         }
@@ -228,12 +306,98 @@ struct ImportKey {
     name: Option<Symbol>,
 }
 
+/// A definition stored in a [`Linker`].
+enum Definition<T> {
+    /// An extern item that belongs to a single store.
+    Extern(Extern),
+    /// A host function that is allocated in the store used for instantiation.
+    HostFunc(HostFuncDefinition<T>),
+    /// A shared linear memory that can be imported by instances of any store.
+    #[cfg(feature = "threads")]
+    SharedMemory(SharedMemory),
+}
+
+impl<T> Clone for Definition<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Extern(item) => Self::Extern(*item),
+            Self::HostFunc(func) => Self::HostFunc(func.clone()),
+            #[cfg(feature = "threads")]
+            Self::SharedMemory(memory) => Self::SharedMemory(memory.clone()),
+        }
+    }
+}
+
+impl<T> Debug for Definition<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Extern(item) => f.debug_tuple("Extern").field(item).finish(),
+            Self::HostFunc(func) => f.debug_tuple("HostFunc").field(func).finish(),
+            #[cfg(feature = "threads")]
+            Self::SharedMemory(memory) => f.debug_tuple("SharedMemory").field(memory).finish(),
+        }
+    }
+}
+
+impl<T> Definition<T> {
+    /// Returns the extern item if the definition is an extern item.
+    fn as_extern(&self) -> Option<Extern> {
+        match self {
+            Self::Extern(item) => Some(*item),
+            Self::HostFunc(_) => None,
+            #[cfg(feature = "threads")]
+            Self::SharedMemory(_) => None,
+        }
+    }
+}
+
+/// A host function defined via [`Linker::func_wrap`].
+///
+/// Unlike a [`Func`] this does not belong to a single store.
+struct HostFuncDefinition<T> {
+    /// The function type of the host function.
+    func_type: FuncType,
+    /// The trampoline calling the host function closure.
+    trampoline: HostFuncTrampoline<T>,
+}
+
+impl<T> HostFuncDefinition<T> {
+    /// Returns the host function allocated in the store of `context`.
+    ///
+    /// # Note
+    ///
+    /// The host function is allocated upon its first use in a store
+    /// and reused afterwards.
+    fn to_func(&self, context: impl AsContextMut<UserState = T>) -> Func {
+        Func::new_host(context, self.func_type.clone(), self.trampoline.clone())
+    }
+}
+
+impl<T> Clone for HostFuncDefinition<T> {
+    fn clone(&self) -> Self {
+        Self {
+            func_type: self.func_type.clone(),
+            trampoline: self.trampoline.clone(),
+        }
+    }
+}
+
+impl<T> Debug for HostFuncDefinition<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(&self.func_type, f)
+    }
+}
+
 /// A linker used to define module imports and instantiate module instances.
 pub struct Linker<T> {
     /// Allows to efficiently store strings and deduplicate them..
     strings: StringInterner,
     /// Stores the definitions given their names.
-    definitions: BTreeMap<ImportKey, Extern>,
+    definitions: BTreeMap<ImportKey, Definition<T>>,
+    /// Whether redefining an existing name shadows its previous definition.
+    ///
+    /// If this is `false` redefining an existing name is an error.
+    allow_shadowing: bool,
     /// Reusable buffer to be used for module instantiations.
     ///
     /// Helps to avoid heap memory allocations at the cost of a small
@@ -247,6 +411,7 @@ impl<T> Debug for Linker<T> {
         f.debug_struct("Linker")
             .field("strings", &self.strings)
             .field("definitions", &self.definitions)
+            .field("allow_shadowing", &self.allow_shadowing)
             .finish()
     }
 }
@@ -256,6 +421,7 @@ impl<T> Clone for Linker<T> {
         Self {
             strings: self.strings.clone(),
             definitions: self.definitions.clone(),
+            allow_shadowing: self.allow_shadowing,
             externals: Vec::new(),
             _marker: self._marker,
         }
@@ -274,11 +440,23 @@ impl<T> Linker<T> {
         Self {
             strings: StringInterner::default(),
             definitions: BTreeMap::default(),
+            allow_shadowing: false,
             externals: Vec::new(),
             _marker: PhantomData,
         }
     }
 
+    /// Configures whether definitions may shadow previous definitions of the same name.
+    ///
+    /// # Note
+    ///
+    /// Shadowing is disallowed by default in which case redefining
+    /// an existing name results in a [`LinkerError`].
+    pub fn allow_shadowing(&mut self, allow: bool) -> &mut Self {
+        self.allow_shadowing = allow;
+        self
+    }
+
     /// Define a new item in this [`Linker`].
     pub fn define(
         &mut self,
@@ -287,7 +465,122 @@ impl<T> Linker<T> {
         item: impl Into<Extern>,
     ) -> Result<&mut Self, LinkerError> {
         let key = self.import_key(module, Some(name));
-        self.insert(key, item.into())?;
+        self.insert(key, Definition::Extern(item.into()))?;
+        Ok(self)
+    }
+
+    /// Define a new [`SharedMemory`] in this [`Linker`].
+    ///
+    /// # Note
+    ///
+    /// Unlike the items defined via [`Linker::define`] a [`SharedMemory`]
+    /// does not belong to a single store. Therefore the same [`Linker`] can
+    /// be used to instantiate Wasm modules importing the [`SharedMemory`]
+    /// in multiple stores.
+    #[cfg(feature = "threads")]
+    pub fn define_shared_memory(
+        &mut self,
+        module: &str,
+        name: &str,
+        memory: SharedMemory,
+    ) -> Result<&mut Self, LinkerError> {
+        let key = self.import_key(module, Some(name));
+        self.insert(key, Definition::SharedMemory(memory))?;
+        Ok(self)
+    }
+
+    /// Define a new host function from the given closure in this [`Linker`].
+    ///
+    /// # Note
+    ///
+    /// Unlike [`Func::wrap`] this does not require a store. The host function
+    /// is allocated in the store used for instantiation whenever it is imported
+    /// by a Wasm module. Therefore the same [`Linker`] can be used to instantiate
+    /// Wasm modules importing the host function in multiple stores.
+    pub fn func_wrap<Params, Results>(
+        &mut self,
+        module: &str,
+        name: &str,
+        func: impl IntoFunc<T, Params, Results>,
+    ) -> Result<&mut Self, LinkerError> {
+        let (func_type, trampoline) = func.into_func();
+        let key = self.import_key(module, Some(name));
+        self.insert(
+            key,
+            Definition::HostFunc(HostFuncDefinition {
+                func_type,
+                trampoline,
+            }),
+        )?;
+        Ok(self)
+    }
+
+    /// Define a new dynamically typed host function in this [`Linker`].
+    ///
+    /// # Note
+    ///
+    /// This is the [`Linker`] equivalent of [`Func::new`] and, like
+    /// [`Linker::func_wrap`], does not require a store.
+    pub fn func_new(
+        &mut self,
+        module: &str,
+        name: &str,
+        func_type: FuncType,
+        func: impl Fn(Caller<T>, &[Value], &mut [Value]) -> Result<(), Trap> + Send + Sync + 'static,
+    ) -> Result<&mut Self, LinkerError> {
+        let trampoline = HostFuncTrampoline::new_dynamic(func_type.clone(), func);
+        let key = self.import_key(module, Some(name));
+        self.insert(
+            key,
+            Definition::HostFunc(HostFuncDefinition {
+                func_type,
+                trampoline,
+            }),
+        )?;
+        Ok(self)
+    }
+
+    /// Defines all exports of the [`Instance`] under the `module_name` in this [`Linker`].
+    ///
+    /// # Errors
+    ///
+    /// If any of the exports of the [`Instance`] is already defined under the
+    /// `module_name` and shadowing is not allowed.
+    pub fn instance(
+        &mut self,
+        context: impl AsContext,
+        module_name: &str,
+        instance: Instance,
+    ) -> Result<&mut Self, LinkerError> {
+        for (name, item) in instance.exports(&context) {
+            let key = self.import_key(module_name, Some(name));
+            self.insert(key, Definition::Extern(*item))?;
+        }
+        Ok(self)
+    }
+
+    /// Defines the definition of `module` and `name` also under `as_module` and `as_name`.
+    ///
+    /// # Errors
+    ///
+    /// - If there is no definition for `module` and `name` in this [`Linker`].
+    /// - If there already is a definition for `as_module` and `as_name`
+    ///   and shadowing is not allowed.
+    pub fn alias(
+        &mut self,
+        module: &str,
+        name: &str,
+        as_module: &str,
+        as_name: &str,
+    ) -> Result<&mut Self, LinkerError> {
+        let definition = self
+            .resolve_definition(module, Some(name))
+            .cloned()
+            .ok_or_else(|| LinkerError::CannotFindDefinitionForAlias {
+                name: ImportName::new(module, Some(name)),
+            })?;
+        let key = self.import_key(as_module, Some(as_name));
+        self.insert(key, definition)?;
         Ok(self)
     }
 
@@ -310,21 +603,38 @@ impl<T> Linker<T> {
         Some((module_name, item_name))
     }
 
-    /// Inserts the extern item under the import key.
+    /// Inserts the definition under the import key.
     ///
     /// # Errors
     ///
-    /// If there already is a definition for the import key for this [`Linker`].
-    fn insert(&mut self, key: ImportKey, item: Extern) -> Result<(), LinkerError> {
+    /// If there already is a definition for the import key for this [`Linker`]
+    /// and shadowing is not allowed.
+    fn insert(&mut self, key: ImportKey, item: Definition<T>) -> Result<(), LinkerError> {
         match self.definitions.entry(key) {
+            Entry::Occupied(mut entry) if self.allow_shadowing => {
+                entry.insert(item);
+            }
             Entry::Occupied(_) => {
                 let (module_name, field_name) = self.resolve_import_key(key).unwrap_or_else(|| {
                     panic!("encountered missing import names for key {:?}", key)
                 });
                 let import_name = ImportName::new(module_name, field_name);
-                return Err(LinkerError::DuplicateDefinition {
-                    import_name,
-                    import_item: item,
+                return Err(match item {
+                    Definition::Extern(import_item) => LinkerError::DuplicateDefinition {
+                        import_name,
+                        import_item,
+                    },
+                    Definition::HostFunc(func) => LinkerError::DuplicateHostFuncDefinition {
+                        import_name,
+                        func_type: func.func_type,
+                    },
+                    #[cfg(feature = "threads")]
+                    Definition::SharedMemory(import_item) => {
+                        LinkerError::DuplicateSharedMemoryDefinition {
+                            import_name,
+                            import_item,
+                        }
+                    }
                 });
             }
             Entry::Vacant(v) => {
@@ -338,7 +648,19 @@ impl<T> Linker<T> {
     ///
     /// Returns `None` if this name was not previously defined in this
     /// [`Linker`].
+    ///
+    /// # Note
+    ///
+    /// Host functions defined via [`Linker::func_wrap`] and shared linear
+    /// memories defined via `Linker::define_shared_memory` are not extern
+    /// values and therefore are not returned.
     pub fn resolve(&self, module: &str, name: Option<&str>) -> Option<Extern> {
+        self.resolve_definition(module, name)
+            .and_then(Definition::as_extern)
+    }
+
+    /// Looks up a previously defined definition in this [`Linker`].
+    fn resolve_definition(&self, module: &str, name: Option<&str>) -> Option<&Definition<T>> {
         let key = ImportKey {
             module: self.strings.get(module)?,
             name: match name {
@@ -346,13 +668,48 @@ impl<T> Linker<T> {
                 None => None,
             },
         };
-        self.definitions.get(&key).copied()
+        self.definitions.get(&key)
+    }
+
+    /// Resolves the linear memory for the import.
+    ///
+    /// # Note
+    ///
+    /// Shared linear memories are instantiated as linear memories in the store
+    /// of `context` that operate on the shared bytes. The linear memory is
+    /// allocated upon its first use in a store and reused afterwards.
+    ///
+    /// # Errors
+    ///
+    /// If the [`ResourceLimiter`] of the store denies the new linear memory.
+    ///
+    /// [`ResourceLimiter`]: [`crate::ResourceLimiter`]
+    #[cfg(feature = "threads")]
+    fn resolve_memory(
+        &self,
+        context: impl AsContextMut,
+        module: &str,
+        name: Option<&str>,
+    ) -> Result<Option<Memory>, MemoryError> {
+        let memory = match self.resolve_definition(module, name) {
+            Some(Definition::Extern(item)) => item.into_memory(),
+            Some(Definition::SharedMemory(memory)) => {
+                Some(Memory::resolve_shared(context, memory)?)
+            }
+            Some(Definition::HostFunc(_)) | None => None,
+        };
+        Ok(memory)
     }
 
     /// Instantiates the given [`Module`] using the definitions in the [`Linker`].
+    ///
+    /// # Note
+    ///
+    /// Host functions defined via [`Linker::func_wrap`] that are imported
+    /// by the [`Module`] are allocated in the store of `context`.
     pub fn instantiate<'a>(
         &mut self,
-        context: impl AsContextMut,
+        mut context: impl AsContextMut<UserState = T>,
         module: &'a Module,
     ) -> Result<InstancePre<'a>, Error> {
         // Clear the cached externals buffer.
@@ -363,10 +720,28 @@ impl<T> Linker<T> {
             let field_name = import.field();
             let external = match import.item_type() {
                 ModuleImportType::Func(expected_func_type) => {
-                    let func = self
-                        .resolve(module_name, field_name)
-                        .and_then(Extern::into_func)
-                        .ok_or_else(|| LinkerError::cannot_find_definition_of_import(&import))?;
+                    let func = match self.resolve_definition(module_name, field_name) {
+                        Some(Definition::Extern(item)) => item.into_func(),
+                        Some(Definition::HostFunc(func)) => {
+                            // Check the function type before allocating the host
+                            // function so that mismatches do not leak it.
+                            let expected = context
+                                .as_context()
+                                .store
+                                .resolve_func_type(*expected_func_type);
+                            if func.func_type != expected {
+                                return Err(LinkerError::FuncTypeMismatch {
+                                    name: import.name().clone(),
+                                    expected,
+                                    actual: func.func_type.clone(),
+                                }
+                                .into());
+                            }
+                            Some(func.to_func(&mut context))
+                        }
+                        _ => None,
+                    }
+                    .ok_or_else(|| LinkerError::cannot_find_definition_of_import(&import))?;
                     let actual_func_type = func.signature(&context);
                     if &actual_func_type != expected_func_type {
                         return Err(LinkerError::FuncTypeMismatch {
@@ -394,9 +769,13 @@ impl<T> Linker<T> {
                     Extern::Table(table)
                 }
                 ModuleImportType::Memory(expected_memory_type) => {
+                    #[cfg(feature = "threads")]
+                    let memory = self.resolve_memory(&mut context, module_name, field_name)?;
+                    #[cfg(not(feature = "threads"))]
                     let memory = self
                         .resolve(module_name, field_name)
-                        .and_then(Extern::into_memory)
+                        .and_then(Extern::into_memory);
+                    let memory = memory
                         .ok_or_else(|| LinkerError::cannot_find_definition_of_import(&import))?;
                     let actual_memory_type = memory.memory_type(context.as_context());
                     actual_memory_type.satisfies(expected_memory_type)?;
@@ -418,6 +797,26 @@ impl<T> Linker<T> {
                     }
                     Extern::Global(global)
                 }
+                ModuleImportType::Tag(expected_tag_type) => {
+                    let tag = self
+                        .resolve(module_name, field_name)
+                        .and_then(Extern::into_tag)
+                        .ok_or_else(|| LinkerError::cannot_find_definition_of_import(&import))?;
+                    let actual_tag_type = tag.signature(&context);
+                    if &actual_tag_type != expected_tag_type {
+                        let resolve_tag_type = |tag_type| {
+                            let func_type = context.as_context().store.resolve_func_type(tag_type);
+                            TagType::from_func_type(&func_type)
+                        };
+                        return Err(LinkerError::TagTypeMismatch {
+                            name: import.name().clone(),
+                            expected: resolve_tag_type(*expected_tag_type),
+                            actual: resolve_tag_type(actual_tag_type),
+                        }
+                        .into());
+                    }
+                    Extern::Tag(tag)
+                }
             };
             self.externals.push(external);
         }
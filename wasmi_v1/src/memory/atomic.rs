@@ -0,0 +1,215 @@
+use super::{MemoryEntity, MemoryError, MemoryRepr};
+use core::{
+    mem,
+    sync::atomic::{AtomicU16, AtomicU32, AtomicU64, AtomicU8, Ordering},
+};
+use wasmi_core::LittleEndianConvert;
+
+/// An integer type that can be accessed atomically in linear memory.
+///
+/// # Note
+///
+/// This is used to implement the atomic instructions of the Wasm `threads` proposal
+/// which all operate on unsigned integers of 8, 16, 32 or 64 bits.
+pub trait AtomicInt: LittleEndianConvert + Copy + Eq {
+    /// Atomically loads the value at `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` is valid for reads and aligned to `Self`.
+    unsafe fn atomic_load(ptr: *mut u8) -> Self;
+
+    /// Atomically stores `value` at `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` is valid for writes and aligned to `Self`.
+    unsafe fn atomic_store(ptr: *mut u8, value: Self);
+
+    /// Atomically replaces the value at `ptr` with the result of `op` applied to it and `value`.
+    ///
+    /// Returns the value at `ptr` before the operation.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` is valid for reads and writes and aligned to `Self`.
+    unsafe fn atomic_rmw(ptr: *mut u8, value: Self, op: fn(Self, Self) -> Self) -> Self;
+
+    /// Atomically replaces the value at `ptr` with `replacement` if it is equal to `expected`.
+    ///
+    /// Returns the value at `ptr` before the operation.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` is valid for reads and writes and aligned to `Self`.
+    unsafe fn atomic_cmpxchg(ptr: *mut u8, expected: Self, replacement: Self) -> Self;
+}
+
+macro_rules! impl_atomic_int {
+    ( $( impl AtomicInt for $int:ty as $atomic:ty; )* ) => {
+        $(
+            impl AtomicInt for $int {
+                unsafe fn atomic_load(ptr: *mut u8) -> Self {
+                    let atomic = &*(ptr as *const $atomic);
+                    <$int>::from_le(atomic.load(Ordering::SeqCst))
+                }
+
+                unsafe fn atomic_store(ptr: *mut u8, value: Self) {
+                    let atomic = &*(ptr as *const $atomic);
+                    atomic.store(value.to_le(), Ordering::SeqCst)
+                }
+
+                unsafe fn atomic_rmw(ptr: *mut u8, value: Self, op: fn(Self, Self) -> Self) -> Self {
+                    let atomic = &*(ptr as *const $atomic);
+                    let result = atomic.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |current| {
+                        Some(op(<$int>::from_le(current), value).to_le())
+                    });
+                    // Note: The closure always returns `Some` so the update never fails.
+                    let (Ok(previous) | Err(previous)) = result;
+                    <$int>::from_le(previous)
+                }
+
+                unsafe fn atomic_cmpxchg(ptr: *mut u8, expected: Self, replacement: Self) -> Self {
+                    let atomic = &*(ptr as *const $atomic);
+                    let result = atomic.compare_exchange(
+                        expected.to_le(),
+                        replacement.to_le(),
+                        Ordering::SeqCst,
+                        Ordering::SeqCst,
+                    );
+                    let (Ok(previous) | Err(previous)) = result;
+                    <$int>::from_le(previous)
+                }
+            }
+        )*
+    };
+}
+impl_atomic_int! {
+    impl AtomicInt for u8 as AtomicU8;
+    impl AtomicInt for u16 as AtomicU16;
+    impl AtomicInt for u32 as AtomicU32;
+    impl AtomicInt for u64 as AtomicU64;
+}
+
+/// Checks that an atomic access of type `T` at `address` is naturally aligned.
+///
+/// # Errors
+///
+/// If `address` is not a multiple of the size of `T`.
+pub(super) fn check_alignment<T: AtomicInt>(address: usize) -> Result<(), MemoryError> {
+    // Note: The sizes of all atomic integer types are powers of two.
+    if address & (mem::size_of::<T>() - 1) != 0 {
+        return Err(MemoryError::UnalignedAtomicAccess);
+    }
+    Ok(())
+}
+
+impl MemoryEntity {
+    /// Atomically loads a value of type `T` from the linear memory at `address`.
+    ///
+    /// # Errors
+    ///
+    /// - If `address` is not aligned to the size of `T`.
+    /// - If this operation accesses out of bounds linear memory.
+    pub fn atomic_load<T: AtomicInt>(&self, address: usize) -> Result<T, MemoryError> {
+        match &self.repr {
+            MemoryRepr::Owned { .. } => {
+                check_alignment::<T>(address)?;
+                let mut bytes = <T as LittleEndianConvert>::Bytes::default();
+                self.read(address, bytes.as_mut())?;
+                Ok(T::from_le_bytes(bytes))
+            }
+            MemoryRepr::Shared(memory) => memory.atomic_load(address),
+        }
+    }
+
+    /// Atomically stores `value` of type `T` into the linear memory at `address`.
+    ///
+    /// # Errors
+    ///
+    /// - If `address` is not aligned to the size of `T`.
+    /// - If this operation accesses out of bounds linear memory.
+    pub fn atomic_store<T: AtomicInt>(
+        &mut self,
+        address: usize,
+        value: T,
+    ) -> Result<(), MemoryError> {
+        match &self.repr {
+            MemoryRepr::Owned { .. } => {
+                check_alignment::<T>(address)?;
+                self.write(address, value.into_le_bytes().as_ref())
+            }
+            MemoryRepr::Shared(memory) => memory.atomic_store(address, value),
+        }
+    }
+
+    /// Atomically replaces the value of type `T` at `address` with the result of `op` applied to it and `value`.
+    ///
+    /// Returns the value at `address` before the operation.
+    ///
+    /// # Errors
+    ///
+    /// - If `address` is not aligned to the size of `T`.
+    /// - If this operation accesses out of bounds linear memory.
+    pub fn atomic_rmw<T: AtomicInt>(
+        &mut self,
+        address: usize,
+        value: T,
+        op: fn(T, T) -> T,
+    ) -> Result<T, MemoryError> {
+        match &self.repr {
+            MemoryRepr::Owned { .. } => {
+                // Note: The linear memory is exclusively owned by the current thread
+                //       so there is no need to access it atomically.
+                let previous = self.atomic_load::<T>(address)?;
+                self.atomic_store(address, op(previous, value))?;
+                Ok(previous)
+            }
+            MemoryRepr::Shared(memory) => memory.atomic_rmw(address, value, op),
+        }
+    }
+
+    /// Atomically replaces the value of type `T` at `address` with `replacement` if it is equal to `expected`.
+    ///
+    /// Returns the value at `address` before the operation.
+    ///
+    /// # Errors
+    ///
+    /// - If `address` is not aligned to the size of `T`.
+    /// - If this operation accesses out of bounds linear memory.
+    pub fn atomic_cmpxchg<T: AtomicInt>(
+        &mut self,
+        address: usize,
+        expected: T,
+        replacement: T,
+    ) -> Result<T, MemoryError> {
+        match &self.repr {
+            MemoryRepr::Owned { .. } => {
+                let previous = self.atomic_load::<T>(address)?;
+                if previous == expected {
+                    self.atomic_store(address, replacement)?;
+                }
+                Ok(previous)
+            }
+            MemoryRepr::Shared(memory) => memory.atomic_cmpxchg(address, expected, replacement),
+        }
+    }
+
+    /// Unparks up to `count` threads waiting on the 32-bit value at `address`.
+    ///
+    /// Returns the amount of unparked threads which is always zero for unshared linear memories.
+    ///
+    /// # Errors
+    ///
+    /// - If `address` is not aligned to 4 bytes.
+    /// - If this operation accesses out of bounds linear memory.
+    pub fn atomic_notify(&self, address: usize, count: u32) -> Result<u32, MemoryError> {
+        match &self.repr {
+            MemoryRepr::Owned { .. } => {
+                self.atomic_load::<u32>(address)?;
+                Ok(0)
+            }
+            MemoryRepr::Shared(memory) => memory.atomic_notify(address, count),
+        }
+    }
+}
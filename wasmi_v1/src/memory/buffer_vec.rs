@@ -1,4 +1,4 @@
-use super::{max_memory_len, MemoryError};
+use super::MemoryError;
 use alloc::{vec, vec::Vec};
 use core::{fmt, fmt::Display};
 
@@ -22,33 +22,57 @@ impl Display for VirtualMemoryError {
 #[derive(Debug)]
 pub struct ByteBuffer {
     bytes: Vec<u8>,
+    maximum_len: usize,
 }
 
 impl ByteBuffer {
-    /// Creates a new byte buffer with the given initial length.
+    /// Creates a new byte buffer with the given initial and maximum length.
     ///
     /// # Errors
     ///
-    /// - If the initial length is 0.
-    /// - If the initial length exceeds the maximum supported limit.
-    pub fn new(initial_len: usize) -> Result<Self, MemoryError> {
-        if initial_len > max_memory_len() {
+    /// If the initial length exceeds the maximum length.
+    pub fn new(initial_len: usize, maximum_len: usize) -> Result<Self, MemoryError> {
+        if initial_len > maximum_len {
             return Err(MemoryError::OutOfBoundsAllocation);
         }
         let bytes = vec![0x00_u8; initial_len];
-        Ok(Self { bytes })
+        Ok(Self { bytes, maximum_len })
+    }
+
+    /// Creates a new byte buffer with the given initial and maximum length
+    /// whose bytes never move when growing the byte buffer.
+    ///
+    /// # Note
+    ///
+    /// This allocates the maximum length up front.
+    ///
+    /// # Errors
+    ///
+    /// - If the initial length exceeds the maximum length.
+    /// - If the maximum length cannot be allocated.
+    #[cfg(feature = "threads")]
+    pub fn new_pinned(initial_len: usize, maximum_len: usize) -> Result<Self, MemoryError> {
+        if initial_len > maximum_len {
+            return Err(MemoryError::OutOfBoundsAllocation);
+        }
+        let mut bytes = Vec::new();
+        bytes
+            .try_reserve_exact(maximum_len)
+            .map_err(|_| MemoryError::OutOfBoundsAllocation)?;
+        bytes.resize(initial_len, 0x00_u8);
+        Ok(Self { bytes, maximum_len })
     }
 
     /// Grows the byte buffer by the given delta.
     ///
     /// # Errors
     ///
-    /// If the new length of the byte buffer would exceed the maximum supported limit.
+    /// If the new length of the byte buffer would exceed its maximum length.
     pub fn grow(&mut self, delta: usize) -> Result<(), MemoryError> {
         let new_len = self
             .len()
             .checked_add(delta)
-            .filter(|&new_len| new_len < max_memory_len())
+            .filter(|&new_len| new_len <= self.maximum_len)
             .ok_or(MemoryError::OutOfBoundsGrowth)?;
         assert!(new_len >= self.len());
         self.bytes.resize(new_len, 0x00_u8);
@@ -69,4 +93,10 @@ impl ByteBuffer {
     pub fn data_mut(&mut self) -> &mut [u8] {
         &mut self.bytes[..]
     }
+
+    /// Returns a raw pointer to the first byte underlying to the byte buffer.
+    #[cfg(feature = "threads")]
+    pub fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.bytes.as_mut_ptr()
+    }
 }
@@ -1,5 +1,7 @@
-use super::{max_memory_len, MemoryError};
+use super::MemoryError;
 use core::fmt::Debug;
+#[cfg(target_os = "linux")]
+use wasmi_core::MemoryImage;
 use wasmi_core::VirtualMemory;
 pub use wasmi_core::VirtualMemoryError;
 
@@ -9,53 +11,95 @@ pub use wasmi_core::VirtualMemoryError;
 ///
 /// - This is a more efficient implementation of the byte buffer that
 ///   makes use of operating system provided virtual memory abstractions.
-/// - This implementation allocates 4GB of virtual memory up front so
-///   that grow operations later on are no-ops. The downside to this is
-///   that this implementation is only supported on 64-bit systems.
+/// - This implementation reserves the virtual memory for the maximum length
+///   up front and commits it piece by piece as the byte buffer grows so
+///   that grow operations later on never reallocate. Reserved but not yet
+///   committed virtual memory does not occupy any physical memory which
+///   allows to reserve large sparse regions for 64-bit linear memories.
+///   The downside to this is that this implementation is only supported
+///   on 64-bit systems.
 ///   32-bit systems will fall back to the `Vec`-based implementation
 ///   even if the respective crate feature is enabled.
 #[derive(Debug)]
 pub struct ByteBuffer {
     bytes: VirtualMemory,
     len: usize,
+    maximum_len: usize,
 }
 
 impl ByteBuffer {
-    /// Determines the initial size of the virtual memory allocation.
+    /// Determines the minimum size of the virtual memory reservation.
     ///
     /// # Note
     ///
     /// In this implementation we won't reallocate the virtually allocated
     /// buffer and instead simply adjust the `len` field of the `ByteBuf`
     /// wrapper in order to efficiently grow the virtual memory.
-    const ALLOCATION_SIZE: usize = u32::MAX as usize;
+    /// Byte buffers with a smaller maximum length still reserve 4GB.
+    const MIN_RESERVATION_SIZE: usize = u32::MAX as usize;
 
-    /// Creates a new byte buffer with the given initial length.
+    /// Creates a new byte buffer with the given initial and maximum length.
     ///
     /// # Errors
     ///
-    /// - If the initial length is 0.
-    /// - If the initial length exceeds the maximum supported limit.
-    pub fn new(initial_len: usize) -> Result<Self, MemoryError> {
-        let bytes = VirtualMemory::new(Self::ALLOCATION_SIZE)?;
+    /// - If the initial length exceeds the maximum length.
+    /// - If the virtual memory for the maximum length cannot be reserved.
+    pub fn new(initial_len: usize, maximum_len: usize) -> Result<Self, MemoryError> {
+        if initial_len > maximum_len {
+            return Err(MemoryError::OutOfBoundsAllocation);
+        }
+        let mut bytes = VirtualMemory::reserve(maximum_len.max(Self::MIN_RESERVATION_SIZE))?;
+        bytes.commit(initial_len)?;
         Ok(Self {
             bytes,
             len: initial_len,
+            maximum_len,
         })
     }
 
+    /// Creates a new byte buffer with the given initial and maximum length
+    /// whose bytes never move when growing the byte buffer.
+    ///
+    /// # Note
+    ///
+    /// This is the same as [`ByteBuffer::new`] since this implementation
+    /// never reallocates its bytes.
+    ///
+    /// # Errors
+    ///
+    /// - If the initial length exceeds the maximum length.
+    /// - If the virtual memory for the maximum length cannot be reserved.
+    #[cfg(feature = "threads")]
+    pub fn new_pinned(initial_len: usize, maximum_len: usize) -> Result<Self, MemoryError> {
+        Self::new(initial_len, maximum_len)
+    }
+
+    /// Maps the [`MemoryImage`] copy-on-write over the first bytes of the byte buffer.
+    ///
+    /// # Errors
+    ///
+    /// - If the [`MemoryImage`] is longer than the byte buffer.
+    /// - If the operating system fails to map the [`MemoryImage`].
+    #[cfg(target_os = "linux")]
+    pub fn map_image(&mut self, image: &MemoryImage) -> Result<(), MemoryError> {
+        self.bytes.map_image(image)?;
+        Ok(())
+    }
+
     /// Grows the byte buffer by the given delta.
     ///
     /// # Errors
     ///
-    /// If the new length of the byte buffer would exceed the maximum supported limit.
+    /// - If the new length of the byte buffer would exceed its maximum length.
+    /// - If the operating system fails to commit the additional virtual memory.
     pub fn grow(&mut self, delta: usize) -> Result<(), MemoryError> {
         let new_len = self
             .len()
             .checked_add(delta)
-            .filter(|&new_len| new_len < max_memory_len())
+            .filter(|&new_len| new_len <= self.maximum_len)
             .ok_or(MemoryError::OutOfBoundsGrowth)?;
         assert!(new_len >= self.len());
+        self.bytes.commit(new_len)?;
         self.len = new_len;
         Ok(())
     }
@@ -74,4 +118,10 @@ impl ByteBuffer {
     pub fn data_mut(&mut self) -> &mut [u8] {
         &mut self.bytes.data_mut()[..self.len]
     }
+
+    /// Returns a raw pointer to the first byte underlying to the byte buffer.
+    #[cfg(feature = "threads")]
+    pub fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.bytes.data_mut().as_mut_ptr()
+    }
 }
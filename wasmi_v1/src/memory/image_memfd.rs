@@ -0,0 +1,55 @@
+use super::{byte_buffer::ByteBuffer, MemoryError};
+use wasmi_core::memory_units::{Bytes, Pages};
+
+/// A prepared image of the initial bytes of a linear memory.
+///
+/// # Note
+///
+/// The image is backed by an anonymous `memfd` file and mapped copy-on-write
+/// into the virtual memory of new linear memories instead of copying its bytes.
+/// Therefore creating a linear memory from an image takes constant time
+/// regardless of the amount of initialized bytes.
+#[derive(Debug)]
+pub struct MemoryImage {
+    image: wasmi_core::MemoryImage,
+}
+
+impl MemoryImage {
+    /// Creates a new [`MemoryImage`] of at least `len` bytes with the
+    /// bytes of all `segments` written at their respective offsets.
+    ///
+    /// # Note
+    ///
+    /// - The `segments` are written in order so that later segments
+    ///   overwrite the overlapping bytes of earlier segments.
+    /// - The length of the [`MemoryImage`] is rounded up to whole Wasm pages.
+    ///
+    /// Returns `None` if the operating system fails to provide the [`MemoryImage`].
+    pub fn new(len: usize, segments: &[(usize, &[u8])]) -> Option<Self> {
+        let len = len.checked_next_multiple_of(Bytes::from(Pages(1)).0)?;
+        let mut image = wasmi_core::MemoryImage::new(len).ok()?;
+        for (offset, bytes) in segments {
+            image.write(*offset, bytes).ok()?;
+        }
+        Some(Self { image })
+    }
+
+    /// Returns a new [`ByteBuffer`] with the given initial and maximum length
+    /// whose first bytes are mapped copy-on-write from the [`MemoryImage`].
+    ///
+    /// # Errors
+    ///
+    /// - If the initial length exceeds the maximum length.
+    /// - If the virtual memory for the maximum length cannot be reserved.
+    /// - If the [`MemoryImage`] is longer than the initial length.
+    /// - If the operating system fails to map the [`MemoryImage`].
+    pub fn byte_buffer(
+        &self,
+        initial_len: usize,
+        maximum_len: usize,
+    ) -> Result<ByteBuffer, MemoryError> {
+        let mut bytes = ByteBuffer::new(initial_len, maximum_len)?;
+        bytes.map_image(&self.image)?;
+        Ok(bytes)
+    }
+}
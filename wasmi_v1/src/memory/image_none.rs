@@ -0,0 +1,31 @@
+use super::{byte_buffer::ByteBuffer, MemoryError};
+
+/// A prepared image of the initial bytes of a linear memory.
+///
+/// # Note
+///
+/// Memory images require the `virtual_memory` crate feature on 64-bit Linux
+/// platforms. Elsewhere no [`MemoryImage`] can be created and linear memories
+/// are always initialized by copying the bytes of their data segments.
+#[derive(Debug)]
+pub enum MemoryImage {}
+
+impl MemoryImage {
+    /// Always returns `None` since memory images are unsupported.
+    pub fn new(_len: usize, _segments: &[(usize, &[u8])]) -> Option<Self> {
+        None
+    }
+
+    /// Returns a new [`ByteBuffer`] whose first bytes are mapped from the [`MemoryImage`].
+    ///
+    /// # Note
+    ///
+    /// This is never called since no [`MemoryImage`] can be created.
+    pub fn byte_buffer(
+        &self,
+        _initial_len: usize,
+        _maximum_len: usize,
+    ) -> Result<ByteBuffer, MemoryError> {
+        match *self {}
+    }
+}
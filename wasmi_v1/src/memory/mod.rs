@@ -6,22 +6,54 @@ mod byte_buffer;
 #[path = "buffer_vec.rs"]
 mod byte_buffer;
 
+#[cfg(all(
+    feature = "virtual_memory",
+    target_pointer_width = "64",
+    target_os = "linux"
+))]
+#[path = "image_memfd.rs"]
+mod image;
+
+#[cfg(not(all(
+    feature = "virtual_memory",
+    target_pointer_width = "64",
+    target_os = "linux"
+)))]
+#[path = "image_none.rs"]
+mod image;
+
+#[cfg(feature = "threads")]
+mod atomic;
+#[cfg(feature = "threads")]
+mod parking;
+#[cfg(feature = "threads")]
+mod shared;
+#[cfg(all(test, feature = "threads"))]
+mod tests;
+
 use self::byte_buffer::{ByteBuffer, VirtualMemoryError};
-use super::{AsContext, AsContextMut, Index, StoreContext, StoreContextMut, Stored};
-use core::{fmt, fmt::Display};
+pub use self::image::MemoryImage;
+#[cfg(feature = "threads")]
+pub use self::{
+    atomic::AtomicInt,
+    parking::{ParkResult, Parker, ParkingSpot},
+    shared::SharedMemory,
+};
+use super::{ArenaIndex, AsContext, AsContextMut, StoreContext, StoreContextMut, Stored};
+use core::{fmt, fmt::Display, mem};
 use wasmi_core::memory_units::{Bytes, Pages};
 
 /// A raw index to a linear memory entity.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub struct MemoryIdx(usize);
+pub struct MemoryIdx(u64);
 
-impl Index for MemoryIdx {
-    fn into_usize(self) -> usize {
+impl ArenaIndex for MemoryIdx {
+    fn into_key(self) -> u64 {
         self.0
     }
 
-    fn from_usize(value: usize) -> Self {
-        Self(value)
+    fn from_key(key: u64) -> Self {
+        Self(key)
     }
 }
 
@@ -35,6 +67,8 @@ pub enum MemoryError {
     OutOfBoundsGrowth,
     /// Tried to access linear memory out of bounds.
     OutOfBoundsAccess,
+    /// The resource limiter of the store denied the allocation or growth.
+    ResourceLimitExceeded,
     /// A generic virtual memory error.
     Vmem(byte_buffer::VirtualMemoryError),
     /// Occurs when a memory type does not satisfy the constraints of another.
@@ -44,6 +78,13 @@ pub enum MemoryError {
         /// The required [`MemoryType`].
         required: MemoryType,
     },
+    /// Occurs when creating a [`SharedMemory`] from a memory type that
+    /// is not shared or that has no maximum limit.
+    #[cfg(feature = "threads")]
+    InvalidSharedMemoryType(MemoryType),
+    /// Tried to atomically access linear memory at an unaligned address.
+    #[cfg(feature = "threads")]
+    UnalignedAtomicAccess,
 }
 
 impl Display for MemoryError {
@@ -58,6 +99,9 @@ impl Display for MemoryError {
             MemoryError::OutOfBoundsAccess => {
                 write!(f, "tried to access virtual memory out of bounds")
             }
+            MemoryError::ResourceLimitExceeded => {
+                write!(f, "resource limiter denied the linear memory allocation")
+            }
             MemoryError::Vmem(error) => Display::fmt(error, f),
             Self::UnsatisfyingMemoryType {
                 unsatisfying,
@@ -69,6 +113,21 @@ impl Display for MemoryError {
                     unsatisfying, required,
                 )
             }
+            #[cfg(feature = "threads")]
+            Self::InvalidSharedMemoryType(memory_type) => {
+                write!(
+                    f,
+                    "memory type {:?} is not shared or has no maximum",
+                    memory_type,
+                )
+            }
+            #[cfg(feature = "threads")]
+            Self::UnalignedAtomicAccess => {
+                write!(
+                    f,
+                    "tried to atomically access linear memory at an unaligned address"
+                )
+            }
         }
     }
 }
@@ -79,8 +138,24 @@ impl From<VirtualMemoryError> for MemoryError {
     }
 }
 
-/// Returns the maximum virtual memory buffer length in bytes.
-fn max_memory_len() -> usize {
+/// Returns the amount of bytes of the given amount of `pages` saturating at `usize::MAX`.
+fn pages_to_bytes(pages: Pages) -> usize {
+    pages.0.saturating_mul(Bytes::from(Pages(1)).0)
+}
+
+/// Returns the maximum virtual memory buffer length in bytes for the given [`MemoryType`].
+///
+/// # Note
+///
+/// The length of 64-bit linear memories is limited to 1TB on 64-bit platforms
+/// so that their virtual memory can be reserved up front.
+fn max_memory_len(memory_type: MemoryType) -> usize {
+    #[cfg(target_pointer_width = "64")]
+    if memory_type.is_64() {
+        return 1 << 40;
+    }
+    #[cfg(not(target_pointer_width = "64"))]
+    let _ = memory_type;
     i32::MAX as u32 as usize
 }
 
@@ -89,6 +164,8 @@ fn max_memory_len() -> usize {
 pub struct MemoryType {
     initial_pages: Pages,
     maximum_pages: Option<Pages>,
+    memory64: bool,
+    shared: bool,
 }
 
 impl MemoryType {
@@ -97,9 +174,57 @@ impl MemoryType {
         Self {
             initial_pages: Pages(initial as usize),
             maximum_pages: maximum.map(|value| Pages(value as usize)),
+            memory64: false,
+            shared: false,
         }
     }
 
+    /// Creates a new 64-bit memory type with initial and optional maximum pages.
+    ///
+    /// # Note
+    ///
+    /// - Linear memories of this type are indexed using `i64` addresses
+    ///   as introduced by the Wasm [`memory64`] proposal.
+    /// - Page counts that do not fit into a `usize` are saturated.
+    ///
+    /// [`memory64`]: https://github.com/WebAssembly/memory64
+    pub fn new64(initial: u64, maximum: Option<u64>) -> Self {
+        let into_pages = |value: u64| Pages(usize::try_from(value).unwrap_or(usize::MAX));
+        Self {
+            initial_pages: into_pages(initial),
+            maximum_pages: maximum.map(into_pages),
+            memory64: true,
+            shared: false,
+        }
+    }
+
+    /// Returns the shared variant of the memory type.
+    ///
+    /// # Note
+    ///
+    /// - Linear memories of this type can be accessed by multiple threads
+    ///   as introduced by the Wasm [`threads`] proposal.
+    /// - Shared memory types are required to have a maximum limit.
+    ///
+    /// [`threads`]: https://github.com/WebAssembly/threads
+    #[cfg(feature = "threads")]
+    pub fn into_shared(self) -> Self {
+        Self {
+            shared: true,
+            ..self
+        }
+    }
+
+    /// Returns `true` if the memory type is indexed using `i64` addresses.
+    pub fn is_64(self) -> bool {
+        self.memory64
+    }
+
+    /// Returns `true` if the memory type is shared between threads.
+    pub fn is_shared(self) -> bool {
+        self.shared
+    }
+
     /// Returns the initial pages of the memory type.
     pub fn initial_pages(self) -> Pages {
         self.initial_pages
@@ -110,7 +235,8 @@ impl MemoryType {
     /// # Note
     ///
     /// - Returns `None` if there is no limit set.
-    /// - Maximum memory size cannot exceed `65536` pages or 4GiB.
+    /// - Maximum memory size cannot exceed `65536` pages or 4GiB
+    ///   unless the memory type is a 64-bit memory type.
     pub fn maximum_pages(self) -> Option<Pages> {
         self.maximum_pages
     }
@@ -119,10 +245,15 @@ impl MemoryType {
     ///
     /// # Errors
     ///
+    /// - If the index type of the `required` [`MemoryType`] differs from `self`.
+    /// - If the `required` [`MemoryType`] is shared and `self` is not or vice versa.
     /// - If the initial limits of the `required` [`MemoryType`] are greater than `self`.
     /// - If the maximum limits of the `required` [`MemoryType`] are greater than `self`.
     pub(crate) fn satisfies(&self, required: &MemoryType) -> Result<(), MemoryError> {
-        if required.initial_pages() > self.initial_pages() {
+        if required.is_64() != self.is_64()
+            || required.is_shared() != self.is_shared()
+            || required.initial_pages() > self.initial_pages()
+        {
             return Err(MemoryError::UnsatisfyingMemoryType {
                 unsatisfying: *self,
                 required: *required,
@@ -145,9 +276,21 @@ impl MemoryType {
 /// A linear memory entity.
 #[derive(Debug)]
 pub struct MemoryEntity {
-    bytes: ByteBuffer,
     memory_type: MemoryType,
-    current_pages: Pages,
+    repr: MemoryRepr,
+}
+
+/// The underlying bytes of a [`MemoryEntity`].
+#[derive(Debug)]
+enum MemoryRepr {
+    /// The bytes of a linear memory that is exclusively owned by its store.
+    Owned {
+        bytes: ByteBuffer,
+        current_pages: Pages,
+    },
+    /// The bytes of a linear memory that might be shared with other stores and threads.
+    #[cfg(feature = "threads")]
+    Shared(SharedMemory),
 }
 
 impl MemoryEntity {
@@ -159,26 +302,135 @@ impl MemoryEntity {
     /// can only be 65536 pages for a total of ~4GB bytes of memory.
     const MAX_PAGES: Pages = Pages(65536);
 
+    /// The maximum amount of pages of a 64-bit linear memory.
+    ///
+    /// # Note
+    ///
+    /// This is the maximum amount of pages for which the total
+    /// amount of bytes of the linear memory still fits into a `usize`.
+    const MAX_PAGES64: Pages = Pages(usize::MAX >> 16);
+
     /// Creates a new memory entity with the given memory type.
+    ///
+    /// # Note
+    ///
+    /// Creates a new [`SharedMemory`] for shared memory types.
+    ///
+    /// # Errors
+    ///
+    /// If the initial pages of the memory type exceed the supported limits.
     pub fn new(memory_type: MemoryType) -> Result<Self, MemoryError> {
-        let initial_pages = memory_type.initial_pages();
-        let initial_bytes = Bytes::from(initial_pages);
+        #[cfg(feature = "threads")]
+        if memory_type.is_shared() {
+            return SharedMemory::new(memory_type).map(Self::from_shared);
+        }
+        let (initial_len, maximum_len) = Self::byte_lens(memory_type)?;
         let memory = Self {
-            bytes: ByteBuffer::new(initial_bytes.0)?,
             memory_type,
-            current_pages: initial_pages,
+            repr: MemoryRepr::Owned {
+                bytes: ByteBuffer::new(initial_len, maximum_len)?,
+                current_pages: memory_type.initial_pages(),
+            },
         };
         Ok(memory)
     }
 
+    /// Creates a new memory entity with the given memory type whose initial bytes are
+    /// mapped copy-on-write from the [`MemoryImage`].
+    ///
+    /// # Note
+    ///
+    /// The memory type is required to be unshared.
+    ///
+    /// # Errors
+    ///
+    /// - If the initial pages of the memory type exceed the supported limits.
+    /// - If the [`MemoryImage`] cannot be mapped.
+    pub fn from_image(memory_type: MemoryType, image: &MemoryImage) -> Result<Self, MemoryError> {
+        debug_assert!(!memory_type.is_shared());
+        let (initial_len, maximum_len) = Self::byte_lens(memory_type)?;
+        let memory = Self {
+            memory_type,
+            repr: MemoryRepr::Owned {
+                bytes: image.byte_buffer(initial_len, maximum_len)?,
+                current_pages: memory_type.initial_pages(),
+            },
+        };
+        Ok(memory)
+    }
+
+    /// Creates a new memory entity operating on the bytes of the [`SharedMemory`].
+    #[cfg(feature = "threads")]
+    pub fn from_shared(memory: SharedMemory) -> Self {
+        Self {
+            memory_type: memory.memory_type(),
+            repr: MemoryRepr::Shared(memory),
+        }
+    }
+
+    /// Returns the initial and maximum byte length of a linear memory of the given type.
+    ///
+    /// # Errors
+    ///
+    /// If the initial pages of the memory type exceed the supported limits.
+    fn byte_lens(memory_type: MemoryType) -> Result<(usize, usize), MemoryError> {
+        let initial_pages = memory_type.initial_pages();
+        let maximum_pages = Self::maximum_pages(memory_type);
+        if initial_pages > maximum_pages {
+            return Err(MemoryError::OutOfBoundsAllocation);
+        }
+        let initial_bytes = Bytes::from(initial_pages);
+        let maximum_bytes = Bytes::from(maximum_pages)
+            .0
+            .min(max_memory_len(memory_type));
+        Ok((initial_bytes.0, maximum_bytes))
+    }
+
+    /// Returns the maximum amount of pages a linear memory of the given type may grow to.
+    fn maximum_pages(memory_type: MemoryType) -> Pages {
+        let max_pages = match memory_type.is_64() {
+            true => Self::MAX_PAGES64,
+            false => Self::MAX_PAGES,
+        };
+        memory_type
+            .maximum_pages()
+            .map_or(max_pages, |maximum_pages| maximum_pages.min(max_pages))
+    }
+
     /// Returns the memory type of the linear memory.
     pub fn memory_type(&self) -> MemoryType {
         self.memory_type
     }
 
+    /// Returns the [`SharedMemory`] if the linear memory is shared.
+    #[cfg(feature = "threads")]
+    pub fn shared(&self) -> Option<&SharedMemory> {
+        match &self.repr {
+            MemoryRepr::Owned { .. } => None,
+            MemoryRepr::Shared(memory) => Some(memory),
+        }
+    }
+
+    /// Returns `true` if `self` and `other` operate on the same underlying bytes.
+    ///
+    /// # Note
+    ///
+    /// This is the case for distinct entities of the same [`SharedMemory`].
+    #[cfg(feature = "threads")]
+    pub fn shares_bytes_with(&self, other: &Self) -> bool {
+        matches!(
+            (&self.repr, &other.repr),
+            (MemoryRepr::Shared(lhs), MemoryRepr::Shared(rhs)) if lhs == rhs
+        )
+    }
+
     /// Returns the amount of pages in use by the linear memory.
     pub fn current_pages(&self) -> Pages {
-        self.current_pages
+        match &self.repr {
+            MemoryRepr::Owned { current_pages, .. } => *current_pages,
+            #[cfg(feature = "threads")]
+            MemoryRepr::Shared(memory) => memory.current_pages(),
+        }
     }
 
     /// Grows the linear memory by the given amount of new pages.
@@ -190,15 +442,39 @@ impl MemoryEntity {
     /// If the linear memory would grow beyond its maximum limit after
     /// the grow operation.
     pub fn grow(&mut self, additional: Pages) -> Result<Pages, MemoryError> {
-        let current_pages = self.current_pages();
+        match &mut self.repr {
+            MemoryRepr::Owned {
+                bytes,
+                current_pages,
+            } => {
+                let new_pages =
+                    Self::grow_bytes(self.memory_type, bytes, *current_pages, additional)?;
+                Ok(mem::replace(current_pages, new_pages))
+            }
+            #[cfg(feature = "threads")]
+            MemoryRepr::Shared(memory) => memory.grow(additional),
+        }
+    }
+
+    /// Grows the `bytes` of a linear memory with `current_pages` by `additional` pages.
+    ///
+    /// Returns the amount of pages after the operation upon success.
+    ///
+    /// # Errors
+    ///
+    /// If the linear memory would grow beyond the maximum limit of its
+    /// `memory_type` after the grow operation.
+    fn grow_bytes(
+        memory_type: MemoryType,
+        bytes: &mut ByteBuffer,
+        current_pages: Pages,
+        additional: Pages,
+    ) -> Result<Pages, MemoryError> {
         if additional == Pages(0) {
             // Nothing to do in this case. Bail out early.
             return Ok(current_pages);
         }
-        let maximum_pages = self
-            .memory_type()
-            .maximum_pages()
-            .unwrap_or(Self::MAX_PAGES);
+        let maximum_pages = Self::maximum_pages(memory_type);
         let new_pages = current_pages
             .0
             .checked_add(additional.0)
@@ -207,19 +483,32 @@ impl MemoryEntity {
             .ok_or(MemoryError::OutOfBoundsGrowth)?;
         // At this point it is okay to grow the underlying virtual memory
         // by the given amount of additional pages.
-        self.bytes.grow(Bytes::from(additional).0)?;
-        self.current_pages = new_pages;
-        Ok(current_pages)
+        bytes.grow(Bytes::from(additional).0)?;
+        Ok(new_pages)
     }
 
     /// Returns a shared slice to the bytes underlying to the byte buffer.
     pub fn data(&self) -> &[u8] {
-        self.bytes.data()
+        match &self.repr {
+            MemoryRepr::Owned { bytes, .. } => bytes.data(),
+            // # SAFETY: See `data_mut`.
+            #[cfg(feature = "threads")]
+            MemoryRepr::Shared(memory) => unsafe { memory.data() },
+        }
     }
 
     /// Returns an exclusive slice to the bytes underlying to the byte buffer.
     pub fn data_mut(&mut self) -> &mut [u8] {
-        self.bytes.data_mut()
+        match &mut self.repr {
+            MemoryRepr::Owned { bytes, .. } => bytes.data_mut(),
+            // # SAFETY
+            //
+            // The bytes of a shared linear memory might be accessed concurrently
+            // by other threads. The Wasm `threads` proposal explicitly allows such
+            // racy non-atomic accesses and defines their results to be unspecified.
+            #[cfg(feature = "threads")]
+            MemoryRepr::Shared(memory) => unsafe { memory.data_mut() },
+        }
     }
 
     /// Reads `n` bytes from `memory[offset..offset+n]` into `buffer`
@@ -230,9 +519,9 @@ impl MemoryEntity {
     /// If this operation accesses out of bounds linear memory.
     pub fn read(&self, offset: usize, buffer: &mut [u8]) -> Result<(), MemoryError> {
         let len_buffer = buffer.len();
-        let slice = self
-            .data()
-            .get(offset..(offset + len_buffer))
+        let slice = offset
+            .checked_add(len_buffer)
+            .and_then(|end| self.data().get(offset..end))
             .ok_or(MemoryError::OutOfBoundsAccess)?;
         buffer.copy_from_slice(slice);
         Ok(())
@@ -246,9 +535,9 @@ impl MemoryEntity {
     /// If this operation accesses out of bounds linear memory.
     pub fn write(&mut self, offset: usize, buffer: &[u8]) -> Result<(), MemoryError> {
         let len_buffer = buffer.len();
-        let slice = self
-            .data_mut()
-            .get_mut(offset..(offset + len_buffer))
+        let slice = offset
+            .checked_add(len_buffer)
+            .and_then(|end| self.data_mut().get_mut(offset..end))
             .ok_or(MemoryError::OutOfBoundsAccess)?;
         slice.copy_from_slice(buffer);
         Ok(())
@@ -272,12 +561,132 @@ impl Memory {
     }
 
     /// Creates a new linear memory to the store.
+    ///
+    /// # Errors
+    ///
+    /// - If the memory type is invalid.
+    /// - If the [`ResourceLimiter`] of the store denies the allocation.
+    ///
+    /// [`ResourceLimiter`]: [`crate::ResourceLimiter`]
     pub fn new(mut ctx: impl AsContextMut, memory_type: MemoryType) -> Result<Self, MemoryError> {
+        Self::ensure_creation_permitted(&mut ctx, memory_type)?;
         let entity = MemoryEntity::new(memory_type)?;
         let memory = ctx.as_context_mut().store.alloc_memory(entity);
         Ok(memory)
     }
 
+    /// Creates a new linear memory to the store whose initial bytes are
+    /// mapped copy-on-write from the [`MemoryImage`].
+    ///
+    /// # Errors
+    ///
+    /// - If the memory type is invalid.
+    /// - If the [`ResourceLimiter`] of the store denies the allocation.
+    /// - If the [`MemoryImage`] cannot be mapped.
+    ///
+    /// [`ResourceLimiter`]: [`crate::ResourceLimiter`]
+    pub(crate) fn from_image(
+        mut ctx: impl AsContextMut,
+        memory_type: MemoryType,
+        image: &MemoryImage,
+    ) -> Result<Self, MemoryError> {
+        Self::ensure_creation_permitted(&mut ctx, memory_type)?;
+        let entity = MemoryEntity::from_image(memory_type, image)?;
+        let memory = ctx.as_context_mut().store.alloc_memory(entity);
+        Ok(memory)
+    }
+
+    /// Returns `Ok` if the [`ResourceLimiter`] of the store permits creating
+    /// a new linear memory of the given memory type.
+    ///
+    /// # Errors
+    ///
+    /// If the [`ResourceLimiter`] of the store denies the allocation.
+    ///
+    /// [`ResourceLimiter`]: [`crate::ResourceLimiter`]
+    fn ensure_creation_permitted(
+        mut ctx: impl AsContextMut,
+        memory_type: MemoryType,
+    ) -> Result<(), MemoryError> {
+        let initial = pages_to_bytes(memory_type.initial_pages());
+        let maximum = memory_type.maximum_pages().map(pages_to_bytes);
+        let store = ctx.as_context_mut().store;
+        if !store.can_create_memory() || !store.memory_growing(0, initial, maximum) {
+            return Err(MemoryError::ResourceLimitExceeded);
+        }
+        Ok(())
+    }
+
+    /// Creates a new linear memory to the store that operates on the bytes of the [`SharedMemory`].
+    ///
+    /// # Note
+    ///
+    /// Linear memories of different stores created from the same
+    /// [`SharedMemory`] all operate on the same bytes.
+    ///
+    /// # Errors
+    ///
+    /// If the [`ResourceLimiter`] of the store denies the allocation.
+    ///
+    /// [`ResourceLimiter`]: [`crate::ResourceLimiter`]
+    #[cfg(feature = "threads")]
+    pub fn from_shared(
+        mut ctx: impl AsContextMut,
+        memory: SharedMemory,
+    ) -> Result<Self, MemoryError> {
+        let store = ctx.as_context_mut().store;
+        if !store.can_create_memory() {
+            return Err(MemoryError::ResourceLimitExceeded);
+        }
+        let entity = MemoryEntity::from_shared(memory);
+        Ok(store.alloc_memory(entity))
+    }
+
+    /// Returns the linear memory of the store that operates on the bytes of the [`SharedMemory`].
+    ///
+    /// # Note
+    ///
+    /// This is used by the [`Linker`] to instantiate shared linear memories defined
+    /// via [`Linker::define_shared_memory`] in the store used for instantiation.
+    /// The linear memory is allocated only once per store and reused by later instantiations.
+    ///
+    /// # Errors
+    ///
+    /// If the [`ResourceLimiter`] of the store denies the allocation.
+    ///
+    /// [`Linker`]: [`crate::Linker`]
+    /// [`Linker::define_shared_memory`]: [`crate::Linker::define_shared_memory`]
+    /// [`ResourceLimiter`]: [`crate::ResourceLimiter`]
+    #[cfg(feature = "threads")]
+    pub(crate) fn resolve_shared(
+        mut ctx: impl AsContextMut,
+        memory: &SharedMemory,
+    ) -> Result<Self, MemoryError> {
+        let key = memory.key();
+        if let Some(memory) = ctx.as_context().store.get_linker_memory(key) {
+            return Ok(memory);
+        }
+        let resolved = Self::from_shared(ctx.as_context_mut(), memory.clone())?;
+        ctx.as_context_mut()
+            .store
+            .insert_linker_memory(key, resolved);
+        Ok(resolved)
+    }
+
+    /// Returns the [`SharedMemory`] of the linear memory if it is shared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    #[cfg(feature = "threads")]
+    pub fn shared(&self, ctx: impl AsContext) -> Option<SharedMemory> {
+        ctx.as_context()
+            .store
+            .resolve_memory(*self)
+            .shared()
+            .cloned()
+    }
+
     /// Returns the memory type of the linear memory.
     ///
     /// # Panics
@@ -302,21 +711,32 @@ impl Memory {
     ///
     /// # Errors
     ///
-    /// If the linear memory would grow beyond its maximum limit after
-    /// the grow operation.
+    /// - If the linear memory would grow beyond its maximum limit after
+    ///   the grow operation.
+    /// - If the [`ResourceLimiter`] of the store denies the grow operation.
     ///
     /// # Panics
     ///
     /// Panics if `ctx` does not own this [`Memory`].
+    ///
+    /// [`ResourceLimiter`]: [`crate::ResourceLimiter`]
     pub fn grow(
         &self,
         mut ctx: impl AsContextMut,
         additional: Pages,
     ) -> Result<Pages, MemoryError> {
-        ctx.as_context_mut()
-            .store
-            .resolve_memory_mut(*self)
-            .grow(additional)
+        let store = ctx.as_context_mut().store;
+        if additional != Pages(0) {
+            let memory = store.resolve_memory(*self);
+            let current_pages = memory.current_pages();
+            let maximum = memory.memory_type().maximum_pages().map(pages_to_bytes);
+            let current = pages_to_bytes(current_pages);
+            let desired = pages_to_bytes(Pages(current_pages.0.saturating_add(additional.0)));
+            if !store.memory_growing(current, desired, maximum) {
+                return Err(MemoryError::ResourceLimitExceeded);
+            }
+        }
+        store.resolve_memory_mut(*self).grow(additional)
     }
 
     /// Returns a shared slice to the bytes underlying to the byte buffer.
@@ -337,6 +757,26 @@ impl Memory {
         ctx.into().store.resolve_memory_mut(*self).data_mut()
     }
 
+    /// Returns a raw pointer to the first byte of the linear memory.
+    ///
+    /// # Note
+    ///
+    /// With the `virtual_memory` crate feature on 64-bit platforms the bytes
+    /// of linear memories never move, so the pointer stays valid across
+    /// `memory.grow` for as long as the linear memory is alive.
+    /// Otherwise growing the linear memory may move its bytes and invalidate the pointer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn data_ptr(&self, mut ctx: impl AsContextMut) -> *mut u8 {
+        ctx.as_context_mut()
+            .store
+            .resolve_memory_mut(*self)
+            .data_mut()
+            .as_mut_ptr()
+    }
+
     /// Reads `n` bytes from `memory[offset..offset+n]` into `buffer`
     /// where `n` is the length of `buffer`.
     ///
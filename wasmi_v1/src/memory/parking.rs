@@ -0,0 +1,157 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+        Mutex,
+        PoisonError,
+    },
+    thread::{self, Thread},
+    time::{Duration, Instant},
+};
+
+/// The result of parking a thread via [`Parker::park`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParkResult {
+    /// The thread has been unparked via [`Parker::unpark`].
+    Unparked,
+    /// The thread has not been parked since its validation failed.
+    Invalid,
+    /// The thread has been parked until its timeout expired.
+    TimedOut,
+}
+
+/// Parks and unparks threads waiting on the bytes of a [`SharedMemory`].
+///
+/// # Note
+///
+/// - This is used to implement the `memory.atomic.wait32`, `memory.atomic.wait64`
+///   and `memory.atomic.notify` instructions of the Wasm `threads` proposal.
+/// - The default [`ParkingSpot`] blocks the parked OS thread. Hosts may provide
+///   their own implementation via [`SharedMemory::with_parker`], for example
+///   in order to integrate waiting Wasm executions with their own scheduler.
+///
+/// [`SharedMemory`]: [`super::SharedMemory`]
+/// [`SharedMemory::with_parker`]: [`super::SharedMemory::with_parker`]
+pub trait Parker: Send + Sync {
+    /// Parks the current thread on `key` until it is unparked or the `timeout` expires.
+    ///
+    /// # Note
+    ///
+    /// - The `key` uniquely identifies the waited on bytes of the [`SharedMemory`].
+    /// - The current thread must only be parked if `validate` returns `true`.
+    ///   Implementations must call `validate` while holding the lock that is also
+    ///   acquired by [`Parker::unpark`] so that no unpark operation is missed.
+    /// - A `timeout` of `None` parks the current thread indefinitely.
+    ///
+    /// [`SharedMemory`]: [`super::SharedMemory`]
+    fn park(
+        &self,
+        key: usize,
+        validate: &mut dyn FnMut() -> bool,
+        timeout: Option<Duration>,
+    ) -> ParkResult;
+
+    /// Unparks up to `count` threads parked on `key` in the order they were parked.
+    ///
+    /// Returns the amount of unparked threads.
+    fn unpark(&self, key: usize, count: u32) -> u32;
+}
+
+/// The default [`Parker`] that blocks parked OS threads.
+#[derive(Debug, Default)]
+pub struct ParkingSpot {
+    /// The threads parked on a key in the order they were parked.
+    queues: Mutex<BTreeMap<usize, VecDeque<Arc<Waiter>>>>,
+}
+
+/// A thread parked on a [`ParkingSpot`].
+#[derive(Debug)]
+struct Waiter {
+    /// The parked thread.
+    thread: Thread,
+    /// Is `true` once the thread has been unparked via [`Parker::unpark`].
+    unparked: AtomicBool,
+}
+
+impl ParkingSpot {
+    /// Removes the `waiter` from the queue of the `key` if it has not yet been unparked.
+    ///
+    /// Returns `true` if the `waiter` has been unparked in the meantime.
+    fn cancel(&self, key: usize, waiter: &Arc<Waiter>) -> bool {
+        let mut queues = self.queues.lock().unwrap_or_else(PoisonError::into_inner);
+        if waiter.unparked.load(Ordering::Acquire) {
+            return true;
+        }
+        if let Some(queue) = queues.get_mut(&key) {
+            queue.retain(|queued| !Arc::ptr_eq(queued, waiter));
+            if queue.is_empty() {
+                queues.remove(&key);
+            }
+        }
+        false
+    }
+}
+
+impl Parker for ParkingSpot {
+    fn park(
+        &self,
+        key: usize,
+        validate: &mut dyn FnMut() -> bool,
+        timeout: Option<Duration>,
+    ) -> ParkResult {
+        let waiter = {
+            let mut queues = self.queues.lock().unwrap_or_else(PoisonError::into_inner);
+            if !validate() {
+                return ParkResult::Invalid;
+            }
+            let waiter = Arc::new(Waiter {
+                thread: thread::current(),
+                unparked: AtomicBool::new(false),
+            });
+            queues.entry(key).or_default().push_back(waiter.clone());
+            waiter
+        };
+        // Note: Timeouts too large to be represented park the thread indefinitely.
+        let deadline = timeout.and_then(|timeout| Instant::now().checked_add(timeout));
+        // Note: Threads might wake up spuriously so we have to loop.
+        while !waiter.unparked.load(Ordering::Acquire) {
+            match deadline {
+                None => thread::park(),
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        if self.cancel(key, &waiter) {
+                            break;
+                        }
+                        return ParkResult::TimedOut;
+                    }
+                    thread::park_timeout(deadline - now);
+                }
+            }
+        }
+        ParkResult::Unparked
+    }
+
+    fn unpark(&self, key: usize, count: u32) -> u32 {
+        let mut queues = self.queues.lock().unwrap_or_else(PoisonError::into_inner);
+        let queue = match queues.get_mut(&key) {
+            Some(queue) => queue,
+            None => return 0,
+        };
+        let mut unparked = 0;
+        while unparked < count {
+            let waiter = match queue.pop_front() {
+                Some(waiter) => waiter,
+                None => break,
+            };
+            waiter.unparked.store(true, Ordering::Release);
+            waiter.thread.unpark();
+            unparked += 1;
+        }
+        if queue.is_empty() {
+            queues.remove(&key);
+        }
+        unparked
+    }
+}
@@ -0,0 +1,360 @@
+use super::{
+    atomic::{check_alignment, AtomicInt},
+    ByteBuffer,
+    MemoryEntity,
+    MemoryError,
+    MemoryType,
+    ParkResult,
+    Parker,
+    ParkingSpot,
+};
+use core::{
+    fmt,
+    mem,
+    ptr,
+    slice,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use spin::mutex::Mutex;
+use std::{sync::Arc, time::Duration};
+use wasmi_core::memory_units::{Bytes, Pages};
+
+/// A linear memory that can be shared by multiple [`Store`]s on different threads.
+///
+/// # Note
+///
+/// - Shared linear memories are introduced by the Wasm [`threads`] proposal.
+/// - A [`SharedMemory`] can be defined in a [`Linker`] in order to be imported
+///   by Wasm modules that are instantiated in any [`Store`].
+/// - Cloning a [`SharedMemory`] is cheap and returns another handle to the same bytes.
+///
+/// [`Store`]: [`crate::Store`]
+/// [`Linker`]: [`crate::Linker`]
+/// [`threads`]: https://github.com/WebAssembly/threads
+#[derive(Clone)]
+pub struct SharedMemory {
+    inner: Arc<SharedMemoryEntity>,
+}
+
+/// The entity shared by all handles of the same [`SharedMemory`].
+struct SharedMemoryEntity {
+    /// The memory type of the shared linear memory.
+    memory_type: MemoryType,
+    /// The underlying bytes of the shared linear memory.
+    ///
+    /// # Note
+    ///
+    /// The lock is only acquired when growing the shared linear memory.
+    /// All other accesses operate on the bytes through `base` instead
+    /// which is valid since the byte buffer is pinned.
+    bytes: Mutex<ByteBuffer>,
+    /// Points to the first byte of the pinned byte buffer.
+    base: *mut u8,
+    /// The amount of pages in use by the shared linear memory.
+    current_pages: AtomicUsize,
+    /// Parks and unparks threads waiting on the shared linear memory.
+    parker: Arc<dyn Parker>,
+}
+
+// # SAFETY
+//
+// The `base` pointer refers to the bytes of the byte buffer which are never moved
+// and owned by the entity. Growing the byte buffer is synchronized via its lock
+// and all other accesses of the bytes are either atomic or racy accesses that
+// are permitted by the memory model of the Wasm `threads` proposal.
+unsafe impl Send for SharedMemoryEntity {}
+unsafe impl Sync for SharedMemoryEntity {}
+
+impl fmt::Debug for SharedMemory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SharedMemory")
+            .field("memory_type", &self.memory_type())
+            .field("current_pages", &self.current_pages())
+            .finish()
+    }
+}
+
+impl PartialEq for SharedMemory {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Eq for SharedMemory {}
+
+impl SharedMemory {
+    /// Creates a new shared linear memory with the given memory type.
+    ///
+    /// # Note
+    ///
+    /// Threads waiting on the shared linear memory are parked using a [`ParkingSpot`].
+    ///
+    /// # Errors
+    ///
+    /// - If the memory type is not shared or has no maximum limit.
+    /// - If the initial pages of the memory type exceed the supported limits.
+    pub fn new(memory_type: MemoryType) -> Result<Self, MemoryError> {
+        Self::with_parker(memory_type, Arc::new(ParkingSpot::default()))
+    }
+
+    /// Creates a new shared linear memory with the given memory type and [`Parker`].
+    ///
+    /// # Errors
+    ///
+    /// - If the memory type is not shared or has no maximum limit.
+    /// - If the initial pages of the memory type exceed the supported limits.
+    pub fn with_parker(
+        memory_type: MemoryType,
+        parker: Arc<dyn Parker>,
+    ) -> Result<Self, MemoryError> {
+        if !memory_type.is_shared() || memory_type.maximum_pages().is_none() {
+            return Err(MemoryError::InvalidSharedMemoryType(memory_type));
+        }
+        let (initial_len, maximum_len) = MemoryEntity::byte_lens(memory_type)?;
+        let mut bytes = ByteBuffer::new_pinned(initial_len, maximum_len)?;
+        let base = bytes.as_mut_ptr();
+        // Note: Atomic accesses rely on the alignment of the Wasm address
+        //       which requires the base address to be aligned as well.
+        assert!(
+            maximum_len == 0 || base as usize & (mem::align_of::<u64>() - 1) == 0,
+            "encountered unaligned shared linear memory at {:p}",
+            base,
+        );
+        let entity = SharedMemoryEntity {
+            memory_type,
+            bytes: Mutex::new(bytes),
+            base,
+            current_pages: AtomicUsize::new(memory_type.initial_pages().0),
+            parker,
+        };
+        Ok(Self {
+            inner: Arc::new(entity),
+        })
+    }
+
+    /// Returns the memory type of the shared linear memory.
+    pub fn memory_type(&self) -> MemoryType {
+        self.inner.memory_type
+    }
+
+    /// Returns a key that identifies the shared linear memory.
+    ///
+    /// # Note
+    ///
+    /// Clones of a [`SharedMemory`] share the same key. The key is unique
+    /// for as long as any clone of the [`SharedMemory`] is alive.
+    pub(crate) fn key(&self) -> usize {
+        Arc::as_ptr(&self.inner) as usize
+    }
+
+    /// Returns the amount of pages in use by the shared linear memory.
+    pub fn current_pages(&self) -> Pages {
+        Pages(self.inner.current_pages.load(Ordering::Acquire))
+    }
+
+    /// Returns the amount of bytes in use by the shared linear memory.
+    fn len(&self) -> usize {
+        Bytes::from(self.current_pages()).0
+    }
+
+    /// Grows the shared linear memory by the given amount of new pages.
+    ///
+    /// Returns the amount of pages before the operation upon success.
+    ///
+    /// # Errors
+    ///
+    /// If the shared linear memory would grow beyond its maximum limit
+    /// after the grow operation.
+    pub fn grow(&self, additional: Pages) -> Result<Pages, MemoryError> {
+        let mut bytes = self.inner.bytes.lock();
+        let current_pages = self.current_pages();
+        let new_pages =
+            MemoryEntity::grow_bytes(self.memory_type(), &mut bytes, current_pages, additional)?;
+        self.inner
+            .current_pages
+            .store(new_pages.0, Ordering::Release);
+        Ok(current_pages)
+    }
+
+    /// Returns a shared slice to the bytes of the shared linear memory.
+    ///
+    /// # Safety
+    ///
+    /// The returned bytes might be mutated concurrently by other threads.
+    pub(super) unsafe fn data(&self) -> &[u8] {
+        slice::from_raw_parts(self.inner.base, self.len())
+    }
+
+    /// Returns an exclusive slice to the bytes of the shared linear memory.
+    ///
+    /// # Safety
+    ///
+    /// The returned bytes might be accessed concurrently by other threads.
+    #[allow(clippy::mut_from_ref)]
+    pub(super) unsafe fn data_mut(&self) -> &mut [u8] {
+        slice::from_raw_parts_mut(self.inner.base, self.len())
+    }
+
+    /// Returns a pointer to the `len` bytes at `offset` of the shared linear memory.
+    ///
+    /// # Errors
+    ///
+    /// If the bytes are out of bounds of the shared linear memory.
+    fn bytes_at(&self, offset: usize, len: usize) -> Result<*mut u8, MemoryError> {
+        offset
+            .checked_add(len)
+            .filter(|&end| end <= self.len())
+            .ok_or(MemoryError::OutOfBoundsAccess)?;
+        Ok(self.inner.base.wrapping_add(offset))
+    }
+
+    /// Returns a pointer to the value of type `T` at `address` for atomic accesses.
+    ///
+    /// # Errors
+    ///
+    /// - If `address` is not aligned to the size of `T`.
+    /// - If the value is out of bounds of the shared linear memory.
+    fn atomic_ptr<T: AtomicInt>(&self, address: usize) -> Result<*mut u8, MemoryError> {
+        check_alignment::<T>(address)?;
+        self.bytes_at(address, mem::size_of::<T>())
+    }
+
+    /// Reads `n` bytes from `memory[offset..offset+n]` into `buffer`
+    /// where `n` is the length of `buffer`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    pub fn read(&self, offset: usize, buffer: &mut [u8]) -> Result<(), MemoryError> {
+        let src = self.bytes_at(offset, buffer.len())?;
+        // # SAFETY
+        //
+        // The bytes are in bounds of the shared linear memory and cannot overlap
+        // with `buffer` since it is borrowed exclusively.
+        unsafe { ptr::copy_nonoverlapping(src, buffer.as_mut_ptr(), buffer.len()) };
+        Ok(())
+    }
+
+    /// Writes `n` bytes to `memory[offset..offset+n]` from `buffer`
+    /// where `n` if the length of `buffer`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    pub fn write(&self, offset: usize, buffer: &[u8]) -> Result<(), MemoryError> {
+        let dst = self.bytes_at(offset, buffer.len())?;
+        // # SAFETY
+        //
+        // The bytes are in bounds of the shared linear memory and cannot overlap
+        // with `buffer` since it is borrowed.
+        unsafe { ptr::copy_nonoverlapping(buffer.as_ptr(), dst, buffer.len()) };
+        Ok(())
+    }
+
+    /// Atomically loads a value of type `T` from the shared linear memory at `address`.
+    pub(super) fn atomic_load<T: AtomicInt>(&self, address: usize) -> Result<T, MemoryError> {
+        let ptr = self.atomic_ptr::<T>(address)?;
+        // # SAFETY
+        //
+        // The pointer is in bounds and aligned due to the checks of `atomic_ptr`.
+        Ok(unsafe { T::atomic_load(ptr) })
+    }
+
+    /// Atomically stores `value` of type `T` into the shared linear memory at `address`.
+    pub(super) fn atomic_store<T: AtomicInt>(
+        &self,
+        address: usize,
+        value: T,
+    ) -> Result<(), MemoryError> {
+        let ptr = self.atomic_ptr::<T>(address)?;
+        // # SAFETY: See `atomic_load`.
+        unsafe { T::atomic_store(ptr, value) };
+        Ok(())
+    }
+
+    /// Atomically replaces the value of type `T` at `address` with the result of `op` applied to it and `value`.
+    pub(super) fn atomic_rmw<T: AtomicInt>(
+        &self,
+        address: usize,
+        value: T,
+        op: fn(T, T) -> T,
+    ) -> Result<T, MemoryError> {
+        let ptr = self.atomic_ptr::<T>(address)?;
+        // # SAFETY: See `atomic_load`.
+        Ok(unsafe { T::atomic_rmw(ptr, value, op) })
+    }
+
+    /// Atomically replaces the value of type `T` at `address` with `replacement` if it is equal to `expected`.
+    pub(super) fn atomic_cmpxchg<T: AtomicInt>(
+        &self,
+        address: usize,
+        expected: T,
+        replacement: T,
+    ) -> Result<T, MemoryError> {
+        let ptr = self.atomic_ptr::<T>(address)?;
+        // # SAFETY: See `atomic_load`.
+        Ok(unsafe { T::atomic_cmpxchg(ptr, expected, replacement) })
+    }
+
+    /// Unparks up to `count` threads waiting on the 32-bit value at `address`.
+    ///
+    /// Returns the amount of unparked threads.
+    ///
+    /// # Errors
+    ///
+    /// - If `address` is not aligned to 4 bytes.
+    /// - If this operation accesses out of bounds linear memory.
+    pub fn atomic_notify(&self, address: usize, count: u32) -> Result<u32, MemoryError> {
+        let ptr = self.atomic_ptr::<u32>(address)?;
+        Ok(self.inner.parker.unpark(ptr as usize, count))
+    }
+
+    /// Parks the current thread on the 32-bit value at `address` if it is equal to `expected`.
+    ///
+    /// The thread is parked until it is unparked via [`SharedMemory::atomic_notify`]
+    /// or the `timeout` expires. A `timeout` of `None` parks the thread indefinitely.
+    ///
+    /// # Errors
+    ///
+    /// - If `address` is not aligned to 4 bytes.
+    /// - If this operation accesses out of bounds linear memory.
+    pub fn atomic_wait32(
+        &self,
+        address: usize,
+        expected: u32,
+        timeout: Option<Duration>,
+    ) -> Result<ParkResult, MemoryError> {
+        self.atomic_wait(address, expected, timeout)
+    }
+
+    /// Parks the current thread on the 64-bit value at `address` if it is equal to `expected`.
+    ///
+    /// The thread is parked until it is unparked via [`SharedMemory::atomic_notify`]
+    /// or the `timeout` expires. A `timeout` of `None` parks the thread indefinitely.
+    ///
+    /// # Errors
+    ///
+    /// - If `address` is not aligned to 8 bytes.
+    /// - If this operation accesses out of bounds linear memory.
+    pub fn atomic_wait64(
+        &self,
+        address: usize,
+        expected: u64,
+        timeout: Option<Duration>,
+    ) -> Result<ParkResult, MemoryError> {
+        self.atomic_wait(address, expected, timeout)
+    }
+
+    /// Parks the current thread on the value of type `T` at `address` if it is equal to `expected`.
+    pub(crate) fn atomic_wait<T: AtomicInt>(
+        &self,
+        address: usize,
+        expected: T,
+        timeout: Option<Duration>,
+    ) -> Result<ParkResult, MemoryError> {
+        let ptr = self.atomic_ptr::<T>(address)?;
+        // # SAFETY: See `atomic_load`.
+        let mut validate = || unsafe { T::atomic_load(ptr) } == expected;
+        Ok(self.inner.parker.park(ptr as usize, &mut validate, timeout))
+    }
+}
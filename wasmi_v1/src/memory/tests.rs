@@ -0,0 +1,23 @@
+use super::*;
+use crate::{Config, Engine, Linker, Module, Store};
+
+#[test]
+fn linker_shared_memory_is_allocated_once_per_store() {
+    let wasm = wat::parse_str(r#"(module (import "host" "memory" (memory 1 4 shared)))"#).unwrap();
+    let engine = Engine::new(&Config::default().enable_threads(true));
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let memory = SharedMemory::new(MemoryType::new(1, Some(4)).into_shared()).unwrap();
+    let mut linker = <Linker<()>>::new();
+    linker
+        .define_shared_memory("host", "memory", memory)
+        .unwrap();
+    let mut store = Store::new(&engine, ());
+    for _ in 0..10 {
+        linker.instantiate(&mut store, &module).unwrap();
+    }
+    assert_eq!(store.len_memories(), 1);
+    // Another store allocates its own linear memory for the shared bytes.
+    let mut other = Store::new(&engine, ());
+    linker.instantiate(&mut other, &module).unwrap();
+    assert_eq!(other.len_memories(), 1);
+}
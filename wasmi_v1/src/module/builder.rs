@@ -10,10 +10,15 @@ use super::{
     ImportKind,
     ImportName,
     InitExpr,
+    MemoryIdx,
     Module,
+    ModuleHash,
+    ModuleNames,
+    TableIdx,
+    TagIdx,
 };
 use crate::{
-    engine::{DedupFuncType, FuncBody},
+    engine::{DedupFuncType, FuncBodies},
     Engine,
     FuncType,
     GlobalType,
@@ -33,11 +38,13 @@ pub struct ModuleBuilder<'engine> {
     pub(super) memories: Vec<MemoryType>,
     pub(super) globals: Vec<GlobalType>,
     pub(super) globals_init: Vec<InitExpr>,
+    pub(super) tags: Vec<DedupFuncType>,
     pub(super) exports: Vec<Export>,
     pub(super) start: Option<FuncIdx>,
-    pub(super) func_bodies: Vec<FuncBody>,
+    pub(super) func_bodies: FuncBodies,
     pub(super) element_segments: Vec<ElementSegment>,
     pub(super) data_segments: Vec<DataSegment>,
+    pub(super) names: ModuleNames,
 }
 
 /// The import names of the [`Module`] imports.
@@ -47,6 +54,7 @@ pub struct ModuleImports {
     pub(super) tables: Vec<ImportName>,
     pub(super) memories: Vec<ImportName>,
     pub(super) globals: Vec<ImportName>,
+    pub(super) tags: Vec<ImportName>,
 }
 
 /// The resources of a [`Module`] required for translating function bodies.
@@ -80,6 +88,21 @@ impl<'a> ModuleResources<'a> {
     pub fn get_type_of_global(&self, global_idx: GlobalIdx) -> GlobalType {
         self.res.globals[global_idx.into_usize()]
     }
+
+    /// Returns the [`TableType`] the the indexed table.
+    pub fn get_type_of_table(&self, table_idx: TableIdx) -> TableType {
+        self.res.tables[table_idx.into_usize()]
+    }
+
+    /// Returns the [`MemoryType`] the the indexed linear memory.
+    pub fn get_type_of_memory(&self, memory_idx: MemoryIdx) -> MemoryType {
+        self.res.memories[memory_idx.into_usize()]
+    }
+
+    /// Returns the signature of the indexed exception tag.
+    pub fn get_type_of_tag(&self, tag_idx: TagIdx) -> DedupFuncType {
+        self.res.tags[tag_idx.into_usize()]
+    }
 }
 
 impl<'engine> ModuleBuilder<'engine> {
@@ -94,11 +117,13 @@ impl<'engine> ModuleBuilder<'engine> {
             memories: Vec::new(),
             globals: Vec::new(),
             globals_init: Vec::new(),
+            tags: Vec::new(),
             exports: Vec::new(),
             start: None,
-            func_bodies: Vec::new(),
+            func_bodies: FuncBodies::new(engine),
             element_segments: Vec::new(),
             data_segments: Vec::new(),
+            names: ModuleNames::default(),
         }
     }
 
@@ -170,6 +195,11 @@ impl<'engine> ModuleBuilder<'engine> {
                     self.imports.globals.push(name);
                     self.globals.push(global_type);
                 }
+                ImportKind::Tag(func_type_idx) => {
+                    self.imports.tags.push(name);
+                    let func_type = self.func_types[func_type_idx.into_usize()];
+                    self.tags.push(func_type);
+                }
             }
         }
         Ok(())
@@ -292,6 +322,35 @@ impl<'engine> ModuleBuilder<'engine> {
         Ok(())
     }
 
+    /// Pushes the given exception tag declarations to the [`Module`] under construction.
+    ///
+    /// # Errors
+    ///
+    /// If an exception tag declaration fails to validate.
+    ///
+    /// # Panics
+    ///
+    /// If this function has already been called on the same [`ModuleBuilder`].
+    pub fn push_tags<T>(&mut self, tags: T) -> Result<(), ModuleError>
+    where
+        T: IntoIterator<Item = Result<FuncTypeIdx, ModuleError>>,
+        T::IntoIter: ExactSizeIterator,
+    {
+        assert_eq!(
+            self.tags.len(),
+            self.imports.tags.len(),
+            "tried to initialize module exception tag declarations twice"
+        );
+        let tags = tags.into_iter();
+        self.tags.reserve_exact(tags.len());
+        for tag in tags {
+            let func_type_idx = tag?;
+            let func_type = self.func_types[func_type_idx.into_usize()];
+            self.tags.push(func_type);
+        }
+        Ok(())
+    }
+
     /// Pushes the given exports to the [`Module`] under construction.
     ///
     /// # Errors
@@ -314,6 +373,15 @@ impl<'engine> ModuleBuilder<'engine> {
         Ok(())
     }
 
+    /// Sets the debug names of the [`Module`].
+    ///
+    /// # Note
+    ///
+    /// Replaces the names of a previously processed `name` custom section.
+    pub fn set_names(&mut self, names: ModuleNames) {
+        self.names = names;
+    }
+
     /// Sets the start function of the [`Module`] to the given index.
     ///
     /// # Panics
@@ -374,7 +442,7 @@ impl<'engine> ModuleBuilder<'engine> {
     }
 
     /// Finishes construction of the WebAssembly [`Module`].
-    pub fn finish(self) -> Module {
-        Module::from_builder(self)
+    pub fn finish(self, hash: ModuleHash) -> Module {
+        Module::from_builder(self, hash)
     }
 }
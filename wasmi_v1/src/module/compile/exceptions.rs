@@ -0,0 +1,42 @@
+use super::{BlockType, FunctionTranslator};
+use crate::{module::TagIdx, ModuleError};
+use wasmparser::TypeOrFuncType;
+
+impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
+    /// Translate a Wasm `try` control flow operator.
+    pub fn translate_try(&mut self, ty: TypeOrFuncType) -> Result<(), ModuleError> {
+        let block_type = BlockType::try_from_wasmparser(ty, self.res)?;
+        self.func_builder.translate_try(block_type)?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `catch` control flow operator.
+    pub fn translate_catch(&mut self, tag_index: u32) -> Result<(), ModuleError> {
+        self.func_builder.translate_catch(TagIdx(tag_index))?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `catch_all` control flow operator.
+    pub fn translate_catch_all(&mut self) -> Result<(), ModuleError> {
+        self.func_builder.translate_catch_all()?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `delegate` control flow operator.
+    pub fn translate_delegate(&mut self, relative_depth: u32) -> Result<(), ModuleError> {
+        self.func_builder.translate_delegate(relative_depth)?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `throw` instruction.
+    pub fn translate_throw(&mut self, tag_index: u32) -> Result<(), ModuleError> {
+        self.func_builder.translate_throw(TagIdx(tag_index))?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `rethrow` instruction.
+    pub fn translate_rethrow(&mut self, relative_depth: u32) -> Result<(), ModuleError> {
+        self.func_builder.translate_rethrow(relative_depth)?;
+        Ok(())
+    }
+}
@@ -8,7 +8,10 @@ use crate::{
 use wasmparser::{FuncValidator, FunctionBody, Operator, ValidatorResources};
 
 mod block_type;
+mod exceptions;
 mod operator;
+mod simd;
+mod threads;
 
 /// Translates the Wasm bytecode into `wasmi` bytecode.
 ///
@@ -45,6 +48,8 @@ struct FunctionTranslator<'engine, 'parser> {
     func_builder: FunctionBuilder<'engine, 'parser>,
     /// The Wasm validator.
     validator: FuncValidator<ValidatorResources>,
+    /// Is `true` if the offsets of the Wasm operators are recorded for Wasm backtraces.
+    record_offsets: bool,
     /// The `wasmi` module resources.
     ///
     /// Provides immutable information about the translated Wasm module
@@ -62,12 +67,14 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         res: ModuleResources<'parser>,
     ) -> Self {
         let func_builder = FunctionBuilder::new(engine, func, res);
+        let record_offsets = engine.config().wasm_backtrace();
         Self {
             engine,
             func,
             func_body,
             func_builder,
             validator,
+            record_offsets,
             res,
         }
     }
@@ -105,6 +112,9 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         while !reader.eof() {
             let (operator, offset) = reader.read_with_offset()?;
             self.validator.op(offset, &operator)?;
+            if self.record_offsets {
+                self.func_builder.set_source_offset(offset);
+            }
             self.translate_operator(operator)?;
         }
         reader.ensure_end()?;
@@ -122,10 +132,10 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
             Operator::Loop { ty } => self.translate_loop(ty),
             Operator::If { ty } => self.translate_if(ty),
             Operator::Else => self.translate_else(),
-            Operator::Try { .. }
-            | Operator::Catch { .. }
-            | Operator::Throw { .. }
-            | Operator::Rethrow { .. } => unsupported_error(),
+            Operator::Try { ty } => self.translate_try(ty),
+            Operator::Catch { index } => self.translate_catch(index),
+            Operator::Throw { index } => self.translate_throw(index),
+            Operator::Rethrow { relative_depth } => self.translate_rethrow(relative_depth),
             Operator::End => self.translate_end(),
             Operator::Br { relative_depth } => self.translate_br(relative_depth),
             Operator::BrIf { relative_depth } => self.translate_br_if(relative_depth),
@@ -135,13 +145,15 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
             Operator::CallIndirect { index, table_index } => {
                 self.translate_call_indirect(index, table_index)
             }
-            Operator::ReturnCall { .. }
-            | Operator::ReturnCallIndirect { .. }
-            | Operator::Delegate { .. }
-            | Operator::CatchAll => unsupported_error(),
+            Operator::ReturnCall { function_index } => self.translate_return_call(function_index),
+            Operator::ReturnCallIndirect { index, table_index } => {
+                self.translate_return_call_indirect(index, table_index)
+            }
+            Operator::Delegate { relative_depth } => self.translate_delegate(relative_depth),
+            Operator::CatchAll => self.translate_catch_all(),
             Operator::Drop => self.translate_drop(),
             Operator::Select => self.translate_select(),
-            Operator::TypedSelect { ty: _ } => unsupported_error(),
+            Operator::TypedSelect { ty } => self.translate_typed_select(ty),
             Operator::LocalGet { local_index } => self.translate_local_get(local_index),
             Operator::LocalSet { local_index } => self.translate_local_set(local_index),
             Operator::LocalTee { local_index } => self.translate_local_tee(local_index),
@@ -176,9 +188,9 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
             Operator::I64Const { value } => self.translate_i64_const(value),
             Operator::F32Const { value } => self.translate_f32_const(value),
             Operator::F64Const { value } => self.translate_f64_const(value),
-            Operator::RefNull { .. } | Operator::RefIsNull | Operator::RefFunc { .. } => {
-                unsupported_error()
-            }
+            Operator::RefNull { ty } => self.translate_ref_null(ty),
+            Operator::RefIsNull => self.translate_ref_is_null(),
+            Operator::RefFunc { function_index } => self.translate_ref_func(function_index),
             Operator::I32Eqz => self.translate_i32_eqz(),
             Operator::I32Eq => self.translate_i32_eq(),
             Operator::I32Ne => self.translate_i32_ne(),
@@ -315,86 +327,22 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
             Operator::I64Extend8S => self.translate_i64_sign_extend8(),
             Operator::I64Extend16S => self.translate_i64_sign_extend16(),
             Operator::I64Extend32S => self.translate_i64_sign_extend32(),
-            Operator::MemoryInit { .. }
-            | Operator::DataDrop { .. }
-            | Operator::MemoryCopy { .. }
-            | Operator::MemoryFill { .. }
-            | Operator::TableInit { .. }
-            | Operator::ElemDrop { .. }
-            | Operator::TableCopy { .. }
-            | Operator::TableFill { .. }
-            | Operator::TableGet { .. }
-            | Operator::TableSet { .. }
-            | Operator::TableGrow { .. }
-            | Operator::TableSize { .. }
-            | Operator::MemoryAtomicNotify { .. }
-            | Operator::MemoryAtomicWait32 { .. }
-            | Operator::MemoryAtomicWait64 { .. }
-            | Operator::AtomicFence { .. }
-            | Operator::I32AtomicLoad { .. }
-            | Operator::I64AtomicLoad { .. }
-            | Operator::I32AtomicLoad8U { .. }
-            | Operator::I32AtomicLoad16U { .. }
-            | Operator::I64AtomicLoad8U { .. }
-            | Operator::I64AtomicLoad16U { .. }
-            | Operator::I64AtomicLoad32U { .. }
-            | Operator::I32AtomicStore { .. }
-            | Operator::I64AtomicStore { .. }
-            | Operator::I32AtomicStore8 { .. }
-            | Operator::I32AtomicStore16 { .. }
-            | Operator::I64AtomicStore8 { .. }
-            | Operator::I64AtomicStore16 { .. }
-            | Operator::I64AtomicStore32 { .. }
-            | Operator::I32AtomicRmwAdd { .. }
-            | Operator::I64AtomicRmwAdd { .. }
-            | Operator::I32AtomicRmw8AddU { .. }
-            | Operator::I32AtomicRmw16AddU { .. }
-            | Operator::I64AtomicRmw8AddU { .. }
-            | Operator::I64AtomicRmw16AddU { .. }
-            | Operator::I64AtomicRmw32AddU { .. }
-            | Operator::I32AtomicRmwSub { .. }
-            | Operator::I64AtomicRmwSub { .. }
-            | Operator::I32AtomicRmw8SubU { .. }
-            | Operator::I32AtomicRmw16SubU { .. }
-            | Operator::I64AtomicRmw8SubU { .. }
-            | Operator::I64AtomicRmw16SubU { .. }
-            | Operator::I64AtomicRmw32SubU { .. }
-            | Operator::I32AtomicRmwAnd { .. }
-            | Operator::I64AtomicRmwAnd { .. }
-            | Operator::I32AtomicRmw8AndU { .. }
-            | Operator::I32AtomicRmw16AndU { .. }
-            | Operator::I64AtomicRmw8AndU { .. }
-            | Operator::I64AtomicRmw16AndU { .. }
-            | Operator::I64AtomicRmw32AndU { .. }
-            | Operator::I32AtomicRmwOr { .. }
-            | Operator::I64AtomicRmwOr { .. }
-            | Operator::I32AtomicRmw8OrU { .. }
-            | Operator::I32AtomicRmw16OrU { .. }
-            | Operator::I64AtomicRmw8OrU { .. }
-            | Operator::I64AtomicRmw16OrU { .. }
-            | Operator::I64AtomicRmw32OrU { .. }
-            | Operator::I32AtomicRmwXor { .. }
-            | Operator::I64AtomicRmwXor { .. }
-            | Operator::I32AtomicRmw8XorU { .. }
-            | Operator::I32AtomicRmw16XorU { .. }
-            | Operator::I64AtomicRmw8XorU { .. }
-            | Operator::I64AtomicRmw16XorU { .. }
-            | Operator::I64AtomicRmw32XorU { .. }
-            | Operator::I32AtomicRmwXchg { .. }
-            | Operator::I64AtomicRmwXchg { .. }
-            | Operator::I32AtomicRmw8XchgU { .. }
-            | Operator::I32AtomicRmw16XchgU { .. }
-            | Operator::I64AtomicRmw8XchgU { .. }
-            | Operator::I64AtomicRmw16XchgU { .. }
-            | Operator::I64AtomicRmw32XchgU { .. }
-            | Operator::I32AtomicRmwCmpxchg { .. }
-            | Operator::I64AtomicRmwCmpxchg { .. }
-            | Operator::I32AtomicRmw8CmpxchgU { .. }
-            | Operator::I32AtomicRmw16CmpxchgU { .. }
-            | Operator::I64AtomicRmw8CmpxchgU { .. }
-            | Operator::I64AtomicRmw16CmpxchgU { .. }
-            | Operator::I64AtomicRmw32CmpxchgU { .. }
-            | Operator::V128Load { .. }
+            Operator::MemoryInit { segment, mem } => self.translate_memory_init(segment, mem),
+            Operator::DataDrop { segment } => self.translate_data_drop(segment),
+            Operator::MemoryCopy { src, dst } => self.translate_memory_copy(dst, src),
+            Operator::MemoryFill { mem } => self.translate_memory_fill(mem),
+            Operator::TableInit { segment, table } => self.translate_table_init(segment, table),
+            Operator::ElemDrop { segment } => self.translate_elem_drop(segment),
+            Operator::TableCopy {
+                dst_table,
+                src_table,
+            } => self.translate_table_copy(dst_table, src_table),
+            Operator::TableFill { table } => self.translate_table_fill(table),
+            Operator::TableGet { table } => self.translate_table_get(table),
+            Operator::TableSet { table } => self.translate_table_set(table),
+            Operator::TableGrow { table } => self.translate_table_grow(table),
+            Operator::TableSize { table } => self.translate_table_size(table),
+            Operator::V128Load { .. }
             | Operator::V128Load8x8S { .. }
             | Operator::V128Load8x8U { .. }
             | Operator::V128Load16x4S { .. }
@@ -629,8 +577,75 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
             | Operator::F64x2ConvertLowI32x4S
             | Operator::F64x2ConvertLowI32x4U
             | Operator::F32x4DemoteF64x2Zero
-            | Operator::F64x2PromoteLowF32x4
-            | Operator::I8x16RelaxedSwizzle
+            | Operator::F64x2PromoteLowF32x4 => self.translate_simd(operator),
+            Operator::MemoryAtomicNotify { .. }
+            | Operator::MemoryAtomicWait32 { .. }
+            | Operator::MemoryAtomicWait64 { .. }
+            | Operator::AtomicFence { .. }
+            | Operator::I32AtomicLoad { .. }
+            | Operator::I64AtomicLoad { .. }
+            | Operator::I32AtomicLoad8U { .. }
+            | Operator::I32AtomicLoad16U { .. }
+            | Operator::I64AtomicLoad8U { .. }
+            | Operator::I64AtomicLoad16U { .. }
+            | Operator::I64AtomicLoad32U { .. }
+            | Operator::I32AtomicStore { .. }
+            | Operator::I64AtomicStore { .. }
+            | Operator::I32AtomicStore8 { .. }
+            | Operator::I32AtomicStore16 { .. }
+            | Operator::I64AtomicStore8 { .. }
+            | Operator::I64AtomicStore16 { .. }
+            | Operator::I64AtomicStore32 { .. }
+            | Operator::I32AtomicRmwAdd { .. }
+            | Operator::I64AtomicRmwAdd { .. }
+            | Operator::I32AtomicRmw8AddU { .. }
+            | Operator::I32AtomicRmw16AddU { .. }
+            | Operator::I64AtomicRmw8AddU { .. }
+            | Operator::I64AtomicRmw16AddU { .. }
+            | Operator::I64AtomicRmw32AddU { .. }
+            | Operator::I32AtomicRmwSub { .. }
+            | Operator::I64AtomicRmwSub { .. }
+            | Operator::I32AtomicRmw8SubU { .. }
+            | Operator::I32AtomicRmw16SubU { .. }
+            | Operator::I64AtomicRmw8SubU { .. }
+            | Operator::I64AtomicRmw16SubU { .. }
+            | Operator::I64AtomicRmw32SubU { .. }
+            | Operator::I32AtomicRmwAnd { .. }
+            | Operator::I64AtomicRmwAnd { .. }
+            | Operator::I32AtomicRmw8AndU { .. }
+            | Operator::I32AtomicRmw16AndU { .. }
+            | Operator::I64AtomicRmw8AndU { .. }
+            | Operator::I64AtomicRmw16AndU { .. }
+            | Operator::I64AtomicRmw32AndU { .. }
+            | Operator::I32AtomicRmwOr { .. }
+            | Operator::I64AtomicRmwOr { .. }
+            | Operator::I32AtomicRmw8OrU { .. }
+            | Operator::I32AtomicRmw16OrU { .. }
+            | Operator::I64AtomicRmw8OrU { .. }
+            | Operator::I64AtomicRmw16OrU { .. }
+            | Operator::I64AtomicRmw32OrU { .. }
+            | Operator::I32AtomicRmwXor { .. }
+            | Operator::I64AtomicRmwXor { .. }
+            | Operator::I32AtomicRmw8XorU { .. }
+            | Operator::I32AtomicRmw16XorU { .. }
+            | Operator::I64AtomicRmw8XorU { .. }
+            | Operator::I64AtomicRmw16XorU { .. }
+            | Operator::I64AtomicRmw32XorU { .. }
+            | Operator::I32AtomicRmwXchg { .. }
+            | Operator::I64AtomicRmwXchg { .. }
+            | Operator::I32AtomicRmw8XchgU { .. }
+            | Operator::I32AtomicRmw16XchgU { .. }
+            | Operator::I64AtomicRmw8XchgU { .. }
+            | Operator::I64AtomicRmw16XchgU { .. }
+            | Operator::I64AtomicRmw32XchgU { .. }
+            | Operator::I32AtomicRmwCmpxchg { .. }
+            | Operator::I64AtomicRmwCmpxchg { .. }
+            | Operator::I32AtomicRmw8CmpxchgU { .. }
+            | Operator::I32AtomicRmw16CmpxchgU { .. }
+            | Operator::I64AtomicRmw8CmpxchgU { .. }
+            | Operator::I64AtomicRmw16CmpxchgU { .. }
+            | Operator::I64AtomicRmw32CmpxchgU { .. } => self.translate_threads(operator),
+            Operator::I8x16RelaxedSwizzle
             | Operator::I32x4RelaxedTruncSatF32x4S
             | Operator::I32x4RelaxedTruncSatF32x4U
             | Operator::I32x4RelaxedTruncSatF64x2SZero
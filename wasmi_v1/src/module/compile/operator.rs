@@ -1,10 +1,17 @@
 use super::{BlockType, FunctionTranslator};
 use crate::{
     engine::RelativeDepth,
-    module::{export::TableIdx, import::FuncTypeIdx, FuncIdx, GlobalIdx, MemoryIdx},
+    module::{
+        export::TableIdx,
+        import::FuncTypeIdx,
+        utils::value_type_from_wasmparser,
+        FuncIdx,
+        GlobalIdx,
+        MemoryIdx,
+    },
     ModuleError,
 };
-use wasmparser::{Ieee32, Ieee64, TypeOrFuncType};
+use wasmparser::{Ieee32, Ieee64, Type, TypeOrFuncType};
 
 impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
     /// Translate a Wasm `nop` (no operation) instruction.
@@ -84,6 +91,30 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         Ok(())
     }
 
+    /// Translate a Wasm `return_call` instruction.
+    pub fn translate_return_call(&mut self, func_idx: u32) -> Result<(), ModuleError> {
+        self.func_builder.translate_return_call(FuncIdx(func_idx))?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `return_call_indirect` instruction.
+    pub fn translate_return_call_indirect(
+        &mut self,
+        func_type_idx: u32,
+        table_idx: u32,
+    ) -> Result<(), ModuleError> {
+        self.func_builder
+            .translate_return_call_indirect(FuncTypeIdx(func_type_idx), TableIdx(table_idx))?;
+        Ok(())
+    }
+
+    /// Translate a Wasm typed `select` instruction.
+    pub fn translate_typed_select(&mut self, ty: Type) -> Result<(), ModuleError> {
+        let value_type = value_type_from_wasmparser(&ty)?;
+        self.func_builder.translate_typed_select(value_type)?;
+        Ok(())
+    }
+
     /// Translate a Wasm `local.get` instruction.
     pub fn translate_local_get(&mut self, local_idx: u32) -> Result<(), ModuleError> {
         self.func_builder.translate_local_get(local_idx)?;
@@ -122,7 +153,7 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         memarg: wasmparser::MemoryImmediate,
     ) -> Result<(), ModuleError> {
         let memory_idx = MemoryIdx(memarg.memory);
-        let offset = memarg.offset as u32;
+        let offset = memarg.offset;
         self.func_builder.translate_i32_load(memory_idx, offset)?;
         Ok(())
     }
@@ -133,7 +164,7 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         memarg: wasmparser::MemoryImmediate,
     ) -> Result<(), ModuleError> {
         let memory_idx = MemoryIdx(memarg.memory);
-        let offset = memarg.offset as u32;
+        let offset = memarg.offset;
         self.func_builder.translate_i64_load(memory_idx, offset)?;
         Ok(())
     }
@@ -144,7 +175,7 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         memarg: wasmparser::MemoryImmediate,
     ) -> Result<(), ModuleError> {
         let memory_idx = MemoryIdx(memarg.memory);
-        let offset = memarg.offset as u32;
+        let offset = memarg.offset;
         self.func_builder.translate_f32_load(memory_idx, offset)?;
         Ok(())
     }
@@ -155,7 +186,7 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         memarg: wasmparser::MemoryImmediate,
     ) -> Result<(), ModuleError> {
         let memory_idx = MemoryIdx(memarg.memory);
-        let offset = memarg.offset as u32;
+        let offset = memarg.offset;
         self.func_builder.translate_f64_load(memory_idx, offset)?;
         Ok(())
     }
@@ -166,7 +197,7 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         memarg: wasmparser::MemoryImmediate,
     ) -> Result<(), ModuleError> {
         let memory_idx = MemoryIdx(memarg.memory);
-        let offset = memarg.offset as u32;
+        let offset = memarg.offset;
         self.func_builder
             .translate_i32_load_i8(memory_idx, offset)?;
         Ok(())
@@ -178,7 +209,7 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         memarg: wasmparser::MemoryImmediate,
     ) -> Result<(), ModuleError> {
         let memory_idx = MemoryIdx(memarg.memory);
-        let offset = memarg.offset as u32;
+        let offset = memarg.offset;
         self.func_builder
             .translate_i32_load_u8(memory_idx, offset)?;
         Ok(())
@@ -190,7 +221,7 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         memarg: wasmparser::MemoryImmediate,
     ) -> Result<(), ModuleError> {
         let memory_idx = MemoryIdx(memarg.memory);
-        let offset = memarg.offset as u32;
+        let offset = memarg.offset;
         self.func_builder
             .translate_i32_load_i16(memory_idx, offset)?;
         Ok(())
@@ -202,7 +233,7 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         memarg: wasmparser::MemoryImmediate,
     ) -> Result<(), ModuleError> {
         let memory_idx = MemoryIdx(memarg.memory);
-        let offset = memarg.offset as u32;
+        let offset = memarg.offset;
         self.func_builder
             .translate_i32_load_u16(memory_idx, offset)?;
         Ok(())
@@ -214,7 +245,7 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         memarg: wasmparser::MemoryImmediate,
     ) -> Result<(), ModuleError> {
         let memory_idx = MemoryIdx(memarg.memory);
-        let offset = memarg.offset as u32;
+        let offset = memarg.offset;
         self.func_builder
             .translate_i64_load_i8(memory_idx, offset)?;
         Ok(())
@@ -226,7 +257,7 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         memarg: wasmparser::MemoryImmediate,
     ) -> Result<(), ModuleError> {
         let memory_idx = MemoryIdx(memarg.memory);
-        let offset = memarg.offset as u32;
+        let offset = memarg.offset;
         self.func_builder
             .translate_i64_load_u8(memory_idx, offset)?;
         Ok(())
@@ -238,7 +269,7 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         memarg: wasmparser::MemoryImmediate,
     ) -> Result<(), ModuleError> {
         let memory_idx = MemoryIdx(memarg.memory);
-        let offset = memarg.offset as u32;
+        let offset = memarg.offset;
         self.func_builder
             .translate_i64_load_i16(memory_idx, offset)?;
         Ok(())
@@ -250,7 +281,7 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         memarg: wasmparser::MemoryImmediate,
     ) -> Result<(), ModuleError> {
         let memory_idx = MemoryIdx(memarg.memory);
-        let offset = memarg.offset as u32;
+        let offset = memarg.offset;
         self.func_builder
             .translate_i64_load_u16(memory_idx, offset)?;
         Ok(())
@@ -262,7 +293,7 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         memarg: wasmparser::MemoryImmediate,
     ) -> Result<(), ModuleError> {
         let memory_idx = MemoryIdx(memarg.memory);
-        let offset = memarg.offset as u32;
+        let offset = memarg.offset;
         self.func_builder
             .translate_i64_load_i32(memory_idx, offset)?;
         Ok(())
@@ -274,7 +305,7 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         memarg: wasmparser::MemoryImmediate,
     ) -> Result<(), ModuleError> {
         let memory_idx = MemoryIdx(memarg.memory);
-        let offset = memarg.offset as u32;
+        let offset = memarg.offset;
         self.func_builder
             .translate_i64_load_u32(memory_idx, offset)?;
         Ok(())
@@ -286,7 +317,7 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         memarg: wasmparser::MemoryImmediate,
     ) -> Result<(), ModuleError> {
         let memory_idx = MemoryIdx(memarg.memory);
-        let offset = memarg.offset as u32;
+        let offset = memarg.offset;
         self.func_builder.translate_i32_store(memory_idx, offset)?;
         Ok(())
     }
@@ -297,7 +328,7 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         memarg: wasmparser::MemoryImmediate,
     ) -> Result<(), ModuleError> {
         let memory_idx = MemoryIdx(memarg.memory);
-        let offset = memarg.offset as u32;
+        let offset = memarg.offset;
         self.func_builder.translate_i64_store(memory_idx, offset)?;
         Ok(())
     }
@@ -308,7 +339,7 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         memarg: wasmparser::MemoryImmediate,
     ) -> Result<(), ModuleError> {
         let memory_idx = MemoryIdx(memarg.memory);
-        let offset = memarg.offset as u32;
+        let offset = memarg.offset;
         self.func_builder.translate_f32_store(memory_idx, offset)?;
         Ok(())
     }
@@ -319,7 +350,7 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         memarg: wasmparser::MemoryImmediate,
     ) -> Result<(), ModuleError> {
         let memory_idx = MemoryIdx(memarg.memory);
-        let offset = memarg.offset as u32;
+        let offset = memarg.offset;
         self.func_builder.translate_f64_store(memory_idx, offset)?;
         Ok(())
     }
@@ -330,7 +361,7 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         memarg: wasmparser::MemoryImmediate,
     ) -> Result<(), ModuleError> {
         let memory_idx = MemoryIdx(memarg.memory);
-        let offset = memarg.offset as u32;
+        let offset = memarg.offset;
         self.func_builder
             .translate_i32_store_i8(memory_idx, offset)?;
         Ok(())
@@ -342,7 +373,7 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         memarg: wasmparser::MemoryImmediate,
     ) -> Result<(), ModuleError> {
         let memory_idx = MemoryIdx(memarg.memory);
-        let offset = memarg.offset as u32;
+        let offset = memarg.offset;
         self.func_builder
             .translate_i32_store_i16(memory_idx, offset)?;
         Ok(())
@@ -354,7 +385,7 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         memarg: wasmparser::MemoryImmediate,
     ) -> Result<(), ModuleError> {
         let memory_idx = MemoryIdx(memarg.memory);
-        let offset = memarg.offset as u32;
+        let offset = memarg.offset;
         self.func_builder
             .translate_i64_store_i8(memory_idx, offset)?;
         Ok(())
@@ -366,7 +397,7 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         memarg: wasmparser::MemoryImmediate,
     ) -> Result<(), ModuleError> {
         let memory_idx = MemoryIdx(memarg.memory);
-        let offset = memarg.offset as u32;
+        let offset = memarg.offset;
         self.func_builder
             .translate_i64_store_i16(memory_idx, offset)?;
         Ok(())
@@ -378,7 +409,7 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         memarg: wasmparser::MemoryImmediate,
     ) -> Result<(), ModuleError> {
         let memory_idx = MemoryIdx(memarg.memory);
-        let offset = memarg.offset as u32;
+        let offset = memarg.offset;
         self.func_builder
             .translate_i64_store_i32(memory_idx, offset)?;
         Ok(())
@@ -406,6 +437,117 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         Ok(())
     }
 
+    /// Translate a Wasm `memory.init` instruction.
+    pub fn translate_memory_init(
+        &mut self,
+        segment: u32,
+        memory_idx: u32,
+    ) -> Result<(), ModuleError> {
+        self.func_builder
+            .translate_memory_init(segment, MemoryIdx(memory_idx))?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `data.drop` instruction.
+    pub fn translate_data_drop(&mut self, segment: u32) -> Result<(), ModuleError> {
+        self.func_builder.translate_data_drop(segment)?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `memory.copy` instruction.
+    pub fn translate_memory_copy(&mut self, dst: u32, src: u32) -> Result<(), ModuleError> {
+        self.func_builder
+            .translate_memory_copy(MemoryIdx(dst), MemoryIdx(src))?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `memory.fill` instruction.
+    pub fn translate_memory_fill(&mut self, memory_idx: u32) -> Result<(), ModuleError> {
+        self.func_builder
+            .translate_memory_fill(MemoryIdx(memory_idx))?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `table.init` instruction.
+    pub fn translate_table_init(
+        &mut self,
+        segment: u32,
+        table_idx: u32,
+    ) -> Result<(), ModuleError> {
+        self.func_builder
+            .translate_table_init(segment, TableIdx(table_idx))?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `elem.drop` instruction.
+    pub fn translate_elem_drop(&mut self, segment: u32) -> Result<(), ModuleError> {
+        self.func_builder.translate_elem_drop(segment)?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `table.copy` instruction.
+    pub fn translate_table_copy(
+        &mut self,
+        dst_table: u32,
+        src_table: u32,
+    ) -> Result<(), ModuleError> {
+        self.func_builder
+            .translate_table_copy(TableIdx(dst_table), TableIdx(src_table))?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `table.get` instruction.
+    pub fn translate_table_get(&mut self, table_idx: u32) -> Result<(), ModuleError> {
+        self.func_builder.translate_table_get(TableIdx(table_idx))?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `table.set` instruction.
+    pub fn translate_table_set(&mut self, table_idx: u32) -> Result<(), ModuleError> {
+        self.func_builder.translate_table_set(TableIdx(table_idx))?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `table.size` instruction.
+    pub fn translate_table_size(&mut self, table_idx: u32) -> Result<(), ModuleError> {
+        self.func_builder
+            .translate_table_size(TableIdx(table_idx))?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `table.grow` instruction.
+    pub fn translate_table_grow(&mut self, table_idx: u32) -> Result<(), ModuleError> {
+        self.func_builder
+            .translate_table_grow(TableIdx(table_idx))?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `table.fill` instruction.
+    pub fn translate_table_fill(&mut self, table_idx: u32) -> Result<(), ModuleError> {
+        self.func_builder
+            .translate_table_fill(TableIdx(table_idx))?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `ref.null` instruction.
+    pub fn translate_ref_null(&mut self, ty: Type) -> Result<(), ModuleError> {
+        let value_type = value_type_from_wasmparser(&ty)?;
+        self.func_builder.translate_ref_null(value_type)?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `ref.is_null` instruction.
+    pub fn translate_ref_is_null(&mut self) -> Result<(), ModuleError> {
+        self.func_builder.translate_ref_is_null()?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `ref.func` instruction.
+    pub fn translate_ref_func(&mut self, func_idx: u32) -> Result<(), ModuleError> {
+        self.func_builder.translate_ref_func(FuncIdx(func_idx))?;
+        Ok(())
+    }
+
     /// Translate a Wasm `i32.const` instruction.
     pub fn translate_i32_const(&mut self, value: i32) -> Result<(), ModuleError> {
         self.func_builder.translate_i32_const(value)?;
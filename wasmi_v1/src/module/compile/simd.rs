@@ -0,0 +1,639 @@
+use super::FunctionTranslator;
+use crate::ModuleError;
+#[cfg(feature = "simd")]
+use crate::{engine::bytecode::SimdInstruction, module::MemoryIdx};
+#[cfg(feature = "simd")]
+use wasmi_core::{ValueType, V128};
+#[cfg(feature = "simd")]
+use wasmparser::MemoryImmediate;
+use wasmparser::Operator;
+
+impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
+    /// Translate a Wasm operator of the `simd` Wasm proposal.
+    ///
+    /// # Errors
+    ///
+    /// If the `simd` crate feature is disabled.
+    #[cfg(not(feature = "simd"))]
+    pub fn translate_simd(&mut self, operator: Operator) -> Result<(), ModuleError> {
+        Err(ModuleError::unsupported(&operator))
+    }
+
+    /// Translate a Wasm operator of the `simd` Wasm proposal.
+    ///
+    /// # Errors
+    ///
+    /// If the `operator` is not part of the `simd` Wasm proposal.
+    #[cfg(feature = "simd")]
+    pub fn translate_simd(&mut self, operator: Operator) -> Result<(), ModuleError> {
+        let builder = &mut self.func_builder;
+        match operator {
+            Operator::V128Load { memarg } => builder.translate_v128_load(
+                MemoryIdx(memarg.memory),
+                simd_offset(memarg)?,
+                |memory, offset| SimdInstruction::V128Load { memory, offset },
+            ),
+            Operator::V128Load8x8S { memarg } => builder.translate_v128_load(
+                MemoryIdx(memarg.memory),
+                simd_offset(memarg)?,
+                |memory, offset| SimdInstruction::V128Load8x8S { memory, offset },
+            ),
+            Operator::V128Load8x8U { memarg } => builder.translate_v128_load(
+                MemoryIdx(memarg.memory),
+                simd_offset(memarg)?,
+                |memory, offset| SimdInstruction::V128Load8x8U { memory, offset },
+            ),
+            Operator::V128Load16x4S { memarg } => builder.translate_v128_load(
+                MemoryIdx(memarg.memory),
+                simd_offset(memarg)?,
+                |memory, offset| SimdInstruction::V128Load16x4S { memory, offset },
+            ),
+            Operator::V128Load16x4U { memarg } => builder.translate_v128_load(
+                MemoryIdx(memarg.memory),
+                simd_offset(memarg)?,
+                |memory, offset| SimdInstruction::V128Load16x4U { memory, offset },
+            ),
+            Operator::V128Load32x2S { memarg } => builder.translate_v128_load(
+                MemoryIdx(memarg.memory),
+                simd_offset(memarg)?,
+                |memory, offset| SimdInstruction::V128Load32x2S { memory, offset },
+            ),
+            Operator::V128Load32x2U { memarg } => builder.translate_v128_load(
+                MemoryIdx(memarg.memory),
+                simd_offset(memarg)?,
+                |memory, offset| SimdInstruction::V128Load32x2U { memory, offset },
+            ),
+            Operator::V128Load8Splat { memarg } => builder.translate_v128_load(
+                MemoryIdx(memarg.memory),
+                simd_offset(memarg)?,
+                |memory, offset| SimdInstruction::V128Load8Splat { memory, offset },
+            ),
+            Operator::V128Load16Splat { memarg } => builder.translate_v128_load(
+                MemoryIdx(memarg.memory),
+                simd_offset(memarg)?,
+                |memory, offset| SimdInstruction::V128Load16Splat { memory, offset },
+            ),
+            Operator::V128Load32Splat { memarg } => builder.translate_v128_load(
+                MemoryIdx(memarg.memory),
+                simd_offset(memarg)?,
+                |memory, offset| SimdInstruction::V128Load32Splat { memory, offset },
+            ),
+            Operator::V128Load64Splat { memarg } => builder.translate_v128_load(
+                MemoryIdx(memarg.memory),
+                simd_offset(memarg)?,
+                |memory, offset| SimdInstruction::V128Load64Splat { memory, offset },
+            ),
+            Operator::V128Load32Zero { memarg } => builder.translate_v128_load(
+                MemoryIdx(memarg.memory),
+                simd_offset(memarg)?,
+                |memory, offset| SimdInstruction::V128Load32Zero { memory, offset },
+            ),
+            Operator::V128Load64Zero { memarg } => builder.translate_v128_load(
+                MemoryIdx(memarg.memory),
+                simd_offset(memarg)?,
+                |memory, offset| SimdInstruction::V128Load64Zero { memory, offset },
+            ),
+            Operator::V128Store { memarg } => builder.translate_v128_store(
+                MemoryIdx(memarg.memory),
+                simd_offset(memarg)?,
+                |memory, offset| SimdInstruction::V128Store { memory, offset },
+            ),
+            Operator::V128Load8Lane { memarg, lane } => builder.translate_v128_load_lane(
+                MemoryIdx(memarg.memory),
+                simd_offset(memarg)?,
+                lane,
+                |memory, offset, lane| SimdInstruction::V128Load8Lane {
+                    memory,
+                    offset,
+                    lane,
+                },
+            ),
+            Operator::V128Load16Lane { memarg, lane } => builder.translate_v128_load_lane(
+                MemoryIdx(memarg.memory),
+                simd_offset(memarg)?,
+                lane,
+                |memory, offset, lane| SimdInstruction::V128Load16Lane {
+                    memory,
+                    offset,
+                    lane,
+                },
+            ),
+            Operator::V128Load32Lane { memarg, lane } => builder.translate_v128_load_lane(
+                MemoryIdx(memarg.memory),
+                simd_offset(memarg)?,
+                lane,
+                |memory, offset, lane| SimdInstruction::V128Load32Lane {
+                    memory,
+                    offset,
+                    lane,
+                },
+            ),
+            Operator::V128Load64Lane { memarg, lane } => builder.translate_v128_load_lane(
+                MemoryIdx(memarg.memory),
+                simd_offset(memarg)?,
+                lane,
+                |memory, offset, lane| SimdInstruction::V128Load64Lane {
+                    memory,
+                    offset,
+                    lane,
+                },
+            ),
+            Operator::V128Store8Lane { memarg, lane } => builder.translate_v128_store_lane(
+                MemoryIdx(memarg.memory),
+                simd_offset(memarg)?,
+                lane,
+                |memory, offset, lane| SimdInstruction::V128Store8Lane {
+                    memory,
+                    offset,
+                    lane,
+                },
+            ),
+            Operator::V128Store16Lane { memarg, lane } => builder.translate_v128_store_lane(
+                MemoryIdx(memarg.memory),
+                simd_offset(memarg)?,
+                lane,
+                |memory, offset, lane| SimdInstruction::V128Store16Lane {
+                    memory,
+                    offset,
+                    lane,
+                },
+            ),
+            Operator::V128Store32Lane { memarg, lane } => builder.translate_v128_store_lane(
+                MemoryIdx(memarg.memory),
+                simd_offset(memarg)?,
+                lane,
+                |memory, offset, lane| SimdInstruction::V128Store32Lane {
+                    memory,
+                    offset,
+                    lane,
+                },
+            ),
+            Operator::V128Store64Lane { memarg, lane } => builder.translate_v128_store_lane(
+                MemoryIdx(memarg.memory),
+                simd_offset(memarg)?,
+                lane,
+                |memory, offset, lane| SimdInstruction::V128Store64Lane {
+                    memory,
+                    offset,
+                    lane,
+                },
+            ),
+            Operator::V128Const { value } => {
+                builder.translate_v128_const(V128::from_bits(value.i128() as u128))
+            }
+            Operator::I8x16Shuffle { lanes } => builder.translate_i8x16_shuffle(lanes),
+            Operator::I8x16Splat => builder.translate_simd_conversion(
+                ValueType::I32,
+                ValueType::V128,
+                SimdInstruction::I8x16Splat,
+            ),
+            Operator::I16x8Splat => builder.translate_simd_conversion(
+                ValueType::I32,
+                ValueType::V128,
+                SimdInstruction::I16x8Splat,
+            ),
+            Operator::I32x4Splat => builder.translate_simd_conversion(
+                ValueType::I32,
+                ValueType::V128,
+                SimdInstruction::I32x4Splat,
+            ),
+            Operator::I64x2Splat => builder.translate_simd_conversion(
+                ValueType::I64,
+                ValueType::V128,
+                SimdInstruction::I64x2Splat,
+            ),
+            Operator::F32x4Splat => builder.translate_simd_conversion(
+                ValueType::F32,
+                ValueType::V128,
+                SimdInstruction::F32x4Splat,
+            ),
+            Operator::F64x2Splat => builder.translate_simd_conversion(
+                ValueType::F64,
+                ValueType::V128,
+                SimdInstruction::F64x2Splat,
+            ),
+            Operator::I8x16ExtractLaneS { lane } => builder.translate_simd_conversion(
+                ValueType::V128,
+                ValueType::I32,
+                SimdInstruction::I8x16ExtractLaneS { lane },
+            ),
+            Operator::I8x16ExtractLaneU { lane } => builder.translate_simd_conversion(
+                ValueType::V128,
+                ValueType::I32,
+                SimdInstruction::I8x16ExtractLaneU { lane },
+            ),
+            Operator::I16x8ExtractLaneS { lane } => builder.translate_simd_conversion(
+                ValueType::V128,
+                ValueType::I32,
+                SimdInstruction::I16x8ExtractLaneS { lane },
+            ),
+            Operator::I16x8ExtractLaneU { lane } => builder.translate_simd_conversion(
+                ValueType::V128,
+                ValueType::I32,
+                SimdInstruction::I16x8ExtractLaneU { lane },
+            ),
+            Operator::I32x4ExtractLane { lane } => builder.translate_simd_conversion(
+                ValueType::V128,
+                ValueType::I32,
+                SimdInstruction::I32x4ExtractLane { lane },
+            ),
+            Operator::I64x2ExtractLane { lane } => builder.translate_simd_conversion(
+                ValueType::V128,
+                ValueType::I64,
+                SimdInstruction::I64x2ExtractLane { lane },
+            ),
+            Operator::F32x4ExtractLane { lane } => builder.translate_simd_conversion(
+                ValueType::V128,
+                ValueType::F32,
+                SimdInstruction::F32x4ExtractLane { lane },
+            ),
+            Operator::F64x2ExtractLane { lane } => builder.translate_simd_conversion(
+                ValueType::V128,
+                ValueType::F64,
+                SimdInstruction::F64x2ExtractLane { lane },
+            ),
+            Operator::I8x16ReplaceLane { lane } => builder.translate_simd_vector_scalar(
+                ValueType::I32,
+                SimdInstruction::I8x16ReplaceLane { lane },
+            ),
+            Operator::I16x8ReplaceLane { lane } => builder.translate_simd_vector_scalar(
+                ValueType::I32,
+                SimdInstruction::I16x8ReplaceLane { lane },
+            ),
+            Operator::I32x4ReplaceLane { lane } => builder.translate_simd_vector_scalar(
+                ValueType::I32,
+                SimdInstruction::I32x4ReplaceLane { lane },
+            ),
+            Operator::I64x2ReplaceLane { lane } => builder.translate_simd_vector_scalar(
+                ValueType::I64,
+                SimdInstruction::I64x2ReplaceLane { lane },
+            ),
+            Operator::F32x4ReplaceLane { lane } => builder.translate_simd_vector_scalar(
+                ValueType::F32,
+                SimdInstruction::F32x4ReplaceLane { lane },
+            ),
+            Operator::F64x2ReplaceLane { lane } => builder.translate_simd_vector_scalar(
+                ValueType::F64,
+                SimdInstruction::F64x2ReplaceLane { lane },
+            ),
+            Operator::V128AnyTrue => builder.translate_simd_conversion(
+                ValueType::V128,
+                ValueType::I32,
+                SimdInstruction::V128AnyTrue,
+            ),
+            Operator::I8x16AllTrue => builder.translate_simd_conversion(
+                ValueType::V128,
+                ValueType::I32,
+                SimdInstruction::I8x16AllTrue,
+            ),
+            Operator::I8x16Bitmask => builder.translate_simd_conversion(
+                ValueType::V128,
+                ValueType::I32,
+                SimdInstruction::I8x16Bitmask,
+            ),
+            Operator::I16x8AllTrue => builder.translate_simd_conversion(
+                ValueType::V128,
+                ValueType::I32,
+                SimdInstruction::I16x8AllTrue,
+            ),
+            Operator::I16x8Bitmask => builder.translate_simd_conversion(
+                ValueType::V128,
+                ValueType::I32,
+                SimdInstruction::I16x8Bitmask,
+            ),
+            Operator::I32x4AllTrue => builder.translate_simd_conversion(
+                ValueType::V128,
+                ValueType::I32,
+                SimdInstruction::I32x4AllTrue,
+            ),
+            Operator::I32x4Bitmask => builder.translate_simd_conversion(
+                ValueType::V128,
+                ValueType::I32,
+                SimdInstruction::I32x4Bitmask,
+            ),
+            Operator::I64x2AllTrue => builder.translate_simd_conversion(
+                ValueType::V128,
+                ValueType::I32,
+                SimdInstruction::I64x2AllTrue,
+            ),
+            Operator::I64x2Bitmask => builder.translate_simd_conversion(
+                ValueType::V128,
+                ValueType::I32,
+                SimdInstruction::I64x2Bitmask,
+            ),
+            Operator::I8x16Shl => {
+                builder.translate_simd_vector_scalar(ValueType::I32, SimdInstruction::I8x16Shl)
+            }
+            Operator::I8x16ShrS => {
+                builder.translate_simd_vector_scalar(ValueType::I32, SimdInstruction::I8x16ShrS)
+            }
+            Operator::I8x16ShrU => {
+                builder.translate_simd_vector_scalar(ValueType::I32, SimdInstruction::I8x16ShrU)
+            }
+            Operator::I16x8Shl => {
+                builder.translate_simd_vector_scalar(ValueType::I32, SimdInstruction::I16x8Shl)
+            }
+            Operator::I16x8ShrS => {
+                builder.translate_simd_vector_scalar(ValueType::I32, SimdInstruction::I16x8ShrS)
+            }
+            Operator::I16x8ShrU => {
+                builder.translate_simd_vector_scalar(ValueType::I32, SimdInstruction::I16x8ShrU)
+            }
+            Operator::I32x4Shl => {
+                builder.translate_simd_vector_scalar(ValueType::I32, SimdInstruction::I32x4Shl)
+            }
+            Operator::I32x4ShrS => {
+                builder.translate_simd_vector_scalar(ValueType::I32, SimdInstruction::I32x4ShrS)
+            }
+            Operator::I32x4ShrU => {
+                builder.translate_simd_vector_scalar(ValueType::I32, SimdInstruction::I32x4ShrU)
+            }
+            Operator::I64x2Shl => {
+                builder.translate_simd_vector_scalar(ValueType::I32, SimdInstruction::I64x2Shl)
+            }
+            Operator::I64x2ShrS => {
+                builder.translate_simd_vector_scalar(ValueType::I32, SimdInstruction::I64x2ShrS)
+            }
+            Operator::I64x2ShrU => {
+                builder.translate_simd_vector_scalar(ValueType::I32, SimdInstruction::I64x2ShrU)
+            }
+            Operator::V128Not => builder.translate_simd_unary(SimdInstruction::V128Not),
+            Operator::I8x16Abs => builder.translate_simd_unary(SimdInstruction::I8x16Abs),
+            Operator::I8x16Neg => builder.translate_simd_unary(SimdInstruction::I8x16Neg),
+            Operator::I8x16Popcnt => builder.translate_simd_unary(SimdInstruction::I8x16Popcnt),
+            Operator::I16x8ExtAddPairwiseI8x16S => {
+                builder.translate_simd_unary(SimdInstruction::I16x8ExtAddPairwiseI8x16S)
+            }
+            Operator::I16x8ExtAddPairwiseI8x16U => {
+                builder.translate_simd_unary(SimdInstruction::I16x8ExtAddPairwiseI8x16U)
+            }
+            Operator::I16x8Abs => builder.translate_simd_unary(SimdInstruction::I16x8Abs),
+            Operator::I16x8Neg => builder.translate_simd_unary(SimdInstruction::I16x8Neg),
+            Operator::I16x8ExtendLowI8x16S => {
+                builder.translate_simd_unary(SimdInstruction::I16x8ExtendLowI8x16S)
+            }
+            Operator::I16x8ExtendHighI8x16S => {
+                builder.translate_simd_unary(SimdInstruction::I16x8ExtendHighI8x16S)
+            }
+            Operator::I16x8ExtendLowI8x16U => {
+                builder.translate_simd_unary(SimdInstruction::I16x8ExtendLowI8x16U)
+            }
+            Operator::I16x8ExtendHighI8x16U => {
+                builder.translate_simd_unary(SimdInstruction::I16x8ExtendHighI8x16U)
+            }
+            Operator::I32x4ExtAddPairwiseI16x8S => {
+                builder.translate_simd_unary(SimdInstruction::I32x4ExtAddPairwiseI16x8S)
+            }
+            Operator::I32x4ExtAddPairwiseI16x8U => {
+                builder.translate_simd_unary(SimdInstruction::I32x4ExtAddPairwiseI16x8U)
+            }
+            Operator::I32x4Abs => builder.translate_simd_unary(SimdInstruction::I32x4Abs),
+            Operator::I32x4Neg => builder.translate_simd_unary(SimdInstruction::I32x4Neg),
+            Operator::I32x4ExtendLowI16x8S => {
+                builder.translate_simd_unary(SimdInstruction::I32x4ExtendLowI16x8S)
+            }
+            Operator::I32x4ExtendHighI16x8S => {
+                builder.translate_simd_unary(SimdInstruction::I32x4ExtendHighI16x8S)
+            }
+            Operator::I32x4ExtendLowI16x8U => {
+                builder.translate_simd_unary(SimdInstruction::I32x4ExtendLowI16x8U)
+            }
+            Operator::I32x4ExtendHighI16x8U => {
+                builder.translate_simd_unary(SimdInstruction::I32x4ExtendHighI16x8U)
+            }
+            Operator::I64x2Abs => builder.translate_simd_unary(SimdInstruction::I64x2Abs),
+            Operator::I64x2Neg => builder.translate_simd_unary(SimdInstruction::I64x2Neg),
+            Operator::I64x2ExtendLowI32x4S => {
+                builder.translate_simd_unary(SimdInstruction::I64x2ExtendLowI32x4S)
+            }
+            Operator::I64x2ExtendHighI32x4S => {
+                builder.translate_simd_unary(SimdInstruction::I64x2ExtendHighI32x4S)
+            }
+            Operator::I64x2ExtendLowI32x4U => {
+                builder.translate_simd_unary(SimdInstruction::I64x2ExtendLowI32x4U)
+            }
+            Operator::I64x2ExtendHighI32x4U => {
+                builder.translate_simd_unary(SimdInstruction::I64x2ExtendHighI32x4U)
+            }
+            Operator::F32x4Ceil => builder.translate_simd_unary(SimdInstruction::F32x4Ceil),
+            Operator::F32x4Floor => builder.translate_simd_unary(SimdInstruction::F32x4Floor),
+            Operator::F32x4Trunc => builder.translate_simd_unary(SimdInstruction::F32x4Trunc),
+            Operator::F32x4Nearest => builder.translate_simd_unary(SimdInstruction::F32x4Nearest),
+            Operator::F32x4Abs => builder.translate_simd_unary(SimdInstruction::F32x4Abs),
+            Operator::F32x4Neg => builder.translate_simd_unary(SimdInstruction::F32x4Neg),
+            Operator::F32x4Sqrt => builder.translate_simd_unary(SimdInstruction::F32x4Sqrt),
+            Operator::F64x2Ceil => builder.translate_simd_unary(SimdInstruction::F64x2Ceil),
+            Operator::F64x2Floor => builder.translate_simd_unary(SimdInstruction::F64x2Floor),
+            Operator::F64x2Trunc => builder.translate_simd_unary(SimdInstruction::F64x2Trunc),
+            Operator::F64x2Nearest => builder.translate_simd_unary(SimdInstruction::F64x2Nearest),
+            Operator::F64x2Abs => builder.translate_simd_unary(SimdInstruction::F64x2Abs),
+            Operator::F64x2Neg => builder.translate_simd_unary(SimdInstruction::F64x2Neg),
+            Operator::F64x2Sqrt => builder.translate_simd_unary(SimdInstruction::F64x2Sqrt),
+            Operator::I32x4TruncSatF32x4S => {
+                builder.translate_simd_unary(SimdInstruction::I32x4TruncSatF32x4S)
+            }
+            Operator::I32x4TruncSatF32x4U => {
+                builder.translate_simd_unary(SimdInstruction::I32x4TruncSatF32x4U)
+            }
+            Operator::F32x4ConvertI32x4S => {
+                builder.translate_simd_unary(SimdInstruction::F32x4ConvertI32x4S)
+            }
+            Operator::F32x4ConvertI32x4U => {
+                builder.translate_simd_unary(SimdInstruction::F32x4ConvertI32x4U)
+            }
+            Operator::I32x4TruncSatF64x2SZero => {
+                builder.translate_simd_unary(SimdInstruction::I32x4TruncSatF64x2SZero)
+            }
+            Operator::I32x4TruncSatF64x2UZero => {
+                builder.translate_simd_unary(SimdInstruction::I32x4TruncSatF64x2UZero)
+            }
+            Operator::F64x2ConvertLowI32x4S => {
+                builder.translate_simd_unary(SimdInstruction::F64x2ConvertLowI32x4S)
+            }
+            Operator::F64x2ConvertLowI32x4U => {
+                builder.translate_simd_unary(SimdInstruction::F64x2ConvertLowI32x4U)
+            }
+            Operator::F32x4DemoteF64x2Zero => {
+                builder.translate_simd_unary(SimdInstruction::F32x4DemoteF64x2Zero)
+            }
+            Operator::F64x2PromoteLowF32x4 => {
+                builder.translate_simd_unary(SimdInstruction::F64x2PromoteLowF32x4)
+            }
+            Operator::I8x16Swizzle => builder.translate_simd_binary(SimdInstruction::I8x16Swizzle),
+            Operator::I8x16Eq => builder.translate_simd_binary(SimdInstruction::I8x16Eq),
+            Operator::I8x16Ne => builder.translate_simd_binary(SimdInstruction::I8x16Ne),
+            Operator::I8x16LtS => builder.translate_simd_binary(SimdInstruction::I8x16LtS),
+            Operator::I8x16LtU => builder.translate_simd_binary(SimdInstruction::I8x16LtU),
+            Operator::I8x16GtS => builder.translate_simd_binary(SimdInstruction::I8x16GtS),
+            Operator::I8x16GtU => builder.translate_simd_binary(SimdInstruction::I8x16GtU),
+            Operator::I8x16LeS => builder.translate_simd_binary(SimdInstruction::I8x16LeS),
+            Operator::I8x16LeU => builder.translate_simd_binary(SimdInstruction::I8x16LeU),
+            Operator::I8x16GeS => builder.translate_simd_binary(SimdInstruction::I8x16GeS),
+            Operator::I8x16GeU => builder.translate_simd_binary(SimdInstruction::I8x16GeU),
+            Operator::I16x8Eq => builder.translate_simd_binary(SimdInstruction::I16x8Eq),
+            Operator::I16x8Ne => builder.translate_simd_binary(SimdInstruction::I16x8Ne),
+            Operator::I16x8LtS => builder.translate_simd_binary(SimdInstruction::I16x8LtS),
+            Operator::I16x8LtU => builder.translate_simd_binary(SimdInstruction::I16x8LtU),
+            Operator::I16x8GtS => builder.translate_simd_binary(SimdInstruction::I16x8GtS),
+            Operator::I16x8GtU => builder.translate_simd_binary(SimdInstruction::I16x8GtU),
+            Operator::I16x8LeS => builder.translate_simd_binary(SimdInstruction::I16x8LeS),
+            Operator::I16x8LeU => builder.translate_simd_binary(SimdInstruction::I16x8LeU),
+            Operator::I16x8GeS => builder.translate_simd_binary(SimdInstruction::I16x8GeS),
+            Operator::I16x8GeU => builder.translate_simd_binary(SimdInstruction::I16x8GeU),
+            Operator::I32x4Eq => builder.translate_simd_binary(SimdInstruction::I32x4Eq),
+            Operator::I32x4Ne => builder.translate_simd_binary(SimdInstruction::I32x4Ne),
+            Operator::I32x4LtS => builder.translate_simd_binary(SimdInstruction::I32x4LtS),
+            Operator::I32x4LtU => builder.translate_simd_binary(SimdInstruction::I32x4LtU),
+            Operator::I32x4GtS => builder.translate_simd_binary(SimdInstruction::I32x4GtS),
+            Operator::I32x4GtU => builder.translate_simd_binary(SimdInstruction::I32x4GtU),
+            Operator::I32x4LeS => builder.translate_simd_binary(SimdInstruction::I32x4LeS),
+            Operator::I32x4LeU => builder.translate_simd_binary(SimdInstruction::I32x4LeU),
+            Operator::I32x4GeS => builder.translate_simd_binary(SimdInstruction::I32x4GeS),
+            Operator::I32x4GeU => builder.translate_simd_binary(SimdInstruction::I32x4GeU),
+            Operator::I64x2Eq => builder.translate_simd_binary(SimdInstruction::I64x2Eq),
+            Operator::I64x2Ne => builder.translate_simd_binary(SimdInstruction::I64x2Ne),
+            Operator::I64x2LtS => builder.translate_simd_binary(SimdInstruction::I64x2LtS),
+            Operator::I64x2GtS => builder.translate_simd_binary(SimdInstruction::I64x2GtS),
+            Operator::I64x2LeS => builder.translate_simd_binary(SimdInstruction::I64x2LeS),
+            Operator::I64x2GeS => builder.translate_simd_binary(SimdInstruction::I64x2GeS),
+            Operator::F32x4Eq => builder.translate_simd_binary(SimdInstruction::F32x4Eq),
+            Operator::F32x4Ne => builder.translate_simd_binary(SimdInstruction::F32x4Ne),
+            Operator::F32x4Lt => builder.translate_simd_binary(SimdInstruction::F32x4Lt),
+            Operator::F32x4Gt => builder.translate_simd_binary(SimdInstruction::F32x4Gt),
+            Operator::F32x4Le => builder.translate_simd_binary(SimdInstruction::F32x4Le),
+            Operator::F32x4Ge => builder.translate_simd_binary(SimdInstruction::F32x4Ge),
+            Operator::F64x2Eq => builder.translate_simd_binary(SimdInstruction::F64x2Eq),
+            Operator::F64x2Ne => builder.translate_simd_binary(SimdInstruction::F64x2Ne),
+            Operator::F64x2Lt => builder.translate_simd_binary(SimdInstruction::F64x2Lt),
+            Operator::F64x2Gt => builder.translate_simd_binary(SimdInstruction::F64x2Gt),
+            Operator::F64x2Le => builder.translate_simd_binary(SimdInstruction::F64x2Le),
+            Operator::F64x2Ge => builder.translate_simd_binary(SimdInstruction::F64x2Ge),
+            Operator::V128And => builder.translate_simd_binary(SimdInstruction::V128And),
+            Operator::V128AndNot => builder.translate_simd_binary(SimdInstruction::V128AndNot),
+            Operator::V128Or => builder.translate_simd_binary(SimdInstruction::V128Or),
+            Operator::V128Xor => builder.translate_simd_binary(SimdInstruction::V128Xor),
+            Operator::I8x16NarrowI16x8S => {
+                builder.translate_simd_binary(SimdInstruction::I8x16NarrowI16x8S)
+            }
+            Operator::I8x16NarrowI16x8U => {
+                builder.translate_simd_binary(SimdInstruction::I8x16NarrowI16x8U)
+            }
+            Operator::I8x16Add => builder.translate_simd_binary(SimdInstruction::I8x16Add),
+            Operator::I8x16AddSatS => builder.translate_simd_binary(SimdInstruction::I8x16AddSatS),
+            Operator::I8x16AddSatU => builder.translate_simd_binary(SimdInstruction::I8x16AddSatU),
+            Operator::I8x16Sub => builder.translate_simd_binary(SimdInstruction::I8x16Sub),
+            Operator::I8x16SubSatS => builder.translate_simd_binary(SimdInstruction::I8x16SubSatS),
+            Operator::I8x16SubSatU => builder.translate_simd_binary(SimdInstruction::I8x16SubSatU),
+            Operator::I8x16MinS => builder.translate_simd_binary(SimdInstruction::I8x16MinS),
+            Operator::I8x16MinU => builder.translate_simd_binary(SimdInstruction::I8x16MinU),
+            Operator::I8x16MaxS => builder.translate_simd_binary(SimdInstruction::I8x16MaxS),
+            Operator::I8x16MaxU => builder.translate_simd_binary(SimdInstruction::I8x16MaxU),
+            Operator::I8x16RoundingAverageU => {
+                builder.translate_simd_binary(SimdInstruction::I8x16RoundingAverageU)
+            }
+            Operator::I16x8Q15MulrSatS => {
+                builder.translate_simd_binary(SimdInstruction::I16x8Q15MulrSatS)
+            }
+            Operator::I16x8NarrowI32x4S => {
+                builder.translate_simd_binary(SimdInstruction::I16x8NarrowI32x4S)
+            }
+            Operator::I16x8NarrowI32x4U => {
+                builder.translate_simd_binary(SimdInstruction::I16x8NarrowI32x4U)
+            }
+            Operator::I16x8Add => builder.translate_simd_binary(SimdInstruction::I16x8Add),
+            Operator::I16x8AddSatS => builder.translate_simd_binary(SimdInstruction::I16x8AddSatS),
+            Operator::I16x8AddSatU => builder.translate_simd_binary(SimdInstruction::I16x8AddSatU),
+            Operator::I16x8Sub => builder.translate_simd_binary(SimdInstruction::I16x8Sub),
+            Operator::I16x8SubSatS => builder.translate_simd_binary(SimdInstruction::I16x8SubSatS),
+            Operator::I16x8SubSatU => builder.translate_simd_binary(SimdInstruction::I16x8SubSatU),
+            Operator::I16x8Mul => builder.translate_simd_binary(SimdInstruction::I16x8Mul),
+            Operator::I16x8MinS => builder.translate_simd_binary(SimdInstruction::I16x8MinS),
+            Operator::I16x8MinU => builder.translate_simd_binary(SimdInstruction::I16x8MinU),
+            Operator::I16x8MaxS => builder.translate_simd_binary(SimdInstruction::I16x8MaxS),
+            Operator::I16x8MaxU => builder.translate_simd_binary(SimdInstruction::I16x8MaxU),
+            Operator::I16x8RoundingAverageU => {
+                builder.translate_simd_binary(SimdInstruction::I16x8RoundingAverageU)
+            }
+            Operator::I16x8ExtMulLowI8x16S => {
+                builder.translate_simd_binary(SimdInstruction::I16x8ExtMulLowI8x16S)
+            }
+            Operator::I16x8ExtMulHighI8x16S => {
+                builder.translate_simd_binary(SimdInstruction::I16x8ExtMulHighI8x16S)
+            }
+            Operator::I16x8ExtMulLowI8x16U => {
+                builder.translate_simd_binary(SimdInstruction::I16x8ExtMulLowI8x16U)
+            }
+            Operator::I16x8ExtMulHighI8x16U => {
+                builder.translate_simd_binary(SimdInstruction::I16x8ExtMulHighI8x16U)
+            }
+            Operator::I32x4Add => builder.translate_simd_binary(SimdInstruction::I32x4Add),
+            Operator::I32x4Sub => builder.translate_simd_binary(SimdInstruction::I32x4Sub),
+            Operator::I32x4Mul => builder.translate_simd_binary(SimdInstruction::I32x4Mul),
+            Operator::I32x4MinS => builder.translate_simd_binary(SimdInstruction::I32x4MinS),
+            Operator::I32x4MinU => builder.translate_simd_binary(SimdInstruction::I32x4MinU),
+            Operator::I32x4MaxS => builder.translate_simd_binary(SimdInstruction::I32x4MaxS),
+            Operator::I32x4MaxU => builder.translate_simd_binary(SimdInstruction::I32x4MaxU),
+            Operator::I32x4DotI16x8S => {
+                builder.translate_simd_binary(SimdInstruction::I32x4DotI16x8S)
+            }
+            Operator::I32x4ExtMulLowI16x8S => {
+                builder.translate_simd_binary(SimdInstruction::I32x4ExtMulLowI16x8S)
+            }
+            Operator::I32x4ExtMulHighI16x8S => {
+                builder.translate_simd_binary(SimdInstruction::I32x4ExtMulHighI16x8S)
+            }
+            Operator::I32x4ExtMulLowI16x8U => {
+                builder.translate_simd_binary(SimdInstruction::I32x4ExtMulLowI16x8U)
+            }
+            Operator::I32x4ExtMulHighI16x8U => {
+                builder.translate_simd_binary(SimdInstruction::I32x4ExtMulHighI16x8U)
+            }
+            Operator::I64x2Add => builder.translate_simd_binary(SimdInstruction::I64x2Add),
+            Operator::I64x2Sub => builder.translate_simd_binary(SimdInstruction::I64x2Sub),
+            Operator::I64x2Mul => builder.translate_simd_binary(SimdInstruction::I64x2Mul),
+            Operator::I64x2ExtMulLowI32x4S => {
+                builder.translate_simd_binary(SimdInstruction::I64x2ExtMulLowI32x4S)
+            }
+            Operator::I64x2ExtMulHighI32x4S => {
+                builder.translate_simd_binary(SimdInstruction::I64x2ExtMulHighI32x4S)
+            }
+            Operator::I64x2ExtMulLowI32x4U => {
+                builder.translate_simd_binary(SimdInstruction::I64x2ExtMulLowI32x4U)
+            }
+            Operator::I64x2ExtMulHighI32x4U => {
+                builder.translate_simd_binary(SimdInstruction::I64x2ExtMulHighI32x4U)
+            }
+            Operator::F32x4Add => builder.translate_simd_binary(SimdInstruction::F32x4Add),
+            Operator::F32x4Sub => builder.translate_simd_binary(SimdInstruction::F32x4Sub),
+            Operator::F32x4Mul => builder.translate_simd_binary(SimdInstruction::F32x4Mul),
+            Operator::F32x4Div => builder.translate_simd_binary(SimdInstruction::F32x4Div),
+            Operator::F32x4Min => builder.translate_simd_binary(SimdInstruction::F32x4Min),
+            Operator::F32x4Max => builder.translate_simd_binary(SimdInstruction::F32x4Max),
+            Operator::F32x4PMin => builder.translate_simd_binary(SimdInstruction::F32x4PMin),
+            Operator::F32x4PMax => builder.translate_simd_binary(SimdInstruction::F32x4PMax),
+            Operator::F64x2Add => builder.translate_simd_binary(SimdInstruction::F64x2Add),
+            Operator::F64x2Sub => builder.translate_simd_binary(SimdInstruction::F64x2Sub),
+            Operator::F64x2Mul => builder.translate_simd_binary(SimdInstruction::F64x2Mul),
+            Operator::F64x2Div => builder.translate_simd_binary(SimdInstruction::F64x2Div),
+            Operator::F64x2Min => builder.translate_simd_binary(SimdInstruction::F64x2Min),
+            Operator::F64x2Max => builder.translate_simd_binary(SimdInstruction::F64x2Max),
+            Operator::F64x2PMin => builder.translate_simd_binary(SimdInstruction::F64x2PMin),
+            Operator::F64x2PMax => builder.translate_simd_binary(SimdInstruction::F64x2PMax),
+            Operator::V128Bitselect => {
+                builder.translate_simd_ternary(SimdInstruction::V128Bitselect)
+            }
+            unsupported => Err(ModuleError::unsupported(&unsupported)),
+        }
+    }
+}
+
+/// Returns the offset of the `memarg` of a Wasm SIMD memory access.
+///
+/// # Errors
+///
+/// If the offset does not fit into 32 bits which is unsupported for
+/// Wasm SIMD memory accesses to 64-bit linear memories.
+#[cfg(feature = "simd")]
+fn simd_offset(memarg: MemoryImmediate) -> Result<u32, ModuleError> {
+    u32::try_from(memarg.offset).map_err(|_| ModuleError::unsupported(memarg))
+}
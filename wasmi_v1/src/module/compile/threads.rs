@@ -0,0 +1,439 @@
+use super::FunctionTranslator;
+use crate::ModuleError;
+#[cfg(feature = "threads")]
+use crate::{engine::bytecode::AtomicInstruction, module::MemoryIdx};
+#[cfg(feature = "threads")]
+use wasmi_core::ValueType;
+#[cfg(feature = "threads")]
+use wasmparser::MemoryImmediate;
+use wasmparser::Operator;
+
+impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
+    /// Translate a Wasm operator of the `threads` Wasm proposal.
+    ///
+    /// # Errors
+    ///
+    /// If the `threads` crate feature is disabled.
+    #[cfg(not(feature = "threads"))]
+    pub fn translate_threads(&mut self, operator: Operator) -> Result<(), ModuleError> {
+        Err(ModuleError::unsupported(&operator))
+    }
+
+    /// Translate a Wasm operator of the `threads` Wasm proposal.
+    ///
+    /// # Errors
+    ///
+    /// If the `operator` is not part of the `threads` Wasm proposal.
+    #[cfg(feature = "threads")]
+    pub fn translate_threads(&mut self, operator: Operator) -> Result<(), ModuleError> {
+        let builder = &mut self.func_builder;
+        match operator {
+            Operator::MemoryAtomicNotify { memarg } => {
+                builder.translate_atomic_notify(MemoryIdx(memarg.memory), atomic_offset(memarg)?)
+            }
+            Operator::MemoryAtomicWait32 { memarg } => builder.translate_atomic_wait(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::MemoryAtomicWait32 { memory, offset },
+            ),
+            Operator::MemoryAtomicWait64 { memarg } => builder.translate_atomic_wait(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::MemoryAtomicWait64 { memory, offset },
+            ),
+            Operator::AtomicFence { .. } => builder.translate_atomic_fence(),
+            Operator::I32AtomicLoad { memarg } => builder.translate_atomic_load(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicLoad { memory, offset },
+            ),
+            Operator::I64AtomicLoad { memarg } => builder.translate_atomic_load(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicLoad { memory, offset },
+            ),
+            Operator::I32AtomicLoad8U { memarg } => builder.translate_atomic_load(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicLoad8U { memory, offset },
+            ),
+            Operator::I32AtomicLoad16U { memarg } => builder.translate_atomic_load(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicLoad16U { memory, offset },
+            ),
+            Operator::I64AtomicLoad8U { memarg } => builder.translate_atomic_load(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicLoad8U { memory, offset },
+            ),
+            Operator::I64AtomicLoad16U { memarg } => builder.translate_atomic_load(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicLoad16U { memory, offset },
+            ),
+            Operator::I64AtomicLoad32U { memarg } => builder.translate_atomic_load(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicLoad32U { memory, offset },
+            ),
+            Operator::I32AtomicStore { memarg } => builder.translate_atomic_store(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicStore { memory, offset },
+            ),
+            Operator::I64AtomicStore { memarg } => builder.translate_atomic_store(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicStore { memory, offset },
+            ),
+            Operator::I32AtomicStore8 { memarg } => builder.translate_atomic_store(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicStore8 { memory, offset },
+            ),
+            Operator::I32AtomicStore16 { memarg } => builder.translate_atomic_store(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicStore16 { memory, offset },
+            ),
+            Operator::I64AtomicStore8 { memarg } => builder.translate_atomic_store(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicStore8 { memory, offset },
+            ),
+            Operator::I64AtomicStore16 { memarg } => builder.translate_atomic_store(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicStore16 { memory, offset },
+            ),
+            Operator::I64AtomicStore32 { memarg } => builder.translate_atomic_store(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicStore32 { memory, offset },
+            ),
+            Operator::I32AtomicRmwAdd { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicRmwAdd { memory, offset },
+            ),
+            Operator::I64AtomicRmwAdd { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmwAdd { memory, offset },
+            ),
+            Operator::I32AtomicRmw8AddU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicRmw8AddU { memory, offset },
+            ),
+            Operator::I32AtomicRmw16AddU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicRmw16AddU { memory, offset },
+            ),
+            Operator::I64AtomicRmw8AddU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmw8AddU { memory, offset },
+            ),
+            Operator::I64AtomicRmw16AddU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmw16AddU { memory, offset },
+            ),
+            Operator::I64AtomicRmw32AddU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmw32AddU { memory, offset },
+            ),
+            Operator::I32AtomicRmwSub { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicRmwSub { memory, offset },
+            ),
+            Operator::I64AtomicRmwSub { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmwSub { memory, offset },
+            ),
+            Operator::I32AtomicRmw8SubU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicRmw8SubU { memory, offset },
+            ),
+            Operator::I32AtomicRmw16SubU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicRmw16SubU { memory, offset },
+            ),
+            Operator::I64AtomicRmw8SubU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmw8SubU { memory, offset },
+            ),
+            Operator::I64AtomicRmw16SubU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmw16SubU { memory, offset },
+            ),
+            Operator::I64AtomicRmw32SubU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmw32SubU { memory, offset },
+            ),
+            Operator::I32AtomicRmwAnd { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicRmwAnd { memory, offset },
+            ),
+            Operator::I64AtomicRmwAnd { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmwAnd { memory, offset },
+            ),
+            Operator::I32AtomicRmw8AndU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicRmw8AndU { memory, offset },
+            ),
+            Operator::I32AtomicRmw16AndU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicRmw16AndU { memory, offset },
+            ),
+            Operator::I64AtomicRmw8AndU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmw8AndU { memory, offset },
+            ),
+            Operator::I64AtomicRmw16AndU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmw16AndU { memory, offset },
+            ),
+            Operator::I64AtomicRmw32AndU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmw32AndU { memory, offset },
+            ),
+            Operator::I32AtomicRmwOr { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicRmwOr { memory, offset },
+            ),
+            Operator::I64AtomicRmwOr { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmwOr { memory, offset },
+            ),
+            Operator::I32AtomicRmw8OrU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicRmw8OrU { memory, offset },
+            ),
+            Operator::I32AtomicRmw16OrU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicRmw16OrU { memory, offset },
+            ),
+            Operator::I64AtomicRmw8OrU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmw8OrU { memory, offset },
+            ),
+            Operator::I64AtomicRmw16OrU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmw16OrU { memory, offset },
+            ),
+            Operator::I64AtomicRmw32OrU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmw32OrU { memory, offset },
+            ),
+            Operator::I32AtomicRmwXor { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicRmwXor { memory, offset },
+            ),
+            Operator::I64AtomicRmwXor { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmwXor { memory, offset },
+            ),
+            Operator::I32AtomicRmw8XorU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicRmw8XorU { memory, offset },
+            ),
+            Operator::I32AtomicRmw16XorU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicRmw16XorU { memory, offset },
+            ),
+            Operator::I64AtomicRmw8XorU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmw8XorU { memory, offset },
+            ),
+            Operator::I64AtomicRmw16XorU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmw16XorU { memory, offset },
+            ),
+            Operator::I64AtomicRmw32XorU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmw32XorU { memory, offset },
+            ),
+            Operator::I32AtomicRmwXchg { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicRmwXchg { memory, offset },
+            ),
+            Operator::I64AtomicRmwXchg { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmwXchg { memory, offset },
+            ),
+            Operator::I32AtomicRmw8XchgU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicRmw8XchgU { memory, offset },
+            ),
+            Operator::I32AtomicRmw16XchgU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicRmw16XchgU { memory, offset },
+            ),
+            Operator::I64AtomicRmw8XchgU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmw8XchgU { memory, offset },
+            ),
+            Operator::I64AtomicRmw16XchgU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmw16XchgU { memory, offset },
+            ),
+            Operator::I64AtomicRmw32XchgU { memarg } => builder.translate_atomic_rmw(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmw32XchgU { memory, offset },
+            ),
+            Operator::I32AtomicRmwCmpxchg { memarg } => builder.translate_atomic_cmpxchg(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicRmwCmpxchg { memory, offset },
+            ),
+            Operator::I64AtomicRmwCmpxchg { memarg } => builder.translate_atomic_cmpxchg(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmwCmpxchg { memory, offset },
+            ),
+            Operator::I32AtomicRmw8CmpxchgU { memarg } => builder.translate_atomic_cmpxchg(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicRmw8CmpxchgU { memory, offset },
+            ),
+            Operator::I32AtomicRmw16CmpxchgU { memarg } => builder.translate_atomic_cmpxchg(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I32,
+                |memory, offset| AtomicInstruction::I32AtomicRmw16CmpxchgU { memory, offset },
+            ),
+            Operator::I64AtomicRmw8CmpxchgU { memarg } => builder.translate_atomic_cmpxchg(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmw8CmpxchgU { memory, offset },
+            ),
+            Operator::I64AtomicRmw16CmpxchgU { memarg } => builder.translate_atomic_cmpxchg(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmw16CmpxchgU { memory, offset },
+            ),
+            Operator::I64AtomicRmw32CmpxchgU { memarg } => builder.translate_atomic_cmpxchg(
+                MemoryIdx(memarg.memory),
+                atomic_offset(memarg)?,
+                ValueType::I64,
+                |memory, offset| AtomicInstruction::I64AtomicRmw32CmpxchgU { memory, offset },
+            ),
+            unsupported => Err(ModuleError::unsupported(&unsupported)),
+        }
+    }
+}
+
+/// Returns the offset of the `memarg` of a Wasm atomic memory access.
+///
+/// # Errors
+///
+/// If the offset does not fit into 32 bits. Such offsets may only
+/// occur for atomic accesses to 64-bit linear memories.
+#[cfg(feature = "threads")]
+fn atomic_offset(memarg: MemoryImmediate) -> Result<u32, ModuleError> {
+    u32::try_from(memarg.offset).map_err(|_| ModuleError::unsupported(memarg))
+}
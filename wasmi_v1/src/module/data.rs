@@ -1,58 +1,131 @@
-use super::{InitExpr, MemoryIdx, ModuleError};
+use super::{Decode, DeserializeError, Encode, InitExpr, MemoryIdx, ModuleError, Reader, Writer};
+use alloc::sync::Arc;
 
 /// A linear memory data segment within a [`Module`].
 ///
 /// [`Module`]: [`super::Module`]
 #[derive(Debug)]
 pub struct DataSegment {
+    kind: DataSegmentKind,
+    data: Arc<[u8]>,
+}
+
+/// The kind of a [`DataSegment`].
+#[derive(Debug)]
+pub enum DataSegmentKind {
+    /// A passive [`DataSegment`] from the `bulk-memory` Wasm proposal.
+    ///
+    /// # Note
+    ///
+    /// Passive data segments are only used via the `memory.init` instruction.
+    Passive,
+    /// An active [`DataSegment`] that is written to its linear memory upon instantiation.
+    Active(ActiveDataSegment),
+}
+
+/// An active data segment that initializes a linear memory upon instantiation.
+#[derive(Debug)]
+pub struct ActiveDataSegment {
+    /// The index of the linear memory that is initialized.
     memory_index: MemoryIdx,
+    /// The offset at which the linear memory is initialized.
     offset: InitExpr,
-    data: Box<[u8]>,
 }
 
-impl TryFrom<wasmparser::Data<'_>> for DataSegment {
+impl ActiveDataSegment {
+    /// Returns the index of the [`Memory`] manipulated by the [`ActiveDataSegment`].
+    ///
+    /// [`Memory`]: [`crate::Memory`]
+    pub fn memory_index(&self) -> MemoryIdx {
+        self.memory_index
+    }
+
+    /// Returns the offset expression of the [`ActiveDataSegment`].
+    pub fn offset(&self) -> &InitExpr {
+        &self.offset
+    }
+}
+
+impl TryFrom<wasmparser::DataKind<'_>> for DataSegmentKind {
     type Error = ModuleError;
 
-    fn try_from(data: wasmparser::Data<'_>) -> Result<Self, Self::Error> {
-        let (memory_index, offset) = match data.kind {
+    fn try_from(data_kind: wasmparser::DataKind<'_>) -> Result<Self, Self::Error> {
+        match data_kind {
             wasmparser::DataKind::Active {
                 memory_index,
                 init_expr,
             } => {
                 let memory_index = MemoryIdx(memory_index);
                 let offset = InitExpr::try_from(init_expr)?;
-                (memory_index, offset)
-            }
-            wasmparser::DataKind::Passive => {
-                return Err(ModuleError::unsupported(
-                    "encountered unsupported passive data segment",
-                ))
+                Ok(Self::Active(ActiveDataSegment {
+                    memory_index,
+                    offset,
+                }))
             }
-        };
+            wasmparser::DataKind::Passive => Ok(Self::Passive),
+        }
+    }
+}
+
+impl TryFrom<wasmparser::Data<'_>> for DataSegment {
+    type Error = ModuleError;
+
+    fn try_from(data: wasmparser::Data<'_>) -> Result<Self, Self::Error> {
+        let kind = DataSegmentKind::try_from(data.kind)?;
         let data = data.data.into();
-        Ok(DataSegment {
-            memory_index,
-            offset,
-            data,
-        })
+        Ok(DataSegment { kind, data })
     }
 }
 
-impl DataSegment {
-    /// Returns the index of the [`Memory`] manipulated by the [`DataSegment`].
-    ///
-    /// [`Memory`]: [`crate::Memory`]
-    pub fn memory_index(&self) -> MemoryIdx {
-        self.memory_index
+impl Encode for DataSegment {
+    fn encode(&self, writer: &mut Writer) {
+        match &self.kind {
+            DataSegmentKind::Passive => writer.write_tag(0),
+            DataSegmentKind::Active(segment) => {
+                writer.write_tag(1);
+                segment.memory_index.encode(writer);
+                segment.offset.encode(writer);
+            }
+        }
+        self.data.encode(writer);
+    }
+}
+
+impl Decode for DataSegment {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        let kind = match reader.read_tag()? {
+            0 => DataSegmentKind::Passive,
+            1 => DataSegmentKind::Active(ActiveDataSegment {
+                memory_index: Decode::decode(reader)?,
+                offset: Decode::decode(reader)?,
+            }),
+            _ => return Err(DeserializeError::malformed("data segment")),
+        };
+        let data = Decode::decode(reader)?;
+        Ok(DataSegment { kind, data })
     }
+}
 
-    /// Returns the offset expression of the [`DataSegment`].
-    pub fn offset(&self) -> &InitExpr {
-        &self.offset
+impl DataSegment {
+    /// Returns the [`DataSegmentKind`] of the [`DataSegment`].
+    pub fn kind(&self) -> &DataSegmentKind {
+        &self.kind
     }
 
-    /// Returns the element items of the [`DataSegment`].
+    /// Returns the bytes of the [`DataSegment`].
     pub fn data(&self) -> &[u8] {
         &self.data[..]
     }
+
+    /// Returns a shared handle to the bytes of the [`DataSegment`].
+    ///
+    /// # Note
+    ///
+    /// This is used to cheaply share the bytes of passive data
+    /// segments between all instances of the same [`Module`].
+    ///
+    /// [`Module`]: [`super::Module`]
+    pub fn shared_data(&self) -> Arc<[u8]> {
+        self.data.clone()
+    }
 }
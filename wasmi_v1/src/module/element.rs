@@ -1,74 +1,172 @@
 use crate::ModuleError;
 
-use super::{FuncIdx, InitExpr, TableIdx};
+use super::{Decode, DeserializeError, Encode, FuncIdx, InitExpr, Reader, TableIdx, Writer};
 
 /// A table element segment within a [`Module`].
 ///
 /// [`Module`]: [`super::Module`]
 #[derive(Debug)]
 pub struct ElementSegment {
+    kind: ElementSegmentKind,
+    items: Box<[Option<FuncIdx>]>,
+}
+
+/// The kind of an [`ElementSegment`].
+#[derive(Debug)]
+pub enum ElementSegmentKind {
+    /// A passive [`ElementSegment`] from the `bulk-memory` Wasm proposal.
+    ///
+    /// # Note
+    ///
+    /// Passive element segments are only used via the `table.init` instruction.
+    Passive,
+    /// An active [`ElementSegment`] that is written to its table upon instantiation.
+    Active(ActiveElementSegment),
+    /// A declared [`ElementSegment`] from the `bulk-memory` Wasm proposal.
+    ///
+    /// # Note
+    ///
+    /// Declared element segments are only used to forward declare function
+    /// references and are dropped upon instantiation.
+    Declared,
+}
+
+/// An active element segment that initializes a table upon instantiation.
+#[derive(Debug)]
+pub struct ActiveElementSegment {
+    /// The index of the table that is initialized.
     table_index: TableIdx,
+    /// The offset at which the table is initialized.
     offset: InitExpr,
-    items: Box<[FuncIdx]>,
 }
 
-impl TryFrom<wasmparser::Element<'_>> for ElementSegment {
+impl ActiveElementSegment {
+    /// Returns the index of the [`Table`] manipulated by the [`ActiveElementSegment`].
+    ///
+    /// [`Table`]: [`crate::Table`]
+    pub fn table_index(&self) -> TableIdx {
+        self.table_index
+    }
+
+    /// Returns the offset expression of the [`ActiveElementSegment`].
+    pub fn offset(&self) -> &InitExpr {
+        &self.offset
+    }
+}
+
+impl TryFrom<wasmparser::ElementKind<'_>> for ElementSegmentKind {
     type Error = ModuleError;
 
-    fn try_from(element: wasmparser::Element<'_>) -> Result<Self, Self::Error> {
-        if !matches!(element.ty, wasmparser::Type::FuncRef) {
-            return Err(ModuleError::unsupported(element.ty));
-        }
-        let (table_index, offset) = match element.kind {
+    fn try_from(element_kind: wasmparser::ElementKind<'_>) -> Result<Self, Self::Error> {
+        match element_kind {
             wasmparser::ElementKind::Active {
                 table_index,
                 init_expr,
             } => {
                 let table_index = TableIdx(table_index);
                 let offset = InitExpr::try_from(init_expr)?;
-                (table_index, offset)
-            }
-            wasmparser::ElementKind::Passive | wasmparser::ElementKind::Declared => {
-                return Err(ModuleError::unsupported(
-                    "encountered unsupported passive or declared element segment",
-                ))
+                Ok(Self::Active(ActiveElementSegment {
+                    table_index,
+                    offset,
+                }))
             }
-        };
+            wasmparser::ElementKind::Passive => Ok(Self::Passive),
+            wasmparser::ElementKind::Declared => Ok(Self::Declared),
+        }
+    }
+}
+
+impl TryFrom<wasmparser::Element<'_>> for ElementSegment {
+    type Error = ModuleError;
+
+    fn try_from(element: wasmparser::Element<'_>) -> Result<Self, Self::Error> {
+        if !matches!(
+            element.ty,
+            wasmparser::Type::FuncRef | wasmparser::Type::ExternRef
+        ) {
+            return Err(ModuleError::unsupported(element.ty));
+        }
+        let kind = ElementSegmentKind::try_from(element.kind)?;
         let items = element
             .items
             .get_items_reader()?
             .into_iter()
             .map(|item| match item? {
-                wasmparser::ElementItem::Func(func_idx) => Ok(FuncIdx(func_idx)),
-                wasmparser::ElementItem::Expr(_) => {
-                    unreachable!("encountered unexpected init expression for element item")
-                }
+                wasmparser::ElementItem::Func(func_idx) => Ok(Some(FuncIdx(func_idx))),
+                wasmparser::ElementItem::Expr(init_expr) => Self::eval_item_expr(init_expr),
             })
             .collect::<Result<Vec<_>, ModuleError>>()?
             .into_boxed_slice();
-        Ok(ElementSegment {
-            table_index,
-            offset,
-            items,
-        })
+        Ok(ElementSegment { kind, items })
+    }
+}
+
+impl Encode for ElementSegment {
+    fn encode(&self, writer: &mut Writer) {
+        match &self.kind {
+            ElementSegmentKind::Passive => writer.write_tag(0),
+            ElementSegmentKind::Active(segment) => {
+                writer.write_tag(1);
+                segment.table_index.encode(writer);
+                segment.offset.encode(writer);
+            }
+            ElementSegmentKind::Declared => writer.write_tag(2),
+        }
+        self.items.encode(writer);
+    }
+}
+
+impl Decode for ElementSegment {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        let kind = match reader.read_tag()? {
+            0 => ElementSegmentKind::Passive,
+            1 => ElementSegmentKind::Active(ActiveElementSegment {
+                table_index: Decode::decode(reader)?,
+                offset: Decode::decode(reader)?,
+            }),
+            2 => ElementSegmentKind::Declared,
+            _ => return Err(DeserializeError::malformed("element segment")),
+        };
+        let items = Decode::decode(reader)?;
+        Ok(ElementSegment { kind, items })
     }
 }
 
 impl ElementSegment {
-    /// Returns the index of the [`Table`] manipulated by the [`ElementSegment`].
+    /// Evaluates the initializer expression of an element segment item.
     ///
-    /// [`Table`]: [`crate::Table`]
-    pub fn table_index(&self) -> TableIdx {
-        self.table_index
+    /// # Note
+    ///
+    /// Validation guarantees that these expressions are either
+    /// `ref.null` or `ref.func` expressions.
+    ///
+    /// # Errors
+    ///
+    /// If the initializer expression is unsupported.
+    fn eval_item_expr(init_expr: wasmparser::InitExpr) -> Result<Option<FuncIdx>, ModuleError> {
+        let mut reader = init_expr.get_operators_reader();
+        let item = match reader.read()? {
+            wasmparser::Operator::RefNull { .. } => None,
+            wasmparser::Operator::RefFunc { function_index } => Some(FuncIdx(function_index)),
+            unsupported => return Err(ModuleError::unsupported(unsupported)),
+        };
+        if !matches!(reader.read()?, wasmparser::Operator::End) {
+            return Err(ModuleError::unsupported(init_expr));
+        }
+        Ok(item)
     }
 
-    /// Returns the offset expression of the [`ElementSegment`].
-    pub fn offset(&self) -> &InitExpr {
-        &self.offset
+    /// Returns the [`ElementSegmentKind`] of the [`ElementSegment`].
+    pub fn kind(&self) -> &ElementSegmentKind {
+        &self.kind
     }
 
     /// Returns the element items of the [`ElementSegment`].
-    pub fn items(&self) -> &[FuncIdx] {
+    ///
+    /// # Note
+    ///
+    /// A `None` item represents a `null` reference.
+    pub fn items(&self) -> &[Option<FuncIdx>] {
         &self.items[..]
     }
 }
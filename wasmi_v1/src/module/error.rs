@@ -1,4 +1,4 @@
-use super::ReadError;
+use super::{DeserializeError, ReadError, SerializeError};
 use core::{
     fmt,
     fmt::{Debug, Display},
@@ -13,6 +13,10 @@ pub enum ModuleError {
     Parser(ParserError),
     /// Encountered when unsupported Wasm proposal definitions are used.
     Unsupported { message: String },
+    /// Encountered when a Wasm module cannot be serialized.
+    Serialize(SerializeError),
+    /// Encountered when a serialized Wasm module cannot be deserialized.
+    Deserialize(DeserializeError),
 }
 
 impl ModuleError {
@@ -28,6 +32,8 @@ impl Display for ModuleError {
         match self {
             ModuleError::Read(error) => Display::fmt(error, f),
             ModuleError::Parser(error) => Display::fmt(error, f),
+            ModuleError::Serialize(error) => Display::fmt(error, f),
+            ModuleError::Deserialize(error) => Display::fmt(error, f),
             ModuleError::Unsupported { message } => {
                 write!(
                     f,
@@ -50,3 +56,15 @@ impl From<ParserError> for ModuleError {
         Self::Parser(error)
     }
 }
+
+impl From<SerializeError> for ModuleError {
+    fn from(error: SerializeError) -> Self {
+        Self::Serialize(error)
+    }
+}
+
+impl From<DeserializeError> for ModuleError {
+    fn from(error: DeserializeError) -> Self {
+        Self::Deserialize(error)
+    }
+}
@@ -1,4 +1,4 @@
-use super::GlobalIdx;
+use super::{Decode, DeserializeError, Encode, GlobalIdx, Reader, Writer};
 use crate::ModuleError;
 
 /// The index of a function declaration within a [`Module`].
@@ -55,6 +55,24 @@ impl MemoryIdx {
     }
 }
 
+/// The index of an exception tag declaration within a [`Module`].
+///
+/// [`Module`]: [`super::Module`]
+#[derive(Debug, Copy, Clone)]
+pub struct TagIdx(pub(super) u32);
+
+impl TagIdx {
+    /// Returns the [`TagIdx`] as `u32`.
+    pub fn into_u32(self) -> u32 {
+        self.0
+    }
+
+    /// Returns the [`TagIdx`] as `usize`.
+    pub fn into_usize(self) -> usize {
+        self.0 as usize
+    }
+}
+
 /// An export definition within a [`Module`].
 ///
 /// [`Module`]: [`super::Module`]
@@ -76,6 +94,22 @@ impl TryFrom<wasmparser::Export<'_>> for Export {
     }
 }
 
+impl Encode for Export {
+    fn encode(&self, writer: &mut Writer) {
+        self.field.encode(writer);
+        self.external.encode(writer);
+    }
+}
+
+impl Decode for Export {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        Ok(Export {
+            field: Decode::decode(reader)?,
+            external: Decode::decode(reader)?,
+        })
+    }
+}
+
 impl Export {
     /// Returns the field name of the [`Export`].
     pub fn field(&self) -> &str {
@@ -109,6 +143,10 @@ pub enum External {
     ///
     /// [`Module`]: [`super::Module`]
     Global(GlobalIdx),
+    /// An exported exception tag and its index witihn the [`Module`].
+    ///
+    /// [`Module`]: [`super::Module`]
+    Tag(TagIdx),
 }
 
 impl TryFrom<(wasmparser::ExternalKind, u32)> for External {
@@ -120,10 +158,39 @@ impl TryFrom<(wasmparser::ExternalKind, u32)> for External {
             wasmparser::ExternalKind::Table => Ok(External::Table(TableIdx(index))),
             wasmparser::ExternalKind::Memory => Ok(External::Memory(MemoryIdx(index))),
             wasmparser::ExternalKind::Global => Ok(External::Global(GlobalIdx(index))),
-            wasmparser::ExternalKind::Tag
-            | wasmparser::ExternalKind::Type
+            wasmparser::ExternalKind::Tag => Ok(External::Tag(TagIdx(index))),
+            wasmparser::ExternalKind::Type
             | wasmparser::ExternalKind::Module
             | wasmparser::ExternalKind::Instance => Err(ModuleError::unsupported(kind)),
         }
     }
 }
+
+impl Encode for External {
+    fn encode(&self, writer: &mut Writer) {
+        let (tag, index) = match self {
+            External::Func(index) => (0, index.0),
+            External::Table(index) => (1, index.0),
+            External::Memory(index) => (2, index.0),
+            External::Global(index) => (3, index.0),
+            External::Tag(index) => (4, index.0),
+        };
+        writer.write_tag(tag);
+        index.encode(writer);
+    }
+}
+
+impl Decode for External {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        let tag = reader.read_tag()?;
+        let index = u32::decode(reader)?;
+        match tag {
+            0 => Ok(External::Func(FuncIdx(index))),
+            1 => Ok(External::Table(TableIdx(index))),
+            2 => Ok(External::Memory(MemoryIdx(index))),
+            3 => Ok(External::Global(GlobalIdx(index))),
+            4 => Ok(External::Tag(TagIdx(index))),
+            _ => Err(DeserializeError::malformed("export")),
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use core::fmt::{self, Display};
 
+use super::{Decode, DeserializeError, Encode, Reader, Writer};
 use crate::{GlobalType, MemoryType, ModuleError, TableType};
 use wasmparser::ImportSectionEntryType;
 
@@ -62,6 +63,22 @@ impl ImportName {
     }
 }
 
+impl Encode for ImportName {
+    fn encode(&self, writer: &mut Writer) {
+        self.module.encode(writer);
+        self.field.encode(writer);
+    }
+}
+
+impl Decode for ImportName {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        Ok(Self {
+            module: Decode::decode(reader)?,
+            field: Decode::decode(reader)?,
+        })
+    }
+}
+
 impl TryFrom<wasmparser::Import<'_>> for Import {
     type Error = ModuleError;
 
@@ -79,9 +96,12 @@ impl TryFrom<wasmparser::Import<'_>> for Import {
             ImportSectionEntryType::Global(global_type) => {
                 global_type.try_into().map(ImportKind::Global)
             }
-            ImportSectionEntryType::Tag(_)
-            | ImportSectionEntryType::Module(_)
-            | ImportSectionEntryType::Instance(_) => Err(ModuleError::unsupported(import)),
+            ImportSectionEntryType::Tag(tag_type) => {
+                Ok(ImportKind::Tag(FuncTypeIdx(tag_type.type_index)))
+            }
+            ImportSectionEntryType::Module(_) | ImportSectionEntryType::Instance(_) => {
+                Err(ModuleError::unsupported(import))
+            }
         }?;
         Ok(Self::new(import.module, import.field, kind))
     }
@@ -129,6 +149,8 @@ pub enum ImportKind {
     Memory(MemoryType),
     /// An imported global variable.
     Global(GlobalType),
+    /// An imported exception tag with the index of its function type.
+    Tag(FuncTypeIdx),
 }
 
 /// A [`FuncType`] index.
@@ -136,7 +158,7 @@ pub enum ImportKind {
 /// # Note
 ///
 /// This generally refers to a [`FuncType`] within the same [`Module`]
-/// and is used by both function declarations and function imports.
+/// and is used by function declarations, function imports and exception tags.
 ///
 /// [`Module`]: [`super::Module`]
 /// [`FuncType`]: [`crate::FuncType`]
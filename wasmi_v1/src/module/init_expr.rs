@@ -1,6 +1,18 @@
-use super::GlobalIdx;
-use crate::ModuleError;
-use wasmi_core::{Value, F32, F64};
+use super::{
+    utils::value_type_from_wasmparser,
+    Decode,
+    DeserializeError,
+    Encode,
+    FuncIdx,
+    GlobalIdx,
+    Reader,
+    Writer,
+};
+use crate::{value::FromValue, ModuleError, Value};
+use alloc::{boxed::Box, vec::Vec};
+#[cfg(feature = "simd")]
+use wasmi_core::V128;
+use wasmi_core::{F32, F64};
 
 /// An initializer expression.
 ///
@@ -10,14 +22,15 @@ use wasmi_core::{Value, F32, F64};
 /// linear memory data segments.
 #[derive(Debug)]
 pub struct InitExpr {
-    /// The operand of the initializer expression.
+    /// The operands of the initializer expression in evaluation order.
     ///
     /// # Note
     ///
     /// The Wasm MVP only supports initializer expressions with a single
-    /// operand (besides the `End` operand). In later Wasm proposals this
-    /// might change but until then we keep it as simple as possible.
-    op: InitExprOperand,
+    /// operand (besides the `End` operand). The `extended-const` Wasm
+    /// proposal allows for integer arithmetic on multiple operands which
+    /// is evaluated using a small value stack.
+    ops: Box<[InitExprOperand]>,
 }
 
 impl TryFrom<wasmparser::InitExpr<'_>> for InitExpr {
@@ -25,18 +38,126 @@ impl TryFrom<wasmparser::InitExpr<'_>> for InitExpr {
 
     fn try_from(init_expr: wasmparser::InitExpr<'_>) -> Result<Self, Self::Error> {
         let mut reader = init_expr.get_operators_reader();
-        let op = reader.read()?.try_into()?;
-        if !matches!(reader.read()?, wasmparser::Operator::End) {
+        let mut ops = Vec::new();
+        loop {
+            match reader.read()? {
+                wasmparser::Operator::End => break,
+                operator => ops.push(operator.try_into()?),
+            }
+        }
+        if ops.is_empty() {
             return Err(ModuleError::unsupported(init_expr));
         }
-        Ok(InitExpr { op })
+        Ok(InitExpr {
+            ops: ops.into_boxed_slice(),
+        })
+    }
+}
+
+impl Encode for InitExpr {
+    fn encode(&self, writer: &mut Writer) {
+        self.ops.encode(writer)
+    }
+}
+
+impl Decode for InitExpr {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        let ops = Box::<[InitExprOperand]>::decode(reader)?;
+        if ops.is_empty() {
+            return Err(DeserializeError::malformed("initializer expression"));
+        }
+        Ok(InitExpr { ops })
     }
 }
 
 impl InitExpr {
     /// Returns a slice over the operators of the [`InitExpr`].
     pub fn operators(&self) -> &[InitExprOperand] {
-        core::slice::from_ref(&self.op)
+        &self.ops
+    }
+
+    /// Evaluates the [`InitExpr`] and returns its resulting value.
+    ///
+    /// The `global_get` and `ref_func` closures resolve the values of
+    /// `global.get` and `ref.func` operands respectively.
+    ///
+    /// # Panics
+    ///
+    /// If the [`InitExpr`] does not evaluate to exactly one value or if
+    /// an arithmetic operand is applied to operands of mismatching types.
+    /// Both cannot happen for validated initializer expressions.
+    pub fn eval<G, F>(&self, mut global_get: G, mut ref_func: F) -> Value
+    where
+        G: FnMut(GlobalIdx) -> Value,
+        F: FnMut(FuncIdx) -> Value,
+    {
+        let mut stack = Vec::with_capacity(self.ops.len());
+        for op in &self.ops[..] {
+            let value = match *op {
+                InitExprOperand::Const(value) => value,
+                InitExprOperand::GlobalGet(global_index) => global_get(global_index),
+                InitExprOperand::RefFunc(func_index) => ref_func(func_index),
+                InitExprOperand::I32Add => Self::eval_i32(&mut stack, i32::wrapping_add),
+                InitExprOperand::I32Sub => Self::eval_i32(&mut stack, i32::wrapping_sub),
+                InitExprOperand::I32Mul => Self::eval_i32(&mut stack, i32::wrapping_mul),
+                InitExprOperand::I64Add => Self::eval_i64(&mut stack, i64::wrapping_add),
+                InitExprOperand::I64Sub => Self::eval_i64(&mut stack, i64::wrapping_sub),
+                InitExprOperand::I64Mul => Self::eval_i64(&mut stack, i64::wrapping_mul),
+            };
+            stack.push(value);
+        }
+        assert_eq!(
+            stack.len(),
+            1,
+            "initializer expression must evaluate to a single value but found {} values",
+            stack.len(),
+        );
+        stack[0]
+    }
+
+    /// Evaluates the [`InitExpr`] if its value does not depend on the instantiation.
+    ///
+    /// Returns `None` if the [`InitExpr`] contains `global.get` or `ref.func` operands.
+    pub fn eval_const(&self) -> Option<Value> {
+        let is_const = self.ops.iter().all(|op| {
+            !matches!(
+                op,
+                InitExprOperand::GlobalGet(_) | InitExprOperand::RefFunc(_)
+            )
+        });
+        is_const.then(|| self.eval(|_| unreachable!(), |_| unreachable!()))
+    }
+
+    /// Pops the two topmost operands of the `stack` and returns the results of `f` applied to them.
+    fn eval_binary<T, F>(stack: &mut Vec<Value>, f: F) -> Value
+    where
+        T: FromValue + Into<Value>,
+        F: FnOnce(T, T) -> T,
+    {
+        let mut pop = || {
+            stack
+                .pop()
+                .and_then(Value::try_into::<T>)
+                .unwrap_or_else(|| {
+                    panic!(
+                        "encountered invalid operand in initializer expression: {:?}",
+                        stack
+                    )
+                })
+        };
+        let rhs = pop();
+        let lhs = pop();
+        f(lhs, rhs).into()
+    }
+
+    /// Evaluates a binary `i32` arithmetic operand.
+    fn eval_i32(stack: &mut Vec<Value>, f: fn(i32, i32) -> i32) -> Value {
+        Self::eval_binary(stack, f)
+    }
+
+    /// Evaluates a binary `i64` arithmetic operand.
+    fn eval_i64(stack: &mut Vec<Value>, f: fn(i64, i64) -> i64) -> Value {
+        Self::eval_binary(stack, f)
     }
 }
 
@@ -45,18 +166,37 @@ impl InitExpr {
 /// # Note
 ///
 /// The Wasm MVP only supports `const` and `global.get` expressions
-/// inside initializer expressions. In later Wasm proposals this might
-/// change but until then we keep it as simple as possible.
+/// inside initializer expressions. The `reference-types` Wasm proposal
+/// adds `ref.null` and `ref.func` expressions and the `extended-const`
+/// Wasm proposal adds `add`, `sub` and `mul` for `i32` and `i64`.
 #[derive(Debug)]
 pub enum InitExprOperand {
     /// A constant value.
+    ///
+    /// # Note
+    ///
+    /// This also represents `ref.null` expressions.
     Const(Value),
+    /// A reference to the function at the given index.
+    RefFunc(FuncIdx),
     /// The value of a global variable at the time of evaluation.
     ///
     /// # Note
     ///
     /// In the Wasm MVP only immutable globals are allowed to be evaluated.
     GlobalGet(GlobalIdx),
+    /// Wrapping addition of the two topmost `i32` operands.
+    I32Add,
+    /// Wrapping subtraction of the two topmost `i32` operands.
+    I32Sub,
+    /// Wrapping multiplication of the two topmost `i32` operands.
+    I32Mul,
+    /// Wrapping addition of the two topmost `i64` operands.
+    I64Add,
+    /// Wrapping subtraction of the two topmost `i64` operands.
+    I64Sub,
+    /// Wrapping multiplication of the two topmost `i64` operands.
+    I64Mul,
 }
 
 impl InitExprOperand {
@@ -69,6 +209,48 @@ impl InitExprOperand {
     }
 }
 
+impl Encode for InitExprOperand {
+    fn encode(&self, writer: &mut Writer) {
+        match self {
+            InitExprOperand::Const(value) => {
+                writer.write_tag(0);
+                value.encode(writer);
+            }
+            InitExprOperand::RefFunc(func_index) => {
+                writer.write_tag(1);
+                func_index.encode(writer);
+            }
+            InitExprOperand::GlobalGet(global_index) => {
+                writer.write_tag(2);
+                global_index.encode(writer);
+            }
+            InitExprOperand::I32Add => writer.write_tag(3),
+            InitExprOperand::I32Sub => writer.write_tag(4),
+            InitExprOperand::I32Mul => writer.write_tag(5),
+            InitExprOperand::I64Add => writer.write_tag(6),
+            InitExprOperand::I64Sub => writer.write_tag(7),
+            InitExprOperand::I64Mul => writer.write_tag(8),
+        }
+    }
+}
+
+impl Decode for InitExprOperand {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        match reader.read_tag()? {
+            0 => Value::decode(reader).map(InitExprOperand::Const),
+            1 => FuncIdx::decode(reader).map(InitExprOperand::RefFunc),
+            2 => GlobalIdx::decode(reader).map(InitExprOperand::GlobalGet),
+            3 => Ok(InitExprOperand::I32Add),
+            4 => Ok(InitExprOperand::I32Sub),
+            5 => Ok(InitExprOperand::I32Mul),
+            6 => Ok(InitExprOperand::I64Add),
+            7 => Ok(InitExprOperand::I64Sub),
+            8 => Ok(InitExprOperand::I64Mul),
+            _ => Err(DeserializeError::malformed("initializer expression")),
+        }
+    }
+}
+
 impl TryFrom<wasmparser::Operator<'_>> for InitExprOperand {
     type Error = ModuleError;
 
@@ -82,9 +264,26 @@ impl TryFrom<wasmparser::Operator<'_>> for InitExprOperand {
             wasmparser::Operator::F64Const { value } => {
                 Ok(InitExprOperand::constant(F64::from(value.bits())))
             }
+            #[cfg(feature = "simd")]
+            wasmparser::Operator::V128Const { value } => Ok(InitExprOperand::constant(
+                V128::from_bits(value.i128() as u128),
+            )),
             wasmparser::Operator::GlobalGet { global_index } => {
                 Ok(InitExprOperand::GlobalGet(GlobalIdx(global_index)))
             }
+            wasmparser::Operator::RefNull { ty } => {
+                let value_type = value_type_from_wasmparser(&ty)?;
+                Ok(InitExprOperand::Const(Value::default(value_type)))
+            }
+            wasmparser::Operator::RefFunc { function_index } => {
+                Ok(InitExprOperand::RefFunc(FuncIdx(function_index)))
+            }
+            wasmparser::Operator::I32Add => Ok(InitExprOperand::I32Add),
+            wasmparser::Operator::I32Sub => Ok(InitExprOperand::I32Sub),
+            wasmparser::Operator::I32Mul => Ok(InitExprOperand::I32Mul),
+            wasmparser::Operator::I64Add => Ok(InitExprOperand::I64Add),
+            wasmparser::Operator::I64Sub => Ok(InitExprOperand::I64Sub),
+            wasmparser::Operator::I64Mul => Ok(InitExprOperand::I64Mul),
             unsupported => Err(ModuleError::unsupported(unsupported)),
         }
     }
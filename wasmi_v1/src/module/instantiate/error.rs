@@ -40,6 +40,13 @@ pub enum InstantiationError {
         /// The actual global type found for the global variable import.
         actual: GlobalType,
     },
+    /// Caused when an exception tag has a mismatching tag type.
+    TagTypeMismatch {
+        /// The expected signature for the exception tag import.
+        expected: DedupFuncType,
+        /// The actual signature found for the exception tag import.
+        actual: DedupFuncType,
+    },
     /// Caused when an element segment does not fit into the specified table instance.
     ElementSegmentDoesNotFit {
         /// The table of the element segment.
@@ -49,6 +56,20 @@ pub enum InstantiationError {
         /// The amount of elements with which the table is initialized at the `offset`.
         amount: usize,
     },
+    /// Caused when the instantiation exceeds a limit of the [`ResourceLimiter`] of the store.
+    ///
+    /// [`ResourceLimiter`]: [`crate::ResourceLimiter`]
+    ResourceLimitExceeded {
+        /// The name of the limited resource, e.g. `"memories"`.
+        resource: &'static str,
+    },
+    /// Caused when the [`Store`] has run out of slots for the entities of the instantiation.
+    ///
+    /// [`Store`]: [`crate::Store`]
+    OutOfSlots {
+        /// The name of the exhausted resource, e.g. `"funcs"`.
+        resource: &'static str,
+    },
     /// Caused when the `start` function was unexpectedly found in the instantiated module.
     FoundStartFn {
         /// The index of the found `start` function.
@@ -83,6 +104,11 @@ impl Display for InstantiationError {
                 "expected {:?} global type but found {:?} value type",
                 expected, actual,
             ),
+            Self::TagTypeMismatch { expected, actual } => write!(
+                f,
+                "expected {:?} exception tag type but found {:?}",
+                expected, actual,
+            ),
             Self::ElementSegmentDoesNotFit {
                 table,
                 offset,
@@ -92,6 +118,16 @@ impl Display for InstantiationError {
                 "table {:?} does not fit {} elements starting from offset {}",
                 table, offset, amount,
             ),
+            Self::ResourceLimitExceeded { resource } => {
+                write!(
+                    f,
+                    "instantiation exceeds the resource limit for {}",
+                    resource
+                )
+            }
+            Self::OutOfSlots { resource } => {
+                write!(f, "store has run out of slots for {}", resource)
+            }
             Self::FoundStartFn { index } => {
                 write!(f, "found an unexpected start function with index {}", index)
             }
@@ -2,27 +2,44 @@ mod error;
 mod pre;
 
 pub use self::{error::InstantiationError, pre::InstancePre};
-use super::{export, InitExpr, Module, ModuleImportType};
+use super::{
+    data::DataSegmentKind,
+    element::ElementSegmentKind,
+    export,
+    FuncIdx,
+    InitExpr,
+    Module,
+    ModuleImportType,
+};
 use crate::{
-    module::{init_expr::InitExprOperand, DEFAULT_MEMORY_INDEX},
+    errors::{MemoryError, TableError},
     AsContext,
     AsContextMut,
+    DataSegmentEntity,
+    ElementSegmentEntity,
     Error,
     Extern,
+    Func,
     FuncEntity,
+    FuncRef,
     FuncType,
     Global,
     GlobalType,
     Instance,
     InstanceEntity,
     InstanceEntityBuilder,
+    InstanceSnapshot,
     Memory,
     MemoryType,
     Mutability,
     Table,
     TableType,
+    Tag,
+    TagEntity,
+    Value,
 };
-use wasmi_core::{Value, ValueType, F32, F64};
+use alloc::boxed::Box;
+use wasmi_core::{UntypedValue, ValueType, F32, F64};
 
 impl Module {
     /// Instantiates a new [`Instance`] from the given compiled [`Module`].
@@ -50,22 +67,37 @@ impl Module {
     where
         I: IntoIterator<Item = Extern>,
     {
+        context.as_context_mut().store.check_instantiation_limits(
+            self.tables.len(),
+            self.memories.len() - self.imports.len_memories,
+            self.globals.len() - self.imports.len_globals,
+        )?;
+        context.as_context().store.check_instantiation_slots(
+            self.funcs.len() - self.imports.len_funcs,
+            self.tables.len(),
+            self.memories.len() - self.imports.len_memories,
+            self.globals.len() - self.imports.len_globals,
+            self.tags.len() - self.imports.len_tags,
+        )?;
         let handle = context.as_context_mut().store.alloc_instance();
         let mut builder = InstanceEntity::build();
+        builder.set_module_hash(self.hash());
+        builder.set_names(self.names());
+        builder.set_func_bodies(self.func_bodies.clone());
 
         self.extract_func_types(&mut context, &mut builder);
         self.extract_imports(&mut context, &mut builder, externals)?;
+        builder.finish_imports();
         self.extract_functions(&mut context, &mut builder, handle);
-        self.extract_tables(&mut context, &mut builder);
-        self.extract_memories(&mut context, &mut builder);
-        self.extract_globals(&mut context, &mut builder);
+        self.extract_tables(&mut context, &mut builder)?;
+        self.extract_memories(&mut context, &mut builder)?;
+        self.extract_globals(&mut context, &mut builder)?;
+        self.extract_tags(&mut context, &mut builder);
         self.extract_exports(&mut builder);
 
-        self.initialize_table_elements(&mut context, &mut builder)?;
-        self.initialize_memory_data(&mut context, &mut builder)?;
-
         // At this point the module instantiation is nearly done.
-        // The only thing that is missing is to run the `start` function.
+        // The only things that are missing are to initialize the tables and
+        // linear memories with the segments and to run the `start` function.
         Ok(InstancePre::new(handle, self, builder))
     }
 
@@ -96,8 +128,8 @@ impl Module {
     /// - If the zipped import and given external have mismatching types, e.g. on index `i`
     ///   the module requires a function import but on index `i` the externals provide a global
     ///   variable external value.
-    /// - If the externally provided [`Table`], [`Memory`], [`Func`] or [`Global`] has a type
-    ///   mismatch with the expected module import type.
+    /// - If the externally provided [`Table`], [`Memory`], [`Func`], [`Global`] or [`Tag`]
+    ///   has a type mismatch with the expected module import type.
     ///
     /// [`Func`]: [`crate::v1::Func`]
     fn extract_imports<I>(
@@ -156,6 +188,14 @@ impl Module {
                     }
                     builder.push_global(global);
                 }
+                (ModuleImportType::Tag(expected), Extern::Tag(tag)) => {
+                    let expected = *expected;
+                    let actual = tag.signature(context.as_context());
+                    if expected != actual {
+                        return Err(InstantiationError::TagTypeMismatch { expected, actual });
+                    }
+                    builder.push_tag(tag);
+                }
                 (expected_import, actual_extern_val) => {
                     return Err(InstantiationError::ImportsExternalsMismatch {
                         expected: expected_import.clone(),
@@ -192,51 +232,102 @@ impl Module {
     ///
     /// This also stores [`Table`] references into the [`Instance`] under construction.
     ///
+    /// # Errors
+    ///
+    /// If the [`ResourceLimiter`] of the [`Store`] denies the allocation of a table.
+    ///
     /// [`Store`]: struct.Store.html
-    fn extract_tables(&self, context: &mut impl AsContextMut, builder: &mut InstanceEntityBuilder) {
+    /// [`ResourceLimiter`]: [`crate::ResourceLimiter`]
+    fn extract_tables(
+        &self,
+        context: &mut impl AsContextMut,
+        builder: &mut InstanceEntityBuilder,
+    ) -> Result<(), InstantiationError> {
         for table_type in self.tables.iter().copied() {
-            builder.push_table(Table::new(context.as_context_mut(), table_type));
+            builder.push_table(Table::new(context.as_context_mut(), table_type)?);
         }
+        Ok(())
     }
 
     /// Extracts the Wasm linear memories from the module and stores them into the [`Store`].
     ///
     /// This also stores [`Memory`] references into the [`Instance`] under construction.
     ///
+    /// # Errors
+    ///
+    /// If the [`ResourceLimiter`] of the [`Store`] denies the allocation of a linear memory.
+    ///
     /// [`Store`]: struct.Store.html
+    /// [`ResourceLimiter`]: [`crate::ResourceLimiter`]
     fn extract_memories(
         &self,
         context: &mut impl AsContextMut,
         builder: &mut InstanceEntityBuilder,
-    ) {
-        for memory_type in self.memories.iter().copied() {
-            let memory =
-                Memory::new(context.as_context_mut(), memory_type).unwrap_or_else(|error| {
-                    panic!(
-                        "encountered unexpected invalid memory type {:?} after Wasm validation: {}",
-                        memory_type, error,
-                    )
-                });
+    ) -> Result<(), InstantiationError> {
+        let len_imported = self.imports.len_memories;
+        for (index, memory_type) in self.internal_memories().copied().enumerate() {
+            let memory_index = (len_imported + index) as u32;
+            let memory = match self.memory_image(memory_index) {
+                Some(image) => Memory::from_image(context.as_context_mut(), memory_type, image),
+                None => Memory::new(context.as_context_mut(), memory_type),
+            };
+            let memory = match memory {
+                Ok(memory) => memory,
+                Err(MemoryError::ResourceLimitExceeded) => {
+                    return Err(MemoryError::ResourceLimitExceeded.into())
+                }
+                Err(error) => panic!(
+                    "encountered unexpected invalid memory type {:?} after Wasm validation: {}",
+                    memory_type, error,
+                ),
+            };
             builder.push_memory(memory);
         }
+        Ok(())
     }
 
     /// Extracts the Wasm global variables from the module and stores them into the [`Store`].
     ///
     /// This also stores [`Global`] references into the [`Instance`] under construction.
     ///
+    /// # Errors
+    ///
+    /// If the [`ResourceLimiter`] of the [`Store`] denies the allocation of a global variable.
+    ///
     /// [`Store`]: struct.Store.html
+    /// [`ResourceLimiter`]: [`crate::ResourceLimiter`]
     fn extract_globals(
         &self,
         context: &mut impl AsContextMut,
         builder: &mut InstanceEntityBuilder,
-    ) {
+    ) -> Result<(), InstantiationError> {
         for (global_type, global_init) in self.internal_globals() {
             let init_value = Self::eval_init_expr(context.as_context_mut(), builder, global_init);
             let mutability = global_type.mutability();
-            let global = Global::new(context.as_context_mut(), init_value, mutability);
+            let global =
+                Global::new(context.as_context_mut(), init_value, mutability).map_err(|_| {
+                    InstantiationError::ResourceLimitExceeded {
+                        resource: "globals",
+                    }
+                })?;
             builder.push_global(global);
         }
+        Ok(())
+    }
+
+    /// Extracts the Wasm exception tags from the module and stores them into the [`Store`].
+    ///
+    /// This also stores [`Tag`] references into the [`Instance`] under construction.
+    ///
+    /// [`Store`]: struct.Store.html
+    fn extract_tags(&self, context: &mut impl AsContextMut, builder: &mut InstanceEntityBuilder) {
+        for signature in self.internal_tags().copied() {
+            let tag = context
+                .as_context_mut()
+                .store
+                .alloc_tag(TagEntity::new(signature));
+            builder.push_tag(tag);
+        }
     }
 
     /// Evaluates the given initializer expression using the partially constructed [`Instance`].
@@ -245,16 +336,8 @@ impl Module {
         builder: &InstanceEntityBuilder,
         init_expr: &InitExpr,
     ) -> Value {
-        let operands = init_expr.operators();
-        debug_assert_eq!(
-            operands.len(),
-            1,
-            "in Wasm MVP code length of initializer expressions must be 1 but found {} operands",
-            operands.len(),
-        );
-        match operands[0] {
-            InitExprOperand::Const(value) => value,
-            InitExprOperand::GlobalGet(global_index) => {
+        init_expr.eval(
+            |global_index| {
                 let global = builder
                     .get_global(global_index.into_u32())
                     .unwrap_or_else(|| {
@@ -263,9 +346,18 @@ impl Module {
                             global_index
                         )
                     });
-                global.get(context)
-            }
-        }
+                global.get(&context)
+            },
+            |func_index| {
+                let func = builder.get_func(func_index.into_u32()).unwrap_or_else(|| {
+                    panic!(
+                        "encountered missing function at index {:?} for initializer expression evaluation",
+                        func_index
+                    )
+                });
+                Value::FuncRef(FuncRef::new(func))
+            },
+        )
     }
 
     /// Extracts the Wasm exports from the module and registers them into the [`Instance`].
@@ -313,90 +405,210 @@ impl Module {
                     });
                     Extern::Global(global)
                 }
+                export::External::Tag(tag_index) => {
+                    let tag_index = tag_index.into_u32();
+                    let tag = builder.get_tag(tag_index).unwrap_or_else(|| {
+                        panic!(
+                            "encountered missing exception tag at index {:?} upon export extraction",
+                            tag_index,
+                        )
+                    });
+                    Extern::Tag(tag)
+                }
             };
             builder.push_export(field, external);
         }
     }
 
-    /// The index of the default Wasm table.
-    const DEFAULT_TABLE_INDEX: u32 = 0;
+    /// Evaluates the offset of an active element or data segment.
+    ///
+    /// # Note
+    ///
+    /// The offsets of active data segments of 64-bit linear memories are `i64` values.
+    /// Offsets that do not fit into a `usize` are saturated since they are out of bounds anyways.
+    ///
+    /// # Panics
+    ///
+    /// If the offset does not evaluate to an `i32` or `i64` value albeit successful validation.
+    fn eval_segment_offset(
+        context: impl AsContext,
+        builder: &InstanceEntityBuilder,
+        offset_expr: &InitExpr,
+    ) -> usize {
+        match Self::eval_init_expr(context, builder, offset_expr) {
+            Value::I32(offset) => offset as u32 as usize,
+            Value::I64(offset) => usize::try_from(offset as u64).unwrap_or(usize::MAX),
+            _ => panic!(
+                "expected offset value of type `i32` or `i64` due to Wasm validation but found: {:?}",
+                offset_expr,
+            ),
+        }
+    }
+
+    /// Resolves the function indices of the element segment `items` to their [`Func`] references.
+    ///
+    /// # Note
+    ///
+    /// The resolved references are returned in their untyped encoding.
+    /// A `None` item is resolved to a `null` reference.
+    ///
+    /// [`Func`]: [`crate::v1::Func`]
+    fn resolve_element_items(
+        builder: &InstanceEntityBuilder,
+        items: &[Option<FuncIdx>],
+    ) -> Box<[UntypedValue]> {
+        items
+            .iter()
+            .map(|item| {
+                let func = item.map(|func_index| {
+                    let func_index = func_index.into_u32();
+                    builder.get_func(func_index).unwrap_or_else(|| {
+                        panic!(
+                            "encountered missing function at index {} upon element initialization",
+                            func_index
+                        )
+                    })
+                });
+                UntypedValue::from(FuncRef::new(func))
+            })
+            .collect()
+    }
+
+    /// Initializes the [`Instance`] tables and linear memories with the
+    /// Wasm element and data segments of the [`Module`].
+    ///
+    /// # Errors
+    ///
+    /// If an active element or data segment does not fit into its table or linear memory.
+    fn initialize_segments(
+        &self,
+        context: &mut impl AsContextMut,
+        builder: &mut InstanceEntityBuilder,
+    ) -> Result<(), InstantiationError> {
+        self.initialize_table_elements(context, builder)?;
+        self.initialize_memory_data(context, builder)
+    }
+
+    /// Registers the Wasm element and data segments of the [`Module`] to the [`Instance`]
+    /// without initializing its tables and linear memories.
+    ///
+    /// # Note
+    ///
+    /// Used upon restoring an [`InstanceSnapshot`] which already captures the contents
+    /// of the tables and linear memories. Passive segments are registered as dropped
+    /// if they have been dropped at the time the [`InstanceSnapshot`] was captured.
+    fn restore_segments(&self, builder: &mut InstanceEntityBuilder, snapshot: &InstanceSnapshot) {
+        for (index, element_segment) in self.element_segments.iter().enumerate() {
+            let segment = match element_segment.kind() {
+                ElementSegmentKind::Passive if !snapshot.is_element_segment_dropped(index) => {
+                    let items = Self::resolve_element_items(builder, element_segment.items());
+                    ElementSegmentEntity::new(items)
+                }
+                _ => ElementSegmentEntity::dropped(),
+            };
+            builder.push_element_segment(segment);
+        }
+        for (index, data_segment) in self.data_segments.iter().enumerate() {
+            let segment = match data_segment.kind() {
+                DataSegmentKind::Passive if !snapshot.is_data_segment_dropped(index) => {
+                    DataSegmentEntity::new(data_segment.shared_data())
+                }
+                _ => DataSegmentEntity::dropped(),
+            };
+            builder.push_data_segment(segment);
+        }
+    }
 
     /// Initializes the [`Instance`] tables with the Wasm element segments of the [`Module`].
+    ///
+    /// # Note
+    ///
+    /// This also registers the element segments of the [`Module`] to the [`Instance`].
+    /// Active and declared element segments are dropped after initialization.
     fn initialize_table_elements(
         &self,
         context: &mut impl AsContextMut,
         builder: &mut InstanceEntityBuilder,
-    ) -> Result<(), Error> {
+    ) -> Result<(), InstantiationError> {
         for element_segment in &self.element_segments[..] {
-            let offset_expr = element_segment.offset();
-            let offset = Self::eval_init_expr(context.as_context_mut(), builder, offset_expr)
-                .try_into::<u32>()
-                .unwrap_or_else(|| {
-                    panic!(
-                    "expected offset value of type `i32` due to Wasm validation but found: {:?}",
-                    offset_expr,
+            let items = Self::resolve_element_items(builder, element_segment.items());
+            let active = match element_segment.kind() {
+                ElementSegmentKind::Passive => {
+                    builder.push_element_segment(ElementSegmentEntity::new(items));
+                    continue;
+                }
+                ElementSegmentKind::Declared => {
+                    builder.push_element_segment(ElementSegmentEntity::dropped());
+                    continue;
+                }
+                ElementSegmentKind::Active(active) => active,
+            };
+            builder.push_element_segment(ElementSegmentEntity::dropped());
+            let offset =
+                Self::eval_segment_offset(context.as_context_mut(), builder, active.offset());
+            let table_index = active.table_index().into_u32();
+            let table = builder.get_table(table_index).unwrap_or_else(|| {
+                panic!(
+                    "expected table at index {} for active element segment but found none",
+                    table_index
                 )
-                }) as usize;
-            let table = builder
-                .get_table(Self::DEFAULT_TABLE_INDEX)
-                .unwrap_or_else(|| {
-                    panic!(
-                        "expected default table at index {} but found none",
-                        Self::DEFAULT_TABLE_INDEX
-                    )
-                });
+            });
             // Note: This checks not only that the elements in the element segments properly
             //       fit into the table at the given offset but also that the element segment
             //       consists of at least 1 element member.
             let len_table = table.len(&context);
-            let len_items = element_segment.items().len();
+            let len_items = items.len();
             if offset + len_items > len_table {
                 return Err(InstantiationError::ElementSegmentDoesNotFit {
                     table,
                     offset,
                     amount: len_items,
-                })
-                .map_err(Into::into);
-            }
-            // Finally do the actual initialization of the table elements.
-            for (i, func_index) in element_segment.items().iter().enumerate() {
-                let func_index = func_index.into_u32();
-                let func = builder.get_func(func_index).unwrap_or_else(|| {
-                    panic!(
-                        "encountered missing function at index {} upon element initialization",
-                        func_index
-                    )
                 });
-                table.set(context.as_context_mut(), offset + i, Some(func))?;
             }
+            // Finally do the actual initialization of the table elements.
+            context
+                .as_context_mut()
+                .store
+                .resolve_table_mut(table)
+                .init(offset, &items, 0, len_items)?;
         }
         Ok(())
     }
 
     /// Initializes the [`Instance`] linear memories with the Wasm data segments of the [`Module`].
+    ///
+    /// # Note
+    ///
+    /// This also registers the data segments of the [`Module`] to the [`Instance`].
+    /// Active data segments are dropped after initialization.
     fn initialize_memory_data(
         &self,
         context: &mut impl AsContextMut,
         builder: &mut InstanceEntityBuilder,
-    ) -> Result<(), Error> {
+    ) -> Result<(), InstantiationError> {
         for data_segment in &self.data_segments[..] {
-            let offset_expr = data_segment.offset();
-            let offset = Self::eval_init_expr(context.as_context_mut(), builder, offset_expr)
-                .try_into::<u32>()
-                .unwrap_or_else(|| {
-                    panic!(
-                    "expected offset value of type `i32` due to Wasm validation but found: {:?}",
-                    offset_expr,
+            let active = match data_segment.kind() {
+                DataSegmentKind::Passive => {
+                    let bytes = data_segment.shared_data();
+                    builder.push_data_segment(DataSegmentEntity::new(bytes));
+                    continue;
+                }
+                DataSegmentKind::Active(active) => active,
+            };
+            builder.push_data_segment(DataSegmentEntity::dropped());
+            let memory_index = active.memory_index().into_u32();
+            if self.memory_image(memory_index).is_some() {
+                // The bytes of the data segment are already mapped from the memory image.
+                continue;
+            }
+            let offset =
+                Self::eval_segment_offset(context.as_context_mut(), builder, active.offset());
+            let memory = builder.get_memory(memory_index).unwrap_or_else(|| {
+                panic!(
+                    "expected linear memory at index {} for active data segment but found none",
+                    memory_index
                 )
-                }) as usize;
-            let memory = builder
-                .get_memory(Self::DEFAULT_TABLE_INDEX)
-                .unwrap_or_else(|| {
-                    panic!(
-                        "expected default memory at index {} but found none",
-                        DEFAULT_MEMORY_INDEX
-                    )
-                });
+            });
             memory.write(context.as_context_mut(), offset, data_segment.data())?;
         }
         Ok(())
@@ -1,5 +1,5 @@
 use super::{InstantiationError, Module};
-use crate::{AsContextMut, Error, Instance, InstanceEntityBuilder};
+use crate::{AsContextMut, Error, Instance, InstanceEntityBuilder, InstanceSnapshot};
 
 /// A partially instantiated [`Instance`] where the `start` function has not yet been executed.
 ///
@@ -36,7 +36,8 @@ impl<'a> InstancePre<'a> {
         self.module.start.map(|idx| idx.into_u32())
     }
 
-    /// Runs the `start` function of the [`Instance`] and returns its handle.
+    /// Initializes the tables and linear memories of the [`Instance`], runs its `start`
+    /// function and returns its handle.
     ///
     /// # Note
     ///
@@ -44,13 +45,16 @@ impl<'a> InstancePre<'a> {
     ///
     /// # Errors
     ///
-    /// If executing the `start` function traps.
+    /// - If an active element or data segment does not fit into its table or linear memory.
+    /// - If executing the `start` function traps.
     ///
     /// # Panics
     ///
     /// If the `start` function is invalid albeit successful validation.
-    pub fn start(self, mut context: impl AsContextMut) -> Result<Instance, Error> {
+    pub fn start(mut self, mut context: impl AsContextMut) -> Result<Instance, Error> {
         let opt_start_index = self.start_fn();
+        self.module
+            .initialize_segments(&mut context, &mut self.builder)?;
         context
             .as_context_mut()
             .store
@@ -70,18 +74,49 @@ impl<'a> InstancePre<'a> {
         Ok(self.handle)
     }
 
+    /// Restores the [`InstanceSnapshot`] into the [`Instance`] and returns its handle.
+    ///
+    /// # Note
+    ///
+    /// This finishes the instantiation procedure without initializing tables and linear
+    /// memories with the element and data segments and without running the `start`
+    /// function since their effects are already captured by the [`InstanceSnapshot`].
+    ///
+    /// # Errors
+    ///
+    /// - If the [`InstanceSnapshot`] has been captured from an [`Instance`] of another [`Module`].
+    ///   This includes [`Module`] values that are compiled or deserialized from the same bytes.
+    /// - If growing a linear memory or table to its captured size fails.
+    pub fn restore(
+        mut self,
+        mut context: impl AsContextMut,
+        snapshot: &InstanceSnapshot,
+    ) -> Result<Instance, Error> {
+        snapshot.ensure_matches(&context, &self.builder)?;
+        self.module.restore_segments(&mut self.builder, snapshot);
+        context
+            .as_context_mut()
+            .store
+            .initialize_instance(self.handle, self.builder.finish());
+        snapshot.restore(&mut context, self.handle)?;
+        Ok(self.handle)
+    }
+
     /// Finishes instantiation ensuring that no `start` function exists.
     ///
     /// # Errors
     ///
-    /// If a `start` function exists that needs to be called for conformant module instantiation.
+    /// - If a `start` function exists that needs to be called for conformant module instantiation.
+    /// - If an active element or data segment does not fit into its table or linear memory.
     pub fn ensure_no_start(
-        self,
+        mut self,
         mut context: impl AsContextMut,
     ) -> Result<Instance, InstantiationError> {
         if let Some(index) = self.start_fn() {
             return Err(InstantiationError::FoundStartFn { index });
         }
+        self.module
+            .initialize_segments(&mut context, &mut self.builder)?;
         context
             .as_context_mut()
             .store
@@ -0,0 +1,143 @@
+use super::{data::DataSegmentKind, DataSegment};
+use crate::{MemoryImage, MemoryType, Value};
+use alloc::{boxed::Box, vec::Vec};
+use spin::Once;
+use wasmi_core::memory_units::{Bytes, Pages};
+
+/// The [`MemoryImage`] of the internally defined linear memories of a [`Module`].
+///
+/// # Note
+///
+/// Linear memories with a [`MemoryImage`] are initialized by mapping it copy-on-write
+/// instead of writing the bytes of their active data segments upon instantiation.
+///
+/// The [`MemoryImage`] of a linear memory is prepared upon the first instantiation
+/// of the [`Module`] so that [`Module`] instances that are never instantiated do
+/// not hold any [`MemoryImage`] resources.
+///
+/// [`Module`]: [`super::Module`]
+#[derive(Debug)]
+pub struct MemoryImages {
+    /// The amount of imported linear memories.
+    len_imported: usize,
+    /// The [`MemoryImage`] of every internally defined linear memory if any.
+    ///
+    /// This is empty if memory images are disabled.
+    images: Box<[Once<Option<MemoryImage>>]>,
+}
+
+impl MemoryImages {
+    /// The maximum amount of active data segments of a linear memory with a [`MemoryImage`].
+    ///
+    /// # Note
+    ///
+    /// Every data segment is written to the [`MemoryImage`] via its own system call
+    /// while concurrent instantiations of the [`Module`] wait for its preparation.
+    /// Linear memories with more active data segments are initialized by copying them.
+    ///
+    /// [`Module`]: [`super::Module`]
+    pub const MAX_DATA_SEGMENTS: usize = 1024;
+
+    /// Creates the [`MemoryImages`] for `len_memories` linear memories.
+    ///
+    /// The first `len_imported` linear memories are imported and never have a [`MemoryImage`].
+    /// No [`MemoryImage`] is prepared at all if `enabled` is `false`.
+    pub fn new(len_imported: usize, len_memories: usize, enabled: bool) -> Self {
+        let len_images = match enabled {
+            true => len_memories.saturating_sub(len_imported),
+            false => 0,
+        };
+        let images = (0..len_images).map(|_| Once::new()).collect();
+        Self {
+            len_imported,
+            images,
+        }
+    }
+
+    /// Prepares the [`MemoryImage`] of the linear memory at `memory_index` if possible.
+    ///
+    /// # Note
+    ///
+    /// No [`MemoryImage`] is prepared for a linear memory that
+    ///
+    /// - is shared,
+    /// - is not initialized by any active data segment,
+    /// - is initialized by more than [`MemoryImages::MAX_DATA_SEGMENTS`] active data segments,
+    /// - is initialized by an active data segment whose offset depends on the instantiation or
+    /// - is initialized by an active data segment that does not fit into its initial pages
+    ///   in which case the instantiation fails regardless.
+    fn prepare(
+        memory_index: usize,
+        memory_type: MemoryType,
+        data_segments: &[DataSegment],
+    ) -> Option<MemoryImage> {
+        if memory_type.is_shared() {
+            return None;
+        }
+        let initial_len = memory_type
+            .initial_pages()
+            .0
+            .saturating_mul(Bytes::from(Pages(1)).0);
+        let mut segments = Vec::new();
+        let mut len = 0;
+        for segment in data_segments {
+            let active = match segment.kind() {
+                DataSegmentKind::Active(active)
+                    if active.memory_index().into_usize() == memory_index =>
+                {
+                    active
+                }
+                _ => continue,
+            };
+            let offset = match active.offset().eval_const()? {
+                Value::I32(offset) => offset as u32 as usize,
+                Value::I64(offset) => usize::try_from(offset as u64).ok()?,
+                _ => return None,
+            };
+            let end = offset
+                .checked_add(segment.data().len())
+                .filter(|&end| end <= initial_len)?;
+            if segments.len() == Self::MAX_DATA_SEGMENTS {
+                return None;
+            }
+            len = len.max(end);
+            segments.push((offset, segment.data()));
+        }
+        if len == 0 {
+            return None;
+        }
+        MemoryImage::new(len, &segments)
+    }
+
+    /// Returns the [`MemoryImage`] of the linear memory at `memory_index` if any.
+    ///
+    /// # Note
+    ///
+    /// The [`MemoryImage`] is prepared from the `memories` and `data_segments`
+    /// of the [`Module`] upon the first call for the linear memory.
+    ///
+    /// [`Module`]: [`super::Module`]
+    pub fn get(
+        &self,
+        memory_index: u32,
+        memories: &[MemoryType],
+        data_segments: &[DataSegment],
+    ) -> Option<&MemoryImage> {
+        let memory_index = memory_index as usize;
+        let index = memory_index.checked_sub(self.len_imported)?;
+        self.images
+            .get(index)?
+            .call_once(|| Self::prepare(memory_index, memories[memory_index], data_segments))
+            .as_ref()
+    }
+
+    /// Returns `true` if the [`MemoryImage`] of the linear memory at `memory_index` has been prepared.
+    #[cfg(test)]
+    pub fn is_prepared(&self, memory_index: u32) -> bool {
+        (memory_index as usize)
+            .checked_sub(self.len_imported)
+            .and_then(|index| self.images.get(index))
+            .map(Once::is_completed)
+            .unwrap_or(false)
+    }
+}
@@ -10,13 +10,17 @@ mod global;
 mod import;
 mod init_expr;
 mod instantiate;
+mod memory_image;
+mod names;
 mod parser;
 mod read;
+mod serialize;
 mod utils;
 
 #[cfg(test)]
 mod tests;
 
+pub(crate) use self::serialize::{Decode, Encode, Reader, Writer};
 use self::{
     builder::ModuleBuilder,
     data::DataSegment,
@@ -25,6 +29,7 @@ use self::{
     global::Global,
     import::{Import, ImportKind},
     init_expr::{InitExpr, InitExprOperand},
+    memory_image::MemoryImages,
     parser::parse,
     read::ReadError,
 };
@@ -32,26 +37,31 @@ pub use self::{
     builder::ModuleResources,
     compile::BlockType,
     error::ModuleError,
-    export::{FuncIdx, MemoryIdx, TableIdx},
+    export::{FuncIdx, MemoryIdx, TableIdx, TagIdx},
     global::GlobalIdx,
     import::{FuncTypeIdx, ImportName},
     instantiate::{InstancePre, InstantiationError},
+    names::ModuleNames,
     read::Read,
+    serialize::{DeserializeError, SerializeError},
 };
 use crate::{
-    engine::{DedupFuncType, FuncBody},
+    engine::{DedupFuncType, FuncBodies, FuncBody},
     Engine,
     Error,
     FuncType,
     GlobalType,
+    MemoryImage,
     MemoryType,
     TableType,
 };
+use alloc::sync::Arc;
 use core::{iter, slice::Iter as SliceIter};
 
 /// A parsed and validated WebAssembly module.
 #[derive(Debug)]
 pub struct Module {
+    hash: ModuleHash,
     engine: Engine,
     func_types: Box<[DedupFuncType]>,
     imports: ModuleImports,
@@ -60,19 +70,73 @@ pub struct Module {
     memories: Box<[MemoryType]>,
     globals: Box<[GlobalType]>,
     globals_init: Box<[InitExpr]>,
+    tags: Box<[DedupFuncType]>,
     exports: Box<[Export]>,
     start: Option<FuncIdx>,
-    func_bodies: Box<[FuncBody]>,
+    func_bodies: Arc<FuncBodies>,
     element_segments: Box<[ElementSegment]>,
     data_segments: Box<[DataSegment]>,
+    memory_images: MemoryImages,
+    names: Arc<ModuleNames>,
+}
+
+/// The hash of the Wasm bytes a [`Module`] has been compiled from.
+///
+/// # Note
+///
+/// Used to ensure that an [`InstanceSnapshot`] is only restored into
+/// instances of a [`Module`] compiled from the same Wasm bytes. Unlike the
+/// [`Module`] itself the hash is stable across processes and is preserved
+/// by [`Module::serialize`] and [`Module::deserialize`].
+///
+/// [`InstanceSnapshot`]: [`crate::InstanceSnapshot`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct ModuleHash(pub(crate) u128);
+
+/// Computes the [`ModuleHash`] of a stream of Wasm bytes.
+///
+/// # Note
+///
+/// This is the 128-bit FNV-1a hash which is not cryptographically secure.
+/// It guards against restoring an [`InstanceSnapshot`] into instances of
+/// another [`Module`] by accident, not against crafted Wasm bytes.
+///
+/// [`InstanceSnapshot`]: [`crate::InstanceSnapshot`]
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct ModuleHasher {
+    state: u128,
+}
+
+impl Default for ModuleHasher {
+    fn default() -> Self {
+        Self {
+            state: Self::OFFSET_BASIS,
+        }
+    }
+}
+
+impl ModuleHasher {
+    /// The initial state of the 128-bit FNV-1a hash.
+    const OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+    /// The prime of the 128-bit FNV-1a hash.
+    const PRIME: u128 = 0x0000000001000000000000000000013b;
+
+    /// Feeds the next `bytes` of the Wasm byte stream into the [`ModuleHasher`].
+    pub fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state = (self.state ^ u128::from(*byte)).wrapping_mul(Self::PRIME);
+        }
+    }
+
+    /// Returns the [`ModuleHash`] of all bytes fed into the [`ModuleHasher`].
+    pub fn finish(&self) -> ModuleHash {
+        ModuleHash(self.state)
+    }
 }
 
 /// The index of the default Wasm linear memory.
 pub(crate) const DEFAULT_MEMORY_INDEX: u32 = 0;
 
-/// The index of the default Wasm table.
-pub(crate) const DEFAULT_TABLE_INDEX: u32 = 0;
-
 /// An imported item declaration in the [`Module`].
 #[derive(Debug)]
 pub enum Imported {
@@ -90,6 +154,10 @@ pub enum Imported {
     Memory(ImportName),
     /// The name of an imported [`Global`].
     Global(ImportName),
+    /// The name of an imported [`Tag`].
+    ///
+    /// [`Tag`]: [`crate::Tag`]
+    Tag(ImportName),
 }
 
 /// The import names of the [`Module`] imports.
@@ -101,29 +169,43 @@ pub struct ModuleImports {
     ///
     /// [`Func`]: [`crate::Func`]
     len_funcs: usize,
+    /// The amount of imported [`Memory`].
+    ///
+    /// [`Memory`]: [`crate::Memory`]
+    len_memories: usize,
     /// The amount of imported [`Global`].
     len_globals: usize,
+    /// The amount of imported [`Tag`].
+    ///
+    /// [`Tag`]: [`crate::Tag`]
+    len_tags: usize,
 }
 
 impl ModuleImports {
     /// Creates a new [`ModuleImports`] from the [`ModuleBuilder`] definitions.
     fn from_builder(imports: builder::ModuleImports) -> Self {
         let len_funcs = imports.funcs.len();
+        let len_memories = imports.memories.len();
         let len_globals = imports.globals.len();
+        let len_tags = imports.tags.len();
         let funcs = imports.funcs.into_iter().map(Imported::Func);
         let tables = imports.tables.into_iter().map(Imported::Table);
         let memories = imports.memories.into_iter().map(Imported::Memory);
         let globals = imports.globals.into_iter().map(Imported::Global);
+        let tags = imports.tags.into_iter().map(Imported::Tag);
         let items = funcs
             .chain(tables)
             .chain(memories)
             .chain(globals)
+            .chain(tags)
             .collect::<Vec<_>>()
             .into();
         Self {
             items,
             len_funcs,
+            len_memories,
             len_globals,
+            len_tags,
         }
     }
 }
@@ -144,9 +226,15 @@ impl Module {
         &self.engine
     }
 
-    /// Creates a new [`Module`] from the [`ModuleBuilder`].
-    fn from_builder(builder: ModuleBuilder) -> Self {
+    /// Creates a new [`Module`] from the [`ModuleBuilder`] and the `hash` of its Wasm bytes.
+    fn from_builder(builder: ModuleBuilder, hash: ModuleHash) -> Self {
+        let memory_images = MemoryImages::new(
+            builder.imports.memories.len(),
+            builder.memories.len(),
+            builder.engine.config().memory_images(),
+        );
         Self {
+            hash,
             engine: builder.engine.clone(),
             func_types: builder.func_types.into(),
             imports: ModuleImports::from_builder(builder.imports),
@@ -155,14 +243,37 @@ impl Module {
             memories: builder.memories.into(),
             globals: builder.globals.into(),
             globals_init: builder.globals_init.into(),
+            tags: builder.tags.into(),
             exports: builder.exports.into(),
             start: builder.start,
-            func_bodies: builder.func_bodies.into(),
+            func_bodies: Arc::new(builder.func_bodies),
             element_segments: builder.element_segments.into(),
             data_segments: builder.data_segments.into(),
+            memory_images,
+            names: Arc::new(builder.names),
         }
     }
 
+    /// Returns the name of the [`Module`] as stated by its `name` custom section if any.
+    pub fn name(&self) -> Option<&str> {
+        self.names.module()
+    }
+
+    /// Returns the name of the function at `func_index` as stated by the `name` custom section if any.
+    pub fn func_name(&self, func_index: u32) -> Option<&str> {
+        self.names.func(func_index)
+    }
+
+    /// Returns the hash of the Wasm bytes the [`Module`] has been compiled from.
+    pub(crate) fn hash(&self) -> ModuleHash {
+        self.hash
+    }
+
+    /// Returns the debug names of the [`Module`].
+    fn names(&self) -> Arc<ModuleNames> {
+        self.names.clone()
+    }
+
     /// Returns a slice over the [`FuncType`] of the [`Module`].
     fn func_types(&self) -> &[DedupFuncType] {
         &self.func_types[..]
@@ -172,12 +283,14 @@ impl Module {
     pub(crate) fn imports(&self) -> ModuleImportsIter {
         let len_imported_funcs = self.imports.len_funcs;
         let len_imported_globals = self.imports.len_globals;
+        let len_imported_tags = self.imports.len_tags;
         ModuleImportsIter {
             names: self.imports.items.iter(),
             funcs: self.funcs[..len_imported_funcs].iter(),
             tables: self.tables.iter(),
             memories: self.memories.iter(),
             globals: self.globals[..len_imported_globals].iter(),
+            tags: self.tags[..len_imported_tags].iter(),
         }
     }
 
@@ -197,6 +310,23 @@ impl Module {
         }
     }
 
+    /// Returns the [`MemoryImage`] of the linear memory at `memory_index` if any.
+    fn memory_image(&self, memory_index: u32) -> Option<&MemoryImage> {
+        self.memory_images
+            .get(memory_index, &self.memories, &self.data_segments)
+    }
+
+    /// Returns an iterator over the [`MemoryType`] of the internally defined [`Memory`].
+    ///
+    /// [`Memory`]: [`crate::Memory`]
+    fn internal_memories(&self) -> SliceIter<'_, MemoryType> {
+        let len_imported = self.imports.len_memories;
+        // We skip the first `len_imported` elements in `memories`
+        // since they refer to imported and not internally defined
+        // linear memories.
+        self.memories[len_imported..].iter()
+    }
+
     /// Returns an iterator over the internally defined [`Global`].
     fn internal_globals(&self) -> InternalGlobalsIter {
         let len_imported = self.imports.len_globals;
@@ -209,6 +339,17 @@ impl Module {
             iter: globals.zip(global_inits),
         }
     }
+
+    /// Returns an iterator over the signatures of the internally defined [`Tag`].
+    ///
+    /// [`Tag`]: [`crate::Tag`]
+    fn internal_tags(&self) -> SliceIter<'_, DedupFuncType> {
+        let len_imported = self.imports.len_tags;
+        // We skip the first `len_imported` elements in `tags`
+        // since they refer to imported and not internally defined
+        // exception tags.
+        self.tags[len_imported..].iter()
+    }
 }
 
 /// An iterator over the imports of a [`Module`].
@@ -219,6 +360,7 @@ pub struct ModuleImportsIter<'a> {
     tables: SliceIter<'a, TableType>,
     memories: SliceIter<'a, MemoryType>,
     globals: SliceIter<'a, GlobalType>,
+    tags: SliceIter<'a, DedupFuncType>,
 }
 
 impl<'a> Iterator for ModuleImportsIter<'a> {
@@ -256,6 +398,12 @@ impl<'a> Iterator for ModuleImportsIter<'a> {
                     });
                     ModuleImport::new(name, *global_type)
                 }
+                Imported::Tag(name) => {
+                    let tag_type = self.tags.next().unwrap_or_else(|| {
+                        panic!("unexpected missing imported exception tag for {:?}", name)
+                    });
+                    ModuleImport::new(name, ModuleImportType::Tag(*tag_type))
+                }
             },
         };
         Some(import)
@@ -327,6 +475,10 @@ pub enum ModuleImportType {
     Memory(MemoryType),
     /// An imported [`Global`].
     Global(GlobalType),
+    /// An imported [`Tag`] with the given signature.
+    ///
+    /// [`Tag`]: [`crate::Tag`]
+    Tag(DedupFuncType),
 }
 
 impl From<DedupFuncType> for ModuleImportType {
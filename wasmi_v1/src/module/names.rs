@@ -0,0 +1,90 @@
+use super::{Decode, DeserializeError, Encode, Reader, Writer};
+use alloc::{boxed::Box, collections::BTreeMap};
+use wasmparser::{Name, NameSectionReader};
+
+/// The debug names of a [`Module`] as stated by its `name` custom section.
+///
+/// [`Module`]: [`super::Module`]
+#[derive(Debug, Default)]
+pub struct ModuleNames {
+    /// The name of the [`Module`] if any.
+    ///
+    /// [`Module`]: [`super::Module`]
+    module: Option<Box<str>>,
+    /// The names of the functions indexed by their function index.
+    funcs: BTreeMap<u32, Box<str>>,
+}
+
+impl ModuleNames {
+    /// Creates new [`ModuleNames`] from the contents of a `name` custom section.
+    ///
+    /// # Note
+    ///
+    /// The `name` custom section must not cause a Wasm module to be rejected.
+    /// Therefore parsing stops at the first malformed subsection, keeping
+    /// all names that have been parsed successfully up to this point.
+    pub fn from_section(data: &[u8], data_offset: usize) -> Self {
+        let mut names = Self::default();
+        // Malformed name sections are ignored on purpose.
+        let _ = names.parse_section(data, data_offset);
+        names
+    }
+
+    /// Parses the contents of the `name` custom section into `self`.
+    fn parse_section(&mut self, data: &[u8], data_offset: usize) -> wasmparser::Result<()> {
+        let mut reader = NameSectionReader::new(data, data_offset)?;
+        while !reader.eof() {
+            match reader.read()? {
+                Name::Module(name) => {
+                    self.module = Some(name.get_name()?.into());
+                }
+                Name::Function(names) => {
+                    let mut map = names.get_map()?;
+                    for _ in 0..map.get_count() {
+                        let naming = map.read()?;
+                        self.funcs.insert(naming.index, naming.name.into());
+                    }
+                }
+                _ => {
+                    // Other names are not used by `wasmi` at the moment.
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the name of the [`Module`] if any.
+    ///
+    /// [`Module`]: [`super::Module`]
+    pub fn module(&self) -> Option<&str> {
+        self.module.as_deref()
+    }
+
+    /// Returns the name of the function at `func_index` if any.
+    pub fn func(&self, func_index: u32) -> Option<&str> {
+        self.funcs.get(&func_index).map(|name| &**name)
+    }
+}
+
+impl Encode for ModuleNames {
+    fn encode(&self, writer: &mut Writer) {
+        self.module.encode(writer);
+        writer.write_len(self.funcs.len());
+        for (func_index, name) in &self.funcs {
+            func_index.encode(writer);
+            name.encode(writer);
+        }
+    }
+}
+
+impl Decode for ModuleNames {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        let module = Decode::decode(reader)?;
+        let len_funcs = reader.read_len()?;
+        let mut funcs = BTreeMap::new();
+        for _ in 0..len_funcs {
+            funcs.insert(u32::decode(reader)?, Decode::decode(reader)?);
+        }
+        Ok(Self { module, funcs })
+    }
+}
@@ -5,6 +5,8 @@ use super::{
     Module,
     ModuleBuilder,
     ModuleError,
+    ModuleHasher,
+    ModuleNames,
     ModuleResources,
     Read,
 };
@@ -23,6 +25,7 @@ use wasmparser::{
     Payload,
     Range,
     TableSectionReader,
+    TagSectionReader,
     TypeSectionReader,
     Validator,
     WasmFeatures,
@@ -48,6 +51,8 @@ pub struct ModuleParser<'engine> {
     validator: Validator,
     /// The underlying Wasm parser.
     parser: WasmParser,
+    /// The hasher of the Wasm bytecode stream.
+    hasher: ModuleHasher,
     /// Currently processed function.
     func: FuncIdx,
 }
@@ -63,6 +68,7 @@ impl<'engine> ModuleParser<'engine> {
             builder,
             validator,
             parser,
+            hasher: ModuleHasher::default(),
             func: FuncIdx(0),
         }
     }
@@ -70,19 +76,19 @@ impl<'engine> ModuleParser<'engine> {
     /// Returns the Wasm features supported by `wasmi`.
     fn features(engine: &Engine) -> WasmFeatures {
         WasmFeatures {
-            reference_types: false,
+            reference_types: engine.config().reference_types(),
             multi_value: engine.config().multi_value(),
-            bulk_memory: false,
+            bulk_memory: engine.config().bulk_memory(),
             module_linking: false,
-            simd: false,
+            simd: engine.config().simd(),
             relaxed_simd: false,
-            threads: false,
-            tail_call: false,
+            threads: engine.config().threads(),
+            tail_call: engine.config().tail_call(),
             deterministic_only: true,
-            multi_memory: false,
-            exceptions: false,
-            memory64: false,
-            extended_const: false,
+            multi_memory: engine.config().multi_memory(),
+            exceptions: engine.config().exceptions(),
+            memory64: engine.config().memory64(),
+            extended_const: engine.config().extended_const(),
             mutable_global: engine.config().mutable_global(),
             saturating_float_to_int: engine.config().saturating_float_to_int(),
             sign_extension: engine.config().sign_extension(),
@@ -102,7 +108,9 @@ impl<'engine> ModuleParser<'engine> {
         'outer: loop {
             match self.parser.parse(&buffer[..], eof)? {
                 Chunk::NeedMoreData(hint) => {
+                    let len = buffer.len();
                     eof = Self::pull_bytes(&mut buffer, hint, &mut stream)?;
+                    self.hasher.update(&buffer[len..]);
                     continue 'outer;
                 }
                 Chunk::Parsed { consumed, payload } => {
@@ -115,7 +123,7 @@ impl<'engine> ModuleParser<'engine> {
                 }
             }
         }
-        Ok(self.builder.finish())
+        Ok(self.builder.finish(self.hasher.finish()))
     }
 
     /// Pulls more bytes from the `stream` in order to produce Wasm payload.
@@ -167,6 +175,12 @@ impl<'engine> ModuleParser<'engine> {
             Payload::ElementSection(section) => self.process_element(section),
             Payload::DataCountSection { count, range } => self.process_data_count(count, range),
             Payload::DataSection(section) => self.process_data(section),
+            Payload::CustomSection {
+                name: "name",
+                data_offset,
+                data,
+                ..
+            } => self.process_name_section(data, data_offset),
             Payload::CustomSection { .. } => Ok(()),
             Payload::CodeSectionStart { count, range, .. } => self.process_code_start(count, range),
             Payload::CodeSectionEntry(func_body) => self.process_code_entry(func_body),
@@ -312,14 +326,26 @@ impl<'engine> ModuleParser<'engine> {
         Ok(())
     }
 
-    /// Process module tags.
+    /// Process module exception tag declarations.
     ///
     /// # Note
     ///
-    /// This is part of the module linking Wasm proposal and not yet supported
-    /// by `wasmi`.
-    fn process_tags(&mut self, section: wasmparser::TagSectionReader) -> Result<(), ModuleError> {
-        self.validator.tag_section(&section).map_err(Into::into)
+    /// This extracts all exception tag declarations into the [`Module`] under construction.
+    ///
+    /// # Errors
+    ///
+    /// If an exception tag declaration fails to validate.
+    fn process_tags(&mut self, mut section: TagSectionReader) -> Result<(), ModuleError> {
+        self.validator.tag_section(&section)?;
+        let len_tags = section.get_count();
+        let tags = (0..len_tags).map(|_| {
+            section
+                .read()
+                .map(|tag| FuncTypeIdx(tag.type_index))
+                .map_err(Into::into)
+        });
+        self.builder.push_tags(tags)?;
+        Ok(())
     }
 
     /// Process module global variable declarations.
@@ -371,6 +397,18 @@ impl<'engine> ModuleParser<'engine> {
         Ok(())
     }
 
+    /// Processes the `name` custom section.
+    ///
+    /// # Note
+    ///
+    /// This extracts the debug names for the [`Module`] under construction.
+    /// Malformed `name` sections are ignored as required by the Wasm specification.
+    fn process_name_section(&mut self, data: &[u8], data_offset: usize) -> Result<(), ModuleError> {
+        self.builder
+            .set_names(ModuleNames::from_section(data, data_offset));
+        Ok(())
+    }
+
     /// Process module table element segments.
     ///
     /// # Note
@@ -392,8 +430,8 @@ impl<'engine> ModuleParser<'engine> {
     ///
     /// # Note
     ///
-    /// This is part of the bulk memory operations Wasm proposal and not yet supported
-    /// by `wasmi`.
+    /// This is part of the bulk memory operations Wasm proposal.
+    /// The data count is only required for validation by `wasmi`.
     fn process_data_count(&mut self, count: u32, range: Range) -> Result<(), ModuleError> {
         self.validator
             .data_count_section(count, &range)
@@ -455,9 +493,11 @@ impl<'engine> ModuleParser<'engine> {
     /// # Errors
     ///
     /// If the function body fails to validate.
-    fn process_code_entry(&mut self, func_body: FunctionBody) -> Result<(), ModuleError> {
+    fn process_code_entry(&mut self, mut func_body: FunctionBody) -> Result<(), ModuleError> {
         let func = self.next_func();
         let engine = self.builder.engine();
+        // Note: Memory offsets are encoded as 64-bit integers with the `memory64` proposal.
+        func_body.allow_memarg64(engine.config().memory64());
         let validator = self.validator.code_section_entry()?;
         let module_resources = ModuleResources::new(&self.builder);
         let func_body = translate(engine, func, func_body, validator, module_resources)?;
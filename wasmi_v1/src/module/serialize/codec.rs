@@ -0,0 +1,481 @@
+//! The binary encoding used by serialized Wasm modules.
+//!
+//! # Note
+//!
+//! - Integers are encoded with a fixed width in little-endian byte order.
+//! - Sequences are encoded as their length followed by their elements.
+//! - Variants of enums are encoded as a single tag byte followed by their fields.
+
+use super::{DeserializeError, SerializeError};
+use crate::{FuncType, GlobalType, MemoryType, Mutability, RefType, TableType, Value};
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+#[cfg(feature = "simd")]
+use wasmi_core::V128;
+use wasmi_core::{ValueType, F32, F64};
+
+/// Types that can be encoded into the binary format of serialized Wasm modules.
+pub trait Encode {
+    /// Encodes `self` into the `writer`.
+    fn encode(&self, writer: &mut Writer);
+}
+
+/// Types that can be decoded from the binary format of serialized Wasm modules.
+pub trait Decode: Sized {
+    /// Decodes a value of `Self` from the `reader`.
+    ///
+    /// # Errors
+    ///
+    /// If the bytes of the `reader` do not encode a valid value of `Self`.
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError>;
+}
+
+/// Accumulates the bytes of encoded values.
+#[derive(Debug, Default)]
+pub struct Writer {
+    bytes: Vec<u8>,
+    /// The first error encountered while encoding if any.
+    error: Option<SerializeError>,
+}
+
+impl Writer {
+    /// Creates a new empty [`Writer`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the bytes of all encoded values.
+    ///
+    /// # Errors
+    ///
+    /// If a value could not be encoded.
+    pub fn finish(self) -> Result<Vec<u8>, SerializeError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.bytes),
+        }
+    }
+
+    /// Records that a value could not be encoded.
+    ///
+    /// # Note
+    ///
+    /// Only the first recorded error is returned by [`Writer::finish`].
+    pub fn fail(&mut self, error: SerializeError) {
+        self.error.get_or_insert(error);
+    }
+
+    /// Writes the raw `bytes` without their length.
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    /// Writes the tag byte of an enum variant.
+    pub fn write_tag(&mut self, tag: u8) {
+        self.bytes.push(tag);
+    }
+
+    /// Writes the length of a sequence.
+    pub fn write_len(&mut self, len: usize) {
+        (len as u64).encode(self)
+    }
+}
+
+/// Decodes values from the bytes of a serialized Wasm module.
+#[derive(Debug)]
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    /// Creates a new [`Reader`] decoding the `bytes`.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    /// Returns `true` if all bytes have been read.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Reads the next `len` raw bytes.
+    ///
+    /// # Errors
+    ///
+    /// If there are less than `len` bytes left.
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], DeserializeError> {
+        if len > self.bytes.len() {
+            return Err(DeserializeError::UnexpectedEnd);
+        }
+        let (head, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    /// Reads the next `N` raw bytes.
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], DeserializeError> {
+        let mut array = [0x00; N];
+        array.copy_from_slice(self.read_bytes(N)?);
+        Ok(array)
+    }
+
+    /// Reads the tag byte of an enum variant.
+    pub fn read_tag(&mut self) -> Result<u8, DeserializeError> {
+        self.read_array::<1>().map(|[tag]| tag)
+    }
+
+    /// Reads the length of a sequence.
+    ///
+    /// # Errors
+    ///
+    /// If the length exceeds the amount of bytes left since every
+    /// element of a sequence is encoded by at least a single byte.
+    pub fn read_len(&mut self) -> Result<usize, DeserializeError> {
+        let len = usize::decode(self)?;
+        if len > self.bytes.len() {
+            return Err(DeserializeError::UnexpectedEnd);
+        }
+        Ok(len)
+    }
+}
+
+macro_rules! impl_codec_for_int {
+    ( $( $ty:ty ),* $(,)? ) => {
+        $(
+            impl Encode for $ty {
+                fn encode(&self, writer: &mut Writer) {
+                    writer.write_bytes(&self.to_le_bytes())
+                }
+            }
+
+            impl Decode for $ty {
+                fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+                    reader.read_array().map(<$ty>::from_le_bytes)
+                }
+            }
+        )*
+    };
+}
+impl_codec_for_int!(u8, u16, u32, u64, u128, i32, i64);
+
+impl Encode for usize {
+    fn encode(&self, writer: &mut Writer) {
+        (*self as u64).encode(writer)
+    }
+}
+
+impl Decode for usize {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        u64::decode(reader)?
+            .try_into()
+            .map_err(|_| DeserializeError::malformed("usize"))
+    }
+}
+
+impl Encode for bool {
+    fn encode(&self, writer: &mut Writer) {
+        writer.write_tag(u8::from(*self))
+    }
+}
+
+impl Decode for bool {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        match reader.read_tag()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(DeserializeError::malformed("bool")),
+        }
+    }
+}
+
+impl<T> Encode for Option<T>
+where
+    T: Encode,
+{
+    fn encode(&self, writer: &mut Writer) {
+        match self {
+            None => writer.write_tag(0),
+            Some(value) => {
+                writer.write_tag(1);
+                value.encode(writer);
+            }
+        }
+    }
+}
+
+impl<T> Decode for Option<T>
+where
+    T: Decode,
+{
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        match reader.read_tag()? {
+            0 => Ok(None),
+            1 => T::decode(reader).map(Some),
+            _ => Err(DeserializeError::malformed("option")),
+        }
+    }
+}
+
+impl<T> Encode for [T]
+where
+    T: Encode,
+{
+    fn encode(&self, writer: &mut Writer) {
+        writer.write_len(self.len());
+        for item in self {
+            item.encode(writer);
+        }
+    }
+}
+
+impl<T> Encode for Vec<T>
+where
+    T: Encode,
+{
+    fn encode(&self, writer: &mut Writer) {
+        self[..].encode(writer)
+    }
+}
+
+impl<T> Decode for Vec<T>
+where
+    T: Decode,
+{
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        let len = reader.read_len()?;
+        let mut items = Vec::with_capacity(len);
+        for _ in 0..len {
+            items.push(T::decode(reader)?);
+        }
+        Ok(items)
+    }
+}
+
+impl<T> Encode for Box<[T]>
+where
+    T: Encode,
+{
+    fn encode(&self, writer: &mut Writer) {
+        self[..].encode(writer)
+    }
+}
+
+impl<T> Decode for Box<[T]>
+where
+    T: Decode,
+{
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        Vec::decode(reader).map(Vec::into_boxed_slice)
+    }
+}
+
+impl Encode for str {
+    fn encode(&self, writer: &mut Writer) {
+        writer.write_len(self.len());
+        writer.write_bytes(self.as_bytes());
+    }
+}
+
+impl Encode for Box<str> {
+    fn encode(&self, writer: &mut Writer) {
+        self[..].encode(writer)
+    }
+}
+
+impl Decode for Box<str> {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        String::decode(reader).map(String::into_boxed_str)
+    }
+}
+
+impl Decode for String {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        let len = reader.read_len()?;
+        let bytes = reader.read_bytes(len)?;
+        core::str::from_utf8(bytes)
+            .map(Into::into)
+            .map_err(|_| DeserializeError::malformed("string"))
+    }
+}
+
+impl Encode for Arc<[u8]> {
+    fn encode(&self, writer: &mut Writer) {
+        writer.write_len(self.len());
+        writer.write_bytes(self);
+    }
+}
+
+impl Decode for Arc<[u8]> {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        let len = reader.read_len()?;
+        reader.read_bytes(len).map(Into::into)
+    }
+}
+
+impl Encode for ValueType {
+    fn encode(&self, writer: &mut Writer) {
+        let tag = match self {
+            ValueType::I32 => 0,
+            ValueType::I64 => 1,
+            ValueType::F32 => 2,
+            ValueType::F64 => 3,
+            ValueType::FuncRef => 4,
+            ValueType::ExternRef => 5,
+            ValueType::V128 => 6,
+            value_type => {
+                return writer.fail(SerializeError::UnsupportedValueType {
+                    value_type: *value_type,
+                })
+            }
+        };
+        writer.write_tag(tag)
+    }
+}
+
+impl Decode for ValueType {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        match reader.read_tag()? {
+            0 => Ok(ValueType::I32),
+            1 => Ok(ValueType::I64),
+            2 => Ok(ValueType::F32),
+            3 => Ok(ValueType::F64),
+            4 => Ok(ValueType::FuncRef),
+            5 => Ok(ValueType::ExternRef),
+            6 => Ok(ValueType::V128),
+            _ => Err(DeserializeError::malformed("value type")),
+        }
+    }
+}
+
+/// Encodes the [`Value`] as its [`ValueType`] followed by its bits.
+///
+/// # Note
+///
+/// Reference values are encoded without their referenced entity since
+/// serialized Wasm modules only ever contain `null` references.
+impl Encode for Value {
+    fn encode(&self, writer: &mut Writer) {
+        self.value_type().encode(writer);
+        match self {
+            Value::I32(value) => (*value as u32).encode(writer),
+            Value::I64(value) => (*value as u64).encode(writer),
+            Value::F32(value) => value.to_bits().encode(writer),
+            Value::F64(value) => value.to_bits().encode(writer),
+            Value::FuncRef(value) => assert!(
+                value.is_null(),
+                "encountered non-null function reference in serialized module",
+            ),
+            Value::ExternRef(value) => assert!(
+                value.is_null(),
+                "encountered non-null external reference in serialized module",
+            ),
+            #[cfg(feature = "simd")]
+            Value::V128(value) => value.to_bits().encode(writer),
+        }
+    }
+}
+
+impl Decode for Value {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        let value = match ValueType::decode(reader)? {
+            ValueType::I32 => Value::I32(u32::decode(reader)? as i32),
+            ValueType::I64 => Value::I64(u64::decode(reader)? as i64),
+            ValueType::F32 => Value::F32(F32::from_bits(u32::decode(reader)?)),
+            ValueType::F64 => Value::F64(F64::from_bits(u64::decode(reader)?)),
+            ValueType::FuncRef => Value::default(ValueType::FuncRef),
+            ValueType::ExternRef => Value::default(ValueType::ExternRef),
+            #[cfg(feature = "simd")]
+            ValueType::V128 => Value::V128(V128::from_bits(u128::decode(reader)?)),
+            _ => return Err(DeserializeError::malformed("value")),
+        };
+        Ok(value)
+    }
+}
+
+impl Encode for FuncType {
+    fn encode(&self, writer: &mut Writer) {
+        self.params().encode(writer);
+        self.results().encode(writer);
+    }
+}
+
+impl Decode for FuncType {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        let params = Vec::<ValueType>::decode(reader)?;
+        let results = Vec::<ValueType>::decode(reader)?;
+        Ok(FuncType::new(params, results))
+    }
+}
+
+impl Encode for TableType {
+    fn encode(&self, writer: &mut Writer) {
+        self.element().encode(writer);
+        self.initial().encode(writer);
+        self.maximum().encode(writer);
+    }
+}
+
+impl Decode for TableType {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        let element = RefType::from_value_type(ValueType::decode(reader)?)
+            .ok_or_else(|| DeserializeError::malformed("table type"))?;
+        let initial = usize::decode(reader)?;
+        let maximum = Option::<usize>::decode(reader)?;
+        if matches!(maximum, Some(maximum) if maximum < initial) {
+            return Err(DeserializeError::malformed("table type"));
+        }
+        Ok(TableType::new(element, initial, maximum))
+    }
+}
+
+impl Encode for MemoryType {
+    fn encode(&self, writer: &mut Writer) {
+        self.is_64().encode(writer);
+        self.is_shared().encode(writer);
+        self.initial_pages().0.encode(writer);
+        self.maximum_pages().map(|pages| pages.0).encode(writer);
+    }
+}
+
+impl Decode for MemoryType {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        let memory64 = bool::decode(reader)?;
+        let shared = bool::decode(reader)?;
+        let initial = usize::decode(reader)?;
+        let maximum = Option::<usize>::decode(reader)?;
+        let memory_type = match memory64 {
+            true => MemoryType::new64(initial as u64, maximum.map(|pages| pages as u64)),
+            false => {
+                let into_u32 = |pages: usize| {
+                    u32::try_from(pages).map_err(|_| DeserializeError::malformed("memory type"))
+                };
+                MemoryType::new(into_u32(initial)?, maximum.map(into_u32).transpose()?)
+            }
+        };
+        match shared {
+            false => Ok(memory_type),
+            #[cfg(feature = "threads")]
+            true => Ok(memory_type.into_shared()),
+            #[cfg(not(feature = "threads"))]
+            true => Err(DeserializeError::malformed("memory type")),
+        }
+    }
+}
+
+impl Encode for GlobalType {
+    fn encode(&self, writer: &mut Writer) {
+        self.value_type().encode(writer);
+        let mutable = matches!(self.mutability(), Mutability::Mutable);
+        mutable.encode(writer);
+    }
+}
+
+impl Decode for GlobalType {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        let value_type = ValueType::decode(reader)?;
+        let mutability = match bool::decode(reader)? {
+            true => Mutability::Mutable,
+            false => Mutability::Const,
+        };
+        Ok(GlobalType::new(value_type, mutability))
+    }
+}
@@ -0,0 +1,130 @@
+use alloc::string::String;
+use core::{fmt, fmt::Display};
+use wasmi_core::ValueType;
+
+/// An error that may occur upon serializing a [`Module`].
+///
+/// [`Module`]: [`crate::Module`]
+#[derive(Debug)]
+pub enum SerializeError {
+    /// Encountered when the [`Module`] uses a [`ValueType`] the binary format cannot encode.
+    ///
+    /// [`Module`]: [`crate::Module`]
+    UnsupportedValueType {
+        /// The [`ValueType`] that cannot be encoded.
+        value_type: ValueType,
+    },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SerializeError {}
+
+impl Display for SerializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedValueType { value_type } => {
+                write!(f, "cannot serialize module using {} values", value_type)
+            }
+        }
+    }
+}
+
+/// An error that may occur upon deserializing a [`Module`].
+///
+/// [`Module`]: [`crate::Module`]
+#[derive(Debug)]
+pub enum DeserializeError {
+    /// Encountered when the bytes do not start with the header of a serialized [`Module`].
+    ///
+    /// [`Module`]: [`crate::Module`]
+    InvalidHeader,
+    /// Encountered when the [`Module`] has been serialized in another binary format version.
+    ///
+    /// [`Module`]: [`crate::Module`]
+    IncompatibleFormat {
+        /// The binary format version of the serialized [`Module`].
+        ///
+        /// [`Module`]: [`crate::Module`]
+        format_version: u32,
+    },
+    /// Encountered when the [`Module`] has been serialized by an incompatible `wasmi` version.
+    ///
+    /// [`Module`]: [`crate::Module`]
+    IncompatibleVersion {
+        /// The `wasmi` version that serialized the [`Module`].
+        ///
+        /// [`Module`]: [`crate::Module`]
+        version: String,
+    },
+    /// Encountered when the [`Module`] has been serialized with different `wasmi` crate features.
+    ///
+    /// # Note
+    ///
+    /// The `simd` and `threads` crate features affect the `wasmi` bytecode.
+    ///
+    /// [`Module`]: [`crate::Module`]
+    IncompatibleFeatures,
+    /// Encountered when the [`Module`] has been serialized by an [`Engine`] with
+    /// a [`Config`] that differs in the enabled Wasm proposals or Wasm backtraces.
+    ///
+    /// [`Module`]: [`crate::Module`]
+    /// [`Engine`]: [`crate::Engine`]
+    /// [`Config`]: [`crate::Config`]
+    IncompatibleConfig,
+    /// Encountered when the serialized [`Module`] ends unexpectedly.
+    ///
+    /// [`Module`]: [`crate::Module`]
+    UnexpectedEnd,
+    /// Encountered when a part of the serialized [`Module`] is malformed.
+    ///
+    /// [`Module`]: [`crate::Module`]
+    Malformed {
+        /// The malformed part of the serialized [`Module`].
+        ///
+        /// [`Module`]: [`crate::Module`]
+        part: &'static str,
+    },
+}
+
+impl DeserializeError {
+    /// Creates a new [`DeserializeError`] for the malformed `part`.
+    pub(crate) fn malformed(part: &'static str) -> Self {
+        Self::Malformed { part }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DeserializeError {}
+
+impl Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidHeader => write!(f, "encountered invalid serialized module header"),
+            Self::IncompatibleFormat { format_version } => write!(
+                f,
+                "module has been serialized in format version {} but expected format version {}",
+                format_version,
+                super::FORMAT_VERSION,
+            ),
+            Self::IncompatibleVersion { version } => write!(
+                f,
+                "module has been serialized by wasmi {} but expected wasmi {}",
+                version,
+                env!("CARGO_PKG_VERSION"),
+            ),
+            Self::IncompatibleFeatures => {
+                write!(
+                    f,
+                    "module has been serialized with different crate features"
+                )
+            }
+            Self::IncompatibleConfig => {
+                write!(f, "module has been serialized with an incompatible config")
+            }
+            Self::UnexpectedEnd => write!(f, "encountered unexpected end of serialized module"),
+            Self::Malformed { part } => {
+                write!(f, "encountered malformed {} in serialized module", part)
+            }
+        }
+    }
+}
@@ -0,0 +1,409 @@
+//! Serialization of translated Wasm modules into a versioned binary format.
+
+mod codec;
+mod error;
+
+pub use self::{
+    codec::{Decode, Encode, Reader, Writer},
+    error::{DeserializeError, SerializeError},
+};
+use super::{
+    DataSegment,
+    ElementSegment,
+    Export,
+    FuncIdx,
+    GlobalIdx,
+    Imported,
+    InitExpr,
+    MemoryIdx,
+    MemoryImages,
+    Module,
+    ModuleError,
+    ModuleHash,
+    ModuleImports,
+    ModuleNames,
+    TableIdx,
+    TagIdx,
+};
+use crate::{
+    engine::{DedupFuncType, FuncBodies},
+    Config,
+    Engine,
+    Error,
+    FuncType,
+    GlobalType,
+    MemoryType,
+    TableType,
+};
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+
+/// The magic bytes at the start of every serialized [`Module`].
+const MAGIC: [u8; 8] = *b"\0wasmi\0\0";
+
+/// The version of the binary format of serialized [`Module`] instances.
+///
+/// # Note
+///
+/// This must be incremented whenever the encoding of serialized [`Module`]
+/// instances changes, including the encoding of `wasmi` bytecode instructions.
+/// Deserialization feeds the bytecode to the executor without validating it
+/// again, so bytecode of another format version must never be deserialized.
+const FORMAT_VERSION: u32 = 2;
+
+/// The header of a serialized [`Module`].
+///
+/// # Note
+///
+/// The header guards against deserializing [`Module`] bytecode that has been
+/// encoded in another format or translated by an incompatible `wasmi` version
+/// or [`Engine`] configuration.
+#[derive(Debug)]
+struct Header {
+    /// The [`FORMAT_VERSION`] of the serialized [`Module`].
+    format_version: u32,
+    /// The version of the `wasmi` crate that serialized the [`Module`].
+    version: Box<str>,
+    /// The enabled crate features that affect the `wasmi` bytecode.
+    features: u8,
+    /// The [`Config`] flags that affect the translation of Wasm modules.
+    config: u32,
+}
+
+impl Header {
+    /// Creates the [`Header`] for [`Module`] bytecode translated by the `engine`.
+    fn new(engine: &Engine) -> Self {
+        Self {
+            format_version: FORMAT_VERSION,
+            version: env!("CARGO_PKG_VERSION").into(),
+            features: Self::crate_features(),
+            config: Self::config_flags(&engine.config()),
+        }
+    }
+
+    /// Returns the enabled crate features that affect the `wasmi` bytecode.
+    fn crate_features() -> u8 {
+        u8::from(cfg!(feature = "simd")) | (u8::from(cfg!(feature = "threads")) << 1)
+    }
+
+    /// Returns the flags of the `config` that affect the translation of Wasm modules.
+    ///
+    /// # Note
+    ///
+    /// The limits of the value and call stacks as well as fuel metering and
+    /// epoch interruption do not affect the translated `wasmi` bytecode.
+    fn config_flags(config: &Config) -> u32 {
+        [
+            config.mutable_global(),
+            config.sign_extension(),
+            config.saturating_float_to_int(),
+            config.multi_value(),
+            config.bulk_memory(),
+            config.reference_types(),
+            config.tail_call(),
+            config.simd(),
+            config.multi_memory(),
+            config.memory64(),
+            config.threads(),
+            config.exceptions(),
+            config.extended_const(),
+            config.wasm_backtrace(),
+            config.bytecode_optimizations(),
+        ]
+        .iter()
+        .enumerate()
+        .fold(0, |flags, (n, enabled)| flags | (u32::from(*enabled) << n))
+    }
+
+    /// Decodes the [`Header`] and checks that it is compatible with the `engine`.
+    ///
+    /// # Errors
+    ///
+    /// If the serialized [`Module`] is incompatible with the `engine`.
+    fn check(reader: &mut Reader, engine: &Engine) -> Result<(), DeserializeError> {
+        let expected = Self::new(engine);
+        match reader.read_bytes(MAGIC.len()) {
+            Ok(magic) if magic == MAGIC => {}
+            _ => return Err(DeserializeError::InvalidHeader),
+        }
+        let format_version = u32::decode(reader)?;
+        if format_version != expected.format_version {
+            return Err(DeserializeError::IncompatibleFormat { format_version });
+        }
+        let version = String::decode(reader)?;
+        if *version != *expected.version {
+            return Err(DeserializeError::IncompatibleVersion { version });
+        }
+        if u8::decode(reader)? != expected.features {
+            return Err(DeserializeError::IncompatibleFeatures);
+        }
+        if u32::decode(reader)? != expected.config {
+            return Err(DeserializeError::IncompatibleConfig);
+        }
+        Ok(())
+    }
+}
+
+impl Encode for Header {
+    fn encode(&self, writer: &mut Writer) {
+        writer.write_bytes(&MAGIC);
+        self.format_version.encode(writer);
+        self.version.encode(writer);
+        self.features.encode(writer);
+        self.config.encode(writer);
+    }
+}
+
+macro_rules! impl_codec_for_index {
+    ( $( $ty:ident ),* $(,)? ) => {
+        $(
+            impl Encode for $ty {
+                fn encode(&self, writer: &mut Writer) {
+                    self.0.encode(writer)
+                }
+            }
+
+            impl Decode for $ty {
+                fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+                    u32::decode(reader).map(Self)
+                }
+            }
+        )*
+    };
+}
+impl_codec_for_index!(FuncIdx, TableIdx, MemoryIdx, GlobalIdx, TagIdx);
+
+impl Encode for ModuleHash {
+    fn encode(&self, writer: &mut Writer) {
+        self.0.encode(writer)
+    }
+}
+
+impl Decode for ModuleHash {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        u128::decode(reader).map(Self)
+    }
+}
+
+impl Encode for ModuleImports {
+    fn encode(&self, writer: &mut Writer) {
+        writer.write_len(self.items.len());
+        for imported in &self.items[..] {
+            let (tag, name) = match imported {
+                Imported::Func(name) => (0, name),
+                Imported::Table(name) => (1, name),
+                Imported::Memory(name) => (2, name),
+                Imported::Global(name) => (3, name),
+                Imported::Tag(name) => (4, name),
+            };
+            writer.write_tag(tag);
+            name.encode(writer);
+        }
+    }
+}
+
+impl Decode for ModuleImports {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        let len_items = reader.read_len()?;
+        let mut items = Vec::with_capacity(len_items);
+        for _ in 0..len_items {
+            let imported = match reader.read_tag()? {
+                0 => Imported::Func,
+                1 => Imported::Table,
+                2 => Imported::Memory,
+                3 => Imported::Global,
+                4 => Imported::Tag,
+                _ => return Err(DeserializeError::malformed("import")),
+            };
+            items.push(imported(Decode::decode(reader)?));
+        }
+        let mut imports = Self {
+            items: items.into(),
+            len_funcs: 0,
+            len_memories: 0,
+            len_globals: 0,
+            len_tags: 0,
+        };
+        imports.len_funcs = imports.count(|imported| matches!(imported, Imported::Func(_)));
+        imports.len_memories = imports.count(|imported| matches!(imported, Imported::Memory(_)));
+        imports.len_globals = imports.count(|imported| matches!(imported, Imported::Global(_)));
+        imports.len_tags = imports.count(|imported| matches!(imported, Imported::Tag(_)));
+        Ok(imports)
+    }
+}
+
+impl ModuleImports {
+    /// Returns the amount of imported items that satisfy the `filter`.
+    fn count(&self, filter: fn(&Imported) -> bool) -> usize {
+        self.items
+            .iter()
+            .filter(|imported| filter(imported))
+            .count()
+    }
+}
+
+/// Encodes the function types of `entities` as indices into the `func_types` of a [`Module`].
+fn encode_func_type_indices(
+    func_types: &[DedupFuncType],
+    entities: &[DedupFuncType],
+    writer: &mut Writer,
+) {
+    writer.write_len(entities.len());
+    for entity in entities {
+        let index = func_types
+            .iter()
+            .position(|func_type| func_type == entity)
+            .unwrap_or_else(|| panic!("missing function type {:?} in module", entity));
+        (index as u32).encode(writer);
+    }
+}
+
+/// Decodes function types encoded by [`encode_func_type_indices`].
+fn decode_func_type_indices(
+    func_types: &[DedupFuncType],
+    reader: &mut Reader,
+) -> Result<Box<[DedupFuncType]>, DeserializeError> {
+    Vec::<u32>::decode(reader)?
+        .into_iter()
+        .map(|index| {
+            func_types
+                .get(index as usize)
+                .copied()
+                .ok_or_else(|| DeserializeError::malformed("function type index"))
+        })
+        .collect()
+}
+
+impl Module {
+    /// Serializes the [`Module`] into a versioned binary format.
+    ///
+    /// # Note
+    ///
+    /// The serialized [`Module`] contains the translated `wasmi` bytecode of
+    /// the [`Module`] and can be deserialized via [`Module::deserialize`]
+    /// without parsing, validating and translating its Wasm binary again.
+    ///
+    /// # Errors
+    ///
+    /// If the [`Module`] uses a [`ValueType`] that the binary format cannot encode.
+    ///
+    /// [`ValueType`]: [`wasmi_core::ValueType`]
+    pub fn serialize(&self) -> Result<Vec<u8>, Error> {
+        let mut writer = Writer::new();
+        Header::new(&self.engine).encode(&mut writer);
+        self.hash.encode(&mut writer);
+        let func_types = self
+            .func_types
+            .iter()
+            .map(|func_type| self.engine.resolve_func_type(*func_type, Clone::clone))
+            .collect::<Vec<FuncType>>();
+        func_types.encode(&mut writer);
+        self.imports.encode(&mut writer);
+        encode_func_type_indices(&self.func_types, &self.funcs, &mut writer);
+        self.tables.encode(&mut writer);
+        self.memories.encode(&mut writer);
+        self.globals.encode(&mut writer);
+        self.globals_init.encode(&mut writer);
+        encode_func_type_indices(&self.func_types, &self.tags, &mut writer);
+        self.exports.encode(&mut writer);
+        self.start.encode(&mut writer);
+        writer.write_len(self.func_bodies.len());
+        for func_body in self.func_bodies.iter() {
+            self.engine.encode_func_body(*func_body, &mut writer);
+        }
+        self.element_segments.encode(&mut writer);
+        self.data_segments.encode(&mut writer);
+        self.names.encode(&mut writer);
+        writer
+            .finish()
+            .map_err(|error| ModuleError::from(error).into())
+    }
+
+    /// Deserializes a [`Module`] serialized via [`Module::serialize`] for the `engine`.
+    ///
+    /// # Errors
+    ///
+    /// - If the `bytes` have been serialized in another binary format version.
+    /// - If the `bytes` have not been serialized by the same `wasmi` version
+    ///   compiled with the same `simd` and `threads` crate features.
+    /// - If the `bytes` have been serialized by an [`Engine`] whose [`Config`]
+    ///   differs from the [`Config`] of the `engine` in the enabled Wasm proposals
+    ///   or whether Wasm backtraces are enabled.
+    /// - If the `bytes` are malformed.
+    ///
+    /// # Safety
+    ///
+    /// The deserialized `wasmi` bytecode is not validated again and is executed
+    /// without bounds checks that the validation of Wasm modules renders unnecessary.
+    /// Therefore the `bytes` must have been produced by [`Module::serialize`] and
+    /// must not have been modified since.
+    pub unsafe fn deserialize(engine: &Engine, bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader::new(bytes);
+        Self::decode(engine, &mut reader).map_err(|error| ModuleError::from(error).into())
+    }
+
+    /// Decodes a [`Module`] for the `engine` from the `reader`.
+    fn decode(engine: &Engine, reader: &mut Reader) -> Result<Self, DeserializeError> {
+        Header::check(reader, engine)?;
+        let hash = ModuleHash::decode(reader)?;
+        let func_types = Vec::<FuncType>::decode(reader)?
+            .into_iter()
+            .map(|func_type| engine.alloc_func_type(func_type))
+            .collect::<Box<[_]>>();
+        let imports = ModuleImports::decode(reader)?;
+        let funcs = decode_func_type_indices(&func_types, reader)?;
+        let tables = Box::<[TableType]>::decode(reader)?;
+        let memories = Box::<[MemoryType]>::decode(reader)?;
+        let globals = Box::<[GlobalType]>::decode(reader)?;
+        let globals_init = Box::<[InitExpr]>::decode(reader)?;
+        let tags = decode_func_type_indices(&func_types, reader)?;
+        let exports = Box::<[Export]>::decode(reader)?;
+        let start = Option::<FuncIdx>::decode(reader)?;
+        let len_imported_tables = imports.count(|imported| matches!(imported, Imported::Table(_)));
+        if imports.len_funcs > funcs.len()
+            || len_imported_tables > tables.len()
+            || imports.len_memories > memories.len()
+            || imports.len_globals + globals_init.len() != globals.len()
+            || imports.len_tags > tags.len()
+        {
+            return Err(DeserializeError::malformed("imports"));
+        }
+        let len_func_bodies = reader.read_len()?;
+        if imports.len_funcs + len_func_bodies != funcs.len() {
+            return Err(DeserializeError::malformed("function bodies"));
+        }
+        let mut func_bodies = FuncBodies::new(engine);
+        for _ in 0..len_func_bodies {
+            func_bodies.push(engine.decode_func_body(reader)?);
+        }
+        let element_segments = Box::<[ElementSegment]>::decode(reader)?;
+        let data_segments = Box::<[DataSegment]>::decode(reader)?;
+        let names = ModuleNames::decode(reader)?;
+        if !reader.is_empty() {
+            return Err(DeserializeError::malformed("module"));
+        }
+        let memory_images = MemoryImages::new(
+            imports.len_memories,
+            memories.len(),
+            engine.config().memory_images(),
+        );
+        Ok(Self {
+            hash,
+            engine: engine.clone(),
+            func_types,
+            imports,
+            funcs,
+            tables,
+            memories,
+            globals,
+            globals_init,
+            tags,
+            exports,
+            start,
+            func_bodies: Arc::new(func_bodies),
+            element_segments,
+            data_segments,
+            memory_images,
+            names: Arc::new(names),
+        })
+    }
+}
@@ -6,6 +6,7 @@ use crate::{
         InstructionIdx,
         Target,
     },
+    Config,
     Engine,
 };
 
@@ -14,13 +15,13 @@ fn wat2wasm(wat: &str) -> Vec<u8> {
     wat::parse_str(wat).unwrap()
 }
 
-/// Compiles the `wasm` encoded bytes into a [`Module`].
+/// Compiles the `wasm` encoded bytes into a [`Module`] using the `config`.
 ///
 /// # Panics
 ///
 /// If an error occurred upon module compilation, validation or translation.
-fn create_module(bytes: &[u8]) -> Module {
-    let engine = Engine::default();
+fn create_module(config: &Config, bytes: &[u8]) -> Module {
+    let engine = Engine::new(config);
     Module::new(&engine, bytes).unwrap()
 }
 
@@ -73,18 +74,50 @@ fn assert_func_body<E>(
 
 /// Asserts that the given `wasm` bytes yield functions with expected instructions.
 ///
+/// # Note
+///
+/// The bytecode optimizations are disabled so that the
+/// expected instructions mirror the translated Wasm operators.
+///
 /// # Panics
 ///
 /// If any of the yielded functions consists of instruction different from the
 /// expected instructions for that function.
 fn assert_func_bodies<E, T>(wasm_bytes: impl AsRef<[u8]>, expected: E)
+where
+    E: IntoIterator<Item = T>,
+    T: IntoIterator<Item = Instruction>,
+    <T as IntoIterator>::IntoIter: ExactSizeIterator,
+{
+    let config = Config::default().enable_bytecode_optimizations(false);
+    assert_func_bodies_for(&config, wasm_bytes, expected)
+}
+
+/// Asserts that the given `wasm` bytes yield functions with expected optimized instructions.
+///
+/// # Panics
+///
+/// If any of the yielded functions consists of instruction different from the
+/// expected instructions for that function.
+fn assert_optimized_func_bodies<E, T>(wasm_bytes: impl AsRef<[u8]>, expected: E)
+where
+    E: IntoIterator<Item = T>,
+    T: IntoIterator<Item = Instruction>,
+    <T as IntoIterator>::IntoIter: ExactSizeIterator,
+{
+    let config = Config::default().enable_bytecode_optimizations(true);
+    assert_func_bodies_for(&config, wasm_bytes, expected)
+}
+
+/// Asserts that the `wasm` bytes translated with the `config` yield the expected instructions.
+fn assert_func_bodies_for<E, T>(config: &Config, wasm_bytes: impl AsRef<[u8]>, expected: E)
 where
     E: IntoIterator<Item = T>,
     T: IntoIterator<Item = Instruction>,
     <T as IntoIterator>::IntoIter: ExactSizeIterator,
 {
     let wasm_bytes = wasm_bytes.as_ref();
-    let module = create_module(wasm_bytes);
+    let module = create_module(config, wasm_bytes);
     let engine = module.engine();
     for ((func_type, func_body), expected) in module.internal_funcs().zip(expected) {
         assert_func_body(engine, func_type, func_body, expected);
@@ -725,3 +758,300 @@ fn br_table_return() {
     ];
     assert_func_bodies(&wasm, [expected]);
 }
+
+#[test]
+fn fused_add_imm() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (export "call") (param i64) (result i64)
+                local.get 0
+                i64.const 5
+                i64.sub
+            )
+        )
+    "#,
+    );
+    let expected = [
+        Instruction::local_get(1),
+        Instruction::I64AddImm(-5),
+        Instruction::Return(DropKeep::new(1, 1)),
+    ];
+    assert_optimized_func_bodies(&wasm, [expected]);
+}
+
+#[test]
+fn folded_constants() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (export "call") (result i32)
+                i64.const 7
+                i64.const 0x1_0000_0000
+                i64.add
+                i32.wrap_i64
+                i32.const 3
+                i32.mul
+            )
+        )
+    "#,
+    );
+    let expected = [
+        Instruction::constant(21),
+        Instruction::Return(DropKeep::new(0, 1)),
+    ];
+    assert_optimized_func_bodies(&wasm, [expected]);
+}
+
+#[test]
+fn fused_load_local() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (memory 1)
+            (func (export "call") (param i32) (result i32)
+                local.get 0
+                i32.load offset=4
+            )
+        )
+    "#,
+    );
+    let expected = [
+        Instruction::I32LoadLocal {
+            base: 0.into(),
+            memory: 0.into(),
+            offset: 4.into(),
+        },
+        Instruction::Return(DropKeep::new(1, 1)),
+    ];
+    assert_optimized_func_bodies(&wasm, [expected]);
+}
+
+#[test]
+fn removed_dead_drops() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (global i32 (i32.const 0))
+            (func (export "call") (param i32)
+                local.get 0
+                drop
+                global.get 0
+                drop
+                i32.const 5
+                local.tee 0
+                drop
+            )
+        )
+    "#,
+    );
+    let expected = [
+        Instruction::constant(5),
+        Instruction::local_set(1),
+        Instruction::Return(DropKeep::new(1, 0)),
+    ];
+    assert_optimized_func_bodies(&wasm, [expected]);
+}
+
+#[test]
+fn fused_br_if_compare() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (export "call") (param i32)
+                (loop $continue
+                    (br_if $continue (i32.gt_u (local.get 0) (i32.const 3)))
+                )
+                (block $exit
+                    (br_if $exit (i32.eqz (local.get 0)))
+                )
+            )
+        )
+    "#,
+    );
+    let expected = [
+        /* 0 */ Instruction::local_get(1),
+        /* 1 */ Instruction::constant(3),
+        /* 2 */ Instruction::BrIfI32GtU(target!(0, drop: 0, keep: 0)),
+        /* 3 */ Instruction::local_get(1),
+        /* 4 */ Instruction::BrIfEqz(target!(5, drop: 0, keep: 0)),
+        /* 5 */ Instruction::Return(DropKeep::new(1, 0)),
+    ];
+    assert_optimized_func_bodies(&wasm, [expected]);
+}
+
+#[test]
+fn fused_if_compare() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (export "call") (param i32) (result i32)
+                (if (result i32) (i32.lt_s (local.get 0) (i32.const 10))
+                    (then (i32.const 1))
+                    (else (i32.const 2))
+                )
+            )
+        )
+    "#,
+    );
+    let expected = [
+        /* 0 */ Instruction::local_get(1),
+        /* 1 */ Instruction::constant(10),
+        /* 2 */ Instruction::BrIfI32GeS(target!(5, drop: 0, keep: 0)),
+        /* 3 */ Instruction::constant(1),
+        /* 4 */ Instruction::Br(target!(6, drop: 0, keep: 0)),
+        /* 5 */ Instruction::constant(2),
+        /* 6 */ Instruction::Return(DropKeep::new(1, 1)),
+    ];
+    assert_optimized_func_bodies(&wasm, [expected]);
+}
+
+#[test]
+fn br_if_into_fused_sequence() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (export "call") (param i32) (param i32) (result i32)
+                local.get 0
+                (block $b (result i32)
+                    i32.const 1
+                    local.get 1
+                    br_if $b
+                    drop
+                    i32.const 2
+                )
+                i32.add
+            )
+        )
+    "#,
+    );
+    let expected = [
+        /* 0 */ Instruction::local_get(2),
+        /* 1 */ Instruction::constant(1),
+        /* 2 */ Instruction::local_get(3),
+        /* 3 */ Instruction::BrIfNez(target!(6, drop: 0, keep: 1)),
+        /* 4 */ Instruction::Drop,
+        /* 5 */ Instruction::constant(2),
+        /* 6 */ Instruction::I32Add,
+        /* 7 */ Instruction::Return(DropKeep::new(2, 1)),
+    ];
+    assert_optimized_func_bodies(&wasm, [expected]);
+}
+
+#[test]
+fn loop_header_at_fused_instruction() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (export "call") (param i32) (result i32)
+                i32.const 10
+                (loop $continue (param i32) (result i32)
+                    i32.const 1
+                    i32.sub
+                    local.tee 0
+                    local.get 0
+                    br_if $continue
+                )
+            )
+        )
+    "#,
+    );
+    let expected = [
+        /* 0 */ Instruction::constant(10),
+        /* 1 */ Instruction::I32AddImm(-1),
+        /* 2 */ Instruction::local_tee(2),
+        /* 3 */ Instruction::local_get(2),
+        /* 4 */ Instruction::BrIfNez(target!(1, drop: 0, keep: 1)),
+        /* 5 */ Instruction::Return(DropKeep::new(1, 1)),
+    ];
+    assert_optimized_func_bodies(&wasm, [expected]);
+}
+
+#[test]
+fn unoptimized_bytecode() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (export "call") (param i32) (result i32)
+                local.get 0
+                i32.const 1
+                i32.add
+            )
+        )
+    "#,
+    );
+    let expected = [
+        Instruction::local_get(1),
+        Instruction::constant(1),
+        Instruction::I32Add,
+        Instruction::Return(DropKeep::new(1, 1)),
+    ];
+    assert_func_bodies(&wasm, [expected]);
+}
+
+/// Is `true` if linear memories can be initialized from memory images.
+const MEMORY_IMAGES_SUPPORTED: bool = cfg!(all(
+    feature = "virtual_memory",
+    target_pointer_width = "64",
+    target_os = "linux"
+));
+
+/// Returns the Wasm module whose linear memory is initialized by `len` active data segments.
+fn data_segments_wasm(len: usize) -> Vec<u8> {
+    let segments = (0..len)
+        .map(|offset| format!("(data (i32.const {}) \"\\2A\")", offset))
+        .collect::<Vec<_>>()
+        .join("\n");
+    wat2wasm(&format!("(module (memory 1) {})", segments))
+}
+
+#[test]
+fn memory_images_are_prepared_lazily() {
+    let module = create_module(&Config::default(), &data_segments_wasm(1));
+    assert!(!module.memory_images.is_prepared(0));
+    assert_eq!(module.memory_image(0).is_some(), MEMORY_IMAGES_SUPPORTED);
+    assert!(module.memory_images.is_prepared(0));
+}
+
+#[test]
+fn memory_images_are_limited_by_data_segments() {
+    let len = MemoryImages::MAX_DATA_SEGMENTS;
+    let module = create_module(&Config::default(), &data_segments_wasm(len));
+    assert_eq!(module.memory_image(0).is_some(), MEMORY_IMAGES_SUPPORTED);
+    let module = create_module(&Config::default(), &data_segments_wasm(len + 1));
+    assert!(module.memory_image(0).is_none());
+}
+
+#[test]
+fn memory_images_can_be_disabled() {
+    let config = Config::default().enable_memory_images(false);
+    let module = create_module(&config, &data_segments_wasm(1));
+    assert!(module.memory_image(0).is_none());
+    assert!(!module.memory_images.is_prepared(0));
+}
+
+#[test]
+fn module_hasher_computes_fnv1a_128() {
+    let hash = |bytes: &[u8]| {
+        let mut hasher = ModuleHasher::default();
+        hasher.update(bytes);
+        hasher.finish()
+    };
+    assert_eq!(hash(b""), ModuleHash(0x6c62272e07bb014262b821756295c58d));
+    assert_eq!(hash(b"a"), ModuleHash(0xd228cb696f1a8caf78912b704e4a8964));
+    assert_eq!(
+        hash(b"foobar"),
+        ModuleHash(0x343e1662793c64bf6f0d3597ba446f18)
+    );
+}
+
+#[test]
+fn module_hash_is_hash_of_wasm_bytes() {
+    let wasm = data_segments_wasm(1);
+    let module = create_module(&Config::default(), &wasm);
+    let mut hasher = ModuleHasher::default();
+    hasher.update(&wasm);
+    assert_eq!(module.hash(), hasher.finish());
+    let other = create_module(&Config::default(), &data_segments_wasm(2));
+    assert_ne!(module.hash(), other.hash());
+}
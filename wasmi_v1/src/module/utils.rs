@@ -1,16 +1,16 @@
-use crate::{FuncType, GlobalType, MemoryType, ModuleError, Mutability, TableType};
+use crate::{FuncType, GlobalType, MemoryType, ModuleError, Mutability, RefType, TableType};
 use wasmi_core::ValueType;
 
 impl TryFrom<wasmparser::TableType> for TableType {
     type Error = ModuleError;
 
     fn try_from(table_type: wasmparser::TableType) -> Result<Self, Self::Error> {
-        if table_type.element_type != wasmparser::Type::FuncRef {
-            return Err(ModuleError::unsupported(table_type));
-        }
+        let element = value_type_from_wasmparser(&table_type.element_type)?;
+        let element = RefType::from_value_type(element)
+            .ok_or_else(|| ModuleError::unsupported(table_type))?;
         let initial = table_type.initial as usize;
         let maximum = table_type.maximum.map(|value| value as usize);
-        Ok(TableType::new(initial, maximum))
+        Ok(TableType::new(element, initial, maximum))
     }
 }
 
@@ -20,16 +20,26 @@ impl TryFrom<wasmparser::MemoryType> for MemoryType {
     fn try_from(memory_type: wasmparser::MemoryType) -> Result<Self, Self::Error> {
         let make_error = || ModuleError::unsupported(memory_type);
         let into_error = |_error| make_error();
-        if memory_type.memory64 || memory_type.shared {
+        if memory_type.shared && cfg!(not(feature = "threads")) {
             return Err(make_error());
         }
-        let initial = memory_type.initial.try_into().map_err(into_error)?;
-        let maximum = memory_type
-            .maximum
-            .map(|value| value.try_into())
-            .transpose()
-            .map_err(into_error)?;
-        Ok(MemoryType::new(initial, maximum))
+        let converted = match memory_type.memory64 {
+            true => MemoryType::new64(memory_type.initial, memory_type.maximum),
+            false => {
+                let initial = memory_type.initial.try_into().map_err(into_error)?;
+                let maximum = memory_type
+                    .maximum
+                    .map(|value| value.try_into())
+                    .transpose()
+                    .map_err(into_error)?;
+                MemoryType::new(initial, maximum)
+            }
+        };
+        #[cfg(feature = "threads")]
+        if memory_type.shared {
+            return Ok(converted.into_shared());
+        }
+        Ok(converted)
     }
 }
 
@@ -85,11 +95,14 @@ pub fn value_type_from_wasmparser(value_type: &wasmparser::Type) -> Result<Value
         wasmparser::Type::I64 => Ok(ValueType::I64),
         wasmparser::Type::F32 => Ok(ValueType::F32),
         wasmparser::Type::F64 => Ok(ValueType::F64),
-        wasmparser::Type::V128
-        | wasmparser::Type::FuncRef
-        | wasmparser::Type::ExternRef
-        | wasmparser::Type::ExnRef
-        | wasmparser::Type::Func
-        | wasmparser::Type::EmptyBlockType => Err(ModuleError::unsupported(value_type)),
+        wasmparser::Type::FuncRef => Ok(ValueType::FuncRef),
+        wasmparser::Type::ExternRef => Ok(ValueType::ExternRef),
+        #[cfg(feature = "simd")]
+        wasmparser::Type::V128 => Ok(ValueType::V128),
+        #[cfg(not(feature = "simd"))]
+        wasmparser::Type::V128 => Err(ModuleError::unsupported(value_type)),
+        wasmparser::Type::ExnRef | wasmparser::Type::Func | wasmparser::Type::EmptyBlockType => {
+            Err(ModuleError::unsupported(value_type))
+        }
     }
 }
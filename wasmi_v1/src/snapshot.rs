@@ -0,0 +1,580 @@
+use super::{
+    module::{Decode, DeserializeError, Encode, ModuleHash, Reader, SerializeError, Writer},
+    AsContext,
+    AsContextMut,
+    DataSegmentEntity,
+    ElementSegmentEntity,
+    Error,
+    FuncRef,
+    GlobalType,
+    Instance,
+    InstanceEntity,
+    MemoryType,
+    TableType,
+    Value,
+};
+use alloc::{boxed::Box, vec::Vec};
+use core::{fmt, fmt::Display};
+use wasmi_core::{
+    memory_units::{Bytes, Pages},
+    ValueType,
+};
+
+/// The magic bytes at the start of every serialized [`InstanceSnapshot`].
+const MAGIC: [u8; 8] = *b"\0wasmis\0";
+
+/// The version of the binary format of serialized [`InstanceSnapshot`] instances.
+///
+/// # Note
+///
+/// This must be incremented whenever the encoding of serialized [`InstanceSnapshot`] instances changes.
+const FORMAT_VERSION: u32 = 1;
+
+/// An error that may occur upon capturing or restoring an [`InstanceSnapshot`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SnapshotError {
+    /// Occurs when a captured table or global variable holds a reference
+    /// that cannot be represented by an [`InstanceSnapshot`].
+    ///
+    /// # Note
+    ///
+    /// This is the case for non-null `externref` values as well as
+    /// for `funcref` values of functions not known to the [`Instance`].
+    UnsupportedReference,
+    /// Occurs when an [`InstanceSnapshot`] is restored into an [`Instance`]
+    /// of a [`Module`] that differs from the [`Module`] of the captured [`Instance`].
+    ///
+    /// [`Module`]: [`crate::Module`]
+    ModuleMismatch,
+    /// Occurs when serializing an [`InstanceSnapshot`] that captured
+    /// a value of a type that the binary format cannot encode.
+    UnsupportedValueType {
+        /// The [`ValueType`] that cannot be encoded.
+        value_type: ValueType,
+    },
+    /// Occurs when an [`InstanceSnapshot`] has been serialized in another binary format version.
+    IncompatibleFormat {
+        /// The binary format version of the serialized [`InstanceSnapshot`].
+        format_version: u32,
+    },
+    /// Occurs when the bytes do not encode a valid [`InstanceSnapshot`].
+    Malformed,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SnapshotError {}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnsupportedReference => {
+                write!(
+                    f,
+                    "encountered reference that cannot be captured by a snapshot"
+                )
+            }
+            Self::ModuleMismatch => {
+                write!(f, "snapshot does not match the module of the instance")
+            }
+            Self::UnsupportedValueType { value_type } => {
+                write!(f, "cannot serialize snapshot of {} values", value_type)
+            }
+            Self::IncompatibleFormat { format_version } => write!(
+                f,
+                "snapshot has been serialized in format version {} but expected format version {}",
+                format_version, FORMAT_VERSION,
+            ),
+            Self::Malformed => write!(f, "encountered malformed serialized snapshot"),
+        }
+    }
+}
+
+/// A value captured by an [`InstanceSnapshot`].
+#[derive(Debug, Clone)]
+enum CapturedValue {
+    /// A value that does not refer to any entity of the [`Store`].
+    ///
+    /// [`Store`]: [`crate::Store`]
+    Value(Value),
+    /// A non-null `funcref` to the function at the index of the [`Instance`].
+    Func(u32),
+}
+
+impl Encode for CapturedValue {
+    fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::Value(value) => {
+                writer.write_tag(0);
+                value.encode(writer);
+            }
+            Self::Func(index) => {
+                writer.write_tag(1);
+                index.encode(writer);
+            }
+        }
+    }
+}
+
+impl Decode for CapturedValue {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        match reader.read_tag()? {
+            0 => Value::decode(reader).map(Self::Value),
+            1 => u32::decode(reader).map(Self::Func),
+            _ => Err(DeserializeError::malformed("captured value")),
+        }
+    }
+}
+
+impl CapturedValue {
+    /// Captures the `value` of an entity owned by the `instance`.
+    ///
+    /// # Errors
+    ///
+    /// If the `value` cannot be represented by an [`InstanceSnapshot`].
+    fn capture(instance: &InstanceEntity, value: Value) -> Result<Self, SnapshotError> {
+        match value {
+            Value::FuncRef(func_ref) => match func_ref.func() {
+                None => Ok(Self::Value(value)),
+                Some(func) => instance
+                    .get_func_index(*func)
+                    .map(Self::Func)
+                    .ok_or(SnapshotError::UnsupportedReference),
+            },
+            Value::ExternRef(extern_ref) if !extern_ref.is_null() => {
+                Err(SnapshotError::UnsupportedReference)
+            }
+            value => Ok(Self::Value(value)),
+        }
+    }
+
+    /// Resolves the captured value for the `instance` it is restored into.
+    ///
+    /// # Errors
+    ///
+    /// If the captured function index is out of bounds for the `instance`.
+    fn resolve(&self, ctx: impl AsContext, instance: Instance) -> Result<Value, SnapshotError> {
+        match self {
+            Self::Value(value) => Ok(*value),
+            Self::Func(index) => instance
+                .get_func(ctx, *index)
+                .map(|func| Value::FuncRef(FuncRef::new(func)))
+                .ok_or(SnapshotError::ModuleMismatch),
+        }
+    }
+}
+
+/// The captured state of a linear memory.
+#[derive(Debug, Clone)]
+struct MemorySnapshot {
+    /// The type of the linear memory.
+    memory_type: MemoryType,
+    /// The amount of pages in use by the linear memory.
+    pages: Pages,
+    /// The bytes of the linear memory.
+    bytes: Box<[u8]>,
+}
+
+/// Encodes the [`MemorySnapshot`] as its [`MemoryType`] followed by its bytes.
+///
+/// # Note
+///
+/// The amount of pages is not encoded since it is determined by the amount of bytes.
+impl Encode for MemorySnapshot {
+    fn encode(&self, writer: &mut Writer) {
+        self.memory_type.encode(writer);
+        writer.write_len(self.bytes.len());
+        writer.write_bytes(&self.bytes);
+    }
+}
+
+impl Decode for MemorySnapshot {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        let memory_type = MemoryType::decode(reader)?;
+        let len = reader.read_len()?;
+        let bytes = reader.read_bytes(len)?;
+        let bytes_per_page = Bytes::from(Pages(1)).0;
+        if len % bytes_per_page != 0 {
+            return Err(DeserializeError::malformed("memory snapshot"));
+        }
+        Ok(Self {
+            memory_type,
+            pages: Pages(len / bytes_per_page),
+            bytes: bytes.into(),
+        })
+    }
+}
+
+/// The captured state of a table.
+#[derive(Debug, Clone)]
+struct TableSnapshot {
+    /// The type of the table.
+    table_type: TableType,
+    /// The elements of the table.
+    elements: Box<[CapturedValue]>,
+}
+
+impl Encode for TableSnapshot {
+    fn encode(&self, writer: &mut Writer) {
+        self.table_type.encode(writer);
+        self.elements.encode(writer);
+    }
+}
+
+impl Decode for TableSnapshot {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        Ok(Self {
+            table_type: TableType::decode(reader)?,
+            elements: Box::decode(reader)?,
+        })
+    }
+}
+
+/// The captured state of a global variable.
+#[derive(Debug, Clone)]
+struct GlobalSnapshot {
+    /// The type of the global variable.
+    global_type: GlobalType,
+    /// The value of the global variable.
+    value: CapturedValue,
+}
+
+impl Encode for GlobalSnapshot {
+    fn encode(&self, writer: &mut Writer) {
+        self.global_type.encode(writer);
+        self.value.encode(writer);
+    }
+}
+
+impl Decode for GlobalSnapshot {
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        Ok(Self {
+            global_type: GlobalType::decode(reader)?,
+            value: CapturedValue::decode(reader)?,
+        })
+    }
+}
+
+/// A snapshot of the linear memories, tables, global variables and
+/// dropped segments of an [`Instance`].
+///
+/// # Note
+///
+/// - Only entities owned by the [`Instance`] are captured while imported
+///   entities are owned by the host or other instances and thus left out.
+/// - A snapshot does not refer to the [`Store`] of the captured [`Instance`]
+///   and can be restored into instances of the same [`Module`] in any [`Store`].
+/// - A snapshot is identified with its [`Module`] by the hash of the Wasm bytes
+///   of the [`Module`]. Therefore it can be restored into instances of any [`Module`]
+///   compiled from the same Wasm bytes, including [`Module`] instances deserialized
+///   via [`Module::deserialize`] and [`Module`] instances of other processes.
+///
+/// Use [`Instance::snapshot`] to capture and [`InstancePre::restore`] to restore a snapshot.
+/// Use [`InstanceSnapshot::serialize`] and [`InstanceSnapshot::deserialize`] to persist it.
+///
+/// [`Store`]: [`crate::Store`]
+/// [`Module`]: [`crate::Module`]
+/// [`Module::deserialize`]: [`crate::Module::deserialize`]
+/// [`InstancePre::restore`]: [`crate::InstancePre::restore`]
+#[derive(Debug, Clone)]
+pub struct InstanceSnapshot {
+    /// The hash of the module of the captured [`Instance`].
+    module_hash: ModuleHash,
+    /// The amount of functions owned by the captured [`Instance`].
+    len_funcs: usize,
+    memories: Box<[MemorySnapshot]>,
+    tables: Box<[TableSnapshot]>,
+    globals: Box<[GlobalSnapshot]>,
+    /// Whether the data segment at the respective index has been dropped.
+    dropped_data_segments: Box<[bool]>,
+    /// Whether the element segment at the respective index has been dropped.
+    dropped_element_segments: Box<[bool]>,
+}
+
+impl InstanceSnapshot {
+    /// Captures the [`InstanceSnapshot`] of the `instance`.
+    ///
+    /// # Errors
+    ///
+    /// If a table or global variable holds a reference that cannot be captured.
+    pub(crate) fn capture(ctx: impl AsContext, instance: Instance) -> Result<Self, SnapshotError> {
+        let store = ctx.as_context().store;
+        let entity = store.resolve_instance(instance);
+        let module_hash = entity
+            .module_hash()
+            .unwrap_or_else(|| panic!("tried to capture uninitialized instance: {:?}", instance));
+        let owned = entity.owned_entities();
+        let memories = owned
+            .memories
+            .iter()
+            .map(|memory| {
+                let memory = store.resolve_memory(*memory);
+                let pages = memory.current_pages();
+                MemorySnapshot {
+                    memory_type: memory.memory_type(),
+                    pages,
+                    bytes: memory.data()[..Bytes::from(pages).0].into(),
+                }
+            })
+            .collect();
+        let tables = owned
+            .tables
+            .iter()
+            .map(|table| {
+                let table = store.resolve_table(*table);
+                let elements = (0..table.len())
+                    .map(|offset| {
+                        let value = table.get(offset).unwrap_or_else(|error| {
+                            panic!("table element {} is out of bounds: {}", offset, error)
+                        });
+                        CapturedValue::capture(entity, value)
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(TableSnapshot {
+                    table_type: table.table_type(),
+                    elements,
+                })
+            })
+            .collect::<Result<_, SnapshotError>>()?;
+        let globals = owned
+            .globals
+            .iter()
+            .map(|global| {
+                let global = store.resolve_global(*global);
+                Ok(GlobalSnapshot {
+                    global_type: global.global_type(),
+                    value: CapturedValue::capture(entity, global.get())?,
+                })
+            })
+            .collect::<Result<_, SnapshotError>>()?;
+        let dropped_data_segments = entity
+            .data_segments()
+            .iter()
+            .map(DataSegmentEntity::is_dropped)
+            .collect();
+        let dropped_element_segments = entity
+            .element_segments()
+            .iter()
+            .map(ElementSegmentEntity::is_dropped)
+            .collect();
+        Ok(Self {
+            module_hash,
+            len_funcs: owned.funcs.len(),
+            memories,
+            tables,
+            globals,
+            dropped_data_segments,
+            dropped_element_segments,
+        })
+    }
+
+    /// Returns `true` if the data segment at the `index` has been dropped upon capturing.
+    pub(crate) fn is_data_segment_dropped(&self, index: usize) -> bool {
+        self.dropped_data_segments
+            .get(index)
+            .copied()
+            .unwrap_or(true)
+    }
+
+    /// Returns `true` if the element segment at the `index` has been dropped upon capturing.
+    pub(crate) fn is_element_segment_dropped(&self, index: usize) -> bool {
+        self.dropped_element_segments
+            .get(index)
+            .copied()
+            .unwrap_or(true)
+    }
+
+    /// Returns `Ok` if the [`InstanceSnapshot`] matches the `instance`.
+    ///
+    /// # Errors
+    ///
+    /// - If the `instance` has been instantiated from a module compiled from other Wasm bytes.
+    /// - If the owned entities of the `instance` differ in amount or type
+    ///   from the entities captured by the [`InstanceSnapshot`].
+    pub(crate) fn ensure_matches(
+        &self,
+        ctx: impl AsContext,
+        instance: &InstanceEntity,
+    ) -> Result<(), SnapshotError> {
+        if instance.module_hash() != Some(self.module_hash) {
+            return Err(SnapshotError::ModuleMismatch);
+        }
+        let store = ctx.as_context().store;
+        let owned = instance.owned_entities();
+        let memories_match = owned.memories.len() == self.memories.len()
+            && owned
+                .memories
+                .iter()
+                .zip(&self.memories[..])
+                .all(|(memory, snapshot)| {
+                    store.resolve_memory(*memory).memory_type() == snapshot.memory_type
+                });
+        let tables_match = owned.tables.len() == self.tables.len()
+            && owned
+                .tables
+                .iter()
+                .zip(&self.tables[..])
+                .all(|(table, snapshot)| {
+                    store.resolve_table(*table).table_type() == snapshot.table_type
+                });
+        let globals_match = owned.globals.len() == self.globals.len()
+            && owned
+                .globals
+                .iter()
+                .zip(&self.globals[..])
+                .all(|(global, snapshot)| {
+                    store.resolve_global(*global).global_type() == snapshot.global_type
+                });
+        if owned.funcs.len() != self.len_funcs || !memories_match || !tables_match || !globals_match
+        {
+            return Err(SnapshotError::ModuleMismatch);
+        }
+        Ok(())
+    }
+
+    /// Restores the [`InstanceSnapshot`] into the `instance`.
+    ///
+    /// # Note
+    ///
+    /// - Linear memories and tables are grown to their captured sizes if necessary.
+    /// - Immutable global variables keep their values since they are
+    ///   fully determined by the instantiation of the module.
+    /// - The [`InstanceSnapshot`] is required to match the `instance`
+    ///   as checked by [`InstanceSnapshot::ensure_matches`].
+    ///
+    /// # Errors
+    ///
+    /// If growing a linear memory or table fails.
+    pub(crate) fn restore(
+        &self,
+        mut ctx: impl AsContextMut,
+        instance: Instance,
+    ) -> Result<(), Error> {
+        let owned = ctx
+            .as_context()
+            .store
+            .resolve_instance(instance)
+            .owned_entities();
+        let memories = owned.memories.to_vec();
+        let tables = owned.tables.to_vec();
+        let globals = owned.globals.to_vec();
+        for (memory, snapshot) in memories.into_iter().zip(&self.memories[..]) {
+            let current_pages = memory.current_pages(&ctx);
+            if snapshot.pages < current_pages {
+                return Err(SnapshotError::ModuleMismatch.into());
+            }
+            memory.grow(&mut ctx, Pages(snapshot.pages.0 - current_pages.0))?;
+            memory.data_mut(ctx.as_context_mut())[..snapshot.bytes.len()]
+                .copy_from_slice(&snapshot.bytes);
+        }
+        for (table, snapshot) in tables.into_iter().zip(&self.tables[..]) {
+            let current_len = table.len(&ctx);
+            let grow_by = snapshot
+                .elements
+                .len()
+                .checked_sub(current_len)
+                .ok_or(SnapshotError::ModuleMismatch)?;
+            let null = Value::default(snapshot.table_type.element());
+            table.grow(&mut ctx, grow_by, null)?;
+            for (offset, element) in snapshot.elements.iter().enumerate() {
+                let value = element.resolve(&ctx, instance)?;
+                table.set(&mut ctx, offset, value)?;
+            }
+        }
+        for (global, snapshot) in globals.into_iter().zip(&self.globals[..]) {
+            if global.is_mutable(&ctx) {
+                let value = snapshot.value.resolve(&ctx, instance)?;
+                global.set(&mut ctx, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl InstanceSnapshot {
+    /// Serializes the [`InstanceSnapshot`] into a versioned binary format.
+    ///
+    /// # Note
+    ///
+    /// The serialized [`InstanceSnapshot`] can be deserialized via
+    /// [`InstanceSnapshot::deserialize`], for example in another process.
+    ///
+    /// # Errors
+    ///
+    /// If the [`InstanceSnapshot`] captured a [`ValueType`] that the binary format cannot encode.
+    pub fn serialize(&self) -> Result<Vec<u8>, SnapshotError> {
+        let mut writer = Writer::new();
+        writer.write_bytes(&MAGIC);
+        FORMAT_VERSION.encode(&mut writer);
+        self.module_hash.encode(&mut writer);
+        self.len_funcs.encode(&mut writer);
+        self.memories.encode(&mut writer);
+        self.tables.encode(&mut writer);
+        self.globals.encode(&mut writer);
+        self.dropped_data_segments.encode(&mut writer);
+        self.dropped_element_segments.encode(&mut writer);
+        writer.finish().map_err(|error| match error {
+            SerializeError::UnsupportedValueType { value_type } => {
+                SnapshotError::UnsupportedValueType { value_type }
+            }
+        })
+    }
+
+    /// Deserializes an [`InstanceSnapshot`] serialized via [`InstanceSnapshot::serialize`].
+    ///
+    /// # Note
+    ///
+    /// The deserialized [`InstanceSnapshot`] is checked against the instance
+    /// it is restored into the same way as a captured [`InstanceSnapshot`].
+    ///
+    /// # Errors
+    ///
+    /// - If the `bytes` have been serialized in another binary format version.
+    /// - If the `bytes` are malformed.
+    pub fn deserialize(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        let mut reader = Reader::new(bytes);
+        match reader.read_bytes(MAGIC.len()) {
+            Ok(magic) if magic == MAGIC => {}
+            _ => return Err(SnapshotError::Malformed),
+        }
+        let format_version = u32::decode(&mut reader).map_err(|_| SnapshotError::Malformed)?;
+        if format_version != FORMAT_VERSION {
+            return Err(SnapshotError::IncompatibleFormat { format_version });
+        }
+        let snapshot = Self::decode(&mut reader).map_err(|_| SnapshotError::Malformed)?;
+        if !reader.is_empty() {
+            return Err(SnapshotError::Malformed);
+        }
+        Ok(snapshot)
+    }
+
+    /// Decodes the [`InstanceSnapshot`] following its header from the `reader`.
+    fn decode(reader: &mut Reader) -> Result<Self, DeserializeError> {
+        Ok(Self {
+            module_hash: ModuleHash::decode(reader)?,
+            len_funcs: usize::decode(reader)?,
+            memories: Box::decode(reader)?,
+            tables: Box::decode(reader)?,
+            globals: Box::decode(reader)?,
+            dropped_data_segments: Box::decode(reader)?,
+            dropped_element_segments: Box::decode(reader)?,
+        })
+    }
+}
+
+impl Instance {
+    /// Captures an [`InstanceSnapshot`] of the linear memories, tables and
+    /// global variables owned by the [`Instance`].
+    ///
+    /// # Errors
+    ///
+    /// If a table or global variable of the [`Instance`] holds a non-null `externref`
+    /// or a `funcref` to a function that is not known to the [`Instance`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Instance`].
+    pub fn snapshot(&self, ctx: impl AsContext) -> Result<InstanceSnapshot, SnapshotError> {
+        InstanceSnapshot::capture(ctx, *self)
+    }
+}
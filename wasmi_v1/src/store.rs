@@ -1,7 +1,11 @@
 use super::{
-    arena::Arena,
-    engine::DedupFuncType,
+    arena::{Arena, MAX_ENCODED_GUARD},
+    engine::{CallDepth, DedupFuncType},
+    extensions::Extensions,
     Engine,
+    ExternObject,
+    ExternObjectEntity,
+    ExternObjectIdx,
     Func,
     FuncEntity,
     FuncIdx,
@@ -18,15 +22,34 @@ use super::{
     Table,
     TableEntity,
     TableIdx,
+    Tag,
+    TagEntity,
+    TagIdx,
+};
+use crate::{
+    core::{Trap, TrapCode},
+    errors::InstantiationError,
+    ArenaIndex,
+    GuardedEntity,
+    Index,
+    ResourceLimiter,
+};
+use alloc::{boxed::Box, collections::BTreeMap};
+use core::{
+    fmt,
+    fmt::Display,
+    sync::atomic::{AtomicUsize, Ordering},
 };
-use crate::{GuardedEntity, Index};
-use core::sync::atomic::{AtomicUsize, Ordering};
 
-/// A unique store index.
+/// A store index.
 ///
 /// # Note
 ///
 /// Used to protect against invalid entity indices.
+///
+/// Store indices are never reused so that entities of different stores
+/// can always be told apart. Since they are part of references encoded as
+/// untyped values at most [`MAX_ENCODED_GUARD`] stores can be created.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct StoreIdx(usize);
 
@@ -41,11 +64,19 @@ impl Index for StoreIdx {
 }
 
 impl StoreIdx {
-    /// Returns a new unique [`StoreIdx`].
+    /// Returns a new [`StoreIdx`].
+    ///
+    /// # Panics
+    ///
+    /// If more than [`MAX_ENCODED_GUARD`] store indices have been created.
     fn new() -> Self {
         /// A static store index counter.
         static CURRENT_STORE_IDX: AtomicUsize = AtomicUsize::new(0);
-        let next_idx = CURRENT_STORE_IDX.fetch_add(1, Ordering::AcqRel);
+        let next_idx = CURRENT_STORE_IDX
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |idx| {
+                (idx <= MAX_ENCODED_GUARD).then_some(idx + 1)
+            })
+            .unwrap_or_else(|_| panic!("ran out of store indices"));
         Self(next_idx)
     }
 }
@@ -53,6 +84,170 @@ impl StoreIdx {
 /// A stored entity.
 pub type Stored<Idx> = GuardedEntity<StoreIdx, Idx>;
 
+/// An error that may occur upon operating on the fuel of a [`Store`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FuelError {
+    /// Occurs when using fuel APIs while fuel metering is disabled.
+    FuelMeteringDisabled,
+    /// Occurs when trying to consume more fuel than there is remaining.
+    OutOfFuel,
+}
+
+impl Display for FuelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::FuelMeteringDisabled => write!(f, "fuel metering is disabled"),
+            Self::OutOfFuel => write!(f, "all fuel consumed"),
+        }
+    }
+}
+
+/// An error that may occur upon removing an [`Instance`] from a [`Store`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RemoveInstanceError {
+    /// Occurs when other instances still import entities owned by the removed instance.
+    ImportedByOtherInstance,
+}
+
+impl Display for RemoveInstanceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ImportedByOtherInstance => {
+                write!(f, "instance entities are still imported by other instances")
+            }
+        }
+    }
+}
+
+/// The fuel of a [`Store`] used to meter Wasm executions.
+#[derive(Debug, Copy, Clone)]
+pub struct Fuel {
+    /// Is `true` if fuel metering is enabled for the [`Store`].
+    enabled: bool,
+    /// The remaining amount of fuel.
+    remaining: u64,
+    /// The amount of fuel consumed so far.
+    consumed: u64,
+}
+
+impl Fuel {
+    /// Creates a new [`Fuel`] without any remaining fuel.
+    fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            remaining: 0,
+            consumed: 0,
+        }
+    }
+
+    /// Returns `true` if fuel metering is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns `Ok` if fuel metering is enabled.
+    ///
+    /// # Errors
+    ///
+    /// If fuel metering is disabled.
+    fn ensure_enabled(&self) -> Result<(), FuelError> {
+        if !self.enabled {
+            return Err(FuelError::FuelMeteringDisabled);
+        }
+        Ok(())
+    }
+
+    /// Adds `delta` units of fuel.
+    ///
+    /// # Errors
+    ///
+    /// If fuel metering is disabled.
+    fn add(&mut self, delta: u64) -> Result<(), FuelError> {
+        self.ensure_enabled()?;
+        self.remaining = self.remaining.saturating_add(delta);
+        Ok(())
+    }
+
+    /// Consumes `delta` units of fuel and returns the remaining fuel.
+    ///
+    /// # Note
+    ///
+    /// No fuel is consumed if there is not enough fuel remaining.
+    ///
+    /// # Errors
+    ///
+    /// - If fuel metering is disabled.
+    /// - If there is less than `delta` fuel remaining.
+    pub fn consume(&mut self, delta: u64) -> Result<u64, FuelError> {
+        self.ensure_enabled()?;
+        self.remaining = self
+            .remaining
+            .checked_sub(delta)
+            .ok_or(FuelError::OutOfFuel)?;
+        self.consumed = self.consumed.saturating_add(delta);
+        Ok(self.remaining)
+    }
+}
+
+/// The callback invoked once the epoch deadline of a [`Store`] has been reached.
+///
+/// Returns the number of epoch ticks to extend the deadline by or a [`Trap`]
+/// to interrupt the execution.
+type EpochDeadlineCallback<T> =
+    Box<dyn FnMut(StoreContextMut<T>) -> Result<u64, Trap> + Send + Sync + 'static>;
+
+/// The epoch deadline of a [`Store`] used to interrupt Wasm executions.
+struct EpochDeadline<T> {
+    /// Is `true` if epoch interruption is enabled for the [`Store`].
+    enabled: bool,
+    /// The epoch at which Wasm executions are interrupted.
+    deadline: u64,
+    /// The optional callback invoked once the deadline has been reached.
+    ///
+    /// Wasm executions trap with [`TrapCode::Interrupted`] if this is `None`.
+    callback: Option<EpochDeadlineCallback<T>>,
+}
+
+impl<T> fmt::Debug for EpochDeadline<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EpochDeadline")
+            .field("enabled", &self.enabled)
+            .field("deadline", &self.deadline)
+            .field("callback", &self.callback.is_some())
+            .finish()
+    }
+}
+
+impl<T> EpochDeadline<T> {
+    /// Creates a new [`EpochDeadline`] that is never reached.
+    fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            deadline: u64::MAX,
+            callback: None,
+        }
+    }
+}
+
+/// Returns the [`ResourceLimiter`] of a [`Store`] given its user provided state.
+type ResourceLimiterQuery<T> =
+    Box<dyn FnMut(&mut T) -> &mut (dyn ResourceLimiter) + Send + Sync + 'static>;
+
+/// The optional [`ResourceLimiter`] of a [`Store`].
+struct StoreLimiter<T> {
+    query: Option<ResourceLimiterQuery<T>>,
+}
+
+impl<T> fmt::Debug for StoreLimiter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("StoreLimiter")
+            .field("query", &self.query.is_some())
+            .finish()
+    }
+}
+
 /// The store that owns all data associated to Wasm modules.
 #[derive(Debug)]
 pub struct Store<T> {
@@ -66,29 +261,73 @@ pub struct Store<T> {
     tables: Arena<TableIdx, TableEntity>,
     /// Stored global variables.
     globals: Arena<GlobalIdx, GlobalEntity>,
+    /// Stored exception tags.
+    tags: Arena<TagIdx, TagEntity>,
     /// Stored Wasm or host functions.
     funcs: Arena<FuncIdx, FuncEntity<T>>,
     /// Stored module instances.
     instances: Arena<InstanceIdx, InstanceEntity>,
+    /// Stored host data referenced by [`ExternRef`] values.
+    ///
+    /// [`ExternRef`]: [`crate::ExternRef`]
+    extern_objects: Arena<ExternObjectIdx, ExternObjectEntity>,
+    /// Host functions of [`Linker`] definitions that have been allocated in the [`Store`].
+    ///
+    /// Keyed by the address of their trampoline which is kept alive by the function entity.
+    ///
+    /// [`Linker`]: [`crate::Linker`]
+    linker_funcs: BTreeMap<usize, Func>,
+    /// Linear memories of shared [`Linker`] definitions that have been allocated in the [`Store`].
+    ///
+    /// Keyed by the address of their shared memory which is kept alive by the memory entity.
+    ///
+    /// [`Linker`]: [`crate::Linker`]
+    #[cfg(feature = "threads")]
+    linker_memories: BTreeMap<usize, Memory>,
     /// The [`Engine`] in use by the [`Store`].
     ///
     /// Amongst others the [`Engine`] stores the Wasm function definitions.
     engine: Engine,
+    /// The fuel used to meter Wasm executions if enabled.
+    fuel: Fuel,
+    /// The epoch deadline used to interrupt Wasm executions if enabled.
+    epoch_deadline: EpochDeadline<T>,
+    /// The call depth of the Wasm executions in progress.
+    call_depth: CallDepth,
+    /// Host state indexed by type that is independent of the user provided state.
+    extensions: Extensions,
+    /// The resource limiter consulted upon instantiation and growth operations if any.
+    limiter: StoreLimiter<T>,
     /// User provided state.
     user_state: T,
 }
 
 impl<T> Store<T> {
     /// Creates a new store.
+    ///
+    /// # Panics
+    ///
+    /// If the process has run out of store indices which happens after
+    /// creating about 4 billion stores.
     pub fn new(engine: &Engine, user_state: T) -> Self {
         Self {
             store_idx: StoreIdx::new(),
             memories: Arena::new(),
             tables: Arena::new(),
             globals: Arena::new(),
+            tags: Arena::new(),
             funcs: Arena::new(),
             instances: Arena::new(),
+            extern_objects: Arena::new(),
+            linker_funcs: BTreeMap::new(),
+            #[cfg(feature = "threads")]
+            linker_memories: BTreeMap::new(),
             engine: engine.clone(),
+            fuel: Fuel::new(engine.config().fuel_metering()),
+            epoch_deadline: EpochDeadline::new(engine.config().epoch_interruption()),
+            call_depth: CallDepth::default(),
+            extensions: Extensions::default(),
+            limiter: StoreLimiter { query: None },
             user_state,
         }
     }
@@ -113,6 +352,345 @@ impl<T> Store<T> {
         self.user_state
     }
 
+    /// Inserts the extension `value` of type `U` into the [`Store`].
+    ///
+    /// Returns the previous extension of type `U` if any.
+    ///
+    /// # Note
+    ///
+    /// Extensions are slots of host state indexed by their type that are
+    /// independent of the user provided state `T`. This allows host functions
+    /// to keep their own mutable state in the [`Store`] and access it via
+    /// [`Caller::extension_mut`] upon every call.
+    ///
+    /// [`Caller::extension_mut`]: crate::Caller::extension_mut
+    pub fn insert_extension<U>(&mut self, value: U) -> Option<U>
+    where
+        U: Send + Sync + 'static,
+    {
+        self.extensions.insert(value)
+    }
+
+    /// Returns a shared reference to the extension of type `U` if any.
+    pub fn extension<U>(&self) -> Option<&U>
+    where
+        U: Send + Sync + 'static,
+    {
+        self.extensions.get()
+    }
+
+    /// Returns an exclusive reference to the extension of type `U` if any.
+    pub fn extension_mut<U>(&mut self) -> Option<&mut U>
+    where
+        U: Send + Sync + 'static,
+    {
+        self.extensions.get_mut()
+    }
+
+    /// Removes and returns the extension of type `U` from the [`Store`] if any.
+    pub fn remove_extension<U>(&mut self) -> Option<U>
+    where
+        U: Send + Sync + 'static,
+    {
+        self.extensions.remove()
+    }
+
+    /// Adds `delta` units of fuel to the [`Store`].
+    ///
+    /// # Errors
+    ///
+    /// If fuel metering is disabled for the [`Engine`] of the [`Store`].
+    pub fn add_fuel(&mut self, delta: u64) -> Result<(), FuelError> {
+        self.fuel.add(delta)
+    }
+
+    /// Returns the amount of fuel consumed by executions of the [`Store`] so far.
+    ///
+    /// Returns `None` if fuel metering is disabled.
+    pub fn fuel_consumed(&self) -> Option<u64> {
+        self.fuel.ensure_enabled().ok()?;
+        Some(self.fuel.consumed)
+    }
+
+    /// Returns the amount of fuel remaining in the [`Store`].
+    ///
+    /// Returns `None` if fuel metering is disabled.
+    pub fn remaining_fuel(&self) -> Option<u64> {
+        self.fuel.ensure_enabled().ok()?;
+        Some(self.fuel.remaining)
+    }
+
+    /// Consumes `delta` units of fuel from the [`Store`] and returns the remaining fuel.
+    ///
+    /// # Note
+    ///
+    /// No fuel is consumed if there is not enough fuel remaining.
+    ///
+    /// # Errors
+    ///
+    /// - If fuel metering is disabled for the [`Engine`] of the [`Store`].
+    /// - If there is less than `delta` fuel remaining.
+    pub fn consume_fuel(&mut self, delta: u64) -> Result<u64, FuelError> {
+        self.fuel.consume(delta)
+    }
+
+    /// Returns an exclusive reference to the [`Fuel`] of the [`Store`].
+    pub(super) fn fuel_mut(&mut self) -> &mut Fuel {
+        &mut self.fuel
+    }
+
+    /// Returns the [`CallDepth`] of the Wasm executions of the [`Store`] in progress.
+    pub(super) fn call_depth(&self) -> CallDepth {
+        self.call_depth
+    }
+
+    /// Returns an exclusive reference to the [`CallDepth`] of the [`Store`].
+    pub(super) fn call_depth_mut(&mut self) -> &mut CallDepth {
+        &mut self.call_depth
+    }
+
+    /// Sets the epoch deadline of the [`Store`] to `ticks_beyond_current`
+    /// epochs after the current epoch of its [`Engine`].
+    ///
+    /// # Note
+    ///
+    /// - Wasm executions of the [`Store`] are interrupted once the epoch
+    ///   of the [`Engine`] reaches the deadline.
+    /// - Without a deadline Wasm executions are never interrupted.
+    /// - This has no effect if epoch interruption is disabled.
+    pub fn set_epoch_deadline(&mut self, ticks_beyond_current: u64) {
+        let current = self.engine.current_epoch();
+        self.epoch_deadline.deadline = current.saturating_add(ticks_beyond_current);
+    }
+
+    /// Configures the [`Store`] to trap with [`TrapCode::Interrupted`]
+    /// once its epoch deadline has been reached.
+    ///
+    /// # Note
+    ///
+    /// This is the default behavior and removes any previously set
+    /// epoch deadline callback.
+    pub fn epoch_deadline_trap(&mut self) {
+        self.epoch_deadline.callback = None;
+    }
+
+    /// Configures the [`Store`] to call `callback` once its epoch deadline
+    /// has been reached.
+    ///
+    /// # Note
+    ///
+    /// The `callback` either returns the number of epoch ticks beyond the
+    /// current epoch the deadline is extended by, after which the execution
+    /// continues, or a [`Trap`] that interrupts the execution.
+    pub fn epoch_deadline_callback<F>(&mut self, callback: F)
+    where
+        F: FnMut(StoreContextMut<T>) -> Result<u64, Trap> + Send + Sync + 'static,
+    {
+        self.epoch_deadline.callback = Some(Box::new(callback));
+    }
+
+    /// Returns `true` if epoch interruption is enabled for the [`Store`].
+    pub(super) fn is_epoch_interruption_enabled(&self) -> bool {
+        self.epoch_deadline.enabled
+    }
+
+    /// Returns `true` if the epoch deadline of the [`Store`] has been reached.
+    pub(super) fn is_epoch_deadline_reached(&self) -> bool {
+        self.engine.current_epoch() >= self.epoch_deadline.deadline
+    }
+
+    /// Checks if the epoch deadline of the [`Store`] has been reached.
+    ///
+    /// # Note
+    ///
+    /// If the deadline has been reached the epoch deadline callback is
+    /// invoked to extend the deadline if there is one.
+    ///
+    /// # Errors
+    ///
+    /// - If the deadline has been reached and there is no callback.
+    /// - If the epoch deadline callback returned a [`Trap`].
+    pub(super) fn check_epoch_deadline(&mut self) -> Result<(), Trap> {
+        if !self.is_epoch_deadline_reached() {
+            return Ok(());
+        }
+        let mut callback = match self.epoch_deadline.callback.take() {
+            Some(callback) => callback,
+            None => return Err(TrapCode::Interrupted.into()),
+        };
+        let result = callback(StoreContextMut { store: self });
+        // The callback may have installed a new callback in the meantime.
+        if self.epoch_deadline.callback.is_none() {
+            self.epoch_deadline.callback = Some(callback);
+        }
+        let delta = result?;
+        self.set_epoch_deadline(delta);
+        Ok(())
+    }
+
+    /// Installs the [`ResourceLimiter`] returned by `limiter` for the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// The `limiter` closure receives the user provided state `T` so that the
+    /// [`ResourceLimiter`] can be stored within it. The [`Store`] consults the
+    /// [`ResourceLimiter`] upon instantiation as well as upon memory and table growth.
+    pub fn limiter<F>(&mut self, limiter: F)
+    where
+        F: FnMut(&mut T) -> &mut dyn ResourceLimiter,
+        F: Send + Sync + 'static,
+    {
+        self.limiter.query = Some(Box::new(limiter));
+    }
+
+    /// Returns the [`ResourceLimiter`] of the [`Store`] if any.
+    fn resource_limiter(&mut self) -> Option<&mut dyn ResourceLimiter> {
+        let query = self.limiter.query.as_mut()?;
+        Some(query(&mut self.user_state))
+    }
+
+    /// Returns `true` if a linear memory may grow from `current` to `desired` bytes.
+    ///
+    /// Always returns `true` if the [`Store`] has no [`ResourceLimiter`].
+    pub(super) fn memory_growing(
+        &mut self,
+        current: usize,
+        desired: usize,
+        maximum: Option<usize>,
+    ) -> bool {
+        match self.resource_limiter() {
+            Some(limiter) => limiter.memory_growing(current, desired, maximum),
+            None => true,
+        }
+    }
+
+    /// Returns `true` if a table may grow from `current` to `desired` elements.
+    ///
+    /// Always returns `true` if the [`Store`] has no [`ResourceLimiter`].
+    pub(super) fn table_growing(
+        &mut self,
+        current: usize,
+        desired: usize,
+        maximum: Option<usize>,
+    ) -> bool {
+        match self.resource_limiter() {
+            Some(limiter) => limiter.table_growing(current, desired, maximum),
+            None => true,
+        }
+    }
+
+    /// Returns `true` if the [`ResourceLimiter`] permits another table in the [`Store`].
+    ///
+    /// Always returns `true` if the [`Store`] has no [`ResourceLimiter`].
+    pub(super) fn can_create_table(&mut self) -> bool {
+        let len_tables = self.tables.len();
+        match self.resource_limiter() {
+            Some(limiter) => len_tables < limiter.tables(),
+            None => true,
+        }
+    }
+
+    /// Returns `true` if the [`ResourceLimiter`] permits another linear memory in the [`Store`].
+    ///
+    /// Always returns `true` if the [`Store`] has no [`ResourceLimiter`].
+    pub(super) fn can_create_memory(&mut self) -> bool {
+        let len_memories = self.memories.len();
+        match self.resource_limiter() {
+            Some(limiter) => len_memories < limiter.memories(),
+            None => true,
+        }
+    }
+
+    /// Returns `true` if the [`ResourceLimiter`] permits another global variable in the [`Store`].
+    ///
+    /// Always returns `true` if the [`Store`] has no [`ResourceLimiter`].
+    pub(super) fn can_create_global(&mut self) -> bool {
+        let len_globals = self.globals.len();
+        match self.resource_limiter() {
+            Some(limiter) => len_globals < limiter.globals(),
+            None => true,
+        }
+    }
+
+    /// Returns `true` if growth operations denied by the [`ResourceLimiter`] trap.
+    pub(super) fn trap_on_grow_failure(&mut self) -> bool {
+        match self.resource_limiter() {
+            Some(limiter) => limiter.trap_on_grow_failure(),
+            None => false,
+        }
+    }
+
+    /// Checks if a new instance with the given amount of tables, linear memories
+    /// and global variables fits into the limits of the [`ResourceLimiter`].
+    ///
+    /// # Errors
+    ///
+    /// If any of the resulting amounts exceeds its limit.
+    pub(super) fn check_instantiation_limits(
+        &mut self,
+        tables: usize,
+        memories: usize,
+        globals: usize,
+    ) -> Result<(), InstantiationError> {
+        let (len_instances, len_tables, len_memories, len_globals) = (
+            self.instances.len(),
+            self.tables.len(),
+            self.memories.len(),
+            self.globals.len(),
+        );
+        let limiter = match self.resource_limiter() {
+            Some(limiter) => limiter,
+            None => return Ok(()),
+        };
+        let exceeds = |current: usize, additional: usize, limit: usize| {
+            current.saturating_add(additional) > limit
+        };
+        let resource = if exceeds(len_instances, 1, limiter.instances()) {
+            "instances"
+        } else if exceeds(len_tables, tables, limiter.tables()) {
+            "tables"
+        } else if exceeds(len_memories, memories, limiter.memories()) {
+            "memories"
+        } else if exceeds(len_globals, globals, limiter.globals()) {
+            "globals"
+        } else {
+            return Ok(());
+        };
+        Err(InstantiationError::ResourceLimitExceeded { resource })
+    }
+
+    /// Checks if the store has enough vacant slots for a new instance with the given
+    /// amount of functions, tables, linear memories, global variables and exception tags.
+    ///
+    /// # Errors
+    ///
+    /// If the store has run out of slots for any of the entities.
+    pub(super) fn check_instantiation_slots(
+        &self,
+        funcs: usize,
+        tables: usize,
+        memories: usize,
+        globals: usize,
+        tags: usize,
+    ) -> Result<(), InstantiationError> {
+        let resource = if !self.instances.can_alloc(1) {
+            "instances"
+        } else if !self.funcs.can_alloc(funcs) {
+            "funcs"
+        } else if !self.tables.can_alloc(tables) {
+            "tables"
+        } else if !self.memories.can_alloc(memories) {
+            "memories"
+        } else if !self.globals.can_alloc(globals) {
+            "globals"
+        } else if !self.tags.can_alloc(tags) {
+            "tags"
+        } else {
+            return Ok(());
+        };
+        Err(InstantiationError::OutOfSlots { resource })
+    }
+
     /// Allocates a new function type to the store.
     pub(super) fn alloc_func_type(&mut self, func_type: FuncType) -> DedupFuncType {
         self.engine.alloc_func_type(func_type)
@@ -123,6 +701,11 @@ impl<T> Store<T> {
         Global::from_inner(Stored::new(self.store_idx, self.globals.alloc(global)))
     }
 
+    /// Allocates a new exception tag to the store.
+    pub(super) fn alloc_tag(&mut self, tag: TagEntity) -> Tag {
+        Tag::from_inner(Stored::new(self.store_idx, self.tags.alloc(tag)))
+    }
+
     /// Allocates a new table to the store.
     pub(super) fn alloc_table(&mut self, table: TableEntity) -> Table {
         Table::from_inner(Stored::new(self.store_idx, self.tables.alloc(table)))
@@ -138,6 +721,58 @@ impl<T> Store<T> {
         Func::from_inner(Stored::new(self.store_idx, self.funcs.alloc(func)))
     }
 
+    /// Returns the host function allocated for the [`Linker`] definition with the `key` if any.
+    ///
+    /// [`Linker`]: [`crate::Linker`]
+    pub(super) fn get_linker_func(&self, key: usize) -> Option<Func> {
+        self.linker_funcs.get(&key).copied()
+    }
+
+    /// Remembers the host function allocated for the [`Linker`] definition with the `key`.
+    ///
+    /// [`Linker`]: [`crate::Linker`]
+    pub(super) fn insert_linker_func(&mut self, key: usize, func: Func) {
+        self.linker_funcs.insert(key, func);
+    }
+
+    /// Returns the linear memory allocated for the shared [`Linker`] definition with the `key` if any.
+    ///
+    /// [`Linker`]: [`crate::Linker`]
+    #[cfg(feature = "threads")]
+    pub(super) fn get_linker_memory(&self, key: usize) -> Option<Memory> {
+        self.linker_memories.get(&key).copied()
+    }
+
+    /// Remembers the linear memory allocated for the shared [`Linker`] definition with the `key`.
+    ///
+    /// [`Linker`]: [`crate::Linker`]
+    #[cfg(feature = "threads")]
+    pub(super) fn insert_linker_memory(&mut self, key: usize, memory: Memory) {
+        self.linker_memories.insert(key, memory);
+    }
+
+    /// Returns the amount of linear memories allocated in the store.
+    #[cfg(all(test, feature = "threads"))]
+    pub(crate) fn len_memories(&self) -> usize {
+        self.memories.len()
+    }
+
+    /// Returns the amount of host functions allocated for [`Linker`] definitions.
+    ///
+    /// [`Linker`]: [`crate::Linker`]
+    #[cfg(test)]
+    pub(crate) fn len_linker_funcs(&self) -> usize {
+        self.linker_funcs.len()
+    }
+
+    /// Allocates a new external object to the store.
+    pub(super) fn alloc_extern_object(&mut self, object: ExternObjectEntity) -> ExternObject {
+        ExternObject::from_inner(Stored::new(
+            self.store_idx,
+            self.extern_objects.alloc(object),
+        ))
+    }
+
     /// Allocates a new [`Instance`] to the store.
     ///
     /// # Note
@@ -185,6 +820,72 @@ impl<T> Store<T> {
         *entity = initialized;
     }
 
+    /// Removes the `instance` and all entities owned by it from the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// - The owned entities are the functions, tables, linear memories, global
+    ///   variables and exception tags that have been created upon instantiation.
+    ///   Imported entities are not owned by the `instance` and are kept.
+    /// - The Wasm function bodies of the module of the `instance` are freed from
+    ///   the [`Engine`] once the [`Module`] and all of its instances are dropped.
+    /// - Using the `instance` or any of its owned entities afterwards panics.
+    ///   Wasm calls to removed functions trap with [`TrapCode::DanglingFunc`]
+    ///   instead since functions may still be referenced by other instances,
+    ///   e.g. by importing them or via their tables.
+    /// - The slots of removed entities are reused for later entities. Handles
+    ///   of removed entities never refer to those later entities.
+    ///
+    /// # Errors
+    ///
+    /// If other instances still import any of the tables, linear memories, global
+    /// variables or exception tags owned by the `instance`. Those instances must be
+    /// removed first. The `instance` is kept in this case.
+    ///
+    /// # Panics
+    ///
+    /// If the `instance` does not originate from this [`Store`] or has already been removed.
+    ///
+    /// [`Module`]: [`crate::Module`]
+    pub fn remove_instance(&mut self, instance: Instance) -> Result<(), RemoveInstanceError> {
+        let entity_index = self.unwrap_index(instance.into_inner());
+        let entity = self.instances.get(entity_index).unwrap_or_else(|| {
+            panic!(
+                "failed to resolve stored module instance: {:?}",
+                entity_index,
+            )
+        });
+        let owned = entity.owned_entities();
+        let is_imported = self
+            .instances
+            .iter()
+            .any(|(index, other)| index != entity_index && other.imports_any(&owned));
+        if is_imported {
+            return Err(RemoveInstanceError::ImportedByOtherInstance);
+        }
+        let entity = self
+            .instances
+            .remove(entity_index)
+            .expect("the instance has been resolved above");
+        let owned = entity.owned_entities();
+        for func in owned.funcs {
+            self.funcs.remove(self.unwrap_index(func.into_inner()));
+        }
+        for table in owned.tables {
+            self.tables.remove(self.unwrap_index(table.into_inner()));
+        }
+        for memory in owned.memories {
+            self.memories.remove(self.unwrap_index(memory.into_inner()));
+        }
+        for global in owned.globals {
+            self.globals.remove(self.unwrap_index(global.into_inner()));
+        }
+        for tag in owned.tags {
+            self.tags.remove(self.unwrap_index(tag.into_inner()));
+        }
+        Ok(())
+    }
+
     /// Unpacks and checks the stored entity index.
     ///
     /// # Panics
@@ -192,7 +893,7 @@ impl<T> Store<T> {
     /// If the stored entity does not originate from this store.
     fn unwrap_index<Idx>(&self, stored: Stored<Idx>) -> Idx
     where
-        Idx: Index,
+        Idx: ArenaIndex,
     {
         stored.entity_index(self.store_idx).unwrap_or_else(|| {
             panic!(
@@ -244,6 +945,19 @@ impl<T> Store<T> {
         })
     }
 
+    /// Returns a shared reference to the associated entity of the exception tag.
+    ///
+    /// # Panics
+    ///
+    /// - If the exception tag does not originate from this store.
+    /// - If the exception tag cannot be resolved to its entity.
+    pub(super) fn resolve_tag(&self, tag: Tag) -> &TagEntity {
+        let entity_index = self.unwrap_index(tag.into_inner());
+        self.tags
+            .get(entity_index)
+            .unwrap_or_else(|| panic!("failed to resolve stored exception tag: {:?}", entity_index))
+    }
+
     /// Returns a shared reference to the associated entity of the table.
     ///
     /// # Panics
@@ -270,6 +984,54 @@ impl<T> Store<T> {
             .unwrap_or_else(|| panic!("failed to resolve stored table: {:?}", entity_index))
     }
 
+    /// Returns an exclusive reference to the associated entities of the pair of tables.
+    ///
+    /// # Panics
+    ///
+    /// - If the tables do not originate from this store.
+    /// - If the tables cannot be resolved to their entities.
+    /// - If `fst` and `snd` refer to the same table.
+    pub(super) fn resolve_table_pair_mut(
+        &mut self,
+        fst: Table,
+        snd: Table,
+    ) -> (&mut TableEntity, &mut TableEntity) {
+        let fst_index = self.unwrap_index(fst.into_inner());
+        let snd_index = self.unwrap_index(snd.into_inner());
+        self.tables
+            .get_pair_mut(fst_index, snd_index)
+            .unwrap_or_else(|| {
+                panic!(
+                    "failed to resolve stored pair of tables: {:?} and {:?}",
+                    fst_index, snd_index,
+                )
+            })
+    }
+
+    /// Returns an exclusive reference to the associated entities of the pair of linear memories.
+    ///
+    /// # Panics
+    ///
+    /// - If the linear memories do not originate from this store.
+    /// - If the linear memories cannot be resolved to their entities.
+    /// - If `fst` and `snd` refer to the same linear memory.
+    pub(super) fn resolve_memory_pair_mut(
+        &mut self,
+        fst: Memory,
+        snd: Memory,
+    ) -> (&mut MemoryEntity, &mut MemoryEntity) {
+        let fst_index = self.unwrap_index(fst.into_inner());
+        let snd_index = self.unwrap_index(snd.into_inner());
+        self.memories
+            .get_pair_mut(fst_index, snd_index)
+            .unwrap_or_else(|| {
+                panic!(
+                    "failed to resolve stored pair of linear memories: {:?} and {:?}",
+                    fst_index, snd_index,
+                )
+            })
+    }
+
     /// Returns a shared reference to the associated entity of the linear memory.
     ///
     /// # Panics
@@ -312,6 +1074,34 @@ impl<T> Store<T> {
         })
     }
 
+    /// Returns a shared reference to the associated entity of the Wasm or host function.
+    ///
+    /// Returns `None` if the Wasm or host function has been removed from the store.
+    ///
+    /// # Panics
+    ///
+    /// If the Wasm or host function does not originate from this store.
+    pub(super) fn try_resolve_func(&self, func: Func) -> Option<&FuncEntity<T>> {
+        let entity_index = self.unwrap_index(func.into_inner());
+        self.funcs.get(entity_index)
+    }
+
+    /// Returns a shared reference to the associated entity of the external object.
+    ///
+    /// # Panics
+    ///
+    /// - If the external object does not originate from this store.
+    /// - If the external object cannot be resolved to its entity.
+    pub(super) fn resolve_extern_object(&self, object: ExternObject) -> &ExternObjectEntity {
+        let entity_index = self.unwrap_index(object.into_inner());
+        self.extern_objects.get(entity_index).unwrap_or_else(|| {
+            panic!(
+                "failed to resolve stored external object: {:?}",
+                entity_index
+            )
+        })
+    }
+
     /// Returns a shared reference to the associated entity of the [`Instance`].
     ///
     /// # Panics
@@ -327,6 +1117,83 @@ impl<T> Store<T> {
             )
         })
     }
+
+    /// Returns an exclusive reference to the associated entity of the [`Instance`].
+    ///
+    /// # Panics
+    ///
+    /// - If the [`Instance`] does not originate from this store.
+    /// - If the [`Instance`] cannot be resolved to its entity.
+    pub(super) fn resolve_instance_mut(&mut self, instance: Instance) -> &mut InstanceEntity {
+        let entity_index = self.unwrap_index(instance.into_inner());
+        self.instances.get_mut(entity_index).unwrap_or_else(|| {
+            panic!(
+                "failed to resolve stored module instance: {:?}",
+                entity_index
+            )
+        })
+    }
+
+    /// Returns a shared reference to the [`Instance`] entity and an exclusive
+    /// reference to the linear memory entity at the same time.
+    ///
+    /// # Note
+    ///
+    /// This is required to copy data segments of an [`Instance`] into a linear memory.
+    ///
+    /// # Panics
+    ///
+    /// - If the [`Instance`] or linear memory do not originate from this store.
+    /// - If the [`Instance`] or linear memory cannot be resolved to their entities.
+    pub(super) fn resolve_instance_and_memory_mut(
+        &mut self,
+        instance: Instance,
+        memory: Memory,
+    ) -> (&InstanceEntity, &mut MemoryEntity) {
+        let instance_index = self.unwrap_index(instance.into_inner());
+        let memory_index = self.unwrap_index(memory.into_inner());
+        let instance = self.instances.get(instance_index).unwrap_or_else(|| {
+            panic!(
+                "failed to resolve stored module instance: {:?}",
+                instance_index
+            )
+        });
+        let memory = self.memories.get_mut(memory_index).unwrap_or_else(|| {
+            panic!("failed to resolve stored linear memory: {:?}", memory_index)
+        });
+        (instance, memory)
+    }
+
+    /// Returns a shared reference to the [`Instance`] entity and an exclusive
+    /// reference to the table entity at the same time.
+    ///
+    /// # Note
+    ///
+    /// This is required to copy element segments of an [`Instance`] into a table.
+    ///
+    /// # Panics
+    ///
+    /// - If the [`Instance`] or table do not originate from this store.
+    /// - If the [`Instance`] or table cannot be resolved to their entities.
+    pub(super) fn resolve_instance_and_table_mut(
+        &mut self,
+        instance: Instance,
+        table: Table,
+    ) -> (&InstanceEntity, &mut TableEntity) {
+        let instance_index = self.unwrap_index(instance.into_inner());
+        let table_index = self.unwrap_index(table.into_inner());
+        let instance = self.instances.get(instance_index).unwrap_or_else(|| {
+            panic!(
+                "failed to resolve stored module instance: {:?}",
+                instance_index
+            )
+        });
+        let table = self
+            .tables
+            .get_mut(table_index)
+            .unwrap_or_else(|| panic!("failed to resolve stored table: {:?}", table_index));
+        (instance, table)
+    }
 }
 
 /// A trait used to get shared access to a [`Store`] in `wasmi`.
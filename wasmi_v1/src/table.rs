@@ -1,20 +1,21 @@
 #![allow(clippy::len_without_is_empty)]
 
-use super::{AsContext, AsContextMut, Func, Index, Stored};
+use super::{ArenaIndex, AsContext, AsContextMut, Stored, Value};
 use alloc::vec::Vec;
 use core::{fmt, fmt::Display};
+use wasmi_core::{UntypedValue, ValueType};
 
 /// A raw index to a table entity.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub struct TableIdx(usize);
+pub struct TableIdx(u64);
 
-impl Index for TableIdx {
-    fn into_usize(self) -> usize {
+impl ArenaIndex for TableIdx {
+    fn into_key(self) -> u64 {
         self.0
     }
 
-    fn from_usize(value: usize) -> Self {
-        Self(value)
+    fn from_key(key: u64) -> Self {
+        Self(key)
     }
 }
 
@@ -38,6 +39,17 @@ pub enum TableError {
         /// The accessed index that is out of bounds.
         offset: usize,
     },
+    /// Occurs when initializing or copying table elements out of bounds.
+    CopyOutOfBounds,
+    /// Occurs when the resource limiter of the store denied the allocation or growth.
+    ResourceLimitExceeded,
+    /// Occurs when writing a value with mismatching type to a table element.
+    ElementTypeMismatch {
+        /// The element type of the table.
+        expected: ValueType,
+        /// The type of the value that mismatches the element type of the table.
+        encountered: ValueType,
+    },
     /// Occurs when a table type does not satisfy the constraints of another.
     UnsatisfyingTableType {
         /// The unsatisfying [`TableType`].
@@ -68,6 +80,22 @@ impl Display for TableError {
                     offset, current,
                 )
             }
+            Self::CopyOutOfBounds => {
+                write!(f, "out of bounds access of table elements while copying")
+            }
+            Self::ResourceLimitExceeded => {
+                write!(f, "resource limiter denied the table allocation")
+            }
+            Self::ElementTypeMismatch {
+                expected,
+                encountered,
+            } => {
+                write!(
+                    f,
+                    "type mismatch upon writing table element. expected {} but encountered {}.",
+                    expected, encountered,
+                )
+            }
             Self::UnsatisfyingTableType {
                 unsatisfying,
                 required,
@@ -82,9 +110,40 @@ impl Display for TableError {
     }
 }
 
+/// The reference type of the elements of a [`Table`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RefType {
+    /// A nullable function reference.
+    FuncRef,
+    /// A nullable external reference.
+    ExternRef,
+}
+
+impl RefType {
+    /// Returns the [`RefType`] of the `value_type` if it is a reference type.
+    pub fn from_value_type(value_type: ValueType) -> Option<Self> {
+        match value_type {
+            ValueType::FuncRef => Some(Self::FuncRef),
+            ValueType::ExternRef => Some(Self::ExternRef),
+            _ => None,
+        }
+    }
+}
+
+impl From<RefType> for ValueType {
+    fn from(ref_type: RefType) -> Self {
+        match ref_type {
+            RefType::FuncRef => Self::FuncRef,
+            RefType::ExternRef => Self::ExternRef,
+        }
+    }
+}
+
 /// A descriptor for a [`Table`] instance.
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub struct TableType {
+    /// The type of the elements of the [`Table`].
+    element: RefType,
     /// The initial size of the [`Table`].
     initial: usize,
     /// The optional maximum size fo the [`Table`].
@@ -96,12 +155,26 @@ impl TableType {
     ///
     /// # Panics
     ///
-    /// - If the `initial` limit is greater than the `maximum` limit if any.
-    pub fn new(initial: usize, maximum: Option<usize>) -> Self {
+    /// If the `initial` limit is greater than the `maximum` limit if any.
+    pub fn new(element: RefType, initial: usize, maximum: Option<usize>) -> Self {
         if let Some(maximum) = maximum {
             assert!(initial <= maximum);
         }
-        Self { initial, maximum }
+        Self {
+            element,
+            initial,
+            maximum,
+        }
+    }
+
+    /// Returns the [`ValueType`] of the elements.
+    pub fn element(self) -> ValueType {
+        self.element.into()
+    }
+
+    /// Returns the [`RefType`] of the elements.
+    pub fn ref_type(self) -> RefType {
+        self.element
     }
 
     /// Returns the initial size.
@@ -118,10 +191,11 @@ impl TableType {
     ///
     /// # Errors
     ///
+    /// - If the element type of `self` does not match the `required` [`TableType`].
     /// - If the initial limits of the `required` [`TableType`] are greater than `self`.
     /// - If the maximum limits of the `required` [`TableType`] are greater than `self`.
     pub(crate) fn satisfies(&self, required: &TableType) -> Result<(), TableError> {
-        if required.initial() > self.initial() {
+        if required.element() != self.element() || required.initial() > self.initial() {
             return Err(TableError::UnsatisfyingTableType {
                 unsatisfying: *self,
                 required: *required,
@@ -145,14 +219,18 @@ impl TableType {
 #[derive(Debug)]
 pub struct TableEntity {
     table_type: TableType,
-    elements: Vec<Option<Func>>,
+    elements: Vec<UntypedValue>,
 }
 
 impl TableEntity {
     /// Creates a new table entity with the given resizable limits.
+    ///
+    /// # Note
+    ///
+    /// All elements of the table are initialized to `null`.
     pub fn new(table_type: TableType) -> Self {
         Self {
-            elements: vec![None; table_type.initial()],
+            elements: vec![UntypedValue::default(); table_type.initial()],
             table_type,
         }
     }
@@ -172,16 +250,49 @@ impl TableEntity {
         self.elements.len()
     }
 
+    /// Returns `Ok` if the type of `value` matches the element type of the table.
+    ///
+    /// # Errors
+    ///
+    /// If the type of `value` does not match the element type of the table.
+    fn ensure_element_type(&self, value: &Value) -> Result<(), TableError> {
+        let expected = self.table_type.element();
+        let encountered = value.value_type();
+        if expected != encountered {
+            return Err(TableError::ElementTypeMismatch {
+                expected,
+                encountered,
+            });
+        }
+        Ok(())
+    }
+
+    /// Grows the table by the given amount of elements.
+    ///
+    /// # Note
+    ///
+    /// The newly added elements are initialized to `init`.
+    ///
+    /// # Errors
+    ///
+    /// - If the type of `init` does not match the element type of the table.
+    /// - If the table is grown beyond its maximum limits.
+    pub fn grow(&mut self, grow_by: usize, init: Value) -> Result<(), TableError> {
+        self.ensure_element_type(&init)?;
+        self.grow_untyped(grow_by, init.into())
+    }
+
     /// Grows the table by the given amount of elements.
     ///
     /// # Note
     ///
-    /// The newly added elements are initialized to `None`.
+    /// - The newly added elements are initialized to `init`.
+    /// - The type of `init` is required to match the element type of the table.
     ///
     /// # Errors
     ///
     /// If the table is grown beyond its maximum limits.
-    pub fn grow(&mut self, grow_by: usize) -> Result<(), TableError> {
+    pub fn grow_untyped(&mut self, grow_by: usize, init: UntypedValue) -> Result<(), TableError> {
         let maximum = self.table_type.maximum().unwrap_or(u32::MAX as usize);
         let current = self.len();
         let new_len = current
@@ -192,16 +303,26 @@ impl TableEntity {
                 current,
                 grow_by,
             })?;
-        self.elements.resize(new_len, None);
+        self.elements.resize(new_len, init);
         Ok(())
     }
 
-    /// Returns the element at the given offset if any.
+    /// Returns the element at the given offset.
+    ///
+    /// # Errors
+    ///
+    /// If the accesses element is out of bounds of the table.
+    pub fn get(&self, offset: usize) -> Result<Value, TableError> {
+        let element = self.get_untyped(offset)?;
+        Ok(Value::from_untyped(element, self.table_type.element()))
+    }
+
+    /// Returns the untyped element at the given offset.
     ///
     /// # Errors
     ///
     /// If the accesses element is out of bounds of the table.
-    pub fn get(&self, offset: usize) -> Result<Option<Func>, TableError> {
+    pub fn get_untyped(&self, offset: usize) -> Result<UntypedValue, TableError> {
         let element =
             self.elements
                 .get(offset)
@@ -217,8 +338,27 @@ impl TableEntity {
     ///
     /// # Errors
     ///
+    /// - If the type of `new_value` does not match the element type of the table.
+    /// - If the accesses element is out of bounds of the table.
+    pub fn set(&mut self, offset: usize, new_value: Value) -> Result<(), TableError> {
+        self.ensure_element_type(&new_value)?;
+        self.set_untyped(offset, new_value.into())
+    }
+
+    /// Sets a new untyped value to the table element at the given offset.
+    ///
+    /// # Note
+    ///
+    /// The type of `new_value` is required to match the element type of the table.
+    ///
+    /// # Errors
+    ///
     /// If the accesses element is out of bounds of the table.
-    pub fn set(&mut self, offset: usize, new_value: Option<Func>) -> Result<(), TableError> {
+    pub fn set_untyped(
+        &mut self,
+        offset: usize,
+        new_value: UntypedValue,
+    ) -> Result<(), TableError> {
         let current = self.len();
         let element = self
             .elements
@@ -227,6 +367,82 @@ impl TableEntity {
         *element = new_value;
         Ok(())
     }
+
+    /// Sets `len` elements of the table starting at `dst_index` to `value`.
+    ///
+    /// # Note
+    ///
+    /// The type of `value` is required to match the element type of the table.
+    ///
+    /// # Errors
+    ///
+    /// If the accessed table elements are out of bounds.
+    pub fn fill(
+        &mut self,
+        dst_index: usize,
+        value: UntypedValue,
+        len: usize,
+    ) -> Result<(), TableError> {
+        let dst_elements = dst_index
+            .checked_add(len)
+            .and_then(|dst_end| self.elements.get_mut(dst_index..dst_end))
+            .ok_or(TableError::CopyOutOfBounds)?;
+        dst_elements.fill(value);
+        Ok(())
+    }
+
+    /// Initializes `len` elements of the table starting at `dst_index`
+    /// with the `items` starting at `src_index`.
+    ///
+    /// # Errors
+    ///
+    /// If the accessed table elements or `items` are out of bounds.
+    pub fn init(
+        &mut self,
+        dst_index: usize,
+        items: &[UntypedValue],
+        src_index: usize,
+        len: usize,
+    ) -> Result<(), TableError> {
+        let src_items = src_index
+            .checked_add(len)
+            .and_then(|src_end| items.get(src_index..src_end))
+            .ok_or(TableError::CopyOutOfBounds)?;
+        let dst_elements = dst_index
+            .checked_add(len)
+            .and_then(|dst_end| self.elements.get_mut(dst_index..dst_end))
+            .ok_or(TableError::CopyOutOfBounds)?;
+        dst_elements.copy_from_slice(src_items);
+        Ok(())
+    }
+
+    /// Copies `len` elements of the table from `src_index` to `dst_index`.
+    ///
+    /// # Note
+    ///
+    /// The source and destination ranges are allowed to overlap.
+    ///
+    /// # Errors
+    ///
+    /// If the accessed table elements are out of bounds.
+    pub fn copy_within(
+        &mut self,
+        dst_index: usize,
+        src_index: usize,
+        len: usize,
+    ) -> Result<(), TableError> {
+        let len_table = self.len();
+        let src_end = src_index
+            .checked_add(len)
+            .filter(|&src_end| src_end <= len_table)
+            .ok_or(TableError::CopyOutOfBounds)?;
+        dst_index
+            .checked_add(len)
+            .filter(|&dst_end| dst_end <= len_table)
+            .ok_or(TableError::CopyOutOfBounds)?;
+        self.elements.copy_within(src_index..src_end, dst_index);
+        Ok(())
+    }
 }
 
 /// A Wasm table reference.
@@ -246,10 +462,20 @@ impl Table {
     }
 
     /// Creates a new table to the store.
-    pub fn new(mut ctx: impl AsContextMut, table_type: TableType) -> Self {
-        ctx.as_context_mut()
-            .store
-            .alloc_table(TableEntity::new(table_type))
+    ///
+    /// # Errors
+    ///
+    /// If the [`ResourceLimiter`] of the store denies the allocation.
+    ///
+    /// [`ResourceLimiter`]: [`crate::ResourceLimiter`]
+    pub fn new(mut ctx: impl AsContextMut, table_type: TableType) -> Result<Self, TableError> {
+        let store = ctx.as_context_mut().store;
+        let initial = table_type.initial();
+        let maximum = table_type.maximum();
+        if !store.can_create_table() || !store.table_growing(0, initial, maximum) {
+            return Err(TableError::ResourceLimitExceeded);
+        }
+        Ok(store.alloc_table(TableEntity::new(table_type)))
     }
 
     /// Returns the type and limits of the table.
@@ -279,23 +505,58 @@ impl Table {
     ///
     /// # Note
     ///
-    /// The newly added elements are initialized to `None`.
+    /// The newly added elements are initialized to `init`.
     ///
     /// # Errors
     ///
-    /// If the table is grown beyond its maximum limits.
+    /// - If the type of `init` does not match the element type of the table.
+    /// - If the table is grown beyond its maximum limits.
+    /// - If the [`ResourceLimiter`] of the store denies the grow operation.
     ///
     /// # Panics
     ///
     /// Panics if `ctx` does not own this [`Table`].
-    pub fn grow(&self, mut ctx: impl AsContextMut, grow_by: usize) -> Result<(), TableError> {
+    ///
+    /// [`ResourceLimiter`]: [`crate::ResourceLimiter`]
+    pub fn grow(
+        &self,
+        mut ctx: impl AsContextMut,
+        grow_by: usize,
+        init: Value,
+    ) -> Result<(), TableError> {
+        self.ensure_growth_permitted(&mut ctx, grow_by)?;
         ctx.as_context_mut()
             .store
             .resolve_table_mut(*self)
-            .grow(grow_by)
+            .grow(grow_by, init)
+    }
+
+    /// Returns `Ok` if the [`ResourceLimiter`] of the store permits growing the table by `grow_by`.
+    ///
+    /// # Errors
+    ///
+    /// If the [`ResourceLimiter`] of the store denies the grow operation.
+    ///
+    /// [`ResourceLimiter`]: [`crate::ResourceLimiter`]
+    pub(crate) fn ensure_growth_permitted(
+        &self,
+        mut ctx: impl AsContextMut,
+        grow_by: usize,
+    ) -> Result<(), TableError> {
+        if grow_by == 0 {
+            return Ok(());
+        }
+        let store = ctx.as_context_mut().store;
+        let table = store.resolve_table(*self);
+        let current = table.len();
+        let maximum = table.table_type().maximum();
+        if !store.table_growing(current, current.saturating_add(grow_by), maximum) {
+            return Err(TableError::ResourceLimitExceeded);
+        }
+        Ok(())
     }
 
-    /// Returns the element at the given offset if any.
+    /// Returns the element at the given offset.
     ///
     /// # Errors
     ///
@@ -304,7 +565,7 @@ impl Table {
     /// # Panics
     ///
     /// Panics if `ctx` does not own this [`Table`].
-    pub fn get(&self, ctx: impl AsContext, offset: usize) -> Result<Option<Func>, TableError> {
+    pub fn get(&self, ctx: impl AsContext, offset: usize) -> Result<Value, TableError> {
         ctx.as_context().store.resolve_table(*self).get(offset)
     }
 
@@ -312,7 +573,8 @@ impl Table {
     ///
     /// # Errors
     ///
-    /// If the accesses element is out of bounds of the table.
+    /// - If the type of `new_value` does not match the element type of the table.
+    /// - If the accesses element is out of bounds of the table.
     ///
     /// # Panics
     ///
@@ -321,11 +583,52 @@ impl Table {
         &self,
         mut ctx: impl AsContextMut,
         offset: usize,
-        new_value: Option<Func>,
+        new_value: Value,
     ) -> Result<(), TableError> {
         ctx.as_context_mut()
             .store
             .resolve_table_mut(*self)
             .set(offset, new_value)
     }
+
+    /// Copies `len` elements from `src_table[src_index..]` to `dst_table[dst_index..]`.
+    ///
+    /// # Note
+    ///
+    /// The source and destination ranges are allowed to overlap
+    /// if `dst_table` and `src_table` refer to the same [`Table`].
+    ///
+    /// # Errors
+    ///
+    /// - If the element types of `dst_table` and `src_table` do not match.
+    /// - If the accessed table elements are out of bounds of either table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own `dst_table` or `src_table`.
+    pub fn copy(
+        mut ctx: impl AsContextMut,
+        dst_table: &Table,
+        dst_index: usize,
+        src_table: &Table,
+        src_index: usize,
+        len: usize,
+    ) -> Result<(), TableError> {
+        let store = ctx.as_context_mut().store;
+        let expected = store.resolve_table(*dst_table).table_type().element();
+        let encountered = store.resolve_table(*src_table).table_type().element();
+        if expected != encountered {
+            return Err(TableError::ElementTypeMismatch {
+                expected,
+                encountered,
+            });
+        }
+        if dst_table.into_inner() == src_table.into_inner() {
+            return store
+                .resolve_table_mut(*dst_table)
+                .copy_within(dst_index, src_index, len);
+        }
+        let (dst_table, src_table) = store.resolve_table_pair_mut(*dst_table, *src_table);
+        dst_table.init(dst_index, &src_table.elements, src_index, len)
+    }
 }
@@ -0,0 +1,163 @@
+use super::{engine::DedupFuncType, ArenaIndex, AsContext, AsContextMut, FuncType, Stored};
+use crate::core::ValueType;
+use core::{fmt, fmt::Display};
+
+/// A raw index to a tag entity.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TagIdx(u64);
+
+impl ArenaIndex for TagIdx {
+    fn into_key(self) -> u64 {
+        self.0
+    }
+
+    fn from_key(key: u64) -> Self {
+        Self(key)
+    }
+}
+
+/// An error that may occur upon operating on exception tags.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum TagError {
+    /// Occurs when the payload of an exception does not match the parameters of its tag.
+    PayloadMismatch {
+        /// The type of the tag.
+        expected: TagType,
+        /// The types of the payload values that mismatch the tag.
+        encountered: FuncType,
+    },
+}
+
+impl Display for TagError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::PayloadMismatch {
+                expected,
+                encountered,
+            } => {
+                write!(
+                    f,
+                    "exception payload mismatch. expected {} but encountered {}.",
+                    expected.func_type(),
+                    encountered,
+                )
+            }
+        }
+    }
+}
+
+/// The type of an exception tag.
+///
+/// # Note
+///
+/// The type of a tag describes the types of the payload values of
+/// the exceptions thrown with the tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagType {
+    /// The function type with the payload as parameters and without results.
+    func_type: FuncType,
+}
+
+impl TagType {
+    /// Creates a new [`TagType`] with the given payload types.
+    pub fn new<I>(params: I) -> Self
+    where
+        I: IntoIterator<Item = ValueType>,
+    {
+        Self {
+            func_type: FuncType::new(params, []),
+        }
+    }
+
+    /// Creates a [`TagType`] from the parameters of the [`FuncType`].
+    pub(crate) fn from_func_type(func_type: &FuncType) -> Self {
+        Self::new(func_type.params().iter().copied())
+    }
+
+    /// Returns the types of the payload values of the tag.
+    pub fn params(&self) -> &[ValueType] {
+        self.func_type.params()
+    }
+
+    /// Returns the underlying [`FuncType`] without results.
+    pub(crate) fn func_type(&self) -> &FuncType {
+        &self.func_type
+    }
+}
+
+/// An exception tag entity.
+#[derive(Debug)]
+pub struct TagEntity {
+    /// The deduplicated function type describing the payload of the tag.
+    signature: DedupFuncType,
+}
+
+impl TagEntity {
+    /// Creates a new tag entity with the given signature.
+    pub fn new(signature: DedupFuncType) -> Self {
+        Self { signature }
+    }
+
+    /// Returns the signature of the tag.
+    pub fn signature(&self) -> DedupFuncType {
+        self.signature
+    }
+}
+
+/// A Wasm exception tag reference.
+///
+/// # Note
+///
+/// Two [`Tag`] references are equal if they refer to the same tag entity.
+/// Exceptions are only caught by `catch` clauses of the very same tag.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct Tag(Stored<TagIdx>);
+
+impl Tag {
+    /// Creates a new stored tag reference.
+    ///
+    /// # Note
+    ///
+    /// This API is primarily used by the [`Store`] itself.
+    ///
+    /// [`Store`]: [`crate::v1::Store`]
+    pub(super) fn from_inner(stored: Stored<TagIdx>) -> Self {
+        Self(stored)
+    }
+
+    /// Returns the underlying stored representation.
+    pub(super) fn into_inner(self) -> Stored<TagIdx> {
+        self.0
+    }
+
+    /// Creates a new exception tag to the store.
+    pub fn new(mut ctx: impl AsContextMut, tag_type: TagType) -> Self {
+        let signature = ctx
+            .as_context_mut()
+            .store
+            .alloc_func_type(tag_type.func_type);
+        ctx.as_context_mut()
+            .store
+            .alloc_tag(TagEntity::new(signature))
+    }
+
+    /// Returns the signature of the tag.
+    pub(crate) fn signature(&self, ctx: impl AsContext) -> DedupFuncType {
+        ctx.as_context().store.resolve_tag(*self).signature()
+    }
+
+    /// Returns the [`TagType`] of the tag.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Tag`].
+    pub fn tag_type(&self, ctx: impl AsContext) -> TagType {
+        let func_type = ctx
+            .as_context()
+            .store
+            .resolve_func_type(self.signature(&ctx));
+        TagType { func_type }
+    }
+}
@@ -0,0 +1,243 @@
+#[cfg(feature = "simd")]
+use crate::core::V128;
+use crate::{
+    core::{UntypedValue, ValueType, F32, F64},
+    ExternRef,
+    FuncRef,
+};
+
+/// Runtime representation of a value.
+///
+/// Wasm code manipulate values of the four basic value types:
+/// integers and floating-point (IEEE 754-2008) data of 32 or 64 bit width each, respectively.
+///
+/// With the `reference-types` Wasm proposal there are additional
+/// nullable references to functions or host data.
+///
+/// With the `simd` Wasm proposal there is an additional 128-bit vector type.
+///
+/// There is no distinction between signed and unsigned integer types. Instead, integers are
+/// interpreted by respective operations as either unsigned or signed in two’s complement representation.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Value {
+    /// Value of 32-bit signed or unsigned integer.
+    I32(i32),
+    /// Value of 64-bit signed or unsigned integer.
+    I64(i64),
+    /// Value of 32-bit IEEE 754-2008 floating point number.
+    F32(F32),
+    /// Value of 64-bit IEEE 754-2008 floating point number.
+    F64(F64),
+    /// A nullable [`Func`][`crate::Func`] reference.
+    FuncRef(FuncRef),
+    /// A nullable reference to host data.
+    ExternRef(ExternRef),
+    /// Value of 128-bit vector of packed integer or floating point lanes.
+    #[cfg(feature = "simd")]
+    V128(V128),
+}
+
+impl Value {
+    /// Creates new default value of given type.
+    ///
+    /// # Note
+    ///
+    /// The default value of reference types is `null`.
+    ///
+    /// # Panics
+    ///
+    /// If `value_type` is not supported, e.g. [`ValueType::V128`] without the `simd` crate feature.
+    #[inline]
+    pub fn default(value_type: ValueType) -> Self {
+        match value_type {
+            ValueType::I32 => Self::I32(0),
+            ValueType::I64 => Self::I64(0),
+            ValueType::F32 => Self::F32(0f32.into()),
+            ValueType::F64 => Self::F64(0f64.into()),
+            ValueType::FuncRef => Self::FuncRef(FuncRef::null()),
+            ValueType::ExternRef => Self::ExternRef(ExternRef::null()),
+            #[cfg(feature = "simd")]
+            ValueType::V128 => Self::V128(V128::default()),
+            _ => panic!("{} values are not supported", value_type),
+        }
+    }
+
+    /// Returns the [`ValueType`] of the [`Value`].
+    #[inline]
+    pub fn value_type(&self) -> ValueType {
+        match self {
+            Self::I32(_) => ValueType::I32,
+            Self::I64(_) => ValueType::I64,
+            Self::F32(_) => ValueType::F32,
+            Self::F64(_) => ValueType::F64,
+            Self::FuncRef(_) => ValueType::FuncRef,
+            Self::ExternRef(_) => ValueType::ExternRef,
+            #[cfg(feature = "simd")]
+            Self::V128(_) => ValueType::V128,
+        }
+    }
+
+    /// Returns `T` if this particular [`Value`] contains
+    /// appropriate type.
+    ///
+    /// See [`FromValue`] for details.
+    #[inline]
+    pub fn try_into<T: FromValue>(self) -> Option<T> {
+        FromValue::from_value(self)
+    }
+
+    /// Creates a [`Value`] of the given [`ValueType`] from the [`UntypedValue`].
+    ///
+    /// # Note
+    ///
+    /// The `value_type` is required to match the type of the `untyped` value.
+    ///
+    /// # Panics
+    ///
+    /// If `value_type` is not supported, e.g. [`ValueType::V128`] without the `simd` crate feature.
+    pub(crate) fn from_untyped(untyped: UntypedValue, value_type: ValueType) -> Self {
+        match value_type {
+            ValueType::I32 => Self::I32(<_>::from(untyped)),
+            ValueType::I64 => Self::I64(<_>::from(untyped)),
+            ValueType::F32 => Self::F32(<_>::from(untyped)),
+            ValueType::F64 => Self::F64(<_>::from(untyped)),
+            ValueType::FuncRef => Self::FuncRef(<_>::from(untyped)),
+            ValueType::ExternRef => Self::ExternRef(<_>::from(untyped)),
+            #[cfg(feature = "simd")]
+            ValueType::V128 => Self::V128(<_>::from(untyped)),
+            _ => panic!("{} values are not supported", value_type),
+        }
+    }
+}
+
+impl From<Value> for UntypedValue {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::I32(value) => value.into(),
+            Value::I64(value) => value.into(),
+            Value::F32(value) => value.into(),
+            Value::F64(value) => value.into(),
+            Value::FuncRef(value) => value.into(),
+            Value::ExternRef(value) => value.into(),
+            #[cfg(feature = "simd")]
+            Value::V128(value) => value.into(),
+        }
+    }
+}
+
+impl From<crate::core::Value> for Value {
+    fn from(value: crate::core::Value) -> Self {
+        match value {
+            crate::core::Value::I32(value) => Self::I32(value),
+            crate::core::Value::I64(value) => Self::I64(value),
+            crate::core::Value::F32(value) => Self::F32(value),
+            crate::core::Value::F64(value) => Self::F64(value),
+        }
+    }
+}
+
+macro_rules! impl_from_for_value {
+    ( $( impl From<$from:ty> for Value::$variant:ident );* $(;)? ) => {
+        $(
+            impl From<$from> for Value {
+                #[inline]
+                fn from(value: $from) -> Self {
+                    Self::$variant(value.into())
+                }
+            }
+        )*
+    };
+}
+impl_from_for_value! {
+    impl From<i8> for Value::I32;
+    impl From<i16> for Value::I32;
+    impl From<i32> for Value::I32;
+    impl From<i64> for Value::I64;
+    impl From<u8> for Value::I32;
+    impl From<u16> for Value::I32;
+    impl From<F32> for Value::F32;
+    impl From<F64> for Value::F64;
+    impl From<FuncRef> for Value::FuncRef;
+    impl From<ExternRef> for Value::ExternRef;
+}
+
+#[cfg(feature = "simd")]
+impl_from_for_value! {
+    impl From<V128> for Value::V128;
+}
+
+impl From<u32> for Value {
+    #[inline]
+    fn from(value: u32) -> Self {
+        Self::I32(value as i32)
+    }
+}
+
+impl From<u64> for Value {
+    #[inline]
+    fn from(value: u64) -> Self {
+        Self::I64(value as i64)
+    }
+}
+
+/// Trait for creating value from a [`Value`].
+///
+/// Typically each implementation can create a value from the specific type.
+/// For example, values of type `i32` or `u32` are both represented by [`I32`] and `f64` values are represented by
+/// [`F64`].
+///
+/// [`I32`]: enum.Value.html#variant.I32
+/// [`F64`]: enum.Value.html#variant.F64
+pub trait FromValue
+where
+    Self: Sized,
+{
+    /// Create a value of type `Self` from a given [`Value`].
+    ///
+    /// Returns `None` if the [`Value`] is of type different than
+    /// expected by the conversion in question.
+    fn from_value(value: Value) -> Option<Self>;
+}
+
+macro_rules! impl_from_value {
+    ( $( impl FromValue for $into:ty = Value::$variant:ident );* $(;)? ) => {
+        $(
+            impl FromValue for $into {
+                #[inline]
+                fn from_value(value: Value) -> Option<Self> {
+                    match value {
+                        Value::$variant(value) => Some(value),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+impl_from_value! {
+    impl FromValue for i32 = Value::I32;
+    impl FromValue for i64 = Value::I64;
+    impl FromValue for F32 = Value::F32;
+    impl FromValue for F64 = Value::F64;
+    impl FromValue for FuncRef = Value::FuncRef;
+    impl FromValue for ExternRef = Value::ExternRef;
+}
+
+#[cfg(feature = "simd")]
+impl_from_value! {
+    impl FromValue for V128 = Value::V128;
+}
+
+impl FromValue for u32 {
+    #[inline]
+    fn from_value(value: Value) -> Option<Self> {
+        i32::from_value(value).map(|value| value as u32)
+    }
+}
+
+impl FromValue for u64 {
+    #[inline]
+    fn from_value(value: Value) -> Option<Self> {
+        i64::from_value(value).map(|value| value as u64)
+    }
+}